@@ -2,9 +2,11 @@ package main
 
 import (
 	"context"
+	"errors"
 	"flag"
 	"fmt"
 	"log"
+	"net/http"
 	"os"
 	"os/signal"
 	"path/filepath"
@@ -84,7 +86,8 @@ func main() {
 	log.Printf("Starting vget server on port %d", serverPort)
 	log.Printf("Output directory: %s", outputDir)
 
-	if err := srv.Start(); err != nil {
+	// Stopping the server, on a signal or as the after-queue action, isn't an error
+	if err := srv.Start(); err != nil && !errors.Is(err, http.ErrServerClosed) {
 		log.Fatalf("Server error: %v", err)
 	}
 }
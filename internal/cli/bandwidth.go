@@ -0,0 +1,43 @@
+package cli
+
+import (
+	"fmt"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/bandwidth"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/spf13/cobra"
+)
+
+var bandwidthCmd = &cobra.Command{
+	Use:   "bandwidth",
+	Short: "Show bytes downloaded by the server queue per day and month",
+	RunE: func(cmd *cobra.Command, args []string) error {
+		usage, err := bandwidth.Open()
+		if err != nil {
+			return err
+		}
+
+		cfg := config.LoadOrDefault()
+		report := usage.Report(bandwidth.CapBytes(cfg.Bandwidth), time.Now())
+
+		fmt.Printf("Today:      %s\n", formatSize(report.Today))
+		fmt.Printf("This month: %s", formatSize(report.ThisMonth))
+		if report.MonthlyCap > 0 {
+			fmt.Printf(" of %s (%s left)", formatSize(report.MonthlyCap), formatSize(report.Remaining))
+		}
+		fmt.Println()
+
+		if len(report.Months) > 1 {
+			fmt.Println("\nBy month:")
+			for _, m := range report.Months {
+				fmt.Printf("  %s  %s\n", m.Month, formatSize(m.Bytes))
+			}
+		}
+		return nil
+	},
+}
+
+func init() {
+	rootCmd.AddCommand(bandwidthCmd)
+}
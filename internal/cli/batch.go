@@ -1,47 +1,24 @@
 package cli
 
 import (
-	"bufio"
+	"errors"
 	"fmt"
 	"os"
-	"strings"
 
-	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/downloadlist"
 )
 
-// runBatch reads URLs from a file and downloads each one
+// runBatch reads URLs from a text or JSON list file and downloads each one.
+// JSON entries can set their own quality, overriding --quality.
 func runBatch(filename string) error {
-	file, err := os.Open(filename)
+	list, err := downloadlist.ParseFile(filename)
 	if err != nil {
-		return fmt.Errorf("failed to open file: %w", err)
+		return err
 	}
-	defer file.Close()
+	invalidURLs := list.Invalid
 
-	var urls []string
-	var invalidURLs []string
-	scanner := bufio.NewScanner(file)
-	lineNum := 0
-	for scanner.Scan() {
-		lineNum++
-		line := strings.TrimSpace(scanner.Text())
-		// Skip empty lines and comments
-		if line == "" || strings.HasPrefix(line, "#") {
-			continue
-		}
-		// Normalize URL: add https:// if missing
-		normalized, err := extractor.NormalizeURL(line)
-		if err != nil {
-			invalidURLs = append(invalidURLs, fmt.Sprintf("line %d: %s", lineNum, line))
-			continue
-		}
-		urls = append(urls, normalized)
-	}
-
-	if err := scanner.Err(); err != nil {
-		return fmt.Errorf("failed to read file: %w", err)
-	}
-
-	if len(urls) == 0 {
+	if len(list.Items) == 0 {
 		if len(invalidURLs) > 0 {
 			return fmt.Errorf("no valid URLs found in file (%d invalid)", len(invalidURLs))
 		}
@@ -57,15 +34,17 @@ func runBatch(filename string) error {
 		fmt.Println()
 	}
 
-	fmt.Printf("Found %d URL(s) to download\n\n", len(urls))
+	total := len(list.Items)
+	fmt.Printf("Found %d URL(s) to download\n\n", total)
 
 	// Separate Telegram URLs from other URLs
-	var telegramURLs, otherURLs []string
-	for _, url := range urls {
-		if isTelegramURL(url) {
-			telegramURLs = append(telegramURLs, url)
+	var telegramURLs []string
+	var otherItems []downloadlist.Item
+	for _, item := range list.Items {
+		if isTelegramURL(item.URL) {
+			telegramURLs = append(telegramURLs, item.URL)
 		} else {
-			otherURLs = append(otherURLs, url)
+			otherItems = append(otherItems, item)
 		}
 	}
 
@@ -81,7 +60,7 @@ func runBatch(filename string) error {
 	} else {
 		// Single Telegram URL - use regular download
 		for _, url := range telegramURLs {
-			fmt.Printf("[1/%d] %s\n", len(urls), truncateURL(url, 60))
+			fmt.Printf("[1/%d] %s\n", total, truncateURL(url, 60))
 			if err := runTelegramDownload(url, ""); err != nil {
 				fmt.Fprintf(os.Stderr, "  Error: %v\n", err)
 				failed++
@@ -95,13 +74,24 @@ func runBatch(filename string) error {
 
 	// Download other URLs
 	startIdx := len(telegramURLs) + 1
-	for i, url := range otherURLs {
-		fmt.Printf("[%d/%d] %s\n", startIdx+i, len(urls), truncateURL(url, 60))
-
+	defaultQuality := quality
+	defer func() { quality = defaultQuality }()
+	for i, item := range otherItems {
+		url := item.URL
+		fmt.Printf("[%d/%d] %s\n", startIdx+i, total, truncateURL(url, 60))
+
+		quality = defaultQuality
+		if item.Quality != "" {
+			quality = item.Quality
+		}
 		if err := runDownload(url); err != nil {
 			fmt.Fprintf(os.Stderr, "  Error: %v\n", err)
 			failed++
 			failedURLs = append(failedURLs, url)
+			// Quitting a progress display stops the rest of the batch too
+			if errors.Is(err, downloader.ErrCancelled) {
+				break
+			}
 		} else {
 			succeeded++
 		}
@@ -110,7 +100,7 @@ func runBatch(filename string) error {
 
 	// Print summary
 	fmt.Println("----------------------------------------")
-	fmt.Printf("Completed: %d/%d", succeeded, len(urls))
+	fmt.Printf("Completed: %d/%d", succeeded, total)
 	if failed > 0 {
 		fmt.Printf(", Failed: %d", failed)
 	}
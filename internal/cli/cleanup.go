@@ -0,0 +1,61 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/spf13/cobra"
+)
+
+var cleanupDryRun bool
+
+var cleanupCmd = &cobra.Command{
+	Use:   "cleanup [dir]",
+	Short: "Remove partial files left by interrupted downloads",
+	Long: `Remove leftovers of interrupted downloads (.part files and their resume
+state, unfinished merges, HLS segment directories) not touched for
+download.temp_max_age_days (default: 7). The directory defaults to output_dir.`,
+	Args: cobra.MaximumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		cfg := config.LoadOrDefault()
+		dir := cfg.OutputDir
+		if dir == "" {
+			dir = config.DefaultDownloadDir()
+		}
+		if len(args) == 1 {
+			dir = args[0]
+		}
+
+		files, err := downloader.FindTempFiles(dir, cfg.Download.TempMaxAge())
+		if err != nil {
+			return err
+		}
+		if len(files) == 0 {
+			fmt.Println("No stale temp files found")
+			return nil
+		}
+
+		var total int64
+		for _, f := range files {
+			fmt.Printf("  %s  %s\n", formatSize(f.Size), f.Path)
+			total += f.Size
+		}
+		if cleanupDryRun {
+			fmt.Printf("%d files, %s would be reclaimed\n", len(files), formatSize(total))
+			return nil
+		}
+
+		reclaimed, failed := downloader.RemoveTempFiles(files)
+		for _, path := range failed {
+			fmt.Printf("✗ could not remove %s\n", path)
+		}
+		fmt.Printf("✓ Reclaimed %s\n", formatSize(reclaimed))
+		return nil
+	},
+}
+
+func init() {
+	cleanupCmd.Flags().BoolVar(&cleanupDryRun, "dry-run", false, "list the files without removing them")
+	rootCmd.AddCommand(cleanupCmd)
+}
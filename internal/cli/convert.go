@@ -0,0 +1,612 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/signal"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+	"github.com/guiyumin/vget/internal/core/subtitle"
+	"github.com/spf13/cobra"
+)
+
+var (
+	convertOutput   string
+	convertFormat   string
+	convertPreset   string
+	convertBitrate  string
+	convertChannels int
+	convertVideo    string
+	convertSoftware bool
+	convertStart    string
+	convertEnd      string
+	convertReencode bool
+	convertDuration float64
+	convertFPS      int
+	convertWidth    int
+	convertAt       string
+	convertBurn     bool
+	convertLangs    string
+	convertTo       string
+	convertTarget   float64
+	convertImage    string
+	convertText     string
+	convertPosition string
+	convertOpacity  float64
+	convertMargin   int
+	convertFontSize int
+	convertSpeed    float64
+	convertGain     float64
+)
+
+var convertCmd = &cobra.Command{
+	Use:   "convert",
+	Short: "Convert downloaded media with ffmpeg",
+	Long: `Convert downloaded media with ffmpeg.
+
+Examples:
+  vget convert audio video.mp4
+  vget convert audio lecture.mp4 --preset podcast
+  vget convert audio concert.mkv --format opus --bitrate 160k
+  vget convert video clip.webm --preset web
+  vget convert trim talk.mp4 --start 1:30 --end 4:05
+  vget convert gif clip.mp4 --start 0:12 --duration 4
+  vget convert frame talk.mp4 --at 1:30
+  vget convert subs talk.mp4 --lang en
+  vget convert subs talk.mp4 talk.en.srt --burn
+  vget convert remux video.mkv --to mp4
+  vget convert loudness episode.mp3 --target -16
+  vget convert watermark clip.mp4 --image logo.png --position top-right
+  vget convert watermark clip.mp4 --text "@mychannel" --opacity 0.6
+  vget convert speed lecture.mp4 --speed 1.5
+  vget convert volume episode.mp3 --gain 6
+  vget convert batch ~/Downloads/lectures --preset podcast
+  vget convert batch a.webm b.webm --preset web
+  vget convert encoders`,
+}
+
+var convertAudioCmd = &cobra.Command{
+	Use:   "audio <file>",
+	Short: "Extract the audio of a file to MP3, M4A, or Opus",
+	Long: `Extract the audio of a file to MP3, M4A, or Opus.
+
+Presets:
+  podcast  mono, 64k
+  music    320k
+
+The output goes next to the input with the format's extension unless -o is given.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		opts, err := ffmpeg.ApplyAudioPreset(ffmpeg.AudioOptions{
+			Format:   convertFormat,
+			Bitrate:  convertBitrate,
+			Channels: convertChannels,
+		}, convertPreset)
+		if err != nil {
+			return err
+		}
+		if opts.Format == "" && convertOutput == "" {
+			opts.Format = ffmpeg.AudioMP3
+		}
+		if opts.Format, err = ffmpeg.AudioFormat(opts.Format, convertOutput); err != nil {
+			return err
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.SwapExt(input, opts.Format))
+		}
+		if err := ffmpeg.ExtractAudio(ctx, input, output, opts, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertVideoCmd = &cobra.Command{
+	Use:   "video <file>",
+	Short: "Re-encode a video with a preset",
+	Long: `Re-encode a video with a preset, for players that can't handle the
+downloaded codecs.
+
+Presets:
+` + videoPresetHelp() + `
+A working hardware encoder (VideoToolbox, NVENC, Quick Sync, VAAPI) is used
+when there is one for the preset's codec; --software skips it. The output goes
+next to the input with the preset's extension unless -o is given.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		preset, err := ffmpeg.FindVideoPreset(convertVideo)
+		if err != nil {
+			return err
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.VideoPath(input, preset))
+		}
+		enc, err := ffmpeg.Transcode(ctx, input, output, preset, !convertSoftware, printConvertProgress)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s (%s)\n", output, enc.Name)
+		return nil
+	},
+}
+
+var convertTrimCmd = &cobra.Command{
+	Use:   "trim <file>",
+	Short: "Cut a clip out of a file",
+	Long: `Cut a clip out of a file between --start and --end (seconds, MM:SS, or
+HH:MM:SS).
+
+Streams are copied, which is fast and lossless but starts the clip at the
+keyframe at or before --start. --reencode cuts at the exact frame instead.
+The output is "<name> (clip).<ext>" next to the input unless -o is given.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		var start, end time.Duration
+		var err error
+		if convertStart != "" {
+			if start, err = ffmpeg.ParseTimestamp(convertStart); err != nil {
+				return err
+			}
+		}
+		if convertEnd != "" {
+			if end, err = ffmpeg.ParseTimestamp(convertEnd); err != nil {
+				return err
+			}
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.ClipPath(input))
+		}
+		if err := ffmpeg.Trim(ctx, input, output, start, end, convertReencode, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertGIFCmd = &cobra.Command{
+	Use:   "gif <file>",
+	Short: "Turn a stretch of a video into an animated GIF or WebP",
+	Long: `Turn a stretch of a video into an animated GIF or WebP, silent and
+looping. Keep --duration short: animations get large fast.
+
+The output is the input with a .gif extension unless -o is given; an -o
+ending in .webp writes WebP.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		opts := ffmpeg.AnimationOptions{
+			Duration: time.Duration(convertDuration * float64(time.Second)),
+			FPS:      convertFPS,
+			Width:    convertWidth,
+		}
+		if convertStart != "" {
+			var err error
+			if opts.Start, err = ffmpeg.ParseTimestamp(convertStart); err != nil {
+				return err
+			}
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.SwapExt(input, ffmpeg.AnimationGIF))
+		}
+		if err := ffmpeg.Animate(ctx, input, output, opts, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertFrameCmd = &cobra.Command{
+	Use:   "frame <file>",
+	Short: "Save a frame of a video as a PNG or JPEG",
+	Long: `Save a frame of a video as a PNG or JPEG.
+
+The output is "<name> (<at>).png" next to the input unless -o is given; the
+format follows --format, else the extension of -o.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		var at time.Duration
+		if convertAt != "" {
+			var err error
+			if at, err = ffmpeg.ParseTimestamp(convertAt); err != nil {
+				return err
+			}
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			format := convertFormat
+			if format == "" {
+				format = ffmpeg.FramePNG
+			}
+			format, err := ffmpeg.FrameFormat(format, "")
+			if err != nil {
+				return err
+			}
+			output = downloader.UniquePath(ffmpeg.FramePath(input, at, format))
+		}
+		if err := ffmpeg.CaptureFrame(ctx, input, output, at, convertWidth); err != nil {
+			return err
+		}
+		fmt.Printf("✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertSubsCmd = &cobra.Command{
+	Use:   "subs <video> [subtitle files...]",
+	Short: "Embed subtitles in a video as tracks or burned in",
+	Long: `Embed subtitles in a video. By default they are added as tracks that
+players can turn on and off, copying the video and audio; with --burn the
+first subtitles are drawn onto the video, which is re-encoded to H.264.
+
+Without subtitle files, the ones saved next to the video are used
+("<name>.<language>.srt" and the like), narrowed down with --lang.`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		input := args[0]
+		var tracks []ffmpeg.SubtitleTrack
+		for _, path := range args[1:] {
+			tracks = append(tracks, ffmpeg.SubtitleTrack{Path: path})
+		}
+		if len(tracks) == 0 {
+			sidecars, err := subtitle.Sidecars(input, subtitle.ParseLanguages(convertLangs))
+			if err != nil {
+				return err
+			}
+			for _, sc := range sidecars {
+				tracks = append(tracks, ffmpeg.SubtitleTrack{Path: sc.Path, Language: sc.Language})
+			}
+		}
+		if len(tracks) == 0 {
+			return fmt.Errorf("no subtitle files found next to %s", input)
+		}
+
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.SubtitledPath(input, convertBurn))
+		}
+		var err error
+		if convertBurn {
+			fmt.Printf("Burning in %s\n", tracks[0].Path)
+			err = ffmpeg.BurnSubtitles(ctx, input, output, tracks[0].Path, printConvertProgress)
+		} else {
+			for _, track := range tracks {
+				fmt.Printf("Adding %s\n", track.Path)
+			}
+			err = ffmpeg.EmbedSubtitles(ctx, input, output, tracks, printConvertProgress)
+		}
+		if err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertRemuxCmd = &cobra.Command{
+	Use:   "remux <file>",
+	Short: "Copy media into another container without re-encoding",
+	Long: `Copy the streams of a file into another container (mp4, m4a, mkv, or
+webm) without re-encoding, so nothing is lost and it takes seconds. Remuxing
+to m4a keeps only the audio.
+
+If the container can't hold a codec of the file, e.g. Opus audio in m4a,
+nothing is written and the streams that would need re-encoding are listed.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		if convertTo == "" && convertOutput == "" {
+			return fmt.Errorf("--to or -o is required")
+		}
+		container, err := ffmpeg.RemuxContainer(convertTo, convertOutput)
+		if err != nil {
+			return err
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.SwapExt(input, container))
+		}
+		if err := ffmpeg.Remux(ctx, input, output, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertLoudnessCmd = &cobra.Command{
+	Use:   "loudness <file>",
+	Short: "Even out the volume of audio with two-pass loudness normalization",
+	Long: `Even out the volume of a file's audio to a target loudness with ffmpeg's
+loudnorm filter, measuring the whole file first. The audio is re-encoded in
+its codec at its bitrate; any video is copied.
+
+The output is "<name> (normalized).<ext>" next to the input unless -o is
+given; -o may be the input itself. Set download.normalize_audio to do this
+for every downloaded audio file.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		target := convertTarget
+		if target == 0 {
+			target = config.LoadOrDefault().Download.LoudnessTarget
+		}
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.NormalizedPath(input))
+		}
+		if err := ffmpeg.NormalizeLoudness(ctx, input, output, target, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertWatermarkCmd = &cobra.Command{
+	Use:   "watermark <video>",
+	Short: "Overlay an image or text onto a video",
+	Long: `Overlay a PNG (--image) or a line of text (--text) onto a video, in a
+corner or the center. The video is re-encoded to H.264 and the audio copied.
+
+The output is "<name> (watermarked).mp4" next to the input unless -o is given.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		opts := ffmpeg.WatermarkOptions{
+			Image:    convertImage,
+			Text:     convertText,
+			Position: convertPosition,
+			Opacity:  convertOpacity,
+			Margin:   convertMargin,
+			Width:    convertWidth,
+			FontSize: convertFontSize,
+		}
+		if err := ffmpeg.ValidateWatermark(opts); err != nil {
+			return err
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.WatermarkedPath(input))
+		}
+		if err := ffmpeg.Watermark(ctx, input, output, opts, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertAdjustCmd = &cobra.Command{
+	Use:     "adjust <file>",
+	Aliases: []string{"speed", "volume"},
+	Short:   "Change the playback speed and volume of a file",
+	Long: `Change the playback speed (--speed, 0.25 to 4) and volume (--gain, in dB)
+of a file, e.g. to watch lectures faster. The audio keeps its pitch and is
+re-encoded; video is re-encoded only when the speed changes.
+
+The output is e.g. "<name> (1.5x).<ext>" next to the input unless -o is given.`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		opts := ffmpeg.AdjustOptions{Speed: convertSpeed, Gain: convertGain}
+		if err := ffmpeg.ValidateAdjust(opts); err != nil {
+			return err
+		}
+
+		input := args[0]
+		output := convertOutput
+		if output == "" {
+			output = downloader.UniquePath(ffmpeg.AdjustedPath(input, opts))
+		}
+		if err := ffmpeg.Adjust(ctx, input, output, opts, printConvertProgress); err != nil {
+			return err
+		}
+		fmt.Printf("\r✓ Saved %s\n", output)
+		return nil
+	},
+}
+
+var convertBatchCmd = &cobra.Command{
+	Use:   "batch <folder | files...>",
+	Short: "Convert several files, or a folder, with one preset",
+	Long: `Convert files one after another with one preset: a video preset (see
+'vget convert video --help'), or podcast or music to extract the audio in
+--format. Given a folder, the audio and video files directly in it are
+converted, by name.
+
+Each output goes next to its input, named as 'vget convert video' or
+'vget convert audio' would name it. A file that fails doesn't stop the rest.`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, stop := convertContext()
+		defer stop()
+
+		if convertPreset == "" {
+			return fmt.Errorf("--preset is required")
+		}
+		preset, err := ffmpeg.FindBatchPreset(convertPreset, convertFormat)
+		if err != nil {
+			return err
+		}
+		inputs := args
+		if info, err := os.Stat(args[0]); err == nil && info.IsDir() && len(args) == 1 {
+			if inputs, err = ffmpeg.MediaFiles(args[0]); err != nil {
+				return err
+			}
+			if len(inputs) == 0 {
+				return fmt.Errorf("no audio or video files in %s", args[0])
+			}
+		}
+
+		failed := 0
+		for i, input := range inputs {
+			fmt.Printf("[%d/%d] %s\n", i+1, len(inputs), input)
+			output := downloader.UniquePath(preset.OutputPath(input))
+			err := preset.Convert(ctx, input, output, func(done, total time.Duration) {
+				if total > 0 {
+					item := float64(done) / float64(total)
+					fmt.Printf("\r  Converting... %5.1f%% (overall %5.1f%%)", item*100, (float64(i)+item)/float64(len(inputs))*100)
+				} else {
+					printConvertProgress(done, total)
+				}
+			})
+			if ctx.Err() != nil {
+				fmt.Println()
+				return ctx.Err()
+			}
+			if err != nil {
+				failed++
+				fmt.Printf("\r✗ %v\n", err)
+				continue
+			}
+			fmt.Printf("\r✓ Saved %s\n", output)
+		}
+		if failed > 0 {
+			return fmt.Errorf("%d of %d files failed", failed, len(inputs))
+		}
+		return nil
+	},
+}
+
+var convertEncodersCmd = &cobra.Command{
+	Use:   "encoders",
+	Short: "List the hardware encoders that work on this machine",
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if !ffmpeg.Available() {
+			return fmt.Errorf("ffmpeg not found in PATH")
+		}
+		hardware := ffmpeg.HardwareEncoders()
+		if len(hardware) == 0 {
+			fmt.Println("No hardware encoders available, presets use software encoding")
+		}
+		for _, enc := range hardware {
+			fmt.Printf("  %-18s %-5s %s\n", enc.Name, enc.Codec, enc.Hardware)
+		}
+		return nil
+	},
+}
+
+// videoPresetHelp lists the video presets for help text
+func videoPresetHelp() string {
+	var b strings.Builder
+	for _, p := range ffmpeg.VideoPresets {
+		fmt.Fprintf(&b, "  %-11s %s\n", p.Name, p.Description)
+	}
+	return b.String()
+}
+
+// convertContext is cancelled by Ctrl-C, which stops ffmpeg and removes the
+// partial output
+func convertContext() (context.Context, context.CancelFunc) {
+	return signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+}
+
+// printConvertProgress shows how far an ffmpeg conversion is on one line
+func printConvertProgress(done, total time.Duration) {
+	if total > 0 {
+		fmt.Printf("\r  Converting... %5.1f%%", float64(done)/float64(total)*100)
+	} else {
+		fmt.Printf("\r  Converting... %s", done.Truncate(time.Second))
+	}
+}
+
+func init() {
+	convertCmd.PersistentFlags().StringVarP(&convertOutput, "output", "o", "", "output file")
+	convertAudioCmd.Flags().StringVar(&convertFormat, "format", "", "mp3, m4a, or opus (default: from -o, else mp3)")
+	convertAudioCmd.Flags().StringVar(&convertPreset, "preset", "", "podcast or music")
+	convertAudioCmd.Flags().StringVar(&convertBitrate, "bitrate", "", "audio bitrate, e.g. 192k")
+	convertAudioCmd.Flags().IntVar(&convertChannels, "channels", 0, "audio channels, 1 for mono")
+	convertVideoCmd.Flags().StringVar(&convertVideo, "preset", "web", "video preset")
+	convertCmd.AddCommand(convertAudioCmd)
+	convertVideoCmd.Flags().BoolVar(&convertSoftware, "software", false, "don't use a hardware encoder")
+	convertCmd.AddCommand(convertVideoCmd)
+	convertTrimCmd.Flags().StringVar(&convertStart, "start", "", "where the clip starts (default: the beginning)")
+	convertTrimCmd.Flags().StringVar(&convertEnd, "end", "", "where the clip ends (default: the end)")
+	convertTrimCmd.Flags().BoolVar(&convertReencode, "reencode", false, "re-encode for a frame-accurate cut")
+	convertCmd.AddCommand(convertTrimCmd)
+	convertGIFCmd.Flags().StringVar(&convertStart, "start", "", "where the animation starts (default: the beginning)")
+	convertGIFCmd.Flags().Float64Var(&convertDuration, "duration", 0, "seconds to include (default: the rest of the video)")
+	convertGIFCmd.Flags().IntVar(&convertFPS, "fps", ffmpeg.DefaultAnimationFPS, "frames per second")
+	convertGIFCmd.Flags().IntVar(&convertWidth, "width", ffmpeg.DefaultAnimationWidth, "width in pixels; height follows")
+	convertCmd.AddCommand(convertGIFCmd)
+	convertFrameCmd.Flags().StringVar(&convertAt, "at", "", "position of the frame (default: the first frame)")
+	convertFrameCmd.Flags().StringVar(&convertFormat, "format", "", "png or jpg (default: from -o, else png)")
+	convertFrameCmd.Flags().IntVar(&convertWidth, "width", 0, "width in pixels; height follows (default: the video's)")
+	convertCmd.AddCommand(convertFrameCmd)
+	convertSubsCmd.Flags().BoolVar(&convertBurn, "burn", false, "draw the subtitles onto the video")
+	convertSubsCmd.Flags().StringVar(&convertLangs, "lang", "", "languages of the saved subtitles to use, e.g. en,zh-Hans (default: all)")
+	convertCmd.AddCommand(convertSubsCmd)
+	convertRemuxCmd.Flags().StringVar(&convertTo, "to", "", "container: mp4, m4a, mkv, or webm (default: from -o)")
+	convertCmd.AddCommand(convertRemuxCmd)
+	convertLoudnessCmd.Flags().Float64Var(&convertTarget, "target", 0, "loudness in LUFS (default: download.loudness_target, else -16)")
+	convertCmd.AddCommand(convertLoudnessCmd)
+	convertWatermarkCmd.Flags().StringVar(&convertImage, "image", "", "PNG to overlay")
+	convertWatermarkCmd.Flags().StringVar(&convertText, "text", "", "text to overlay")
+	convertWatermarkCmd.Flags().StringVar(&convertPosition, "position", ffmpeg.WatermarkBottomRight, "top-left, top-right, bottom-left, bottom-right, or center")
+	convertWatermarkCmd.Flags().Float64Var(&convertOpacity, "opacity", ffmpeg.DefaultWatermarkOpacity, "from 0 to 1, where 1 is opaque")
+	convertWatermarkCmd.Flags().IntVar(&convertMargin, "margin", ffmpeg.DefaultWatermarkMargin, "pixels from the edges")
+	convertWatermarkCmd.Flags().IntVar(&convertWidth, "width", 0, "width of the image in pixels (default: its own)")
+	convertWatermarkCmd.Flags().IntVar(&convertFontSize, "size", 0, "font size of the text (default: from the video's height)")
+	convertCmd.AddCommand(convertWatermarkCmd)
+	convertAdjustCmd.Flags().Float64Var(&convertSpeed, "speed", 1, "playback speed, e.g. 1.5 or 0.75")
+	convertAdjustCmd.Flags().Float64Var(&convertGain, "gain", 0, "volume change in dB, e.g. 6 or -3")
+	convertCmd.AddCommand(convertAdjustCmd)
+	convertBatchCmd.Flags().StringVar(&convertPreset, "preset", "", "video preset, or podcast or music for audio")
+	convertBatchCmd.Flags().StringVar(&convertFormat, "format", "", "mp3, m4a, or opus, for audio presets (default: mp3)")
+	convertCmd.AddCommand(convertBatchCmd)
+	convertCmd.AddCommand(convertEncodersCmd)
+	rootCmd.AddCommand(convertCmd)
+}
@@ -0,0 +1,91 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloaded"
+	"github.com/guiyumin/vget/internal/core/downloader"
+)
+
+// onDuplicate is the --on-duplicate flag
+var onDuplicate string
+
+// skipDownloaded reports whether media in the download archive should be
+// skipped, following --on-duplicate or download.on_duplicate from config.
+// The prompt policy asks first, and skips when stdin is not a terminal.
+func skipDownloaded(entry downloaded.Entry) bool {
+	if info {
+		return false
+	}
+	store, err := downloaded.Open()
+	if err != nil {
+		return false
+	}
+	found, err := store.Find(entry)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: %v\n", err)
+		return false
+	}
+	if found == nil {
+		return false
+	}
+
+	msg := "Already downloaded on " + found.DownloadedAt.Format("2006-01-02 15:04")
+	if found.Filename != "" {
+		msg += ": " + found.Filename
+	}
+
+	switch config.LoadOrDefault().Download.EffectiveOnDuplicate(onDuplicate) {
+	case config.DuplicateOverwrite:
+		return false
+	case config.DuplicateSkip:
+		fmt.Printf("  %s, skipping\n", msg)
+		return true
+	}
+
+	if stat, err := os.Stdin.Stat(); err != nil || stat.Mode()&os.ModeCharDevice == 0 {
+		fmt.Printf("  %s, skipping\n", msg)
+		return true
+	}
+	fmt.Printf("  %s\n", msg)
+	fmt.Print("  Download again? [y/N]: ")
+
+	var response string
+	fmt.Scanln(&response)
+
+	response = strings.TrimSpace(strings.ToLower(response))
+	return response != "y" && response != "yes"
+}
+
+// recordDownloaded adds media to the download archive once its download
+// succeeded. The download error is passed through unchanged.
+func recordDownloaded(entry downloaded.Entry, err error) error {
+	if err != nil || info || (entry.URL == "" && entry.MediaID == "") {
+		return err
+	}
+	store, openErr := downloaded.Open()
+	if openErr != nil {
+		return nil
+	}
+	if addErr := store.Add(entry); addErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: failed to update download archive: %v\n", addErr)
+	}
+	return nil
+}
+
+// freeOutputPath picks "name (n).ext" when the output file already exists,
+// except under the overwrite policy
+func freeOutputPath(path string) string {
+	if config.LoadOrDefault().Download.EffectiveOnDuplicate(onDuplicate) == config.DuplicateOverwrite {
+		return path
+	}
+	free := downloader.UniquePath(path)
+	if free != path {
+		fmt.Printf("  %s exists, saving as %s\n", filepath.Base(path), filepath.Base(free))
+	}
+	return free
+}
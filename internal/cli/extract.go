@@ -168,12 +168,12 @@ func (m extractModel) View() string {
 }
 
 // runExtractWithSpinner runs extraction with a spinner TUI
-func runExtractWithSpinner(ext extractor.Extractor, url, lang string) (extractor.Media, error) {
+func runExtractWithSpinner(ext extractor.Extractor, ectx *extractor.ExtractContext, url, lang string) (extractor.Media, error) {
 	state := &extractState{}
 
 	// Start extraction in background
 	go func() {
-		result, err := ext.Extract(url)
+		result, err := extractor.Extract(ext, url, ectx)
 		if err != nil {
 			state.setError(err)
 		} else {
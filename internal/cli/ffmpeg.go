@@ -0,0 +1,75 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+	"github.com/spf13/cobra"
+)
+
+var ffmpegCmd = &cobra.Command{
+	Use:   "ffmpeg",
+	Short: "Show the ffmpeg vget uses, or download one",
+	Long: `Show the ffmpeg vget uses for merging, conversions, and embedding.
+
+vget runs the ffmpeg in PATH, else the copy 'vget ffmpeg install' downloaded
+into the bin directory of the config directory.
+
+Examples:
+  vget ffmpeg
+  vget ffmpeg install`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if !ffmpeg.Available() {
+			if ffmpeg.Installable() {
+				fmt.Println("ffmpeg not found, run 'vget ffmpeg install' to download it")
+			} else {
+				fmt.Println("ffmpeg not found, install it with your package manager")
+			}
+			return nil
+		}
+		version, err := ffmpeg.Version(context.Background())
+		if err != nil {
+			return err
+		}
+		source := "PATH"
+		if ffmpeg.Managed() {
+			source = "downloaded by vget"
+		}
+		fmt.Printf("ffmpeg %s (%s)\n  %s\n", version, source, ffmpeg.Binary())
+		return nil
+	},
+}
+
+var ffmpegInstallCmd = &cobra.Command{
+	Use:   "install",
+	Short: "Download a static ffmpeg build for this machine",
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if ffmpeg.Available() && !ffmpeg.Managed() {
+			fmt.Printf("ffmpeg is already installed at %s\n", ffmpeg.Binary())
+			return nil
+		}
+
+		path, err := ffmpeg.Install(context.Background(), func(name string, downloaded, total int64) {
+			if total > 0 {
+				fmt.Printf("\r  Downloading %s... %s / %s", name, formatSize(downloaded), formatSize(total))
+			} else {
+				fmt.Printf("\r  Downloading %s... %s", name, formatSize(downloaded))
+			}
+		})
+		fmt.Println()
+		if err != nil {
+			return err
+		}
+		fmt.Printf("Installed ffmpeg at %s\n", path)
+		return nil
+	},
+}
+
+func init() {
+	// Only offered where a build with pinned digests exists
+	if ffmpeg.Installable() {
+		ffmpegCmd.AddCommand(ffmpegInstallCmd)
+	}
+	rootCmd.AddCommand(ffmpegCmd)
+}
@@ -0,0 +1,36 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/ftp"
+	"github.com/guiyumin/vget/internal/core/sanitize"
+)
+
+// runFTPDownload downloads a file from an ftp:// or sftp:// URL
+func runFTPDownload(rawURL string, cfg *config.Config) error {
+	name := ftp.Filename(rawURL)
+	outputFile := output
+	if outputFile == "" {
+		outputFile = sanitize.Filename(name)
+		if cfg.OutputDir != "" {
+			outputFile = filepath.Join(cfg.OutputDir, outputFile)
+		}
+	}
+	outputFile = freeOutputPath(outputFile)
+
+	reader, size, err := ftp.Open(context.Background(), rawURL)
+	if err != nil {
+		return err
+	}
+	if size >= 0 {
+		fmt.Printf("  %s (%s)\n", name, formatSize(size))
+	}
+
+	hookURL = rawURL
+	return finishDownload(outputFile, downloader.RunDownloadFromReaderTUI(reader, size, outputFile, name, cfg.Language))
+}
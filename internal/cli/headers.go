@@ -0,0 +1,49 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+var (
+	siteLogin   string   // --site: send the stored login of this site
+	headerFlags []string // --header "Name: Value", repeatable
+)
+
+// parseHeaderFlags parses --header values of the form "Name: Value"
+func parseHeaderFlags() (map[string]string, error) {
+	headers := make(map[string]string)
+	for _, h := range headerFlags {
+		name, value, ok := strings.Cut(h, ":")
+		name = strings.TrimSpace(name)
+		if !ok || name == "" {
+			return nil, fmt.Errorf("invalid header %q (use \"Name: Value\")", h)
+		}
+		headers[name] = strings.TrimSpace(value)
+	}
+	return headers, nil
+}
+
+// checkHeaderFlags validates --site and --header before anything is downloaded
+func checkHeaderFlags() error {
+	if siteLogin != "" {
+		if _, err := extractor.SiteHeaders(siteLogin); err != nil {
+			return err
+		}
+	}
+	_, err := parseHeaderFlags()
+	return err
+}
+
+// requestHeaders layers the headers for a download request: the stored login
+// of --site, then the extractor's headers, then --header values
+func requestHeaders(extracted map[string]string) map[string]string {
+	var site map[string]string
+	if siteLogin != "" {
+		site, _ = extractor.SiteHeaders(siteLogin)
+	}
+	custom, _ := parseHeaderFlags()
+	return extractor.LayerHeaders(site, extracted, custom)
+}
@@ -0,0 +1,226 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/history"
+	"github.com/spf13/cobra"
+)
+
+var (
+	historyTags   []string
+	historyLimit  int
+	historyExport string
+	historyRange  string
+)
+
+var historyCmd = &cobra.Command{
+	Use:   "history [search]",
+	Short: "Search download history",
+	Long: `Search the history of downloads made by vget-server.
+
+Examples:
+  vget history
+  vget history "interview" --tag research
+  vget history --tag research --export csv > research.csv
+  vget history tag <id> research interviews
+  vget history note <id> "Source for chapter 3"
+  vget history stats --range 30d
+  vget history verify <id>`,
+	Args: cobra.MaximumNArgs(1),
+	RunE: runHistory,
+}
+
+var historyTagCmd = &cobra.Command{
+	Use:   "tag <id> [tags...]",
+	Short: "Set the tags of a history entry (no tags clears them)",
+	Args:  cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		store, err := history.Open()
+		if err != nil {
+			return err
+		}
+		tags := args[1:]
+		if tags == nil {
+			tags = []string{}
+		}
+		entry, err := store.Annotate(args[0], tags, nil)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ %s tags: %s\n", entry.ID, strings.Join(entry.Tags, ", "))
+		return nil
+	},
+}
+
+var historyNoteCmd = &cobra.Command{
+	Use:   "note <id> [text]",
+	Short: "Set the note of a history entry (no text clears it)",
+	Args:  cobra.RangeArgs(1, 2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		store, err := history.Open()
+		if err != nil {
+			return err
+		}
+		note := ""
+		if len(args) == 2 {
+			note = args[1]
+		}
+		entry, err := store.Annotate(args[0], nil, &note)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ %s note: %s\n", entry.ID, entry.Note)
+		return nil
+	},
+}
+
+var historyStatsCmd = &cobra.Command{
+	Use:   "stats",
+	Short: "Show downloads and their size per site and day",
+	Args:  cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		now := time.Now()
+		since, err := history.RangeStart(historyRange, now)
+		if err != nil {
+			return err
+		}
+		store, err := history.Open()
+		if err != nil {
+			return err
+		}
+		entries, err := store.Search(history.Query{Status: "completed", Since: since})
+		if err != nil {
+			return err
+		}
+
+		usage := history.Summarize(entries, now.Location())
+		fmt.Printf("Downloads: %d (%s)\n", usage.Downloads, formatSize(usage.Bytes))
+		if len(usage.Sites) > 0 {
+			fmt.Println("\nBy site:")
+			for _, site := range usage.Sites {
+				fmt.Printf("  %-16s %5d  %s\n", site.Site, site.Downloads, formatSize(site.Bytes))
+			}
+		}
+		if len(usage.Days) > 0 {
+			fmt.Println("\nBy day:")
+			for _, day := range usage.Days {
+				fmt.Printf("  %s  %5d  %s\n", day.Date, day.Downloads, formatSize(day.Bytes))
+			}
+		}
+		return nil
+	},
+}
+
+var historyVerifyCmd = &cobra.Command{
+	Use:   "verify <id>",
+	Short: "Check that the files of a history entry are unchanged since download",
+	Args:  cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		store, err := history.Open()
+		if err != nil {
+			return err
+		}
+		entry, err := store.Get(args[0])
+		if err != nil {
+			return err
+		}
+		if entry == nil || entry.Filename == "" {
+			return fmt.Errorf("history entry has no file: %s", args[0])
+		}
+
+		v := history.Verify(entry)
+		if _, err := store.SetVerification(entry.ID, v); err != nil {
+			return err
+		}
+		switch v.Status {
+		case history.VerifyOK:
+			fmt.Println("✓ Files are intact")
+		case history.VerifyNoChecksum:
+			fmt.Println("Files exist, but no checksum was recorded when they were downloaded")
+		default:
+			for _, path := range v.Missing {
+				fmt.Printf("✗ missing: %s\n", path)
+			}
+			for _, path := range v.Corrupted {
+				fmt.Printf("✗ changed: %s\n", path)
+			}
+			return fmt.Errorf("verification failed: %s", v.Status)
+		}
+		return nil
+	},
+}
+
+func runHistory(cmd *cobra.Command, args []string) error {
+	store, err := history.Open()
+	if err != nil {
+		return err
+	}
+
+	q := history.Query{Tags: history.NormalizeTags(historyTags), Limit: historyLimit}
+	if len(args) == 1 {
+		q.Text = args[0]
+	}
+
+	entries, err := store.Search(q)
+	if err != nil {
+		return err
+	}
+
+	if historyExport != "" {
+		return history.Export(os.Stdout, entries, historyExport)
+	}
+
+	if len(entries) == 0 {
+		fmt.Println("No history entries found")
+		return nil
+	}
+
+	for _, e := range entries {
+		title := e.Title
+		if title == "" {
+			title = e.URL
+		}
+		fmt.Printf("%s  %s  [%s]  %s\n", e.ID, e.FinishedAt.Format("2006-01-02 15:04"), e.Status, title)
+		if len(e.Tags) > 0 {
+			fmt.Printf("    tags: %s\n", strings.Join(e.Tags, ", "))
+		}
+		if e.Note != "" {
+			fmt.Printf("    note: %s\n", e.Note)
+		}
+	}
+
+	// Show tag counts when browsing without filters
+	if q.Text == "" && len(q.Tags) == 0 {
+		counts, _ := store.Tags()
+		if len(counts) > 0 {
+			tags := make([]string, 0, len(counts))
+			for tag := range counts {
+				tags = append(tags, tag)
+			}
+			sort.Strings(tags)
+			parts := make([]string, len(tags))
+			for i, tag := range tags {
+				parts[i] = fmt.Sprintf("%s (%d)", tag, counts[tag])
+			}
+			fmt.Printf("\nTags: %s\n", strings.Join(parts, ", "))
+		}
+	}
+	return nil
+}
+
+func init() {
+	historyCmd.Flags().StringArrayVarP(&historyTags, "tag", "t", nil, "only entries with this tag (repeatable)")
+	historyCmd.Flags().IntVarP(&historyLimit, "limit", "n", 50, "maximum entries to show (0 for all)")
+	historyCmd.Flags().StringVar(&historyExport, "export", "", "print entries as json or csv")
+	historyCmd.AddCommand(historyTagCmd)
+	historyCmd.AddCommand(historyNoteCmd)
+	historyCmd.AddCommand(historyVerifyCmd)
+	historyStatsCmd.Flags().StringVar(&historyRange, "range", "month", "period: 7d, 30d, month, year, or all")
+	historyCmd.AddCommand(historyStatsCmd)
+	rootCmd.AddCommand(historyCmd)
+}
@@ -0,0 +1,37 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/hook"
+)
+
+var (
+	hookCommand string // --hook
+	hookURL     string // page URL of the current download, for {url}
+)
+
+// runHook runs the --hook command, or download.hook from config, for a
+// finished file. The hook's output is shown and its failure is a warning.
+func runHook(path string) {
+	command := hookCommand
+	if command == "" {
+		command = config.LoadOrDefault().Download.Hook
+	}
+	if command == "" {
+		return
+	}
+
+	vars := hook.Vars{
+		Path:  path,
+		Title: strings.TrimSuffix(filepath.Base(path), filepath.Ext(path)),
+		URL:   hookURL,
+	}
+	if err := hook.Run(command, vars, os.Stdout); err != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: %v\n", err)
+	}
+}
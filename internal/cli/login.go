@@ -1,7 +1,14 @@
 package cli
 
 import (
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
 	"github.com/guiyumin/vget/internal/cli/login"
+	"github.com/guiyumin/vget/internal/core/auth"
+	"github.com/guiyumin/vget/internal/core/extractor"
 	"github.com/spf13/cobra"
 )
 
@@ -11,15 +18,209 @@ var loginCmd = &cobra.Command{
 	Long:  "Login to various media platforms to download member-only content",
 }
 
+var loginStatusCmd = &cobra.Command{
+	Use:   "status [site]",
+	Short: "Show the login status of sites",
+	Long:  "Show the login status of one site, or of the known sites and every site with a saved login.",
+	Args:  cobra.MaximumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		statuses := auth.Statuses()
+		if len(args) == 1 {
+			status, err := auth.GetStatus(args[0])
+			if err != nil {
+				return err
+			}
+			statuses = []auth.Status{status}
+		}
+		for _, status := range statuses {
+			printLoginStatus(status)
+		}
+		return nil
+	},
+}
+
+var loginCookieCmd = &cobra.Command{
+	Use:   "cookie <site> [cookie]",
+	Short: "Save a browser cookie as the login of a site",
+	Long: `Save the Cookie header of a logged-in browser session as the login of a site,
+replacing its saved cookies. site is bilibili, xiaohongshu, twitter,
+youtube, or a domain.
+Without a cookie argument it's read from stdin, keeping it out of the shell history.`,
+	Example: `  vget login cookie bilibili "SESSDATA=...; bili_jct=...; DedeUserID=..."
+  vget login cookie twitter "auth_token=...; ct0=..."
+  pbpaste | vget login cookie example.com`,
+	Args: cobra.RangeArgs(1, 2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		var cookie string
+		if len(args) == 2 {
+			cookie = args[1]
+		} else {
+			data, err := io.ReadAll(os.Stdin)
+			if err != nil {
+				return err
+			}
+			cookie = string(data)
+		}
+		if cookie = strings.TrimSpace(cookie); len(auth.ParseCookieHeader(cookie)) == 0 {
+			return fmt.Errorf("no cookies given; expected name=value pairs separated by ';'")
+		}
+		if err := auth.SetCookie(args[0], cookie); err != nil {
+			return err
+		}
+		status, err := auth.GetStatus(args[0])
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ Saved %d cookies for %s\n", status.Cookies, status.Site)
+		return nil
+	},
+}
+
+var (
+	loginBrowserURL      string
+	loginBrowserSelector string
+	loginBrowserDomains  []string
+)
+
+var loginBrowserCmd = &cobra.Command{
+	Use:   "browser <site>",
+	Short: "Login to a site in a browser window",
+	Long: `Open a browser window on a site's login page and wait for you to log in. Once
+an element matching --selector shows up, which the site shows only to logged-in
+users, the cookies the browser holds for the site are saved and the window
+closes. Extractors and downloads from the site then send them.
+
+xiaohongshu, twitter and youtube need no flags. For other sites, site is
+usually the domain the cookies belong to.`,
+	Example: `  vget login browser xiaohongshu
+  vget login browser twitter
+  vget login browser example.com --url https://example.com/login --selector ".user-avatar"`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		site, err := auth.SiteID(args[0])
+		if err != nil {
+			return err
+		}
+		opts := extractor.BrowserLoginOptions{
+			Site:            site,
+			URL:             loginBrowserURL,
+			SuccessSelector: loginBrowserSelector,
+			Domains:         loginBrowserDomains,
+		}
+		if builtin, ok := extractor.SiteLoginOptions(site); ok && loginBrowserURL == "" {
+			opts = builtin
+		} else if loginBrowserURL == "" || loginBrowserSelector == "" {
+			return fmt.Errorf("--url and --selector are required to log in to %s", site)
+		}
+
+		ctx, stop := convertContext()
+		defer stop()
+		fmt.Printf("Log in to %s in the browser window that opens...\n", site)
+		n, err := extractor.BrowserLogin(ctx, opts)
+		if err != nil {
+			return fmt.Errorf("%s login failed: %w", site, err)
+		}
+		fmt.Printf("✓ %s: logged in, saved %d cookies\n", site, n)
+		return nil
+	},
+}
+
+var loginProfilesCmd = &cobra.Command{
+	Use:   "profiles <site>",
+	Short: "List the login profiles of a site",
+	Long:  "List the saved login profiles of a site, marking the active one with *.",
+	Args:  cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		profiles, active, err := auth.Profiles(args[0])
+		if err != nil {
+			return err
+		}
+		for _, profile := range profiles {
+			mark := " "
+			if profile == active {
+				mark = "*"
+			}
+			fmt.Printf("%s %s\n", mark, profile)
+		}
+		return nil
+	},
+}
+
+var loginSwitchCmd = &cobra.Command{
+	Use:   "switch <site> <profile>",
+	Short: "Switch the active login profile of a site",
+	Long: `Switch which of several saved logins of a site, e.g. two Bilibili accounts,
+extraction and downloads use. The login of the profile switched away from is
+kept. A profile that doesn't exist yet starts logged out: log in to save its
+login. The first login of a site is the "default" profile.`,
+	Example: `  vget login switch bilibili work
+  vget login bilibili
+  vget login switch bilibili default`,
+	Args: cobra.ExactArgs(2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := auth.SwitchProfile(args[0], args[1]); err != nil {
+			return fmt.Errorf("failed to switch profile: %w", err)
+		}
+		status, err := auth.GetStatus(args[0])
+		if err != nil {
+			return err
+		}
+		fmt.Printf("✓ %s: switched to profile %s\n", status.Site, status.Profile)
+		if !status.LoggedIn {
+			fmt.Println("  Not logged in yet; log in to save this profile's login")
+		}
+		return nil
+	},
+}
+
 var logoutCmd = &cobra.Command{
-	Use:   "logout",
+	Use:   "logout <site>",
 	Short: "Logout from media platforms",
-	Long:  "Clear saved credentials for media platforms",
+	Long:  "Clear the saved login of a site: bilibili, xiaohongshu, twitter, or a domain",
+	Args:  cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		site, err := auth.SiteID(args[0])
+		if err != nil {
+			return err
+		}
+		if err := auth.Logout(site); err != nil {
+			return fmt.Errorf("failed to clear login: %w", err)
+		}
+		fmt.Printf("✓ %s credentials cleared\n", site)
+		return nil
+	},
+}
+
+// printLoginStatus prints one line of `vget login status`
+func printLoginStatus(status auth.Status) {
+	site := status.Site
+	if len(status.Profiles) > 1 || (status.Profile != "" && status.Profile != auth.DefaultProfile) {
+		site += " [" + status.Profile + "]"
+	}
+	switch {
+	case status.LoggedIn && !status.Expires.IsZero():
+		fmt.Printf("✓ %s: logged in (until %s)\n", site, status.Expires.Local().Format("2006-01-02"))
+	case status.LoggedIn:
+		fmt.Printf("✓ %s: logged in\n", site)
+	case status.Expired:
+		fmt.Printf("✗ %s: login expired\n", site)
+	default:
+		fmt.Printf("✗ %s: not logged in\n", site)
+	}
 }
 
 func init() {
 	loginCmd.AddCommand(login.BilibiliCmd())
-	logoutCmd.AddCommand(login.BilibiliLogoutCmd())
+	loginCmd.AddCommand(login.XiaohongshuCmd())
+	loginCmd.AddCommand(loginStatusCmd)
+	loginCmd.AddCommand(loginCookieCmd)
+	loginCmd.AddCommand(loginProfilesCmd)
+	loginCmd.AddCommand(loginSwitchCmd)
+
+	loginBrowserCmd.Flags().StringVar(&loginBrowserURL, "url", "", "login page to open")
+	loginBrowserCmd.Flags().StringVar(&loginBrowserSelector, "selector", "", "CSS selector of an element shown only once logged in")
+	loginBrowserCmd.Flags().StringSliceVar(&loginBrowserDomains, "domain", nil, "domain whose cookies are saved (default: the site's, or the login page's)")
+	loginCmd.AddCommand(loginBrowserCmd)
 	rootCmd.AddCommand(loginCmd)
 	rootCmd.AddCommand(logoutCmd)
 }
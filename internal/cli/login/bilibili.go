@@ -9,7 +9,7 @@ import (
 	"github.com/charmbracelet/bubbles/textinput"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
-	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/auth"
 	"github.com/guiyumin/vget/internal/core/site/bilibili"
 	"github.com/spf13/cobra"
 	qrcode "github.com/yeqown/go-qrcode/v2"
@@ -56,23 +56,6 @@ func BilibiliCmd() *cobra.Command {
 	return cmd
 }
 
-// BilibiliLogoutCmd returns the bilibili logout command
-func BilibiliLogoutCmd() *cobra.Command {
-	return &cobra.Command{
-		Use:   "bilibili",
-		Short: "Clear Bilibili credentials",
-		RunE: func(cmd *cobra.Command, args []string) error {
-			cfg := config.LoadOrDefault()
-			cfg.Bilibili.Cookie = ""
-			if err := config.Save(cfg); err != nil {
-				return fmt.Errorf("failed to save config: %w", err)
-			}
-			fmt.Println("✓ Bilibili credentials cleared")
-			return nil
-		},
-	}
-}
-
 func bilibiliQRCmd() *cobra.Command {
 	return &cobra.Command{
 		Use:   "qr",
@@ -107,8 +90,8 @@ func bilibiliStatusCmd() *cobra.Command {
 		Use:   "status",
 		Short: "Check Bilibili login status",
 		Run: func(cmd *cobra.Command, args []string) {
-			cfg := config.LoadOrDefault()
-			if cfg.Bilibili.Cookie != "" && strings.Contains(cfg.Bilibili.Cookie, "SESSDATA") {
+			login, _ := auth.Get(auth.Bilibili)
+			if login.LoggedIn() && login.Cookie("SESSDATA") != "" {
 				fmt.Println("✓ Bilibili: logged in")
 			} else {
 				fmt.Println("✗ Bilibili: not logged in")
@@ -263,19 +246,10 @@ func newCookieLoginModel() cookieLoginModel {
 	inputs[2].PromptStyle = biliKeyStyle
 
 	// Load existing cookie if any
-	cfg := config.LoadOrDefault()
-	if cfg.Bilibili.Cookie != "" {
-		for part := range strings.SplitSeq(cfg.Bilibili.Cookie, ";") {
-			part = strings.TrimSpace(part)
-			if val, ok := strings.CutPrefix(part, "SESSDATA="); ok {
-				inputs[0].SetValue(val)
-			} else if val, ok := strings.CutPrefix(part, "bili_jct="); ok {
-				inputs[1].SetValue(val)
-			} else if val, ok := strings.CutPrefix(part, "DedeUserID="); ok {
-				inputs[2].SetValue(val)
-			}
-		}
-	}
+	login, _ := auth.Get(auth.Bilibili)
+	inputs[0].SetValue(login.Cookie("SESSDATA"))
+	inputs[1].SetValue(login.Cookie("bili_jct"))
+	inputs[2].SetValue(login.Cookie("DedeUserID"))
 
 	return cookieLoginModel{
 		inputs:  inputs,
@@ -333,9 +307,7 @@ func (m cookieLoginModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 
 			cookie := fmt.Sprintf("SESSDATA=%s; bili_jct=%s; DedeUserID=%s", sessdata, biliJct, dedeUserID)
 
-			cfg := config.LoadOrDefault()
-			cfg.Bilibili.Cookie = cookie
-			if err := config.Save(cfg); err != nil {
+			if err := auth.SetCookie(auth.Bilibili, cookie); err != nil {
 				m.error = fmt.Sprintf("保存失败: %v", err)
 				return m, nil
 			}
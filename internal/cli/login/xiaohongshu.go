@@ -0,0 +1,36 @@
+package login
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/signal"
+	"syscall"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/spf13/cobra"
+)
+
+// XiaohongshuCmd returns the xiaohongshu login command
+func XiaohongshuCmd() *cobra.Command {
+	return &cobra.Command{
+		Use:     "xiaohongshu",
+		Aliases: []string{"xhs"},
+		Short:   "Login to Xiaohongshu",
+		Long: `Login to Xiaohongshu in a browser window, by QR code or phone number.
+The window closes by itself once you're logged in, and the session's cookies
+are saved for downloads.`,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+			defer stop()
+
+			fmt.Println("Log in to Xiaohongshu in the browser window that opens...")
+			n, err := extractor.XiaohongshuLogin(ctx)
+			if err != nil {
+				return fmt.Errorf("xiaohongshu login failed: %w", err)
+			}
+			fmt.Printf("✓ Xiaohongshu: logged in, saved %d cookies\n", n)
+			return nil
+		},
+	}
+}
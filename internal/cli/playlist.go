@@ -0,0 +1,177 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/downloaded"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/i18n"
+	"github.com/guiyumin/vget/internal/core/sanitize"
+)
+
+var (
+	playlistItems   string // selects playlist entries, e.g. "1-5,8"
+	playlistReverse bool   // downloads the playlist last entry first
+)
+
+// downloadPlaylist downloads the selected entries in playlist order into a
+// folder named after the playlist, numbering the files by position
+func downloadPlaylist(m *extractor.PlaylistMedia, dl *downloader.Downloader, t *i18n.Translations, lang string, outputDir string) error {
+	if playlistReverse {
+		m = m.Reversed()
+	}
+	selected, err := parsePlaylistItems(playlistItems, len(m.Entries))
+	if err != nil {
+		return err
+	}
+	if m.SingleURL != "" {
+		fmt.Printf("  Note: this video is part of the collection %q (%d videos); download %s for only this video\n", m.Title, len(m.Entries), m.SingleURL)
+	}
+
+	// Info only mode
+	if info {
+		fmt.Printf("  Playlist: %s (%d items)\n", m.Title, len(m.Entries))
+		for _, i := range selected {
+			e := m.Entries[i]
+			date := ""
+			if !e.Published.IsZero() {
+				date = e.Published.Format("2006-01-02") + "  "
+			}
+			fmt.Printf("    [%d] %s%s\n", i+1, date, e.Title)
+		}
+		return nil
+	}
+
+	if output != "" {
+		fmt.Println("  Note: --output is ignored for playlists")
+		output = ""
+	}
+
+	// Keep playlist items together in a folder named after the playlist
+	dir := sanitize.Filename(m.Title)
+	if dir == "" {
+		dir = m.ID
+	}
+	if outputDir != "" {
+		dir = filepath.Join(outputDir, dir)
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return fmt.Errorf("failed to create directory: %w", err)
+	}
+
+	fmt.Printf("  Downloading %d item(s) to %s/\n", len(selected), dir)
+
+	var failed []string
+	for n, i := range selected {
+		e := m.Entries[i]
+		fmt.Printf("\n  [%d/%d] %s\n", n+1, len(selected), e.Title)
+
+		media := e.Media
+		if media == nil {
+			media, err = extractPlaylistEntry(e.URL)
+			if err != nil {
+				fmt.Fprintf(os.Stderr, "  Error: %v\n", err)
+				failed = append(failed, e.Title)
+				continue
+			}
+		}
+		// Name files after the numbered entry title
+		setMediaTitle(media, m.IndexedTitle(i))
+
+		var entryExt extractor.Extractor
+		if e.URL != "" {
+			entryExt = extractor.Find(e.URL)
+		}
+		entry := downloaded.NewEntry(entryExt, media, e.URL)
+		if skipDownloaded(entry) {
+			continue
+		}
+		if e.URL != "" {
+			hookURL = e.URL
+		}
+		if err := recordDownloaded(entry, downloadMedia(media, dl, t, lang, dir)); err != nil {
+			fmt.Fprintf(os.Stderr, "  Error: %v\n", err)
+			failed = append(failed, e.Title)
+		}
+	}
+
+	if len(failed) > 0 {
+		return fmt.Errorf("%d of %d item(s) failed", len(failed), len(selected))
+	}
+	return nil
+}
+
+// extractPlaylistEntry extracts an entry that only has a URL (e.g. a video in a playlist)
+func extractPlaylistEntry(url string) (extractor.Media, error) {
+	ext := extractor.Find(url)
+	if ext == nil {
+		return nil, fmt.Errorf("no extractor for %s", url)
+	}
+	return extractor.Extract(ext, url, nil)
+}
+
+// setMediaTitle overrides the title used for the output filename
+func setMediaTitle(media extractor.Media, title string) {
+	if title == "" {
+		return
+	}
+	switch m := media.(type) {
+	case *extractor.VideoMedia:
+		m.Title = title
+	case *extractor.AudioMedia:
+		m.Title = title
+	}
+}
+
+// parsePlaylistItems returns the zero-based indexes selected by a spec like
+// "1-5,8" (one-based, inclusive). An empty spec selects every item.
+func parsePlaylistItems(spec string, count int) ([]int, error) {
+	var result []int
+	if strings.TrimSpace(spec) == "" {
+		for i := 0; i < count; i++ {
+			result = append(result, i)
+		}
+		return result, nil
+	}
+
+	seen := make(map[int]bool)
+	for _, part := range strings.Split(spec, ",") {
+		part = strings.TrimSpace(part)
+		if part == "" {
+			continue
+		}
+		startStr, endStr, isRange := strings.Cut(part, "-")
+		start, err := strconv.Atoi(strings.TrimSpace(startStr))
+		if err != nil || start < 1 {
+			return nil, fmt.Errorf("invalid item %q in --items", part)
+		}
+		end := start
+		if isRange {
+			if strings.TrimSpace(endStr) == "" {
+				end = count // open-ended, e.g. "10-"
+			} else if end, err = strconv.Atoi(strings.TrimSpace(endStr)); err != nil || end < start {
+				return nil, fmt.Errorf("invalid range %q in --items", part)
+			}
+		}
+		for i := start; i <= end && i <= count; i++ {
+			if !seen[i-1] {
+				seen[i-1] = true
+				result = append(result, i-1)
+			}
+		}
+	}
+	if len(result) == 0 {
+		return nil, fmt.Errorf("--items %q selects nothing (playlist has %d items)", spec, count)
+	}
+	return result, nil
+}
+
+func init() {
+	rootCmd.Flags().StringVar(&playlistItems, "items", "", "playlist items to download, e.g. 1-5,8 (default all)")
+	rootCmd.Flags().BoolVar(&playlistReverse, "reverse", false, "download playlist items in reverse order, e.g. a podcast feed oldest first")
+}
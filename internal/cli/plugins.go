@@ -0,0 +1,53 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/plugin"
+	"github.com/spf13/cobra"
+)
+
+var pluginsCmd = &cobra.Command{
+	Use:   "plugins",
+	Short: "List installed extractor plugins",
+	Long: `List WASM extractor plugins installed in the plugins directory.
+
+Each plugin is a directory containing plugin.yml and plugin.wasm:
+  ~/.config/vget/plugins/<name>/plugin.yml
+  ~/.config/vget/plugins/<name>/plugin.wasm
+
+Built-in extractors take precedence over plugins for the same host.`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		dir, err := plugin.Dir()
+		if err != nil {
+			return err
+		}
+
+		manifests, errs := plugin.List()
+		for _, err := range errs {
+			fmt.Printf("  ✗ %v\n", err)
+		}
+		if len(manifests) == 0 {
+			fmt.Printf("No plugins installed in %s\n", dir)
+			return nil
+		}
+
+		fmt.Printf("Plugins in %s:\n", dir)
+		for _, m := range manifests {
+			name := m.Name
+			if m.Version != "" {
+				name += " " + m.Version
+			}
+			fmt.Printf("  %s  (%s)\n", name, strings.Join(m.Hosts, ", "))
+			if m.Description != "" {
+				fmt.Printf("    %s\n", m.Description)
+			}
+		}
+		return nil
+	},
+}
+
+func init() {
+	rootCmd.AddCommand(pluginsCmd)
+}
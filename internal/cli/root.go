@@ -7,22 +7,33 @@ import (
 	"os"
 	"path/filepath"
 	"strings"
+	"time"
 
+	"github.com/guiyumin/vget/internal/core/archive"
+	"github.com/guiyumin/vget/internal/core/auth"
 	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloaded"
 	"github.com/guiyumin/vget/internal/core/downloader"
 	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/ftp"
 	"github.com/guiyumin/vget/internal/core/i18n"
+	"github.com/guiyumin/vget/internal/core/opener"
+	"github.com/guiyumin/vget/internal/core/sanitize"
 	"github.com/guiyumin/vget/internal/core/version"
 	"github.com/guiyumin/vget/internal/core/webdav"
 	"github.com/spf13/cobra"
 )
 
 var (
-	output    string
-	quality   string
-	info      bool
-	inputFile string
-	visible   bool
+	output     string
+	quality    string
+	info       bool
+	inputFile  string
+	visible    bool
+	onComplete string
+	zipOutput  bool
+	formatSpec string
+	mirrors    []string
 )
 
 var rootCmd = &cobra.Command{
@@ -44,8 +55,9 @@ var rootCmd = &cobra.Command{
 			cmd.Help()
 			return
 		}
-		if err := runDownload(args[0]); err != nil {
+		if err := runDownloadWithMirrors(args[0]); err != nil {
 			fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+			printRedirects(err)
 			os.Exit(1)
 		}
 	},
@@ -54,19 +66,71 @@ var rootCmd = &cobra.Command{
 func init() {
 	rootCmd.Flags().StringVarP(&output, "output", "o", "", "output filename")
 	rootCmd.Flags().StringVarP(&quality, "quality", "q", "", "preferred quality (e.g., 1080p, 720p)")
+	rootCmd.Flags().StringVar(&formatSpec, "format", "", `format selector (e.g., "bestvideo[height<=1080]+bestaudio/best")`)
 	rootCmd.Flags().BoolVar(&info, "info", false, "show video info without downloading")
-	rootCmd.Flags().StringVarP(&inputFile, "file", "f", "", "read URLs from file (one per line)")
+	rootCmd.Flags().StringVarP(&inputFile, "file", "f", "", "read URLs from a text file (one per line) or a JSON list")
 	rootCmd.Flags().BoolVar(&visible, "visible", false, "show browser window (for debugging)")
+	rootCmd.Flags().BoolVar(&zipOutput, "zip", false, "zip galleries, and videos with their subtitles and thumbnail, into a single archive")
+	rootCmd.Flags().StringVar(&hookCommand, "hook", "", "command run after each download, with {path}, {title}, {url} placeholders (default: download.hook from config)")
+	rootCmd.Flags().StringVar(&onComplete, "on-complete", "", "action after download: none, open, reveal, play (default from config)")
+	rootCmd.Flags().StringVar(&siteLogin, "site", "", "send the stored login of a site: bilibili, xiaohongshu, twitter, youtube, or a domain")
+	rootCmd.Flags().StringArrayVarP(&headerFlags, "header", "H", nil, `extra request header, e.g. "Referer: https://example.com/" (repeatable)`)
+	rootCmd.Flags().StringArrayVar(&mirrors, "mirror", nil, "fallback URL to try if the download fails (repeatable)")
+	rootCmd.Flags().StringVar(&subtitleLangs, "subs", "", `subtitle languages to save next to the video, e.g. "en,zh-Hans" or "all" (default: download.subtitles from config)`)
+	rootCmd.Flags().StringVar(&onDuplicate, "on-duplicate", "", "already-downloaded media: prompt, skip_existing, overwrite (default from config)")
 }
 
 func Execute() error {
 	return rootCmd.Execute()
 }
 
+// printRedirects lists the redirect chain of a failed HTTP download, to tell
+// an expired signed link from a bad source URL
+func printRedirects(err error) {
+	var statusErr *downloader.StatusError
+	if !errors.As(err, &statusErr) || len(statusErr.RedirectChain) == 0 {
+		return
+	}
+	fmt.Fprintln(os.Stderr, "Redirects:")
+	for i, u := range statusErr.RedirectChain {
+		prefix := "  -> "
+		if i == 0 {
+			prefix = "     "
+		}
+		fmt.Fprintf(os.Stderr, "%s%s\n", prefix, u)
+	}
+}
+
+// runDownloadWithMirrors downloads url, trying each --mirror in turn if it fails
+func runDownloadWithMirrors(url string) error {
+	if len(mirrors) == 0 {
+		return runDownload(url)
+	}
+	candidates := append([]string{url}, mirrors...)
+	source, err := downloader.TryMirrors(context.Background(), candidates, func(candidate string) error {
+		err := runDownload(candidate)
+		if err != nil && candidate != candidates[len(candidates)-1] {
+			fmt.Fprintf(os.Stderr, "  Error: %v\n  Trying mirror...\n", err)
+		}
+		return err
+	})
+	if err == nil && source != url {
+		fmt.Printf("  Downloaded from mirror: %s\n", source)
+	}
+	return err
+}
+
 func runDownload(url string) error {
 	cfg := config.LoadOrDefault()
 	t := i18n.T(cfg.Language)
 
+	if _, err := config.ParseDuplicatePolicy(onDuplicate); err != nil {
+		return err
+	}
+	if err := checkHeaderFlags(); err != nil {
+		return err
+	}
+
 	// Check for config file and warn if missing
 	if !config.Exists() {
 		fmt.Fprintf(os.Stderr, "\033[33m%s. Run 'vget init'.\033[0m\n", t.Errors.ConfigNotFound)
@@ -77,38 +141,31 @@ func runDownload(url string) error {
 		return runWebDAVDownload(url, cfg.Language)
 	}
 
+	// FTP and SFTP files are downloaded directly, without extraction
+	if ftp.IsFTPURL(url) {
+		return runFTPDownload(url, cfg)
+	}
+
 	// Handle Telegram URLs specially (requires authenticated client context for download)
 	if isTelegramURL(url) {
 		return runTelegramDownload(url, output)
 	}
 
-	// Find matching extractor
-	ext := extractor.Match(url)
-	if ext == nil {
-		// Try sites.yml for configured sites first
-		sitesConfig, _ := config.LoadSites()
-		if sitesConfig != nil {
-			if site := sitesConfig.MatchSite(url); site != nil {
-				ext = extractor.NewBrowserExtractor(site, visible)
-			}
-		}
-		// Fall back to generic m3u8 detection for unknown sites
-		if ext == nil {
-			ext = extractor.NewGenericBrowserExtractor(visible)
-		}
+	// Follow short links (b23.tv, t.co, ...) so the right extractor matches
+	url, chain := extractor.ResolveShortURL(url)
+	if len(chain) > 0 {
+		fmt.Printf("  Resolved: %s\n", url)
 	}
 
-	// Configure Twitter extractor with auth if available
-	if twitterExt, ok := ext.(*extractor.TwitterExtractor); ok {
-		if cfg.Twitter.AuthToken != "" {
-			twitterExt.SetAuth(cfg.Twitter.AuthToken)
-		}
-	}
+	// Find matching extractor (built-in, plugin, sites.yml, or generic)
+	ectx := extractor.NewExtractContext()
+	ectx.Visible = visible
+	ext := extractor.Resolve(url, ectx)
 
 	// Check Bilibili login status and prompt for confirmation if not logged in
 	if bilibiliExt, ok := ext.(*extractor.BilibiliExtractor); ok {
 		_ = bilibiliExt // Mark as used
-		if cfg.Bilibili.Cookie == "" {
+		if login, _ := auth.Get(auth.Bilibili); !login.LoggedIn() {
 			if !confirmBilibiliNoLogin() {
 				return nil // User cancelled
 			}
@@ -116,7 +173,7 @@ func runDownload(url string) error {
 	}
 
 	// Extract media info with spinner
-	media, err := runExtractWithSpinner(ext, url, cfg.Language)
+	media, err := runExtractWithSpinner(ext, ectx, url, cfg.Language)
 	if err != nil {
 		// YouTube Docker requirement is already displayed in the TUI, don't show again
 		var ytErr *extractor.YouTubeDockerRequiredError
@@ -139,7 +196,7 @@ func runDownload(url string) error {
 				msg = twitterErr.Message
 			}
 			// Show auth hint if not authenticated
-			if cfg.Twitter.AuthToken == "" {
+			if twitterLogin, _ := auth.Get(auth.Twitter); extractor.TwitterAuthToken(twitterLogin) == "" {
 				return fmt.Errorf("%s\n%s", msg, t.Twitter.AuthHint)
 			}
 			return fmt.Errorf("%s", msg)
@@ -147,26 +204,56 @@ func runDownload(url string) error {
 		return err
 	}
 
+	extractor.SetRedirectChain(media, chain)
+	hookURL = url
+
+	// Playlist entries are checked one by one
+	if _, ok := media.(*extractor.PlaylistMedia); ok {
+		return downloadMedia(media, downloader.New(cfg.Language), t, cfg.Language, cfg.OutputDir)
+	}
+
+	entry := downloaded.NewEntry(ext, media, url)
+	if skipDownloaded(entry) {
+		return nil
+	}
+
 	dl := downloader.New(cfg.Language)
+	return recordDownloaded(entry, downloadMedia(media, dl, t, cfg.Language, cfg.OutputDir))
+}
+
+// downloadMedia downloads extracted media into outputDir based on its type
+func downloadMedia(media extractor.Media, dl *downloader.Downloader, t *i18n.Translations, lang string, outputDir string) error {
+	// Stop early: DRM protected streams only fail later with an opaque HTTP error
+	if reason := extractor.DRMReason(media); reason != "" {
+		return errors.New(reason)
+	}
 
-	// Handle based on media type
 	switch m := media.(type) {
 	case *extractor.YouTubeDirectDownload:
+		if info {
+			fmt.Printf("  %s\n", m.GetTitle())
+			printThumbnails(m)
+			printSubtitles(m.Subtitles)
+			printChapters(m.Chapters)
+			return nil
+		}
 		// YouTube: let yt-dlp handle the entire download (Docker only)
 		fmt.Printf("\n  %s Downloading with yt-dlp...\n\n", "⬇")
-		if err := extractor.DownloadWithYtdlp(m.URL, cfg.OutputDir); err != nil {
+		if err := extractor.DownloadWithYtdlp(m.URL, outputDir); err != nil {
 			return fmt.Errorf("yt-dlp download failed: %w", err)
 		}
 		fmt.Printf("\n  %s %s\n\n", "✓", t.Download.Completed)
 		return nil
 	case *extractor.VideoMedia:
-		return downloadVideo(m, dl, t, cfg.Language, cfg.OutputDir)
+		return downloadVideo(m, dl, t, lang, outputDir)
 	case *extractor.AudioMedia:
-		return downloadAudio(m, dl, cfg.OutputDir)
+		return downloadAudio(m, dl, outputDir)
 	case *extractor.ImageMedia:
-		return downloadImages(m, dl, cfg.OutputDir)
+		return downloadImages(m, dl, outputDir)
 	case *extractor.MultiVideoMedia:
-		return downloadMultiVideo(m, dl, t, cfg.Language, cfg.OutputDir)
+		return downloadMultiVideo(m, dl, t, lang, outputDir)
+	case *extractor.PlaylistMedia:
+		return downloadPlaylist(m, dl, t, lang, outputDir)
 	default:
 		return fmt.Errorf("unsupported media type")
 	}
@@ -307,6 +394,10 @@ func downloadMultiVideo(m *extractor.MultiVideoMedia, dl *downloader.Downloader,
 }
 
 func downloadVideo(m *extractor.VideoMedia, dl *downloader.Downloader, t *i18n.Translations, lang string, outputDir string) error {
+	if m.Locked != nil {
+		fmt.Printf("  Note: %s\n", m.Locked.Note())
+	}
+
 	// Info only mode
 	if info {
 		for i, f := range m.Formats {
@@ -316,6 +407,9 @@ func downloadVideo(m *extractor.VideoMedia, dl *downloader.Downloader, t *i18n.T
 			}
 			fmt.Printf("  [%d] %s %dx%d (%s)%s\n", i, f.Quality, f.Width, f.Height, f.Ext, audioInfo)
 		}
+		printThumbnails(m)
+		printSubtitles(m.Subtitles)
+		printChapters(m.Chapters)
 		return nil
 	}
 
@@ -324,13 +418,14 @@ func downloadVideo(m *extractor.VideoMedia, dl *downloader.Downloader, t *i18n.T
 	if format == nil {
 		return fmt.Errorf("%s", t.Download.NoFormats)
 	}
+	format.Headers = requestHeaders(format.Headers)
 
 	fmt.Printf("  %s: %s (%s)\n", t.Download.SelectedFormat, format.Quality, format.Ext)
 
 	// Determine output filename
 	outputFile := output
 	if outputFile == "" {
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		// For m3u8, output as .ts (MPEG-TS container)
 		ext := format.Ext
 		if ext == "m3u8" {
@@ -345,12 +440,13 @@ func downloadVideo(m *extractor.VideoMedia, dl *downloader.Downloader, t *i18n.T
 		if outputDir != "" {
 			outputFile = filepath.Join(outputDir, outputFile)
 		}
+		outputFile = sanitize.Path(outputFile)
 	}
 
 	// Use HLS downloader for m3u8 streams
 	if format.Ext == "m3u8" {
 		// Create directory with title to keep things organized
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		if title == "" {
 			title = m.ID
 		}
@@ -363,21 +459,50 @@ func downloadVideo(m *extractor.VideoMedia, dl *downloader.Downloader, t *i18n.T
 			return fmt.Errorf("failed to create directory: %w", err)
 		}
 		// Put output file inside the directory
-		outputFile = filepath.Join(baseDir, filepath.Base(outputFile))
+		outputFile = freeOutputPath(filepath.Join(baseDir, filepath.Base(outputFile)))
 		fmt.Printf("  Output directory: %s/\n", baseDir)
-		return downloader.RunHLSDownloadWithHeadersTUI(format.URL, outputFile, m.ID, lang, format.Headers)
+		path, err := downloader.RunHLSDownloadWithHeadersTUI(format.URL, outputFile, m.ID, lang, format.Headers)
+		path = remuxTS(path, err)
+		embedChapters(m.Chapters, path, err)
+		tagMetadata(m, path, err)
+		subs := saveSubtitles(m.Subtitles, path, err)
+		path = splitRecording(path, err)
+		return finishVideo(m, path, subs, outputDir, err)
 	}
 
+	outputFile = freeOutputPath(outputFile)
+
 	// Handle video+audio as separate downloads
 	if format.AudioURL != "" {
-		return downloadVideoAndAudio(format, outputFile, m.ID, dl)
+		path, err := downloadVideoAndAudio(format, m.Chapters, outputFile, m.ID, dl)
+		tagMetadata(m, path, err)
+		subs := saveSubtitles(m.Subtitles, path, err)
+		return finishVideo(m, path, subs, outputDir, err)
+	}
+
+	// Use headers if provided by the extractor, falling back to mirrors on failure
+	err := dl.DownloadMirrors(format.URLs(), outputFile, m.ID, format.Headers)
+	embedChapters(m.Chapters, outputFile, err)
+	tagMetadata(m, outputFile, err)
+	subs := saveSubtitles(m.Subtitles, outputFile, err)
+	return finishVideo(m, outputFile, subs, outputDir, err)
+}
+
+// finishVideo zips a downloaded video with its subtitles and thumbnail when
+// --zip or archive.enabled is set, then finishes the download of the result
+func finishVideo(m *extractor.VideoMedia, path string, subtitles []string, outputDir string, err error) error {
+	if err != nil || path == "" || !archiveEnabled() {
+		return finishDownload(path, err)
 	}
 
-	// Use headers if provided by the extractor
-	if len(format.Headers) > 0 {
-		return dl.DownloadWithHeaders(format.URL, outputFile, m.ID, format.Headers)
+	archivePath, err := archive.PackageVideo(path, subtitles, m.Thumbnail, outputDir, config.LoadOrDefault().Archive, archiveVars(m))
+	if archivePath, err = reportArchive(archivePath, err); err != nil {
+		return err
 	}
-	return dl.Download(format.URL, outputFile, m.ID)
+	if archivePath == "" {
+		return finishDownload(path, nil)
+	}
+	return finishDownload(archivePath, nil)
 }
 
 // downloadVideoWithIndex downloads a video with an index suffix in the filename (for multi-video posts)
@@ -391,6 +516,9 @@ func downloadVideoWithIndex(m *extractor.VideoMedia, dl *downloader.Downloader,
 			}
 			fmt.Printf("  [%d] %s %dx%d (%s)%s\n", i, f.Quality, f.Width, f.Height, f.Ext, audioInfo)
 		}
+		printThumbnails(m)
+		printSubtitles(m.Subtitles)
+		printChapters(m.Chapters)
 		return nil
 	}
 
@@ -399,13 +527,14 @@ func downloadVideoWithIndex(m *extractor.VideoMedia, dl *downloader.Downloader,
 	if format == nil {
 		return fmt.Errorf("%s", t.Download.NoFormats)
 	}
+	format.Headers = requestHeaders(format.Headers)
 
 	fmt.Printf("  %s: %s (%s)\n", t.Download.SelectedFormat, format.Quality, format.Ext)
 
 	// Determine output filename
 	outputFile := output
 	if outputFile == "" {
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		ext := format.Ext
 		if ext == "m3u8" {
 			ext = "ts"
@@ -424,11 +553,12 @@ func downloadVideoWithIndex(m *extractor.VideoMedia, dl *downloader.Downloader,
 		if outputDir != "" {
 			outputFile = filepath.Join(outputDir, outputFile)
 		}
+		outputFile = sanitize.Path(outputFile)
 	}
 
 	// Use HLS downloader for m3u8 streams
 	if format.Ext == "m3u8" {
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		if title == "" {
 			title = m.ID
 		}
@@ -439,25 +569,38 @@ func downloadVideoWithIndex(m *extractor.VideoMedia, dl *downloader.Downloader,
 		if err := os.MkdirAll(baseDir, 0755); err != nil {
 			return fmt.Errorf("failed to create directory: %w", err)
 		}
-		outputFile = filepath.Join(baseDir, filepath.Base(outputFile))
+		outputFile = freeOutputPath(filepath.Join(baseDir, filepath.Base(outputFile)))
 		fmt.Printf("  Output directory: %s/\n", baseDir)
-		return downloader.RunHLSDownloadWithHeadersTUI(format.URL, outputFile, m.ID, lang, format.Headers)
+		path, err := downloader.RunHLSDownloadWithHeadersTUI(format.URL, outputFile, m.ID, lang, format.Headers)
+		path = remuxTS(path, err)
+		embedChapters(m.Chapters, path, err)
+		tagMetadata(m, path, err)
+		saveSubtitles(m.Subtitles, path, err)
+		path = splitRecording(path, err)
+		return err
 	}
 
+	outputFile = freeOutputPath(outputFile)
+
 	// Handle video+audio as separate downloads
 	if format.AudioURL != "" {
-		return downloadVideoAndAudio(format, outputFile, m.ID, dl)
+		path, err := downloadVideoAndAudio(format, m.Chapters, outputFile, m.ID, dl)
+		tagMetadata(m, path, err)
+		saveSubtitles(m.Subtitles, path, err)
+		return err
 	}
 
-	// Use headers if provided by the extractor
-	if len(format.Headers) > 0 {
-		return dl.DownloadWithHeaders(format.URL, outputFile, m.ID, format.Headers)
-	}
-	return dl.Download(format.URL, outputFile, m.ID)
+	// Use headers if provided by the extractor, falling back to mirrors on failure
+	err := dl.DownloadMirrors(format.URLs(), outputFile, m.ID, format.Headers)
+	embedChapters(m.Chapters, outputFile, err)
+	tagMetadata(m, outputFile, err)
+	saveSubtitles(m.Subtitles, outputFile, err)
+	return err
 }
 
-// downloadVideoAndAudio downloads video and audio as separate files, then merges them if ffmpeg is available
-func downloadVideoAndAudio(format *extractor.VideoFormat, outputFile, videoID string, dl *downloader.Downloader) error {
+// downloadVideoAndAudio downloads video and audio as separate files, then merges them if ffmpeg is available.
+// Returns the merged file path, or the video file path if no merge happened.
+func downloadVideoAndAudio(format *extractor.VideoFormat, chapters []extractor.Chapter, outputFile, videoID string, dl *downloader.Downloader) (string, error) {
 	// Determine audio extension based on video format
 	audioExt := "m4a"
 	if format.Ext == "webm" {
@@ -472,31 +615,20 @@ func downloadVideoAndAudio(format *extractor.VideoFormat, outputFile, videoID st
 
 	// Download video with headers if provided
 	fmt.Println("  Downloading video stream...")
-	var err error
-	if len(format.Headers) > 0 {
-		err = dl.DownloadWithHeaders(format.URL, videoFile, videoID+"-video", format.Headers)
-	} else {
-		err = dl.Download(format.URL, videoFile, videoID+"-video")
-	}
-	if err != nil {
-		return fmt.Errorf("failed to download video: %w", err)
+	if err := dl.DownloadMirrors(format.URLs(), videoFile, videoID+"-video", format.Headers); err != nil {
+		return "", fmt.Errorf("failed to download video: %w", err)
 	}
 
 	// Download audio with headers if provided
 	fmt.Println("  Downloading audio stream...")
-	if len(format.Headers) > 0 {
-		err = dl.DownloadWithHeaders(format.AudioURL, audioFile, videoID+"-audio", format.Headers)
-	} else {
-		err = dl.Download(format.AudioURL, audioFile, videoID+"-audio")
-	}
-	if err != nil {
-		return fmt.Errorf("failed to download audio: %w", err)
+	if err := dl.DownloadMirrors(format.AudioURLs(), audioFile, videoID+"-audio", format.Headers); err != nil {
+		return "", fmt.Errorf("failed to download audio: %w", err)
 	}
 
 	// Try to merge with ffmpeg if available
 	if downloader.FFmpegAvailable() {
 		fmt.Println("  Merging video and audio...")
-		mergedPath, err := downloader.MergeVideoAudioKeepOriginals(videoFile, audioFile)
+		mergedPath, err := downloader.MergeVideoAudioKeepOriginals(context.Background(), videoFile, audioFile, chapters, nil)
 		if err != nil {
 			// Merge failed, show manual command
 			fmt.Printf("\n  Warning: ffmpeg merge failed: %v\n", err)
@@ -510,6 +642,7 @@ func downloadVideoAndAudio(format *extractor.VideoFormat, outputFile, videoID st
 			fmt.Printf("    Video: %s\n", videoFile)
 			fmt.Printf("    Audio: %s\n", audioFile)
 			fmt.Printf("    Merged: %s\n", mergedPath)
+			return mergedPath, nil
 		}
 	} else {
 		// No ffmpeg, show manual command
@@ -520,7 +653,7 @@ func downloadVideoAndAudio(format *extractor.VideoFormat, outputFile, videoID st
 		fmt.Printf("    ffmpeg -i \"%s\" -i \"%s\" -c copy \"%s\"\n", videoFile, audioFile, baseName+"_merged.mp4")
 	}
 
-	return nil
+	return videoFile, nil
 }
 
 func downloadAudio(m *extractor.AudioMedia, dl *downloader.Downloader, outputDir string) error {
@@ -533,7 +666,7 @@ func downloadAudio(m *extractor.AudioMedia, dl *downloader.Downloader, outputDir
 	// Determine output filename
 	outputFile := output
 	if outputFile == "" {
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		if title != "" {
 			outputFile = fmt.Sprintf("%s.%s", title, m.Ext)
 		} else {
@@ -543,9 +676,14 @@ func downloadAudio(m *extractor.AudioMedia, dl *downloader.Downloader, outputDir
 		if outputDir != "" {
 			outputFile = filepath.Join(outputDir, outputFile)
 		}
+		outputFile = sanitize.Path(outputFile)
 	}
 
-	return dl.Download(m.URL, outputFile, m.ID)
+	outputFile = freeOutputPath(outputFile)
+	err := dl.DownloadWithHeaders(m.URL, outputFile, m.ID, requestHeaders(nil))
+	embedCoverArt(m.Thumbnail, outputFile, err)
+	tagMetadata(m, outputFile, err)
+	return finishDownload(outputFile, err)
 }
 
 func downloadImages(m *extractor.ImageMedia, dl *downloader.Downloader, outputDir string) error {
@@ -560,6 +698,7 @@ func downloadImages(m *extractor.ImageMedia, dl *downloader.Downloader, outputDi
 
 	fmt.Printf("  Downloading %d image(s)...\n", len(m.Images))
 
+	var files []string
 	for i, img := range m.Images {
 		var outputFile string
 		if output != "" {
@@ -572,7 +711,7 @@ func downloadImages(m *extractor.ImageMedia, dl *downloader.Downloader, outputDi
 		} else {
 			// Use sanitized title or ID with index suffix
 			baseFilename := m.ID
-			if title := extractor.SanitizeFilename(m.Title); title != "" {
+			if title := sanitize.Filename(m.Title); title != "" {
 				baseFilename = title
 			}
 			if len(m.Images) > 1 {
@@ -584,13 +723,173 @@ func downloadImages(m *extractor.ImageMedia, dl *downloader.Downloader, outputDi
 			if outputDir != "" {
 				outputFile = filepath.Join(outputDir, outputFile)
 			}
+			outputFile = sanitize.Path(outputFile)
 		}
 
-		if err := dl.Download(img.URL, outputFile, m.ID); err != nil {
+		outputFile = freeOutputPath(outputFile)
+		if err := dl.DownloadWithHeaders(img.URL, outputFile, m.ID, requestHeaders(nil)); err != nil {
 			return fmt.Errorf("failed to download image %d: %w", i+1, err)
 		}
+		files = append(files, outputFile)
+	}
+
+	if len(files) > 1 {
+		archivePath, err := archiveFiles(files, m, outputDir)
+		if err != nil {
+			return err
+		}
+		if archivePath != "" {
+			return finishDownload(archivePath, nil)
+		}
+	}
+	return finishDownload(files[0], nil)
+}
+
+// archiveEnabled reports whether multi-file downloads are zipped, by --zip or archive.enabled
+func archiveEnabled() bool {
+	return zipOutput || config.LoadOrDefault().Archive.Enabled
+}
+
+// archiveFiles zips the files of a multi-file download when --zip or archive.enabled is set.
+// Returns the archive path, or "" if archiving is disabled.
+func archiveFiles(files []string, media extractor.Media, outputDir string) (string, error) {
+	if !archiveEnabled() {
+		return "", nil
+	}
+	return reportArchive(archive.Package(files, outputDir, config.LoadOrDefault().Archive, archiveVars(media)))
+}
+
+// archiveVars fills the archive name template values from media
+func archiveVars(media extractor.Media) archive.NameVars {
+	return archive.NameVars{
+		Title:    sanitize.Filename(media.GetTitle()),
+		ID:       media.GetID(),
+		Uploader: sanitize.Filename(media.GetUploader()),
+	}
+}
+
+// reportArchive prints where a download was packaged, warning if its
+// originals were kept
+func reportArchive(archivePath string, err error) (string, error) {
+	if err != nil && !errors.Is(err, archive.ErrOriginalsKept) {
+		return "", fmt.Errorf("failed to create archive: %w", err)
+	}
+	if archivePath != "" {
+		fmt.Printf("  Packaged into %s\n", archivePath)
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: %v\n", err)
+	}
+	return archivePath, nil
+}
+
+// printThumbnails lists thumbnail sizes in info mode
+func printThumbnails(media extractor.Media) {
+	thumbs := extractor.Thumbnails(media)
+	if len(thumbs) == 0 {
+		return
+	}
+	fmt.Println("  Thumbnails:")
+	for i, thumb := range thumbs {
+		size := ""
+		if thumb.Width > 0 && thumb.Height > 0 {
+			size = fmt.Sprintf("%dx%d ", thumb.Width, thumb.Height)
+		}
+		fmt.Printf("    [%d] %s%s\n", i, size, thumb.URL)
+	}
+}
+
+// printSubtitles lists available subtitle tracks in info mode
+func printSubtitles(subs []extractor.Subtitle) {
+	if len(subs) == 0 {
+		return
+	}
+	fmt.Println("  Subtitles:")
+	for _, sub := range subs {
+		name := sub.Name
+		if name == "" {
+			name = sub.Language
+		}
+		auto := ""
+		if sub.Auto {
+			auto = " [auto]"
+		}
+		fmt.Printf("    %s - %s (%s)%s\n", sub.Language, name, sub.Ext, auto)
+	}
+}
+
+// printChapters lists chapters in info mode
+func printChapters(chapters []extractor.Chapter) {
+	if len(chapters) == 0 {
+		return
+	}
+	fmt.Println("  Chapters:")
+	for _, c := range chapters {
+		start := int(c.Start)
+		fmt.Printf("    %d:%02d:%02d  %s\n", start/3600, start%3600/60, start%60, c.Title)
+	}
+}
+
+// embedChapters writes chapters into a downloaded video that wasn't merged,
+// as merging embeds them already. Failures are warnings.
+func embedChapters(chapters []extractor.Chapter, videoPath string, err error) {
+	if err != nil || videoPath == "" || len(chapters) == 0 {
+		return
+	}
+	if embedErr := downloader.EmbedChapters(context.Background(), videoPath, chapters); embedErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: chapters: %v\n", embedErr)
+	}
+}
+
+// embedCoverArt makes the thumbnail the cover art of a downloaded audio file
+// when ffmpeg is available
+func embedCoverArt(thumbURL, path string, err error) {
+	if err != nil || path == "" || !downloader.FFmpegAvailable() {
+		return
+	}
+	if coverErr := downloader.EmbedCoverArt(context.Background(), path, thumbURL); coverErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: cover art: %v\n", coverErr)
+	}
+}
+
+// remuxTS moves an HLS download ffmpeg couldn't join into MP4 from MPEG-TS
+// into a faststart MP4, returning where the download ends up
+func remuxTS(path string, err error) string {
+	if err != nil || path == "" {
+		return path
+	}
+	mp4Path, remuxErr := downloader.RemuxTS(context.Background(), path)
+	if remuxErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: remuxing to MP4: %v\n", remuxErr)
+	}
+	return mp4Path
+}
+
+// splitRecording splits a long HLS recording into parts when
+// download.split_minutes is set, returning the first part
+func splitRecording(path string, err error) string {
+	minutes := config.LoadOrDefault().Download.SplitMinutes
+	if err != nil || path == "" || minutes <= 0 || !downloader.FFmpegAvailable() {
+		return path
+	}
+	parts, splitErr := downloader.SplitRecording(context.Background(), path, time.Duration(minutes)*time.Minute)
+	if splitErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: splitting: %v\n", splitErr)
+	} else if len(parts) > 1 {
+		fmt.Printf("  Split into %d parts of %d minutes\n", len(parts), minutes)
+	}
+	return parts[0]
+}
+
+// tagMetadata writes the title, uploader, date, and description of media into
+// a finished download when download.tag_metadata is on. Failures are warnings.
+func tagMetadata(media extractor.Media, path string, err error) {
+	if err != nil || path == "" || !config.LoadOrDefault().Download.TagMetadata {
+		return
+	}
+	if tagErr := downloader.TagMetadata(context.Background(), path, downloader.MediaMetadata(media)); tagErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: metadata: %v\n", tagErr)
 	}
-	return nil
 }
 
 func selectVideoFormat(formats []extractor.VideoFormat, preferred string) *extractor.VideoFormat {
@@ -598,19 +897,18 @@ func selectVideoFormat(formats []extractor.VideoFormat, preferred string) *extra
 		return nil
 	}
 
-	// If quality specified, try to match
-	if preferred != "" {
-		for i := range formats {
-			if formats[i].Quality == preferred {
-				return &formats[i]
-			}
-		}
-		// Also try partial match (e.g., "1080" matches "1080p60")
-		for i := range formats {
-			if strings.Contains(formats[i].Quality, preferred) {
-				return &formats[i]
-			}
+	// A --format selector takes precedence over --quality
+	if formatSpec != "" {
+		f, err := extractor.SelectFormat(formats, formatSpec)
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "  %v\n", err)
 		}
+		return f
+	}
+
+	// If quality specified, try to match
+	if f := extractor.MatchQuality(formats, preferred); f != nil {
+		return f
 	}
 
 	// Prefer highest quality adaptive format with audio (will download both files)
@@ -651,6 +949,31 @@ func selectVideoFormat(formats []extractor.VideoFormat, preferred string) *extra
 	return best
 }
 
+// finishDownload runs the completion action (--on-complete flag, or on_complete from config)
+// and the download hook for a successfully downloaded file. The download error is passed
+// through unchanged.
+func finishDownload(path string, err error) error {
+	if err != nil || path == "" || info {
+		return err
+	}
+	defer runHook(path)
+
+	value := onComplete
+	if value == "" {
+		value = config.LoadOrDefault().OnComplete
+	}
+
+	action, parseErr := opener.ParseAction(value)
+	if parseErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: %v\n", parseErr)
+		return nil
+	}
+	if runErr := opener.Run(action, path); runErr != nil {
+		fmt.Fprintf(os.Stderr, "  Warning: failed to %s %s: %v\n", action, path, runErr)
+	}
+	return nil
+}
+
 // isTelegramURL checks if the URL is a Telegram message URL
 func isTelegramURL(urlStr string) bool {
 	return strings.Contains(urlStr, "t.me/") || strings.Contains(urlStr, "telegram.me/")
@@ -679,8 +1002,8 @@ func runTelegramDownload(urlStr, outputPath string) error {
 	cfg := config.LoadOrDefault()
 	lang := cfg.Language
 
-	downloadFn := func(url, output string, progressFn func(int64, int64)) (*downloader.TelegramDownloadResult, error) {
-		result, err := extractor.TelegramDownload(url, output, progressFn)
+	downloadFn := func(ctx context.Context, url, output string, progressFn func(int64, int64)) (*downloader.TelegramDownloadResult, error) {
+		result, err := extractor.TelegramDownload(ctx, url, output, progressFn)
 		if err != nil {
 			return nil, err
 		}
@@ -708,8 +1031,8 @@ func runTelegramBatchDownload(urls []string) (succeeded, failed int, failedURLs
 	for i, urlStr := range urls {
 		fmt.Printf("  [%d/%d] %s\n", i+1, len(urls), urlStr)
 
-		downloadFn := func(url, output string, progressFn func(int64, int64)) (*downloader.TelegramDownloadResult, error) {
-			result, err := extractor.TelegramDownloadWithOptions(extractor.TelegramDownloadOptions{
+		downloadFn := func(ctx context.Context, url, output string, progressFn func(int64, int64)) (*downloader.TelegramDownloadResult, error) {
+			result, err := extractor.TelegramDownloadWithOptions(ctx, extractor.TelegramDownloadOptions{
 				URL:        url,
 				OutputPath: output,
 				Takeout:    true,
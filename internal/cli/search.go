@@ -19,6 +19,7 @@ import (
 	"github.com/guiyumin/vget/internal/core/config"
 	"github.com/guiyumin/vget/internal/core/downloader"
 	"github.com/guiyumin/vget/internal/core/i18n"
+	"github.com/guiyumin/vget/internal/core/sanitize"
 	"github.com/spf13/cobra"
 )
 
@@ -646,42 +647,19 @@ func runDirectDownload(downloadURL, title string) error {
 		ext = "m4a"
 	}
 
-	filename := sanitizeFilenameForDownload(title) + "." + ext
+	filename := sanitize.Filename(title) + "." + ext
 	outputDir := cfg.OutputDir
 	if outputDir == "" {
 		outputDir = "."
 	}
 
 	// Join directory and filename to create full path
-	outputPath := filepath.Join(outputDir, filename)
+	outputPath := freeOutputPath(sanitize.Path(filepath.Join(outputDir, filename)))
 
 	d := downloader.New(cfg.Language)
 	return d.Download(downloadURL, outputPath, title)
 }
 
-// sanitizeFilenameForDownload removes invalid characters from filename
-func sanitizeFilenameForDownload(name string) string {
-	// Replace invalid characters
-	replacer := strings.NewReplacer(
-		"/", "-",
-		"\\", "-",
-		":", "-",
-		"*", "",
-		"?", "",
-		"\"", "",
-		"<", "",
-		">", "",
-		"|", "",
-	)
-	result := replacer.Replace(name)
-	// Trim spaces and limit length
-	result = strings.TrimSpace(result)
-	if len(result) > 200 {
-		result = result[:200]
-	}
-	return result
-}
-
 // Search spinner model
 type searchSpinnerModel struct {
 	spinner spinner.Model
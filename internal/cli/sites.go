@@ -0,0 +1,35 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/spf13/cobra"
+)
+
+var sitesCmd = &cobra.Command{
+	Use:   "sites",
+	Short: "List supported sites",
+	Long: `List the sites vget has extractors for: built-in extractors, installed
+plugins, and entries in ./sites.yml. Other URLs fall back to direct download
+or generic browser detection.`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		source := ""
+		for _, site := range extractor.Sites() {
+			if site.Source != source {
+				if source != "" {
+					fmt.Println()
+				}
+				source = site.Source
+				fmt.Printf("%s:\n", source)
+			}
+			fmt.Printf("  %-14s %s\n", site.Name, strings.Join(site.Hosts, ", "))
+		}
+		return nil
+	},
+}
+
+func init() {
+	rootCmd.AddCommand(sitesCmd)
+}
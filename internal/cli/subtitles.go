@@ -0,0 +1,39 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/subtitle"
+)
+
+// subtitleLangs is the --subs flag
+var subtitleLangs string
+
+// saveSubtitles saves the languages from --subs, or download.subtitles from
+// config, next to a downloaded video and returns the saved paths. Failures
+// are warnings.
+func saveSubtitles(subs []extractor.Subtitle, videoPath string, err error) []string {
+	if err != nil || videoPath == "" || info || len(subs) == 0 {
+		return nil
+	}
+	value := subtitleLangs
+	if value == "" {
+		value = config.LoadOrDefault().Download.Subtitles
+	}
+
+	var saved []string
+	for _, sub := range subtitle.Select(subs, subtitle.ParseLanguages(value)) {
+		path, saveErr := subtitle.Save(sub, videoPath)
+		if saveErr != nil {
+			fmt.Fprintf(os.Stderr, "  Warning: %s subtitles: %v\n", sub.Language, saveErr)
+			continue
+		}
+		fmt.Printf("  Subtitles: %s\n", filepath.Base(path))
+		saved = append(saved, path)
+	}
+	return saved
+}
@@ -1,18 +1,69 @@
 package cli
 
 import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
 	"github.com/guiyumin/vget/internal/updater"
 	"github.com/spf13/cobra"
 )
 
+var (
+	updateCheckOnly bool
+	updateChannel   string
+)
+
 var updateCmd = &cobra.Command{
 	Use:   "update",
 	Short: "Update vget to the latest version",
+	Long: `Update vget to the latest version.
+
+The release channel comes from --channel, or update_channel in config (default stable).
+The beta channel also includes prereleases.
+
+Examples:
+  vget update
+  vget update --check
+  vget update --channel beta`,
 	RunE: func(cmd *cobra.Command, args []string) error {
-		return updater.Update()
+		channel := updateChannel
+		if channel == "" {
+			channel = config.LoadOrDefault().UpdateChannel
+		}
+
+		if updateCheckOnly {
+			return runUpdateCheck(channel)
+		}
+		return updater.UpdateChannel(channel)
 	},
 }
 
+func runUpdateCheck(channel string) error {
+	info, err := updater.Check(context.Background(), channel)
+	if err != nil {
+		return err
+	}
+
+	if !info.Available {
+		fmt.Printf("Already up to date (v%s, %s channel)\n", info.CurrentVersion, info.Channel)
+		return nil
+	}
+
+	fmt.Printf("Update available on %s channel: v%s → v%s\n", info.Channel, info.CurrentVersion, info.LatestVersion)
+	if !info.PublishedAt.IsZero() {
+		fmt.Printf("Released %s\n", info.PublishedAt.Format("2006-01-02"))
+	}
+	if notes := strings.TrimSpace(info.Notes); notes != "" {
+		fmt.Printf("\n%s\n", notes)
+	}
+	fmt.Println("\nRun 'vget update' to install.")
+	return nil
+}
+
 func init() {
+	updateCmd.Flags().BoolVar(&updateCheckOnly, "check", false, "only check for a new version and show the changelog")
+	updateCmd.Flags().StringVar(&updateChannel, "channel", "", "release channel: stable or beta (default from config)")
 	rootCmd.AddCommand(updateCmd)
 }
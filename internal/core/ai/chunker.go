@@ -12,6 +12,7 @@ import (
 	"time"
 
 	"github.com/guiyumin/vget/internal/core/ai/transcriber"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
 )
 
 const (
@@ -91,8 +92,7 @@ func NewChunkerWithOptions(opts ChunkOptions) *Chunker {
 
 // HasFFmpeg checks if ffmpeg is available.
 func (c *Chunker) HasFFmpeg() bool {
-	_, err := exec.LookPath("ffmpeg")
-	return err == nil
+	return ffmpeg.Available()
 }
 
 // NeedsChunking checks if a file needs to be chunked.
@@ -265,7 +265,7 @@ func (c *Chunker) SplitWithManifest(filePath string) ([]ChunkInfo, *Manifest, er
 // extractFullAudio extracts the complete audio track from a video file.
 // Saves as MP3 with settings optimized for speech transcription.
 func (c *Chunker) extractFullAudio(videoPath, audioPath string) error {
-	cmd := exec.Command("ffmpeg",
+	cmd := exec.Command(ffmpeg.Binary(),
 		"-threads", "1",
 		"-y",
 		"-i", videoPath,
@@ -328,7 +328,7 @@ func LoadManifest(chunksDir string) (*Manifest, error) {
 
 // getAudioDuration gets the duration of an audio file using ffprobe.
 func (c *Chunker) getAudioDuration(filePath string) (time.Duration, error) {
-	cmd := exec.Command("ffprobe",
+	cmd := exec.Command(ffmpeg.ProbeBinary(),
 		"-v", "error",
 		"-show_entries", "format=duration",
 		"-of", "default=noprint_wrappers=1:nokey=1",
@@ -356,7 +356,7 @@ func (c *Chunker) extractChunk(input, output string, start, duration time.Durati
 	// Change output extension to .mp3 for consistent encoding
 	output = strings.TrimSuffix(output, filepath.Ext(output)) + ".mp3"
 
-	cmd := exec.Command("ffmpeg",
+	cmd := exec.Command(ffmpeg.Binary(),
 		"-threads", "1",
 		"-y",
 		"-ss", formatDuration(start),
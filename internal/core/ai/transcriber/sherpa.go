@@ -13,6 +13,7 @@ import (
 
 	"github.com/go-audio/wav"
 	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
 	sherpa "github.com/k2-fsa/sherpa-onnx-go/sherpa_onnx"
 )
 
@@ -188,7 +189,7 @@ func (s *SherpaTranscriber) ensureWAV(filePath string) (string, func(), error) {
 	tmpFile.Close()
 
 	// ffmpeg command to convert to 16kHz mono WAV
-	cmd := exec.Command("ffmpeg",
+	cmd := exec.Command(ffmpeg.Binary(),
 		"-i", filePath,
 		"-ar", "16000",
 		"-ac", "1",
@@ -0,0 +1,201 @@
+// Package archive packages the files of a completed multi-file download
+// (image galleries, video with subtitles and thumbnail) into a single zip.
+package archive
+
+import (
+	"archive/zip"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+)
+
+// DefaultNameTemplate is used when no archive name template is configured
+const DefaultNameTemplate = "{title}.zip"
+
+// ErrOriginalsKept is returned by Zip when the archive was written but some of
+// the original files could not be removed
+var ErrOriginalsKept = errors.New("archive written, but originals could not be removed")
+
+// NameVars are the values available to an archive name template.
+// Values should already be sanitized for use in filenames.
+type NameVars struct {
+	Title    string
+	ID       string
+	Uploader string
+	Count    int
+}
+
+// ExpandName fills in a name template. Supported placeholders:
+// {title}, {id}, {uploader}, {date} (YYYY-MM-DD), {count}.
+// The result always ends in ".zip"; an empty title falls back to the ID.
+func ExpandName(template string, vars NameVars) string {
+	if template == "" {
+		template = DefaultNameTemplate
+	}
+
+	title := vars.Title
+	if title == "" {
+		title = vars.ID
+	}
+
+	name := strings.NewReplacer(
+		"{title}", title,
+		"{id}", vars.ID,
+		"{uploader}", vars.Uploader,
+		"{date}", time.Now().Format("2006-01-02"),
+		"{count}", fmt.Sprintf("%d", vars.Count),
+	).Replace(template)
+
+	name = strings.TrimSpace(name)
+	if name == "" || name == ".zip" {
+		name = "archive.zip"
+	}
+	if !strings.HasSuffix(strings.ToLower(name), ".zip") {
+		name += ".zip"
+	}
+	return name
+}
+
+// Package zips the files of a finished download into dir, named by the
+// configured template. Files renamed since download (e.g., by magic-byte
+// detection) are picked up by base name, and an existing archive of the same
+// name is never overwritten. Returns the archive path, which is also set when
+// the error is ErrOriginalsKept.
+func Package(files []string, dir string, cfg config.ArchiveConfig, vars NameVars) (string, error) {
+	var existing []string
+	for _, f := range files {
+		if _, err := os.Stat(f); err == nil {
+			existing = append(existing, f)
+		} else if renamed := findRenamed(f); renamed != "" {
+			existing = append(existing, renamed)
+		}
+	}
+
+	vars.Count = len(existing)
+	dest := downloader.UniquePath(filepath.Join(dir, ExpandName(cfg.NameTemplate, vars)))
+	if err := Zip(existing, dest, cfg.DeleteOriginals); err != nil {
+		if errors.Is(err, ErrOriginalsKept) {
+			return dest, err
+		}
+		return "", err
+	}
+	return dest, nil
+}
+
+// PackageVideo zips a finished video with its subtitle sidecars and its
+// thumbnail, which is saved next to the video first. A thumbnail that can't be
+// fetched is left out. Returns "" without an error when the video has nothing
+// to be packaged with.
+func PackageVideo(video string, subtitles []string, thumbnailURL, dir string, cfg config.ArchiveConfig, vars NameVars) (string, error) {
+	files := append([]string{video}, subtitles...)
+	if thumbnailURL != "" {
+		if thumb, err := downloader.SaveThumbnail(thumbnailURL, video); err == nil {
+			files = append(files, thumb)
+		}
+	}
+	if len(files) == 1 {
+		return "", nil
+	}
+	return Package(files, dir, cfg, vars)
+}
+
+// Zip writes files into a new zip archive at dest. Entries are stored flat by base name.
+// If deleteOriginals is true, source files are removed after the archive is complete;
+// failing to remove one returns ErrOriginalsKept, with the archive left in place.
+func Zip(files []string, dest string, deleteOriginals bool) error {
+	if len(files) == 0 {
+		return fmt.Errorf("no files to archive")
+	}
+
+	// Write to a temp file first so a failed run never leaves a truncated archive
+	tmpPath := dest + ".part"
+	out, err := os.Create(tmpPath)
+	if err != nil {
+		return fmt.Errorf("failed to create archive: %w", err)
+	}
+
+	zw := zip.NewWriter(out)
+	for _, file := range files {
+		if err := addFile(zw, file); err != nil {
+			zw.Close()
+			out.Close()
+			os.Remove(tmpPath)
+			return err
+		}
+	}
+
+	if err := zw.Close(); err != nil {
+		out.Close()
+		os.Remove(tmpPath)
+		return fmt.Errorf("failed to finalize archive: %w", err)
+	}
+	if err := out.Close(); err != nil {
+		os.Remove(tmpPath)
+		return fmt.Errorf("failed to write archive: %w", err)
+	}
+	if err := os.Rename(tmpPath, dest); err != nil {
+		os.Remove(tmpPath)
+		return fmt.Errorf("failed to move archive into place: %w", err)
+	}
+
+	if deleteOriginals {
+		var errs []error
+		for _, file := range files {
+			if err := os.Remove(file); err != nil {
+				errs = append(errs, err)
+			}
+		}
+		if len(errs) > 0 {
+			return fmt.Errorf("%w: %w", ErrOriginalsKept, errors.Join(errs...))
+		}
+	}
+
+	return nil
+}
+
+func addFile(zw *zip.Writer, path string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return fmt.Errorf("failed to open %s: %w", path, err)
+	}
+	defer f.Close()
+
+	info, err := f.Stat()
+	if err != nil {
+		return fmt.Errorf("failed to stat %s: %w", path, err)
+	}
+
+	header, err := zip.FileInfoHeader(info)
+	if err != nil {
+		return fmt.Errorf("failed to create zip header for %s: %w", path, err)
+	}
+	header.Name = filepath.Base(path)
+	// Media is already compressed; storing avoids burning CPU for no gain
+	header.Method = zip.Store
+
+	w, err := zw.CreateHeader(header)
+	if err != nil {
+		return fmt.Errorf("failed to add %s: %w", path, err)
+	}
+	if _, err := io.Copy(w, f); err != nil {
+		return fmt.Errorf("failed to write %s: %w", path, err)
+	}
+	return nil
+}
+
+// findRenamed looks for a file with the same base name but a different extension
+func findRenamed(path string) string {
+	base := strings.TrimSuffix(path, filepath.Ext(path))
+	matches, _ := filepath.Glob(base + ".*")
+	if len(matches) == 1 {
+		return matches[0]
+	}
+	return ""
+}
@@ -0,0 +1,355 @@
+// Package auth keeps the logins of the sites vget downloads from, one record
+// per site ID holding its cookies, tokens, and the user agent they were issued
+// to. Records live in the credential store (see config.GetCredential).
+package auth
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"regexp"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// IDs of the sites with built-in login support
+const (
+	Bilibili    = "bilibili"
+	Xiaohongshu = "xiaohongshu"
+	Twitter     = "twitter"
+	YouTube     = "youtube"
+)
+
+// KnownSites are the sites with built-in login support. Any domain can hold a
+// login too, e.g. "example.com".
+var KnownSites = []string{Bilibili, Xiaohongshu, Twitter, YouTube}
+
+// siteAliases maps other names of known sites to their ID
+var siteAliases = map[string]string{
+	"xhs": Xiaohongshu,
+	"x":   Twitter,
+	"yt":  YouTube,
+}
+
+var domainRegex = regexp.MustCompile(`^[a-z0-9-]+(\.[a-z0-9-]+)+$`)
+
+// SiteID normalizes a site name to its ID: one of KnownSites, or a domain
+func SiteID(name string) (string, error) {
+	site := strings.ToLower(strings.TrimSpace(name))
+	if alias, ok := siteAliases[site]; ok {
+		site = alias
+	}
+	if slices.Contains(KnownSites, site) || domainRegex.MatchString(site) {
+		return site, nil
+	}
+	return "", fmt.Errorf("unknown site: %s (use %s, or a domain)", name, strings.Join(KnownSites, ", "))
+}
+
+// Cookie is a stored cookie
+type Cookie struct {
+	Name     string    `json:"name"`
+	Value    string    `json:"value"`
+	Domain   string    `json:"domain,omitempty"`
+	Path     string    `json:"path,omitempty"`
+	Expires  time.Time `json:"expires,omitzero"` // zero for session cookies
+	HTTPOnly bool      `json:"http_only,omitempty"`
+	Secure   bool      `json:"secure,omitempty"`
+}
+
+// expired reports whether c has expired by now
+func (c Cookie) expired(now time.Time) bool {
+	return !c.Expires.IsZero() && c.Expires.Before(now)
+}
+
+// Login is the stored login of a site
+type Login struct {
+	Site      string            `json:"site"`
+	Cookies   []Cookie          `json:"cookies,omitempty"`
+	Tokens    map[string]string `json:"tokens,omitempty"`     // e.g. Twitter's auth_token
+	UserAgent string            `json:"user_agent,omitempty"` // the browser the cookies were issued to
+	Expires   time.Time         `json:"expires,omitzero"`     // when the login lapses, if the site said
+	UpdatedAt time.Time         `json:"updated_at"`
+}
+
+// Empty reports whether l holds no cookies or tokens
+func (l *Login) Empty() bool {
+	return len(l.Cookies) == 0 && len(l.Tokens) == 0
+}
+
+// Expired reports whether the login has lapsed
+func (l *Login) Expired() bool {
+	return !l.Expires.IsZero() && l.Expires.Before(time.Now())
+}
+
+// LoggedIn reports whether l holds a login that hasn't lapsed
+func (l *Login) LoggedIn() bool {
+	return !l.Empty() && !l.Expired()
+}
+
+// CookieHeader joins the cookies that haven't expired into a Cookie header
+func (l *Login) CookieHeader() string {
+	now := time.Now()
+	var parts []string
+	for _, c := range l.Cookies {
+		if !c.expired(now) {
+			parts = append(parts, c.Name+"="+c.Value)
+		}
+	}
+	return strings.Join(parts, "; ")
+}
+
+// Cookie returns the value of the named cookie, or ""
+func (l *Login) Cookie(name string) string {
+	for _, c := range l.Cookies {
+		if c.Name == name {
+			return c.Value
+		}
+	}
+	return ""
+}
+
+// UpdateCookies replaces the cookies of l named like those given, and adds
+// the rest, as a browser takes the cookies of a response. It reports whether
+// any changed.
+func (l *Login) UpdateCookies(cookies []Cookie) bool {
+	changed := false
+	for _, c := range cookies {
+		i := slices.IndexFunc(l.Cookies, func(old Cookie) bool { return old.Name == c.Name })
+		switch {
+		case i < 0:
+			l.Cookies = append(l.Cookies, c)
+		case l.Cookies[i].Value != c.Value || !l.Cookies[i].Expires.Equal(c.Expires):
+			l.Cookies[i] = c
+		default:
+			continue
+		}
+		changed = true
+	}
+	return changed
+}
+
+// ParseCookieHeader splits a Cookie header ("a=1; b=2") into cookies
+func ParseCookieHeader(header string) []Cookie {
+	var cookies []Cookie
+	for part := range strings.SplitSeq(header, ";") {
+		name, value, ok := strings.Cut(strings.TrimSpace(part), "=")
+		if name = strings.TrimSpace(name); ok && name != "" {
+			cookies = append(cookies, Cookie{Name: name, Value: strings.TrimSpace(value)})
+		}
+	}
+	return cookies
+}
+
+// Credential names in the store: a record per site, plus the list of sites
+// with one, which the store can't enumerate itself
+const (
+	credentialPrefix = "auth."
+	sitesCredential  = "auth.sites"
+)
+
+// mu serializes changes to the site list
+var mu sync.Mutex
+
+var (
+	watchersMu sync.Mutex
+	watchers   []func(site string)
+)
+
+// OnChange registers fn to be called with the site ID after every login
+// saved or removed by this process, including profile switches. Changes made
+// by other processes, and logins expiring, aren't reported.
+func OnChange(fn func(site string)) {
+	watchersMu.Lock()
+	defer watchersMu.Unlock()
+	watchers = append(watchers, fn)
+}
+
+// notify calls the OnChange functions for site
+func notify(site string) {
+	watchersMu.Lock()
+	fns := slices.Clone(watchers)
+	watchersMu.Unlock()
+	for _, fn := range fns {
+		fn(site)
+	}
+}
+
+// Get returns the stored login of site, empty when there is none. The error
+// reports an unknown site or an unreadable store; the login is still usable.
+func Get(site string) (*Login, error) {
+	id, err := SiteID(site)
+	if err != nil {
+		return &Login{Site: site}, err
+	}
+	data, err := config.GetCredential(credentialPrefix + id)
+	if errors.Is(err, config.ErrCredentialNotFound) {
+		return migrateLegacy(id)
+	}
+	if err != nil {
+		return &Login{Site: id}, err
+	}
+
+	login := &Login{}
+	if err := json.Unmarshal([]byte(data), login); err != nil {
+		return &Login{Site: id}, fmt.Errorf("stored login of %s is corrupt: %w", id, err)
+	}
+	login.Site = id
+	return login, nil
+}
+
+// Save stores login under its site, replacing the previous one. An empty
+// login is a logout.
+func Save(login *Login) error {
+	id, err := SiteID(login.Site)
+	if err != nil {
+		return err
+	}
+	if login.Empty() {
+		return Logout(id)
+	}
+	login.Site = id
+	login.UpdatedAt = time.Now()
+	data, err := json.Marshal(login)
+	if err != nil {
+		return err
+	}
+	if err := config.SetCredential(credentialPrefix+id, string(data)); err != nil {
+		return err
+	}
+	defer notify(id)
+	return updateSites(func(sites []string) []string {
+		if slices.Contains(sites, id) {
+			return sites
+		}
+		return append(sites, id)
+	})
+}
+
+// SetCookie replaces the cookies of site with those of a Cookie header,
+// keeping its tokens and user agent
+func SetCookie(site, header string) error {
+	login, err := Get(site)
+	if err != nil {
+		return err
+	}
+	login.Cookies = ParseCookieHeader(header)
+	login.Expires = time.Time{}
+	return Save(login)
+}
+
+// SetToken sets a token of site; an empty value removes it
+func SetToken(site, name, value string) error {
+	login, err := Get(site)
+	if err != nil {
+		return err
+	}
+	if value == "" {
+		delete(login.Tokens, name)
+	} else {
+		if login.Tokens == nil {
+			login.Tokens = make(map[string]string)
+		}
+		login.Tokens[name] = value
+	}
+	return Save(login)
+}
+
+// Logout removes the stored login of site, that of its active profile
+func Logout(site string) error {
+	id, err := SiteID(site)
+	if err != nil {
+		return err
+	}
+	if err := config.DeleteCredential(credentialPrefix + id); err != nil {
+		return err
+	}
+	defer notify(id)
+	return updateSites(func(sites []string) []string {
+		return slices.DeleteFunc(sites, func(s string) bool { return s == id })
+	})
+}
+
+// Sites lists the sites with a stored login
+func Sites() []string {
+	var sites []string
+	if data, err := config.GetCredential(sitesCredential); err == nil {
+		json.Unmarshal([]byte(data), &sites)
+	}
+	return sites
+}
+
+// updateSites rewrites the list of sites with a stored login
+func updateSites(update func([]string) []string) error {
+	mu.Lock()
+	defer mu.Unlock()
+	sites := update(Sites())
+	if len(sites) == 0 {
+		return config.DeleteCredential(sitesCredential)
+	}
+	data, err := json.Marshal(sites)
+	if err != nil {
+		return err
+	}
+	return config.SetCredential(sitesCredential, string(data))
+}
+
+// Status sums up the login of a site
+type Status struct {
+	Site      string    `json:"site"`
+	LoggedIn  bool      `json:"logged_in"`
+	Expired   bool      `json:"expired,omitempty"`
+	Expires   time.Time `json:"expires,omitzero"`
+	Cookies   int       `json:"cookies"`
+	Tokens    []string  `json:"tokens,omitempty"` // names only
+	UserAgent string    `json:"user_agent,omitempty"`
+	UpdatedAt time.Time `json:"updated_at,omitzero"`
+	Profile   string    `json:"profile"`            // the active profile
+	Profiles  []string  `json:"profiles,omitempty"` // all profiles, with the active one
+}
+
+// GetStatus reports whether vget is logged in to site
+func GetStatus(site string) (Status, error) {
+	login, err := Get(site)
+	if err != nil {
+		return Status{}, err
+	}
+	status := Status{
+		Site:      login.Site,
+		LoggedIn:  login.LoggedIn(),
+		Expired:   !login.Empty() && login.Expired(),
+		Expires:   login.Expires,
+		Cookies:   len(login.Cookies),
+		UserAgent: login.UserAgent,
+		UpdatedAt: login.UpdatedAt,
+	}
+	for name := range login.Tokens {
+		status.Tokens = append(status.Tokens, name)
+	}
+	slices.Sort(status.Tokens)
+	status.Profiles, status.Profile, err = Profiles(login.Site)
+	return status, err
+}
+
+// Statuses reports the login status of the known sites and every other site
+// with a stored login
+func Statuses() []Status {
+	sites := slices.Clone(KnownSites)
+	for _, site := range Sites() {
+		if !slices.Contains(sites, site) {
+			sites = append(sites, site)
+		}
+	}
+	statuses := make([]Status, 0, len(sites))
+	for _, site := range sites {
+		status, err := GetStatus(site)
+		if err != nil {
+			status = Status{Site: site}
+		}
+		statuses = append(statuses, status)
+	}
+	return statuses
+}
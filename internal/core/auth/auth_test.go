@@ -0,0 +1,74 @@
+package auth
+
+import (
+	"testing"
+	"time"
+)
+
+func TestSiteID(t *testing.T) {
+	tests := []struct {
+		name string
+		want string
+	}{
+		{"Bilibili", Bilibili},
+		{" xhs ", Xiaohongshu},
+		{"x", Twitter},
+		{"YT", YouTube},
+		{"Example.com", "example.com"},
+	}
+	for _, tt := range tests {
+		if got, err := SiteID(tt.name); err != nil || got != tt.want {
+			t.Errorf("SiteID(%q) = %q, %v, want %q", tt.name, got, err, tt.want)
+		}
+	}
+	for _, name := range []string{"", "vimeo", "auth.sites/..", "a b.com"} {
+		if _, err := SiteID(name); err == nil {
+			t.Errorf("SiteID(%q) succeeded", name)
+		}
+	}
+}
+
+func TestCookieHeader(t *testing.T) {
+	cookies := ParseCookieHeader(" SESSDATA=abc; bili_jct = def ;; broken; DedeUserID=1")
+	if len(cookies) != 3 || cookies[1].Name != "bili_jct" || cookies[1].Value != "def" {
+		t.Fatalf("ParseCookieHeader() = %+v", cookies)
+	}
+
+	cookies[2].Expires = time.Now().Add(-time.Hour)
+	login := &Login{Cookies: cookies}
+	if got, want := login.CookieHeader(), "SESSDATA=abc; bili_jct=def"; got != want {
+		t.Errorf("CookieHeader() = %q, want %q", got, want)
+	}
+	if !login.LoggedIn() {
+		t.Error("LoggedIn() = false with cookies and no expiry")
+	}
+	login.Expires = time.Now().Add(-time.Minute)
+	if login.LoggedIn() || !login.Expired() {
+		t.Error("login past its expiry still counts as logged in")
+	}
+}
+
+func TestParseBrowserCookies(t *testing.T) {
+	data := `[{"name":"a1","value":"x","domain":".xiaohongshu.com","path":"/","expires":1893456000,"httpOnly":true},
+		{"name":"web_session","value":"y","domain":".xiaohongshu.com","expires":-1}]`
+	cookies := parseBrowserCookies(data)
+	if len(cookies) != 2 || !cookies[0].HTTPOnly || cookies[0].Expires.Unix() != 1893456000 || !cookies[1].Expires.IsZero() {
+		t.Errorf("parseBrowserCookies() = %+v", cookies)
+	}
+	if parseBrowserCookies("not json") != nil {
+		t.Error("parseBrowserCookies() of invalid JSON returned cookies")
+	}
+}
+
+func TestUpdateCookies(t *testing.T) {
+	login := &Login{Cookies: ParseCookieHeader("SESSDATA=old; bili_jct=j")}
+	if login.UpdateCookies(ParseCookieHeader("bili_jct=j")) {
+		t.Error("UpdateCookies() reported a change for the same cookie")
+	}
+	if !login.UpdateCookies(ParseCookieHeader("SESSDATA=new; buvid3=b")) {
+		t.Error("UpdateCookies() reported no change")
+	}
+	if got, want := login.CookieHeader(), "SESSDATA=new; bili_jct=j; buvid3=b"; got != want {
+		t.Errorf("CookieHeader() = %q, want %q", got, want)
+	}
+}
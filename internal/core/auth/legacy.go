@@ -0,0 +1,98 @@
+package auth
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// Where older versions kept logins: credentials moved out of config.yml by
+// config.Load, and the Xiaohongshu cookies, before that in xhs_cookies.json
+const (
+	legacyBilibiliCookie  = "bilibili.cookie"
+	legacyTwitterToken    = "twitter.auth_token"
+	legacyXiaohongshu     = "xiaohongshu.cookies"
+	legacyXiaohongshuFile = "xhs_cookies.json"
+)
+
+// browserCookie is a cookie as the browser automation saved it
+type browserCookie struct {
+	Name     string  `json:"name"`
+	Value    string  `json:"value"`
+	Domain   string  `json:"domain"`
+	Path     string  `json:"path"`
+	Expires  float64 `json:"expires"` // Unix seconds; -1 or 0 for session cookies
+	HTTPOnly bool    `json:"httpOnly"`
+	Secure   bool    `json:"secure"`
+}
+
+// migrateLegacy moves the login of site from where older versions kept it
+// into its record, and returns it; empty when there was none
+func migrateLegacy(site string) (*Login, error) {
+	login := &Login{Site: site}
+	var cleanup func()
+
+	switch site {
+	case Bilibili:
+		if cookie, err := config.GetCredential(legacyBilibiliCookie); err == nil {
+			login.Cookies = ParseCookieHeader(cookie)
+			cleanup = func() { config.DeleteCredential(legacyBilibiliCookie) }
+		}
+	case Twitter:
+		if token, err := config.GetCredential(legacyTwitterToken); err == nil {
+			login.Tokens = map[string]string{"auth_token": token}
+			cleanup = func() { config.DeleteCredential(legacyTwitterToken) }
+		}
+	case Xiaohongshu:
+		data, err := config.GetCredential(legacyXiaohongshu)
+		cleanup = func() { config.DeleteCredential(legacyXiaohongshu) }
+		if errors.Is(err, config.ErrCredentialNotFound) {
+			data, cleanup = readXiaohongshuFile()
+		}
+		login.Cookies = parseBrowserCookies(data)
+	}
+
+	if login.Empty() {
+		return login, nil
+	}
+	if err := Save(login); err != nil {
+		return login, err
+	}
+	cleanup()
+	return login, nil
+}
+
+// readXiaohongshuFile reads xhs_cookies.json, returning a func removing it
+func readXiaohongshuFile() (string, func()) {
+	configDir, err := config.ConfigDir()
+	if err != nil {
+		return "", nil
+	}
+	path := filepath.Join(configDir, legacyXiaohongshuFile)
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return "", nil
+	}
+	return string(data), func() { os.Remove(path) }
+}
+
+// parseBrowserCookies converts cookies saved by the browser automation
+func parseBrowserCookies(data string) []Cookie {
+	var saved []browserCookie
+	if data == "" || json.Unmarshal([]byte(data), &saved) != nil {
+		return nil
+	}
+	cookies := make([]Cookie, 0, len(saved))
+	for _, c := range saved {
+		cookie := Cookie{Name: c.Name, Value: c.Value, Domain: c.Domain, Path: c.Path, HTTPOnly: c.HTTPOnly, Secure: c.Secure}
+		if c.Expires > 0 {
+			cookie.Expires = time.Unix(int64(c.Expires), 0)
+		}
+		cookies = append(cookies, cookie)
+	}
+	return cookies
+}
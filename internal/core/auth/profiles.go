@@ -0,0 +1,135 @@
+package auth
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"regexp"
+	"slices"
+	"sync"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// DefaultProfile names the login of a site before another profile is added
+const DefaultProfile = "default"
+
+// profilesPrefix starts the credential name of a site's profiles record; no
+// site ID contains its ':'
+const profilesPrefix = "auth.profiles:"
+
+var profileRegex = regexp.MustCompile(`^[A-Za-z0-9_-]{1,32}$`)
+
+// ErrInvalidProfile is returned by SwitchProfile for a malformed profile name
+var ErrInvalidProfile = errors.New("invalid profile name: use up to 32 letters, digits, '-' and '_'")
+
+// profilesMu serializes profile switches
+var profilesMu sync.Mutex
+
+// profiles is the record of a site's profiles. The login of the active one
+// is the site's record, which Get returns; the others wait here.
+type profiles struct {
+	Active string            `json:"active"`
+	Saved  map[string]*Login `json:"saved,omitempty"`
+}
+
+// readProfiles returns the profiles record of site, which starts out with
+// just the default profile
+func readProfiles(site string) (*profiles, error) {
+	record := &profiles{Active: DefaultProfile}
+	data, err := config.GetCredential(profilesPrefix + site)
+	if errors.Is(err, config.ErrCredentialNotFound) {
+		record.Saved = make(map[string]*Login)
+		return record, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	if err := json.Unmarshal([]byte(data), record); err != nil {
+		return nil, fmt.Errorf("stored profiles of %s are corrupt: %w", site, err)
+	}
+	if record.Saved == nil {
+		record.Saved = make(map[string]*Login)
+	}
+	return record, nil
+}
+
+// writeProfiles stores the profiles record of site, dropping it once only the
+// default profile is left
+func writeProfiles(site string, record *profiles) error {
+	if record.Active == DefaultProfile && len(record.Saved) == 0 {
+		return config.DeleteCredential(profilesPrefix + site)
+	}
+	data, err := json.Marshal(record)
+	if err != nil {
+		return err
+	}
+	return config.SetCredential(profilesPrefix+site, string(data))
+}
+
+// Profiles lists the profiles of site, sorted, and names the active one
+func Profiles(site string) (names []string, active string, err error) {
+	id, err := SiteID(site)
+	if err != nil {
+		return nil, "", err
+	}
+	record, err := readProfiles(id)
+	if err != nil {
+		return nil, "", err
+	}
+	names = []string{record.Active}
+	for name := range record.Saved {
+		names = append(names, name)
+	}
+	slices.Sort(names)
+	return names, record.Active, nil
+}
+
+// SwitchProfile makes profile the active login of site, which extraction and
+// downloads then use. The login of the profile switched away from is kept.
+// Switching to a profile that doesn't exist yet starts it logged out, so the
+// next login of site is saved to it.
+func SwitchProfile(site, profile string) error {
+	id, err := SiteID(site)
+	if err != nil {
+		return err
+	}
+	if !profileRegex.MatchString(profile) {
+		return fmt.Errorf("%w: %q", ErrInvalidProfile, profile)
+	}
+
+	profilesMu.Lock()
+	defer profilesMu.Unlock()
+	record, err := readProfiles(id)
+	if err != nil {
+		return err
+	}
+	if record.Active == profile {
+		return nil
+	}
+	current, err := Get(id)
+	if err != nil {
+		return err
+	}
+
+	// Record the switch with both logins saved first, so a failure midway
+	// loses neither
+	if !current.Empty() {
+		record.Saved[record.Active] = current
+	}
+	next := record.Saved[profile]
+	record.Active = profile
+	if err := writeProfiles(id, record); err != nil {
+		return err
+	}
+	if next == nil {
+		err = Logout(id)
+	} else {
+		err = Save(next)
+	}
+	if err != nil {
+		return err
+	}
+	delete(record.Saved, profile)
+	return writeProfiles(id, record)
+}
@@ -0,0 +1,225 @@
+// Package bandwidth tracks bytes downloaded per day so usage can be reported
+// per month and capped on metered connections.
+package bandwidth
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+const (
+	// FileName is the usage file inside the config directory
+	FileName = "bandwidth.json"
+
+	// ReasonMonthlyCap is the queue pause reason when the monthly cap is reached
+	ReasonMonthlyCap = "monthly_cap_reached"
+
+	// flushInterval limits how often usage is written to disk
+	flushInterval = 10 * time.Second
+
+	// keepDays is how long daily usage is retained
+	keepDays = 400
+
+	dayLayout   = "2006-01-02"
+	monthLayout = "2006-01"
+)
+
+// DayUsage is the bytes downloaded on one day
+type DayUsage struct {
+	Date  string `json:"date"` // YYYY-MM-DD
+	Bytes int64  `json:"bytes"`
+}
+
+// MonthUsage is the bytes downloaded in one month
+type MonthUsage struct {
+	Month string `json:"month"` // YYYY-MM
+	Bytes int64  `json:"bytes"`
+}
+
+// Report summarizes bandwidth usage
+type Report struct {
+	Today      int64        `json:"today"`
+	ThisMonth  int64        `json:"this_month"`
+	MonthlyCap int64        `json:"monthly_cap,omitempty"` // bytes, 0 if no cap
+	Remaining  int64        `json:"remaining,omitempty"`   // bytes left under the cap
+	Days       []DayUsage   `json:"days"`                  // this month, oldest first
+	Months     []MonthUsage `json:"months"`                // newest first
+}
+
+// Tracker accumulates usage and persists it to disk
+type Tracker struct {
+	path      string
+	mu        sync.Mutex
+	days      map[string]int64
+	dirty     bool
+	lastFlush time.Time
+}
+
+// Open loads the tracker for the default usage file
+func Open() (*Tracker, error) {
+	dir, err := config.ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+
+	t := &Tracker{
+		path: filepath.Join(dir, FileName),
+		days: make(map[string]int64),
+	}
+
+	data, err := os.ReadFile(t.path)
+	if err != nil && !os.IsNotExist(err) {
+		return nil, err
+	}
+	if len(data) > 0 {
+		if err := json.Unmarshal(data, &t.days); err != nil {
+			return nil, fmt.Errorf("failed to parse %s: %w", t.path, err)
+		}
+	}
+	return t, nil
+}
+
+// Add records n bytes downloaded now
+func (t *Tracker) Add(n int64) {
+	if n <= 0 {
+		return
+	}
+
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	t.days[time.Now().Format(dayLayout)] += n
+	t.dirty = true
+	if time.Since(t.lastFlush) >= flushInterval {
+		t.flushLocked()
+	}
+}
+
+// Flush writes pending usage to disk
+func (t *Tracker) Flush() error {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.flushLocked()
+}
+
+func (t *Tracker) flushLocked() error {
+	t.lastFlush = time.Now()
+	if !t.dirty {
+		return nil
+	}
+
+	// Drop days older than the retention window
+	cutoff := time.Now().AddDate(0, 0, -keepDays).Format(dayLayout)
+	for day := range t.days {
+		if day < cutoff {
+			delete(t.days, day)
+		}
+	}
+
+	if err := os.MkdirAll(filepath.Dir(t.path), 0755); err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(t.days, "", "  ")
+	if err != nil {
+		return err
+	}
+	tmp := t.path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0644); err != nil {
+		return err
+	}
+	if err := os.Rename(tmp, t.path); err != nil {
+		return err
+	}
+	t.dirty = false
+	return nil
+}
+
+// Month returns the bytes downloaded in the month containing now
+func (t *Tracker) Month(now time.Time) int64 {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	prefix := now.Format(monthLayout)
+	var total int64
+	for day, n := range t.days {
+		if strings.HasPrefix(day, prefix) {
+			total += n
+		}
+	}
+	return total
+}
+
+// Since returns the bytes downloaded from the day of start on
+func (t *Tracker) Since(start time.Time) int64 {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	first := start.Format(dayLayout)
+	var total int64
+	for day, n := range t.days {
+		if day >= first {
+			total += n
+		}
+	}
+	return total
+}
+
+// CapReached reports whether this month's usage has reached capBytes (0 means no cap)
+func (t *Tracker) CapReached(capBytes int64, now time.Time) bool {
+	return capBytes > 0 && t.Month(now) >= capBytes
+}
+
+// Report summarizes usage as of now against capBytes (0 means no cap)
+func (t *Tracker) Report(capBytes int64, now time.Time) *Report {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	today := now.Format(dayLayout)
+	thisMonth := now.Format(monthLayout)
+
+	r := &Report{
+		Today:      t.days[today],
+		MonthlyCap: capBytes,
+		Days:       []DayUsage{},
+		Months:     []MonthUsage{},
+	}
+
+	months := make(map[string]int64)
+	for day, n := range t.days {
+		if len(day) < len(monthLayout) {
+			continue
+		}
+		months[day[:len(monthLayout)]] += n
+		if strings.HasPrefix(day, thisMonth) {
+			r.Days = append(r.Days, DayUsage{Date: day, Bytes: n})
+		}
+	}
+	r.ThisMonth = months[thisMonth]
+
+	for month, n := range months {
+		r.Months = append(r.Months, MonthUsage{Month: month, Bytes: n})
+	}
+	sort.Slice(r.Days, func(i, j int) bool { return r.Days[i].Date < r.Days[j].Date })
+	sort.Slice(r.Months, func(i, j int) bool { return r.Months[i].Month > r.Months[j].Month })
+
+	if capBytes > 0 && r.ThisMonth < capBytes {
+		r.Remaining = capBytes - r.ThisMonth
+	}
+	return r
+}
+
+// CapBytes converts the configured monthly cap in GB to bytes
+func CapBytes(cfg config.BandwidthConfig) int64 {
+	if cfg.MonthlyCapGB <= 0 {
+		return 0
+	}
+	return int64(cfg.MonthlyCapGB * 1024 * 1024 * 1024)
+}
@@ -6,6 +6,8 @@ import (
 	"path/filepath"
 	"runtime"
 	"strings"
+	"sync"
+	"time"
 
 	"gopkg.in/yaml.v3"
 )
@@ -59,9 +61,6 @@ type Config struct {
 	// WebDAV servers configuration
 	WebDAVServers map[string]WebDAVServer `yaml:"webdavServers,omitempty"`
 
-	// Twitter/X configuration
-	Twitter TwitterConfig `yaml:"twitter,omitempty"`
-
 	// Server configuration for `vget serve`
 	Server ServerConfig `yaml:"server,omitempty"`
 
@@ -79,17 +78,285 @@ type Config struct {
 	// Torrent client configuration for dispatching magnet links
 	Torrent TorrentConfig `yaml:"torrent,omitempty"`
 
-	// Bilibili configuration
-	Bilibili BilibiliConfig `yaml:"bilibili,omitempty"`
-
 	// AI transcription and summarization configuration
 	AI AIConfig `yaml:"ai,omitempty"`
+
+	// Schedule policies that pause the server download queue
+	Schedule ScheduleConfig `yaml:"schedule,omitempty"`
+
+	// OnComplete is the action after a download finishes: "none", "open", "reveal", "play"
+	OnComplete string `yaml:"on_complete,omitempty"`
+
+	// UpdateChannel is the release channel for self-update: "stable" (default) or "beta"
+	UpdateChannel string `yaml:"update_channel,omitempty"`
+
+	// Archive packaging for multi-file downloads
+	Archive ArchiveConfig `yaml:"archive,omitempty"`
+
+	// Upload completed downloads to cloud storage
+	Upload UploadConfig `yaml:"upload,omitempty"`
+
+	// Bandwidth accounting for the server download queue
+	Bandwidth BandwidthConfig `yaml:"bandwidth,omitempty"`
+
+	// yt-dlp fallback for sites without a native extractor
+	Ytdlp YtdlpConfig `yaml:"ytdlp,omitempty"`
+
+	// Politeness limits for extraction requests
+	RateLimit RateLimitConfig `yaml:"rate_limit,omitempty"`
+
+	// HTTP client settings shared by the extractors
+	Network NetworkConfig `yaml:"network,omitempty"`
+
+	// Direct file download settings
+	Download DownloadConfig `yaml:"download,omitempty"`
+}
+
+// DownloadConfig controls how direct files are fetched
+type DownloadConfig struct {
+	// Connections is the number of parallel ranges per file (0 = default, 1 = single stream)
+	Connections int `yaml:"connections,omitempty"`
+
+	// OnDuplicate is what to do with media already in the download archive:
+	// "prompt" (default), "skip_existing", or "overwrite"
+	OnDuplicate string `yaml:"on_duplicate,omitempty"`
+
+	// Hook is a shell command run after each completed download, with {path},
+	// {dir}, {filename}, {title}, and {url} replaced by the download's values
+	Hook string `yaml:"hook,omitempty"`
+
+	// Subtitles are the caption languages saved next to downloaded videos,
+	// comma-separated (e.g. "en,zh-Hans") or "all". Empty saves none.
+	Subtitles string `yaml:"subtitles,omitempty"`
+
+	// SiteLimits maps a domain (matching its subdomains too) to how many of its
+	// downloads the server queue runs at once, within the overall limit
+	SiteLimits map[string]int `yaml:"site_limits,omitempty"`
+
+	// AllowSleep lets the computer sleep while the server queue is downloading
+	AllowSleep bool `yaml:"allow_sleep,omitempty"`
+
+	// TempMaxAgeDays is how many days partial files and other leftovers of
+	// interrupted downloads are kept for resuming before cleanup removes them
+	TempMaxAgeDays int `yaml:"temp_max_age_days,omitempty"`
+
+	// WatchDir is a folder the server checks for dropped .url and .txt files,
+	// queueing the links in each. Empty disables it.
+	WatchDir string `yaml:"watch_dir,omitempty"`
+
+	// NormalizeAudio evens out the volume of downloaded audio files, such as
+	// podcast episodes, with two-pass loudness normalization (needs ffmpeg)
+	NormalizeAudio bool `yaml:"normalize_audio,omitempty"`
+
+	// LoudnessTarget is the loudness NormalizeAudio aims for, in LUFS
+	// (0 = -16, the usual level for podcasts)
+	LoudnessTarget float64 `yaml:"loudness_target,omitempty"`
+
+	// TagMetadata writes the title, uploader, date, and description into the
+	// container metadata of downloaded files (needs ffmpeg)
+	TagMetadata bool `yaml:"tag_metadata,omitempty"`
+
+	// MaxConversions is how many server conversions run ffmpeg at once; the
+	// rest wait in a queue (0 = 1)
+	MaxConversions int `yaml:"max_conversions,omitempty"`
+
+	// SplitMinutes splits HLS recordings longer than this into parts of
+	// about this length, copying the streams (0 = keep them whole)
+	SplitMinutes int `yaml:"split_minutes,omitempty"`
 }
 
-// BilibiliConfig holds Bilibili authentication settings
-type BilibiliConfig struct {
-	// Cookie is the full cookie string (SESSDATA, bili_jct, DedeUserID)
-	Cookie string `yaml:"cookie,omitempty"`
+// DefaultDownloadConnections is used when download.connections is unset
+const DefaultDownloadConnections = 4
+
+// MaxDownloadConnections caps download.connections to stay polite to servers
+const MaxDownloadConnections = 32
+
+// EffectiveConnections returns the configured connection count, applying the default
+func (d DownloadConfig) EffectiveConnections() int {
+	if d.Connections <= 0 {
+		return DefaultDownloadConnections
+	}
+	return min(d.Connections, MaxDownloadConnections)
+}
+
+// DefaultTempMaxAgeDays is used when download.temp_max_age_days is unset
+const DefaultTempMaxAgeDays = 7
+
+// TempMaxAge returns how old a leftover must be before cleanup removes it
+func (d DownloadConfig) TempMaxAge() time.Duration {
+	days := d.TempMaxAgeDays
+	if days <= 0 {
+		days = DefaultTempMaxAgeDays
+	}
+	return time.Duration(days) * 24 * time.Hour
+}
+
+// DefaultMaxConversions is used when download.max_conversions is unset
+const DefaultMaxConversions = 1
+
+// EffectiveMaxConversions returns the configured conversion limit, applying the default
+func (d DownloadConfig) EffectiveMaxConversions() int {
+	if d.MaxConversions <= 0 {
+		return DefaultMaxConversions
+	}
+	return d.MaxConversions
+}
+
+// SiteLimit returns the longest domain in SiteLimits matching host and its
+// limit, or "" and 0 if the host has none
+func (d DownloadConfig) SiteLimit(host string) (string, int) {
+	host = strings.ToLower(host)
+	match, limit := "", 0
+	for domain, n := range d.SiteLimits {
+		domain = strings.ToLower(domain)
+		if (host == domain || strings.HasSuffix(host, "."+domain)) && len(domain) > len(match) {
+			match, limit = domain, n
+		}
+	}
+	return match, limit
+}
+
+// Duplicate policies for media that was downloaded before
+const (
+	DuplicatePrompt    = "prompt"
+	DuplicateSkip      = "skip_existing"
+	DuplicateOverwrite = "overwrite"
+)
+
+// ParseDuplicatePolicy validates a duplicate policy; "" is allowed and means unset
+func ParseDuplicatePolicy(value string) (string, error) {
+	switch value {
+	case "", DuplicatePrompt, DuplicateSkip, DuplicateOverwrite:
+		return value, nil
+	}
+	return "", fmt.Errorf("invalid duplicate policy: %s (use %s, %s, or %s)", value, DuplicatePrompt, DuplicateSkip, DuplicateOverwrite)
+}
+
+// EffectiveOnDuplicate returns the per-job policy if set, else the configured
+// one, else DuplicatePrompt
+func (d DownloadConfig) EffectiveOnDuplicate(job string) string {
+	if job != "" {
+		return job
+	}
+	if d.OnDuplicate != "" {
+		return d.OnDuplicate
+	}
+	return DuplicatePrompt
+}
+
+// NetworkConfig holds the proxy, user agent, and cookies used for extraction.
+// The proxy settings apply to downloads as well.
+type NetworkConfig struct {
+	// Proxy is an http://, https://, or socks5:// URL, with optional user:pass@
+	// credentials (empty uses HTTP_PROXY/HTTPS_PROXY)
+	Proxy string `yaml:"proxy,omitempty"`
+
+	// Proxies overrides Proxy per domain (matching its subdomains too); "direct" bypasses it
+	Proxies map[string]string `yaml:"proxies,omitempty"`
+
+	// UserAgent replaces the browser user agent the extractors send
+	UserAgent string `yaml:"user_agent,omitempty"`
+
+	// Cookies maps a domain (matching its subdomains too) to a Cookie header
+	Cookies map[string]string `yaml:"cookies,omitempty"`
+}
+
+// RateLimitConfig caps how many extractions per minute are made against a site
+type RateLimitConfig struct {
+	// Default applies to every host without its own limit (0 = unlimited)
+	Default int `yaml:"default,omitempty"`
+
+	// Domains maps a domain (matching its subdomains too) to requests per minute
+	Domains map[string]int `yaml:"domains,omitempty"`
+}
+
+// YtdlpConfig controls the yt-dlp fallback extractor
+type YtdlpConfig struct {
+	// Fallback extracts unmatched URLs with yt-dlp before trying browser detection
+	Fallback bool `yaml:"fallback,omitempty"`
+
+	// Path is the yt-dlp binary (default: <config dir>/bin/yt-dlp, then PATH)
+	Path string `yaml:"path,omitempty"`
+}
+
+// BandwidthConfig limits how much the server download queue may download per month
+type BandwidthConfig struct {
+	// MonthlyCapGB pauses the queue once this many GB were downloaded this month (0 = no cap)
+	MonthlyCapGB float64 `yaml:"monthly_cap_gb,omitempty"`
+}
+
+// UploadConfig holds cloud upload destinations for completed downloads
+type UploadConfig struct {
+	// Default is the target name every server job uploads to (empty disables auto-upload)
+	Default string `yaml:"default,omitempty"`
+
+	// DeleteLocal removes the local file after a successful upload
+	DeleteLocal bool `yaml:"delete_local,omitempty"`
+
+	// Targets maps a target name to its destination
+	Targets map[string]UploadTarget `yaml:"targets,omitempty"`
+}
+
+// UploadTarget is a single upload destination
+type UploadTarget struct {
+	// Type is "s3", "webdav", or "smb"
+	Type string `yaml:"type"`
+
+	// S3-compatible storage (AWS, R2, MinIO, B2)
+	Endpoint  string `yaml:"endpoint,omitempty"` // e.g. "s3.us-east-1.amazonaws.com"
+	Region    string `yaml:"region,omitempty"`
+	Bucket    string `yaml:"bucket,omitempty"`
+	AccessKey string `yaml:"access_key,omitempty"`
+	SecretKey string `yaml:"secret_key,omitempty"`
+
+	// WebDAV: name of a server in webdavServers
+	Remote string `yaml:"remote,omitempty"`
+
+	// Path is the destination directory: key prefix for S3, remote directory for WebDAV,
+	// or a UNC path / mounted share for SMB (e.g. \\nas\media or /Volumes/media)
+	Path string `yaml:"path,omitempty"`
+}
+
+// GetUploadTarget returns an upload target by name, or nil if not found
+func (c *Config) GetUploadTarget(name string) *UploadTarget {
+	if c.Upload.Targets == nil {
+		return nil
+	}
+	if t, ok := c.Upload.Targets[name]; ok {
+		return &t
+	}
+	return nil
+}
+
+// ArchiveConfig controls zipping the files of a multi-file download into one archive
+type ArchiveConfig struct {
+	// Enabled zips every multi-file download after it completes
+	Enabled bool `yaml:"enabled,omitempty"`
+
+	// NameTemplate is the archive filename, e.g. "{uploader} - {title}.zip"
+	// Placeholders: {title}, {id}, {uploader}, {date}, {count}
+	// Default: "{title}.zip"
+	NameTemplate string `yaml:"name_template,omitempty"`
+
+	// DeleteOriginals removes the individual files once the archive is written
+	DeleteOriginals bool `yaml:"delete_originals,omitempty"`
+}
+
+// ScheduleConfig holds policies that decide when queued downloads may start.
+// Jobs already downloading are never interrupted; the queue simply stops
+// picking up new jobs until the policy allows it again.
+type ScheduleConfig struct {
+	// PauseOnMetered holds the queue while the active connection is metered
+	PauseOnMetered bool `yaml:"pause_on_metered,omitempty"`
+
+	// PauseOnBattery holds the queue while the machine runs on battery power
+	PauseOnBattery bool `yaml:"pause_on_battery,omitempty"`
+
+	// WindowStart and WindowEnd restrict downloads to a daily time window
+	// in local time ("HH:MM"). The window may wrap past midnight
+	// (e.g., 23:00 - 07:00). Leave both empty to allow downloads at any time.
+	WindowStart string `yaml:"window_start,omitempty"`
+	WindowEnd   string `yaml:"window_end,omitempty"`
 }
 
 // AIConfig holds AI transcription and summarization settings
@@ -255,6 +522,10 @@ type TorrentConfig struct {
 
 	// DefaultSavePath overrides the client's default download directory
 	DefaultSavePath string `yaml:"default_save_path,omitempty"`
+
+	// Seed keeps torrents queued as download jobs seeding after they finish.
+	// By default they are paused once complete.
+	Seed bool `yaml:"seed,omitempty"`
 }
 
 // GetExpressConfig returns the config for a specific express provider
@@ -288,12 +559,6 @@ func (c *Config) DeleteExpressConfig(provider, key string) {
 	}
 }
 
-// TwitterConfig holds Twitter/X authentication settings
-type TwitterConfig struct {
-	// AuthToken is the auth_token cookie value from browser (for NSFW content)
-	AuthToken string `yaml:"auth_token,omitempty"`
-}
-
 // ServerConfig holds HTTP server settings for `vget serve`
 type ServerConfig struct {
 	// Port is the HTTP listen port (default: 8080)
@@ -408,6 +673,10 @@ func Exists() bool {
 	return err == nil
 }
 
+// scheduleWarning reports an invalid time window once per process, as the
+// config is loaded again for every download
+var scheduleWarning sync.Once
+
 // Load reads the config from ~/.config/vget/config.yml
 func Load() (*Config, error) {
 	path, err := ConfigPath()
@@ -425,8 +694,25 @@ func Load() (*Config, error) {
 		return nil, fmt.Errorf("failed to parse %s: %w", path, err)
 	}
 
-	// Expand tilde in OutputDir
+	// Expand tilde in paths
 	cfg.OutputDir = expandPath(cfg.OutputDir)
+	cfg.Download.WatchDir = expandPath(cfg.Download.WatchDir)
+
+	// An invalid time window is dropped, with a warning the first time, rather
+	// than leaving every schedule check to skip it
+	if err := cfg.Schedule.Validate(); err != nil {
+		scheduleWarning.Do(func() {
+			fmt.Fprintf(os.Stderr, "Warning: %s: %v; downloads are not limited to a time window\n", path, err)
+		})
+		cfg.Schedule.WindowStart, cfg.Schedule.WindowEnd = "", ""
+	}
+
+	// Site logins are kept in the auth store; move any left in config.yml out
+	if moveLegacyLogins(data) {
+		if err := Save(cfg); err != nil {
+			fmt.Fprintf(os.Stderr, "Warning: failed to remove logins from %s: %v\n", path, err)
+		}
+	}
 
 	return cfg, nil
 }
@@ -460,6 +746,10 @@ func expandPath(path string) string {
 
 // Save writes the config to ~/.config/vget/config.yml
 func Save(cfg *Config) error {
+	if err := cfg.Schedule.Validate(); err != nil {
+		return err
+	}
+
 	data, err := yaml.Marshal(cfg)
 	if err != nil {
 		return fmt.Errorf("failed to serialize config: %w", err)
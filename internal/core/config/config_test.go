@@ -1,8 +1,11 @@
 package config
 
 import (
+	"bytes"
+	"errors"
 	"os"
 	"path/filepath"
+	"strings"
 	"testing"
 )
 
@@ -68,3 +71,111 @@ func TestExpandPath(t *testing.T) {
 		})
 	}
 }
+
+func TestSiteLimit(t *testing.T) {
+	d := DownloadConfig{SiteLimits: map[string]int{"bilibili.com": 2, "upos.bilibili.com": 4}}
+
+	tests := []struct {
+		host   string
+		domain string
+		limit  int
+	}{
+		{"www.bilibili.com", "bilibili.com", 2},
+		{"BILIBILI.COM", "bilibili.com", 2},
+		{"cn-upos.upos.bilibili.com", "upos.bilibili.com", 4},
+		{"notbilibili.com", "", 0},
+	}
+	for _, tt := range tests {
+		domain, limit := d.SiteLimit(tt.host)
+		if domain != tt.domain || limit != tt.limit {
+			t.Errorf("SiteLimit(%q) = %q, %d, want %q, %d", tt.host, domain, limit, tt.domain, tt.limit)
+		}
+	}
+}
+
+func TestScheduleValidate(t *testing.T) {
+	tests := []struct {
+		start, end string
+		valid      bool
+	}{
+		{"", "", true},
+		{"23:00", "07:00", true},
+		{"7:30", "", true},
+		{"24:00", "07:00", false},
+		{"23:00", "7am", false},
+	}
+	for _, tt := range tests {
+		err := ScheduleConfig{WindowStart: tt.start, WindowEnd: tt.end}.Validate()
+		if (err == nil) != tt.valid {
+			t.Errorf("Validate(%q, %q) = %v, want valid %v", tt.start, tt.end, err, tt.valid)
+		}
+	}
+}
+
+func TestParseClockInvalid(t *testing.T) {
+	for _, input := range []string{"", "9", "24:00", "12:60", "ab:cd", "12:30:00"} {
+		if _, err := ParseClock(input); err == nil {
+			t.Errorf("ParseClock(%q) expected error", input)
+		}
+	}
+}
+
+func TestEncryptedFileStore(t *testing.T) {
+	dir := t.TempDir()
+	key := make([]byte, 32)
+	store := &encryptedFileStore{dir: dir, key: key}
+
+	if _, err := store.Get("bilibili.cookie"); !errors.Is(err, ErrCredentialNotFound) {
+		t.Fatalf("Get() on an empty store = %v, want ErrCredentialNotFound", err)
+	}
+	if err := store.Set("bilibili.cookie", "SESSDATA=abc"); err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(filepath.Join(dir, CredentialsFileName))
+	if err != nil || strings.Contains(string(data), "SESSDATA") {
+		t.Fatalf("credentials file = %q, %v, want it encrypted", data, err)
+	}
+
+	reopened := &encryptedFileStore{dir: dir, key: key}
+	if got, err := reopened.Get("bilibili.cookie"); err != nil || got != "SESSDATA=abc" {
+		t.Errorf("Get() = %q, %v, want SESSDATA=abc", got, err)
+	}
+	other := &encryptedFileStore{dir: dir, key: bytes.Repeat([]byte{1}, 32)}
+	if _, err := other.Get("bilibili.cookie"); err == nil {
+		t.Error("Get() with another machine's key succeeded")
+	}
+
+	if err := reopened.Delete("bilibili.cookie"); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := reopened.Get("bilibili.cookie"); !errors.Is(err, ErrCredentialNotFound) {
+		t.Errorf("Get() after Delete() = %v, want ErrCredentialNotFound", err)
+	}
+}
+
+// unreachableKeychain fails every call, as the Secret Service does without a
+// session
+type unreachableKeychain struct{}
+
+func (unreachableKeychain) Name() string { return "unreachable" }
+func (unreachableKeychain) Get(string) (string, error) { return "", errors.New("no session") }
+func (unreachableKeychain) Set(string, string) error { return errors.New("no session") }
+func (unreachableKeychain) Delete(string) error { return errors.New("no session") }
+
+func TestKeychainStoreFallsBackToFile(t *testing.T) {
+	file := &encryptedFileStore{dir: t.TempDir(), key: make([]byte, 32)}
+	if err := file.Set("twitter.auth_token", "abc"); err != nil {
+		t.Fatal(err)
+	}
+	store := &keychainStore{keychain: unreachableKeychain{}, file: file}
+
+	if got, err := store.Get("twitter.auth_token"); err != nil || got != "abc" {
+		t.Errorf("Get() = %q, %v, want abc from the file", got, err)
+	}
+	if _, err := store.Get("bilibili.cookie"); err == nil || errors.Is(err, ErrCredentialNotFound) {
+		t.Errorf("Get() of a missing name = %v, want the keychain's error", err)
+	}
+	if err := store.Set("bilibili.cookie", "SESSDATA=abc"); err == nil {
+		t.Error("Set() succeeded without the keychain")
+	}
+}
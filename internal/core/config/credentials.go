@@ -0,0 +1,351 @@
+package config
+
+import (
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"time"
+
+	"gopkg.in/yaml.v3"
+)
+
+// Site logins are kept out of config.yml: in the system keychain where there
+// is one, and otherwise in credentials.enc in the config directory, encrypted
+// with a key bound to this machine. The store is picked once and recorded in
+// credentials.store, so every vget process on the machine uses the same one.
+const (
+	CredentialsFileName      = "credentials.enc"
+	credentialsKeyFileName   = "credentials.key"
+	credentialsStoreFileName = "credentials.store"
+)
+
+// ErrCredentialNotFound is returned by GetCredential for a name with nothing stored
+var ErrCredentialNotFound = errors.New("credential not found")
+
+// credentialStore keeps named secrets
+type credentialStore interface {
+	Name() string
+	Get(name string) (string, error)
+	Set(name, value string) error
+	Delete(name string) error
+}
+
+// credentialCacheTTL is how long a value read from the store is reused, so
+// the config loads on every request don't each reach the keychain, while a
+// login made by another vget process is still picked up
+const credentialCacheTTL = time.Minute
+
+type cachedCredential struct {
+	value string
+	found bool
+	at    time.Time
+}
+
+var credentials = struct {
+	sync.Mutex
+	store  credentialStore
+	values map[string]cachedCredential
+}{values: make(map[string]cachedCredential)}
+
+// credentialStoreLocked opens the store on first use. Must be called with
+// credentials held.
+func credentialStoreLocked() (credentialStore, error) {
+	if credentials.store != nil {
+		return credentials.store, nil
+	}
+	dir, err := ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+	file := &encryptedFileStore{dir: dir}
+	credentials.store = file
+	if keychain := openKeychain(recordedStore(dir)); keychain != nil {
+		credentials.store = &keychainStore{keychain: keychain, file: file}
+	}
+	return credentials.store, nil
+}
+
+// recordedStore returns the store recorded in credentials.store, picking and
+// recording one when there is none
+func recordedStore(dir string) string {
+	path := filepath.Join(dir, credentialsStoreFileName)
+	if data, err := os.ReadFile(path); err == nil {
+		if id := strings.TrimSpace(string(data)); id != "" {
+			return id
+		}
+	}
+	id := pickKeychain()
+	err := os.MkdirAll(dir, 0755)
+	if err == nil {
+		err = os.WriteFile(path, []byte(id+"\n"), 0644)
+	}
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Warning: failed to record the credential store: %v\n", err)
+	}
+	return id
+}
+
+// cachedLocked returns the cached entry for name if it's still fresh. Must be
+// called with credentials held.
+func cachedLocked(name string) (cachedCredential, bool) {
+	entry, ok := credentials.values[name]
+	return entry, ok && time.Since(entry.at) < credentialCacheTTL
+}
+
+// CredentialBackend names where credentials are stored, e.g. "macOS Keychain"
+func CredentialBackend() string {
+	credentials.Lock()
+	defer credentials.Unlock()
+	store, err := credentialStoreLocked()
+	if err != nil {
+		return "none"
+	}
+	return store.Name()
+}
+
+// GetCredential reads the secret stored under name
+func GetCredential(name string) (string, error) {
+	credentials.Lock()
+	defer credentials.Unlock()
+	if entry, ok := cachedLocked(name); ok {
+		if !entry.found {
+			return "", ErrCredentialNotFound
+		}
+		return entry.value, nil
+	}
+	store, err := credentialStoreLocked()
+	if err != nil {
+		return "", err
+	}
+
+	value, err := store.Get(name)
+	switch {
+	case errors.Is(err, ErrCredentialNotFound):
+		credentials.values[name] = cachedCredential{at: time.Now()}
+	case err == nil:
+		credentials.values[name] = cachedCredential{value: value, found: true, at: time.Now()}
+	}
+	return value, err
+}
+
+// SetCredential stores value under name, replacing what was there. An empty
+// value deletes it.
+func SetCredential(name, value string) error {
+	if value == "" {
+		return DeleteCredential(name)
+	}
+	credentials.Lock()
+	defer credentials.Unlock()
+	if entry, ok := cachedLocked(name); ok && entry.found && entry.value == value {
+		return nil
+	}
+	store, err := credentialStoreLocked()
+	if err != nil {
+		return err
+	}
+	if err := store.Set(name, value); err != nil {
+		return fmt.Errorf("failed to store %s in %s: %w", name, store.Name(), err)
+	}
+	credentials.values[name] = cachedCredential{value: value, found: true, at: time.Now()}
+	return nil
+}
+
+// DeleteCredential removes the secret stored under name, if any
+func DeleteCredential(name string) error {
+	credentials.Lock()
+	defer credentials.Unlock()
+	if entry, ok := cachedLocked(name); ok && !entry.found {
+		return nil
+	}
+	store, err := credentialStoreLocked()
+	if err != nil {
+		return err
+	}
+	if err := store.Delete(name); err != nil && !errors.Is(err, ErrCredentialNotFound) {
+		return fmt.Errorf("failed to remove %s from %s: %w", name, store.Name(), err)
+	}
+	credentials.values[name] = cachedCredential{at: time.Now()}
+	return nil
+}
+
+// legacyLogins are the logins older versions kept in config.yml
+type legacyLogins struct {
+	Bilibili struct {
+		Cookie string `yaml:"cookie"`
+	} `yaml:"bilibili"`
+	Twitter struct {
+		AuthToken string `yaml:"auth_token"`
+	} `yaml:"twitter"`
+}
+
+// moveLegacyLogins moves the logins found in config.yml data to the
+// credential store, where the auth package takes them over. It reports
+// whether all there were moved, so config.yml can be rewritten without them.
+func moveLegacyLogins(data []byte) bool {
+	var legacy legacyLogins
+	if yaml.Unmarshal(data, &legacy) != nil {
+		return false
+	}
+	found, moved := 0, 0
+	for name, value := range map[string]string{
+		"bilibili.cookie":    legacy.Bilibili.Cookie,
+		"twitter.auth_token": legacy.Twitter.AuthToken,
+	} {
+		if value == "" {
+			continue
+		}
+		found++
+		if err := SetCredential(name, value); err != nil {
+			fmt.Fprintf(os.Stderr, "Warning: keeping %s in config.yml: %v\n", name, err)
+			continue
+		}
+		moved++
+	}
+	return found > 0 && moved == found
+}
+
+// encryptedFileStore keeps credentials as JSON in credentials.enc, sealed
+// with AES-256-GCM under machineKey
+type encryptedFileStore struct {
+	dir string
+	key []byte
+}
+
+func (s *encryptedFileStore) Name() string {
+	return "encrypted file"
+}
+
+func (s *encryptedFileStore) Get(name string) (string, error) {
+	values, err := s.read()
+	if err != nil {
+		return "", err
+	}
+	value, ok := values[name]
+	if !ok {
+		return "", ErrCredentialNotFound
+	}
+	return value, nil
+}
+
+func (s *encryptedFileStore) Set(name, value string) error {
+	values, err := s.read()
+	if err != nil {
+		return err
+	}
+	values[name] = value
+	return s.write(values)
+}
+
+func (s *encryptedFileStore) Delete(name string) error {
+	values, err := s.read()
+	if err != nil {
+		return err
+	}
+	if _, ok := values[name]; !ok {
+		return ErrCredentialNotFound
+	}
+	delete(values, name)
+	return s.write(values)
+}
+
+func (s *encryptedFileStore) aead() (cipher.AEAD, error) {
+	if s.key == nil {
+		key, err := machineKey(s.dir)
+		if err != nil {
+			return nil, err
+		}
+		s.key = key
+	}
+	block, err := aes.NewCipher(s.key)
+	if err != nil {
+		return nil, err
+	}
+	return cipher.NewGCM(block)
+}
+
+// read decrypts the stored credentials; a missing file holds none
+func (s *encryptedFileStore) read() (map[string]string, error) {
+	values := make(map[string]string)
+	data, err := os.ReadFile(filepath.Join(s.dir, CredentialsFileName))
+	if errors.Is(err, os.ErrNotExist) {
+		return values, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	aead, err := s.aead()
+	if err != nil {
+		return nil, err
+	}
+	if len(data) < aead.NonceSize() {
+		return nil, fmt.Errorf("%s is corrupt", CredentialsFileName)
+	}
+	plain, err := aead.Open(nil, data[:aead.NonceSize()], data[aead.NonceSize():], nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to decrypt %s; it was written on another machine or is corrupt", CredentialsFileName)
+	}
+	if err := json.Unmarshal(plain, &values); err != nil {
+		return nil, fmt.Errorf("%s is corrupt: %w", CredentialsFileName, err)
+	}
+	return values, nil
+}
+
+// write encrypts values into credentials.enc, replacing it atomically
+func (s *encryptedFileStore) write(values map[string]string) error {
+	plain, err := json.Marshal(values)
+	if err != nil {
+		return err
+	}
+	aead, err := s.aead()
+	if err != nil {
+		return err
+	}
+	nonce := make([]byte, aead.NonceSize())
+	if _, err := rand.Read(nonce); err != nil {
+		return err
+	}
+	if err := os.MkdirAll(s.dir, 0755); err != nil {
+		return err
+	}
+
+	path := filepath.Join(s.dir, CredentialsFileName)
+	tmp := path + ".tmp"
+	if err := os.WriteFile(tmp, aead.Seal(nonce, nonce, plain, nil), 0600); err != nil {
+		return err
+	}
+	return os.Rename(tmp, path)
+}
+
+// machineKey is the key credentials.enc is encrypted with: derived from the
+// machine's ID, so the file is useless copied elsewhere, or where there is no
+// stable ID, as in containers, random and kept in credentials.key next to it
+func machineKey(dir string) ([]byte, error) {
+	if id := machineID(); id != "" && !IsRunningInDocker() {
+		key := sha256.Sum256([]byte("vget credentials\n" + id))
+		return key[:], nil
+	}
+
+	path := filepath.Join(dir, credentialsKeyFileName)
+	if key, err := os.ReadFile(path); err == nil && len(key) == 32 {
+		return key, nil
+	}
+	key := make([]byte, 32)
+	if _, err := rand.Read(key); err != nil {
+		return nil, err
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return nil, err
+	}
+	if err := os.WriteFile(path, key, 0600); err != nil {
+		return nil, fmt.Errorf("failed to save credentials key: %w", err)
+	}
+	return key, nil
+}
@@ -0,0 +1,223 @@
+package config
+
+import (
+	"bytes"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"runtime"
+	"strings"
+)
+
+// keychainService is the service credentials are filed under in the keychain
+const keychainService = AppDirName
+
+// Stores credentials can be kept in, as recorded in credentials.store
+const (
+	storeMacKeychain   = "macos-keychain"
+	storeSecretService = "secret-service"
+	storeFile          = "file"
+)
+
+// pickKeychain picks the store for a machine that has none recorded: the
+// macOS Keychain through security(1), or the Secret Service (GNOME Keyring,
+// KWallet) through secret-tool(1) in a desktop session, else the file
+func pickKeychain() string {
+	switch runtime.GOOS {
+	case "darwin":
+		if _, err := exec.LookPath("security"); err == nil {
+			return storeMacKeychain
+		}
+	case "linux", "freebsd", "openbsd":
+		if _, err := exec.LookPath("secret-tool"); err == nil && os.Getenv("DBUS_SESSION_BUS_ADDRESS") != "" {
+			return storeSecretService
+		}
+	}
+	return storeFile
+}
+
+// openKeychain returns the keychain recorded as id, or nil for the file or
+// when its tool is gone. It doesn't look at the session, so a process
+// without one still uses the same keychain as the rest.
+func openKeychain(id string) credentialStore {
+	switch id {
+	case storeMacKeychain:
+		if _, err := exec.LookPath("security"); err == nil {
+			return macKeychain{}
+		}
+	case storeSecretService:
+		if _, err := exec.LookPath("secret-tool"); err == nil {
+			return secretService{}
+		}
+	}
+	return nil
+}
+
+// keychainStore keeps credentials in a system keychain and still finds those
+// in credentials.enc: written before the keychain was picked, or needed
+// where the keychain can't be reached, as under systemd without a session
+type keychainStore struct {
+	keychain credentialStore
+	file     credentialStore
+}
+
+func (s *keychainStore) Name() string {
+	return s.keychain.Name()
+}
+
+func (s *keychainStore) Get(name string) (string, error) {
+	value, err := s.keychain.Get(name)
+	if err == nil {
+		return value, nil
+	}
+	if value, fileErr := s.file.Get(name); fileErr == nil {
+		return value, nil
+	}
+	return "", err
+}
+
+// Set writes to the keychain, dropping any copy in the file so it can't
+// come back where the keychain isn't reachable
+func (s *keychainStore) Set(name, value string) error {
+	if err := s.keychain.Set(name, value); err != nil {
+		return err
+	}
+	s.file.Delete(name)
+	return nil
+}
+
+func (s *keychainStore) Delete(name string) error {
+	err := s.keychain.Delete(name)
+	if fileErr := s.file.Delete(name); fileErr == nil && errors.Is(err, ErrCredentialNotFound) {
+		return nil
+	}
+	return err
+}
+
+// commandError folds the stderr of a failed command into its error
+func commandError(err error, stderr []byte) error {
+	if msg := strings.TrimSpace(string(stderr)); msg != "" {
+		return fmt.Errorf("%w: %s", err, msg)
+	}
+	return err
+}
+
+// macKeychainNotFound is the exit code of security(1) for a missing item
+const macKeychainNotFound = 44
+
+type macKeychain struct{}
+
+func (macKeychain) Name() string {
+	return "macOS Keychain"
+}
+
+func (macKeychain) Get(name string) (string, error) {
+	var stderr bytes.Buffer
+	cmd := exec.Command("security", "find-generic-password", "-s", keychainService, "-a", name, "-w")
+	cmd.Stderr = &stderr
+	out, err := cmd.Output()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) && exitErr.ExitCode() == macKeychainNotFound {
+		return "", ErrCredentialNotFound
+	}
+	if err != nil {
+		return "", commandError(err, stderr.Bytes())
+	}
+	return strings.TrimSuffix(string(out), "\n"), nil
+}
+
+// Set runs security in interactive mode with the value hex-encoded on stdin,
+// keeping it out of the process list
+func (macKeychain) Set(name, value string) error {
+	cmd := exec.Command("security", "-i")
+	cmd.Stdin = strings.NewReader(fmt.Sprintf("add-generic-password -U -s %s -a %q -X %s\n",
+		keychainService, name, hex.EncodeToString([]byte(value))))
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return commandError(err, out)
+	}
+	return nil
+}
+
+func (macKeychain) Delete(name string) error {
+	out, err := exec.Command("security", "delete-generic-password", "-s", keychainService, "-a", name).CombinedOutput()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) && exitErr.ExitCode() == macKeychainNotFound {
+		return ErrCredentialNotFound
+	}
+	if err != nil {
+		return commandError(err, out)
+	}
+	return nil
+}
+
+type secretService struct{}
+
+func (secretService) Name() string {
+	return "Secret Service"
+}
+
+// Get looks name up; secret-tool exits 1 with no output when nothing matches
+func (secretService) Get(name string) (string, error) {
+	var stderr bytes.Buffer
+	cmd := exec.Command("secret-tool", "lookup", "service", keychainService, "account", name)
+	cmd.Stderr = &stderr
+	out, err := cmd.Output()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) && exitErr.ExitCode() == 1 && len(out) == 0 && stderr.Len() == 0 {
+		return "", ErrCredentialNotFound
+	}
+	if err != nil {
+		return "", commandError(err, stderr.Bytes())
+	}
+	return string(out), nil
+}
+
+// Set passes the value on stdin, keeping it out of the process list
+func (secretService) Set(name, value string) error {
+	cmd := exec.Command("secret-tool", "store", "--label", keychainService+" "+name,
+		"service", keychainService, "account", name)
+	cmd.Stdin = strings.NewReader(value)
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return commandError(err, out)
+	}
+	return nil
+}
+
+func (secretService) Delete(name string) error {
+	if out, err := exec.Command("secret-tool", "clear", "service", keychainService, "account", name).CombinedOutput(); err != nil {
+		return commandError(err, out)
+	}
+	return nil
+}
+
+var (
+	ioregUUID   = regexp.MustCompile(`"IOPlatformUUID" = "([^"]+)"`)
+	machineGUID = regexp.MustCompile(`MachineGuid\s+REG_SZ\s+(\S+)`)
+)
+
+// machineID returns an ID that stays the same across reboots of this
+// machine, or "" when there is none
+func machineID() string {
+	switch runtime.GOOS {
+	case "darwin":
+		out, err := exec.Command("ioreg", "-rd1", "-c", "IOPlatformExpertDevice").Output()
+		if m := ioregUUID.FindSubmatch(out); err == nil && m != nil {
+			return string(m[1])
+		}
+	case "windows":
+		out, err := exec.Command("reg", "query", `HKLM\SOFTWARE\Microsoft\Cryptography`, "/v", "MachineGuid").Output()
+		if m := machineGUID.FindSubmatch(out); err == nil && m != nil {
+			return string(m[1])
+		}
+	default:
+		for _, path := range []string{"/etc/machine-id", "/var/lib/dbus/machine-id"} {
+			if data, err := os.ReadFile(path); err == nil && len(bytes.TrimSpace(data)) > 0 {
+				return string(bytes.TrimSpace(data))
+			}
+		}
+	}
+	return ""
+}
@@ -0,0 +1,90 @@
+package config
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// SetScheduleValue sets a schedule.* config key. An empty value clears the setting.
+func SetScheduleValue(s *ScheduleConfig, key, value string) error {
+	switch key {
+	case "schedule.pause_on_metered", "schedule.pause_on_battery":
+		enabled, err := parseBoolValue(key, value)
+		if err != nil {
+			return err
+		}
+		if key == "schedule.pause_on_metered" {
+			s.PauseOnMetered = enabled
+		} else {
+			s.PauseOnBattery = enabled
+		}
+	case "schedule.window_start", "schedule.window_end":
+		if err := validateClock(key, value); err != nil {
+			return err
+		}
+		if key == "schedule.window_start" {
+			s.WindowStart = value
+		} else {
+			s.WindowEnd = value
+		}
+	default:
+		return fmt.Errorf("unknown schedule config key: %s\nSupported: schedule.pause_on_metered, schedule.pause_on_battery, schedule.window_start, schedule.window_end", key)
+	}
+	return nil
+}
+
+// GetScheduleValue returns a schedule.* config key as a string
+func GetScheduleValue(s *ScheduleConfig, key string) (string, error) {
+	switch key {
+	case "schedule.pause_on_metered":
+		return strconv.FormatBool(s.PauseOnMetered), nil
+	case "schedule.pause_on_battery":
+		return strconv.FormatBool(s.PauseOnBattery), nil
+	case "schedule.window_start":
+		return s.WindowStart, nil
+	case "schedule.window_end":
+		return s.WindowEnd, nil
+	default:
+		return "", fmt.Errorf("unknown schedule config key: %s\nSupported: schedule.pause_on_metered, schedule.pause_on_battery, schedule.window_start, schedule.window_end", key)
+	}
+}
+
+// Validate checks the time window bounds, so a typo in config.yml is reported
+// instead of the window being skipped
+func (s ScheduleConfig) Validate() error {
+	if err := validateClock("schedule.window_start", s.WindowStart); err != nil {
+		return err
+	}
+	return validateClock("schedule.window_end", s.WindowEnd)
+}
+
+// validateClock checks that value is empty or an "HH:MM" time
+func validateClock(key, value string) error {
+	if value == "" {
+		return nil
+	}
+	if _, err := ParseClock(value); err != nil {
+		return fmt.Errorf("invalid time for %s: %s (use HH:MM)", key, value)
+	}
+	return nil
+}
+
+// ParseClock parses an "HH:MM" string into minutes since midnight
+func ParseClock(s string) (int, error) {
+	parts := strings.Split(strings.TrimSpace(s), ":")
+	if len(parts) != 2 {
+		return 0, fmt.Errorf("invalid time %q (use HH:MM)", s)
+	}
+
+	hour, err := strconv.Atoi(parts[0])
+	if err != nil || hour < 0 || hour > 23 {
+		return 0, fmt.Errorf("invalid hour in %q", s)
+	}
+	minute, err := strconv.Atoi(parts[1])
+	if err != nil || minute < 0 || minute > 59 {
+		return 0, fmt.Errorf("invalid minute in %q", s)
+	}
+
+	return hour*60 + minute, nil
+}
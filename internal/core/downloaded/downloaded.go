@@ -0,0 +1,147 @@
+// Package downloaded keeps the download archive: the media IDs and URLs of
+// completed downloads, used to detect media that was already downloaded.
+package downloaded
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// FileName is the archive file inside the config directory
+const FileName = "download_archive.json"
+
+// Entry is a completed download
+type Entry struct {
+	Site         string    `json:"site,omitempty"` // extractor name
+	MediaID      string    `json:"media_id,omitempty"`
+	URL          string    `json:"url"`
+	Filename     string    `json:"filename,omitempty"`
+	DownloadedAt time.Time `json:"downloaded_at"`
+}
+
+// NewEntry describes media extracted from a URL. IDs from the generic browser
+// extractor are derived from stream filenames, so those match by URL only.
+func NewEntry(ext extractor.Extractor, media extractor.Media, rawURL string) Entry {
+	e := Entry{URL: rawURL}
+	if ext != nil && ext.Name() != "browser" {
+		e.Site = ext.Name()
+		e.MediaID = media.GetID()
+	}
+	return e
+}
+
+// Archive reads and writes the archive file
+type Archive struct {
+	path string
+	mu   sync.Mutex
+}
+
+// Open returns the archive for the default archive file
+func Open() (*Archive, error) {
+	dir, err := config.ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+	return &Archive{path: filepath.Join(dir, FileName)}, nil
+}
+
+// NormalizeURL lowercases the host and strips the fragment and trailing slash
+// so equivalent page URLs match
+func NormalizeURL(rawURL string) string {
+	u, err := url.Parse(strings.TrimSpace(rawURL))
+	if err != nil {
+		return rawURL
+	}
+	u.Fragment = ""
+	u.Host = strings.ToLower(u.Host)
+	u.Path = strings.TrimSuffix(u.Path, "/")
+	return u.String()
+}
+
+func (e *Entry) matches(other *Entry, normalizedURL string) bool {
+	if other.MediaID != "" && e.MediaID == other.MediaID && e.Site == other.Site {
+		return true
+	}
+	return normalizedURL != "" && NormalizeURL(e.URL) == normalizedURL
+}
+
+func (a *Archive) load() ([]Entry, error) {
+	data, err := os.ReadFile(a.path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+	var entries []Entry
+	if err := json.Unmarshal(data, &entries); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", a.path, err)
+	}
+	return entries, nil
+}
+
+func (a *Archive) save(entries []Entry) error {
+	if err := os.MkdirAll(filepath.Dir(a.path), 0755); err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(entries, "", "  ")
+	if err != nil {
+		return err
+	}
+	tmp := a.path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0644); err != nil {
+		return err
+	}
+	return os.Rename(tmp, a.path)
+}
+
+// Find returns the archived download of the media, matched by site and media
+// ID or by URL, or nil if it was never downloaded
+func (a *Archive) Find(e Entry) (*Entry, error) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+
+	entries, err := a.load()
+	if err != nil {
+		return nil, err
+	}
+	normalized := NormalizeURL(e.URL)
+	for i := len(entries) - 1; i >= 0; i-- {
+		if entries[i].matches(&e, normalized) {
+			return &entries[i], nil
+		}
+	}
+	return nil, nil
+}
+
+// Add records a completed download, replacing an earlier entry for the same media
+func (a *Archive) Add(e Entry) error {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+
+	entries, err := a.load()
+	if err != nil {
+		return err
+	}
+	if e.DownloadedAt.IsZero() {
+		e.DownloadedAt = time.Now()
+	}
+
+	normalized := NormalizeURL(e.URL)
+	kept := entries[:0]
+	for _, existing := range entries {
+		if !existing.matches(&e, normalized) {
+			kept = append(kept, existing)
+		}
+	}
+	return a.save(append(kept, e))
+}
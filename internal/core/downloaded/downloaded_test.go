@@ -0,0 +1,48 @@
+package downloaded
+
+import (
+	"path/filepath"
+	"testing"
+)
+
+func TestArchiveFind(t *testing.T) {
+	a := &Archive{path: filepath.Join(t.TempDir(), FileName)}
+
+	if err := a.Add(Entry{Site: "bilibili", MediaID: "BV1xx", URL: "https://www.bilibili.com/video/BV1xx/"}); err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name  string
+		query Entry
+		found bool
+	}{
+		{"same media ID from another URL", Entry{Site: "bilibili", MediaID: "BV1xx", URL: "https://b23.tv/abc"}, true},
+		{"same URL without trailing slash", Entry{URL: "https://WWW.bilibili.com/video/BV1xx#reply"}, true},
+		{"same ID on another site", Entry{Site: "tiktok", MediaID: "BV1xx", URL: "https://tiktok.com/v/1"}, false},
+		{"other URL", Entry{URL: "https://www.bilibili.com/video/BV2yy"}, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := a.Find(tt.query)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if (got != nil) != tt.found {
+				t.Errorf("Find(%+v) = %+v, want found=%v", tt.query, got, tt.found)
+			}
+		})
+	}
+
+	// Re-adding the same media replaces the entry
+	if err := a.Add(Entry{Site: "bilibili", MediaID: "BV1xx", URL: "https://b23.tv/abc", Filename: "new.mp4"}); err != nil {
+		t.Fatal(err)
+	}
+	entries, err := a.load()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(entries) != 1 || entries[0].Filename != "new.mp4" {
+		t.Errorf("entries after re-add = %+v", entries)
+	}
+}
@@ -0,0 +1,80 @@
+package downloader
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// chapterContainers are the output formats that can hold chapters
+var chapterContainers = map[string]bool{
+	".mp4": true, ".m4v": true, ".m4a": true, ".mov": true, ".mkv": true, ".webm": true,
+}
+
+// ffmetadataEscaper escapes characters with special meaning in FFMETADATA files
+var ffmetadataEscaper = strings.NewReplacer(
+	`\`, `\\`,
+	"=", `\=`,
+	";", `\;`,
+	"#", `\#`,
+	"\n", `\`+"\n",
+)
+
+// writeChapterMetadata writes chapters to a temporary FFMETADATA file for
+// ffmpeg's -map_chapters. The caller removes the file.
+func writeChapterMetadata(chapters []extractor.Chapter) (string, error) {
+	var b strings.Builder
+	b.WriteString(";FFMETADATA1\n")
+	for _, c := range chapters {
+		if c.End <= c.Start {
+			continue
+		}
+		fmt.Fprintf(&b, "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=%d\nEND=%d\ntitle=%s\n",
+			int64(c.Start*1000), int64(c.End*1000), ffmetadataEscaper.Replace(c.Title))
+	}
+
+	f, err := os.CreateTemp("", "vget-chapters-*.txt")
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+	if _, err := f.WriteString(b.String()); err != nil {
+		os.Remove(f.Name())
+		return "", err
+	}
+	return f.Name(), nil
+}
+
+// EmbedChapters writes chapters into the downloaded file at path, remuxing it
+// in place with stream copy. Files in containers without chapter support,
+// such as MPEG-TS, are left as they are.
+func EmbedChapters(ctx context.Context, path string, chapters []extractor.Chapter) error {
+	if len(chapters) == 0 || !chapterContainers[strings.ToLower(filepath.Ext(path))] {
+		return nil
+	}
+	if !FFmpegAvailable() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if err := CheckDiskSpace(path, fileSize(path)); err != nil {
+		return err
+	}
+
+	metaPath, err := writeChapterMetadata(chapters)
+	if err != nil {
+		return fmt.Errorf("failed to write chapters: %w", err)
+	}
+	defer os.Remove(metaPath)
+
+	partPath := ffmpegPartPath(path)
+	args := []string{"-i", path, "-i", metaPath, "-map", "0", "-map_chapters", "1", "-c", "copy", "-y", partPath}
+	if output, err := ffmpeg.Run(ctx, args, nil); err != nil {
+		os.Remove(partPath)
+		return fmt.Errorf("ffmpeg chapter embedding failed: %w\nOutput: %s", err, string(output))
+	}
+	return commitPart(partPath, path)
+}
@@ -0,0 +1,132 @@
+package downloader
+
+import (
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// tempFilePrefixes name the scratch files vget's ffmpeg runs leave in the
+// system temp directory when vget is killed mid-run
+var tempFilePrefixes = []string{"vget-concat-", "vget-chapters-", "vget-cover-"}
+
+// TempFile is a leftover of an interrupted download or merge
+type TempFile struct {
+	Path    string    `json:"path"`
+	Size    int64     `json:"size"`
+	ModTime time.Time `json:"mod_time"` // newest file inside, for segment directories
+}
+
+// isOutputTemp reports whether a name in a download directory belongs to an
+// unfinished download: partial files and their resume state, ffmpeg output
+// before it's moved into place, and HLS segment directories
+func isOutputTemp(name string, dir bool) bool {
+	if dir {
+		return strings.HasSuffix(name, hlsPartsSuffix)
+	}
+	ext := filepath.Ext(name)
+	return strings.HasSuffix(name, partSuffix) ||
+		strings.HasSuffix(name, partSuffix+partStateSuffix) ||
+		strings.HasSuffix(name, partSuffix+partStateSuffix+".tmp") ||
+		(ext != "" && strings.HasSuffix(strings.TrimSuffix(name, ext), partSuffix))
+}
+
+// FindTempFiles lists leftovers under the download directory outputDir, and
+// vget's scratch files in the system temp directory, not modified since
+// maxAge ago
+func FindTempFiles(outputDir string, maxAge time.Duration) ([]TempFile, error) {
+	cutoff := time.Now().Add(-maxAge)
+	var found []TempFile
+
+	err := filepath.WalkDir(outputDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			// Skip unreadable directories rather than failing the scan
+			if d != nil && d.IsDir() && path != outputDir {
+				return filepath.SkipDir
+			}
+			return err
+		}
+		if path == outputDir || !isOutputTemp(d.Name(), d.IsDir()) {
+			return nil
+		}
+		if tf, ok := tempFileInfo(path); ok && tf.ModTime.Before(cutoff) {
+			found = append(found, tf)
+		}
+		if d.IsDir() {
+			return filepath.SkipDir
+		}
+		return nil
+	})
+	if err != nil && !os.IsNotExist(err) {
+		return nil, err
+	}
+
+	entries, _ := os.ReadDir(os.TempDir())
+	for _, entry := range entries {
+		if entry.IsDir() || !hasTempPrefix(entry.Name()) {
+			continue
+		}
+		if tf, ok := tempFileInfo(filepath.Join(os.TempDir(), entry.Name())); ok && tf.ModTime.Before(cutoff) {
+			found = append(found, tf)
+		}
+	}
+	return found, nil
+}
+
+// RemoveTempFiles deletes the leftovers and returns the bytes reclaimed and
+// the paths that couldn't be removed
+func RemoveTempFiles(files []TempFile) (int64, []string) {
+	var reclaimed int64
+	var failed []string
+	for _, tf := range files {
+		if err := os.RemoveAll(tf.Path); err != nil {
+			failed = append(failed, tf.Path)
+			continue
+		}
+		reclaimed += tf.Size
+	}
+	return reclaimed, failed
+}
+
+func hasTempPrefix(name string) bool {
+	for _, prefix := range tempFilePrefixes {
+		if strings.HasPrefix(name, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+// tempFileInfo sizes a leftover file, or a segment directory with everything in it
+func tempFileInfo(path string) (TempFile, bool) {
+	info, err := os.Stat(path)
+	if err != nil {
+		return TempFile{}, false
+	}
+	tf := TempFile{Path: path, Size: info.Size(), ModTime: info.ModTime()}
+	if !info.IsDir() {
+		return tf, true
+	}
+	// Segments are written in place, so the newest one tells when the
+	// directory was last used; an empty one keeps its own time
+	tf.Size = 0
+	var newest time.Time
+	filepath.WalkDir(path, func(_ string, d fs.DirEntry, err error) error {
+		if err != nil || d.IsDir() {
+			return nil
+		}
+		if fi, err := d.Info(); err == nil {
+			tf.Size += fi.Size()
+			if fi.ModTime().After(newest) {
+				newest = fi.ModTime()
+			}
+		}
+		return nil
+	})
+	if !newest.IsZero() {
+		tf.ModTime = newest
+	}
+	return tf, true
+}
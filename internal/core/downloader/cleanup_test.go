@@ -0,0 +1,60 @@
+package downloader
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+	"time"
+)
+
+func TestFindTempFiles(t *testing.T) {
+	const maxAge = 7 * 24 * time.Hour
+	dir := t.TempDir()
+	old := time.Now().Add(-2 * maxAge)
+	write := func(name string, stale bool) string {
+		path := filepath.Join(dir, name)
+		os.MkdirAll(filepath.Dir(path), 0755)
+		os.WriteFile(path, []byte("12345"), 0644)
+		if stale {
+			os.Chtimes(path, old, old)
+		}
+		return path
+	}
+
+	write("done.mp4", true)
+	write("notes.part 2.txt", true)
+	partial := write("movie.mp4.part", true)
+	write("movie.mp4.part.json", true)
+	write("recent.mp4.part", false)
+	merged := write("sub/show.part.mkv", true)
+	write("stream.ts.parts/00001.ts", true)
+	write("live.ts.parts/00001.ts", true)
+	write("live.ts.parts/00002.ts", false)
+
+	files, err := FindTempFiles(dir, maxAge)
+	if err != nil {
+		t.Fatal(err)
+	}
+	// Leftovers in the system temp directory don't belong to this test
+	files = slices.DeleteFunc(files, func(f TempFile) bool { return filepath.Dir(f.Path) == filepath.Clean(os.TempDir()) })
+	var paths []string
+	for _, f := range files {
+		paths = append(paths, f.Path)
+	}
+	slices.Sort(paths)
+
+	want := []string{partial, partial + ".json", merged, filepath.Join(dir, "stream.ts.parts")}
+	slices.Sort(want)
+	if !slices.Equal(paths, want) {
+		t.Errorf("FindTempFiles() = %v, want %v", paths, want)
+	}
+
+	reclaimed, failed := RemoveTempFiles(files)
+	if len(failed) > 0 || reclaimed < 20 {
+		t.Errorf("RemoveTempFiles() = %d, %v; want at least 20 bytes and no failures", reclaimed, failed)
+	}
+	if _, err := os.Stat(filepath.Join(dir, "stream.ts.parts")); !os.IsNotExist(err) {
+		t.Error("segment directory still exists")
+	}
+}
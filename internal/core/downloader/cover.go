@@ -0,0 +1,43 @@
+package downloader
+
+import (
+	"context"
+	"fmt"
+	"os"
+
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// EmbedCoverArt fetches the thumbnail at thumbURL and makes it the cover art
+// of the downloaded audio file at path, in place. Formats without cover art,
+// such as Opus, are left as they are.
+func EmbedCoverArt(ctx context.Context, path, thumbURL string) error {
+	if thumbURL == "" || !ffmpeg.CanHoldCover(path) {
+		return nil
+	}
+	if !FFmpegAvailable() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if err := CheckDiskSpace(path, fileSize(path)); err != nil {
+		return err
+	}
+
+	data, mimeType, err := FetchThumbnail(thumbURL)
+	if err != nil {
+		return err
+	}
+	f, err := os.CreateTemp("", "vget-cover-*."+thumbnailExt(mimeType, thumbURL))
+	if err != nil {
+		return err
+	}
+	defer os.Remove(f.Name())
+	_, err = f.Write(data)
+	if closeErr := f.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		return fmt.Errorf("failed to write cover art: %w", err)
+	}
+
+	return ffmpeg.EmbedCover(ctx, path, path, f.Name())
+}
@@ -0,0 +1,51 @@
+package downloader
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// ErrInsufficientSpace means the output volume can't hold a download
+var ErrInsufficientSpace = errors.New("not enough disk space")
+
+// diskSpaceMargin is left free on top of what a download needs
+const diskSpaceMargin = 64 * 1024 * 1024
+
+// CheckDiskSpace fails with ErrInsufficientSpace when the volume that path
+// will be written to has less than need bytes free. It's a no-op if the size
+// is unknown or free space can't be determined.
+func CheckDiskSpace(path string, need int64) error {
+	if need <= 0 {
+		return nil
+	}
+	dir := existingDir(path)
+	free, err := freeSpace(dir)
+	if err != nil {
+		return nil
+	}
+	if free < need+diskSpaceMargin {
+		return fmt.Errorf("%w: %s needed, %s free in %s", ErrInsufficientSpace, formatBytes(need), formatBytes(free), dir)
+	}
+	return nil
+}
+
+// existingDir returns the closest directory above path that exists, since
+// the output directory may not have been created yet
+func existingDir(path string) string {
+	dir := filepath.Dir(path)
+	if abs, err := filepath.Abs(dir); err == nil {
+		dir = abs
+	}
+	for {
+		if info, err := os.Stat(dir); err == nil && info.IsDir() {
+			return dir
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return dir
+		}
+		dir = parent
+	}
+}
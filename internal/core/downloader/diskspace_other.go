@@ -0,0 +1,10 @@
+//go:build !linux && !darwin && !freebsd && !windows
+
+package downloader
+
+import "errors"
+
+// freeSpace isn't implemented on this platform, so space checks are skipped
+func freeSpace(dir string) (int64, error) {
+	return 0, errors.New("free space is not available on this platform")
+}
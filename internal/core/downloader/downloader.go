@@ -1,9 +1,14 @@
 package downloader
 
 import (
+	"context"
 	"fmt"
 	"io"
+	"net/http"
+	"net/url"
 	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
 )
 
 // DefaultUserAgent is the default User-Agent header used for downloads
@@ -31,12 +36,30 @@ func (d *Downloader) DownloadWithHeaders(url, output, videoID string, headers ma
 	return RunDownloadTUI(url, output, videoID, d.lang, headers)
 }
 
+// DownloadMirrors downloads from the first of urls that works, moving on to
+// the next mirror when one fails
+func (d *Downloader) DownloadMirrors(urls []string, output, videoID string, headers map[string]string) error {
+	_, err := TryMirrors(context.Background(), urls, func(u string) error {
+		err := RunDownloadTUI(u, output, videoID, d.lang, headers)
+		if err != nil && u != urls[len(urls)-1] {
+			fmt.Printf("  Download failed: %v\n  Trying next mirror...\n", err)
+		}
+		return err
+	})
+	return err
+}
+
 // DownloadFromReader downloads from an io.ReadCloser to the specified path using TUI
 // This is useful for WebDAV and other sources that provide a reader instead of URL
 func (d *Downloader) DownloadFromReader(reader io.ReadCloser, size int64, output, displayID string) error {
 	return RunDownloadFromReaderTUI(reader, size, output, displayID, d.lang)
 }
 
+// proxyFunc applies the network proxy settings from config.yml, including per-site overrides
+func proxyFunc() func(*http.Request) (*url.URL, error) {
+	return config.LoadOrDefault().Network.ProxyFunc()
+}
+
 func formatBytes(b int64) string {
 	const unit = 1024
 	if b < unit {
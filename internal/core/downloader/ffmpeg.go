@@ -1,42 +1,64 @@
 package downloader
 
 import (
+	"context"
 	"fmt"
 	"os"
 	"os/exec"
 	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
 )
 
-// FFmpegAvailable checks if ffmpeg is installed and available in PATH
+// FFmpegAvailable checks if ffmpeg is in PATH or was installed by vget
 func FFmpegAvailable() bool {
-	_, err := exec.LookPath("ffmpeg")
-	return err == nil
+	return ffmpeg.Available()
 }
 
 // MergeVideoAudio merges separate video and audio files into a single output file using ffmpeg.
 // Uses stream copy (-c copy) for fast merging without re-encoding.
+// Chapters, if any, are embedded in the output.
 // If deleteOriginals is true, removes the source files after successful merge.
-// Returns the path to the merged file.
-func MergeVideoAudio(videoPath, audioPath, outputPath string, deleteOriginals bool) error {
+// progressFn, if not nil, is called as ffmpeg writes the output; cancelling ctx stops it.
+func MergeVideoAudio(ctx context.Context, videoPath, audioPath, outputPath string, chapters []extractor.Chapter, deleteOriginals bool, progressFn ffmpeg.ProgressFunc) error {
 	if !FFmpegAvailable() {
 		return fmt.Errorf("ffmpeg not found in PATH")
 	}
 
-	// Run ffmpeg with stream copy (fast, no re-encoding)
-	// -y: overwrite output file without asking
-	cmd := exec.Command("ffmpeg",
+	// The merged file is about as large as both inputs together
+	if err := CheckDiskSpace(outputPath, fileSize(videoPath)+fileSize(audioPath)); err != nil {
+		return err
+	}
+
+	args := []string{
 		"-threads", "1",
 		"-i", videoPath,
 		"-i", audioPath,
-		"-c", "copy",
-		"-y",
-		outputPath,
-	)
+	}
+	if len(chapters) > 0 {
+		metaPath, err := writeChapterMetadata(chapters)
+		if err != nil {
+			return fmt.Errorf("failed to write chapters: %w", err)
+		}
+		defer os.Remove(metaPath)
+		args = append(args, "-i", metaPath, "-map_chapters", "2")
+	}
 
-	output, err := cmd.CombinedOutput()
+	// Run ffmpeg with stream copy (fast, no re-encoding)
+	// -y: overwrite output file without asking
+	partPath := ffmpegPartPath(outputPath)
+	args = append(args, "-c", "copy", "-y", partPath)
+
+	output, err := ffmpeg.Run(ctx, args, progressFn)
 	if err != nil {
+		os.Remove(partPath)
 		return fmt.Errorf("ffmpeg merge failed: %w\nOutput: %s", err, string(output))
 	}
+	if err := commitPart(partPath, outputPath); err != nil {
+		return err
+	}
 
 	// Delete original files if requested
 	if deleteOriginals {
@@ -52,23 +74,90 @@ func MergeVideoAudio(videoPath, audioPath, outputPath string, deleteOriginals bo
 	return nil
 }
 
+// ConcatToMp4 joins media files, in order, into a faststart MP4 using
+// ffmpeg's concat demuxer with stream copy. Inputs must share the same
+// codecs, as the segments of one HLS rendition do.
+func ConcatToMp4(ctx context.Context, inputs []string, outputPath string) error {
+	if !FFmpegAvailable() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+
+	var need int64
+	for _, input := range inputs {
+		need += fileSize(input)
+	}
+	if err := CheckDiskSpace(outputPath, need); err != nil {
+		return err
+	}
+
+	list, err := os.CreateTemp("", "vget-concat-*.txt")
+	if err != nil {
+		return fmt.Errorf("failed to write concat list: %w", err)
+	}
+	defer os.Remove(list.Name())
+	for _, input := range inputs {
+		if abs, err := filepath.Abs(input); err == nil {
+			input = abs
+		}
+		// Single quotes are escaped as '\'' inside the quoted path
+		fmt.Fprintf(list, "file '%s'\n", strings.ReplaceAll(input, "'", `'\''`))
+	}
+	if err := list.Close(); err != nil {
+		return fmt.Errorf("failed to write concat list: %w", err)
+	}
+
+	partPath := ffmpegPartPath(outputPath)
+	cmd := exec.CommandContext(ctx, ffmpeg.Binary(),
+		"-threads", "1",
+		"-f", "concat", "-safe", "0",
+		"-i", list.Name(),
+		"-c", "copy", "-movflags", "+faststart", "-y", partPath,
+	)
+	output, err := cmd.CombinedOutput()
+	if err != nil {
+		os.Remove(partPath)
+		return fmt.Errorf("ffmpeg concat failed: %w\nOutput: %s", err, string(output))
+	}
+	return commitPart(partPath, outputPath)
+}
+
+// fileSize returns the size of path, or 0 if it can't be read
+func fileSize(path string) int64 {
+	info, err := os.Stat(path)
+	if err != nil {
+		return 0
+	}
+	return info.Size()
+}
+
+// ffmpegPartPath is where ffmpeg writes before the result is moved to
+// outputPath. The extension stays last so ffmpeg still picks the container
+// from it.
+func ffmpegPartPath(outputPath string) string {
+	ext := filepath.Ext(outputPath)
+	return strings.TrimSuffix(outputPath, ext) + partSuffix + ext
+}
+
 // MergeVideoAudioKeepOriginals merges video and audio into a new file with "(merged)" prefix.
 // Original video and audio files are kept.
 // Returns the path to the merged file.
-func MergeVideoAudioKeepOriginals(videoPath, audioPath string) (string, error) {
+func MergeVideoAudioKeepOriginals(ctx context.Context, videoPath, audioPath string, chapters []extractor.Chapter, progressFn ffmpeg.ProgressFunc) (string, error) {
 	if !FFmpegAvailable() {
 		return "", fmt.Errorf("ffmpeg not found in PATH")
 	}
 
-	// Build merged output path with "(merged)" prefix
-	dir := filepath.Dir(videoPath)
-	filename := filepath.Base(videoPath)
-	mergedPath := filepath.Join(dir, "(merged)"+filename)
+	mergedPath := MergedPath(videoPath)
 
 	// Merge to new file, keep originals
-	if err := MergeVideoAudio(videoPath, audioPath, mergedPath, false); err != nil {
+	if err := MergeVideoAudio(ctx, videoPath, audioPath, mergedPath, chapters, false, progressFn); err != nil {
 		return "", err
 	}
 
 	return mergedPath, nil
 }
+
+// MergedPath is where MergeVideoAudioKeepOriginals writes the merge of
+// videoPath: the same name with a "(merged)" prefix
+func MergedPath(videoPath string) string {
+	return filepath.Join(filepath.Dir(videoPath), "(merged)"+filepath.Base(videoPath))
+}
@@ -4,21 +4,27 @@ import (
 	"context"
 	"crypto/aes"
 	"crypto/cipher"
+	"encoding/binary"
 	"encoding/hex"
 	"fmt"
 	"io"
 	"net/http"
 	"os"
-	"os/exec"
+	"path/filepath"
 	"strings"
 	"sync"
 	"sync/atomic"
 	"time"
 
 	tea "github.com/charmbracelet/bubbletea"
-	"github.com/guiyumin/vget/internal/core/config"
 )
 
+// hlsPartsSuffix names the directory holding a stream's segments until they are joined
+const hlsPartsSuffix = ".parts"
+
+// hlsSegmentRetries is how many times a failed segment is fetched again
+const hlsSegmentRetries = 3
+
 // HLSConfig holds configuration for HLS downloads
 type HLSConfig struct {
 	Workers    int // Number of parallel segment downloads
@@ -56,25 +62,75 @@ func (s *hlsState) incDownloaded() {
 	atomic.AddInt64(&s.downloaded, 1)
 }
 
+// estimatedTotal extrapolates the stream size from the segments done so far,
+// or returns -1 before the first one finishes
+func (s *hlsState) estimatedTotal() int64 {
+	downloaded, total := s.getProgress()
+	bytes := s.getBytes()
+	if downloaded == 0 || bytes == 0 {
+		return -1
+	}
+	return bytes * total / downloaded
+}
+
+// hlsDownloader fetches the segments of a media playlist in parallel,
+// decrypting AES-128 segments, then joins them with ffmpeg
+type hlsDownloader struct {
+	client   *http.Client
+	headers  map[string]string
+	workers  int
+	state    *hlsState
+	progress func(done, total, bytes int64) // called periodically while segments download
+
+	keysMu sync.Mutex
+	keys   map[string][]byte // by key URL
+}
+
+func newHLSDownloader(config HLSConfig, headers map[string]string) *hlsDownloader {
+	return &hlsDownloader{
+		client: &http.Client{
+			Timeout: 60 * time.Second,
+			Transport: &http.Transport{
+				Proxy:               proxyFunc(),
+				MaxIdleConnsPerHost: config.Workers * 2,
+				DisableCompression:  true,
+			},
+		},
+		headers: headers,
+		workers: config.Workers,
+		state:   &hlsState{},
+		keys:    make(map[string][]byte),
+	}
+}
+
 // RunHLSDownloadTUI downloads an HLS stream with TUI progress
-func RunHLSDownloadTUI(m3u8URL, output, displayID, lang string) error {
+func RunHLSDownloadTUI(m3u8URL, output, displayID, lang string) (string, error) {
 	return RunHLSDownloadWithHeadersTUI(m3u8URL, output, displayID, lang, nil)
 }
 
-// RunHLSDownloadWithHeadersTUI downloads an HLS stream with custom headers and TUI progress
-func RunHLSDownloadWithHeadersTUI(m3u8URL, output, displayID, lang string, headers map[string]string) error {
+// RunHLSDownloadWithHeadersTUI downloads an HLS stream with custom headers and
+// TUI progress. It returns the final path, which is an .mp4 when ffmpeg
+// joined the segments.
+func RunHLSDownloadWithHeadersTUI(m3u8URL, output, displayID, lang string, headers map[string]string) (string, error) {
 	state := &downloadState{startTime: time.Now()}
 	ctx, cancel := context.WithCancel(context.Background())
 	defer cancel()
 
+	d := newHLSDownloader(DefaultHLSConfig(), headers)
+	d.progress = func(done, total, bytes int64) {
+		state.update(bytes, d.state.estimatedTotal())
+		state.setPlaylistProgress(done, total)
+	}
+
 	// Start download in background
 	go func() {
-		err := downloadHLSWithHeaders(ctx, m3u8URL, output, state, DefaultHLSConfig(), headers)
+		finalPath, err := d.download(ctx, m3u8URL, output)
 		if err != nil {
 			state.setError(err)
-		} else {
-			state.setDone()
+			return
 		}
+		state.setFinalPath(finalPath)
+		state.setDone()
 	}()
 
 	// Run TUI
@@ -82,301 +138,334 @@ func RunHLSDownloadWithHeadersTUI(m3u8URL, output, displayID, lang string, heade
 	p := tea.NewProgram(model)
 	finalModel, err := p.Run()
 	if err != nil {
-		return err
+		return "", err
+	}
+	if state.isCancelled() {
+		return "", ErrCancelled
 	}
 
 	m := finalModel.(downloadModel)
 	_, _, _, _, downloadErr := m.state.get()
 	if downloadErr != nil {
-		return downloadErr
+		return "", downloadErr
 	}
+	return state.getFinalPath(), nil
+}
 
-	// Convert .ts to .mp4 in Docker environment
-	mp4Path, err := convertTsToMp4(output)
-	if err != nil {
-		// Log warning but don't fail - the .ts file is still usable
-		fmt.Printf("Warning: %v\n", err)
-	} else if mp4Path != output {
-		fmt.Printf("Converted to: %s\n", mp4Path)
+// DownloadHLSWithProgress downloads an HLS stream with a progress callback (for server use)
+// Returns the final output path (.mp4 if ffmpeg joined the segments) and error
+func DownloadHLSWithProgress(ctx context.Context, m3u8URL, output string, headers map[string]string, progressFn func(downloaded, total int64)) (string, error) {
+	d := newHLSDownloader(DefaultHLSConfig(), headers)
+	if progressFn != nil {
+		d.progress = func(done, total, bytes int64) {
+			progressFn(bytes, d.state.estimatedTotal())
+		}
 	}
-
-	return nil
+	return d.download(ctx, m3u8URL, output)
 }
 
- 
+// downloadSegmentList fetches the segments of a media playlist and joins them
+// byte for byte into output, keeping its name. It serves segmented DASH
+// streams, whose fragments need no remuxing, through the plain download path.
+func downloadSegmentList(ctx context.Context, m3u8URL, output string, headers map[string]string, state *downloadState) error {
+	d := newHLSDownloader(DefaultHLSConfig(), headers)
+	d.progress = func(done, total, bytes int64) {
+		state.update(bytes, d.state.estimatedTotal())
+		state.setPlaylistProgress(done, total)
+	}
 
-// downloadHLSWithHeaders downloads an HLS stream with custom headers
-func downloadHLSWithHeaders(ctx context.Context, m3u8URL, output string, state *downloadState, config HLSConfig, headers map[string]string) error {
-	// Parse the m3u8 playlist
-	playlist, err := ParseM3U8WithHeaders(m3u8URL, headers)
+	partsDir := output + hlsPartsSuffix
+	initPath, paths, err := d.fetch(ctx, m3u8URL, partsDir)
 	if err != nil {
-		return fmt.Errorf("failed to parse m3u8: %w", err)
+		return err
 	}
-
-	// If master playlist, get the best variant and parse it
-	if playlist.IsMaster {
-		variant := playlist.SelectBestVariant()
-		if variant == nil {
-			return fmt.Errorf("no variants found in master playlist")
-		}
-		playlist, err = ParseM3U8WithHeaders(variant.URL, headers)
-		if err != nil {
-			return fmt.Errorf("failed to parse variant playlist: %w", err)
-		}
+	if initPath != "" {
+		paths = append([]string{initPath}, paths...)
 	}
-
-	if len(playlist.Segments) == 0 {
-		return fmt.Errorf("no segments found in playlist")
+	if err := concatFiles(paths, output); err != nil {
+		return err
 	}
+	os.RemoveAll(partsDir)
+	return nil
+}
 
-	// Get encryption key if needed
-	var decryptKey []byte
-	var decryptIV []byte
-	if playlist.IsEncrypted && playlist.KeyURL != "" {
-		decryptKey, err = fetchKeyWithHeaders(playlist.KeyURL, headers)
-		if err != nil {
-			return fmt.Errorf("failed to fetch encryption key: %w", err)
-		}
-		if playlist.KeyIV != "" {
-			decryptIV, _ = hex.DecodeString(playlist.KeyIV)
-		}
+// download fetches the stream into output and returns the path of the joined file
+func (d *hlsDownloader) download(ctx context.Context, m3u8URL, output string) (string, error) {
+	partsDir := output + hlsPartsSuffix
+	initPath, paths, err := d.fetch(ctx, m3u8URL, partsDir)
+	if err != nil {
+		return "", err
 	}
+	finalPath, err := joinHLSSegments(ctx, initPath, paths, output)
+	if err != nil {
+		return "", err
+	}
+	os.RemoveAll(partsDir)
+	return finalPath, nil
+}
 
-	// Create output file
-	file, err := os.Create(output)
+// fetch downloads the stream's segments into partsDir and returns the paths
+// of its initialization segment, if any, and of its media segments in order
+func (d *hlsDownloader) fetch(ctx context.Context, m3u8URL, partsDir string) (string, []string, error) {
+	playlist, err := d.mediaPlaylist(m3u8URL)
 	if err != nil {
-		return fmt.Errorf("failed to create output file: %w", err)
+		return "", nil, err
 	}
-	defer file.Close()
+	for _, seg := range playlist.Segments {
+		if seg.Key != nil && seg.Key.Method != "AES-128" {
+			return "", nil, fmt.Errorf("unsupported HLS encryption method %s", seg.Key.Method)
+		}
+	}
+	d.state.totalSegments = int64(len(playlist.Segments))
 
-	// Set up progress tracking
-	// For HLS we estimate total size (unknown until download complete)
-	// We'll use segment count for progress
-	totalSegments := int64(len(playlist.Segments))
-	hlsState := &hlsState{totalSegments: totalSegments}
+	// Segments stay in a directory next to the output until they are joined,
+	// so an interrupted download only fetches the ones still missing
+	if err := os.MkdirAll(partsDir, 0755); err != nil {
+		return "", nil, fmt.Errorf("failed to create segment directory: %w", err)
+	}
 
-	// Progress updater
-	progressDone := make(chan struct{})
-	go func() {
-		ticker := time.NewTicker(100 * time.Millisecond)
-		defer ticker.Stop()
-		for {
-			select {
-			case <-progressDone:
-				return
-			case <-ticker.C:
-				downloaded, total := hlsState.getProgress()
-				bytes := hlsState.getBytes()
-				// Estimate total bytes based on progress
-				if downloaded > 0 {
-					estimatedTotal := bytes * total / downloaded
-					state.update(bytes, estimatedTotal)
+	if d.progress != nil {
+		progressDone := make(chan struct{})
+		defer close(progressDone)
+		go func() {
+			ticker := time.NewTicker(200 * time.Millisecond)
+			defer ticker.Stop()
+			for {
+				select {
+				case <-progressDone:
+					return
+				case <-ticker.C:
+					done, total := d.state.getProgress()
+					d.progress(done, total, d.state.getBytes())
 				}
 			}
+		}()
+	}
+
+	var initPath string
+	if playlist.InitURL != "" {
+		initPath = filepath.Join(partsDir, "init.mp4")
+		if err := d.fetchToFile(ctx, playlist.InitURL, nil, initPath); err != nil {
+			return "", nil, fmt.Errorf("failed to download initialization segment: %w", err)
 		}
-	}()
-	defer close(progressDone)
+	}
 
-	// Download segments
-	// We need to maintain order, so we download in parallel but write sequentially
-	err = downloadSegmentsOrdered(ctx, playlist.Segments, file, decryptKey, decryptIV, hlsState, config, headers)
-	if err != nil {
-		return err
+	if err := d.downloadSegments(ctx, playlist.Segments, partsDir); err != nil {
+		return "", nil, err
+	}
+	if d.progress != nil {
+		done, total := d.state.getProgress()
+		d.progress(done, total, d.state.getBytes())
 	}
 
-	return nil
+	paths := make([]string, len(playlist.Segments))
+	for i, seg := range playlist.Segments {
+		paths[i] = segmentPath(partsDir, seg.Index)
+	}
+	return initPath, paths, nil
 }
 
-// downloadSegmentsOrdered downloads segments in parallel but writes them in order
-func downloadSegmentsOrdered(ctx context.Context, segments []Segment, file *os.File,
-	decryptKey, decryptIV []byte, hlsState *hlsState, config HLSConfig, headers map[string]string) error {
-
-	type segmentResult struct {
-		index int
-		data  []byte
-		err   error
+// mediaPlaylist parses m3u8URL, following a master playlist to its best variant
+func (d *hlsDownloader) mediaPlaylist(m3u8URL string) (*M3U8Playlist, error) {
+	playlist, err := ParseM3U8WithHeaders(m3u8URL, d.headers)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse m3u8: %w", err)
 	}
 
-	// Buffer to hold downloaded segments waiting to be written
-	results := make(map[int][]byte)
-	resultsChan := make(chan segmentResult, config.Workers)
-	var resultsLock sync.Mutex
-
-	// Segment queue
-	segmentChan := make(chan Segment, len(segments))
-	for _, seg := range segments {
-		segmentChan <- seg
+	if playlist.IsMaster {
+		variant := playlist.SelectBestVariant()
+		if variant == nil {
+			return nil, fmt.Errorf("no variants found in master playlist")
+		}
+		playlist, err = ParseM3U8WithHeaders(variant.URL, d.headers)
+		if err != nil {
+			return nil, fmt.Errorf("failed to parse variant playlist: %w", err)
+		}
 	}
-	close(segmentChan)
 
-	// Create HTTP client
-	client := &http.Client{
-		Timeout: 60 * time.Second,
-		Transport: &http.Transport{
-			Proxy:               http.ProxyFromEnvironment,
-			MaxIdleConnsPerHost: config.Workers * 2,
-			DisableCompression:  true,
-		},
+	if len(playlist.Segments) == 0 {
+		return nil, fmt.Errorf("no segments found in playlist")
 	}
+	return playlist, nil
+}
+
+// downloadSegments fetches every segment not already in dir, stopping at the
+// first one that still fails after retries
+func (d *hlsDownloader) downloadSegments(ctx context.Context, segments []Segment, dir string) error {
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	segmentChan := make(chan Segment)
+	errChan := make(chan error, 1)
 
-	// Start workers
 	var wg sync.WaitGroup
-	for i := 0; i < config.Workers; i++ {
+	for i := 0; i < d.workers; i++ {
 		wg.Add(1)
 		go func() {
 			defer wg.Done()
 			for seg := range segmentChan {
-				select {
-				case <-ctx.Done():
+				if err := d.fetchToFile(ctx, seg.URL, &seg, segmentPath(dir, seg.Index)); err != nil {
+					select {
+					case errChan <- fmt.Errorf("segment %d: %w", seg.Index, err):
+					default:
+					}
+					cancel()
 					return
-				default:
-				}
-
-				data, err := downloadSegment(client, seg.URL, decryptKey, decryptIV, seg.Index, headers)
-				resultsChan <- segmentResult{
-					index: seg.Index,
-					data:  data,
-					err:   err,
 				}
 			}
 		}()
 	}
 
-	// Close results channel when all workers done
-	go func() {
-		wg.Wait()
-		close(resultsChan)
-	}()
-
-	// Collect results and write in order
-	nextIndex := 0
-	var writeErr error
-
-	for result := range resultsChan {
-		if result.err != nil {
-			writeErr = result.err
+feed:
+	for _, seg := range segments {
+		// Segments left by an earlier attempt are complete; partial ones
+		// only ever exist under a .tmp name
+		if info, err := os.Stat(segmentPath(dir, seg.Index)); err == nil {
+			d.state.addBytes(info.Size())
+			d.state.incDownloaded()
 			continue
 		}
+		select {
+		case segmentChan <- seg:
+		case <-ctx.Done():
+			break feed
+		}
+	}
+	close(segmentChan)
+	wg.Wait()
 
-		resultsLock.Lock()
-		results[result.index] = result.data
-		hlsState.incDownloaded()
-
-		// Write all consecutive segments we have
-		for {
-			if data, ok := results[nextIndex]; ok {
-				_, err := file.Write(data)
-				if err != nil {
-					writeErr = err
-					resultsLock.Unlock()
-					break
-				}
-				hlsState.addBytes(int64(len(data)))
-				delete(results, nextIndex)
-				nextIndex++
-			} else {
-				break
+	select {
+	case err := <-errChan:
+		return err
+	default:
+		return ctx.Err()
+	}
+}
+
+// fetchToFile downloads url, decrypting it if seg has a key, and writes it to path
+func (d *hlsDownloader) fetchToFile(ctx context.Context, url string, seg *Segment, path string) error {
+	if _, err := os.Stat(path); err == nil && seg == nil {
+		return nil
+	}
+
+	var data []byte
+	var err error
+	for attempt := 0; attempt <= hlsSegmentRetries; attempt++ {
+		if attempt > 0 {
+			select {
+			case <-time.After(time.Duration(attempt) * time.Second):
+			case <-ctx.Done():
+				return ctx.Err()
 			}
 		}
-		resultsLock.Unlock()
+		if data, err = d.get(ctx, url); err == nil || ctx.Err() != nil {
+			break
+		}
+	}
+	if err != nil {
+		return err
 	}
 
-	if writeErr != nil {
-		return fmt.Errorf("failed to write segment: %w", writeErr)
+	if seg != nil && seg.Key != nil {
+		key, err := d.key(ctx, seg.Key.URL)
+		if err != nil {
+			return err
+		}
+		iv, err := segmentIV(seg)
+		if err != nil {
+			return err
+		}
+		if data, err = decryptAES128(data, key, iv); err != nil {
+			return fmt.Errorf("failed to decrypt: %w", err)
+		}
 	}
 
+	tmp := path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0644); err != nil {
+		return err
+	}
+	if err := os.Rename(tmp, path); err != nil {
+		return err
+	}
+	if seg != nil {
+		d.state.addBytes(int64(len(data)))
+		d.state.incDownloaded()
+	}
 	return nil
 }
 
-// downloadSegment downloads a single segment
-func downloadSegment(client *http.Client, url string, decryptKey, decryptIV []byte, index int, headers map[string]string) ([]byte, error) {
-	req, err := http.NewRequest("GET", url, nil)
+func (d *hlsDownloader) get(ctx context.Context, url string) ([]byte, error) {
+	req, err := http.NewRequestWithContext(ctx, "GET", url, nil)
 	if err != nil {
 		return nil, err
 	}
 	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
 
 	// Apply custom headers
-	for key, value := range headers {
+	for key, value := range d.headers {
 		req.Header.Set(key, value)
 	}
 
-	resp, err := client.Do(req)
+	resp, err := d.client.Do(req)
 	if err != nil {
 		return nil, err
 	}
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
-		return nil, fmt.Errorf("segment %d returned status %d", index, resp.StatusCode)
+		return nil, fmt.Errorf("server returned status %d", resp.StatusCode)
 	}
+	return io.ReadAll(resp.Body)
+}
 
-	data, err := io.ReadAll(resp.Body)
+// key returns the AES-128 key at url, fetching it once per download
+func (d *hlsDownloader) key(ctx context.Context, url string) ([]byte, error) {
+	d.keysMu.Lock()
+	defer d.keysMu.Unlock()
+
+	if key, ok := d.keys[url]; ok {
+		return key, nil
+	}
+	key, err := d.get(ctx, url)
 	if err != nil {
-		return nil, err
+		return nil, fmt.Errorf("failed to fetch encryption key: %w", err)
 	}
-
-	// Decrypt if needed
-	if decryptKey != nil {
-		data, err = decryptAES128(data, decryptKey, decryptIV, index)
-		if err != nil {
-			return nil, fmt.Errorf("failed to decrypt segment %d: %w", index, err)
-		}
+	if len(key) != aes.BlockSize {
+		return nil, fmt.Errorf("encryption key is %d bytes, expected %d", len(key), aes.BlockSize)
 	}
+	d.keys[url] = key
+	return key, nil
+}
 
-	return data, nil
+func segmentPath(dir string, index int) string {
+	return filepath.Join(dir, fmt.Sprintf("%05d.ts", index))
 }
 
-// fetchKeyWithHeaders fetches the encryption key from the URL with custom headers
-func fetchKeyWithHeaders(url string, headers map[string]string) ([]byte, error) {
-	client := &http.Client{
-		Timeout: 30 * time.Second,
-		Transport: &http.Transport{
-			Proxy: http.ProxyFromEnvironment,
-		},
+// segmentIV returns the key's IV, or the segment's media sequence number as a
+// big-endian 128-bit integer when the playlist doesn't give one (per HLS spec)
+func segmentIV(seg *Segment) ([]byte, error) {
+	iv := make([]byte, aes.BlockSize)
+	if seg.Key.IV == "" {
+		binary.BigEndian.PutUint64(iv[8:], uint64(seg.Sequence))
+		return iv, nil
 	}
-
-	req, err := http.NewRequest("GET", url, nil)
-	if err != nil {
-		return nil, err
+	if len(seg.Key.IV) > 2*aes.BlockSize {
+		return nil, fmt.Errorf("invalid IV %q", seg.Key.IV)
 	}
-	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-
-	// Apply custom headers
-	for key, value := range headers {
-		req.Header.Set(key, value)
-	}
-
-	resp, err := client.Do(req)
+	raw, err := hex.DecodeString(strings.Repeat("0", 2*aes.BlockSize-len(seg.Key.IV)) + seg.Key.IV)
 	if err != nil {
-		return nil, err
-	}
-	defer resp.Body.Close()
-
-	if resp.StatusCode != http.StatusOK {
-		return nil, fmt.Errorf("key server returned status %d", resp.StatusCode)
+		return nil, fmt.Errorf("invalid IV %q", seg.Key.IV)
 	}
-
-	return io.ReadAll(resp.Body)
+	return raw, nil
 }
 
 // decryptAES128 decrypts AES-128-CBC encrypted data
-func decryptAES128(data, key, iv []byte, segmentIndex int) ([]byte, error) {
+func decryptAES128(data, key, iv []byte) ([]byte, error) {
 	block, err := aes.NewCipher(key)
 	if err != nil {
 		return nil, err
 	}
 
-	// If no IV provided, use segment index as IV (per HLS spec)
-	if iv == nil {
-		iv = make([]byte, 16)
-		// Segment sequence number as big-endian 128-bit integer
-		iv[15] = byte(segmentIndex)
-		iv[14] = byte(segmentIndex >> 8)
-		iv[13] = byte(segmentIndex >> 16)
-		iv[12] = byte(segmentIndex >> 24)
-	}
-
 	if len(data)%aes.BlockSize != 0 {
 		return nil, fmt.Errorf("ciphertext is not a multiple of block size")
 	}
@@ -395,146 +484,53 @@ func decryptAES128(data, key, iv []byte, segmentIndex int) ([]byte, error) {
 	return data, nil
 }
 
-// DownloadHLSWithProgress downloads an HLS stream with a progress callback (for server use)
-// Returns the final output path (may be .mp4 if converted in Docker) and error
-func DownloadHLSWithProgress(ctx context.Context, m3u8URL, output string, headers map[string]string, progressFn func(downloaded, total int64)) (string, error) {
-	hlsConfig := DefaultHLSConfig()
+// joinHLSSegments combines the downloaded segments into the final file. MPEG-TS
+// segments are concatenated into an .mp4 by ffmpeg when it's installed;
+// otherwise, and for fragmented MP4 streams, the bytes are joined in order.
+func joinHLSSegments(ctx context.Context, initPath string, segments []string, output string) (string, error) {
+	mp4Path := strings.TrimSuffix(output, filepath.Ext(output)) + ".mp4"
 
-	// Parse the m3u8 playlist
-	playlist, err := ParseM3U8WithHeaders(m3u8URL, headers)
-	if err != nil {
-		return "", fmt.Errorf("failed to parse m3u8: %w", err)
+	if initPath != "" {
+		// Fragmented MP4 segments form a valid file once the init segment is in front
+		return mp4Path, concatFiles(append([]string{initPath}, segments...), mp4Path)
 	}
 
-	// If master playlist, get the best variant and parse it
-	if playlist.IsMaster {
-		variant := playlist.SelectBestVariant()
-		if variant == nil {
-			return "", fmt.Errorf("no variants found in master playlist")
+	if FFmpegAvailable() {
+		err := ConcatToMp4(ctx, segments, mp4Path)
+		if err == nil {
+			return mp4Path, nil
 		}
-		playlist, err = ParseM3U8WithHeaders(variant.URL, headers)
-		if err != nil {
-			return "", fmt.Errorf("failed to parse variant playlist: %w", err)
+		if ctx.Err() != nil {
+			return "", ctx.Err()
 		}
+		// Keep the stream as MPEG-TS instead
+		fmt.Printf("Warning: %v\n", err)
 	}
+	return output, concatFiles(segments, output)
+}
 
-	if len(playlist.Segments) == 0 {
-		return "", fmt.Errorf("no segments found in playlist")
+// concatFiles writes the contents of inputs, in order, to output
+func concatFiles(inputs []string, output string) error {
+	partPath := output + partSuffix
+	out, err := os.Create(partPath)
+	if err != nil {
+		return fmt.Errorf("failed to create output file: %w", err)
 	}
+	defer out.Close()
 
-	// Get encryption key if needed
-	var decryptKey []byte
-	var decryptIV []byte
-	if playlist.IsEncrypted && playlist.KeyURL != "" {
-		decryptKey, err = fetchKeyWithHeaders(playlist.KeyURL, headers)
+	for _, path := range inputs {
+		in, err := os.Open(path)
 		if err != nil {
-			return "", fmt.Errorf("failed to fetch encryption key: %w", err)
-		}
-		if playlist.KeyIV != "" {
-			decryptIV, _ = hex.DecodeString(playlist.KeyIV)
+			return err
 		}
-	}
-
-	// Create output file
-	file, err := os.Create(output)
-	if err != nil {
-		return "", fmt.Errorf("failed to create output file: %w", err)
-	}
-
-	// Set up progress tracking using segment count
-	totalSegments := int64(len(playlist.Segments))
-	hlsState := &hlsState{totalSegments: totalSegments}
-
-	// Progress updater goroutine
-	progressDone := make(chan struct{})
-	go func() {
-		ticker := time.NewTicker(200 * time.Millisecond)
-		defer ticker.Stop()
-		for {
-			select {
-			case <-progressDone:
-				return
-			case <-ctx.Done():
-				return
-			case <-ticker.C:
-				if progressFn != nil {
-					downloaded, total := hlsState.getProgress()
-					bytes := hlsState.getBytes()
-					// Report actual bytes with estimated total based on segment progress
-					if downloaded > 0 && bytes > 0 && total > 0 {
-						estimatedTotal := bytes * total / downloaded
-						progressFn(bytes, estimatedTotal)
-					} else {
-						// No estimate yet, report bytes downloaded with unknown total
-						progressFn(bytes, -1)
-					}
-				}
-			}
+		_, err = io.Copy(out, in)
+		in.Close()
+		if err != nil {
+			return fmt.Errorf("failed to write segment: %w", err)
 		}
-	}()
-	defer close(progressDone)
-
-	// Download segments
-	err = downloadSegmentsOrdered(ctx, playlist.Segments, file, decryptKey, decryptIV, hlsState, hlsConfig, headers)
-	if err != nil {
-		file.Close()
-		return "", err
 	}
-
-	// Close file before conversion (ffmpeg needs exclusive access)
-	file.Close()
-
-	// Final progress update - download complete
-	if progressFn != nil {
-		finalBytes := hlsState.getBytes()
-		progressFn(finalBytes, finalBytes)
-	}
-
-	// Convert .ts to .mp4 in Docker environment
-	finalPath, convErr := convertTsToMp4(output)
-	if convErr != nil {
-		// Log warning but don't fail - the .ts file is still usable
-		fmt.Printf("Warning: %v\n", convErr)
-		return output, nil
-	}
-
-	return finalPath, nil
-}
-
-// convertTsToMp4 converts a .ts file to .mp4 using ffmpeg (copy, no re-encoding)
-// Only runs in Docker environment where ffmpeg is available
-// Returns the new .mp4 path if conversion succeeded, otherwise returns original path
-func convertTsToMp4(tsPath string) (string, error) {
-	// Only convert in Docker
-	if !config.IsRunningInDocker() {
-		return tsPath, nil
-	}
-
-	// Only convert .ts files
-	if !strings.HasSuffix(strings.ToLower(tsPath), ".ts") {
-		return tsPath, nil
-	}
-
-	// Build mp4 output path
-	mp4Path := strings.TrimSuffix(tsPath, ".ts") + ".mp4"
-	if strings.HasSuffix(tsPath, ".TS") {
-		mp4Path = strings.TrimSuffix(tsPath, ".TS") + ".mp4"
-	}
-
-	// Run ffmpeg with stream copy (fast, no re-encoding)
-	// -threads 1: Limit threads to work on resource-constrained systems (Docker, low ulimits)
-	cmd := exec.Command("ffmpeg", "-threads", "1", "-i", tsPath, "-c", "copy", "-y", mp4Path)
-	output, err := cmd.CombinedOutput()
-	if err != nil {
-		// Conversion failed, keep the .ts file
-		return tsPath, fmt.Errorf("ffmpeg conversion failed: %w\nOutput: %s", err, string(output))
-	}
-
-	// Conversion succeeded, delete the .ts file
-	if err := os.Remove(tsPath); err != nil {
-		// Log but don't fail - the mp4 was created successfully
-		fmt.Printf("Warning: could not remove original .ts file: %v\n", err)
+	if err := out.Close(); err != nil {
+		return err
 	}
-
-	return mp4Path, nil
+	return commitPart(partPath, output)
 }
@@ -2,6 +2,8 @@ package downloader
 
 import (
 	"bufio"
+	"bytes"
+	"encoding/base64"
 	"fmt"
 	"io"
 	"net/http"
@@ -12,6 +14,9 @@ import (
 	"time"
 )
 
+// playlistDataURLPrefix starts a media playlist packed into a data: URL
+const playlistDataURLPrefix = "data:application/vnd.apple.mpegurl"
+
 // M3U8Playlist represents a parsed m3u8 playlist
 type M3U8Playlist struct {
 	Variants      []Variant // For master playlists
@@ -21,6 +26,8 @@ type M3U8Playlist struct {
 	IsEncrypted   bool      // True if segments are encrypted
 	KeyURL        string    // URL of encryption key
 	KeyIV         string    // Initialization vector for encryption
+	InitURL       string    // EXT-X-MAP initialization segment, for fragmented MP4
+	MediaSequence int64     // Sequence number of the first segment
 }
 
 // Variant represents a stream variant in a master playlist
@@ -38,6 +45,15 @@ type Segment struct {
 	Duration float64
 	Index    int
 	Title    string
+	Sequence int64       // Media sequence number, the default AES-128 IV
+	Key      *SegmentKey // nil if the segment isn't encrypted
+}
+
+// SegmentKey is the EXT-X-KEY in effect for a segment
+type SegmentKey struct {
+	Method string // AES-128 is the only method that can be decrypted
+	URL    string
+	IV     string // hex, without the 0x prefix; empty means use the sequence number
 }
 
 var (
@@ -48,7 +64,7 @@ var (
 	extinfoRegex     = regexp.MustCompile(`#EXTINF:([\d.]+)(?:,(.*))?`)
 	keyMethodRegex   = regexp.MustCompile(`METHOD=([^,]+)`)
 	keyURIRegex      = regexp.MustCompile(`URI="([^"]+)"`)
-	keyIVRegex       = regexp.MustCompile(`IV=0x([0-9a-fA-F]+)`)
+	keyIVRegex       = regexp.MustCompile(`IV=0[xX]([0-9a-fA-F]+)`)
 )
 
 // ParseM3U8 parses an m3u8 playlist from a URL
@@ -56,12 +72,21 @@ func ParseM3U8(m3u8URL string) (*M3U8Playlist, error) {
 	return ParseM3U8WithHeaders(m3u8URL, nil)
 }
 
-// ParseM3U8WithHeaders parses an m3u8 playlist from a URL with custom headers
+// ParseM3U8WithHeaders parses an m3u8 playlist from a URL with custom headers.
+// A data: URL is decoded in place of a request.
 func ParseM3U8WithHeaders(m3u8URL string, headers map[string]string) (*M3U8Playlist, error) {
+	if strings.HasPrefix(m3u8URL, "data:") {
+		content, err := decodeDataURL(m3u8URL)
+		if err != nil {
+			return nil, err
+		}
+		return parseM3U8Content(bytes.NewReader(content), m3u8URL)
+	}
+
 	client := &http.Client{
 		Timeout: 60 * time.Second,
 		Transport: &http.Transport{
-			Proxy:                  http.ProxyFromEnvironment,
+			Proxy:                  proxyFunc(),
 			ResponseHeaderTimeout:  30 * time.Second,
 			IdleConnTimeout:        90 * time.Second,
 		},
@@ -91,6 +116,28 @@ func ParseM3U8WithHeaders(m3u8URL string, headers map[string]string) (*M3U8Playl
 	return parseM3U8Content(resp.Body, m3u8URL)
 }
 
+// isPlaylistDataURL reports whether u is a media playlist packed into a data:
+// URL, the form segmented DASH representations are passed around in
+func isPlaylistDataURL(u string) bool {
+	return strings.HasPrefix(u, playlistDataURLPrefix)
+}
+
+// decodeDataURL returns the content of a base64 or percent-encoded data: URL
+func decodeDataURL(u string) ([]byte, error) {
+	meta, data, ok := strings.Cut(strings.TrimPrefix(u, "data:"), ",")
+	if !ok {
+		return nil, fmt.Errorf("invalid data URL")
+	}
+	if strings.HasSuffix(meta, ";base64") {
+		return base64.StdEncoding.DecodeString(data)
+	}
+	content, err := url.PathUnescape(data)
+	if err != nil {
+		return nil, fmt.Errorf("invalid data URL: %w", err)
+	}
+	return []byte(content), nil
+}
+
 // parseM3U8Content parses m3u8 content from a reader
 func parseM3U8Content(reader io.Reader, baseURL string) (*M3U8Playlist, error) {
 	scanner := bufio.NewScanner(reader)
@@ -98,6 +145,7 @@ func parseM3U8Content(reader io.Reader, baseURL string) (*M3U8Playlist, error) {
 
 	var currentSegmentDuration float64
 	var currentSegmentTitle string
+	var currentKey *SegmentKey
 	var segmentIndex int
 
 	// Parse base URL for resolving relative URLs
@@ -130,16 +178,37 @@ func parseM3U8Content(reader io.Reader, baseURL string) (*M3U8Playlist, error) {
 			continue
 		}
 
-		// Parse encryption key
+		// Parse encryption key; it applies to every following segment until
+		// the next EXT-X-KEY
 		if strings.HasPrefix(line, "#EXT-X-KEY:") {
 			method := extractRegex(keyMethodRegex, line)
-			if method != "NONE" && method != "" {
+			if method == "NONE" || method == "" {
+				currentKey = nil
+				continue
+			}
+			currentKey = &SegmentKey{
+				Method: method,
+				IV:     extractRegex(keyIVRegex, line),
+			}
+			if keyURI := extractRegex(keyURIRegex, line); keyURI != "" {
+				currentKey.URL = resolveURL(base, keyURI)
+			}
+			if !playlist.IsEncrypted {
 				playlist.IsEncrypted = true
-				keyURI := extractRegex(keyURIRegex, line)
-				if keyURI != "" {
-					playlist.KeyURL = resolveURL(base, keyURI)
-				}
-				playlist.KeyIV = extractRegex(keyIVRegex, line)
+				playlist.KeyURL = currentKey.URL
+				playlist.KeyIV = currentKey.IV
+			}
+			continue
+		}
+
+		if strings.HasPrefix(line, "#EXT-X-MEDIA-SEQUENCE:") {
+			playlist.MediaSequence, _ = strconv.ParseInt(strings.TrimPrefix(line, "#EXT-X-MEDIA-SEQUENCE:"), 10, 64)
+			continue
+		}
+
+		if strings.HasPrefix(line, "#EXT-X-MAP:") {
+			if uri := extractRegex(keyURIRegex, line); uri != "" && playlist.InitURL == "" {
+				playlist.InitURL = resolveURL(base, uri)
 			}
 			continue
 		}
@@ -168,6 +237,8 @@ func parseM3U8Content(reader io.Reader, baseURL string) (*M3U8Playlist, error) {
 				Duration: currentSegmentDuration,
 				Index:    segmentIndex,
 				Title:    currentSegmentTitle,
+				Sequence: playlist.MediaSequence + int64(segmentIndex),
+				Key:      currentKey,
 			}
 			playlist.Segments = append(playlist.Segments, segment)
 			playlist.TotalDuration += currentSegmentDuration
@@ -0,0 +1,69 @@
+package downloader
+
+import (
+	"bytes"
+	"encoding/base64"
+	"strings"
+	"testing"
+)
+
+func TestParseM3U8Keys(t *testing.T) {
+	content := `#EXTM3U
+#EXT-X-MEDIA-SEQUENCE:100
+#EXT-X-KEY:METHOD=AES-128,URI="key1.bin"
+#EXTINF:4.0,
+a.ts
+#EXT-X-KEY:METHOD=AES-128,URI="https://keys.example.com/key2.bin",IV=0x0A
+#EXTINF:4.0,
+b.ts
+#EXT-X-KEY:METHOD=NONE
+#EXTINF:4.0,
+c.ts
+`
+	playlist, err := parseM3U8Content(strings.NewReader(content), "https://cdn.example.com/video/index.m3u8")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(playlist.Segments) != 3 {
+		t.Fatalf("got %d segments, want 3", len(playlist.Segments))
+	}
+
+	a, b, c := playlist.Segments[0], playlist.Segments[1], playlist.Segments[2]
+	if a.Key == nil || a.Key.URL != "https://cdn.example.com/video/key1.bin" || a.Sequence != 100 {
+		t.Errorf("segment a: key %+v, sequence %d", a.Key, a.Sequence)
+	}
+	if b.Key == nil || b.Key.URL != "https://keys.example.com/key2.bin" || b.Key.IV != "0A" {
+		t.Errorf("segment b: key %+v", b.Key)
+	}
+	if c.Key != nil {
+		t.Errorf("segment c should be unencrypted, got key %+v", c.Key)
+	}
+
+	iv, err := segmentIV(&a)
+	if err != nil || iv[15] != 100 || !bytes.Equal(iv[:15], make([]byte, 15)) {
+		t.Errorf("segmentIV(a) = %x, %v", iv, err)
+	}
+	iv, err = segmentIV(&b)
+	if err != nil || iv[15] != 0x0A {
+		t.Errorf("segmentIV(b) = %x, %v", iv, err)
+	}
+}
+
+func TestParseM3U8DataURL(t *testing.T) {
+	content := "#EXTM3U\n#EXT-X-MAP:URI=\"https://cdn.example.com/init.mp4\"\n#EXTINF:4.000,\nhttps://cdn.example.com/1.m4s\n#EXT-X-ENDLIST\n"
+	dataURL := playlistDataURLPrefix + ";base64," + base64.StdEncoding.EncodeToString([]byte(content))
+	if !isPlaylistDataURL(dataURL) {
+		t.Fatalf("isPlaylistDataURL(%q) = false", dataURL)
+	}
+
+	playlist, err := ParseM3U8WithHeaders(dataURL, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if playlist.InitURL != "https://cdn.example.com/init.mp4" {
+		t.Errorf("InitURL = %q", playlist.InitURL)
+	}
+	if len(playlist.Segments) != 1 || playlist.Segments[0].URL != "https://cdn.example.com/1.m4s" {
+		t.Errorf("segments = %+v", playlist.Segments)
+	}
+}
@@ -0,0 +1,88 @@
+package downloader
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// tagContainers are the output formats whose tags ffmpeg can write with
+// stream copy
+var tagContainers = map[string]bool{
+	".mp4": true, ".m4v": true, ".m4a": true, ".mov": true, ".mkv": true, ".webm": true,
+	".mp3": true, ".flac": true, ".ogg": true, ".opus": true,
+}
+
+// Metadata is what TagMetadata writes into a downloaded file
+type Metadata struct {
+	Title       string
+	Artist      string // the uploader or channel
+	Date        string // YYYY-MM-DD
+	Description string
+}
+
+// MediaMetadata collects the tags of extracted media
+func MediaMetadata(media extractor.Media) Metadata {
+	md := Metadata{Title: media.GetTitle(), Artist: media.GetUploader()}
+	switch m := media.(type) {
+	case *extractor.VideoMedia:
+		md.Description = m.Description
+		if !m.Published.IsZero() {
+			md.Date = m.Published.Format("2006-01-02")
+		}
+	case *extractor.AudioMedia:
+		md.Description = m.Description
+		if !m.Published.IsZero() {
+			md.Date = m.Published.Format("2006-01-02")
+		}
+	}
+	return md
+}
+
+// metadataArgs are ffmpeg's -metadata options for the tags of md that are
+// set. The description also goes in the comment, which more players show.
+func metadataArgs(md Metadata) []string {
+	var args []string
+	add := func(key, value string) {
+		if value = strings.TrimSpace(value); value != "" {
+			args = append(args, "-metadata", key+"="+value)
+		}
+	}
+	add("title", md.Title)
+	add("artist", md.Artist)
+	add("date", md.Date)
+	add("description", md.Description)
+	add("comment", md.Description)
+	return args
+}
+
+// TagMetadata writes md into the container of the downloaded file at path,
+// remuxing it in place with stream copy. Tags the file already has are kept
+// unless md replaces them; formats without tags, such as MPEG-TS, are left
+// as they are.
+func TagMetadata(ctx context.Context, path string, md Metadata) error {
+	args := metadataArgs(md)
+	if len(args) == 0 || !tagContainers[strings.ToLower(filepath.Ext(path))] {
+		return nil
+	}
+	if !FFmpegAvailable() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if err := CheckDiskSpace(path, fileSize(path)); err != nil {
+		return err
+	}
+
+	partPath := ffmpegPartPath(path)
+	args = append([]string{"-i", path, "-map", "0", "-c", "copy"}, args...)
+	args = append(args, "-y", partPath)
+	if output, err := ffmpeg.Run(ctx, args, nil); err != nil {
+		os.Remove(partPath)
+		return fmt.Errorf("ffmpeg metadata tagging failed: %w\nOutput: %s", err, string(output))
+	}
+	return commitPart(partPath, path)
+}
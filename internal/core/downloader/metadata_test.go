@@ -0,0 +1,32 @@
+package downloader
+
+import (
+	"slices"
+	"testing"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+func TestMetadataArgs(t *testing.T) {
+	md := MediaMetadata(&extractor.AudioMedia{
+		Title:       "Episode 12",
+		Uploader:    " Some Podcast ",
+		Description: "Show notes",
+		Published:   time.Date(2026, 3, 4, 18, 0, 0, 0, time.UTC),
+	})
+	want := []string{
+		"-metadata", "title=Episode 12",
+		"-metadata", "artist=Some Podcast",
+		"-metadata", "date=2026-03-04",
+		"-metadata", "description=Show notes",
+		"-metadata", "comment=Show notes",
+	}
+	if got := metadataArgs(md); !slices.Equal(got, want) {
+		t.Errorf("metadataArgs() = %q, want %q", got, want)
+	}
+
+	if got := metadataArgs(MediaMetadata(&extractor.VideoMedia{Title: "clip"})); !slices.Equal(got, []string{"-metadata", "title=clip"}) {
+		t.Errorf("metadataArgs() with only a title = %q", got)
+	}
+}
@@ -0,0 +1,22 @@
+package downloader
+
+import (
+	"context"
+	"errors"
+)
+
+// TryMirrors calls download with each URL in turn until one succeeds and
+// returns the URL that worked. Cancellation or a full disk ends the fallback
+// early, since another mirror won't help.
+func TryMirrors(ctx context.Context, urls []string, download func(url string) error) (string, error) {
+	err := errors.New("no download URL")
+	for _, u := range urls {
+		if err = download(u); err == nil {
+			return u, nil
+		}
+		if ctx.Err() != nil || errors.Is(err, ErrInsufficientSpace) {
+			break
+		}
+	}
+	return "", err
+}
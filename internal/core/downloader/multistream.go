@@ -145,7 +145,7 @@ func MultiStreamDownload(ctx context.Context, url, output string, config MultiSt
 	client := &http.Client{
 		Timeout: 0,
 		Transport: &http.Transport{
-			Proxy:               http.ProxyFromEnvironment,
+			Proxy:               proxyFunc(),
 			MaxIdleConns:        0,                 // Unlimited idle connections
 			MaxIdleConnsPerHost: config.Streams*2 + 10,
 			MaxConnsPerHost:     0,                 // Unlimited connections per host (like rclone)
@@ -170,13 +170,18 @@ func MultiStreamDownload(ctx context.Context, url, output string, config MultiSt
 
 	// Fall back to single-stream if range not supported
 	if !supportsRange {
-		return downloadWithProgress(client, url, output, state, nil)
+		return downloadWithProgress(ctx, client, url, output, state, nil)
 	}
 
 	state.update(0, totalSize)
 
+	if err := CheckDiskSpace(output, totalSize); err != nil {
+		return err
+	}
+
 	// Create the output file
-	file, err := os.Create(output)
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
 	}
@@ -246,8 +251,11 @@ func MultiStreamDownload(ctx context.Context, url, output string, config MultiSt
 		return fmt.Errorf("download failed with %d errors: %v", len(errs), errs[0])
 	}
 
-	// Close file and rename by magic bytes if needed
+	// Close file, move it into place and rename by magic bytes if needed
 	file.Close()
+	if err := commitPart(partPath, output); err != nil {
+		return err
+	}
 	state.setFinalPath(RenameByMagicBytes(output))
 
 	return nil
@@ -421,6 +429,9 @@ func RunMultiStreamDownloadTUI(url, output, displayID, lang string, config Multi
 		cancel()
 		return err
 	}
+	if state.isCancelled() {
+		return ErrCancelled
+	}
 
 	m := finalModel.(downloadModel)
 	_, _, _, _, downloadErr := m.state.get()
@@ -437,7 +448,7 @@ func MultiStreamDownloadWithAuth(ctx context.Context, url, authHeader, output st
 	client := &http.Client{
 		Timeout: 0,
 		Transport: &http.Transport{
-			Proxy:               http.ProxyFromEnvironment,
+			Proxy:               proxyFunc(),
 			MaxIdleConns:        0,                 // Unlimited idle connections
 			MaxIdleConnsPerHost: config.Streams*2 + 10,
 			MaxConnsPerHost:     0,                 // Unlimited connections per host (like rclone)
@@ -463,8 +474,13 @@ func MultiStreamDownloadWithAuth(ctx context.Context, url, authHeader, output st
 		return downloadWithAuthSingleStream(ctx, client, url, authHeader, output, totalSize, state)
 	}
 
+	if err := CheckDiskSpace(output, totalSize); err != nil {
+		return err
+	}
+
 	// Create the output file
-	file, err := os.Create(output)
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
 	}
@@ -534,8 +550,11 @@ func MultiStreamDownloadWithAuth(ctx context.Context, url, authHeader, output st
 		return fmt.Errorf("download failed with %d errors: %v", len(errs), errs[0])
 	}
 
-	// Close file and rename by magic bytes if needed
+	// Close file, move it into place and rename by magic bytes if needed
 	file.Close()
+	if err := commitPart(partPath, output); err != nil {
+		return err
+	}
 	state.setFinalPath(RenameByMagicBytes(output))
 
 	return nil
@@ -652,7 +671,7 @@ func downloadChunkWithAuthOnce(ctx context.Context, client *http.Client, url, au
 }
 
 // downloadWithAuthSingleStream falls back to single-stream download when Range not supported
-func downloadWithAuthSingleStream(ctx context.Context, client *http.Client, url, authHeader, output string, total int64, state *downloadState) error {
+func downloadWithAuthSingleStream(ctx context.Context, client *http.Client, url, authHeader, output string, total int64, state *downloadState) (err error) {
 	req, err := http.NewRequestWithContext(ctx, "GET", url, nil)
 	if err != nil {
 		return fmt.Errorf("failed to create request: %w", err)
@@ -670,21 +689,32 @@ func downloadWithAuthSingleStream(ctx context.Context, client *http.Client, url,
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
-		return fmt.Errorf("download failed with status %d", resp.StatusCode)
+		return statusError(resp)
 	}
 
 	// Create output file
-	file, err := os.Create(output)
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
 	}
 	defer file.Close()
+	// A single stream can't be resumed, so don't leave its partial file behind
+	defer func() {
+		if err != nil {
+			file.Close()
+			removePart(partPath)
+		}
+	}()
 
 	// Download with progress tracking
 	buf := make([]byte, 128*1024) // 128KB buffer
 	var current int64
 
 	for {
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
 		n, err := resp.Body.Read(buf)
 		if n > 0 {
 			_, writeErr := file.Write(buf[:n])
@@ -702,8 +732,11 @@ func downloadWithAuthSingleStream(ctx context.Context, client *http.Client, url,
 		}
 	}
 
-	// Close file and rename by magic bytes if needed
+	// Close file, move it into place and rename by magic bytes if needed
 	file.Close()
+	if err := commitPart(partPath, output); err != nil {
+		return err
+	}
 	state.setFinalPath(RenameByMagicBytes(output))
 
 	return nil
@@ -736,6 +769,9 @@ func RunMultiStreamDownloadWithAuthTUI(url, authHeader, output, displayID, lang
 		cancel()
 		return err
 	}
+	if state.isCancelled() {
+		return ErrCancelled
+	}
 
 	m := finalModel.(downloadModel)
 	_, _, _, _, downloadErr := m.state.get()
@@ -0,0 +1,109 @@
+package downloader
+
+import (
+	"context"
+	"fmt"
+	"mime"
+	"net/http"
+	"net/url"
+	"path"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/network"
+	"github.com/guiyumin/vget/internal/core/sanitize"
+)
+
+// probeTimeout bounds a probe, which only waits for response headers
+const probeTimeout = 30 * time.Second
+
+// ProbeResult describes a remote file without downloading it
+type ProbeResult struct {
+	URL           string   `json:"url"` // final URL after redirects
+	RedirectChain []string `json:"redirect_chain,omitempty"`
+	Size          int64    `json:"size"` // -1 if unknown
+	ContentType   string   `json:"content_type,omitempty"`
+	AcceptsRanges bool     `json:"accepts_ranges"` // resumable and splittable into segments
+	Filename      string   `json:"filename,omitempty"`
+	LastModified  string   `json:"last_modified,omitempty"`
+}
+
+// Probe requests the first byte of rawURL to learn its size, type, range
+// support, and file name. A server rejecting the range is asked with HEAD.
+// Loopback, private, and link-local addresses are refused, redirects
+// included, as the URL comes from API clients.
+func Probe(ctx context.Context, rawURL string, headers map[string]string) (*ProbeResult, error) {
+	ctx, cancel := context.WithTimeout(ctx, probeTimeout)
+	defer cancel()
+	transport := &http.Transport{Proxy: proxyFunc()}
+	network.ControlDials(transport, network.PublicOnly)
+	client := &http.Client{Transport: transport}
+
+	do := func(method string, ranged bool) (*http.Response, error) {
+		req, err := http.NewRequestWithContext(ctx, method, rawURL, nil)
+		if err != nil {
+			return nil, fmt.Errorf("invalid URL: %w", err)
+		}
+		if len(headers) > 0 {
+			for key, value := range headers {
+				req.Header.Set(key, value)
+			}
+		} else {
+			req.Header.Set("User-Agent", DefaultUserAgent)
+		}
+		if ranged {
+			req.Header.Set("Range", "bytes=0-0")
+		}
+		resp, err := client.Do(req)
+		if err != nil {
+			return nil, fmt.Errorf("probe request failed: %w", err)
+		}
+		// Only the headers are needed
+		resp.Body.Close()
+		return resp, nil
+	}
+
+	resp, err := do(http.MethodGet, true)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK && resp.StatusCode != http.StatusPartialContent {
+		if resp, err = do(http.MethodHead, false); err != nil {
+			return nil, err
+		}
+		if resp.StatusCode != http.StatusOK {
+			return nil, statusError(resp)
+		}
+	}
+
+	result := &ProbeResult{
+		URL:           resp.Request.URL.String(),
+		RedirectChain: RedirectChain(resp),
+		Size:          resp.ContentLength,
+		ContentType:   resp.Header.Get("Content-Type"),
+		AcceptsRanges: resp.Header.Get("Accept-Ranges") == "bytes",
+		Filename:      probeFilename(resp),
+		LastModified:  resp.Header.Get("Last-Modified"),
+	}
+	if resp.StatusCode == http.StatusPartialContent {
+		result.Size = contentRangeTotal(resp.Header.Get("Content-Range"))
+		result.AcceptsRanges = true
+	}
+	return result, nil
+}
+
+// probeFilename names the file from Content-Disposition, else from the last
+// segment of the final URL's path
+func probeFilename(resp *http.Response) string {
+	if _, params, err := mime.ParseMediaType(resp.Header.Get("Content-Disposition")); err == nil {
+		// ParseMediaType decodes filename* into filename
+		if name := path.Base(strings.ReplaceAll(params["filename"], `\`, "/")); name != "" && name != "." && name != "/" {
+			return sanitize.Filename(name)
+		}
+	}
+	name, err := url.PathUnescape(path.Base(resp.Request.URL.Path))
+	if err != nil || name == "" || name == "." || name == "/" {
+		return ""
+	}
+	return sanitize.Filename(name)
+}
@@ -0,0 +1,31 @@
+package downloader
+
+import (
+	"net/http"
+	"net/url"
+	"testing"
+)
+
+func TestProbeFilename(t *testing.T) {
+	tests := []struct {
+		url         string
+		disposition string
+		want        string
+	}{
+		{"https://cdn.example.com/files/report%20v2.pdf?sig=abc", "", "report v2.pdf"},
+		{"https://example.com/download?id=1", `attachment; filename="video.mp4"`, "video.mp4"},
+		{"https://example.com/download?id=1", `attachment; filename*=UTF-8''%E8%A7%86%E9%A2%91.mp4`, "视频.mp4"},
+		{"https://example.com/download", `attachment; filename="../../etc/passwd"`, "passwd"},
+		{"https://example.com/", "", ""},
+	}
+	for _, tt := range tests {
+		u, _ := url.Parse(tt.url)
+		resp := &http.Response{Request: &http.Request{URL: u}, Header: http.Header{}}
+		if tt.disposition != "" {
+			resp.Header.Set("Content-Disposition", tt.disposition)
+		}
+		if got := probeFilename(resp); got != tt.want {
+			t.Errorf("probeFilename(%s, %q) = %q, want %q", tt.url, tt.disposition, got, tt.want)
+		}
+	}
+}
@@ -2,11 +2,13 @@ package downloader
 
 import (
 	"context"
+	"errors"
 	"fmt"
 	"io"
 	"net/http"
 	"os"
 	"path/filepath"
+	"strings"
 	"sync"
 	"time"
 
@@ -14,6 +16,7 @@ import (
 	"github.com/charmbracelet/bubbles/spinner"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	"github.com/guiyumin/vget/internal/core/config"
 	"github.com/guiyumin/vget/internal/core/i18n"
 )
 
@@ -26,18 +29,27 @@ var (
 
 // downloadState holds the shared download state
 type downloadState struct {
-	mu          sync.RWMutex
-	current     int64
-	total       int64
-	speed       float64
-	done        bool
-	err         error
-	startTime   time.Time
-	endTime     time.Time
-	finalSpeed  float64
-	finalPath   string
+	mu            sync.RWMutex
+	current       int64
+	total         int64
+	speed         float64
+	done          bool
+	err           error
+	startTime     time.Time
+	endTime       time.Time
+	finalSpeed    float64
+	finalPath     string
+	segments      []SegmentProgress
+	playlistDone  int64 // HLS segments finished
+	playlistTotal int64
+	resumed       int64 // bytes already on disk when the download started
+	cancelled     bool
 }
 
+// ErrCancelled is returned when the user quits a download's progress display
+// before it finishes
+var ErrCancelled = errors.New("download cancelled")
+
 func (s *downloadState) update(current, total int64) {
 	s.mu.Lock()
 	defer s.mu.Unlock()
@@ -45,17 +57,23 @@ func (s *downloadState) update(current, total int64) {
 	s.total = total
 	elapsed := time.Since(s.startTime).Seconds()
 	if elapsed > 0 {
-		s.speed = float64(current) / elapsed
+		s.speed = float64(current-s.resumed) / elapsed
 	}
 }
 
+func (s *downloadState) setResumed(n int64) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.resumed = n
+}
+
 func (s *downloadState) setDone() {
 	s.mu.Lock()
 	defer s.mu.Unlock()
 	s.endTime = time.Now()
 	elapsed := s.endTime.Sub(s.startTime).Seconds()
 	if elapsed > 0 {
-		s.finalSpeed = float64(s.current) / elapsed
+		s.finalSpeed = float64(s.current-s.resumed) / elapsed
 	}
 	s.done = true
 }
@@ -67,6 +85,21 @@ func (s *downloadState) setError(err error) {
 	s.done = true
 }
 
+// markCancelled records that the user quit before the download finished
+func (s *downloadState) markCancelled() {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if !s.done {
+		s.cancelled = true
+	}
+}
+
+func (s *downloadState) isCancelled() bool {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.cancelled
+}
+
 func (s *downloadState) setFinalPath(path string) {
 	s.mu.Lock()
 	defer s.mu.Unlock()
@@ -79,6 +112,31 @@ func (s *downloadState) getFinalPath() string {
 	return s.finalPath
 }
 
+func (s *downloadState) setSegments(segments []SegmentProgress) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.segments = segments
+}
+
+func (s *downloadState) getSegments() []SegmentProgress {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.segments
+}
+
+func (s *downloadState) setPlaylistProgress(done, total int64) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.playlistDone = done
+	s.playlistTotal = total
+}
+
+func (s *downloadState) getPlaylistProgress() (done, total int64) {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.playlistDone, s.playlistTotal
+}
+
 func (s *downloadState) get() (int64, int64, float64, bool, error) {
 	s.mu.RLock()
 	defer s.mu.RUnlock()
@@ -152,6 +210,7 @@ func (m downloadModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case tea.KeyMsg:
 		switch msg.String() {
 		case "ctrl+c", "q":
+			m.state.markCancelled()
 			return m, tea.Quit
 		}
 
@@ -235,6 +294,16 @@ func (m downloadModel) View() string {
 	// Progress bar
 	s += fmt.Sprintf("  %s\n\n", m.progress.View())
 
+	// One block per connection when the file is fetched in ranges
+	if segments := m.state.getSegments(); len(segments) > 1 {
+		s += fmt.Sprintf("  %s: %s\n\n", m.t.Download.Connections, renderSegments(segments))
+	}
+
+	// Segment count for HLS streams, whose byte total is only an estimate
+	if done, total := m.state.getPlaylistProgress(); total > 0 {
+		s += fmt.Sprintf("  %s: %d/%d\n\n", m.t.Download.Segments, done, total)
+	}
+
 	// Stats
 	if total > 0 {
 		percent := float64(current) / float64(total) * 100
@@ -264,6 +333,21 @@ func (m downloadModel) View() string {
 	return s
 }
 
+// renderSegments draws each segment's progress as a block whose height grows with it
+func renderSegments(segments []SegmentProgress) string {
+	blocks := []rune(" ▁▂▃▄▅▆▇█")
+	var b strings.Builder
+	for _, seg := range segments {
+		size := seg.End - seg.Start + 1
+		level := 0
+		if size > 0 {
+			level = int(seg.Downloaded * int64(len(blocks)-1) / size)
+		}
+		b.WriteRune(blocks[min(level, len(blocks)-1)])
+	}
+	return infoStyle.Render(b.String())
+}
+
 func calculateETA(remaining int64, speed float64) string {
 	if speed <= 0 {
 		return "??:??"
@@ -273,24 +357,25 @@ func calculateETA(remaining int64, speed float64) string {
 }
 
 // RunDownloadTUI runs the download with a TUI progress display
+// Files are fetched over download.connections parallel ranges when the server supports them
 func RunDownloadTUI(url, output, videoID, lang string, headers map[string]string) error {
-	client := &http.Client{
-		Timeout: 0,
-		Transport: &http.Transport{
-			Proxy: http.ProxyFromEnvironment,
-		},
-	}
+	connections := config.LoadOrDefault().Download.EffectiveConnections()
+	client := newSegmentedClient(connections)
 
 	state := &downloadState{
 		startTime: time.Now(),
 	}
 
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+
 	// Start download in background
 	go func() {
-		err := downloadWithProgress(client, url, output, state, headers)
+		err := downloadSegmented(ctx, client, url, output, headers, connections, state)
 		if err != nil {
 			state.setError(err)
 		} else {
+			state.setFinalPath(RenameByMagicBytes(output))
 			state.setDone()
 		}
 	}()
@@ -300,8 +385,12 @@ func RunDownloadTUI(url, output, videoID, lang string, headers map[string]string
 	p := tea.NewProgram(model)
 	finalModel, err := p.Run()
 	if err != nil {
+		cancel()
 		return err
 	}
+	if state.isCancelled() {
+		return ErrCancelled
+	}
 
 	m := finalModel.(downloadModel)
 	_, _, _, _, downloadErr := m.state.get()
@@ -312,9 +401,12 @@ func RunDownloadTUI(url, output, videoID, lang string, headers map[string]string
 	return nil
 }
 
-func downloadWithProgress(client *http.Client, url, output string, state *downloadState, headers map[string]string) error {
+// downloadWithProgress downloads url in a single stream, stopping as soon as
+// ctx is cancelled. The server doesn't support ranges, so a partial file
+// can't be resumed and is removed on failure.
+func downloadWithProgress(ctx context.Context, client *http.Client, url, output string, state *downloadState, headers map[string]string) (err error) {
 	// Create HTTP request
-	req, err := http.NewRequest("GET", url, nil)
+	req, err := http.NewRequestWithContext(ctx, "GET", url, nil)
 	if err != nil {
 		return fmt.Errorf("failed to create request: %w", err)
 	}
@@ -337,24 +429,34 @@ func downloadWithProgress(client *http.Client, url, output string, state *downlo
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
-		return fmt.Errorf("download failed with status %d", resp.StatusCode)
+		return statusError(resp)
 	}
 
 	total := resp.ContentLength
 	state.update(0, total)
 
 	// Create output file
-	file, err := os.Create(output)
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
 	}
 	defer file.Close()
+	defer func() {
+		if err != nil {
+			file.Close()
+			removePart(partPath)
+		}
+	}()
 
 	// Download with progress tracking
 	buf := make([]byte, 32*1024)
 	var current int64
 
 	for {
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
 		n, err := resp.Body.Read(buf)
 		if n > 0 {
 			_, writeErr := file.Write(buf[:n])
@@ -372,8 +474,11 @@ func downloadWithProgress(client *http.Client, url, output string, state *downlo
 		}
 	}
 
-	// Close file and rename by magic bytes if needed
+	// Close file, move it into place and rename by magic bytes if needed
 	file.Close()
+	if err := commitPart(partPath, output); err != nil {
+		return err
+	}
 	state.setFinalPath(RenameByMagicBytes(output))
 
 	return nil
@@ -402,6 +507,11 @@ func RunDownloadFromReaderTUI(reader io.ReadCloser, size int64, output, displayI
 	if err != nil {
 		return err
 	}
+	if state.isCancelled() {
+		// Closing the source unblocks the copy loop
+		reader.Close()
+		return ErrCancelled
+	}
 
 	m := finalModel.(downloadModel)
 	_, _, _, _, downloadErr := m.state.get()
@@ -418,7 +528,8 @@ func downloadFromReaderWithProgress(reader io.ReadCloser, total int64, output st
 	state.update(0, total)
 
 	// Create output file
-	file, err := os.Create(output)
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
 	}
@@ -446,13 +557,64 @@ func downloadFromReaderWithProgress(reader io.ReadCloser, total int64, output st
 		}
 	}
 
-	// Close file and rename by magic bytes if needed
+	// Close file, move it into place and rename by magic bytes if needed
 	file.Close()
+	if err := commitPart(partPath, output); err != nil {
+		return err
+	}
 	state.setFinalPath(RenameByMagicBytes(output))
 
 	return nil
 }
 
+// SaveReader writes reader to output through a .part file, reporting progress
+// to progressFn. Cancelling ctx closes the reader, which stops the copy.
+func SaveReader(ctx context.Context, reader io.ReadCloser, total int64, output string, progressFn func(downloaded, total int64)) error {
+	stop := context.AfterFunc(ctx, func() { reader.Close() })
+	defer stop()
+
+	partPath := output + partSuffix
+	file, err := os.Create(partPath)
+	if err != nil {
+		reader.Close()
+		return fmt.Errorf("failed to create output file: %w", err)
+	}
+
+	var current int64
+	buf := make([]byte, 32*1024)
+	for {
+		n, readErr := reader.Read(buf)
+		if n > 0 {
+			if _, err = file.Write(buf[:n]); err != nil {
+				err = fmt.Errorf("failed to write file: %w", err)
+				break
+			}
+			current += int64(n)
+			if progressFn != nil {
+				progressFn(current, total)
+			}
+		}
+		if readErr == io.EOF {
+			break
+		}
+		if readErr != nil {
+			err = fmt.Errorf("download failed: %w", readErr)
+			break
+		}
+	}
+	reader.Close()
+	file.Close()
+
+	if ctx.Err() != nil {
+		err = ctx.Err()
+	}
+	if err != nil {
+		os.Remove(partPath)
+		return err
+	}
+	return commitPart(partPath, output)
+}
+
 // TelegramDownloadResult matches the extractor telegram result
 type TelegramDownloadResult struct {
 	Title    string
@@ -460,24 +622,31 @@ type TelegramDownloadResult struct {
 	Size     int64
 }
 
-// TelegramDownloadFunc is the signature for the telegram download function
-type TelegramDownloadFunc func(urlStr string, outputPath string, progressFn func(downloaded, total int64)) (*TelegramDownloadResult, error)
+// TelegramDownloadFunc is the signature for the telegram download function.
+// It must stop when ctx is cancelled.
+type TelegramDownloadFunc func(ctx context.Context, urlStr string, outputPath string, progressFn func(downloaded, total int64)) (*TelegramDownloadResult, error)
 
-// RunTelegramDownloadTUI runs Telegram download with TUI progress
+// RunTelegramDownloadTUI runs Telegram download with TUI progress.
+// Quitting the TUI cancels the download and waits for it to stop.
 func RunTelegramDownloadTUI(urlStr, outputPath, lang string, downloadFn TelegramDownloadFunc) error {
 	state := &downloadState{
 		startTime: time.Now(),
 	}
 
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+
 	var result *TelegramDownloadResult
 	var downloadErr error
+	finished := make(chan struct{})
 
 	// Start download in background
 	go func() {
+		defer close(finished)
 		progressFn := func(downloaded, total int64) {
 			state.update(downloaded, total)
 		}
-		result, downloadErr = downloadFn(urlStr, outputPath, progressFn)
+		result, downloadErr = downloadFn(ctx, urlStr, outputPath, progressFn)
 		if downloadErr != nil {
 			state.setError(downloadErr)
 		} else {
@@ -492,9 +661,15 @@ func RunTelegramDownloadTUI(urlStr, outputPath, lang string, downloadFn Telegram
 
 	p := tea.NewProgram(model)
 	_, err := p.Run()
-	if err != nil {
-		return err
+	if err != nil || state.isCancelled() {
+		cancel()
+		<-finished
+		if err != nil {
+			return err
+		}
+		return ErrCancelled
 	}
+	<-finished
 
 	if downloadErr != nil {
 		return downloadErr
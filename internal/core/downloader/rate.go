@@ -0,0 +1,61 @@
+package downloader
+
+import (
+	"math"
+	"time"
+)
+
+const (
+	// rateSampleInterval is the shortest span a speed sample is taken over,
+	// so bursty progress callbacks don't swing the estimate
+	rateSampleInterval = 500 * time.Millisecond
+	// rateTimeConstant is how quickly the estimate follows speed changes
+	rateTimeConstant = 5 * time.Second
+)
+
+// RateEstimator smooths transfer speed with an exponential moving average
+type RateEstimator struct {
+	rate    float64
+	lastAt  time.Time
+	lastN   int64
+	sampled bool
+}
+
+// Update records n bytes transferred in total as of now and returns the
+// smoothed speed in bytes per second. A counter that goes backwards (the
+// audio stream after the video) starts a new baseline but keeps the speed.
+func (r *RateEstimator) Update(n int64, now time.Time) float64 {
+	if r.lastAt.IsZero() || n < r.lastN {
+		r.lastAt, r.lastN = now, n
+		return r.rate
+	}
+	dt := now.Sub(r.lastAt)
+	if dt < rateSampleInterval {
+		return r.rate
+	}
+
+	sample := float64(n-r.lastN) / dt.Seconds()
+	if r.sampled {
+		alpha := 1 - math.Exp(-dt.Seconds()/rateTimeConstant.Seconds())
+		r.rate += alpha * (sample - r.rate)
+	} else {
+		r.rate = sample
+		r.sampled = true
+	}
+	r.lastAt, r.lastN = now, n
+	return r.rate
+}
+
+// Rate returns the current smoothed speed in bytes per second
+func (r *RateEstimator) Rate() float64 {
+	return r.rate
+}
+
+// ETA returns the whole seconds needed for the remaining bytes at the smoothed
+// speed, or -1 when it can't be estimated yet
+func (r *RateEstimator) ETA(remaining int64) int64 {
+	if remaining < 0 || r.rate <= 0 {
+		return -1
+	}
+	return int64(math.Ceil(float64(remaining) / r.rate))
+}
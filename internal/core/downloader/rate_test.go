@@ -0,0 +1,40 @@
+package downloader
+
+import (
+	"testing"
+	"time"
+)
+
+func TestRateEstimator(t *testing.T) {
+	var r RateEstimator
+	start := time.Unix(0, 0)
+
+	r.Update(0, start)
+	if got := r.ETA(1000); got != -1 {
+		t.Errorf("ETA before any sample = %d, want -1", got)
+	}
+
+	// First sample is taken as is
+	if got := r.Update(1000, start.Add(time.Second)); got != 1000 {
+		t.Errorf("first rate = %v, want 1000", got)
+	}
+	if got := r.ETA(2500); got != 3 {
+		t.Errorf("ETA(2500) = %d, want 3", got)
+	}
+
+	// Samples closer than rateSampleInterval are ignored
+	if got := r.Update(5000, start.Add(time.Second+100*time.Millisecond)); got != 1000 {
+		t.Errorf("rate after short burst = %v, want 1000", got)
+	}
+
+	// A spike moves the estimate only part of the way
+	got := r.Update(11000, start.Add(2*time.Second))
+	if got <= 1000 || got >= 10000 {
+		t.Errorf("rate after spike = %v, want between 1000 and 10000", got)
+	}
+
+	// A restarted counter keeps the speed
+	if after := r.Update(0, start.Add(3*time.Second)); after != got {
+		t.Errorf("rate after counter reset = %v, want %v", after, got)
+	}
+}
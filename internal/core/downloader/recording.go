@@ -0,0 +1,57 @@
+package downloader
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// RemuxTS moves an HLS download left as MPEG-TS, because ffmpeg couldn't
+// join its segments into MP4, into a faststart MP4 next to it and returns the
+// new path. Other files, and TS files when ffmpeg is missing, are left as
+// they are. On failure the TS file is kept and its path returned.
+func RemuxTS(ctx context.Context, path string) (string, error) {
+	if !strings.EqualFold(filepath.Ext(path), ".ts") || !FFmpegAvailable() {
+		return path, nil
+	}
+	if err := CheckDiskSpace(path, fileSize(path)); err != nil {
+		return path, err
+	}
+
+	mp4Path := UniquePath(ffmpeg.SwapExt(path, ffmpeg.ContainerMP4))
+	if err := ffmpeg.Remux(ctx, path, mp4Path, nil); err != nil {
+		return path, err
+	}
+	os.Remove(path)
+	return mp4Path, nil
+}
+
+// SplitRecording splits the recording at path into parts of about every
+// each when it's longer than that, removing it once the parts are written.
+// It returns the files the recording ends up as: the parts, or just path.
+func SplitRecording(ctx context.Context, path string, every time.Duration) ([]string, error) {
+	duration, err := ffmpeg.ProbeDuration(ctx, path)
+	if err != nil {
+		return []string{path}, err
+	}
+	if duration <= every {
+		return []string{path}, nil
+	}
+	if err := CheckDiskSpace(path, fileSize(path)); err != nil {
+		return []string{path}, err
+	}
+
+	parts, err := ffmpeg.Split(ctx, path, every, nil)
+	if err != nil {
+		for _, part := range parts {
+			os.Remove(part)
+		}
+		return []string{path}, err
+	}
+	os.Remove(path)
+	return parts, nil
+}
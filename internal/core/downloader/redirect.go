@@ -0,0 +1,92 @@
+package downloader
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"sync"
+)
+
+// StatusError is an unexpected HTTP status answering a download request
+type StatusError struct {
+	StatusCode    int
+	RedirectChain []string // requested URL first, the URL that answered last; empty if not redirected
+}
+
+func (e *StatusError) Error() string {
+	if len(e.RedirectChain) == 0 {
+		return fmt.Sprintf("download failed with status %d", e.StatusCode)
+	}
+	// Signed CDN links usually expire at the end of the chain, so name it
+	return fmt.Sprintf("download failed with status %d from %s (after %d redirects)",
+		e.StatusCode, e.RedirectChain[len(e.RedirectChain)-1], len(e.RedirectChain)-1)
+}
+
+// statusError returns a StatusError for resp
+func statusError(resp *http.Response) error {
+	return &StatusError{StatusCode: resp.StatusCode, RedirectChain: RedirectChain(resp)}
+}
+
+// RedirectChain returns the URLs the request of resp went through, requested
+// URL first and final URL last, or nil if it wasn't redirected
+func RedirectChain(resp *http.Response) []string {
+	if resp == nil || resp.Request == nil || resp.Request.Response == nil {
+		return nil
+	}
+	var chain []string
+	for req := resp.Request; req != nil; {
+		chain = append([]string{req.URL.String()}, chain...)
+		if req.Response == nil {
+			break
+		}
+		req = req.Response.Request
+	}
+	return chain
+}
+
+// RedirectTrace records the redirects followed by downloads made with the
+// context from TraceRedirects
+type RedirectTrace struct {
+	mu    sync.Mutex
+	chain []string
+}
+
+type redirectTraceKey struct{}
+
+// TraceRedirects returns a context whose downloads record their redirects in
+// the returned trace
+func TraceRedirects(ctx context.Context) (context.Context, *RedirectTrace) {
+	trace := &RedirectTrace{}
+	return context.WithValue(ctx, redirectTraceKey{}, trace), trace
+}
+
+// Chain returns the latest redirect chain, requested URL first and final URL
+// last, or nil if no download was redirected
+func (t *RedirectTrace) Chain() []string {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.chain
+}
+
+// FinalURL returns the URL the latest redirected download ended at, or ""
+func (t *RedirectTrace) FinalURL() string {
+	chain := t.Chain()
+	if len(chain) == 0 {
+		return ""
+	}
+	return chain[len(chain)-1]
+}
+
+// traceRedirects records the redirects of resp in the trace of its request's
+// context, if any
+func traceRedirects(resp *http.Response) {
+	trace, ok := resp.Request.Context().Value(redirectTraceKey{}).(*RedirectTrace)
+	if !ok {
+		return
+	}
+	if chain := RedirectChain(resp); chain != nil {
+		trace.mu.Lock()
+		trace.chain = chain
+		trace.mu.Unlock()
+	}
+}
@@ -0,0 +1,33 @@
+package downloader
+
+import (
+	"net/http"
+	"net/url"
+	"slices"
+	"testing"
+)
+
+func TestRedirectChain(t *testing.T) {
+	request := func(rawURL string, via *http.Response) *http.Request {
+		u, _ := url.Parse(rawURL)
+		return &http.Request{URL: u, Response: via}
+	}
+	first := request("https://example.com/v/1", nil)
+	second := request("https://cdn.example.com/v/1?sig=a", &http.Response{Request: first, StatusCode: 302})
+	final := request("https://edge.example.net/v/1?sig=b", &http.Response{Request: second, StatusCode: 302})
+
+	resp := &http.Response{Request: final, StatusCode: 403}
+	want := []string{"https://example.com/v/1", "https://cdn.example.com/v/1?sig=a", "https://edge.example.net/v/1?sig=b"}
+	if got := RedirectChain(resp); !slices.Equal(got, want) {
+		t.Errorf("RedirectChain() = %v, want %v", got, want)
+	}
+
+	if got := RedirectChain(&http.Response{Request: first}); got != nil {
+		t.Errorf("RedirectChain() without redirects = %v, want nil", got)
+	}
+
+	err := statusError(resp).Error()
+	if wantErr := "download failed with status 403 from https://edge.example.net/v/1?sig=b (after 2 redirects)"; err != wantErr {
+		t.Errorf("statusError() = %q, want %q", err, wantErr)
+	}
+}
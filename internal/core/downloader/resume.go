@@ -0,0 +1,105 @@
+package downloader
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"os"
+	"strings"
+)
+
+// partSuffix marks a file that is still being downloaded
+const partSuffix = ".part"
+
+// partStateSuffix is appended to the .part path for its sidecar state file
+const partStateSuffix = ".json"
+
+// errResumeInvalid means the saved progress no longer matches the remote file
+var errResumeInvalid = errors.New("partial download no longer matches the remote file")
+
+// partState is the sidecar written next to a .part file so the download can
+// continue after an interruption
+type partState struct {
+	URL       string            `json:"url"`
+	Size      int64             `json:"size"`
+	Validator string            `json:"validator"` // ETag or Last-Modified, sent as If-Range
+	Segments  []SegmentProgress `json:"segments"`
+}
+
+// loadPartState returns the saved state for partPath if it belongs to url and
+// the partial file is still there, or nil
+func loadPartState(partPath, url string) *partState {
+	data, err := os.ReadFile(partPath + partStateSuffix)
+	if err != nil {
+		return nil
+	}
+	var saved partState
+	if err := json.Unmarshal(data, &saved); err != nil {
+		return nil
+	}
+	if saved.URL != url || saved.Validator == "" || len(saved.Segments) == 0 {
+		return nil
+	}
+	if _, err := os.Stat(partPath); err != nil {
+		return nil
+	}
+	return &saved
+}
+
+// savePartState writes the sidecar through a temp file so a crash mid-write
+// can't leave it truncated
+func savePartState(partPath string, state *partState) error {
+	data, err := json.Marshal(state)
+	if err != nil {
+		return err
+	}
+	tmp := partPath + partStateSuffix + ".tmp"
+	if err := os.WriteFile(tmp, data, 0644); err != nil {
+		return err
+	}
+	return os.Rename(tmp, partPath+partStateSuffix)
+}
+
+// removePart deletes a partial file and its sidecar
+func removePart(partPath string) {
+	os.Remove(partPath)
+	os.Remove(partPath + partStateSuffix)
+}
+
+// finishPart moves a completed .part file into place. On failure the partial
+// file and its sidecar are kept so the next attempt can resume.
+func finishPart(partPath, output string, err error) error {
+	if err != nil {
+		return err
+	}
+	return commitPart(partPath, output)
+}
+
+// commitPart renames a completed .part file to its final name. The rename
+// stays on one volume, so the final name only ever refers to a whole file.
+func commitPart(partPath, output string) error {
+	if err := os.Rename(partPath, output); err != nil {
+		return fmt.Errorf("failed to move download into place: %w", err)
+	}
+	os.Remove(partPath + partStateSuffix)
+	return nil
+}
+
+// resumeValidator picks the response header that If-Range can use to detect a
+// changed file. Weak ETags aren't allowed in If-Range, so Last-Modified is
+// used instead.
+func resumeValidator(resp *http.Response) string {
+	if etag := resp.Header.Get("ETag"); etag != "" && !strings.HasPrefix(etag, "W/") {
+		return etag
+	}
+	return resp.Header.Get("Last-Modified")
+}
+
+func sumDownloaded(segments []SegmentProgress) int64 {
+	var n int64
+	for _, s := range segments {
+		n += s.Downloaded
+	}
+	return n
+}
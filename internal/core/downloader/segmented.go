@@ -0,0 +1,502 @@
+package downloader
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"time"
+)
+
+// minSegmentSize keeps small files from being split into tiny ranges
+const minSegmentSize = 2 * 1024 * 1024
+
+// SegmentProgress is a snapshot of one byte range of a segmented download
+type SegmentProgress struct {
+	Index      int   `json:"index"`
+	Start      int64 `json:"start"`
+	End        int64 `json:"end"` // inclusive
+	Downloaded int64 `json:"downloaded"`
+}
+
+// segment is one byte range fetched by its own connection
+type segment struct {
+	index      int
+	start      int64
+	end        int64 // inclusive
+	downloaded atomic.Int64
+}
+
+// splitSegments divides size bytes into at most n contiguous ranges of at
+// least minSegmentSize each
+func splitSegments(size int64, n int) []*segment {
+	if limit := size / minSegmentSize; int64(n) > limit {
+		n = int(limit)
+	}
+	if n < 1 {
+		n = 1
+	}
+
+	per := size / int64(n)
+	segments := make([]*segment, n)
+	var start int64
+	for i := range segments {
+		end := start + per - 1
+		if i == n-1 {
+			end = size - 1
+		}
+		segments[i] = &segment{index: i, start: start, end: end}
+		start = end + 1
+	}
+	return segments
+}
+
+// newSegmentedClient creates a client that keeps one idle connection per segment
+func newSegmentedClient(connections int) *http.Client {
+	return &http.Client{
+		Timeout: 0,
+		Transport: &http.Transport{
+			Proxy:               proxyFunc(),
+			MaxIdleConnsPerHost: connections + 2,
+			IdleConnTimeout:     90 * time.Second,
+			DisableCompression:  true,
+		},
+	}
+}
+
+// segmentedDownload holds the shared state of one segmented transfer
+type segmentedDownload struct {
+	client  *http.Client
+	url     string
+	headers map[string]string
+	file    *os.File
+
+	// validator is the ETag or Last-Modified sent as If-Range; empty disables resuming
+	validator string
+}
+
+// get requests bytes from offset to end (inclusive); end < 0 means to the end
+// of the file and offset < 0 means the whole file without a Range header
+func (d *segmentedDownload) get(ctx context.Context, offset, end int64) (*http.Response, error) {
+	req, err := http.NewRequestWithContext(ctx, "GET", d.url, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+
+	if len(d.headers) > 0 {
+		for key, value := range d.headers {
+			req.Header.Set(key, value)
+		}
+	} else {
+		req.Header.Set("User-Agent", DefaultUserAgent)
+	}
+	switch {
+	case offset < 0:
+	case end < 0:
+		req.Header.Set("Range", fmt.Sprintf("bytes=%d-", offset))
+	default:
+		req.Header.Set("Range", fmt.Sprintf("bytes=%d-%d", offset, end))
+	}
+	if offset >= 0 && d.validator != "" {
+		req.Header.Set("If-Range", d.validator)
+	}
+
+	resp, err := d.client.Do(req)
+	if err == nil {
+		traceRedirects(resp)
+	}
+	return resp, err
+}
+
+// fetch downloads a segment, resuming from its last written byte on failure.
+// resp, when non-nil, is an already open response starting at the segment.
+func (d *segmentedDownload) fetch(ctx context.Context, seg *segment, resp *http.Response) error {
+	const maxRetries = 5
+	var err error
+
+	for attempt := 0; ; attempt++ {
+		offset := seg.start + seg.downloaded.Load()
+		if resp == nil {
+			resp, err = d.get(ctx, offset, seg.end)
+			if err == nil && !rangeStartsAt(resp, offset) {
+				err = fmt.Errorf("range request returned status %d", resp.StatusCode)
+				resp.Body.Close()
+				resp = nil
+			}
+		}
+
+		if resp != nil {
+			before := seg.downloaded.Load()
+			err = d.copySegment(resp.Body, seg)
+			resp.Body.Close()
+			resp = nil
+			if err == nil {
+				return nil
+			}
+			// Progress was made, so the connection is worth retrying afresh
+			if seg.downloaded.Load() > before {
+				attempt = 0
+			}
+		}
+
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		if attempt >= maxRetries {
+			return fmt.Errorf("segment %d failed after %d retries: %w", seg.index, maxRetries, err)
+		}
+
+		backoff := time.Duration(1<<uint(attempt)) * 500 * time.Millisecond
+		select {
+		case <-ctx.Done():
+			return ctx.Err()
+		case <-time.After(backoff):
+		}
+	}
+}
+
+// copySegment writes body into the segment's range until the range is full
+func (d *segmentedDownload) copySegment(body io.Reader, seg *segment) error {
+	buf := make([]byte, 128*1024)
+	for {
+		offset := seg.start + seg.downloaded.Load()
+		remaining := seg.end + 1 - offset
+		if remaining <= 0 {
+			return nil
+		}
+		if int64(len(buf)) > remaining {
+			buf = buf[:remaining]
+		}
+
+		n, readErr := body.Read(buf)
+		if n > 0 {
+			if _, err := d.file.WriteAt(buf[:n], offset); err != nil {
+				return fmt.Errorf("failed to write file: %w", err)
+			}
+			seg.downloaded.Add(int64(n))
+		}
+		if readErr == io.EOF {
+			if seg.start+seg.downloaded.Load() <= seg.end {
+				return io.ErrUnexpectedEOF
+			}
+			return nil
+		}
+		if readErr != nil {
+			return readErr
+		}
+	}
+}
+
+// rangeStartsAt reports whether resp is a 206 whose Content-Range begins at offset
+func rangeStartsAt(resp *http.Response, offset int64) bool {
+	if resp.StatusCode != http.StatusPartialContent {
+		return false
+	}
+	var start int64
+	_, err := fmt.Sscanf(resp.Header.Get("Content-Range"), "bytes %d-", &start)
+	return err == nil && start == offset
+}
+
+// contentRangeTotal returns the total size from a "bytes a-b/total" header, or -1
+func contentRangeTotal(header string) int64 {
+	_, total, ok := strings.Cut(header, "/")
+	if !ok {
+		return -1
+	}
+	var size int64
+	if _, err := fmt.Sscanf(total, "%d", &size); err != nil {
+		return -1
+	}
+	return size
+}
+
+// downloadSegmented downloads url into output over up to connections parallel
+// Range requests. Data goes to output.part, with a sidecar recording each
+// segment's progress, so an interrupted download continues where it stopped.
+// A fresh download asks for "bytes=0-" first: a server without range support
+// answers 200 and that response is used as a single stream.
+// A media playlist data: URL, as segmented DASH streams are handed over, is
+// fetched segment by segment instead.
+func downloadSegmented(ctx context.Context, client *http.Client, url, output string, headers map[string]string, connections int, state *downloadState) error {
+	if isPlaylistDataURL(url) {
+		return downloadSegmentList(ctx, url, output, headers, state)
+	}
+
+	d := &segmentedDownload{client: client, url: url, headers: headers}
+	partPath := output + partSuffix
+
+	if saved := loadPartState(partPath, url); saved != nil {
+		err := d.resume(ctx, partPath, saved, state)
+		if !errors.Is(err, errResumeInvalid) {
+			return finishPart(partPath, output, err)
+		}
+		// The file changed on the server; start over
+		d.validator = ""
+	}
+	removePart(partPath)
+
+	resp, err := d.get(ctx, 0, -1)
+	if err != nil {
+		return fmt.Errorf("download request failed: %w", err)
+	}
+
+	total := int64(-1)
+	if resp.StatusCode == http.StatusPartialContent {
+		total = contentRangeTotal(resp.Header.Get("Content-Range"))
+	}
+	if total < 0 {
+		// No usable range support: some servers reject ranges on empty or
+		// dynamic files, so retry those as a plain GET
+		if resp.StatusCode == http.StatusRequestedRangeNotSatisfiable {
+			resp.Body.Close()
+			resp, err = d.get(ctx, -1, -1)
+			if err != nil {
+				return fmt.Errorf("download request failed: %w", err)
+			}
+		}
+		defer resp.Body.Close()
+		if resp.StatusCode != http.StatusOK {
+			return statusError(resp)
+		}
+		if err := CheckDiskSpace(output, resp.ContentLength); err != nil {
+			return err
+		}
+		if err := downloadSingleStream(ctx, resp, partPath, state); err != nil {
+			// A single stream can't be resumed, so don't leave its partial file behind
+			removePart(partPath)
+			return err
+		}
+		return commitPart(partPath, output)
+	}
+
+	if err := CheckDiskSpace(output, total); err != nil {
+		resp.Body.Close()
+		return err
+	}
+	file, err := os.Create(partPath)
+	if err != nil {
+		resp.Body.Close()
+		return fmt.Errorf("failed to create output file: %w", err)
+	}
+	defer file.Close()
+	d.file = file
+
+	// Pre-allocating is only an optimization, WriteAt extends the file anyway
+	_ = file.Truncate(total)
+
+	d.validator = resumeValidator(resp)
+	segments := splitSegments(total, connections)
+	return finishPart(partPath, output, d.run(ctx, partPath, total, segments, segments[0], resp, state))
+}
+
+// resume continues a download from its sidecar state. The first request
+// carries If-Range, so a changed file comes back as 200 and errResumeInvalid
+// is returned.
+func (d *segmentedDownload) resume(ctx context.Context, partPath string, saved *partState, state *downloadState) error {
+	file, err := os.OpenFile(partPath, os.O_WRONLY, 0644)
+	if err != nil {
+		return errResumeInvalid
+	}
+	defer file.Close()
+	d.file = file
+	d.validator = saved.Validator
+
+	segments := make([]*segment, len(saved.Segments))
+	var first *segment
+	for i, sp := range saved.Segments {
+		segments[i] = &segment{index: sp.Index, start: sp.Start, end: sp.End}
+		segments[i].downloaded.Store(sp.Downloaded)
+		if first == nil && sp.Start+sp.Downloaded <= sp.End {
+			first = segments[i]
+		}
+	}
+	if first == nil {
+		// Everything was written before the rename was interrupted
+		return nil
+	}
+
+	offset := first.start + first.downloaded.Load()
+	resp, err := d.get(ctx, offset, -1)
+	if err != nil {
+		return fmt.Errorf("download request failed: %w", err)
+	}
+	if !rangeStartsAt(resp, offset) || contentRangeTotal(resp.Header.Get("Content-Range")) != saved.Size {
+		resp.Body.Close()
+		return errResumeInvalid
+	}
+
+	// The sparse .part file only needs room for what's still missing
+	if err := CheckDiskSpace(partPath, saved.Size-sumDownloaded(saved.Segments)); err != nil {
+		resp.Body.Close()
+		return err
+	}
+	state.setResumed(sumDownloaded(saved.Segments))
+	return d.run(ctx, partPath, saved.Size, segments, first, resp, state)
+}
+
+// run fetches all segments concurrently, reusing resp for the segment it
+// starts in, and keeps the sidecar state current until they finish
+func (d *segmentedDownload) run(ctx context.Context, partPath string, total int64, segments []*segment, first *segment, resp *http.Response, state *downloadState) error {
+	// Without a validator a resumed range can't be checked, so nothing is saved
+	resumable := d.validator != ""
+	saveState := func() []SegmentProgress {
+		snapshot := snapshotSegments(segments)
+		if resumable {
+			saved := &partState{URL: d.url, Size: total, Validator: d.validator, Segments: snapshot}
+			if err := savePartState(partPath, saved); err != nil {
+				// The download itself is unaffected, it just won't be resumable
+				resumable = false
+			}
+		}
+		return snapshot
+	}
+
+	snapshot := saveState()
+	state.update(sumDownloaded(snapshot), total)
+	state.setSegments(snapshot)
+
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	var wg sync.WaitGroup
+	var firstErr error
+	var errOnce sync.Once
+	for _, seg := range segments {
+		var initial *http.Response
+		if seg == first {
+			initial = resp
+		}
+		wg.Add(1)
+		go func(seg *segment, initial *http.Response) {
+			defer wg.Done()
+			if err := d.fetch(ctx, seg, initial); err != nil {
+				errOnce.Do(func() {
+					firstErr = err
+					cancel()
+				})
+			}
+		}(seg, initial)
+	}
+
+	done := make(chan struct{})
+	go func() {
+		wg.Wait()
+		close(done)
+	}()
+
+	ticker := time.NewTicker(100 * time.Millisecond)
+	defer ticker.Stop()
+	lastSave := time.Now()
+	for running := true; running; {
+		select {
+		case <-done:
+			running = false
+		case <-ticker.C:
+		}
+		snapshot := snapshotSegments(segments)
+		if !running || time.Since(lastSave) >= time.Second {
+			snapshot = saveState()
+			lastSave = time.Now()
+		}
+		state.update(sumDownloaded(snapshot), total)
+		state.setSegments(snapshot)
+	}
+
+	if firstErr != nil {
+		return firstErr
+	}
+	return d.file.Close()
+}
+
+// downloadSingleStream writes a full (200) response to output
+func downloadSingleStream(ctx context.Context, resp *http.Response, output string, state *downloadState) error {
+	total := resp.ContentLength
+	state.update(0, total)
+
+	file, err := os.Create(output)
+	if err != nil {
+		return fmt.Errorf("failed to create output file: %w", err)
+	}
+	defer file.Close()
+
+	buf := make([]byte, 32*1024)
+	var current int64
+	for {
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		n, readErr := resp.Body.Read(buf)
+		if n > 0 {
+			if _, err := file.Write(buf[:n]); err != nil {
+				return fmt.Errorf("failed to write file: %w", err)
+			}
+			current += int64(n)
+			state.update(current, total)
+		}
+		if readErr == io.EOF {
+			break
+		}
+		if readErr != nil {
+			return fmt.Errorf("download failed: %w", readErr)
+		}
+	}
+	return file.Close()
+}
+
+func snapshotSegments(segments []*segment) []SegmentProgress {
+	snapshot := make([]SegmentProgress, len(segments))
+	for i, s := range segments {
+		snapshot[i] = SegmentProgress{
+			Index:      s.index,
+			Start:      s.start,
+			End:        s.end,
+			Downloaded: s.downloaded.Load(),
+		}
+	}
+	return snapshot
+}
+
+// DownloadSegmented downloads url to output over up to connections parallel
+// ranges, falling back to a single stream when the server lacks range support.
+// progressFn receives the combined progress and segmentFn the per-range
+// progress; either may be nil.
+func DownloadSegmented(ctx context.Context, url, output string, headers map[string]string, connections int, progressFn func(downloaded, total int64), segmentFn func([]SegmentProgress)) error {
+	state := &downloadState{
+		startTime: time.Now(),
+	}
+
+	done := make(chan struct{})
+	report := func() {
+		current, total, _, _, _ := state.get()
+		if progressFn != nil {
+			progressFn(current, total)
+		}
+		if segmentFn != nil {
+			if segments := state.getSegments(); len(segments) > 0 {
+				segmentFn(segments)
+			}
+		}
+	}
+	go func() {
+		ticker := time.NewTicker(100 * time.Millisecond)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-done:
+				return
+			case <-ticker.C:
+				report()
+			}
+		}
+	}()
+
+	err := downloadSegmented(ctx, newSegmentedClient(connections), url, output, headers, connections, state)
+	close(done)
+	report()
+	return err
+}
@@ -0,0 +1,49 @@
+package downloader
+
+import "testing"
+
+func TestSplitSegments(t *testing.T) {
+	tests := []struct {
+		size int64
+		n    int
+		want int
+	}{
+		{size: 100 * 1024 * 1024, n: 4, want: 4},
+		{size: 5 * 1024 * 1024, n: 8, want: 2}, // capped by minSegmentSize
+		{size: 1024, n: 4, want: 1},
+		{size: 10 * 1024 * 1024, n: 0, want: 1},
+	}
+
+	for _, tt := range tests {
+		segments := splitSegments(tt.size, tt.n)
+		if len(segments) != tt.want {
+			t.Errorf("splitSegments(%d, %d) = %d segments, want %d", tt.size, tt.n, len(segments), tt.want)
+			continue
+		}
+		// Ranges must be contiguous and cover the whole file
+		var next int64
+		for _, s := range segments {
+			if s.start != next || s.end < s.start {
+				t.Errorf("splitSegments(%d, %d): bad range %d-%d", tt.size, tt.n, s.start, s.end)
+			}
+			next = s.end + 1
+		}
+		if next != tt.size {
+			t.Errorf("splitSegments(%d, %d) covers %d bytes", tt.size, tt.n, next)
+		}
+	}
+}
+
+func TestContentRangeTotal(t *testing.T) {
+	tests := map[string]int64{
+		"bytes 0-1/12345":   12345,
+		"bytes 0-99/*":      -1,
+		"":                  -1,
+		"bytes 100-199/200": 200,
+	}
+	for header, want := range tests {
+		if got := contentRangeTotal(header); got != want {
+			t.Errorf("contentRangeTotal(%q) = %d, want %d", header, got, want)
+		}
+	}
+}
@@ -0,0 +1,88 @@
+package downloader
+
+import (
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// maxThumbnailSize caps thumbnail downloads
+const maxThumbnailSize = 10 * 1024 * 1024
+
+// FetchThumbnail downloads a thumbnail image and returns its bytes and MIME type
+func FetchThumbnail(thumbURL string) ([]byte, string, error) {
+	req, err := http.NewRequest("GET", thumbURL, nil)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", DefaultUserAgent)
+
+	client := &http.Client{Timeout: 30 * time.Second}
+	resp, err := client.Do(req)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to fetch thumbnail: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, "", fmt.Errorf("thumbnail returned status %d", resp.StatusCode)
+	}
+
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxThumbnailSize+1))
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to read thumbnail: %w", err)
+	}
+	if len(data) > maxThumbnailSize {
+		return nil, "", fmt.Errorf("thumbnail larger than %s", formatBytes(maxThumbnailSize))
+	}
+
+	mimeType := strings.TrimSpace(strings.Split(resp.Header.Get("Content-Type"), ";")[0])
+	if !strings.HasPrefix(mimeType, "image/") {
+		mimeType = http.DetectContentType(data)
+	}
+	return data, mimeType, nil
+}
+
+// SaveThumbnail downloads a thumbnail next to mediaPath, named like the media
+// file with the image's extension. Returns the saved path.
+func SaveThumbnail(thumbURL, mediaPath string) (string, error) {
+	data, mimeType, err := FetchThumbnail(thumbURL)
+	if err != nil {
+		return "", err
+	}
+
+	base := strings.TrimSuffix(mediaPath, filepath.Ext(mediaPath))
+	output := base + "." + thumbnailExt(mimeType, thumbURL)
+	if err := os.WriteFile(output, data, 0644); err != nil {
+		return "", fmt.Errorf("failed to save thumbnail: %w", err)
+	}
+	return output, nil
+}
+
+// thumbnailExt picks a file extension from the MIME type, then the URL
+func thumbnailExt(mimeType, thumbURL string) string {
+	switch mimeType {
+	case "image/jpeg":
+		return "jpg"
+	case "image/png":
+		return "png"
+	case "image/webp":
+		return "webp"
+	case "image/gif":
+		return "gif"
+	case "image/avif":
+		return "avif"
+	}
+	if u, err := url.Parse(thumbURL); err == nil {
+		if ext := strings.ToLower(strings.TrimPrefix(path.Ext(u.Path), ".")); ext != "" && len(ext) <= 4 {
+			return ext
+		}
+	}
+	return "jpg"
+}
@@ -0,0 +1,154 @@
+// Package downloadlist parses lists of URLs to download: plain text with one
+// URL per line, or JSON such as a history export.
+package downloadlist
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// Item is a URL to download. JSON lists can set a quality, tags, and a note
+// per item.
+type Item struct {
+	URL     string   `json:"url"`
+	Quality string   `json:"quality,omitempty"`
+	Tags    []string `json:"tags,omitempty"`
+	Note    string   `json:"note,omitempty"`
+}
+
+// List is a parsed download list
+type List struct {
+	Items   []Item
+	Invalid []string // lines or entries that aren't a usable URL
+}
+
+// ParseFile reads and parses a download list file. A .url file is read as
+// an Internet Shortcut.
+func ParseFile(path string) (*List, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open file: %w", err)
+	}
+	if strings.EqualFold(filepath.Ext(path), ".url") {
+		return ParseShortcut(data)
+	}
+	return Parse(data)
+}
+
+// ParseShortcut reads the link out of an Internet Shortcut (.url) file, as
+// saved by dragging a link out of a browser on Windows:
+//
+//	[InternetShortcut]
+//	URL=https://example.com/video
+func ParseShortcut(data []byte) (*List, error) {
+	var urls []string
+	scanner := bufio.NewScanner(bytes.NewReader(data))
+	for scanner.Scan() {
+		key, value, ok := strings.Cut(strings.TrimSpace(scanner.Text()), "=")
+		if ok && strings.EqualFold(strings.TrimSpace(key), "URL") {
+			urls = append(urls, strings.TrimSpace(value))
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("failed to read shortcut: %w", err)
+	}
+	if len(urls) == 0 {
+		return nil, fmt.Errorf("invalid shortcut: no URL= line")
+	}
+	return Parse([]byte(strings.Join(urls, "\n")))
+}
+
+// Parse parses a download list. JSON is either an array of URLs or of
+// objects with a "url" field (as in a history export), or an object with
+// such an array under "urls", "items", or "jobs". Anything else is read as
+// text, one URL per line, skipping empty lines and lines starting with #.
+// Duplicate URLs are dropped.
+func Parse(data []byte) (*List, error) {
+	data = bytes.TrimPrefix(bytes.TrimSpace(data), []byte("\xef\xbb\xbf"))
+
+	var items []Item
+	var where []string // position of each item, for reporting invalid ones
+	switch {
+	case bytes.HasPrefix(data, []byte("[")), bytes.HasPrefix(data, []byte("{")):
+		var err error
+		if items, err = parseJSON(data); err != nil {
+			return nil, err
+		}
+		for i := range items {
+			where = append(where, fmt.Sprintf("entry %d", i+1))
+		}
+	default:
+		scanner := bufio.NewScanner(bytes.NewReader(data))
+		for lineNum := 1; scanner.Scan(); lineNum++ {
+			line := strings.TrimSpace(scanner.Text())
+			if line == "" || strings.HasPrefix(line, "#") {
+				continue
+			}
+			items = append(items, Item{URL: line})
+			where = append(where, fmt.Sprintf("line %d", lineNum))
+		}
+		if err := scanner.Err(); err != nil {
+			return nil, fmt.Errorf("failed to read list: %w", err)
+		}
+	}
+
+	list := &List{}
+	seen := make(map[string]bool)
+	for i, item := range items {
+		normalized, err := extractor.NormalizeURL(item.URL)
+		if err != nil {
+			list.Invalid = append(list.Invalid, where[i]+": "+item.URL)
+			continue
+		}
+		if seen[normalized] {
+			continue
+		}
+		seen[normalized] = true
+		item.URL = normalized
+		list.Items = append(list.Items, item)
+	}
+	return list, nil
+}
+
+// parseJSON decodes the JSON list shapes Parse accepts
+func parseJSON(data []byte) ([]Item, error) {
+	if data[0] == '{' {
+		var wrapper map[string]json.RawMessage
+		if err := json.Unmarshal(data, &wrapper); err != nil {
+			return nil, fmt.Errorf("invalid JSON list: %w", err)
+		}
+		data = nil
+		for _, key := range []string{"urls", "items", "jobs"} {
+			if raw, ok := wrapper[key]; ok {
+				data = raw
+				break
+			}
+		}
+		if data == nil {
+			return nil, fmt.Errorf("invalid JSON list: expected a \"urls\", \"items\", or \"jobs\" array")
+		}
+	}
+
+	var raw []json.RawMessage
+	if err := json.Unmarshal(data, &raw); err != nil {
+		return nil, fmt.Errorf("invalid JSON list: %w", err)
+	}
+	items := make([]Item, 0, len(raw))
+	for _, entry := range raw {
+		var item Item
+		if err := json.Unmarshal(entry, &item.URL); err != nil {
+			if err := json.Unmarshal(entry, &item); err != nil {
+				return nil, fmt.Errorf("invalid JSON list entry %s: %w", entry, err)
+			}
+		}
+		items = append(items, item)
+	}
+	return items, nil
+}
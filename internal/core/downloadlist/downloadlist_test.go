@@ -0,0 +1,66 @@
+package downloadlist
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestParse(t *testing.T) {
+	tests := []struct {
+		name    string
+		input   string
+		want    []Item
+		invalid int
+	}{
+		{
+			name:  "text",
+			input: "# my list\nhttps://example.com/a\n\nexample.com/b\nhttps://example.com/a\n",
+			want:  []Item{{URL: "https://example.com/a"}, {URL: "https://example.com/b"}},
+		},
+		{
+			name:  "JSON strings",
+			input: `["https://example.com/a", "https://example.com/b"]`,
+			want:  []Item{{URL: "https://example.com/a"}, {URL: "https://example.com/b"}},
+		},
+		{
+			name:  "history export",
+			input: `[{"id": "1", "url": "https://example.com/a", "status": "completed", "tags": ["music"]}, {"url": "https://example.com/b", "quality": "720p"}]`,
+			want:  []Item{{URL: "https://example.com/a", Tags: []string{"music"}}, {URL: "https://example.com/b", Quality: "720p"}},
+		},
+		{
+			name:    "wrapped",
+			input:   `{"urls": ["https://example.com/a", ""]}`,
+			want:    []Item{{URL: "https://example.com/a"}},
+			invalid: 1,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			list, err := Parse([]byte(tt.input))
+			if err != nil {
+				t.Fatal(err)
+			}
+			if !reflect.DeepEqual(list.Items, tt.want) {
+				t.Errorf("Items = %+v, want %+v", list.Items, tt.want)
+			}
+			if len(list.Invalid) != tt.invalid {
+				t.Errorf("Invalid = %v, want %d entries", list.Invalid, tt.invalid)
+			}
+		})
+	}
+}
+
+func TestParseShortcut(t *testing.T) {
+	input := "[InternetShortcut]\r\nURL=https://example.com/watch?v=1\r\nIconIndex=0\r\n"
+	list, err := ParseShortcut([]byte(input))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if want := []Item{{URL: "https://example.com/watch?v=1"}}; !reflect.DeepEqual(list.Items, want) {
+		t.Errorf("Items = %+v, want %+v", list.Items, want)
+	}
+
+	if _, err := ParseShortcut([]byte("[InternetShortcut]\n")); err == nil {
+		t.Error("ParseShortcut() without a URL line succeeded, want an error")
+	}
+}
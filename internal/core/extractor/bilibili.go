@@ -14,7 +14,7 @@ import (
 	"strings"
 	"time"
 
-	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/auth"
 )
 
 // BV/AV conversion constants (from https://github.com/Colerar/abv)
@@ -97,6 +97,7 @@ var (
 	bilibiliVideoRegex   = regexp.MustCompile(`bilibili\.com/video/(BV[\w]+|av\d+)`)
 	bilibiliShortRegex   = regexp.MustCompile(`b23\.tv/(BV[\w]+|av\d+|\w+)`)
 	bilibiliBangumiRegex = regexp.MustCompile(`bilibili\.com/bangumi/play/(ep|ss)(\d+)`)
+	bilibiliFavRegex     = regexp.MustCompile(`bilibili\.com/(?:\d+/favlist\?(?:.*&)?fid=|medialist/detail/ml|list/ml)(\d+)`)
 	bvRegex              = regexp.MustCompile(`(?i)^BV1[\w]{9}$`)
 	avRegex              = regexp.MustCompile(`(?i)^av(\d+)$`)
 )
@@ -128,30 +129,51 @@ func (b *BilibiliExtractor) Name() string {
 	return "bilibili"
 }
 
+// CacheTTL keeps results well within the ~2h lifetime of signed stream URLs
+func (b *BilibiliExtractor) CacheTTL() time.Duration {
+	return 30 * time.Minute
+}
+
 // Match checks if URL is a Bilibili video URL
 func (b *BilibiliExtractor) Match(u *url.URL) bool {
 	urlStr := u.String()
 	return bilibiliVideoRegex.MatchString(urlStr) ||
 		bilibiliShortRegex.MatchString(urlStr) ||
-		bilibiliBangumiRegex.MatchString(urlStr)
+		bilibiliBangumiRegex.MatchString(urlStr) ||
+		bilibiliFavRegex.MatchString(urlStr)
 }
 
 // Extract retrieves video information from a Bilibili URL
 func (b *BilibiliExtractor) Extract(urlStr string) (Media, error) {
-	// Initialize HTTP client
-	if b.client == nil {
-		b.client = &http.Client{
-			Timeout: 30 * time.Second,
-			CheckRedirect: func(req *http.Request, via []*http.Request) error {
-				return http.ErrUseLastResponse // Don't follow redirects automatically
-			},
-		}
+	return b.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (b *BilibiliExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *b
+	client := *ctx.HTTPClient()
+	client.CheckRedirect = func(req *http.Request, via []*http.Request) error {
+		return http.ErrUseLastResponse // Don't follow redirects automatically
+	}
+	call.client = &client
+	return call.extract(urlStr)
+}
+
+func (b *BilibiliExtractor) extract(urlStr string) (Media, error) {
+	// Load cookie from the auth store
+	login, _ := auth.Get(auth.Bilibili)
+	if cookie := login.CookieHeader(); cookie != "" {
+		b.cookie = cookie
 	}
 
-	// Load cookie from config
-	cfg := config.LoadOrDefault()
-	if cfg.Bilibili.Cookie != "" {
-		b.cookie = cfg.Bilibili.Cookie
+	// Favorites folders list their saved videos
+	if matches := bilibiliFavRegex.FindStringSubmatch(urlStr); len(matches) > 1 {
+		return b.extractFavorites(matches[1])
+	}
+
+	// Bangumi seasons (ss) list their episodes; episodes (ep) download directly
+	if matches := bilibiliBangumiRegex.FindStringSubmatch(urlStr); len(matches) > 2 {
+		return b.extractBangumi(matches[1], matches[2])
 	}
 
 	// Resolve short URLs and extract video ID
@@ -172,11 +194,31 @@ func (b *BilibiliExtractor) Extract(urlStr string) (Media, error) {
 		return nil, fmt.Errorf("failed to fetch video info: %w", err)
 	}
 
-	// Get first page CID
 	if len(videoInfo.Pages) == 0 {
 		return nil, fmt.Errorf("no video pages found")
 	}
-	cid := videoInfo.Pages[0].CID
+
+	// Without ?p=, multi-part videos and collections expand to all parts/episodes
+	page := requestedPage(urlStr)
+	if page == 0 {
+		if playlist := partsPlaylist(bvid, videoInfo); playlist != nil {
+			return playlist, nil
+		}
+		page = 1
+	}
+	if page > len(videoInfo.Pages) {
+		return nil, fmt.Errorf("part %d not found (video has %d parts)", page, len(videoInfo.Pages))
+	}
+	part := videoInfo.Pages[page-1]
+	cid := part.CID
+
+	title := videoInfo.Title
+	duration := videoInfo.Duration
+	if len(videoInfo.Pages) > 1 {
+		width := len(strconv.Itoa(len(videoInfo.Pages)))
+		title = fmt.Sprintf("%s - P%0*d %s", videoInfo.Title, width, part.Page, part.Part)
+		duration = part.Duration
+	}
 
 	// Fetch play URL to get stream info
 	streams, err := b.fetchPlayURL(aid, cid)
@@ -190,16 +232,112 @@ func (b *BilibiliExtractor) Extract(urlStr string) (Media, error) {
 		return nil, fmt.Errorf("no playable streams found")
 	}
 
+	id := bvid
+	if len(videoInfo.Pages) > 1 {
+		id = fmt.Sprintf("%s_p%d", bvid, part.Page)
+	}
+
+	subtitles, chapters := b.fetchPlayerExtras(aid, cid, duration)
+
 	return &VideoMedia{
-		ID:        bvid,
-		Title:     videoInfo.Title,
+		ID:        id,
+		Title:     title,
 		Uploader:  videoInfo.Owner.Name,
-		Duration:  videoInfo.Duration,
+		Duration:  duration,
 		Thumbnail: videoInfo.Pic,
 		Formats:   formats,
+		Subtitles: subtitles,
+		Chapters:  chapters,
+		Locked:    b.lockedQualities(streams),
 	}, nil
 }
 
+// lockedQualities reports the qualities withheld without a (premium) login,
+// or nil if every quality was returned
+func (b *BilibiliExtractor) lockedQualities(streams *BilibiliStreamInfo) *LockedQualities {
+	locked := lockedQualities(streams)
+	if len(locked) == 0 {
+		return nil
+	}
+	return &LockedQualities{
+		Site:       auth.Bilibili,
+		Qualities:  locked,
+		NeedsLogin: b.cookie == "",
+	}
+}
+
+// requestedPage returns the part number from ?p=, or 0 if not given
+func requestedPage(urlStr string) int {
+	u, err := url.Parse(urlStr)
+	if err != nil {
+		return 0
+	}
+	p, err := strconv.Atoi(u.Query().Get("p"))
+	if err != nil || p < 1 {
+		return 0
+	}
+	return p
+}
+
+// partsPlaylist lists every part (分P) of a multi-part video, or every episode
+// of the collection (合集) it belongs to. Entry titles are numbered so the
+// downloaded files sort in order. Returns nil for a standalone single video.
+func partsPlaylist(bvid string, info *BilibiliVideoInfo) *PlaylistMedia {
+	if len(info.Pages) > 1 {
+		playlist := &PlaylistMedia{
+			ID:        bvid,
+			Title:     info.Title,
+			Uploader:  info.Owner.Name,
+			Thumbnail: info.Pic,
+			Numbered:  true,
+		}
+		width := len(strconv.Itoa(len(info.Pages)))
+		for _, p := range info.Pages {
+			playlist.Entries = append(playlist.Entries, PlaylistEntry{
+				Title:     fmt.Sprintf("P%0*d %s", width, p.Page, p.Part),
+				URL:       fmt.Sprintf("https://www.bilibili.com/video/%s?p=%d", bvid, p.Page),
+				Thumbnail: info.Pic,
+				Duration:  p.Duration,
+			})
+		}
+		return playlist
+	}
+
+	season := info.UgcSeason
+	if season == nil {
+		return nil
+	}
+	playlist := &PlaylistMedia{
+		ID:        fmt.Sprintf("season%d", season.ID),
+		Title:     season.Title,
+		Uploader:  info.Owner.Name,
+		Thumbnail: season.Cover,
+		Numbered:  true,
+	}
+	count := 0
+	for _, section := range season.Sections {
+		count += len(section.Episodes)
+	}
+	if count <= 1 {
+		return nil
+	}
+	width := len(strconv.Itoa(count))
+	n := 0
+	for _, section := range season.Sections {
+		for _, ep := range section.Episodes {
+			n++
+			playlist.Entries = append(playlist.Entries, PlaylistEntry{
+				Title:     fmt.Sprintf("%0*d %s", width, n, ep.Title),
+				URL:       fmt.Sprintf("https://www.bilibili.com/video/%s?p=1", ep.BVID),
+				Thumbnail: ep.Arc.Pic,
+				Duration:  ep.Arc.Duration,
+			})
+		}
+	}
+	playlist.SingleURL = fmt.Sprintf("https://www.bilibili.com/video/%s?p=1", bvid)
+	return playlist
+}
+
 // resolveVideoID extracts aid and bvid from URL
 func (b *BilibiliExtractor) resolveVideoID(urlStr string) (aid int64, bvid string, err error) {
 	// Handle short URLs
@@ -400,6 +538,26 @@ type BilibiliVideoInfo struct {
 		Part     string `json:"part"`
 		Duration int    `json:"duration"`
 	} `json:"pages"`
+	UgcSeason *BilibiliSeason `json:"ugc_season"`
+}
+
+// BilibiliSeason is the collection (合集) a video belongs to
+type BilibiliSeason struct {
+	ID       int64  `json:"id"`
+	Title    string `json:"title"`
+	Cover    string `json:"cover"`
+	Sections []struct {
+		Episodes []struct {
+			Aid   int64  `json:"aid"`
+			CID   int64  `json:"cid"`
+			BVID  string `json:"bvid"`
+			Title string `json:"title"`
+			Arc   struct {
+				Pic      string `json:"pic"`
+				Duration int    `json:"duration"`
+			} `json:"arc"`
+		} `json:"episodes"`
+	} `json:"sections"`
 }
 
 // fetchVideoInfo retrieves video metadata
@@ -443,22 +601,37 @@ func (b *BilibiliExtractor) fetchVideoInfo(aid int64) (*BilibiliVideoInfo, error
 // BilibiliStreamInfo represents stream data
 type BilibiliStreamInfo struct {
 	Videos []struct {
-		ID        int    `json:"id"`
-		BaseURL   string `json:"baseUrl"`
+		ID        int      `json:"id"`
+		BaseURL   string   `json:"baseUrl"`
 		BackupURL []string `json:"backupUrl"`
-		Bandwidth int64  `json:"bandwidth"`
-		Width     int    `json:"width"`
-		Height    int    `json:"height"`
-		Codecs    string `json:"codecs"`
-		CodecID   int    `json:"codecid"`
+		Bandwidth int64    `json:"bandwidth"`
+		Width     int      `json:"width"`
+		Height    int      `json:"height"`
+		Codecs    string   `json:"codecs"`
+		CodecID   int      `json:"codecid"`
 	} `json:"video"`
-	Audios []struct {
-		ID        int    `json:"id"`
-		BaseURL   string `json:"baseUrl"`
-		BackupURL []string `json:"backupUrl"`
-		Bandwidth int64  `json:"bandwidth"`
-		Codecs    string `json:"codecs"`
-	} `json:"audio"`
+
+	Audios []BilibiliAudioStream `json:"audio"`
+
+	// AcceptQuality lists every quality the video offers, including ones
+	// that need a (premium) login and are missing from Videos
+	AcceptQuality []int `json:"-"`
+}
+
+// BilibiliAudioStream is a single DASH audio stream
+type BilibiliAudioStream struct {
+	ID        int      `json:"id"`
+	BaseURL   string   `json:"baseUrl"`
+	BackupURL []string `json:"backupUrl"`
+	Bandwidth int64    `json:"bandwidth"`
+	Codecs    string   `json:"codecs"`
+}
+
+// Audio quality definitions
+var audioQualityMap = map[int]string{
+	30216: "64K",
+	30232: "132K",
+	30280: "192K",
 }
 
 // fetchPlayURL retrieves stream URLs
@@ -497,7 +670,8 @@ func (b *BilibiliExtractor) fetchPlayURL(aid, cid int64) (*BilibiliStreamInfo, e
 		Code    int    `json:"code"`
 		Message string `json:"message"`
 		Data    struct {
-			Dash *BilibiliStreamInfo `json:"dash"`
+			AcceptQuality []int               `json:"accept_quality"`
+			Dash          *BilibiliStreamInfo `json:"dash"`
 		} `json:"data"`
 	}
 
@@ -513,22 +687,102 @@ func (b *BilibiliExtractor) fetchPlayURL(aid, cid int64) (*BilibiliStreamInfo, e
 		return nil, fmt.Errorf("no DASH streams available")
 	}
 
+	result.Data.Dash.AcceptQuality = result.Data.AcceptQuality
 	return result.Data.Dash, nil
 }
 
+// fetchPlayerExtras lists the subtitle tracks and chapters (view points) of a
+// part. Bilibili only returns subtitles when logged in; failures are ignored
+// since both are optional.
+func (b *BilibiliExtractor) fetchPlayerExtras(aid, cid int64, duration int) ([]Subtitle, []Chapter) {
+	params := url.Values{}
+	params.Set("aid", strconv.FormatInt(aid, 10))
+	params.Set("cid", strconv.FormatInt(cid, 10))
+
+	req, err := http.NewRequest("GET", "https://api.bilibili.com/x/player/wbi/v2?"+b.wbiSign(params), nil)
+	if err != nil {
+		return nil, nil
+	}
+	b.setHeaders(req)
+
+	resp, err := b.client.Do(req)
+	if err != nil {
+		return nil, nil
+	}
+	defer resp.Body.Close()
+
+	var result struct {
+		Code int `json:"code"`
+		Data struct {
+			Subtitle struct {
+				Subtitles []struct {
+					Lan         string `json:"lan"`
+					LanDoc      string `json:"lan_doc"`
+					SubtitleURL string `json:"subtitle_url"`
+				} `json:"subtitles"`
+			} `json:"subtitle"`
+			ViewPoints []struct {
+				Type    int    `json:"type"` // 2 = chapter
+				From    int    `json:"from"` // seconds
+				To      int    `json:"to"`
+				Content string `json:"content"`
+			} `json:"view_points"`
+		} `json:"data"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&result); err != nil || result.Code != 0 {
+		return nil, nil
+	}
+
+	var subs []Subtitle
+	for _, track := range result.Data.Subtitle.Subtitles {
+		if track.SubtitleURL == "" {
+			continue
+		}
+		subURL := track.SubtitleURL
+		if strings.HasPrefix(subURL, "//") {
+			subURL = "https:" + subURL
+		}
+		subs = append(subs, Subtitle{
+			Language: track.Lan,
+			Name:     track.LanDoc,
+			Ext:      "json", // Bilibili BCC JSON
+			URL:      subURL,
+			Auto:     strings.HasPrefix(track.Lan, "ai-"),
+		})
+	}
+
+	var chapters []Chapter
+	for _, vp := range result.Data.ViewPoints {
+		if vp.Type != 2 || vp.To <= vp.From {
+			continue
+		}
+		end := vp.To
+		if duration > 0 && end > duration {
+			end = duration
+		}
+		chapters = append(chapters, Chapter{Title: vp.Content, Start: float64(vp.From), End: float64(end)})
+	}
+	return subs, chapters
+}
+
 // buildFormats converts stream info to VideoFormat slice
 func (b *BilibiliExtractor) buildFormats(streams *BilibiliStreamInfo) []VideoFormat {
 	var formats []VideoFormat
 
-	// Find best audio stream
-	var bestAudioURL string
-	var bestAudioBandwidth int64
-	for _, audio := range streams.Audios {
-		if audio.Bandwidth > bestAudioBandwidth {
-			bestAudioBandwidth = audio.Bandwidth
-			bestAudioURL = audio.BaseURL
+	// Find best audio stream to merge with each video stream
+	var bestAudio *BilibiliAudioStream
+	for i := range streams.Audios {
+		if bestAudio == nil || streams.Audios[i].Bandwidth > bestAudio.Bandwidth {
+			bestAudio = &streams.Audios[i]
 		}
 	}
+	var bestAudioURL, audioLabel string
+	var audioMirrors []string
+	if bestAudio != nil {
+		bestAudioURL = bestAudio.BaseURL
+		audioMirrors = bestAudio.BackupURL
+		audioLabel = audioLabelFor(bestAudio)
+	}
 
 	// Build video formats
 	for _, video := range streams.Videos {
@@ -538,15 +792,21 @@ func (b *BilibiliExtractor) buildFormats(streams *BilibiliStreamInfo) []VideoFor
 		}
 
 		codec := getCodecName(video.CodecID)
+		label := fmt.Sprintf("%s [%s]", quality, codec)
+		if audioLabel != "" {
+			label += " + " + audioLabel
+		}
 
 		format := VideoFormat{
-			URL:      video.BaseURL,
-			Quality:  fmt.Sprintf("%s [%s]", quality, codec),
-			Ext:      "mp4",
-			Width:    video.Width,
-			Height:   video.Height,
-			Bitrate:  int(video.Bandwidth / 1000), // Convert to kbps
-			AudioURL: bestAudioURL,
+			URL:          video.BaseURL,
+			Mirrors:      video.BackupURL,
+			Quality:      label,
+			Ext:          "mp4",
+			Width:        video.Width,
+			Height:       video.Height,
+			Bitrate:      int(video.Bandwidth / 1000), // Convert to kbps
+			AudioURL:     bestAudioURL,
+			AudioMirrors: audioMirrors,
 			Headers: map[string]string{
 				"Referer":    "https://www.bilibili.com/",
 				"User-Agent": b.userAgent(),
@@ -564,9 +824,52 @@ func (b *BilibiliExtractor) buildFormats(streams *BilibiliStreamInfo) []VideoFor
 		return formats[i].Bitrate > formats[j].Bitrate
 	})
 
+	// Audio-only formats come last (height 0)
+	for _, audio := range streams.Audios {
+		formats = append(formats, VideoFormat{
+			URL:     audio.BaseURL,
+			Mirrors: audio.BackupURL,
+			Quality: "Audio only [" + audioLabelFor(&audio) + "]",
+			Ext:     "m4a",
+			Bitrate: int(audio.Bandwidth / 1000),
+			Headers: map[string]string{
+				"Referer":    "https://www.bilibili.com/",
+				"User-Agent": b.userAgent(),
+			},
+		})
+	}
+
 	return formats
 }
 
+// audioLabelFor returns a quality label for an audio stream
+func audioLabelFor(audio *BilibiliAudioStream) string {
+	if label, ok := audioQualityMap[audio.ID]; ok {
+		return label
+	}
+	return fmt.Sprintf("%dK", audio.Bandwidth/1000)
+}
+
+// lockedQualities returns labels of qualities the video offers that were not returned,
+// which happens when they require a login or premium membership
+func lockedQualities(streams *BilibiliStreamInfo) []string {
+	available := make(map[int]bool)
+	for _, video := range streams.Videos {
+		available[video.ID] = true
+	}
+
+	var locked []string
+	for _, qn := range streams.AcceptQuality {
+		if available[qn] {
+			continue
+		}
+		if label, ok := qualityMap[qn]; ok {
+			locked = append(locked, label)
+		}
+	}
+	return locked
+}
+
 // getCodecName converts codec ID to name
 func getCodecName(codecID int) string {
 	switch codecID {
@@ -601,6 +904,7 @@ func init() {
 	Register(&BilibiliExtractor{},
 		"bilibili.com",
 		"www.bilibili.com",
+		"space.bilibili.com",
 		"b23.tv",
 	)
 }
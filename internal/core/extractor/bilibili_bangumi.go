@@ -0,0 +1,217 @@
+package extractor
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+)
+
+// bilibiliPGCSeason is the bangumi season metadata returned by the PGC view API
+type bilibiliPGCSeason struct {
+	SeasonID    int64  `json:"season_id"`
+	SeasonTitle string `json:"season_title"`
+	Title       string `json:"title"`
+	Cover       string `json:"cover"`
+	UpInfo      struct {
+		Uname string `json:"uname"`
+	} `json:"up_info"`
+	Episodes []bilibiliEpisode `json:"episodes"`
+}
+
+// bilibiliEpisode is a single bangumi episode
+type bilibiliEpisode struct {
+	ID        int64  `json:"id"` // ep_id
+	Aid       int64  `json:"aid"`
+	BVID      string `json:"bvid"`
+	CID       int64  `json:"cid"`
+	Title     string `json:"title"` // episode number, e.g. "1"
+	LongTitle string `json:"long_title"`
+	Duration  int    `json:"duration"` // milliseconds
+	Cover     string `json:"cover"`
+	Badge     string `json:"badge"` // e.g. "会员" for premium-only episodes
+	PubTime   int64  `json:"pub_time"`
+}
+
+// displayTitle combines the episode number and name
+func (e *bilibiliEpisode) displayTitle() string {
+	title := e.Title
+	if _, err := strconv.Atoi(title); err == nil {
+		title = "EP" + title
+	}
+	if e.LongTitle != "" {
+		title = strings.TrimSpace(title + " " + e.LongTitle)
+	}
+	return title
+}
+
+// extractBangumi resolves a bangumi season (kind "ss") into a playlist of its
+// episodes, or an episode (kind "ep") into a video
+func (b *BilibiliExtractor) extractBangumi(kind, id string) (Media, error) {
+	season, err := b.fetchSeason(kind, id)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch season info: %w", err)
+	}
+	if len(season.Episodes) == 0 {
+		return nil, fmt.Errorf("season has no episodes")
+	}
+
+	if kind == "ss" {
+		playlist := &PlaylistMedia{
+			ID:        fmt.Sprintf("ss%d", season.SeasonID),
+			Title:     season.Title,
+			Uploader:  season.UpInfo.Uname,
+			Thumbnail: season.Cover,
+			Numbered:  true,
+		}
+		width := len(strconv.Itoa(len(season.Episodes)))
+		for i := range season.Episodes {
+			ep := &season.Episodes[i]
+			playlist.Entries = append(playlist.Entries, PlaylistEntry{
+				Title:     fmt.Sprintf("%0*d %s", width, i+1, ep.displayTitle()),
+				URL:       fmt.Sprintf("https://www.bilibili.com/bangumi/play/ep%d", ep.ID),
+				Published: unixTime(ep.PubTime),
+				Thumbnail: ep.Cover,
+				Duration:  ep.Duration / 1000,
+			})
+		}
+		return playlist, nil
+	}
+
+	epID, _ := strconv.ParseInt(id, 10, 64)
+	var ep *bilibiliEpisode
+	for i := range season.Episodes {
+		if season.Episodes[i].ID == epID {
+			ep = &season.Episodes[i]
+			break
+		}
+	}
+	if ep == nil {
+		return nil, fmt.Errorf("episode ep%s not found in season", id)
+	}
+
+	streams, err := b.fetchPGCPlayURL(ep)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch play URL: %w", err)
+	}
+
+	formats := b.buildFormats(streams)
+	if len(formats) == 0 {
+		return nil, fmt.Errorf("no playable streams found")
+	}
+
+	return &VideoMedia{
+		ID:        fmt.Sprintf("ep%d", ep.ID),
+		Title:     fmt.Sprintf("%s - %s", season.Title, ep.displayTitle()),
+		Uploader:  season.UpInfo.Uname,
+		Duration:  ep.Duration / 1000,
+		Thumbnail: ep.Cover,
+		Formats:   formats,
+		Locked:    b.lockedQualities(streams),
+	}, nil
+}
+
+// fetchSeason retrieves season metadata by ep_id or season_id
+func (b *BilibiliExtractor) fetchSeason(kind, id string) (*bilibiliPGCSeason, error) {
+	params := url.Values{}
+	if kind == "ep" {
+		params.Set("ep_id", id)
+	} else {
+		params.Set("season_id", id)
+	}
+
+	req, err := http.NewRequest("GET", "https://api.bilibili.com/pgc/view/web/season?"+params.Encode(), nil)
+	if err != nil {
+		return nil, err
+	}
+	b.setHeaders(req)
+
+	resp, err := b.client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+
+	var result struct {
+		Code    int               `json:"code"`
+		Message string            `json:"message"`
+		Result  bilibiliPGCSeason `json:"result"`
+	}
+	if err := json.Unmarshal(body, &result); err != nil {
+		return nil, err
+	}
+	if result.Code != 0 {
+		return nil, fmt.Errorf("API error: %s (code: %d)", result.Message, result.Code)
+	}
+	return &result.Result, nil
+}
+
+// fetchPGCPlayURL retrieves episode streams. Premium-only episodes and
+// qualities need the stored cookie of a premium account.
+func (b *BilibiliExtractor) fetchPGCPlayURL(ep *bilibiliEpisode) (*BilibiliStreamInfo, error) {
+	params := url.Values{}
+	params.Set("avid", strconv.FormatInt(ep.Aid, 10))
+	params.Set("cid", strconv.FormatInt(ep.CID, 10))
+	params.Set("ep_id", strconv.FormatInt(ep.ID, 10))
+	params.Set("fnval", "4048") // DASH + HDR + Dolby + 8K + AV1
+	params.Set("fnver", "0")
+	params.Set("fourk", "1")
+	params.Set("qn", "127")
+
+	req, err := http.NewRequest("GET", "https://api.bilibili.com/pgc/player/web/playurl?"+params.Encode(), nil)
+	if err != nil {
+		return nil, err
+	}
+	b.setHeaders(req)
+
+	resp, err := b.client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+
+	var result struct {
+		Code    int    `json:"code"`
+		Message string `json:"message"`
+		Result  struct {
+			AcceptQuality []int               `json:"accept_quality"`
+			IsPreview     int                 `json:"is_preview"`
+			Dash          *BilibiliStreamInfo `json:"dash"`
+		} `json:"result"`
+	}
+	if err := json.Unmarshal(body, &result); err != nil {
+		return nil, err
+	}
+
+	if result.Code != 0 {
+		if result.Code == -10403 {
+			if b.cookie == "" {
+				return nil, fmt.Errorf("episode needs a Bilibili login (vget login bilibili): %s", result.Message)
+			}
+			return nil, fmt.Errorf("episode needs a Bilibili premium membership: %s", result.Message)
+		}
+		return nil, fmt.Errorf("API error: %s (code: %d)", result.Message, result.Code)
+	}
+	if result.Result.Dash == nil {
+		return nil, fmt.Errorf("no DASH streams available")
+	}
+	if result.Result.IsPreview == 1 {
+		fmt.Println("Note: only a preview is available; the full episode needs a premium login")
+	}
+
+	result.Result.Dash.AcceptQuality = result.Result.AcceptQuality
+	return result.Result.Dash, nil
+}
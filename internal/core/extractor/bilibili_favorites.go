@@ -0,0 +1,142 @@
+package extractor
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strconv"
+	"time"
+)
+
+// bilibiliFavPageSize is the largest page the favorites API accepts
+const bilibiliFavPageSize = 20
+
+// bilibiliFavMaxPages bounds paging; a folder holds at most 1000 videos
+const bilibiliFavMaxPages = 50
+
+// bilibiliFavPage is one page of the favorites resource list API
+type bilibiliFavPage struct {
+	Info struct {
+		Title      string `json:"title"`
+		Cover      string `json:"cover"`
+		MediaCount int    `json:"media_count"`
+		Upper      struct {
+			Name string `json:"name"`
+		} `json:"upper"`
+	} `json:"info"`
+	Medias []struct {
+		BVID     string `json:"bvid"`
+		Title    string `json:"title"`
+		Cover    string `json:"cover"`
+		Duration int    `json:"duration"`
+		Attr     int    `json:"attr"` // non-zero when the video was deleted or made private
+		Pubtime  int64  `json:"pubtime"`
+	} `json:"medias"`
+	HasMore bool `json:"has_more"`
+}
+
+// extractFavorites pages through a favorites folder and returns its videos as a playlist.
+// Private folders need the stored login cookie.
+func (b *BilibiliExtractor) extractFavorites(mediaID string) (Media, error) {
+	playlist := &PlaylistMedia{ID: "fav" + mediaID}
+	skipped := 0
+
+	for pn := 1; pn <= bilibiliFavMaxPages; pn++ {
+		page, err := b.fetchFavPage(mediaID, pn)
+		if err != nil {
+			if len(playlist.Entries) > 0 {
+				fmt.Printf("Warning: stopped at page %d: %v\n", pn, err)
+				break
+			}
+			return nil, err
+		}
+
+		if pn == 1 {
+			playlist.Title = page.Info.Title
+			playlist.Uploader = page.Info.Upper.Name
+			playlist.Thumbnail = page.Info.Cover
+		}
+
+		for _, m := range page.Medias {
+			if m.Attr != 0 || m.BVID == "" {
+				skipped++
+				continue
+			}
+			playlist.Entries = append(playlist.Entries, PlaylistEntry{
+				Title:     m.Title,
+				URL:       fmt.Sprintf("https://www.bilibili.com/video/%s?p=1", m.BVID),
+				Published: unixTime(m.Pubtime),
+				Thumbnail: m.Cover,
+				Duration:  m.Duration,
+			})
+		}
+
+		if !page.HasMore || len(page.Medias) == 0 {
+			break
+		}
+	}
+
+	if skipped > 0 {
+		fmt.Printf("Note: skipped %d unavailable video(s) in favorites\n", skipped)
+	}
+	if len(playlist.Entries) == 0 {
+		return nil, fmt.Errorf("favorites folder is empty")
+	}
+	if playlist.Title == "" {
+		playlist.Title = playlist.ID
+	}
+	return playlist, nil
+}
+
+// fetchFavPage fetches one page of a favorites folder
+func (b *BilibiliExtractor) fetchFavPage(mediaID string, pn int) (*bilibiliFavPage, error) {
+	params := url.Values{}
+	params.Set("media_id", mediaID)
+	params.Set("pn", strconv.Itoa(pn))
+	params.Set("ps", strconv.Itoa(bilibiliFavPageSize))
+	params.Set("platform", "web")
+
+	req, err := http.NewRequest("GET", "https://api.bilibili.com/x/v3/fav/resource/list?"+params.Encode(), nil)
+	if err != nil {
+		return nil, err
+	}
+	b.setHeaders(req)
+
+	resp, err := b.client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+
+	var result struct {
+		Code    int             `json:"code"`
+		Message string          `json:"message"`
+		Data    bilibiliFavPage `json:"data"`
+	}
+	if err := json.Unmarshal(body, &result); err != nil {
+		return nil, err
+	}
+
+	if result.Code != 0 {
+		if b.cookie == "" {
+			return nil, fmt.Errorf("API error: %s (code: %d); private folders need a login (vget login bilibili)", result.Message, result.Code)
+		}
+		return nil, fmt.Errorf("API error: %s (code: %d)", result.Message, result.Code)
+	}
+	return &result.Data, nil
+}
+
+// unixTime converts a Unix timestamp, treating 0 as unknown
+func unixTime(sec int64) time.Time {
+	if sec <= 0 {
+		return time.Time{}
+	}
+	return time.Unix(sec, 0)
+}
@@ -21,6 +21,8 @@ import (
 type BrowserExtractor struct {
 	site    *config.Site
 	visible bool
+	generic bool // unknown site: sniff the page HTML before launching a browser
+	ctx     *ExtractContext
 }
 
 // NewBrowserExtractor creates a new browser extractor for the given site
@@ -33,6 +35,7 @@ func NewGenericBrowserExtractor(visible bool) *BrowserExtractor {
 	return &BrowserExtractor{
 		site:    &config.Site{Type: "m3u8"},
 		visible: visible,
+		generic: true,
 	}
 }
 
@@ -40,6 +43,11 @@ func (e *BrowserExtractor) Name() string {
 	return "browser"
 }
 
+// CacheTTL is short because captured streams often carry expiring tokens
+func (e *BrowserExtractor) CacheTTL() time.Duration {
+	return 5 * time.Minute
+}
+
 func (e *BrowserExtractor) Match(u *url.URL) bool {
 	return true // Called only when site matches
 }
@@ -48,6 +56,21 @@ func (e *BrowserExtractor) Match(u *url.URL) bool {
 type extractionStrategy func(page *rod.Page, targetExt string) string
 
 func (e *BrowserExtractor) Extract(rawURL string) (Media, error) {
+	return e.ExtractWithContext(rawURL, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client for page
+// sniffing and its proxy for the browser
+func (e *BrowserExtractor) ExtractWithContext(rawURL string, ctx *ExtractContext) (Media, error) {
+	if ctx == nil {
+		ctx = NewExtractContext()
+	}
+	call := *e
+	call.ctx = ctx
+	return call.extract(rawURL)
+}
+
+func (e *BrowserExtractor) extract(rawURL string) (Media, error) {
 	if e.site == nil {
 		return nil, fmt.Errorf("no site configuration provided")
 	}
@@ -59,13 +82,20 @@ func (e *BrowserExtractor) Extract(rawURL string) (Media, error) {
 	}
 	pageOrigin := fmt.Sprintf("%s://%s", pageURL.Scheme, pageURL.Host)
 
+	// Unknown sites often declare their media in og:video, <video>, or JSON-LD
+	if e.generic {
+		if media, err := sniffPage(e.ctx.HTTPClient(), rawURL); err == nil {
+			return media, nil
+		}
+	}
+
 	// Normalize extension to lowercase once for consistent matching
 	targetExt := strings.ToLower("." + e.site.Type) // e.g., ".m3u8", ".mp4"
 
 	fmt.Printf("  Trying to detecting %s stream...\n", e.site.Type)
 
 	// Launch browser
-	l := e.createLauncher(!e.visible) // headless unless --visible flag
+	l := e.createLauncher(rawURL, !e.visible) // headless unless --visible flag
 	defer l.Cleanup()
 
 	u, err := l.Launch()
@@ -326,7 +356,11 @@ func (e *BrowserExtractor) findInPageSource(page *rod.Page, targetExt string) st
 	return ""
 }
 
-func (e *BrowserExtractor) createLauncher(headless bool) *launcher.Launcher {
+// browserUserAgent is the user agent of the automated browser, which cookies
+// saved from it are issued to
+const browserUserAgent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36"
+
+func (e *BrowserExtractor) createLauncher(rawURL string, headless bool) *launcher.Launcher {
 	userDataDir := e.getUserDataDir()
 
 	// Check for ROD_BROWSER env var (set in Docker)
@@ -346,13 +380,19 @@ func (e *BrowserExtractor) createLauncher(headless bool) *launcher.Launcher {
 		Set("no-first-run").
 		Set("safebrowsing-disable-auto-update").
 		Set("window-size", "1920,1080").
-		Set("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+		Set("user-agent", browserUserAgent)
 
 	// Explicitly set browser path if provided (required for Docker)
 	if browserPath != "" {
 		l = l.Bin(browserPath)
 	}
 
+	if e.ctx != nil {
+		if proxy := e.ctx.ProxyFor(rawURL); proxy != "" && proxy != config.ProxyDirect {
+			l = l.Proxy(proxy)
+		}
+	}
+
 	return l
 }
 
@@ -0,0 +1,207 @@
+package extractor
+
+import (
+	"context"
+	"fmt"
+	"net/url"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/go-rod/rod"
+	"github.com/go-rod/stealth"
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+// BrowserLoginTimeout is how long BrowserLogin waits for the user
+const BrowserLoginTimeout = 5 * time.Minute
+
+// BrowserLoginOptions says where BrowserLogin logs in and how it tells the
+// user is logged in
+type BrowserLoginOptions struct {
+	Site            string   // site ID the cookies are stored under: a known site or a domain
+	URL             string   // login page
+	SuccessSelector string   // CSS selector of an element shown only once logged in
+	Domains         []string // domains whose cookies are saved; defaults to the site's, or else URL's
+
+	// loggedIn replaces SuccessSelector with a check of the page's state
+	loggedIn func(page *rod.Page) (bool, error)
+}
+
+// hasCookie is a BrowserLoginOptions.loggedIn that reports whether the
+// browser holds one of the named cookies for pageURL, as set once logged in
+func hasCookie(pageURL string, names ...string) func(page *rod.Page) (bool, error) {
+	return func(page *rod.Page) (bool, error) {
+		cookies, err := page.Cookies([]string{pageURL})
+		if err != nil {
+			return false, err
+		}
+		for _, c := range cookies {
+			if c.Value != "" && slices.Contains(names, c.Name) {
+				return true, nil
+			}
+		}
+		return false, nil
+	}
+}
+
+// SiteLoginOptions returns the built-in BrowserLoginOptions of site, which
+// need no URL or selector, if it has them
+func SiteLoginOptions(site string) (BrowserLoginOptions, bool) {
+	switch site {
+	case auth.Xiaohongshu:
+		return XiaohongshuLoginOptions(), true
+	case auth.YouTube:
+		return YouTubeLoginOptions(), true
+	case auth.Twitter:
+		return TwitterLoginOptions(), true
+	}
+	return BrowserLoginOptions{}, false
+}
+
+// loginDomains are the domains whose cookies a login with opts saves
+func (opts BrowserLoginOptions) loginDomains() []string {
+	if len(opts.Domains) > 0 {
+		return opts.Domains
+	}
+	if strings.Contains(opts.Site, ".") {
+		return []string{opts.Site}
+	}
+	if u, err := url.Parse(opts.URL); err == nil {
+		return []string{strings.TrimPrefix(u.Hostname(), "www.")}
+	}
+	return nil
+}
+
+// cookieInDomains reports whether a cookie set for cookieDomain is sent to
+// one of domains or their subdomains
+func cookieInDomains(cookieDomain string, domains []string) bool {
+	cookieDomain = strings.ToLower(strings.TrimPrefix(cookieDomain, "."))
+	for _, domain := range domains {
+		domain = strings.ToLower(domain)
+		if cookieDomain == domain || strings.HasSuffix(cookieDomain, "."+domain) || strings.HasSuffix(domain, "."+cookieDomain) {
+			return true
+		}
+	}
+	return false
+}
+
+// BrowserLogin opens a browser window on opts.URL for the user to log in to
+// a site. Once logged in, it saves the cookies the browser holds for the
+// site's domains to the auth store, closes the window, and returns how many
+// cookies were saved. It fails when the window is closed first, ctx is done,
+// or BrowserLoginTimeout passes.
+func BrowserLogin(ctx context.Context, opts BrowserLoginOptions) (int, error) {
+	site, err := auth.SiteID(opts.Site)
+	if err != nil {
+		return 0, err
+	}
+	opts.Site = site
+	if u, err := url.Parse(opts.URL); err != nil || (u.Scheme != "http" && u.Scheme != "https") {
+		return 0, fmt.Errorf("invalid login URL: %q", opts.URL)
+	}
+	if opts.loggedIn == nil {
+		if opts.SuccessSelector == "" {
+			return 0, fmt.Errorf("a success selector is required to tell when %s is logged in", site)
+		}
+		opts.loggedIn = func(page *rod.Page) (bool, error) {
+			has, _, err := page.Has(opts.SuccessSelector)
+			return has, err
+		}
+	}
+
+	ctx, cancel := context.WithTimeout(ctx, BrowserLoginTimeout)
+	defer cancel()
+
+	l := (&BrowserExtractor{ctx: NewExtractContext()}).createLauncher(opts.URL, false)
+	defer l.Cleanup()
+	u, err := l.Launch()
+	if err != nil {
+		return 0, fmt.Errorf("failed to launch browser: %w", err)
+	}
+
+	browser := rod.New().ControlURL(u)
+	if err := browser.Connect(); err != nil {
+		return 0, fmt.Errorf("failed to connect to browser: %w", err)
+	}
+	defer browser.Close()
+
+	page, err := stealth.Page(browser)
+	if err != nil {
+		return 0, fmt.Errorf("failed to open page: %w", err)
+	}
+	if err := page.Navigate(opts.URL); err != nil {
+		return 0, fmt.Errorf("failed to open %s: %w", opts.URL, err)
+	}
+
+	ticker := time.NewTicker(2 * time.Second)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			if ctx.Err() == context.DeadlineExceeded {
+				return 0, fmt.Errorf("timed out waiting for login")
+			}
+			return 0, ctx.Err()
+		case <-ticker.C:
+		}
+
+		loggedIn, err := opts.loggedIn(page)
+		if err != nil {
+			// The page navigates while logging in; only a gone browser is fatal
+			if _, versionErr := browser.Version(); versionErr != nil {
+				return 0, fmt.Errorf("login window was closed")
+			}
+			continue
+		}
+		if !loggedIn {
+			continue
+		}
+
+		n, err := saveBrowserCookies(browser, site, opts.loginDomains())
+		if err != nil {
+			return 0, fmt.Errorf("failed to save cookies: %w", err)
+		}
+		if n == 0 {
+			return 0, fmt.Errorf("logged in, but the browser holds no cookies for %s", site)
+		}
+		return n, nil
+	}
+}
+
+// saveBrowserCookies stores the cookies browser holds for domains as the
+// login of site, with the user agent they were issued to, and returns how
+// many there were. Its tokens are kept; nothing is saved when there are none.
+func saveBrowserCookies(browser *rod.Browser, site string, domains []string) (int, error) {
+	cookies, err := browser.GetCookies()
+	if err != nil {
+		return 0, err
+	}
+
+	var saved []auth.Cookie
+	for _, c := range cookies {
+		if !cookieInDomains(c.Domain, domains) {
+			continue
+		}
+		cookie := auth.Cookie{
+			Name: c.Name, Value: c.Value, Domain: c.Domain, Path: c.Path,
+			HTTPOnly: c.HTTPOnly, Secure: c.Secure,
+		}
+		if !c.Session && c.Expires > 0 {
+			cookie.Expires = c.Expires.Time()
+		}
+		saved = append(saved, cookie)
+	}
+	if len(saved) == 0 {
+		return 0, nil
+	}
+
+	login, _ := auth.Get(site)
+	login.Cookies = saved
+	login.UserAgent = browserUserAgent
+	login.Expires = time.Time{}
+	if err := auth.Save(login); err != nil {
+		return 0, err
+	}
+	return len(saved), nil
+}
@@ -0,0 +1,41 @@
+package extractor
+
+import (
+	"slices"
+	"testing"
+)
+
+func TestCookieInDomains(t *testing.T) {
+	domains := []string{"www.example.com", "cdn.net"}
+	tests := []struct {
+		cookieDomain string
+		want         bool
+	}{
+		{".example.com", true}, // parent domain, sent to www.example.com too
+		{"www.example.com", true},
+		{"img.cdn.net", true},
+		{"other.example.com", false},
+		{"notcdn.net", false},
+	}
+	for _, tt := range tests {
+		if got := cookieInDomains(tt.cookieDomain, domains); got != tt.want {
+			t.Errorf("cookieInDomains(%q) = %v, want %v", tt.cookieDomain, got, tt.want)
+		}
+	}
+}
+
+func TestLoginDomains(t *testing.T) {
+	tests := []struct {
+		opts BrowserLoginOptions
+		want []string
+	}{
+		{BrowserLoginOptions{Site: "example.com", URL: "https://accounts.example.org/login"}, []string{"example.com"}},
+		{BrowserLoginOptions{Site: "twitter", URL: "https://www.x.com/login"}, []string{"x.com"}},
+		{BrowserLoginOptions{Site: "xiaohongshu", Domains: xhsCookieDomains}, xhsCookieDomains},
+	}
+	for _, tt := range tests {
+		if got := tt.opts.loginDomains(); !slices.Equal(got, tt.want) {
+			t.Errorf("loginDomains(%+v) = %v, want %v", tt.opts, got, tt.want)
+		}
+	}
+}
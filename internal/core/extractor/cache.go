@@ -0,0 +1,120 @@
+package extractor
+
+import (
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+)
+
+// DefaultCacheTTL is how long extraction results are reused when the
+// extractor doesn't set its own TTL. Stream URLs usually expire within hours.
+const DefaultCacheTTL = 10 * time.Minute
+
+// CacheTTLer is implemented by extractors whose results stay valid for a
+// different time than DefaultCacheTTL. A TTL of 0 disables caching.
+type CacheTTLer interface {
+	CacheTTL() time.Duration
+}
+
+// CacheTTL returns how long results of the extractor may be cached
+func CacheTTL(e Extractor) time.Duration {
+	if t, ok := e.(CacheTTLer); ok {
+		return t.CacheTTL()
+	}
+	return DefaultCacheTTL
+}
+
+// trackingParams are query parameters that don't change what a URL points to
+var trackingParams = map[string]bool{
+	"fbclid": true, "gclid": true, "si": true, "feature": true,
+	"spm_id_from": true, "vd_source": true, "share_source": true, "share_medium": true,
+	"from_spmid": true, "xsec_source": true, "igsh": true, "ref_src": true,
+}
+
+// CanonicalURL normalizes a URL for use as a cache key: lowercase host, no
+// fragment, and no tracking parameters
+func CanonicalURL(rawURL string) string {
+	normalized, err := NormalizeURL(rawURL)
+	if err != nil {
+		return rawURL
+	}
+	u, err := url.Parse(normalized)
+	if err != nil {
+		return normalized
+	}
+
+	u.Host = strings.ToLower(u.Host)
+	u.Fragment = ""
+	q := u.Query()
+	for key := range q {
+		if trackingParams[key] || strings.HasPrefix(key, "utm_") {
+			q.Del(key)
+		}
+	}
+	u.RawQuery = q.Encode()
+	return u.String()
+}
+
+type cacheEntry struct {
+	media   Media
+	expires time.Time
+}
+
+// Cache keeps extraction results in memory until their TTL expires
+type Cache struct {
+	mu      sync.Mutex
+	entries map[string]cacheEntry
+}
+
+// NewCache creates an empty cache
+func NewCache() *Cache {
+	return &Cache{entries: make(map[string]cacheEntry)}
+}
+
+// Get returns the cached media for a URL, if present and not expired
+func (c *Cache) Get(rawURL string) (Media, bool) {
+	key := CanonicalURL(rawURL)
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	entry, ok := c.entries[key]
+	if !ok {
+		return nil, false
+	}
+	if time.Now().After(entry.expires) {
+		delete(c.entries, key)
+		return nil, false
+	}
+	return entry.media, true
+}
+
+// Put caches media for a URL for ttl; a ttl of 0 or less is ignored
+func (c *Cache) Put(rawURL string, media Media, ttl time.Duration) {
+	if ttl <= 0 || media == nil {
+		return
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	// Drop expired entries so the cache doesn't grow without bound
+	now := time.Now()
+	for key, entry := range c.entries {
+		if now.After(entry.expires) {
+			delete(c.entries, key)
+		}
+	}
+	c.entries[CanonicalURL(rawURL)] = cacheEntry{media: media, expires: now.Add(ttl)}
+}
+
+// Clear removes all entries and returns how many there were
+func (c *Cache) Clear() int {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	n := len(c.entries)
+	c.entries = make(map[string]cacheEntry)
+	return n
+}
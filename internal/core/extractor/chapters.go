@@ -0,0 +1,55 @@
+package extractor
+
+import (
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// chapterLineRegex matches a description line starting with a timestamp,
+// e.g. "0:00 Intro", "01:02:03 - Outro", "• 12:34 Part two"
+var chapterLineRegex = regexp.MustCompile(`^\s*[-*•▶►]?\s*\(?((?:\d{1,2}:)?\d{1,2}:\d{2})\)?\s*[-–—:|.]?\s*(.+?)\s*$`)
+
+// parseDescriptionChapters reads YouTube-style chapters from a description.
+// Like YouTube, it requires the first chapter at 0:00, at least three
+// chapters, and increasing timestamps; otherwise it returns nil.
+// duration (seconds) ends the last chapter.
+func parseDescriptionChapters(description string, duration int) []Chapter {
+	var chapters []Chapter
+	for _, line := range strings.Split(description, "\n") {
+		m := chapterLineRegex.FindStringSubmatch(line)
+		if m == nil {
+			continue
+		}
+		start := parseTimestamp(m[1])
+		if len(chapters) > 0 && start <= chapters[len(chapters)-1].Start {
+			return nil
+		}
+		chapters = append(chapters, Chapter{Title: m[2], Start: start})
+	}
+
+	if len(chapters) < 3 || chapters[0].Start != 0 {
+		return nil
+	}
+	for i := range chapters {
+		if i+1 < len(chapters) {
+			chapters[i].End = chapters[i+1].Start
+		} else {
+			chapters[i].End = float64(duration)
+		}
+	}
+	if last := chapters[len(chapters)-1]; last.End <= last.Start {
+		return nil
+	}
+	return chapters
+}
+
+// parseTimestamp converts "h:mm:ss" or "m:ss" to seconds
+func parseTimestamp(ts string) float64 {
+	var seconds int
+	for _, part := range strings.Split(ts, ":") {
+		n, _ := strconv.Atoi(part)
+		seconds = seconds*60 + n
+	}
+	return float64(seconds)
+}
@@ -0,0 +1,44 @@
+package extractor
+
+import "testing"
+
+func TestParseDescriptionChapters(t *testing.T) {
+	description := `Thanks for watching!
+
+Chapters:
+0:00 Intro
+1:30 - Setup
+• 12:05 The main part
+1:02:00 Outro
+
+Links: https://example.com`
+
+	got := parseDescriptionChapters(description, 3900)
+	want := []Chapter{
+		{Title: "Intro", Start: 0, End: 90},
+		{Title: "Setup", Start: 90, End: 725},
+		{Title: "The main part", Start: 725, End: 3720},
+		{Title: "Outro", Start: 3720, End: 3900},
+	}
+	if len(got) != len(want) {
+		t.Fatalf("got %d chapters, want %d: %+v", len(got), len(want), got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("chapter %d = %+v, want %+v", i, got[i], want[i])
+		}
+	}
+}
+
+func TestParseDescriptionChaptersRejectsNonChapters(t *testing.T) {
+	tests := map[string]string{
+		"not from zero": "0:10 A\n1:00 B\n2:00 C",
+		"too few":       "0:00 A\n1:00 B",
+		"out of order":  "0:00 A\n2:00 B\n1:00 C",
+	}
+	for name, description := range tests {
+		if got := parseDescriptionChapters(description, 300); got != nil {
+			t.Errorf("%s: got %+v, want nil", name, got)
+		}
+	}
+}
@@ -0,0 +1,211 @@
+package extractor
+
+import (
+	"maps"
+	"net"
+	"net/http"
+	"net/url"
+	"sort"
+	"strings"
+	"sync"
+	"syscall"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/network"
+)
+
+// extractTimeout bounds each HTTP request made during extraction
+const extractTimeout = 30 * time.Second
+
+// ExtractContext holds per-request settings for choosing and running an
+// extractor: the HTTP client (with proxy, user agent, and cookie settings)
+// and the rate limiter shared by every extraction
+type ExtractContext struct {
+	Visible   bool              // show the browser window for browser-based extraction
+	Limiter   *RateLimiter      // per-site request pacing (nil uses the shared limiter)
+	Proxy     string            // proxy URL; empty uses HTTP_PROXY/HTTPS_PROXY
+	Proxies   map[string]string // per-domain proxy overrides, config.ProxyDirect for none
+	UserAgent string            // replaces the user agent extractors send, if set
+	Cookies   map[string]string // Cookie header per domain, matching subdomains
+
+	once   sync.Once
+	client *http.Client
+}
+
+// NewExtractContext creates a context from the network settings in config.yml.
+// Domains with a login in the auth store, e.g. from a browser login, get its
+// cookies unless network.cookies sets theirs.
+func NewExtractContext() *ExtractContext {
+	cfg := config.LoadOrDefault()
+	cookies := maps.Clone(cfg.Network.Cookies)
+	for _, site := range auth.Sites() {
+		if _, ok := cookies[site]; ok || !strings.Contains(site, ".") {
+			continue
+		}
+		login, err := auth.Get(site)
+		if header := login.CookieHeader(); err == nil && header != "" {
+			if cookies == nil {
+				cookies = make(map[string]string)
+			}
+			cookies[site] = header
+		}
+	}
+	return &ExtractContext{
+		Proxy:     cfg.Network.Proxy,
+		Proxies:   cfg.Network.Proxies,
+		UserAgent: cfg.Network.UserAgent,
+		Cookies:   cookies,
+	}
+}
+
+// ContextExtractor is an extractor that makes its requests with the HTTP
+// client of an ExtractContext instead of building its own
+type ContextExtractor interface {
+	Extractor
+	ExtractWithContext(url string, ctx *ExtractContext) (Media, error)
+}
+
+// Extract waits for the URL's rate limit, then runs the extractor with the
+// context's HTTP client when it supports one. A nil context uses the config
+// defaults.
+func Extract(e Extractor, rawURL string, ctx *ExtractContext) (Media, error) {
+	if ctx == nil {
+		ctx = NewExtractContext()
+	}
+	limiter := sharedLimiter
+	if ctx.Limiter != nil {
+		limiter = ctx.Limiter
+	}
+	limiter.Wait(rawURL)
+
+	if ce, ok := e.(ContextExtractor); ok {
+		return ce.ExtractWithContext(rawURL, ctx)
+	}
+	return e.Extract(rawURL)
+}
+
+// HTTPClient returns the context's client. Connections are pooled across
+// contexts that use the same proxy settings. A nil context uses the config
+// defaults.
+func (c *ExtractContext) HTTPClient() *http.Client {
+	if c == nil {
+		return NewExtractContext().HTTPClient()
+	}
+	c.once.Do(func() {
+		c.client = &http.Client{
+			Timeout:   extractTimeout,
+			Transport: &contextTransport{base: pooledTransport(c.network()), ctx: c},
+		}
+	})
+	return c.client
+}
+
+// HTTPClientWithControl is HTTPClient with control run on every connection it
+// makes, with the address actually dialed, so the address can be vetted after
+// the host was resolved. Connections to a proxy aren't passed to control, the
+// proxy resolves the host itself. The client has its own connection pool.
+func (c *ExtractContext) HTTPClientWithControl(control func(network, address string, conn syscall.RawConn) error) *http.Client {
+	if c == nil {
+		c = NewExtractContext()
+	}
+	t := pooledTransport(c.network()).Clone()
+	network.ControlDials(t, control)
+	return &http.Client{
+		Timeout:   extractTimeout,
+		Transport: &contextTransport{base: t, ctx: c},
+	}
+}
+
+// ProxyFor returns the proxy URL for rawURL's host, "" for the environment
+// default, or config.ProxyDirect
+func (c *ExtractContext) ProxyFor(rawURL string) string {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return c.Proxy
+	}
+	return c.network().ProxyFor(u.Hostname())
+}
+
+func (c *ExtractContext) network() config.NetworkConfig {
+	return config.NetworkConfig{Proxy: c.Proxy, Proxies: c.Proxies}
+}
+
+// Cookie returns the configured Cookie header for a host, or ""
+func (c *ExtractContext) Cookie(host string) string {
+	host = strings.ToLower(host)
+	match, cookie := "", ""
+	for domain, value := range c.Cookies {
+		domain = strings.ToLower(domain)
+		if (host == domain || strings.HasSuffix(host, "."+domain)) && len(domain) > len(match) {
+			match, cookie = domain, value
+		}
+	}
+	return cookie
+}
+
+// contextTransport applies the context's user agent and cookies to each request
+type contextTransport struct {
+	base http.RoundTripper
+	ctx  *ExtractContext
+}
+
+func (t *contextTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	cookie := t.ctx.Cookie(req.URL.Hostname())
+	if t.ctx.UserAgent == "" && cookie == "" {
+		return t.base.RoundTrip(req)
+	}
+
+	req = req.Clone(req.Context())
+	if t.ctx.UserAgent != "" {
+		req.Header.Set("User-Agent", t.ctx.UserAgent)
+	}
+	// Cookies set by the extractor itself (e.g. a logged-in Bilibili session) win
+	if cookie != "" && req.Header.Get("Cookie") == "" {
+		req.Header.Set("Cookie", cookie)
+	}
+	return t.base.RoundTrip(req)
+}
+
+var (
+	transportsMu sync.Mutex
+	transports   = make(map[string]*http.Transport) // by proxyKey
+)
+
+// proxyKey identifies a set of proxy settings, "" for the environment only
+func proxyKey(n config.NetworkConfig) string {
+	key := n.Proxy
+	domains := make([]string, 0, len(n.Proxies))
+	for domain := range n.Proxies {
+		domains = append(domains, domain)
+	}
+	sort.Strings(domains)
+	for _, domain := range domains {
+		key += "|" + domain + "=" + n.Proxies[domain]
+	}
+	return key
+}
+
+// pooledTransport returns the shared transport for a set of proxy settings
+func pooledTransport(n config.NetworkConfig) *http.Transport {
+	transportsMu.Lock()
+	defer transportsMu.Unlock()
+
+	key := proxyKey(n)
+	if t, ok := transports[key]; ok {
+		return t
+	}
+
+	t := &http.Transport{
+		Proxy:               n.ProxyFunc(),
+		DialContext:         (&net.Dialer{Timeout: 15 * time.Second, KeepAlive: 30 * time.Second}).DialContext,
+		ForceAttemptHTTP2:   true,
+		MaxIdleConns:        100,
+		MaxIdleConnsPerHost: 8,
+		IdleConnTimeout:     90 * time.Second,
+		TLSHandshakeTimeout: 10 * time.Second,
+	}
+	transports[key] = t
+	return t
+}
@@ -0,0 +1,574 @@
+package extractor
+
+import (
+	"encoding/base64"
+	"encoding/xml"
+	"fmt"
+	"io"
+	"math"
+	"net/http"
+	"net/url"
+	"path"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// maxManifestSize caps how much of an MPD manifest is read
+const maxManifestSize = 8 * 1024 * 1024
+
+var (
+	isoDurationRegex     = regexp.MustCompile(`^P(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:([\d.]+)S)?)?$`)
+	segmentTemplateRegex = regexp.MustCompile(`\$(RepresentationID|Number|Bandwidth|Time)(%0\d+d)?\$`)
+)
+
+// DASHExtractor handles MPEG-DASH (.mpd) manifest URLs
+type DASHExtractor struct {
+	client *http.Client
+}
+
+// mpdManifest is the subset of an MPD manifest needed to list representations
+type mpdManifest struct {
+	Duration string      `xml:"mediaPresentationDuration,attr"`
+	BaseURL  string      `xml:"BaseURL"`
+	Periods  []mpdPeriod `xml:"Period"`
+}
+
+type mpdPeriod struct {
+	Duration        string              `xml:"duration,attr"`
+	BaseURL         string              `xml:"BaseURL"`
+	SegmentTemplate *mpdSegmentTemplate `xml:"SegmentTemplate"`
+	AdaptationSets  []mpdAdaptationSet  `xml:"AdaptationSet"`
+}
+
+type mpdAdaptationSet struct {
+	MimeType          string                 `xml:"mimeType,attr"`
+	ContentType       string                 `xml:"contentType,attr"`
+	Codecs            string                 `xml:"codecs,attr"`
+	BaseURL           string                 `xml:"BaseURL"`
+	SegmentTemplate   *mpdSegmentTemplate    `xml:"SegmentTemplate"`
+	SegmentList       *mpdSegmentList        `xml:"SegmentList"`
+	ContentProtection []mpdContentProtection `xml:"ContentProtection"`
+	Representations   []mpdRepresentation    `xml:"Representation"`
+}
+
+type mpdRepresentation struct {
+	ID                string                 `xml:"id,attr"`
+	MimeType          string                 `xml:"mimeType,attr"`
+	Codecs            string                 `xml:"codecs,attr"`
+	Bandwidth         int                    `xml:"bandwidth,attr"`
+	Width             int                    `xml:"width,attr"`
+	Height            int                    `xml:"height,attr"`
+	BaseURL           string                 `xml:"BaseURL"`
+	SegmentTemplate   *mpdSegmentTemplate    `xml:"SegmentTemplate"`
+	SegmentList       *mpdSegmentList        `xml:"SegmentList"`
+	ContentProtection []mpdContentProtection `xml:"ContentProtection"`
+}
+
+type mpdContentProtection struct {
+	SchemeIDURI string `xml:"schemeIdUri,attr"`
+}
+
+// mpdSegmentTemplate is a SegmentTemplate element. Unset attributes are
+// inherited from the template one level up.
+type mpdSegmentTemplate struct {
+	Media          string         `xml:"media,attr"`
+	Initialization string         `xml:"initialization,attr"`
+	StartNumber    *int           `xml:"startNumber,attr"`
+	Timescale      int            `xml:"timescale,attr"`
+	Duration       int64          `xml:"duration,attr"`
+	Timeline       []mpdTimelineS `xml:"SegmentTimeline>S"`
+}
+
+// mpdTimelineS is an S entry of a SegmentTimeline: r+1 segments of d ticks
+// each, starting at t when given
+type mpdTimelineS struct {
+	T *int64 `xml:"t,attr"`
+	D int64  `xml:"d,attr"`
+	R int    `xml:"r,attr"`
+}
+
+type mpdSegmentList struct {
+	Timescale      int             `xml:"timescale,attr"`
+	Duration       int64           `xml:"duration,attr"`
+	Initialization *mpdURLElement  `xml:"Initialization"`
+	SegmentURLs    []mpdSegmentURL `xml:"SegmentURL"`
+}
+
+type mpdURLElement struct {
+	SourceURL string `xml:"sourceURL,attr"`
+}
+
+type mpdSegmentURL struct {
+	Media string `xml:"media,attr"`
+}
+
+// dashStream is a representation resolved to absolute URLs: either a single
+// file in URL, or an initialization segment and media segments
+type dashStream struct {
+	URL       string
+	InitURL   string
+	Segments  []dashSegment
+	MimeType  string
+	Codecs    string
+	Bandwidth int
+	Width     int
+	Height    int
+}
+
+// dashSegment is one media segment of a segmented representation
+type dashSegment struct {
+	URL      string
+	Duration float64 // seconds
+}
+
+// Name returns the extractor name
+func (d *DASHExtractor) Name() string {
+	return "dash"
+}
+
+// Match checks if the URL is an MPD manifest
+func (d *DASHExtractor) Match(u *url.URL) bool {
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return false
+	}
+	return strings.ToLower(path.Ext(u.Path)) == ".mpd"
+}
+
+// Extract fetches the manifest and lists its video representations as formats.
+// Each format carries a matching audio representation in AudioURL so the
+// downloader fetches both streams and merges them. Segmented representations
+// are passed on as a media playlist for the HLS segment downloader.
+func (d *DASHExtractor) Extract(urlStr string) (Media, error) {
+	return d.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (d *DASHExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *d
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
+
+func (d *DASHExtractor) extract(urlStr string) (Media, error) {
+	req, err := http.NewRequest("GET", urlStr, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+
+	resp, err := d.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch manifest: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("server returned status %d", resp.StatusCode)
+	}
+
+	finalURL := resp.Request.URL.String()
+	videos, audios, duration, drm, err := parseMPD(io.LimitReader(resp.Body, maxManifestSize), finalURL)
+	if err != nil {
+		return nil, err
+	}
+
+	parsedURL, _ := url.Parse(finalURL)
+	title := strings.TrimSuffix(path.Base(parsedURL.Path), path.Ext(parsedURL.Path))
+	if title == "" || title == "/" || title == "." {
+		title = "stream"
+	}
+	id := generateM3U8ID(finalURL)
+
+	// Protected manifests are usually segmented too; report the DRM rather than
+	// "no downloadable representations"
+	if drm != "" && len(videos) == 0 && len(audios) == 0 {
+		return &VideoMedia{
+			ID:       id,
+			Title:    title,
+			Duration: duration,
+			DRM:      drm,
+		}, nil
+	}
+
+	// Audio-only manifests (e.g. music streams)
+	if len(videos) == 0 {
+		if len(audios) == 0 {
+			return nil, fmt.Errorf("no downloadable representations in manifest")
+		}
+		return &AudioMedia{
+			ID:       id,
+			Title:    title,
+			Duration: duration,
+			URL:      audios[0].downloadURL(),
+			Ext:      mimeExt(audios[0].MimeType, "m4a"),
+			DRM:      drm,
+		}, nil
+	}
+
+	return &VideoMedia{
+		ID:       id,
+		Title:    title,
+		Duration: duration,
+		Formats:  dashVideoFormats(videos, audios),
+		DRM:      drm,
+	}, nil
+}
+
+// dashVideoFormats turns video representations into formats, each paired with
+// its audio representation (if any) in AudioURL
+func dashVideoFormats(videos, audios []dashStream) []VideoFormat {
+	formats := make([]VideoFormat, 0, len(videos))
+	for _, v := range videos {
+		quality := fmt.Sprintf("%dp", v.Height)
+		if v.Height == 0 {
+			quality = fmt.Sprintf("%dk", v.Bandwidth/1000)
+		}
+		if codec := videoCodecName(v.Codecs); codec != "" {
+			quality += " [" + codec + "]"
+		}
+		var audioURL string
+		if a := dashAudioFor(v, audios); a != nil {
+			audioURL = a.downloadURL()
+		}
+		formats = append(formats, VideoFormat{
+			URL:      v.downloadURL(),
+			Quality:  quality,
+			Ext:      mimeExt(v.MimeType, "mp4"),
+			Width:    v.Width,
+			Height:   v.Height,
+			Bitrate:  v.Bandwidth / 1000,
+			AudioURL: audioURL,
+		})
+	}
+	return formats
+}
+
+// dashAudioFor picks the audio to merge with video: the best audio in the same
+// container, so WebM video gets Opus rather than AAC, or else the best overall.
+// audios is sorted best first.
+func dashAudioFor(video dashStream, audios []dashStream) *dashStream {
+	if len(audios) == 0 {
+		return nil
+	}
+	_, container, _ := strings.Cut(video.MimeType, "/")
+	for i, a := range audios {
+		if _, c, _ := strings.Cut(a.MimeType, "/"); c == container {
+			return &audios[i]
+		}
+	}
+	return &audios[0]
+}
+
+// downloadURL is the URL the downloader fetches for the stream. Segments are
+// packed into a media playlist data: URL, which the downloader recognizes and
+// fetches segment by segment into a single file.
+func (s dashStream) downloadURL() string {
+	if len(s.Segments) == 0 {
+		return s.URL
+	}
+
+	var b strings.Builder
+	target := 0.0
+	for _, seg := range s.Segments {
+		target = math.Max(target, seg.Duration)
+	}
+	fmt.Fprintf(&b, "#EXTM3U\n#EXT-X-VERSION:7\n#EXT-X-TARGETDURATION:%d\n#EXT-X-PLAYLIST-TYPE:VOD\n", int(math.Ceil(target)))
+	if s.InitURL != "" {
+		fmt.Fprintf(&b, "#EXT-X-MAP:URI=\"%s\"\n", s.InitURL)
+	}
+	for _, seg := range s.Segments {
+		fmt.Fprintf(&b, "#EXTINF:%.3f,\n%s\n", seg.Duration, seg.URL)
+	}
+	b.WriteString("#EXT-X-ENDLIST\n")
+	return "data:application/vnd.apple.mpegurl;base64," + base64.StdEncoding.EncodeToString([]byte(b.String()))
+}
+
+// parseMPD returns the video and audio representations of a manifest, best
+// first, plus the presentation duration in seconds and the DRM system named by
+// its ContentProtection elements, if any. Segmented representations whose
+// segments can't be listed, such as live ones, are skipped.
+func parseMPD(r io.Reader, manifestURL string) (videos, audios []dashStream, duration int, drm string, err error) {
+	var mpd mpdManifest
+	if err := xml.NewDecoder(r).Decode(&mpd); err != nil {
+		return nil, nil, 0, "", fmt.Errorf("failed to parse manifest: %w", err)
+	}
+
+	base, err := url.Parse(manifestURL)
+	if err != nil {
+		return nil, nil, 0, "", fmt.Errorf("invalid manifest URL: %w", err)
+	}
+	base = resolveBase(base, mpd.BaseURL)
+
+	for _, period := range mpd.Periods {
+		periodBase := resolveBase(base, period.BaseURL)
+		periodSeconds := isoDurationSeconds(period.Duration)
+		if periodSeconds == 0 && len(mpd.Periods) == 1 {
+			periodSeconds = isoDurationSeconds(mpd.Duration)
+		}
+		for _, set := range period.AdaptationSets {
+			setBase := resolveBase(periodBase, set.BaseURL)
+			if drm == "" {
+				drm = mpdDRMSystem(set.ContentProtection)
+			}
+			setTemplate := mergeSegmentTemplate(period.SegmentTemplate, set.SegmentTemplate)
+			for _, rep := range set.Representations {
+				if drm == "" {
+					drm = mpdDRMSystem(rep.ContentProtection)
+				}
+				repBase := resolveBase(setBase, rep.BaseURL)
+				s := dashStream{
+					MimeType:  firstNonEmpty(rep.MimeType, set.MimeType),
+					Codecs:    firstNonEmpty(rep.Codecs, set.Codecs),
+					Bandwidth: rep.Bandwidth,
+					Width:     rep.Width,
+					Height:    rep.Height,
+				}
+				switch {
+				case rep.SegmentList != nil || set.SegmentList != nil:
+					list := rep.SegmentList
+					if list == nil {
+						list = set.SegmentList
+					}
+					s.InitURL, s.Segments = list.segments(repBase)
+				case rep.SegmentTemplate != nil || setTemplate != nil:
+					tmpl := mergeSegmentTemplate(setTemplate, rep.SegmentTemplate)
+					s.InitURL, s.Segments = tmpl.segments(rep, repBase, periodSeconds)
+				case rep.BaseURL != "":
+					s.URL = repBase.String()
+				}
+				if s.URL == "" && len(s.Segments) == 0 {
+					continue
+				}
+				kind := set.ContentType
+				if kind == "" {
+					kind, _, _ = strings.Cut(s.MimeType, "/")
+				}
+				switch kind {
+				case "video":
+					videos = append(videos, s)
+				case "audio":
+					audios = append(audios, s)
+				}
+			}
+		}
+	}
+
+	sort.SliceStable(videos, func(i, j int) bool {
+		if videos[i].Height != videos[j].Height {
+			return videos[i].Height > videos[j].Height
+		}
+		return videos[i].Bandwidth > videos[j].Bandwidth
+	})
+	sort.SliceStable(audios, func(i, j int) bool {
+		return audios[i].Bandwidth > audios[j].Bandwidth
+	})
+
+	return videos, audios, parseISODuration(mpd.Duration), drm, nil
+}
+
+// mergeSegmentTemplate returns child with its unset attributes taken from parent
+func mergeSegmentTemplate(parent, child *mpdSegmentTemplate) *mpdSegmentTemplate {
+	if parent == nil {
+		return child
+	}
+	if child == nil {
+		return parent
+	}
+	merged := *child
+	merged.Media = firstNonEmpty(child.Media, parent.Media)
+	merged.Initialization = firstNonEmpty(child.Initialization, parent.Initialization)
+	if merged.StartNumber == nil {
+		merged.StartNumber = parent.StartNumber
+	}
+	if merged.Timescale == 0 {
+		merged.Timescale = parent.Timescale
+	}
+	if merged.Duration == 0 {
+		merged.Duration = parent.Duration
+	}
+	if len(merged.Timeline) == 0 {
+		merged.Timeline = parent.Timeline
+	}
+	return &merged
+}
+
+// segments lists the initialization and media segment URLs of rep. Without a
+// SegmentTimeline the segment count comes from the period's length in
+// seconds, so nothing is returned when that's unknown.
+func (t *mpdSegmentTemplate) segments(rep mpdRepresentation, base *url.URL, periodSeconds float64) (string, []dashSegment) {
+	if t.Media == "" {
+		return "", nil
+	}
+	timescale := float64(t.Timescale)
+	if timescale <= 0 {
+		timescale = 1
+	}
+	number := 1
+	if t.StartNumber != nil {
+		number = *t.StartNumber
+	}
+
+	var initURL string
+	if t.Initialization != "" {
+		initURL = resolveBase(base, expandSegmentTemplate(t.Initialization, rep, 0, 0)).String()
+	}
+
+	var segments []dashSegment
+	add := func(time, duration int64) {
+		segments = append(segments, dashSegment{
+			URL:      resolveBase(base, expandSegmentTemplate(t.Media, rep, number, time)).String(),
+			Duration: float64(duration) / timescale,
+		})
+		number++
+	}
+
+	if len(t.Timeline) > 0 {
+		var time int64
+		end := int64(periodSeconds * timescale)
+		for i, s := range t.Timeline {
+			if s.T != nil {
+				time = *s.T
+			}
+			if s.D <= 0 {
+				return "", nil
+			}
+			repeat := s.R
+			if repeat < 0 {
+				// Repeat until the next S entry or the end of the period
+				next := end
+				if i+1 < len(t.Timeline) && t.Timeline[i+1].T != nil {
+					next = *t.Timeline[i+1].T
+				}
+				repeat = int((next-time+s.D-1)/s.D) - 1
+			}
+			for j := 0; j <= repeat; j++ {
+				add(time, s.D)
+				time += s.D
+			}
+		}
+		return initURL, segments
+	}
+
+	if t.Duration <= 0 || periodSeconds <= 0 {
+		return "", nil
+	}
+	count := int(math.Ceil(periodSeconds * timescale / float64(t.Duration)))
+	for i := 0; i < count; i++ {
+		add(int64(i)*t.Duration, t.Duration)
+	}
+	return initURL, segments
+}
+
+// segments lists the initialization and media segment URLs of an explicit
+// SegmentList
+func (l *mpdSegmentList) segments(base *url.URL) (string, []dashSegment) {
+	var initURL string
+	if l.Initialization != nil && l.Initialization.SourceURL != "" {
+		initURL = resolveBase(base, l.Initialization.SourceURL).String()
+	}
+	timescale := float64(l.Timescale)
+	if timescale <= 0 {
+		timescale = 1
+	}
+
+	segments := make([]dashSegment, 0, len(l.SegmentURLs))
+	for _, s := range l.SegmentURLs {
+		segments = append(segments, dashSegment{
+			// A SegmentURL without media addresses the BaseURL itself
+			URL:      resolveBase(base, s.Media).String(),
+			Duration: float64(l.Duration) / timescale,
+		})
+	}
+	return initURL, segments
+}
+
+// expandSegmentTemplate fills in the $identifiers$ of a SegmentTemplate URL,
+// including printf widths like $Number%05d$
+func expandSegmentTemplate(tmpl string, rep mpdRepresentation, number int, time int64) string {
+	parts := strings.Split(tmpl, "$$")
+	for i, part := range parts {
+		parts[i] = segmentTemplateRegex.ReplaceAllStringFunc(part, func(m string) string {
+			sub := segmentTemplateRegex.FindStringSubmatch(m)
+			format := "%d"
+			if sub[2] != "" {
+				format = sub[2]
+			}
+			switch sub[1] {
+			case "RepresentationID":
+				return rep.ID
+			case "Number":
+				return fmt.Sprintf(format, number)
+			case "Bandwidth":
+				return fmt.Sprintf(format, rep.Bandwidth)
+			default:
+				return fmt.Sprintf(format, time)
+			}
+		})
+	}
+	return strings.Join(parts, "$")
+}
+
+// mpdDRMSystem returns the first DRM system among ContentProtection elements
+func mpdDRMSystem(protections []mpdContentProtection) string {
+	for _, p := range protections {
+		if system := drmSystem(p.SchemeIDURI); system != "" {
+			return system
+		}
+	}
+	return ""
+}
+
+// resolveBase resolves a BaseURL element against its parent
+func resolveBase(base *url.URL, ref string) *url.URL {
+	ref = strings.TrimSpace(ref)
+	if ref == "" {
+		return base
+	}
+	refURL, err := url.Parse(ref)
+	if err != nil {
+		return base
+	}
+	return base.ResolveReference(refURL)
+}
+
+// parseISODuration converts an ISO 8601 duration like "PT1H2M3.5S" to whole seconds
+func parseISODuration(s string) int {
+	return int(isoDurationSeconds(s))
+}
+
+// isoDurationSeconds converts an ISO 8601 duration to seconds, keeping the fraction
+func isoDurationSeconds(s string) float64 {
+	m := isoDurationRegex.FindStringSubmatch(strings.TrimSpace(s))
+	if m == nil {
+		return 0
+	}
+	days, _ := strconv.Atoi(m[1])
+	hours, _ := strconv.Atoi(m[2])
+	minutes, _ := strconv.Atoi(m[3])
+	seconds, _ := strconv.ParseFloat(m[4], 64)
+	return float64(days*86400+hours*3600+minutes*60) + seconds
+}
+
+// mimeExt maps a representation MIME type to a file extension
+func mimeExt(mimeType, fallback string) string {
+	switch mimeType {
+	case "video/webm", "audio/webm":
+		return "webm"
+	case "video/mp4":
+		return "mp4"
+	case "audio/mp4":
+		return "m4a"
+	}
+	return fallback
+}
+
+func firstNonEmpty(values ...string) string {
+	for _, v := range values {
+		if v != "" {
+			return v
+		}
+	}
+	return ""
+}
@@ -0,0 +1,153 @@
+package extractor
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestParseMPDSegmentTemplate(t *testing.T) {
+	manifest := `<?xml version="1.0"?>
+<MPD mediaPresentationDuration="PT9.5S">
+  <Period>
+    <AdaptationSet contentType="video" mimeType="video/mp4">
+      <SegmentTemplate media="$RepresentationID$/seg-$Number%03d$.m4s" initialization="$RepresentationID$/init.mp4" startNumber="0" timescale="1000" duration="4000"/>
+      <Representation id="720" bandwidth="3000000" width="1280" height="720" codecs="avc1.64001f"/>
+      <Representation id="1080" bandwidth="6000000" width="1920" height="1080" codecs="avc1.640028"/>
+    </AdaptationSet>
+    <AdaptationSet contentType="audio" mimeType="audio/mp4">
+      <Representation id="aac" bandwidth="128000" codecs="mp4a.40.2">
+        <SegmentTemplate media="audio/$Time$.m4s" initialization="audio/init.mp4" timescale="48000">
+          <SegmentTimeline>
+            <S t="0" d="192000" r="1"/>
+            <S d="72000"/>
+          </SegmentTimeline>
+        </SegmentTemplate>
+      </Representation>
+    </AdaptationSet>
+  </Period>
+</MPD>`
+	videos, audios, duration, _, err := parseMPD(strings.NewReader(manifest), "https://cdn.example.com/show/manifest.mpd")
+	if err != nil {
+		t.Fatalf("parseMPD() error = %v", err)
+	}
+	if duration != 9 {
+		t.Errorf("duration = %d, want 9", duration)
+	}
+	if len(videos) != 2 || len(audios) != 1 {
+		t.Fatalf("got %d videos and %d audios, want 2 and 1", len(videos), len(audios))
+	}
+
+	video := videos[0]
+	if video.Height != 1080 {
+		t.Errorf("best video height = %d, want 1080", video.Height)
+	}
+	if video.InitURL != "https://cdn.example.com/show/1080/init.mp4" {
+		t.Errorf("video InitURL = %q", video.InitURL)
+	}
+	wantVideo := []string{
+		"https://cdn.example.com/show/1080/seg-000.m4s",
+		"https://cdn.example.com/show/1080/seg-001.m4s",
+		"https://cdn.example.com/show/1080/seg-002.m4s",
+	}
+	if len(video.Segments) != len(wantVideo) {
+		t.Fatalf("got %d video segments, want %d", len(video.Segments), len(wantVideo))
+	}
+	for i, w := range wantVideo {
+		if video.Segments[i].URL != w || video.Segments[i].Duration != 4 {
+			t.Errorf("video segment %d = %+v, want %s lasting 4s", i, video.Segments[i], w)
+		}
+	}
+
+	audio := audios[0]
+	wantAudio := []dashSegment{
+		{URL: "https://cdn.example.com/show/audio/0.m4s", Duration: 4},
+		{URL: "https://cdn.example.com/show/audio/192000.m4s", Duration: 4},
+		{URL: "https://cdn.example.com/show/audio/384000.m4s", Duration: 1.5},
+	}
+	if len(audio.Segments) != len(wantAudio) {
+		t.Fatalf("got %d audio segments, want %d", len(audio.Segments), len(wantAudio))
+	}
+	for i, w := range wantAudio {
+		if audio.Segments[i] != w {
+			t.Errorf("audio segment %d = %+v, want %+v", i, audio.Segments[i], w)
+		}
+	}
+}
+
+func TestParseMPDSegmentList(t *testing.T) {
+	manifest := `<MPD mediaPresentationDuration="PT4S">
+  <Period>
+    <BaseURL>media/</BaseURL>
+    <AdaptationSet mimeType="video/webm">
+      <Representation id="v" bandwidth="1000000" height="480">
+        <SegmentList timescale="10" duration="20">
+          <Initialization sourceURL="init.webm"/>
+          <SegmentURL media="a.webm"/>
+          <SegmentURL media="b.webm"/>
+        </SegmentList>
+      </Representation>
+      <Representation id="file" bandwidth="500000" height="360">
+        <BaseURL>360.webm</BaseURL>
+      </Representation>
+    </AdaptationSet>
+  </Period>
+</MPD>`
+	videos, _, _, _, err := parseMPD(strings.NewReader(manifest), "https://example.com/v/manifest.mpd")
+	if err != nil {
+		t.Fatalf("parseMPD() error = %v", err)
+	}
+	if len(videos) != 2 {
+		t.Fatalf("got %d videos, want 2", len(videos))
+	}
+
+	list := videos[0]
+	if list.InitURL != "https://example.com/v/media/init.webm" {
+		t.Errorf("InitURL = %q", list.InitURL)
+	}
+	if len(list.Segments) != 2 || list.Segments[1].URL != "https://example.com/v/media/b.webm" || list.Segments[1].Duration != 2 {
+		t.Errorf("segments = %+v", list.Segments)
+	}
+	if file := videos[1]; file.URL != "https://example.com/v/media/360.webm" || len(file.Segments) != 0 {
+		t.Errorf("single-file representation = %+v", file)
+	}
+}
+
+func TestDashAudioFor(t *testing.T) {
+	audios := []dashStream{
+		{URL: "aac-high", MimeType: "audio/mp4", Bandwidth: 256000},
+		{URL: "opus", MimeType: "audio/webm", Bandwidth: 160000},
+		{URL: "aac-low", MimeType: "audio/mp4", Bandwidth: 64000},
+	}
+	tests := []struct {
+		mimeType string
+		want     string
+	}{
+		{"video/webm", "opus"},
+		{"video/mp4", "aac-high"},
+		{"video/x-matroska", "aac-high"},
+	}
+	for _, tt := range tests {
+		got := dashAudioFor(dashStream{MimeType: tt.mimeType}, audios)
+		if got == nil || got.URL != tt.want {
+			t.Errorf("dashAudioFor(%s) = %+v, want %s", tt.mimeType, got, tt.want)
+		}
+	}
+	if got := dashAudioFor(dashStream{MimeType: "video/mp4"}, nil); got != nil {
+		t.Errorf("dashAudioFor() without audio = %+v, want nil", got)
+	}
+}
+
+func TestDashStreamDownloadURL(t *testing.T) {
+	single := dashStream{URL: "https://example.com/video.mp4"}
+	if got := single.downloadURL(); got != single.URL {
+		t.Errorf("single-file downloadURL() = %q, want %q", got, single.URL)
+	}
+
+	segmented := dashStream{
+		InitURL:  "https://example.com/init.mp4",
+		Segments: []dashSegment{{URL: "https://example.com/1.m4s", Duration: 4}},
+	}
+	if got := segmented.downloadURL(); !strings.HasPrefix(got, "data:application/vnd.apple.mpegurl;base64,") {
+		t.Errorf("segmented downloadURL() = %q, want a playlist data URL", got)
+	}
+}
@@ -20,6 +20,11 @@ func (d *DirectExtractor) Name() string {
 	return "direct"
 }
 
+// CacheTTL is long since direct file URLs rarely expire
+func (d *DirectExtractor) CacheTTL() time.Duration {
+	return time.Hour
+}
+
 // Match always returns true - this is the fallback extractor
 func (d *DirectExtractor) Match(u *url.URL) bool {
 	// Only match http/https URLs
@@ -28,22 +33,17 @@ func (d *DirectExtractor) Match(u *url.URL) bool {
 
 // Extract retrieves media information from a direct URL
 func (d *DirectExtractor) Extract(urlStr string) (Media, error) {
-	if d.client == nil {
-		d.client = &http.Client{
-			Timeout: 30 * time.Second,
-			Transport: &http.Transport{
-				Proxy: http.ProxyFromEnvironment,
-			},
-			CheckRedirect: func(req *http.Request, via []*http.Request) error {
-				// Follow redirects but limit to 10
-				if len(via) >= 10 {
-					return fmt.Errorf("too many redirects")
-				}
-				return nil
-			},
-		}
-	}
+	return d.ExtractWithContext(urlStr, nil)
+}
 
+// ExtractWithContext is Extract using the context's HTTP client
+func (d *DirectExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *d
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
+
+func (d *DirectExtractor) extract(urlStr string) (Media, error) {
 	// HEAD request to get Content-Type and filename
 	req, err := http.NewRequest("HEAD", urlStr, nil)
 	if err != nil {
@@ -67,6 +67,17 @@ func (d *DirectExtractor) Extract(urlStr string) (Media, error) {
 	// Determine media type and extension
 	mediaType, ext := detectMediaType(contentType, finalURL)
 
+	// Streaming manifests served without a known extension: list their formats
+	if ext == "m3u8" {
+		return (&M3U8Extractor{client: d.client}).extract(finalURL)
+	}
+	if ext == "mpd" {
+		return (&DASHExtractor{client: d.client}).extract(finalURL)
+	}
+	if ext == "rss" {
+		return (&PodcastExtractor{client: d.client}).extract(finalURL)
+	}
+
 	// Extract filename from URL path
 	parsedURL, _ := url.Parse(finalURL)
 	filename := path.Base(parsedURL.Path)
@@ -152,6 +163,13 @@ func detectMediaType(contentType, urlStr string) (MediaType, string) {
 		contentType == "application/x-mpegurl":
 		return MediaTypeVideo, "m3u8"
 
+	case contentType == "application/dash+xml":
+		return MediaTypeVideo, "mpd"
+
+	case contentType == "application/rss+xml",
+		contentType == "application/atom+xml":
+		return MediaTypeAudio, "rss"
+
 	// Audio types
 	case strings.HasPrefix(contentType, "audio/"):
 		ext := strings.TrimPrefix(contentType, "audio/")
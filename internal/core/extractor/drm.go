@@ -0,0 +1,84 @@
+package extractor
+
+import (
+	"fmt"
+	"regexp"
+	"strings"
+)
+
+// DRM system names reported in VideoMedia.DRM and AudioMedia.DRM
+const (
+	DRMWidevine  = "Widevine"
+	DRMFairPlay  = "FairPlay"
+	DRMPlayReady = "PlayReady"
+	DRMUnknown   = "unknown"
+)
+
+var hlsKeyFormatRegex = regexp.MustCompile(`KEYFORMAT="([^"]+)"`)
+
+// drmSystem maps a DASH ContentProtection scheme or an HLS KEYFORMAT to a DRM
+// system name. Clear-key schemes (and the generic cenc marker) return "".
+func drmSystem(scheme string) string {
+	scheme = strings.ToLower(strings.TrimSpace(scheme))
+	switch {
+	case strings.Contains(scheme, "edef8ba9-79d6-4ace-a3c8-27dcd51d21ed"):
+		return DRMWidevine
+	case strings.Contains(scheme, "94ce86fb-07ff-4f43-adb8-93d2fa968ca2"),
+		strings.Contains(scheme, "com.apple.streamingkeydelivery"):
+		return DRMFairPlay
+	case strings.Contains(scheme, "9a04f079-9840-4286-ab92-e65be0885f95"),
+		strings.Contains(scheme, "com.microsoft.playready"):
+		return DRMPlayReady
+	}
+	return ""
+}
+
+// hlsDRMSystem returns the DRM system named by the EXT-X-KEY or
+// EXT-X-SESSION-KEY tags of a playlist. AES-128 encryption with a plain key
+// URI is not DRM and downloads normally.
+func hlsDRMSystem(playlist string) string {
+	for _, line := range strings.Split(playlist, "\n") {
+		line = strings.TrimSpace(line)
+		if !strings.HasPrefix(line, "#EXT-X-KEY:") && !strings.HasPrefix(line, "#EXT-X-SESSION-KEY:") {
+			continue
+		}
+		if m := hlsKeyFormatRegex.FindStringSubmatch(line); m != nil {
+			if system := drmSystem(m[1]); system != "" {
+				return system
+			}
+		}
+		// SAMPLE-AES without a known key format still needs a license server
+		if strings.Contains(line, "METHOD=SAMPLE-AES") && !strings.Contains(line, `KEYFORMAT="identity"`) {
+			return DRMUnknown
+		}
+	}
+	return ""
+}
+
+// DRMReason returns a human-readable explanation when media is DRM protected,
+// or "" if it can be downloaded
+func DRMReason(media Media) string {
+	var system string
+	switch m := media.(type) {
+	case *VideoMedia:
+		system = m.DRM
+	case *AudioMedia:
+		system = m.DRM
+	case *MultiVideoMedia:
+		for _, v := range m.Videos {
+			if v.DRM == "" {
+				return ""
+			}
+			system = v.DRM
+		}
+	}
+
+	switch system {
+	case "":
+		return ""
+	case DRMUnknown:
+		return "this stream is DRM protected and cannot be downloaded"
+	default:
+		return fmt.Sprintf("this stream is protected by %s DRM and cannot be downloaded", system)
+	}
+}
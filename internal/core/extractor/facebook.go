@@ -0,0 +1,181 @@
+package extractor
+
+import (
+	"encoding/json"
+	"fmt"
+	"html"
+	"io"
+	"net/http"
+	"net/url"
+	"regexp"
+	"strings"
+)
+
+var (
+	facebookVideoIDRegex = regexp.MustCompile(`/(?:videos|reel|reels)/(?:[^/]+/)?(\d+)`)
+	facebookTitleRegex   = regexp.MustCompile(`<meta property="og:title" content="([^"]*)"`)
+	facebookThumbRegex   = regexp.MustCompile(`<meta property="og:image" content="([^"]*)"`)
+
+	// Stream URLs embedded in the page's JSON, as JSON string literals
+	facebookHDRegex   = regexp.MustCompile(`"(?:browser_native_hd_url|playable_url_quality_hd|hd_src)":("(?:[^"\\]|\\.)+")`)
+	facebookSDRegex   = regexp.MustCompile(`"(?:browser_native_sd_url|playable_url|sd_src)":("(?:[^"\\]|\\.)+")`)
+	facebookDASHRegex = regexp.MustCompile(`"dash_manifest":("(?:[^"\\]|\\.)+")`)
+)
+
+// FacebookExtractor handles public Facebook videos, Watch links, and reels
+type FacebookExtractor struct {
+	client *http.Client
+}
+
+func (e *FacebookExtractor) Name() string {
+	return "facebook"
+}
+
+func (e *FacebookExtractor) Match(u *url.URL) bool {
+	// Host matching is done by registry, check path pattern
+	if u.Path == "/watch" || u.Path == "/watch/" {
+		return u.Query().Get("v") != ""
+	}
+	return facebookVideoIDRegex.MatchString(u.Path)
+}
+
+func (e *FacebookExtractor) Extract(urlStr string) (Media, error) {
+	return e.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (e *FacebookExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
+
+func (e *FacebookExtractor) extract(urlStr string) (Media, error) {
+	u, err := url.Parse(urlStr)
+	if err != nil {
+		return nil, fmt.Errorf("invalid URL: %w", err)
+	}
+	videoID := u.Query().Get("v")
+	if m := facebookVideoIDRegex.FindStringSubmatch(u.Path); m != nil {
+		videoID = m[1]
+	}
+	if videoID == "" {
+		return nil, fmt.Errorf("could not extract video ID from URL")
+	}
+
+	page, err := e.fetchPage(urlStr)
+	if err != nil {
+		return nil, err
+	}
+
+	// The page may embed several videos (related, comments); prefer the
+	// streams that follow the requested video's ID
+	if idx := strings.Index(page, `"video_id":"`+videoID+`"`); idx >= 0 {
+		if formats := facebookFormats(page[idx:]); len(formats) > 0 {
+			return e.media(page, videoID, formats), nil
+		}
+	}
+	if formats := facebookFormats(page); len(formats) > 0 {
+		return e.media(page, videoID, formats), nil
+	}
+
+	if strings.Contains(page, `"login_form"`) || strings.Contains(page, "/login/?next=") {
+		return nil, fmt.Errorf("video is private or requires login")
+	}
+	return nil, fmt.Errorf("no video streams found on page")
+}
+
+// fetchPage downloads the video page with a desktop browser user agent
+func (e *FacebookExtractor) fetchPage(urlStr string) (string, error) {
+	req, err := http.NewRequest("GET", urlStr, nil)
+	if err != nil {
+		return "", fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+	req.Header.Set("Accept", "text/html,application/xhtml+xml")
+	req.Header.Set("Accept-Language", "en-US,en;q=0.9")
+	req.Header.Set("Sec-Fetch-Mode", "navigate")
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return "", fmt.Errorf("failed to fetch page: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return "", fmt.Errorf("facebook returned status %d", resp.StatusCode)
+	}
+
+	body, err := io.ReadAll(io.LimitReader(resp.Body, 16*1024*1024))
+	if err != nil {
+		return "", fmt.Errorf("failed to read page: %w", err)
+	}
+	return string(body), nil
+}
+
+func (e *FacebookExtractor) media(page, videoID string, formats []VideoFormat) *VideoMedia {
+	title := "Facebook Video"
+	if m := facebookTitleRegex.FindStringSubmatch(page); m != nil && m[1] != "" {
+		title = html.UnescapeString(m[1])
+	}
+	var thumbnail string
+	if m := facebookThumbRegex.FindStringSubmatch(page); m != nil {
+		thumbnail = html.UnescapeString(m[1])
+	}
+
+	return &VideoMedia{
+		ID:        videoID,
+		Title:     title,
+		Thumbnail: thumbnail,
+		Formats:   formats,
+	}
+}
+
+// facebookFormats lists the DASH representations (best first) followed by the
+// progressive HD and SD MP4s found in page data
+func facebookFormats(page string) []VideoFormat {
+	var formats []VideoFormat
+
+	if m := facebookDASHRegex.FindStringSubmatch(page); m != nil {
+		if manifest := unquoteJSON(m[1]); manifest != "" {
+			videos, audios, _, _, err := parseMPD(strings.NewReader(manifest), "https://www.facebook.com/")
+			if err == nil {
+				formats = append(formats, dashVideoFormats(videos, audios)...)
+			}
+		}
+	}
+
+	for _, q := range []struct {
+		regex *regexp.Regexp
+		label string
+	}{
+		{facebookHDRegex, "HD"},
+		{facebookSDRegex, "SD"},
+	} {
+		m := q.regex.FindStringSubmatch(page)
+		if m == nil {
+			continue
+		}
+		if streamURL := unquoteJSON(m[1]); strings.HasPrefix(streamURL, "http") {
+			formats = append(formats, VideoFormat{URL: streamURL, Quality: q.label, Ext: "mp4"})
+		}
+	}
+	return formats
+}
+
+// unquoteJSON decodes a JSON string literal, or returns "" if it is invalid
+func unquoteJSON(literal string) string {
+	var s string
+	if err := json.Unmarshal([]byte(literal), &s); err != nil {
+		return ""
+	}
+	return s
+}
+
+func init() {
+	Register(&FacebookExtractor{},
+		"facebook.com",
+		"m.facebook.com",
+		"web.facebook.com",
+	)
+}
@@ -0,0 +1,237 @@
+package extractor
+
+import (
+	"fmt"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+var formatFilterRegex = regexp.MustCompile(`^(\w+)\s*(<=|>=|!=|\^=|\$=|\*=|=|<|>)(\?)?\s*(.+)$`)
+
+// SelectFormat picks a format with a yt-dlp style selector, e.g.
+// "bestvideo[height<=1080]+bestaudio/best". Supported selectors are best (b),
+// worst (w), bestvideo (bv), and worstvideo (wv), each with optional filters
+// on height, width, bitrate (tbr), ext, and quality. "+bestaudio" (+ba)
+// requires a separate audio track to merge. Alternatives separated by "/" are
+// tried in order. An empty spec means "best".
+func SelectFormat(formats []VideoFormat, spec string) (*VideoFormat, error) {
+	spec = strings.TrimSpace(spec)
+	if spec == "" {
+		spec = "best"
+	}
+
+	for _, alt := range strings.Split(spec, "/") {
+		f, err := selectFormatAlternative(formats, strings.TrimSpace(alt))
+		if err != nil {
+			return nil, err
+		}
+		if f != nil {
+			return f, nil
+		}
+	}
+	return nil, fmt.Errorf("no format matches %q", spec)
+}
+
+// MatchQuality returns the format labeled quality (e.g. "720p"), or else the
+// first whose label contains it ("1080" matches "1080p60"). It returns nil for
+// an empty quality, "best", or when nothing matches.
+func MatchQuality(formats []VideoFormat, quality string) *VideoFormat {
+	if quality == "" || quality == "best" {
+		return nil
+	}
+	for i := range formats {
+		if formats[i].Quality == quality {
+			return &formats[i]
+		}
+	}
+	for i := range formats {
+		if strings.Contains(formats[i].Quality, quality) {
+			return &formats[i]
+		}
+	}
+	return nil
+}
+
+// selectFormatAlternative evaluates one "/"-separated alternative. It returns
+// nil without an error when the alternative is valid but nothing matches.
+func selectFormatAlternative(formats []VideoFormat, alt string) (*VideoFormat, error) {
+	video, audio, merge := strings.Cut(alt, "+")
+	if merge {
+		switch strings.TrimSpace(audio) {
+		case "bestaudio", "ba":
+		default:
+			return nil, fmt.Errorf("unsupported audio selector %q (use bestaudio)", audio)
+		}
+	}
+
+	name, filters, err := parseFormatSelector(video)
+	if err != nil {
+		return nil, err
+	}
+
+	var best *VideoFormat
+	for i := range formats {
+		f := &formats[i]
+		if merge && f.AudioURL == "" {
+			continue
+		}
+		if !formatMatchesFilters(f, filters) {
+			continue
+		}
+		if best == nil {
+			best = f
+			continue
+		}
+		better := f.Height > best.Height || (f.Height == best.Height && f.Bitrate > best.Bitrate)
+		worse := f.Height < best.Height || (f.Height == best.Height && f.Bitrate < best.Bitrate)
+		if (name == "best" && better) || (name == "worst" && worse) {
+			best = f
+		}
+	}
+	return best, nil
+}
+
+// formatFilter is one "[field op value]" condition
+type formatFilter struct {
+	field    string
+	op       string
+	value    string
+	optional bool // "?" after the operator: formats missing the field pass
+}
+
+// parseFormatSelector splits "bestvideo[height<=1080][ext=mp4]" into its
+// normalized name ("best" or "worst") and filters
+func parseFormatSelector(selector string) (string, []formatFilter, error) {
+	selector = strings.TrimSpace(selector)
+	base := selector
+	if i := strings.Index(selector, "["); i >= 0 {
+		base = selector[:i]
+	}
+
+	var name string
+	switch base {
+	case "best", "b", "bestvideo", "bv", "bv*", "":
+		name = "best"
+	case "worst", "w", "worstvideo", "wv", "wv*":
+		name = "worst"
+	case "bestaudio", "ba", "worstaudio", "wa":
+		return "", nil, fmt.Errorf("audio-only selection is not supported; use bestvideo+bestaudio")
+	default:
+		return "", nil, fmt.Errorf("unknown format selector %q", base)
+	}
+
+	var filters []formatFilter
+	rest := selector[len(base):]
+	for rest != "" {
+		end := strings.Index(rest, "]")
+		if rest[0] != '[' || end < 0 {
+			return "", nil, fmt.Errorf("invalid format filter in %q", selector)
+		}
+		m := formatFilterRegex.FindStringSubmatch(strings.TrimSpace(rest[1:end]))
+		if m == nil {
+			return "", nil, fmt.Errorf("invalid format filter %q", rest[:end+1])
+		}
+		f := formatFilter{field: m[1], op: m[2], optional: m[3] == "?", value: strings.TrimSpace(m[4])}
+		if err := f.validate(); err != nil {
+			return "", nil, err
+		}
+		filters = append(filters, f)
+		rest = rest[end+1:]
+	}
+	return name, filters, nil
+}
+
+func (f formatFilter) numeric() bool {
+	switch f.field {
+	case "height", "width", "tbr", "bitrate":
+		return true
+	}
+	return false
+}
+
+func (f formatFilter) validate() error {
+	switch f.field {
+	case "height", "width", "tbr", "bitrate":
+		if _, err := strconv.ParseFloat(strings.TrimSuffix(f.value, "p"), 64); err != nil {
+			return fmt.Errorf("invalid number in format filter %s%s%s", f.field, f.op, f.value)
+		}
+		switch f.op {
+		case "^=", "$=", "*=":
+			return fmt.Errorf("operator %s needs a text field, not %s", f.op, f.field)
+		}
+	case "ext", "quality":
+		switch f.op {
+		case "<", "<=", ">", ">=":
+			return fmt.Errorf("operator %s needs a numeric field, not %s", f.op, f.field)
+		}
+	default:
+		return fmt.Errorf("unknown format filter field %q (use height, width, tbr, ext, or quality)", f.field)
+	}
+	return nil
+}
+
+func formatMatchesFilters(format *VideoFormat, filters []formatFilter) bool {
+	for _, f := range filters {
+		if !f.matches(format) {
+			return false
+		}
+	}
+	return true
+}
+
+func (f formatFilter) matches(format *VideoFormat) bool {
+	if f.numeric() {
+		var actual int
+		switch f.field {
+		case "height":
+			actual = format.Height
+		case "width":
+			actual = format.Width
+		default:
+			actual = format.Bitrate
+		}
+		if actual == 0 {
+			return f.optional
+		}
+		want, _ := strconv.ParseFloat(strings.TrimSuffix(f.value, "p"), 64)
+		a := float64(actual)
+		switch f.op {
+		case "<":
+			return a < want
+		case "<=":
+			return a <= want
+		case ">":
+			return a > want
+		case ">=":
+			return a >= want
+		case "=":
+			return a == want
+		case "!=":
+			return a != want
+		}
+		return false
+	}
+
+	actual := format.Ext
+	if f.field == "quality" {
+		actual = format.QualityLabel()
+	}
+	if actual == "" {
+		return f.optional
+	}
+	actual, want := strings.ToLower(actual), strings.ToLower(f.value)
+	switch f.op {
+	case "=":
+		return actual == want
+	case "!=":
+		return actual != want
+	case "^=":
+		return strings.HasPrefix(actual, want)
+	case "$=":
+		return strings.HasSuffix(actual, want)
+	case "*=":
+		return strings.Contains(actual, want)
+	}
+	return false
+}
@@ -0,0 +1,42 @@
+package extractor
+
+import "testing"
+
+func TestSelectFormat(t *testing.T) {
+	formats := []VideoFormat{
+		{Quality: "2160p", Ext: "webm", Height: 2160, Bitrate: 16000, AudioURL: "a"},
+		{Quality: "1080p", Ext: "mp4", Height: 1080, Bitrate: 4000, AudioURL: "a"},
+		{Quality: "1080p", Ext: "webm", Height: 1080, Bitrate: 3000, AudioURL: "a"},
+		{Quality: "360p", Ext: "mp4", Height: 360, Bitrate: 600},
+	}
+
+	tests := []struct {
+		spec string
+		want int
+	}{
+		{"", 0},
+		{"best", 0},
+		{"worst", 3},
+		{"bestvideo[height<=1080]+bestaudio", 1},
+		{"bv[height<=1080][ext=webm]+ba", 2},
+		{"bestvideo[height<=720]+bestaudio/best[ext=mp4]", 1},
+		{"bestvideo[height<=720]+bestaudio/worst", 3},
+		{"best[quality^=360]", 3},
+	}
+	for _, tt := range tests {
+		got, err := SelectFormat(formats, tt.spec)
+		if err != nil {
+			t.Errorf("SelectFormat(%q) error = %v", tt.spec, err)
+			continue
+		}
+		if got != &formats[tt.want] {
+			t.Errorf("SelectFormat(%q) = %+v, want %+v", tt.spec, *got, formats[tt.want])
+		}
+	}
+
+	for _, spec := range []string{"bestvideo[height<=720]+bestaudio", "bestaudio", "best[fps>30]", "best[height<=abc]", "best[height<=1080"} {
+		if _, err := SelectFormat(formats, spec); err == nil {
+			t.Errorf("SelectFormat(%q) succeeded, want error", spec)
+		}
+	}
+}
@@ -6,10 +6,14 @@ import (
 	"net/http"
 	"net/url"
 	"regexp"
+
+	"github.com/guiyumin/vget/internal/core/sanitize"
 )
 
 // iTunesExtractor handles Apple Podcasts downloads via iTunes API
-type iTunesExtractor struct{}
+type iTunesExtractor struct {
+	client *http.Client
+}
 
 func (e *iTunesExtractor) Name() string {
 	return "itunes"
@@ -27,6 +31,17 @@ func (e *iTunesExtractor) Match(u *url.URL) bool {
 }
 
 func (e *iTunesExtractor) Extract(rawURL string) (Media, error) {
+	return e.ExtractWithContext(rawURL, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (e *iTunesExtractor) ExtractWithContext(rawURL string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	call.client = ctx.HTTPClient()
+	return call.extract(rawURL)
+}
+
+func (e *iTunesExtractor) extract(rawURL string) (Media, error) {
 	u, err := url.Parse(rawURL)
 	if err != nil {
 		return nil, fmt.Errorf("invalid URL: %w", err)
@@ -45,15 +60,15 @@ func (e *iTunesExtractor) Extract(rawURL string) (Media, error) {
 		return e.extractEpisode(podcastID, episodeID)
 	}
 
-	// Otherwise list episodes from the podcast
-	return e.listEpisodes()
+	// Otherwise list episodes from the podcast's feed
+	return e.listEpisodes(podcastID)
 }
 
 func (e *iTunesExtractor) extractEpisode(podcastID, episodeID string) (*AudioMedia, error) {
 	// Lookup episode by ID
 	url := fmt.Sprintf("https://itunes.apple.com/lookup?id=%s&entity=podcastEpisode", podcastID)
 
-	resp, err := http.Get(url)
+	resp, err := e.client.Get(url)
 	if err != nil {
 		return nil, err
 	}
@@ -73,15 +88,16 @@ func (e *iTunesExtractor) extractEpisode(podcastID, episodeID string) (*AudioMed
 			}
 
 			// Create filename: {podcast} - {episode}
-			filename := SanitizeFilename(fmt.Sprintf("%s - %s", item.CollectionName, item.TrackName))
+			filename := sanitize.Filename(fmt.Sprintf("%s - %s", item.CollectionName, item.TrackName))
 
 			return &AudioMedia{
-				ID:       episodeID,
-				Title:    filename,
-				Uploader: item.ArtistName,
-				Duration: item.TrackTimeMillis / 1000,
-				URL:      item.EpisodeURL,
-				Ext:      ext,
+				ID:        episodeID,
+				Title:     filename,
+				Uploader:  item.ArtistName,
+				Duration:  item.TrackTimeMillis / 1000,
+				Thumbnail: item.ArtworkURL600,
+				URL:       item.EpisodeURL,
+				Ext:       ext,
 			}, nil
 		}
 	}
@@ -89,10 +105,28 @@ func (e *iTunesExtractor) extractEpisode(podcastID, episodeID string) (*AudioMed
 	return nil, fmt.Errorf("episode not found")
 }
 
-func (e *iTunesExtractor) listEpisodes() (*AudioMedia, error) {
-	// Return error suggesting to use specific episode URL
-	// We could list episodes here but for now keep it simple
-	return nil, fmt.Errorf("please provide a specific episode URL. Use 'vget search --podcast <name>' to find episodes, or visit the podcast page and select an episode")
+func (e *iTunesExtractor) listEpisodes(podcastID string) (Media, error) {
+	// Lookup the podcast to find its RSS feed
+	url := fmt.Sprintf("https://itunes.apple.com/lookup?id=%s", podcastID)
+
+	resp, err := e.client.Get(url)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	var result iTunesLookupResponse
+	if err := json.NewDecoder(resp.Body).Decode(&result); err != nil {
+		return nil, err
+	}
+
+	for _, item := range result.Results {
+		if item.FeedURL != "" {
+			return (&PodcastExtractor{client: e.client}).extract(item.FeedURL)
+		}
+	}
+
+	return nil, fmt.Errorf("podcast feed not found. Use 'vget search --podcast <name>' to find episodes, or visit the podcast page and select an episode")
 }
 
 // iTunes API response structures
@@ -112,6 +146,8 @@ type iTunesLookupResult struct {
 	EpisodeURL           string `json:"episodeUrl"`
 	EpisodeFileExtension string `json:"episodeFileExtension"`
 	ReleaseDate          string `json:"releaseDate"`
+	FeedURL              string `json:"feedUrl"`
+	ArtworkURL600        string `json:"artworkUrl600"`
 }
 
 func init() {
@@ -1,11 +1,26 @@
 package extractor
 
 import (
+	"bufio"
+	"bytes"
+	"fmt"
+	"io"
 	"net/http"
 	"net/url"
 	"path"
+	"regexp"
+	"sort"
+	"strconv"
 	"strings"
-	"time"
+)
+
+// maxPlaylistSize caps how much of a playlist is read when listing variants
+const maxPlaylistSize = 4 * 1024 * 1024
+
+var (
+	hlsBandwidthRegex  = regexp.MustCompile(`(?:^|[:,])BANDWIDTH=(\d+)`)
+	hlsResolutionRegex = regexp.MustCompile(`RESOLUTION=(\d+)x(\d+)`)
+	hlsCodecsRegex     = regexp.MustCompile(`CODECS="([^"]+)"`)
 )
 
 // M3U8Extractor handles direct m3u8 playlist URLs
@@ -30,17 +45,21 @@ func (m *M3U8Extractor) Match(u *url.URL) bool {
 	return ext == ".m3u8" || ext == ".m3u"
 }
 
-// Extract retrieves media information from an m3u8 URL
+// Extract retrieves media information from an m3u8 URL.
+// Master playlists are expanded into one format per variant stream; every
+// format has Ext "m3u8" so the downloader uses segment downloading.
 func (m *M3U8Extractor) Extract(urlStr string) (Media, error) {
-	if m.client == nil {
-		m.client = &http.Client{
-			Timeout: 30 * time.Second,
-			Transport: &http.Transport{
-				Proxy: http.ProxyFromEnvironment,
-			},
-		}
-	}
+	return m.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (m *M3U8Extractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *m
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
 
+func (m *M3U8Extractor) extract(urlStr string) (Media, error) {
 	// Parse URL to extract filename
 	parsedURL, _ := url.Parse(urlStr)
 	filename := path.Base(parsedURL.Path)
@@ -52,18 +71,173 @@ func (m *M3U8Extractor) Extract(urlStr string) (Media, error) {
 	// Generate ID from URL
 	id := generateM3U8ID(urlStr)
 
-	return &VideoMedia{
-		ID:    id,
-		Title: title,
-		Formats: []VideoFormat{
+	formats, drm, err := m.fetchVariants(urlStr)
+	if err != nil || len(formats) == 0 {
+		// Not a master playlist (or unreachable now): let the HLS downloader handle it
+		formats = []VideoFormat{
 			{
 				URL: urlStr,
 				Ext: "m3u8",
 			},
-		},
+		}
+	}
+
+	return &VideoMedia{
+		ID:      id,
+		Title:   title,
+		Formats: formats,
+		DRM:     drm,
 	}, nil
 }
 
+// fetchVariants downloads the playlist and returns its variant streams and
+// the DRM system its key tags name, if any
+func (m *M3U8Extractor) fetchVariants(urlStr string) ([]VideoFormat, string, error) {
+	data, finalURL, err := m.fetchPlaylist(urlStr)
+	if err != nil {
+		return nil, "", err
+	}
+
+	// Resolve relative variant URIs against the URL after redirects
+	formats, err := parseMasterPlaylist(bytes.NewReader(data), finalURL)
+	if err != nil {
+		return nil, "", err
+	}
+
+	// Master playlists often leave keys to the variant playlists
+	drm := hlsDRMSystem(string(data))
+	if drm == "" && len(formats) > 0 {
+		if variant, _, err := m.fetchPlaylist(formats[0].URL); err == nil {
+			drm = hlsDRMSystem(string(variant))
+		}
+	}
+	return formats, drm, nil
+}
+
+// fetchPlaylist downloads a playlist, returning its body and the URL after redirects
+func (m *M3U8Extractor) fetchPlaylist(urlStr string) ([]byte, string, error) {
+	req, err := http.NewRequest("GET", urlStr, nil)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+
+	resp, err := m.client.Do(req)
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to fetch playlist: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, "", fmt.Errorf("server returned status %d", resp.StatusCode)
+	}
+
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxPlaylistSize))
+	if err != nil {
+		return nil, "", fmt.Errorf("failed to read playlist: %w", err)
+	}
+	return data, resp.Request.URL.String(), nil
+}
+
+// parseMasterPlaylist lists the variant streams of an HLS master playlist,
+// highest resolution and bitrate first. Media playlists yield no formats.
+func parseMasterPlaylist(r io.Reader, baseURL string) ([]VideoFormat, error) {
+	base, err := url.Parse(baseURL)
+	if err != nil {
+		return nil, fmt.Errorf("invalid base URL: %w", err)
+	}
+
+	var formats []VideoFormat
+	var pending *VideoFormat
+	seen := make(map[string]bool)
+
+	scanner := bufio.NewScanner(r)
+	scanner.Buffer(make([]byte, 64*1024), 1024*1024)
+	for scanner.Scan() {
+		line := strings.TrimSpace(scanner.Text())
+		if line == "" {
+			continue
+		}
+
+		if strings.HasPrefix(line, "#EXT-X-STREAM-INF:") {
+			pending = parseStreamInf(line)
+			continue
+		}
+		if strings.HasPrefix(line, "#") || pending == nil {
+			continue
+		}
+
+		// The first URI line after EXT-X-STREAM-INF is the variant playlist
+		ref, err := url.Parse(line)
+		if err == nil {
+			pending.URL = base.ResolveReference(ref).String()
+			if !seen[pending.URL] {
+				seen[pending.URL] = true
+				formats = append(formats, *pending)
+			}
+		}
+		pending = nil
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("error reading playlist: %w", err)
+	}
+
+	sort.SliceStable(formats, func(i, j int) bool {
+		if formats[i].Height != formats[j].Height {
+			return formats[i].Height > formats[j].Height
+		}
+		return formats[i].Bitrate > formats[j].Bitrate
+	})
+	return formats, nil
+}
+
+// parseStreamInf reads BANDWIDTH, RESOLUTION, and CODECS from an EXT-X-STREAM-INF tag
+func parseStreamInf(line string) *VideoFormat {
+	f := &VideoFormat{Ext: "m3u8"}
+
+	if m := hlsBandwidthRegex.FindStringSubmatch(line); m != nil {
+		bandwidth, _ := strconv.Atoi(m[1])
+		f.Bitrate = bandwidth / 1000 // kbps
+	}
+	if m := hlsResolutionRegex.FindStringSubmatch(line); m != nil {
+		f.Width, _ = strconv.Atoi(m[1])
+		f.Height, _ = strconv.Atoi(m[2])
+	}
+
+	// Label like "720p [H.264]", or by bitrate for audio-only variants
+	switch {
+	case f.Height > 0:
+		f.Quality = fmt.Sprintf("%dp", f.Height)
+	case f.Bitrate > 0:
+		f.Quality = fmt.Sprintf("%dk", f.Bitrate)
+	}
+	if m := hlsCodecsRegex.FindStringSubmatch(line); m != nil && f.Quality != "" {
+		if codec := videoCodecName(m[1]); codec != "" {
+			f.Quality += " [" + codec + "]"
+		}
+	}
+	return f
+}
+
+// videoCodecName returns a readable name for the video codec in an RFC 6381
+// codecs list (e.g. "avc1.64001f,mp4a.40.2" -> "H.264")
+func videoCodecName(codecs string) string {
+	for _, codec := range strings.Split(codecs, ",") {
+		codec = strings.ToLower(strings.TrimSpace(codec))
+		switch {
+		case strings.HasPrefix(codec, "avc"):
+			return "H.264"
+		case strings.HasPrefix(codec, "hvc"), strings.HasPrefix(codec, "hev"):
+			return "H.265"
+		case strings.HasPrefix(codec, "av01"):
+			return "AV1"
+		case strings.HasPrefix(codec, "vp09"):
+			return "VP9"
+		}
+	}
+	return ""
+}
+
 // generateM3U8ID creates a short ID from URL
 func generateM3U8ID(urlStr string) string {
 	parsedURL, err := url.Parse(urlStr)
@@ -87,4 +261,3 @@ func generateM3U8ID(urlStr string) string {
 
 	return base
 }
-
@@ -0,0 +1,73 @@
+package extractor
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestParseMasterPlaylist(t *testing.T) {
+	playlist := `#EXTM3U
+#EXT-X-VERSION:4
+#EXT-X-STREAM-INF:AVERAGE-BANDWIDTH=500000,BANDWIDTH=800000,RESOLUTION=640x360,CODECS="avc1.4d401e,mp4a.40.2"
+360/index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=5000000,RESOLUTION=1920x1080,CODECS="hvc1.1.6.L120.90,mp4a.40.2"
+https://cdn.example.com/1080/index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2500000,RESOLUTION=1280x720,CODECS="avc1.64001f,mp4a.40.2"
+/live/720/index.m3u8
+`
+	formats, err := parseMasterPlaylist(strings.NewReader(playlist), "https://example.com/live/master.m3u8")
+	if err != nil {
+		t.Fatalf("parseMasterPlaylist() error = %v", err)
+	}
+	if len(formats) != 3 {
+		t.Fatalf("got %d formats, want 3", len(formats))
+	}
+
+	want := []VideoFormat{
+		{URL: "https://cdn.example.com/1080/index.m3u8", Quality: "1080p [H.265]", Ext: "m3u8", Width: 1920, Height: 1080, Bitrate: 5000},
+		{URL: "https://example.com/live/720/index.m3u8", Quality: "720p [H.264]", Ext: "m3u8", Width: 1280, Height: 720, Bitrate: 2500},
+		{URL: "https://example.com/live/360/index.m3u8", Quality: "360p [H.264]", Ext: "m3u8", Width: 640, Height: 360, Bitrate: 800},
+	}
+	for i, w := range want {
+		got := formats[i]
+		if got.URL != w.URL || got.Quality != w.Quality || got.Ext != w.Ext ||
+			got.Width != w.Width || got.Height != w.Height || got.Bitrate != w.Bitrate {
+			t.Errorf("format %d = %+v, want %+v", i, got, w)
+		}
+	}
+}
+
+func TestParseMasterPlaylistMediaPlaylist(t *testing.T) {
+	playlist := `#EXTM3U
+#EXT-X-TARGETDURATION:10
+#EXTINF:10.0,
+segment0.ts
+#EXTINF:10.0,
+segment1.ts
+#EXT-X-ENDLIST
+`
+	formats, err := parseMasterPlaylist(strings.NewReader(playlist), "https://example.com/video.m3u8")
+	if err != nil {
+		t.Fatalf("parseMasterPlaylist() error = %v", err)
+	}
+	if len(formats) != 0 {
+		t.Errorf("got %d formats for a media playlist, want 0", len(formats))
+	}
+}
+
+func TestHLSDRMSystem(t *testing.T) {
+	tests := []struct {
+		playlist string
+		want     string
+	}{
+		{`#EXT-X-KEY:METHOD=AES-128,URI="https://example.com/key"`, ""},
+		{`#EXT-X-KEY:METHOD=SAMPLE-AES,URI="skd://key",KEYFORMAT="com.apple.streamingkeydelivery",KEYFORMATVERSIONS="1"`, DRMFairPlay},
+		{`#EXT-X-SESSION-KEY:METHOD=SAMPLE-CTR,URI="data:...",KEYFORMAT="urn:uuid:edef8ba9-79d6-4ace-a3c8-27dcd51d21ed"`, DRMWidevine},
+		{`#EXT-X-KEY:METHOD=SAMPLE-AES,URI="https://example.com/key"`, DRMUnknown},
+	}
+	for _, tt := range tests {
+		if got := hlsDRMSystem("#EXTM3U\n" + tt.playlist + "\n#EXTINF:6,\nseg0.ts\n"); got != tt.want {
+			t.Errorf("hlsDRMSystem(%q) = %q, want %q", tt.playlist, got, tt.want)
+		}
+	}
+}
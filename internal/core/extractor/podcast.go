@@ -0,0 +1,333 @@
+package extractor
+
+import (
+	"crypto/md5"
+	"encoding/hex"
+	"encoding/xml"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"path"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// maxFeedSize caps how much of a feed is read; large shows can have thousands of episodes
+const maxFeedSize = 32 * 1024 * 1024
+
+// PodcastExtractor handles podcast RSS and Atom feeds, listing every episode
+type PodcastExtractor struct {
+	client *http.Client
+}
+
+// rssFeed is the subset of an RSS 2.0 podcast feed used for episode listing
+type rssFeed struct {
+	Channel struct {
+		Title        string      `xml:"title"`
+		ITunesAuthor string      `xml:"http://www.itunes.com/dtds/podcast-1.0.dtd author"`
+		ITunesImage  itunesImage `xml:"http://www.itunes.com/dtds/podcast-1.0.dtd image"`
+		ImageURL     string      `xml:"image>url"`
+		Items        []rssItem   `xml:"item"`
+	} `xml:"channel"`
+}
+
+type rssItem struct {
+	Title          string      `xml:"title"`
+	GUID           string      `xml:"guid"`
+	PubDate        string      `xml:"pubDate"`
+	ITunesDuration string      `xml:"http://www.itunes.com/dtds/podcast-1.0.dtd duration"`
+	ITunesImage    itunesImage `xml:"http://www.itunes.com/dtds/podcast-1.0.dtd image"`
+	Enclosure      struct {
+		URL  string `xml:"url,attr"`
+		Type string `xml:"type,attr"`
+	} `xml:"enclosure"`
+}
+
+type itunesImage struct {
+	Href string `xml:"href,attr"`
+}
+
+// atomFeed is the subset of an Atom feed used for episode listing
+type atomFeed struct {
+	Title   string      `xml:"title"`
+	Author  string      `xml:"author>name"`
+	Logo    string      `xml:"logo"`
+	Entries []atomEntry `xml:"entry"`
+}
+
+type atomEntry struct {
+	ID        string     `xml:"id"`
+	Title     string     `xml:"title"`
+	Published string     `xml:"published"`
+	Updated   string     `xml:"updated"`
+	Links     []atomLink `xml:"link"`
+}
+
+type atomLink struct {
+	Rel  string `xml:"rel,attr"`
+	Href string `xml:"href,attr"`
+	Type string `xml:"type,attr"`
+}
+
+// Name returns the extractor name
+func (e *PodcastExtractor) Name() string {
+	return "podcast"
+}
+
+// CacheTTL reuses parsed feeds for an hour
+func (e *PodcastExtractor) CacheTTL() time.Duration {
+	return time.Hour
+}
+
+// Match checks if the URL looks like a feed
+func (e *PodcastExtractor) Match(u *url.URL) bool {
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return false
+	}
+	// Host matching for feed hosts is done by registry
+	return true
+}
+
+// Extract fetches the feed and returns a playlist with one entry per episode
+func (e *PodcastExtractor) Extract(rawURL string) (Media, error) {
+	return e.ExtractWithContext(rawURL, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (e *PodcastExtractor) ExtractWithContext(rawURL string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	client := *ctx.HTTPClient()
+	client.Timeout = 60 * time.Second
+	call.client = &client
+	return call.extract(rawURL)
+}
+
+func (e *PodcastExtractor) extract(rawURL string) (Media, error) {
+	req, err := http.NewRequest("GET", rawURL, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+	req.Header.Set("Accept", "application/rss+xml, application/atom+xml, application/xml;q=0.9, */*;q=0.8")
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch feed: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("server returned status %d", resp.StatusCode)
+	}
+
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxFeedSize))
+	if err != nil {
+		return nil, fmt.Errorf("failed to read feed: %w", err)
+	}
+
+	playlist, err := parseFeed(data)
+	if err != nil {
+		return nil, err
+	}
+	playlist.ID = feedID(resp.Request.URL.String())
+	if len(playlist.Entries) == 0 {
+		return nil, fmt.Errorf("no episodes with audio found in feed")
+	}
+	return playlist, nil
+}
+
+// parseFeed parses an RSS or Atom feed into a playlist, newest episode first
+func parseFeed(data []byte) (*PlaylistMedia, error) {
+	var root struct {
+		XMLName xml.Name
+	}
+	if err := xml.Unmarshal(data, &root); err != nil {
+		return nil, fmt.Errorf("failed to parse feed: %w", err)
+	}
+
+	switch root.XMLName.Local {
+	case "rss":
+		var feed rssFeed
+		if err := xml.Unmarshal(data, &feed); err != nil {
+			return nil, fmt.Errorf("failed to parse RSS feed: %w", err)
+		}
+		return feed.toPlaylist(), nil
+	case "feed":
+		var feed atomFeed
+		if err := xml.Unmarshal(data, &feed); err != nil {
+			return nil, fmt.Errorf("failed to parse Atom feed: %w", err)
+		}
+		return feed.toPlaylist(), nil
+	default:
+		return nil, fmt.Errorf("not an RSS or Atom feed (root element <%s>)", root.XMLName.Local)
+	}
+}
+
+func (f *rssFeed) toPlaylist() *PlaylistMedia {
+	ch := f.Channel
+	p := &PlaylistMedia{
+		Title:     strings.TrimSpace(ch.Title),
+		Uploader:  strings.TrimSpace(ch.ITunesAuthor),
+		Thumbnail: firstNonEmpty(ch.ITunesImage.Href, strings.TrimSpace(ch.ImageURL)),
+	}
+
+	for _, item := range ch.Items {
+		enclosureURL := strings.TrimSpace(item.Enclosure.URL)
+		if enclosureURL == "" {
+			continue
+		}
+		title := strings.TrimSpace(item.Title)
+		guid := firstNonEmpty(strings.TrimSpace(item.GUID), enclosureURL)
+		duration := parseFeedDuration(item.ITunesDuration)
+		published := parseFeedDate(item.PubDate)
+		thumbnail := firstNonEmpty(item.ITunesImage.Href, p.Thumbnail)
+		p.Entries = append(p.Entries, PlaylistEntry{
+			Title:     title,
+			URL:       enclosureURL,
+			Published: published,
+			Thumbnail: thumbnail,
+			Duration:  duration,
+			Media:     episodeMedia(guid, title, p.Uploader, thumbnail, enclosureURL, item.Enclosure.Type, duration, published),
+		})
+	}
+	return p
+}
+
+func (f *atomFeed) toPlaylist() *PlaylistMedia {
+	p := &PlaylistMedia{
+		Title:     strings.TrimSpace(f.Title),
+		Uploader:  strings.TrimSpace(f.Author),
+		Thumbnail: strings.TrimSpace(f.Logo),
+	}
+
+	for _, entry := range f.Entries {
+		var enclosure *atomLink
+		for i := range entry.Links {
+			if entry.Links[i].Rel == "enclosure" && entry.Links[i].Href != "" {
+				enclosure = &entry.Links[i]
+				break
+			}
+		}
+		if enclosure == nil {
+			continue
+		}
+		title := strings.TrimSpace(entry.Title)
+		published := parseFeedDate(firstNonEmpty(entry.Published, entry.Updated))
+		p.Entries = append(p.Entries, PlaylistEntry{
+			Title:     title,
+			URL:       enclosure.Href,
+			Published: published,
+			Thumbnail: p.Thumbnail,
+			Media:     episodeMedia(firstNonEmpty(entry.ID, enclosure.Href), title, p.Uploader, p.Thumbnail, enclosure.Href, enclosure.Type, 0, published),
+		})
+	}
+	return p
+}
+
+// episodeMedia builds the media for an episode enclosure (audio, or video for video podcasts)
+func episodeMedia(guid, title, uploader, thumbnail, enclosureURL, mimeType string, duration int, published time.Time) Media {
+	id := feedID(guid)
+	ext := enclosureExt(enclosureURL, mimeType)
+
+	if strings.HasPrefix(mimeType, "video/") {
+		return &VideoMedia{
+			ID:        id,
+			Title:     title,
+			Uploader:  uploader,
+			Published: published,
+			Duration:  duration,
+			Thumbnail: thumbnail,
+			Formats:   []VideoFormat{{URL: enclosureURL, Ext: ext}},
+		}
+	}
+	return &AudioMedia{
+		ID:        id,
+		Title:     title,
+		Uploader:  uploader,
+		Published: published,
+		Duration:  duration,
+		Thumbnail: thumbnail,
+		URL:       enclosureURL,
+		Ext:       ext,
+	}
+}
+
+// enclosureExt returns the file extension from the enclosure URL, or its MIME type
+func enclosureExt(rawURL, mimeType string) string {
+	if u, err := url.Parse(rawURL); err == nil {
+		ext := strings.ToLower(strings.TrimPrefix(path.Ext(u.Path), "."))
+		if _, ok := sniffMediaExts[ext]; ok {
+			return ext
+		}
+	}
+	switch strings.ToLower(mimeType) {
+	case "audio/mp4", "audio/x-m4a", "audio/aac":
+		return "m4a"
+	case "video/mp4", "video/x-m4v":
+		return "mp4"
+	case "audio/ogg":
+		return "ogg"
+	}
+	return "mp3"
+}
+
+// parseFeedDate parses RFC 822 (RSS) and RFC 3339 (Atom) dates
+func parseFeedDate(s string) time.Time {
+	s = strings.TrimSpace(s)
+	layouts := []string{
+		time.RFC1123Z,
+		time.RFC1123,
+		"Mon, 2 Jan 2006 15:04:05 -0700",
+		"Mon, 2 Jan 2006 15:04:05 MST",
+		"2 Jan 2006 15:04:05 -0700",
+		time.RFC3339,
+		"2006-01-02",
+	}
+	for _, layout := range layouts {
+		if t, err := time.Parse(layout, s); err == nil {
+			return t
+		}
+	}
+	return time.Time{}
+}
+
+// parseFeedDuration parses itunes:duration as seconds, MM:SS, or HH:MM:SS
+func parseFeedDuration(s string) int {
+	s = strings.TrimSpace(s)
+	if s == "" {
+		return 0
+	}
+	total := 0
+	for _, part := range strings.Split(s, ":") {
+		n, err := strconv.Atoi(part)
+		if err != nil {
+			return 0
+		}
+		total = total*60 + n
+	}
+	return total
+}
+
+// feedID creates a short stable ID from a feed URL or episode GUID
+func feedID(s string) string {
+	sum := md5.Sum([]byte(s))
+	return hex.EncodeToString(sum[:])[:12]
+}
+
+func init() {
+	Register(podcastExtractor,
+		"feeds.megaphone.fm",
+		"feeds.simplecast.com",
+		"feeds.buzzsprout.com",
+		"feeds.libsyn.com",
+		"feeds.transistor.fm",
+		"feeds.acast.com",
+		"feeds.captivate.fm",
+		"feeds.npr.org",
+		"feeds.soundcloud.com",
+		"feed.podbean.com",
+		"rss.art19.com",
+	)
+}
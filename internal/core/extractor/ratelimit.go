@@ -0,0 +1,78 @@
+package extractor
+
+import (
+	"net/url"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// RateLimiter spaces out extractions against the same site so batch extraction
+// and playlist expansion stay within the configured requests per minute
+type RateLimiter struct {
+	mu   sync.Mutex
+	next map[string]time.Time // earliest time the next request may start, per bucket
+}
+
+// NewRateLimiter creates an empty rate limiter
+func NewRateLimiter() *RateLimiter {
+	return &RateLimiter{next: make(map[string]time.Time)}
+}
+
+// sharedLimiter is used when an ExtractContext doesn't carry its own limiter
+var sharedLimiter = NewRateLimiter()
+
+// Wait blocks until a request to rawURL is allowed under the configured limits
+func (l *RateLimiter) Wait(rawURL string) {
+	cfg := config.LoadOrDefault()
+	if delay := l.reserve(rawURL, cfg.RateLimit, time.Now()); delay > 0 {
+		time.Sleep(delay)
+	}
+}
+
+// reserve claims the next request slot for rawURL and returns how long to wait for it
+func (l *RateLimiter) reserve(rawURL string, limits config.RateLimitConfig, now time.Time) time.Duration {
+	bucket, rpm := rateLimitFor(rawURL, limits)
+	if rpm <= 0 {
+		return 0
+	}
+	interval := time.Minute / time.Duration(rpm)
+
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	slot := now
+	if next, ok := l.next[bucket]; ok && next.After(now) {
+		slot = next
+	}
+	l.next[bucket] = slot.Add(interval)
+	return slot.Sub(now)
+}
+
+// rateLimitFor returns the bucket and requests per minute for a URL. The
+// longest configured domain matching the host wins; otherwise the default
+// limit applies to the host on its own.
+func rateLimitFor(rawURL string, limits config.RateLimitConfig) (string, int) {
+	u, err := url.Parse(rawURL)
+	if err != nil || u.Host == "" {
+		return "", 0
+	}
+	host := strings.ToLower(u.Hostname())
+
+	bucket, rpm := "", 0
+	for domain, n := range limits.Domains {
+		domain = strings.ToLower(domain)
+		if host != domain && !strings.HasSuffix(host, "."+domain) {
+			continue
+		}
+		if len(domain) > len(bucket) {
+			bucket, rpm = domain, n
+		}
+	}
+	if bucket != "" {
+		return bucket, rpm
+	}
+	return strings.TrimPrefix(host, "www."), limits.Default
+}
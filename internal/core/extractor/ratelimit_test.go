@@ -0,0 +1,35 @@
+package extractor
+
+import (
+	"testing"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+func TestRateLimiterReserve(t *testing.T) {
+	limits := config.RateLimitConfig{
+		Default: 0,
+		Domains: map[string]int{"bilibili.com": 30},
+	}
+	l := NewRateLimiter()
+	now := time.Now()
+
+	// Subdomains share the domain's bucket, spaced 2s apart at 30 rpm
+	if d := l.reserve("https://www.bilibili.com/video/BV1", limits, now); d != 0 {
+		t.Errorf("first request waited %v", d)
+	}
+	if d := l.reserve("https://api.bilibili.com/x/web", limits, now); d != 2*time.Second {
+		t.Errorf("second request waited %v, want 2s", d)
+	}
+	if d := l.reserve("https://www.bilibili.com/video/BV2", limits, now.Add(time.Second)); d != 3*time.Second {
+		t.Errorf("third request waited %v, want 3s", d)
+	}
+
+	// Hosts without a limit are never delayed
+	for i := 0; i < 3; i++ {
+		if d := l.reserve("https://example.com/a.mp4", limits, now); d != 0 {
+			t.Errorf("unlimited host waited %v", d)
+		}
+	}
+}
@@ -0,0 +1,225 @@
+package extractor
+
+import (
+	"encoding/json"
+	"fmt"
+	"html"
+	"io"
+	"net/http"
+	"net/url"
+	"regexp"
+	"strings"
+)
+
+// Reddit rejects requests with generic client user agents
+const redditUserAgent = "vget/1.0 (+https://github.com/guiyumin/vget)"
+
+var (
+	redditVideoIDRegex = regexp.MustCompile(`^/([a-zA-Z0-9]+)`)
+	redditPostRegex    = regexp.MustCompile(`/comments/([a-z0-9]+)`)
+)
+
+// RedditExtractor handles Reddit video posts and v.redd.it links. Reddit
+// serves video and audio as separate DASH representations, so formats carry
+// the audio track in AudioURL and are merged after download.
+type RedditExtractor struct {
+	client *http.Client
+}
+
+func (e *RedditExtractor) Name() string {
+	return "reddit"
+}
+
+func (e *RedditExtractor) Match(u *url.URL) bool {
+	// Host matching is done by registry, check path pattern
+	if strings.EqualFold(u.Hostname(), "v.redd.it") {
+		return redditVideoIDRegex.MatchString(u.Path)
+	}
+	return redditPostRegex.MatchString(u.Path)
+}
+
+func (e *RedditExtractor) Extract(urlStr string) (Media, error) {
+	return e.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (e *RedditExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
+
+func (e *RedditExtractor) extract(urlStr string) (Media, error) {
+	u, err := url.Parse(urlStr)
+	if err != nil {
+		return nil, fmt.Errorf("invalid URL: %w", err)
+	}
+
+	if !strings.EqualFold(u.Hostname(), "v.redd.it") {
+		return e.extractPost(u)
+	}
+
+	m := redditVideoIDRegex.FindStringSubmatch(u.Path)
+	if m == nil {
+		return nil, fmt.Errorf("could not extract video ID from URL")
+	}
+	videoID := m[1]
+
+	// v.redd.it redirects to the post, which has the title and author
+	if postURL := e.postURL(videoID); postURL != nil {
+		if media, err := e.extractPost(postURL); err == nil {
+			return media, nil
+		}
+	}
+	return e.extractManifest("https://v.redd.it/"+videoID+"/DASHPlaylist.mpd", videoID, videoID, "", 0, "")
+}
+
+// postURL follows a v.redd.it link to its post, or returns nil
+func (e *RedditExtractor) postURL(videoID string) *url.URL {
+	req, err := http.NewRequest("HEAD", "https://v.redd.it/"+videoID, nil)
+	if err != nil {
+		return nil
+	}
+	req.Header.Set("User-Agent", redditUserAgent)
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return nil
+	}
+	resp.Body.Close()
+
+	final := resp.Request.URL
+	if !redditPostRegex.MatchString(final.Path) {
+		return nil
+	}
+	return final
+}
+
+// redditPost is the subset of a post's JSON needed to find its video
+type redditPost struct {
+	ID          string `json:"id"`
+	Title       string `json:"title"`
+	Author      string `json:"author"`
+	Thumbnail   string `json:"thumbnail"`
+	SecureMedia *struct {
+		RedditVideo *struct {
+			DashURL  string `json:"dash_url"`
+			Duration int    `json:"duration"`
+		} `json:"reddit_video"`
+	} `json:"secure_media"`
+	CrosspostParentList []redditPost `json:"crosspost_parent_list"`
+}
+
+// dashURL returns the post's DASH manifest, following crossposts
+func (p *redditPost) dashURL() (string, int) {
+	if p.SecureMedia != nil && p.SecureMedia.RedditVideo != nil && p.SecureMedia.RedditVideo.DashURL != "" {
+		return html.UnescapeString(p.SecureMedia.RedditVideo.DashURL), p.SecureMedia.RedditVideo.Duration
+	}
+	for i := range p.CrosspostParentList {
+		if dash, duration := p.CrosspostParentList[i].dashURL(); dash != "" {
+			return dash, duration
+		}
+	}
+	return "", 0
+}
+
+// extractPost reads a post's JSON and extracts its hosted video
+func (e *RedditExtractor) extractPost(u *url.URL) (Media, error) {
+	jsonURL := *u
+	jsonURL.Host = "www.reddit.com"
+	jsonURL.Path = strings.TrimSuffix(jsonURL.Path, "/") + ".json"
+	jsonURL.RawQuery = "raw_json=1"
+	jsonURL.Fragment = ""
+
+	req, err := http.NewRequest("GET", jsonURL.String(), nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", redditUserAgent)
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch post: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("reddit returned status %d", resp.StatusCode)
+	}
+
+	var listings []struct {
+		Data struct {
+			Children []struct {
+				Data redditPost `json:"data"`
+			} `json:"children"`
+		} `json:"data"`
+	}
+	if err := json.NewDecoder(io.LimitReader(resp.Body, 16*1024*1024)).Decode(&listings); err != nil {
+		return nil, fmt.Errorf("failed to parse post JSON: %w", err)
+	}
+	if len(listings) == 0 || len(listings[0].Data.Children) == 0 {
+		return nil, fmt.Errorf("post not found")
+	}
+
+	post := listings[0].Data.Children[0].Data
+	dashURL, duration := post.dashURL()
+	if dashURL == "" {
+		return nil, fmt.Errorf("post has no Reddit-hosted video")
+	}
+
+	thumbnail := ""
+	if strings.HasPrefix(post.Thumbnail, "http") {
+		thumbnail = post.Thumbnail
+	}
+	return e.extractManifest(dashURL, post.ID, post.Title, post.Author, duration, thumbnail)
+}
+
+// extractManifest lists the video representations of a DASH manifest, each
+// paired with the best audio representation for merging
+func (e *RedditExtractor) extractManifest(manifestURL, id, title, author string, duration int, thumbnail string) (Media, error) {
+	req, err := http.NewRequest("GET", manifestURL, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", redditUserAgent)
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch manifest: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("manifest returned status %d", resp.StatusCode)
+	}
+
+	videos, audios, mpdDuration, drm, err := parseMPD(io.LimitReader(resp.Body, maxManifestSize), resp.Request.URL.String())
+	if err != nil {
+		return nil, err
+	}
+	if len(videos) == 0 {
+		return nil, fmt.Errorf("no video streams in manifest")
+	}
+	if duration == 0 {
+		duration = mpdDuration
+	}
+
+	return &VideoMedia{
+		ID:        id,
+		Title:     title,
+		Uploader:  author,
+		Duration:  duration,
+		Thumbnail: thumbnail,
+		Formats:   dashVideoFormats(videos, audios),
+		DRM:       drm,
+	}, nil
+}
+
+func init() {
+	Register(&RedditExtractor{},
+		"reddit.com",
+		"old.reddit.com",
+		"new.reddit.com",
+		"v.redd.it",
+	)
+}
@@ -4,23 +4,36 @@ import (
 	"fmt"
 	"net/url"
 	"path"
+	"sort"
 	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
 )
 
 // extractorsByHost maps hostnames to their extractors
 var extractorsByHost = map[string]Extractor{}
 
+// providers supply extractors that are not registered by host (e.g. WASM plugins)
+var providers []Provider
+
 // fallbackExtractor handles direct file URLs and unknown hosts
 var fallbackExtractor Extractor
 
 // m3u8Extractor handles m3u8 URLs specifically (no HEAD request validation)
 var m3u8Extractor = &M3U8Extractor{}
 
+// dashExtractor handles MPEG-DASH manifest URLs
+var dashExtractor = &DASHExtractor{}
+
+// podcastExtractor handles podcast RSS and Atom feeds
+var podcastExtractor = &PodcastExtractor{}
+
 // directDownloadExtensions are file extensions that bypass host-based extractors
 var directDownloadExtensions = map[string]bool{
 	// Video
 	".mp4": true, ".webm": true, ".mov": true, ".avi": true, ".mkv": true,
 	".flv": true, ".m3u8": true, ".ts": true, ".m4v": true, ".wmv": true,
+	".mpd": true,
 	// Audio
 	".mp3": true, ".m4a": true, ".aac": true, ".ogg": true, ".wav": true,
 	".flac": true, ".wma": true,
@@ -75,6 +88,19 @@ func NormalizeURL(rawURL string) (string, error) {
 	return candidate, nil
 }
 
+// Provider supplies extractors discovered at runtime rather than registered by host
+type Provider interface {
+	// Match returns an extractor for rawURL, or nil
+	Match(rawURL string) Extractor
+	// Sites lists the sites the provider currently handles
+	Sites() []SiteInfo
+}
+
+// RegisterProvider adds a provider consulted after the built-in extractors
+func RegisterProvider(p Provider) {
+	providers = append(providers, p)
+}
+
 // RegisterFallback sets the fallback extractor for direct files and unknown hosts
 func RegisterFallback(e Extractor) {
 	fallbackExtractor = e
@@ -101,9 +127,17 @@ func Match(rawURL string) Extractor {
 		if ext == ".m3u8" || ext == ".m3u" {
 			return m3u8Extractor
 		}
+		if ext == ".mpd" {
+			return dashExtractor
+		}
 		return fallbackExtractor
 	}
 
+	// Podcast feeds (e.g. /podcast.rss, /feed/podcast.xml)
+	if ext == ".rss" || (ext == ".xml" && isFeedPath(u.Path)) {
+		return podcastExtractor
+	}
+
 	// Lookup by hostname
 	host := strings.ToLower(u.Hostname())
 
@@ -128,6 +162,12 @@ func Match(rawURL string) Extractor {
 	return nil
 }
 
+// isFeedPath reports whether an .xml path looks like an RSS or Atom feed
+func isFeedPath(p string) bool {
+	p = strings.ToLower(p)
+	return strings.Contains(p, "rss") || strings.Contains(p, "feed") || strings.Contains(p, "podcast")
+}
+
 // List returns all unique registered extractors
 func List() []Extractor {
 	seen := make(map[string]bool)
@@ -140,3 +180,93 @@ func List() []Extractor {
 	}
 	return result
 }
+
+// Find returns a built-in, provider, or yt-dlp fallback extractor for a URL, or nil
+func Find(rawURL string) Extractor {
+	if e := findRegistered(rawURL); e != nil {
+		return e
+	}
+	return ytdlpFallback()
+}
+
+// findRegistered tries the built-in extractors, then providers
+func findRegistered(rawURL string) Extractor {
+	if e := Match(rawURL); e != nil {
+		return e
+	}
+	for _, p := range providers {
+		if e := p.Match(rawURL); e != nil {
+			return e
+		}
+	}
+	return nil
+}
+
+// Resolve returns the extractor for a URL, trying in order: built-in extractors,
+// providers (plugins), sites.yml, the yt-dlp fallback when enabled, and finally
+// generic browser detection. It never returns nil.
+func Resolve(rawURL string, ctx *ExtractContext) Extractor {
+	if ctx == nil {
+		ctx = NewExtractContext()
+	}
+
+	if e := findRegistered(rawURL); e != nil {
+		return e
+	}
+
+	// sites.yml is read fresh so edits apply to the next download
+	sitesConfig, _ := config.LoadSites()
+	if site := sitesConfig.MatchSite(rawURL); site != nil {
+		return NewBrowserExtractor(site, ctx.Visible)
+	}
+	if e := ytdlpFallback(); e != nil {
+		return e
+	}
+	return NewGenericBrowserExtractor(ctx.Visible)
+}
+
+// SiteInfo describes an extractor and the hosts it covers
+type SiteInfo struct {
+	Name   string   `json:"name"`
+	Hosts  []string `json:"hosts"`
+	Source string   `json:"source"` // builtin, plugin, sites.yml, or yt-dlp
+}
+
+// Site sources reported in SiteInfo
+const (
+	SourceBuiltin = "builtin"
+	SourcePlugin  = "plugin"
+	SourceSites   = "sites.yml"
+	SourceYtdlp   = "yt-dlp"
+)
+
+// Sites lists every supported site: built-in extractors sorted by name, then
+// provider sites, then sites.yml entries, then the yt-dlp fallback if enabled
+func Sites() []SiteInfo {
+	hostsByName := make(map[string][]string)
+	for host, e := range extractorsByHost {
+		hostsByName[e.Name()] = append(hostsByName[e.Name()], host)
+	}
+
+	var result []SiteInfo
+	for name, hosts := range hostsByName {
+		sort.Strings(hosts)
+		result = append(result, SiteInfo{Name: name, Hosts: hosts, Source: SourceBuiltin})
+	}
+	sort.Slice(result, func(i, j int) bool { return result[i].Name < result[j].Name })
+
+	for _, p := range providers {
+		result = append(result, p.Sites()...)
+	}
+
+	if sitesConfig, _ := config.LoadSites(); sitesConfig != nil {
+		for _, site := range sitesConfig.Sites {
+			result = append(result, SiteInfo{Name: site.Type, Hosts: []string{site.Match}, Source: SourceSites})
+		}
+	}
+
+	if ytdlpFallback() != nil {
+		result = append(result, SiteInfo{Name: "yt-dlp", Hosts: []string{"*"}, Source: SourceYtdlp})
+	}
+	return result
+}
@@ -0,0 +1,100 @@
+package extractor
+
+import (
+	"net/http"
+	"net/url"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// maxShortURLHops bounds how many redirects are followed when resolving a short URL
+const maxShortURLHops = 10
+
+// shortURLHosts are link shorteners resolved before matching an extractor
+var shortURLHosts = map[string]bool{
+	"b23.tv":        true,
+	"t.co":          true,
+	"fb.watch":      true,
+	"v.douyin.com":  true,
+	"xhslink.com":   true,
+	"vm.tiktok.com": true,
+	"vt.tiktok.com": true,
+}
+
+// IsShortURL reports whether the URL is on a known link shortener
+func IsShortURL(rawURL string) bool {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return false
+	}
+	return shortURLHosts[strings.TrimPrefix(strings.ToLower(u.Hostname()), "www.")]
+}
+
+// ResolveShortURL follows known shorteners to their canonical destination so the
+// right extractor matches. It returns the final URL and the redirect chain
+// (starting with rawURL), or rawURL and a nil chain if it isn't a short URL or
+// can't be resolved.
+func ResolveShortURL(rawURL string) (string, []string) {
+	if !IsShortURL(rawURL) {
+		return rawURL, nil
+	}
+
+	client := &http.Client{
+		Timeout: 15 * time.Second,
+		Transport: &http.Transport{
+			Proxy: config.LoadOrDefault().Network.ProxyFunc(),
+		},
+		CheckRedirect: func(req *http.Request, via []*http.Request) error {
+			return http.ErrUseLastResponse // Follow hop by hop to record the chain
+		},
+	}
+
+	chain := []string{rawURL}
+	current := rawURL
+	for hop := 0; hop < maxShortURLHops && IsShortURL(current); hop++ {
+		next := nextRedirect(client, current)
+		if next == "" {
+			break
+		}
+		chain = append(chain, next)
+		current = next
+	}
+
+	if len(chain) == 1 {
+		return rawURL, nil
+	}
+	return current, chain
+}
+
+// nextRedirect returns the Location a URL redirects to, or "" if it doesn't
+func nextRedirect(client *http.Client, rawURL string) string {
+	for _, method := range []string{"HEAD", "GET"} {
+		req, err := http.NewRequest(method, rawURL, nil)
+		if err != nil {
+			return ""
+		}
+		// A non-browser agent gets a plain redirect instead of an HTML interstitial (t.co)
+		req.Header.Set("User-Agent", "curl/8.0")
+
+		resp, err := client.Do(req)
+		if err != nil {
+			return ""
+		}
+		resp.Body.Close()
+
+		if resp.StatusCode >= 300 && resp.StatusCode < 400 {
+			loc, err := resp.Location()
+			if err != nil {
+				return ""
+			}
+			return loc.String()
+		}
+		// Some shorteners reject HEAD; retry with GET
+		if resp.StatusCode != http.StatusMethodNotAllowed && resp.StatusCode != http.StatusNotFound {
+			return ""
+		}
+	}
+	return ""
+}
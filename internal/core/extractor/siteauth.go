@@ -0,0 +1,81 @@
+package extractor
+
+import (
+	"net/http"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// AuthSites are the site identifiers SiteHeaders knows the login of
+var AuthSites = auth.KnownSites
+
+// SiteHeaders returns the headers a download from site needs, taken from the
+// stored login: the Referer and User-Agent its CDN checks plus the saved
+// cookies. site is one of AuthSites or a domain, which gets a Referer and its
+// stored login, or else its network.cookies entry. network.user_agent
+// replaces the default user agent, and the user agent stored with the login
+// replaces both.
+func SiteHeaders(site string) (map[string]string, error) {
+	id, err := auth.SiteID(site)
+	if err != nil {
+		return nil, err
+	}
+	login, err := auth.Get(id)
+	if err != nil {
+		return nil, err
+	}
+	cfg := config.LoadOrDefault()
+	headers := map[string]string{"Cookie": login.CookieHeader()}
+	userAgent := (&BilibiliExtractor{}).userAgent()
+
+	switch id {
+	case auth.Bilibili:
+		headers["Referer"] = "https://www.bilibili.com/"
+	case auth.Xiaohongshu:
+		headers["Referer"] = "https://www.xiaohongshu.com/"
+	case auth.YouTube:
+		headers["Referer"] = "https://www.youtube.com/"
+	case auth.Twitter:
+		headers["Referer"] = "https://x.com/"
+		if token := login.Tokens["auth_token"]; token != "" && login.Cookie("auth_token") == "" {
+			headers["Cookie"] = strings.TrimPrefix(headers["Cookie"]+"; auth_token="+token, "; ")
+		}
+	default:
+		headers["Referer"] = "https://" + id + "/"
+		if headers["Cookie"] == "" {
+			headers["Cookie"] = (&ExtractContext{Cookies: cfg.Network.Cookies}).Cookie(id)
+		}
+	}
+
+	if cfg.Network.UserAgent != "" {
+		userAgent = cfg.Network.UserAgent
+	}
+	if login.UserAgent != "" {
+		userAgent = login.UserAgent
+	}
+	headers["User-Agent"] = userAgent
+	if headers["Cookie"] == "" {
+		delete(headers, "Cookie")
+	}
+	return headers, nil
+}
+
+// LayerHeaders merges header maps, later ones overriding earlier ones
+// regardless of name case. It returns nil when every map is empty.
+func LayerHeaders(layers ...map[string]string) map[string]string {
+	var merged map[string]string
+	for _, layer := range layers {
+		if len(layer) == 0 {
+			continue
+		}
+		if merged == nil {
+			merged = make(map[string]string)
+		}
+		for name, value := range layer {
+			merged[http.CanonicalHeaderKey(name)] = value
+		}
+	}
+	return merged
+}
@@ -0,0 +1,348 @@
+package extractor
+
+import (
+	"encoding/json"
+	"fmt"
+	"html"
+	"io"
+	"net/http"
+	"net/url"
+	"path"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// maxPageSize caps how much HTML is read when sniffing a page
+const maxPageSize = 5 * 1024 * 1024
+
+var (
+	sniffMetaRegex   = regexp.MustCompile(`(?is)<meta\s[^>]*>`)
+	sniffTagRegex    = regexp.MustCompile(`(?is)<(?:video|source)\s[^>]*>`)
+	sniffAttrRegex   = regexp.MustCompile(`(?is)([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')`)
+	sniffTitleRegex  = regexp.MustCompile(`(?is)<title[^>]*>(.*?)</title>`)
+	sniffLDJSONRegex = regexp.MustCompile(`(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>`)
+)
+
+// sniffMediaExts are URL extensions accepted as media when sniffing a page
+var sniffMediaExts = map[string]MediaType{
+	"mp4": MediaTypeVideo, "webm": MediaTypeVideo, "mov": MediaTypeVideo, "m4v": MediaTypeVideo,
+	"mkv": MediaTypeVideo, "flv": MediaTypeVideo, "ts": MediaTypeVideo, "m3u8": MediaTypeVideo,
+	"mpd": MediaTypeVideo,
+	"mp3": MediaTypeAudio, "m4a": MediaTypeAudio, "aac": MediaTypeAudio, "ogg": MediaTypeAudio,
+	"opus": MediaTypeAudio, "wav": MediaTypeAudio, "flac": MediaTypeAudio,
+}
+
+// sniffedPage is the media information found in a page's HTML
+type sniffedPage struct {
+	Title     string
+	Thumbnail string
+	Duration  int
+	Width     int
+	Height    int
+	Streams   []sniffedStream // in order of preference
+}
+
+// sniffedStream is a media URL found in a page
+type sniffedStream struct {
+	URL string
+	Ext string
+}
+
+// sniffPage fetches a page over HTTP and builds a best-effort Media from
+// JSON-LD VideoObject entries, og:video meta tags, and <video>/<source> tags
+func sniffPage(client *http.Client, rawURL string) (Media, error) {
+	req, err := http.NewRequest("GET", rawURL, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+	req.Header.Set("Accept", "text/html,application/xhtml+xml")
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch page: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("server returned status %d", resp.StatusCode)
+	}
+	if ct := resp.Header.Get("Content-Type"); ct != "" && !strings.Contains(ct, "html") {
+		// Feed URLs without a recognizable path are common for podcasts
+		if strings.Contains(ct, "rss") || strings.Contains(ct, "atom") || strings.Contains(ct, "xml") {
+			return (&PodcastExtractor{client: client}).extract(rawURL)
+		}
+		return nil, fmt.Errorf("not an HTML page: %s", ct)
+	}
+
+	body, err := io.ReadAll(io.LimitReader(resp.Body, maxPageSize))
+	if err != nil {
+		return nil, fmt.Errorf("failed to read page: %w", err)
+	}
+
+	pageURL := resp.Request.URL
+	page := parseSniffedPage(string(body), pageURL)
+	if len(page.Streams) == 0 {
+		return nil, fmt.Errorf("no media found in page")
+	}
+
+	// A DASH manifest needs its representations listed
+	if page.Streams[0].Ext == "mpd" {
+		media, err := (&DASHExtractor{client: client}).extract(page.Streams[0].URL)
+		if err != nil {
+			return nil, err
+		}
+		if v, ok := media.(*VideoMedia); ok && page.Title != "" {
+			v.Title = page.Title
+			v.Thumbnail = page.Thumbnail
+		}
+		return media, nil
+	}
+	return page.toMedia(pageURL), nil
+}
+
+// parseSniffedPage collects media URLs and metadata from page HTML
+func parseSniffedPage(body string, pageURL *url.URL) *sniffedPage {
+	page := &sniffedPage{}
+	seen := make(map[string]bool)
+	add := func(raw, mimeType string) {
+		u := resolveSniffedURL(pageURL, raw)
+		if u == "" || seen[u] {
+			return
+		}
+		ext := sniffedExt(u, mimeType)
+		if ext == "" {
+			return
+		}
+		seen[u] = true
+		page.Streams = append(page.Streams, sniffedStream{URL: u, Ext: ext})
+	}
+
+	// JSON-LD VideoObject entries are the most reliable
+	for _, m := range sniffLDJSONRegex.FindAllStringSubmatch(body, -1) {
+		var data any
+		if err := json.Unmarshal([]byte(strings.TrimSpace(m[1])), &data); err != nil {
+			continue
+		}
+		for _, obj := range findVideoObjects(data) {
+			if s, ok := obj["contentUrl"].(string); ok {
+				add(s, "")
+			}
+			if page.Title == "" {
+				page.Title, _ = obj["name"].(string)
+			}
+			if page.Thumbnail == "" {
+				page.Thumbnail = jsonLDString(obj["thumbnailUrl"])
+			}
+			if page.Duration == 0 {
+				if s, ok := obj["duration"].(string); ok {
+					page.Duration = parseISODuration(s)
+				}
+			}
+		}
+	}
+
+	// Open Graph and Twitter card meta tags
+	var ogTitle string
+	for _, tag := range sniffMetaRegex.FindAllString(body, -1) {
+		attrs := parseSniffAttrs(tag)
+		key := attrs["property"]
+		if key == "" {
+			key = attrs["name"]
+		}
+		content := attrs["content"]
+		switch strings.ToLower(key) {
+		case "og:video", "og:video:url", "og:video:secure_url", "twitter:player:stream":
+			add(content, attrs["type"])
+		case "og:video:width":
+			page.Width, _ = strconv.Atoi(content)
+		case "og:video:height":
+			page.Height, _ = strconv.Atoi(content)
+		case "og:title":
+			ogTitle = content
+		case "og:image":
+			if page.Thumbnail == "" {
+				page.Thumbnail = resolveSniffedURL(pageURL, content)
+			}
+		}
+	}
+
+	// <video src> and <source src type>
+	for _, tag := range sniffTagRegex.FindAllString(body, -1) {
+		attrs := parseSniffAttrs(tag)
+		add(attrs["src"], attrs["type"])
+	}
+
+	if page.Title == "" {
+		page.Title = ogTitle
+	}
+	if page.Title == "" {
+		if m := sniffTitleRegex.FindStringSubmatch(body); m != nil {
+			page.Title = m[1]
+		}
+	}
+	page.Title = strings.TrimSpace(html.UnescapeString(page.Title))
+	return page
+}
+
+// toMedia builds the Media for the sniffed URLs, using the page as Referer
+func (p *sniffedPage) toMedia(pageURL *url.URL) Media {
+	headers := map[string]string{
+		"Referer": pageURL.String(),
+		"Origin":  pageURL.Scheme + "://" + pageURL.Host,
+	}
+
+	title := p.Title
+	if title == "" {
+		title = pageURL.Host
+	}
+	id := generateID(pageURL.String())
+
+	var formats []VideoFormat
+	var audioURL, audioExt string
+	for _, s := range p.Streams {
+		switch {
+		case s.Ext == "mpd":
+			continue
+		case sniffMediaExts[s.Ext] == MediaTypeAudio:
+			if audioURL == "" {
+				audioURL, audioExt = s.URL, s.Ext
+			}
+			continue
+		}
+		f := VideoFormat{URL: s.URL, Ext: s.Ext, Headers: headers}
+		if len(formats) == 0 {
+			// Dimensions from og:video apply to the preferred URL
+			f.Width, f.Height = p.Width, p.Height
+		}
+		formats = append(formats, f)
+	}
+
+	if len(formats) == 0 {
+		return &AudioMedia{
+			ID:       id,
+			Title:    title,
+			Duration: p.Duration,
+			URL:      audioURL,
+			Ext:      audioExt,
+		}
+	}
+	return &VideoMedia{
+		ID:        id,
+		Title:     title,
+		Duration:  p.Duration,
+		Thumbnail: p.Thumbnail,
+		Formats:   formats,
+	}
+}
+
+// findVideoObjects walks JSON-LD data (including @graph arrays) for VideoObject entries
+func findVideoObjects(data any) []map[string]any {
+	var result []map[string]any
+	switch v := data.(type) {
+	case []any:
+		for _, item := range v {
+			result = append(result, findVideoObjects(item)...)
+		}
+	case map[string]any:
+		isVideo := false
+		switch t := v["@type"].(type) {
+		case string:
+			isVideo = t == "VideoObject"
+		case []any:
+			for _, item := range t {
+				if s, _ := item.(string); s == "VideoObject" {
+					isVideo = true
+				}
+			}
+		}
+		if isVideo {
+			result = append(result, v)
+		}
+		for key, child := range v {
+			if key == "@graph" || key == "video" || key == "mainEntity" {
+				result = append(result, findVideoObjects(child)...)
+			}
+		}
+	}
+	return result
+}
+
+// jsonLDString returns a string value, or the first string of an array
+func jsonLDString(v any) string {
+	switch t := v.(type) {
+	case string:
+		return t
+	case []any:
+		if len(t) > 0 {
+			s, _ := t[0].(string)
+			return s
+		}
+	}
+	return ""
+}
+
+// parseSniffAttrs returns the lowercased attribute names and unescaped values of a tag
+func parseSniffAttrs(tag string) map[string]string {
+	attrs := make(map[string]string)
+	for _, m := range sniffAttrRegex.FindAllStringSubmatch(tag, -1) {
+		value := m[2]
+		if value == "" {
+			value = m[3]
+		}
+		attrs[strings.ToLower(m[1])] = html.UnescapeString(value)
+	}
+	return attrs
+}
+
+// resolveSniffedURL resolves a page-relative URL, ignoring blob: and data: URLs
+func resolveSniffedURL(pageURL *url.URL, raw string) string {
+	raw = strings.TrimSpace(raw)
+	if raw == "" {
+		return ""
+	}
+	ref, err := url.Parse(raw)
+	if err != nil {
+		return ""
+	}
+	u := pageURL.ResolveReference(ref)
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return ""
+	}
+	return u.String()
+}
+
+// sniffedExt returns the media extension for a URL (or its declared MIME type),
+// or "" if it doesn't look like media (e.g. an embedded player page)
+func sniffedExt(rawURL, mimeType string) string {
+	switch strings.ToLower(mimeType) {
+	case "application/x-mpegurl", "application/vnd.apple.mpegurl":
+		return "m3u8"
+	case "application/dash+xml":
+		return "mpd"
+	}
+
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return ""
+	}
+	ext := strings.ToLower(strings.TrimPrefix(path.Ext(u.Path), "."))
+	if _, ok := sniffMediaExts[ext]; ok {
+		return ext
+	}
+
+	// Extensionless URLs declared as video/audio by a <source type>
+	mimeType = strings.ToLower(mimeType)
+	switch {
+	case strings.HasPrefix(mimeType, "video/webm"):
+		return "webm"
+	case strings.HasPrefix(mimeType, "video/"):
+		return "mp4"
+	case strings.HasPrefix(mimeType, "audio/mpeg"):
+		return "mp3"
+	case strings.HasPrefix(mimeType, "audio/"):
+		return "m4a"
+	}
+	return ""
+}
@@ -0,0 +1,95 @@
+package extractor
+
+import (
+	"net/url"
+	"testing"
+)
+
+func TestParseSniffedPage(t *testing.T) {
+	pageURL, _ := url.Parse("https://example.com/watch/episode-1")
+
+	tests := []struct {
+		name      string
+		body      string
+		title     string
+		thumbnail string
+		duration  int
+		streams   []sniffedStream
+	}{
+		{
+			name: "og:video",
+			body: `<html><head>
+<title>Fallback title</title>
+<meta property="og:title" content="Episode &amp; More">
+<meta property="og:image" content="/images/cover.jpg">
+<meta property="og:video" content="https://cdn.example.com/v/1.mp4">
+<meta property="og:video:secure_url" content="https://cdn.example.com/v/1.mp4">
+<meta property="og:video:width" content="1280">
+<meta name="twitter:player:stream" content="https://example.com/embed/1">
+</head></html>`,
+			title:     "Episode & More",
+			thumbnail: "https://example.com/images/cover.jpg",
+			streams:   []sniffedStream{{URL: "https://cdn.example.com/v/1.mp4", Ext: "mp4"}},
+		},
+		{
+			name: "video and source tags",
+			body: `<title> Clip </title>
+<video src="blob:https://example.com/1234"></video>
+<video controls>
+  <source src='media/clip.webm' type="video/webm">
+  <source src="/stream" type="application/x-mpegURL">
+  <source src="/download?id=7" type="video/mp4">
+</video>`,
+			title: "Clip",
+			streams: []sniffedStream{
+				{URL: "https://example.com/watch/media/clip.webm", Ext: "webm"},
+				{URL: "https://example.com/stream", Ext: "m3u8"},
+				{URL: "https://example.com/download?id=7", Ext: "mp4"},
+			},
+		},
+		{
+			name: "JSON-LD",
+			body: `<script type="application/ld+json">
+{"@context": "https://schema.org", "@graph": [
+  {"@type": "WebPage", "name": "Page"},
+  {"@type": "VideoObject", "name": "Talk", "contentUrl": "../files/talk.m4v",
+   "thumbnailUrl": ["https://example.com/t.jpg"], "duration": "PT1M30S"}
+]}
+</script>
+<meta property="og:title" content="Not this title">
+<meta property="og:video" content="https://example.com/files/talk.m4v">`,
+			title:     "Talk",
+			thumbnail: "https://example.com/t.jpg",
+			duration:  90,
+			streams:   []sniffedStream{{URL: "https://example.com/files/talk.m4v", Ext: "m4v"}},
+		},
+		{
+			name:  "no media",
+			body:  `<title>Article</title><meta property="og:video" content="https://example.com/player.html">`,
+			title: "Article",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			page := parseSniffedPage(tt.body, pageURL)
+			if page.Title != tt.title {
+				t.Errorf("Title = %q, want %q", page.Title, tt.title)
+			}
+			if page.Thumbnail != tt.thumbnail {
+				t.Errorf("Thumbnail = %q, want %q", page.Thumbnail, tt.thumbnail)
+			}
+			if page.Duration != tt.duration {
+				t.Errorf("Duration = %d, want %d", page.Duration, tt.duration)
+			}
+			if len(page.Streams) != len(tt.streams) {
+				t.Fatalf("Streams = %+v, want %+v", page.Streams, tt.streams)
+			}
+			for i, want := range tt.streams {
+				if page.Streams[i] != want {
+					t.Errorf("Streams[%d] = %+v, want %+v", i, page.Streams[i], want)
+				}
+			}
+		})
+	}
+}
@@ -2,6 +2,7 @@ package extractor
 
 import (
 	"net/url"
+	"time"
 
 	"github.com/guiyumin/vget/internal/core/extractor/telegram"
 )
@@ -33,6 +34,11 @@ func (t *TelegramExtractor) Name() string {
 	return t.ext.Name()
 }
 
+// CacheTTL disables caching; media is tied to the live session
+func (t *TelegramExtractor) CacheTTL() time.Duration {
+	return 0
+}
+
 func (t *TelegramExtractor) Match(u *url.URL) bool {
 	return t.ext.Match(u)
 }
@@ -5,14 +5,14 @@ import (
 	"fmt"
 	"io"
 	"os"
-	"regexp"
-	"strings"
 	"time"
 
 	"github.com/gotd/td/session"
 	"github.com/gotd/td/telegram"
 	"github.com/gotd/td/telegram/downloader"
 	"github.com/gotd/td/tg"
+
+	"github.com/guiyumin/vget/internal/core/sanitize"
 )
 
 // DownloadResult contains the result of a Telegram download
@@ -33,8 +33,9 @@ type DownloadOptions struct {
 // Download downloads media from a Telegram URL directly.
 // This combines extraction and download because Telegram requires
 // the download to happen within the authenticated client context.
-func Download(urlStr string, outputPath string, progressFn func(downloaded, total int64)) (*DownloadResult, error) {
-	return DownloadWithOptions(DownloadOptions{
+// Cancelling ctx stops the download and removes the partial file.
+func Download(ctx context.Context, urlStr string, outputPath string, progressFn func(downloaded, total int64)) (*DownloadResult, error) {
+	return DownloadWithOptions(ctx, DownloadOptions{
 		URL:        urlStr,
 		OutputPath: outputPath,
 		ProgressFn: progressFn,
@@ -42,7 +43,7 @@ func Download(urlStr string, outputPath string, progressFn func(downloaded, tota
 }
 
 // DownloadWithOptions downloads media with configurable options including takeout mode
-func DownloadWithOptions(opts DownloadOptions) (*DownloadResult, error) {
+func DownloadWithOptions(ctx context.Context, opts DownloadOptions) (*DownloadResult, error) {
 	if !SessionExists() {
 		return nil, fmt.Errorf("not logged in to Telegram. Run 'vget telegram login' first")
 	}
@@ -52,7 +53,7 @@ func DownloadWithOptions(opts DownloadOptions) (*DownloadResult, error) {
 		return nil, err
 	}
 
-	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Minute)
+	ctx, cancel := context.WithTimeout(ctx, 30*time.Minute)
 	defer cancel()
 
 	storage := &session.FileStorage{Path: SessionFile()}
@@ -306,12 +307,13 @@ func downloadDocument(
 		if info.Filename != "" {
 			outFile = info.Filename
 		} else {
-			outFile = fmt.Sprintf("%s.%s", sanitizeFilename(info.Title), info.Ext)
+			outFile = fmt.Sprintf("%s.%s", sanitize.Filename(info.Title), info.Ext)
 		}
 	}
 
 	// Create output file
-	f, err := os.Create(outFile)
+	partFile := outFile + partSuffix
+	f, err := os.Create(partFile)
 	if err != nil {
 		return nil, fmt.Errorf("failed to create output file: %w", err)
 	}
@@ -329,9 +331,12 @@ func downloadDocument(
 		FileReference: doc.FileReference,
 	}).Stream(ctx, writer)
 	if err != nil {
-		os.Remove(outFile)
+		os.Remove(partFile)
 		return nil, fmt.Errorf("download failed: %w", err)
 	}
+	if err := commitFile(f, partFile, outFile); err != nil {
+		return nil, err
+	}
 
 	return &DownloadResult{
 		Title:    info.Title,
@@ -366,10 +371,11 @@ func downloadPhoto(
 
 	outFile := outputPath
 	if outFile == "" {
-		outFile = fmt.Sprintf("%s.jpg", sanitizeFilename(title))
+		outFile = fmt.Sprintf("%s.jpg", sanitize.Filename(title))
 	}
 
-	f, err := os.Create(outFile)
+	partFile := outFile + partSuffix
+	f, err := os.Create(partFile)
 	if err != nil {
 		return nil, fmt.Errorf("failed to create output file: %w", err)
 	}
@@ -382,9 +388,12 @@ func downloadPhoto(
 		ThumbSize:     largest.Type,
 	}).Stream(ctx, f)
 	if err != nil {
-		os.Remove(outFile)
+		os.Remove(partFile)
 		return nil, fmt.Errorf("download failed: %w", err)
 	}
+	if err := commitFile(f, partFile, outFile); err != nil {
+		return nil, err
+	}
 
 	return &DownloadResult{
 		Title:    title,
@@ -410,35 +419,17 @@ func (pw *progressWriter) Write(p []byte) (int, error) {
 	return n, err
 }
 
-func sanitizeFilename(name string) string {
-	replacer := strings.NewReplacer(
-		"/", "-",
-		"\\", "-",
-		":", "-",
-		"*", "",
-		"?", "",
-		"\"", "",
-		"<", "",
-		">", "",
-		"|", "",
-		"\n", " ",
-		"\r", "",
-	)
-	result := replacer.Replace(name)
-
-	urlRegex := regexp.MustCompile(`https?://[^\s]+`)
-	result = urlRegex.ReplaceAllString(result, "")
-
-	result = strings.TrimSpace(result)
-	result = strings.Trim(result, ".")
-
-	spaceRegex := regexp.MustCompile(`\s+`)
-	result = spaceRegex.ReplaceAllString(result, " ")
-
-	runes := []rune(result)
-	if len(runes) > 200 {
-		result = string(runes[:200])
-	}
+// partSuffix marks a download that hasn't finished yet
+const partSuffix = ".part"
 
-	return strings.TrimSpace(result)
+// commitFile closes a finished .part file and renames it to outFile
+func commitFile(f *os.File, partFile, outFile string) error {
+	if err := f.Close(); err != nil {
+		os.Remove(partFile)
+		return fmt.Errorf("failed to write file: %w", err)
+	}
+	if err := os.Rename(partFile, outFile); err != nil {
+		return fmt.Errorf("failed to move download into place: %w", err)
+	}
+	return nil
 }
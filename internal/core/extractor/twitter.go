@@ -2,6 +2,7 @@ package extractor
 
 import (
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
 	"net/http"
@@ -10,7 +11,8 @@ import (
 	"sort"
 	"strconv"
 	"strings"
-	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
 )
 
 const (
@@ -25,6 +27,10 @@ const (
 var (
 	// Matches twitter.com and x.com URLs with status
 	twitterURLRegex = regexp.MustCompile(`(?:twitter\.com|x\.com)/(?:[^/]+)/status/(\d+)`)
+
+	// errTwitterForbidden is an authenticated request refused, as when its
+	// ct0 doesn't belong to the session
+	errTwitterForbidden = errors.New("forbidden")
 )
 
 // Twitter-specific error types for i18n support
@@ -63,9 +69,31 @@ func (t *TwitterExtractor) Match(u *url.URL) bool {
 	return twitterURLRegex.MatchString(u.String())
 }
 
-// SetAuth sets authentication credentials for accessing restricted content
+// SetAuth sets authentication credentials for accessing restricted content,
+// replacing the stored login
 func (t *TwitterExtractor) SetAuth(authToken string) {
 	t.authToken = authToken
+	t.csrfToken = ""
+}
+
+// TwitterLoginOptions are the BrowserLoginOptions of Twitter: log in on x.com,
+// until it has set the auth_token cookie the extractor sends along with ct0
+func TwitterLoginOptions() BrowserLoginOptions {
+	return BrowserLoginOptions{
+		Site:     auth.Twitter,
+		URL:      "https://x.com/i/flow/login",
+		Domains:  []string{"x.com", "twitter.com"},
+		loggedIn: hasCookie("https://x.com/", "auth_token"),
+	}
+}
+
+// TwitterAuthToken returns the auth_token of a stored Twitter login: its
+// auth_token cookie, or else the token set as twitter.auth_token
+func TwitterAuthToken(login *auth.Login) string {
+	if token := login.Cookie("auth_token"); token != "" {
+		return token
+	}
+	return login.Tokens["auth_token"]
 }
 
 // IsAuthenticated returns true if auth credentials are set
@@ -75,16 +103,17 @@ func (t *TwitterExtractor) IsAuthenticated() bool {
 
 // Extract retrieves media from a Twitter/X URL
 func (t *TwitterExtractor) Extract(urlStr string) (Media, error) {
-	// Initialize HTTP client
-	if t.client == nil {
-		t.client = &http.Client{
-			Timeout: 30 * time.Second,
-			Transport: &http.Transport{
-				Proxy: http.ProxyFromEnvironment,
-			},
-		}
-	}
+	return t.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (t *TwitterExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	call := *t
+	call.client = ctx.HTTPClient()
+	return call.extract(urlStr)
+}
 
+func (t *TwitterExtractor) extract(urlStr string) (Media, error) {
 	// Extract tweet ID from URL
 	matches := twitterURLRegex.FindStringSubmatch(urlStr)
 	if len(matches) < 2 {
@@ -92,9 +121,23 @@ func (t *TwitterExtractor) Extract(urlStr string) (Media, error) {
 	}
 	tweetID := matches[1]
 
-	// If authenticated, use GraphQL API directly (supports NSFW content)
+	// Without credentials from SetAuth, use the stored login: its auth_token,
+	// and its ct0 when it was saved along
+	if !t.IsAuthenticated() {
+		if login, _ := auth.Get(auth.Twitter); !login.Expired() {
+			t.authToken, t.csrfToken = TwitterAuthToken(login), login.Cookie("ct0")
+		}
+	}
+
+	// If authenticated, use GraphQL API directly (supports NSFW and
+	// followers-only content)
 	if t.IsAuthenticated() {
 		media, err := t.fetchFromGraphQLAuth(tweetID)
+		if errors.Is(err, errTwitterForbidden) && t.csrfToken != "" {
+			// The stored ct0 may be stale; retry with a fresh one
+			t.csrfToken = ""
+			media, err = t.fetchFromGraphQLAuth(tweetID)
+		}
 		if err != nil {
 			return nil, fmt.Errorf("failed to fetch tweet: %w", err)
 		}
@@ -282,7 +325,8 @@ func (t *TwitterExtractor) fetchCsrfToken() error {
 	return fmt.Errorf("could not obtain CSRF token")
 }
 
-// fetchFromGraphQLAuth uses the GraphQL API with authentication (for NSFW content)
+// fetchFromGraphQLAuth uses the GraphQL API with authentication (for NSFW and
+// followers-only content)
 func (t *TwitterExtractor) fetchFromGraphQLAuth(tweetID string) (Media, error) {
 	// Fetch CSRF token if not already set
 	if t.csrfToken == "" {
@@ -354,6 +398,10 @@ func (t *TwitterExtractor) fetchFromGraphQLAuth(tweetID string) (Media, error) {
 	}
 	defer resp.Body.Close()
 
+	if resp.StatusCode == http.StatusForbidden {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, fmt.Errorf("GraphQL request %w: %s", errTwitterForbidden, string(body))
+	}
 	if resp.StatusCode != http.StatusOK {
 		body, _ := io.ReadAll(resp.Body)
 		return nil, fmt.Errorf("GraphQL request failed with status %d: %s", resp.StatusCode, string(body))
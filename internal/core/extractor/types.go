@@ -3,9 +3,10 @@ package extractor
 import (
 	"fmt"
 	"net/url"
-	"regexp"
 	"slices"
+	"sort"
 	"strings"
+	"time"
 )
 
 // MediaType represents the type of media being downloaded
@@ -38,14 +39,62 @@ type Extractor interface {
 	Extract(url string) (Media, error)
 }
 
+// Origin records how media was reached from the URL the user gave
+type Origin struct {
+	RedirectChain []string // input URL first, canonical URL last; empty if not redirected
+}
+
+func (o *Origin) origin() *Origin { return o }
+
+// SetRedirectChain records the short URL redirect chain on the media
+func SetRedirectChain(media Media, chain []string) {
+	if m, ok := media.(interface{ origin() *Origin }); ok {
+		m.origin().RedirectChain = chain
+	}
+}
+
+// RedirectChain returns the short URL redirect chain recorded on the media,
+// nil if it wasn't redirected
+func RedirectChain(media Media) []string {
+	if m, ok := media.(interface{ origin() *Origin }); ok {
+		return m.origin().RedirectChain
+	}
+	return nil
+}
+
 // VideoMedia represents video content with multiple format options
 type VideoMedia struct {
-	ID        string
-	Title     string
-	Uploader  string
-	Duration  int // seconds
-	Thumbnail string
-	Formats   []VideoFormat
+	ID          string
+	Title       string
+	Uploader    string
+	Description string
+	Published   time.Time // zero if unknown
+	Duration    int       // seconds
+	Thumbnail   string
+	Thumbnails  []Thumbnail // available sizes, when the site offers several
+	Formats     []VideoFormat
+	Subtitles   []Subtitle
+	Chapters    []Chapter
+	DRM         string           // DRM system protecting the streams (e.g. "Widevine"), empty if none
+	Locked      *LockedQualities // qualities withheld from this session, nil if none
+	Origin
+}
+
+// LockedQualities are qualities a video offers that the site withheld because
+// they need a login or a paid membership
+type LockedQualities struct {
+	Site       string   // auth site name, for the login hint
+	Qualities  []string // quality labels, best first
+	NeedsLogin bool     // false when logged in without the required membership
+}
+
+// Note explains which qualities are locked and how to unlock them
+func (l *LockedQualities) Note() string {
+	qualities := strings.Join(l.Qualities, ", ")
+	if l.NeedsLogin {
+		return fmt.Sprintf("%s need a login (vget login %s)", qualities, l.Site)
+	}
+	return fmt.Sprintf("%s need a premium membership on %s", qualities, l.Site)
 }
 
 func (v *VideoMedia) GetID() string       { return v.ID }
@@ -55,14 +104,26 @@ func (v *VideoMedia) Type() MediaType     { return MediaTypeVideo }
 
 // VideoFormat represents a single video quality option
 type VideoFormat struct {
-	URL     string
-	Quality string // "1080p", "720p", etc.
-	Ext     string // "mp4", "m3u8", "ts"
-	Width   int
-	Height  int
-	Bitrate int
-	Headers map[string]string // Custom headers for download (e.g., Referer)
-	AudioURL string // Separate audio stream URL (for adaptive formats that need merging)
+	URL          string
+	Quality      string // "1080p", "720p", etc.
+	Ext          string // "mp4", "m3u8", "ts"
+	Width        int
+	Height       int
+	Bitrate      int
+	Headers      map[string]string // Custom headers for download (e.g., Referer)
+	AudioURL     string            // Separate audio stream URL (for adaptive formats that need merging)
+	Mirrors      []string          // Fallback URLs serving the same stream as URL (e.g., Bilibili backupUrl)
+	AudioMirrors []string          // Fallback URLs for AudioURL
+}
+
+// URLs returns URL followed by its mirrors
+func (f *VideoFormat) URLs() []string {
+	return append([]string{f.URL}, f.Mirrors...)
+}
+
+// AudioURLs returns AudioURL followed by its mirrors
+func (f *VideoFormat) AudioURLs() []string {
+	return append([]string{f.AudioURL}, f.AudioMirrors...)
 }
 
 // QualityLabel returns a human-readable quality label
@@ -76,14 +137,75 @@ func (f *VideoFormat) QualityLabel() string {
 	return "unknown"
 }
 
+// Subtitle is a caption track that can be downloaded alongside a video
+type Subtitle struct {
+	Language string `json:"language"`       // language code, e.g. "en", "zh-Hans", "ai-zh"
+	Name     string `json:"name,omitempty"` // display name, e.g. "English (auto-generated)"
+	Ext      string `json:"ext"`            // "vtt", "srt", "json" (Bilibili), ...
+	URL      string `json:"url"`
+	Auto     bool   `json:"auto,omitempty"` // machine-generated captions
+}
+
+// Thumbnail is one size of a media item's thumbnail image
+type Thumbnail struct {
+	URL    string `json:"url"`
+	Width  int    `json:"width,omitempty"`
+	Height int    `json:"height,omitempty"`
+}
+
+// Thumbnails returns the thumbnails of a media item, largest first.
+// Media with only a single thumbnail URL returns it without dimensions.
+func Thumbnails(media Media) []Thumbnail {
+	var thumbs []Thumbnail
+	var single string
+	switch m := media.(type) {
+	case *VideoMedia:
+		thumbs, single = m.Thumbnails, m.Thumbnail
+	case *YouTubeDirectDownload:
+		thumbs = m.Thumbnails
+	case *AudioMedia:
+		single = m.Thumbnail
+	case *MultiVideoMedia:
+		if len(m.Videos) > 0 {
+			return Thumbnails(m.Videos[0])
+		}
+	case *PlaylistMedia:
+		single = m.Thumbnail
+	}
+
+	if len(thumbs) == 0 {
+		if single == "" {
+			return nil
+		}
+		return []Thumbnail{{URL: single}}
+	}
+	result := slices.Clone(thumbs)
+	sort.SliceStable(result, func(i, j int) bool {
+		return result[i].Width*result[i].Height > result[j].Width*result[j].Height
+	})
+	return result
+}
+
+// Chapter is a named section of a video
+type Chapter struct {
+	Title string  `json:"title"`
+	Start float64 `json:"start"` // seconds
+	End   float64 `json:"end"`   // seconds
+}
+
 // AudioMedia represents audio content (podcasts, music)
 type AudioMedia struct {
-	ID       string
-	Title    string
-	Uploader string
-	Duration int // seconds
-	URL      string
-	Ext      string // "mp3", "m4a", etc.
+	ID          string
+	Title       string
+	Uploader    string
+	Description string
+	Published   time.Time // zero if unknown
+	Duration    int       // seconds
+	Thumbnail   string    // cover art, embedded into the download
+	URL         string
+	Ext         string // "mp3", "m4a", etc.
+	DRM         string // DRM system protecting the stream, empty if none
+	Origin
 }
 
 func (a *AudioMedia) GetID() string       { return a.ID }
@@ -97,6 +219,7 @@ type ImageMedia struct {
 	Title    string
 	Uploader string
 	Images   []Image
+	Origin
 }
 
 func (i *ImageMedia) GetID() string       { return i.ID }
@@ -110,6 +233,7 @@ type MultiVideoMedia struct {
 	Title    string
 	Uploader string
 	Videos   []*VideoMedia
+	Origin
 }
 
 func (m *MultiVideoMedia) GetID() string       { return m.ID }
@@ -117,93 +241,69 @@ func (m *MultiVideoMedia) GetTitle() string    { return m.Title }
 func (m *MultiVideoMedia) GetUploader() string { return m.Uploader }
 func (m *MultiVideoMedia) Type() MediaType     { return MediaTypeVideo }
 
-// Image represents a single image to download
-type Image struct {
-	URL    string
-	Ext    string // "jpg", "png", "webp"
-	Width  int
-	Height int
+// PlaylistMedia represents a list of items from one source (podcast feeds, playlists, channels)
+type PlaylistMedia struct {
+	ID        string
+	Title     string
+	Uploader  string
+	Thumbnail string
+	Entries   []PlaylistEntry
+	Numbered  bool   // entry titles already start with their position
+	SingleURL string // for a collection expanded from one of its videos, the URL of just that video
+	Origin
 }
 
-// SanitizeFilename removes or replaces characters that are invalid in filenames
-func SanitizeFilename(name string) string {
-	// Remove URLs first (before character replacement mangles them)
-	urlRegex := regexp.MustCompile(`https?://[^\s]+`)
-	result := urlRegex.ReplaceAllString(name, "")
-
-	// Replace characters that are problematic in filenames
-	// Includes both ASCII and full-width (CJK) versions of reserved characters
-	replacer := strings.NewReplacer(
-		// ASCII versions
-		"/", "-",
-		"\\", "-",
-		":", "-",
-		"*", "",
-		"?", "",
-		"\"", "",
-		"<", "",
-		">", "",
-		"|", "",
-		"\n", " ",
-		"\r", "",
-		"\t", " ",
-		// Full-width versions (common in Chinese/Japanese text)
-		"：", "-", // U+FF1A Full-width colon
-		"／", "-", // U+FF0F Full-width solidus
-		"＼", "-", // U+FF3C Full-width reverse solidus
-		"。", "-", // U+3002 CJK full stop
-		"＊", "",  // U+FF0A Full-width asterisk
-		"？", "",  // U+FF1F Full-width question mark
-		"＂", "",  // U+FF02 Full-width quotation mark
-		"＜", "",  // U+FF1C Full-width less-than
-		"＞", "",  // U+FF1E Full-width greater-than
-		"｜", "",  // U+FF5C Full-width vertical line
-		// Additional problematic characters
-		"「", "",  // CJK left corner bracket
-		"」", "",  // CJK right corner bracket
-		"【", "",  // CJK left black lenticular bracket
-		"】", "",  // CJK right black lenticular bracket
-	)
-	result = replacer.Replace(result)
-
-	// Remove control characters (0x00-0x1F, 0x7F) which are invalid on Windows
-	result = strings.Map(func(r rune) rune {
-		if r < 32 || r == 127 {
-			return -1 // -1 means delete the rune
-		}
-		return r
-	}, result)
-
-	// Trim spaces and dots from ends
-	result = strings.TrimSpace(result)
-	result = strings.Trim(result, ".")
-
-	// Collapse multiple spaces
-	spaceRegex := regexp.MustCompile(`\s+`)
-	result = spaceRegex.ReplaceAllString(result, " ")
-
-	// Limit length to avoid "file name too long" errors
-	// Most filesystems limit filenames to 255 bytes. For UTF-8 with CJK characters
-	// (3-4 bytes each), 60 runes is safe (~180-240 bytes), leaving room for extension.
-	const maxRunes = 60
-	runes := []rune(result)
-	if len(runes) > maxRunes {
-		result = string(runes[:maxRunes])
-	}
+func (p *PlaylistMedia) GetID() string       { return p.ID }
+func (p *PlaylistMedia) GetTitle() string    { return p.Title }
+func (p *PlaylistMedia) GetUploader() string { return p.Uploader }
 
-	// If result is empty after sanitization, return empty
-	result = strings.TrimSpace(result)
+// Reversed returns a copy of the playlist with the entry order flipped, e.g.
+// to take a newest-first podcast feed oldest first
+func (p *PlaylistMedia) Reversed() *PlaylistMedia {
+	reversed := *p
+	reversed.Entries = slices.Clone(p.Entries)
+	slices.Reverse(reversed.Entries)
+	return &reversed
+}
 
-	// Handle Windows reserved names (CON, PRN, AUX, NUL, COM1-9, LPT1-9)
-	// These names (with or without extension) cannot be used as filenames on Windows
-	reservedNames := []string{
-		"CON", "PRN", "AUX", "NUL",
-		"COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
-		"LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+// IndexedTitle returns the title of entry i prefixed with its zero-padded
+// one-based position ("07 Title"), padded to the playlist length so files
+// sort in playlist order. Titles the extractor numbered are kept as is.
+func (p *PlaylistMedia) IndexedTitle(i int) string {
+	title := p.Entries[i].Title
+	if p.Numbered {
+		return title
 	}
-	if slices.Contains(reservedNames, strings.ToUpper(result)) {
-		result = "_" + result
+	width := len(fmt.Sprint(len(p.Entries)))
+	if title == "" {
+		return fmt.Sprintf("%0*d", width, i+1)
 	}
+	return fmt.Sprintf("%0*d %s", width, i+1, title)
+}
 
-	return result
+// Type returns the type of the resolved entries (audio for podcasts), video otherwise
+func (p *PlaylistMedia) Type() MediaType {
+	if len(p.Entries) > 0 && p.Entries[0].Media != nil {
+		return p.Entries[0].Media.Type()
+	}
+	return MediaTypeVideo
+}
+
+// PlaylistEntry is a single playlist item. Media is set when the entry is
+// already resolved (e.g. podcast episodes); otherwise URL is extracted on download.
+type PlaylistEntry struct {
+	Title     string
+	URL       string
+	Published time.Time
+	Thumbnail string
+	Duration  int // seconds
+	Media     Media
+}
+
+// Image represents a single image to download
+type Image struct {
+	URL    string
+	Ext    string // "jpg", "png", "webp"
+	Width  int
+	Height int
 }
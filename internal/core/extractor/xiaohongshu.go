@@ -3,6 +3,8 @@ package extractor
 import (
 	"encoding/json"
 	"fmt"
+	"io"
+	"net/http"
 	"net/url"
 	"os"
 	"path/filepath"
@@ -14,12 +16,14 @@ import (
 	"github.com/go-rod/rod/lib/launcher"
 	"github.com/go-rod/rod/lib/proto"
 	"github.com/go-rod/stealth"
+	"github.com/guiyumin/vget/internal/core/auth"
 	"github.com/guiyumin/vget/internal/core/config"
 )
 
 // XiaohongshuExtractor handles Xiaohongshu video/image downloads using browser automation
 type XiaohongshuExtractor struct {
 	visible bool
+	client  *http.Client
 }
 
 // SetVisible configures whether to show the browser window
@@ -54,16 +58,41 @@ type xhsNoteDetail struct {
 		Video struct {
 			Media struct {
 				Stream struct {
-					H264 []struct {
-						MasterURL string `json:"masterUrl"`
-					} `json:"h264"`
+					H264 []xhsStream `json:"h264"`
+					H265 []xhsStream `json:"h265"`
 				} `json:"stream"`
 			} `json:"media"`
 		} `json:"video"`
 	} `json:"note"`
 }
 
+// xhsStream is a single video stream in a note
+type xhsStream struct {
+	MasterURL  string `json:"masterUrl"`
+	Width      int    `json:"width"`
+	Height     int    `json:"height"`
+	AvgBitrate int    `json:"avgBitrate"`
+}
+
+// xhsInitialStateRegex captures the page state embedded in note HTML
+var xhsInitialStateRegex = regexp.MustCompile(`(?s)window\.__INITIAL_STATE__\s*=\s*(\{.*?\})\s*</script>`)
+
+// xhsUndefinedRegex matches JavaScript undefined values, which are not valid JSON
+var xhsUndefinedRegex = regexp.MustCompile(`([:\[,])\s*undefined\b`)
+
 func (e *XiaohongshuExtractor) Extract(rawURL string) (Media, error) {
+	return e.ExtractWithContext(rawURL, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client for the
+// cookie-based page fetch
+func (e *XiaohongshuExtractor) ExtractWithContext(rawURL string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	call.client = ctx.HTTPClient()
+	return call.extract(rawURL)
+}
+
+func (e *XiaohongshuExtractor) extract(rawURL string) (Media, error) {
 	// Resolve short URL if needed
 	finalURL := rawURL
 	if strings.Contains(rawURL, "xhslink.com") {
@@ -80,10 +109,78 @@ func (e *XiaohongshuExtractor) Extract(rawURL string) (Media, error) {
 		return nil, fmt.Errorf("could not extract note ID from URL: %s", finalURL)
 	}
 
+	// Fetch the note page directly with cookies saved by the login flow,
+	// falling back to the browser (which can prompt for login) if that fails
+	if media, err := e.extractWithHTTP(finalURL, noteID); err == nil {
+		return media, nil
+	}
+
 	// Launch browser and extract data
 	return e.extractWithBrowser(finalURL, noteID)
 }
 
+// extractWithHTTP fetches the note page and parses the embedded __INITIAL_STATE__
+func (e *XiaohongshuExtractor) extractWithHTTP(targetURL, noteID string) (Media, error) {
+	cookie := e.cookieHeader()
+	if cookie == "" {
+		return nil, fmt.Errorf("no saved xiaohongshu cookies")
+	}
+
+	req, err := http.NewRequest("GET", targetURL, nil)
+	if err != nil {
+		return nil, err
+	}
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+	req.Header.Set("Referer", "https://www.xiaohongshu.com/")
+	req.Header.Set("Cookie", cookie)
+
+	resp, err := e.client.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("note page returned HTTP %d", resp.StatusCode)
+	}
+	// A redirect to the login page means the cookies expired
+	if strings.Contains(resp.Request.URL.Path, "/login") {
+		return nil, fmt.Errorf("saved xiaohongshu cookies have expired")
+	}
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, err
+	}
+
+	matches := xhsInitialStateRegex.FindSubmatch(body)
+	if len(matches) < 2 {
+		return nil, fmt.Errorf("note data not found in page")
+	}
+	stateJSON := xhsUndefinedRegex.ReplaceAll(matches[1], []byte("${1}null"))
+
+	var state struct {
+		Note struct {
+			NoteDetailMap map[string]xhsNoteDetail `json:"noteDetailMap"`
+		} `json:"note"`
+	}
+	if err := json.Unmarshal(stateJSON, &state); err != nil {
+		return nil, fmt.Errorf("failed to parse note data: %w", err)
+	}
+
+	detail, ok := state.Note.NoteDetailMap[noteID]
+	if !ok || detail.Note.NoteID == "" {
+		return nil, fmt.Errorf("note %s not found in page", noteID)
+	}
+	return e.noteToMedia(detail)
+}
+
+// cookieHeader builds a Cookie header from the saved login, skipping expired cookies
+func (e *XiaohongshuExtractor) cookieHeader() string {
+	login, _ := auth.Get(auth.Xiaohongshu)
+	return login.CookieHeader()
+}
+
 func (e *XiaohongshuExtractor) extractNoteID(rawURL string) string {
 	// Pattern: /explore/{noteId} or /discovery/item/{noteId}
 	patterns := []string{
@@ -216,7 +313,11 @@ func (e *XiaohongshuExtractor) extractWithBrowser(targetURL, noteID string) (Med
 	fmt.Println() // newline after progress
 
 	// Save cookies for future sessions
-	e.saveCookies(browser)
+	if n, err := e.saveCookies(browser); err != nil {
+		fmt.Printf("Warning: failed to save cookies: %v\n", err)
+	} else if n > 0 {
+		fmt.Printf("Saved %d cookies for future sessions\n", n)
+	}
 
 	// Parse the response
 	var noteDetailMap map[string]xhsNoteDetail
@@ -259,6 +360,11 @@ func (e *XiaohongshuExtractor) extractWithBrowser(targetURL, noteID string) (Med
 		return nil, fmt.Errorf("note %s not found in response (available: %d keys)", noteID, len(noteDetailMap))
 	}
 
+	return e.noteToMedia(noteDetail)
+}
+
+// noteToMedia converts a note into video media or an image gallery
+func (e *XiaohongshuExtractor) noteToMedia(noteDetail xhsNoteDetail) (Media, error) {
 	note := noteDetail.Note
 	title := note.Title
 	if title == "" {
@@ -278,39 +384,55 @@ func (e *XiaohongshuExtractor) extractWithBrowser(targetURL, noteID string) (Med
 }
 
 func (e *XiaohongshuExtractor) extractVideo(id, title, uploader string, detail xhsNoteDetail) (Media, error) {
-	var videoURL string
-
-	// Try to get video URL from the structure
-	h264Streams := detail.Note.Video.Media.Stream.H264
-	if len(h264Streams) > 0 && h264Streams[0].MasterURL != "" {
-		videoURL = h264Streams[0].MasterURL
-	}
+	var formats []VideoFormat
+
+	// H.264 first for compatibility, then H.265
+	stream := detail.Note.Video.Media.Stream
+	for _, group := range []struct {
+		codec   string
+		streams []xhsStream
+	}{{"H.264", stream.H264}, {"H.265", stream.H265}} {
+		for _, s := range group.streams {
+			if s.MasterURL == "" {
+				continue
+			}
 
-	if videoURL == "" {
-		return nil, fmt.Errorf("could not find video URL in note data")
-	}
+			// Ensure HTTPS
+			videoURL := s.MasterURL
+			if strings.HasPrefix(videoURL, "//") {
+				videoURL = "https:" + videoURL
+			}
 
-	// Ensure HTTPS
-	if strings.HasPrefix(videoURL, "//") {
-		videoURL = "https:" + videoURL
-	}
+			quality := "best"
+			if s.Height > 0 {
+				quality = fmt.Sprintf("%dp", s.Height)
+			}
 
-	return &VideoMedia{
-		ID:       id,
-		Title:    title,
-		Uploader: uploader,
-		Formats: []VideoFormat{
-			{
+			formats = append(formats, VideoFormat{
 				URL:     videoURL,
-				Quality: "best",
+				Quality: fmt.Sprintf("%s [%s]", quality, group.codec),
 				Ext:     "mp4",
+				Width:   s.Width,
+				Height:  s.Height,
+				Bitrate: s.AvgBitrate / 1000,
 				Headers: map[string]string{
 					"Referer":    "https://www.xiaohongshu.com/",
 					"Origin":     "https://www.xiaohongshu.com",
 					"User-Agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
 				},
-			},
-		},
+			})
+		}
+	}
+
+	if len(formats) == 0 {
+		return nil, fmt.Errorf("could not find video URL in note data")
+	}
+
+	return &VideoMedia{
+		ID:       id,
+		Title:    title,
+		Uploader: uploader,
+		Formats:  formats,
 	}, nil
 }
 
@@ -371,7 +493,7 @@ func (e *XiaohongshuExtractor) createLauncher(headless bool) *launcher.Launcher
 		Set("no-first-run").
 		Set("safebrowsing-disable-auto-update").
 		Set("window-size", "1920,1080").
-		Set("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+		Set("user-agent", browserUserAgent)
 
 	// Explicitly set browser path if provided (required for Docker)
 	if browserPath != "" {
@@ -394,67 +516,33 @@ func (e *XiaohongshuExtractor) getUserDataDir() string {
 }
 
 func (e *XiaohongshuExtractor) loadCookies(browser *rod.Browser) {
-	// Try to load cookies from ~/.config/vget/xhs_cookies.json
-	configDir, err := config.ConfigDir()
-	if err != nil {
-		return
-	}
-
-	cookiePath := filepath.Join(configDir, "xhs_cookies.json")
-	data, err := os.ReadFile(cookiePath)
-	if err != nil {
-		return // No cookies file, that's fine
+	login, _ := auth.Get(auth.Xiaohongshu)
+	if len(login.Cookies) == 0 {
+		return // Not logged in, that's fine
 	}
 
-	var cookies []*proto.NetworkCookie
-	if err := json.Unmarshal(data, &cookies); err != nil {
-		return
+	cookies := make([]*proto.NetworkCookie, 0, len(login.Cookies))
+	for _, c := range login.Cookies {
+		cookie := &proto.NetworkCookie{
+			Name: c.Name, Value: c.Value, Domain: c.Domain, Path: c.Path,
+			HTTPOnly: c.HTTPOnly, Secure: c.Secure, Session: c.Expires.IsZero(),
+		}
+		if !c.Expires.IsZero() {
+			cookie.Expires = proto.TimeSinceEpoch(c.Expires.Unix())
+		}
+		cookies = append(cookies, cookie)
 	}
-
 	fmt.Println("Loaded saved cookies from previous session")
 	browser.MustSetCookies(cookies...)
 }
 
-func (e *XiaohongshuExtractor) saveCookies(browser *rod.Browser) {
-	// Save cookies to ~/.config/vget/xhs_cookies.json
-	configDir, err := config.ConfigDir()
-	if err != nil {
-		return
-	}
-
-	// Ensure directory exists
-	if err := os.MkdirAll(configDir, 0755); err != nil {
-		return
-	}
-
-	cookies, err := browser.GetCookies()
-	if err != nil {
-		return
-	}
+// xhsCookieDomains are the domains of the Xiaohongshu login cookies
+var xhsCookieDomains = []string{"xiaohongshu.com", "xhscdn.com"}
 
-	// Filter to only XHS-related cookies
-	var xhsCookies []*proto.NetworkCookie
-	for _, c := range cookies {
-		if strings.Contains(c.Domain, "xiaohongshu") || strings.Contains(c.Domain, "xhscdn") {
-			xhsCookies = append(xhsCookies, c)
-		}
-	}
-
-	if len(xhsCookies) == 0 {
-		return
-	}
-
-	data, err := json.MarshalIndent(xhsCookies, "", "  ")
-	if err != nil {
-		return
-	}
-
-	cookiePath := filepath.Join(configDir, "xhs_cookies.json")
-	if err := os.WriteFile(cookiePath, data, 0600); err != nil {
-		fmt.Printf("Warning: failed to save cookies: %v\n", err)
-		return
-	}
-	fmt.Printf("Saved %d cookies for future sessions\n", len(xhsCookies))
+// saveCookies stores the Xiaohongshu cookies of browser in the auth store and
+// returns how many there were
+func (e *XiaohongshuExtractor) saveCookies(browser *rod.Browser) (int, error) {
+	return saveBrowserCookies(browser, auth.Xiaohongshu, xhsCookieDomains)
 }
 
 func init() {
@@ -0,0 +1,46 @@
+package extractor
+
+import (
+	"context"
+
+	"github.com/go-rod/rod"
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+// xhsLoginURL shows the login dialog to visitors who aren't logged in
+const xhsLoginURL = "https://www.xiaohongshu.com/explore"
+
+// xhsLoggedInJS reports whether the page's session is logged in. The user
+// store keeps loggedIn as a Vue ref, unwrapped here.
+const xhsLoggedInJS = `() => {
+	const state = window.__INITIAL_STATE__;
+	if (!state || !state.user) return false;
+	const loggedIn = state.user.loggedIn;
+	if (loggedIn && typeof loggedIn === "object") {
+		return !!(loggedIn.value ?? loggedIn._value);
+	}
+	return !!loggedIn;
+}`
+
+// XiaohongshuLoginOptions are the BrowserLogin options of Xiaohongshu: log in
+// by QR code or phone number, until the page's state reports the session
+// logged in
+func XiaohongshuLoginOptions() BrowserLoginOptions {
+	return BrowserLoginOptions{
+		Site:    auth.Xiaohongshu,
+		URL:     xhsLoginURL,
+		Domains: xhsCookieDomains,
+		loggedIn: func(page *rod.Page) (bool, error) {
+			result, err := page.Eval(xhsLoggedInJS)
+			if err != nil {
+				return false, err
+			}
+			return result.Value.Bool(), nil
+		},
+	}
+}
+
+// XiaohongshuLogin logs in to Xiaohongshu in a browser window; see BrowserLogin
+func XiaohongshuLogin(ctx context.Context) (int, error) {
+	return BrowserLogin(ctx, XiaohongshuLoginOptions())
+}
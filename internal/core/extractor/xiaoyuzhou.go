@@ -8,10 +8,14 @@ import (
 	"net/url"
 	"regexp"
 	"strings"
+
+	"github.com/guiyumin/vget/internal/core/sanitize"
 )
 
 // XiaoyuzhouExtractor handles xiaoyuzhoufm.com podcast downloads
-type XiaoyuzhouExtractor struct{}
+type XiaoyuzhouExtractor struct {
+	client *http.Client
+}
 
 func (e *XiaoyuzhouExtractor) Name() string {
 	return "xiaoyuzhou"
@@ -23,6 +27,17 @@ func (e *XiaoyuzhouExtractor) Match(u *url.URL) bool {
 }
 
 func (e *XiaoyuzhouExtractor) Extract(url string) (Media, error) {
+	return e.ExtractWithContext(url, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client
+func (e *XiaoyuzhouExtractor) ExtractWithContext(url string, ctx *ExtractContext) (Media, error) {
+	call := *e
+	call.client = ctx.HTTPClient()
+	return call.extract(url)
+}
+
+func (e *XiaoyuzhouExtractor) extract(url string) (Media, error) {
 	if strings.Contains(url, "/episode/") {
 		return e.extractEpisode(url)
 	}
@@ -43,7 +58,7 @@ func (e *XiaoyuzhouExtractor) extractEpisode(url string) (*AudioMedia, error) {
 	episodeID := matches[1]
 
 	// Fetch the episode page to get JSON data
-	resp, err := http.Get(url)
+	resp, err := e.client.Get(url)
 	if err != nil {
 		return nil, err
 	}
@@ -82,8 +97,10 @@ func (e *XiaoyuzhouExtractor) extractEpisode(url string) (*AudioMedia, error) {
 					Enclosure struct {
 						URL string `json:"url"`
 					} `json:"enclosure"`
+					Image   xiaoyuzhouImage `json:"image"`
 					Podcast struct {
-						Title string `json:"title"`
+						Title string          `json:"title"`
+						Image xiaoyuzhouImage `json:"image"`
 					} `json:"podcast"`
 				} `json:"episode"`
 			} `json:"pageProps"`
@@ -106,18 +123,24 @@ func (e *XiaoyuzhouExtractor) extractEpisode(url string) (*AudioMedia, error) {
 	}
 
 	// Create filename: {podcast} - {title}
-	filename := SanitizeFilename(fmt.Sprintf("%s - %s", episode.Podcast.Title, episode.Title))
+	filename := sanitize.Filename(fmt.Sprintf("%s - %s", episode.Podcast.Title, episode.Title))
 
 	return &AudioMedia{
-		ID:       episodeID,
-		Title:    filename,
-		Uploader: episode.Podcast.Title,
-		Duration: episode.Duration,
-		URL:      episode.Enclosure.URL,
-		Ext:      ext,
+		ID:        episodeID,
+		Title:     filename,
+		Uploader:  episode.Podcast.Title,
+		Duration:  episode.Duration,
+		Thumbnail: firstNonEmpty(episode.Image.PicURL, episode.Podcast.Image.PicURL),
+		URL:       episode.Enclosure.URL,
+		Ext:       ext,
 	}, nil
 }
 
+// xiaoyuzhouImage is the artwork of an episode or podcast
+type xiaoyuzhouImage struct {
+	PicURL string `json:"picUrl"`
+}
+
 // extractPodcast lists all episodes from a podcast
 func (e *XiaoyuzhouExtractor) extractPodcast(_ string) (*AudioMedia, error) {
 	// For now, return an error suggesting to use search
@@ -3,6 +3,7 @@ package extractor
 import (
 	"bufio"
 	"context"
+	"fmt"
 	"net/url"
 	"os"
 	"os/exec"
@@ -23,18 +24,31 @@ func (e *YouTubeDockerRequiredError) Error() string {
 	return "YouTube extraction requires Docker"
 }
 
-// YouTubeDirectDownload indicates yt-dlp should handle the download directly
+// YouTubeDirectDownload indicates yt-dlp should handle the download directly.
+// Metadata is filled in best-effort from the player API and may be empty.
 type YouTubeDirectDownload struct {
-	URL       string
-	OutputDir string
+	URL        string
+	OutputDir  string
+	Title      string
+	Uploader   string
+	Duration   int // seconds
+	Thumbnails []Thumbnail
+	Subtitles  []Subtitle
+	Chapters   []Chapter
 }
 
 // Implement Media interface for YouTubeDirectDownload
 func (y *YouTubeDirectDownload) GetID() string       { return y.URL }
-func (y *YouTubeDirectDownload) GetTitle() string    { return "YouTube Video" }
-func (y *YouTubeDirectDownload) GetUploader() string { return "" }
+func (y *YouTubeDirectDownload) GetUploader() string { return y.Uploader }
 func (y *YouTubeDirectDownload) Type() MediaType     { return MediaTypeVideo }
 
+func (y *YouTubeDirectDownload) GetTitle() string {
+	if y.Title != "" {
+		return y.Title
+	}
+	return "YouTube Video"
+}
+
 // ytdlpExtractor uses yt-dlp/youtube-dl for YouTube extraction (Docker only)
 type ytdlpExtractor struct{}
 
@@ -52,6 +66,21 @@ func (e *ytdlpExtractor) Match(u *url.URL) bool {
 }
 
 func (e *ytdlpExtractor) Extract(urlStr string) (Media, error) {
+	return e.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract using the context's HTTP client for the web API
+// and the stored YouTube login, if any, for age-restricted, members-only and
+// private videos and playlists
+func (e *ytdlpExtractor) ExtractWithContext(urlStr string, ctx *ExtractContext) (Media, error) {
+	lister := &youtubeLister{client: ctx.HTTPClient(), login: youtubeLogin(ctx)}
+
+	// Playlists and channels are listed via the web API; each entry is
+	// extracted (and downloaded with yt-dlp) separately
+	if u, err := url.Parse(urlStr); err == nil && isYouTubeListURL(u) {
+		return lister.List(u)
+	}
+
 	if !config.IsRunningInDocker() {
 		return nil, &YouTubeDockerRequiredError{URL: urlStr}
 	}
@@ -59,9 +88,17 @@ func (e *ytdlpExtractor) Extract(urlStr string) (Media, error) {
 	// For YouTube, we return a special marker that tells the CLI
 	// to use yt-dlp for direct download instead of vget's downloader
 	// OutputDir will be set by CLI from config
-	return &YouTubeDirectDownload{
+	media := &YouTubeDirectDownload{
 		URL: urlStr,
-	}, nil
+	}
+	if u, err := url.Parse(urlStr); err == nil {
+		if id := youtubeVideoID(u); id != "" {
+			if player, err := fetchYouTubePlayer(lister, id); err == nil {
+				player.apply(media)
+			}
+		}
+	}
+	return media, nil
 }
 
 // DownloadWithYtdlp downloads a YouTube video using yt-dlp directly
@@ -69,19 +106,34 @@ func DownloadWithYtdlp(url, outputDir string) error {
 	return DownloadWithYtdlpProgress(context.Background(), url, outputDir, nil)
 }
 
-// DownloadWithYtdlpProgress downloads a YouTube video using yt-dlp with progress callback
+// DownloadWithYtdlpProgress downloads a YouTube video using yt-dlp with progress
+// callback, passing it the stored YouTube login, if any
 func DownloadWithYtdlpProgress(ctx context.Context, url, outputDir string, progressFn func(downloaded, total int64)) error {
 	outputTemplate := filepath.Join(outputDir, "%(title)s.%(ext)s")
+	cookieFile, err := youtubeCookieFile()
+	if err != nil {
+		return fmt.Errorf("failed to write YouTube cookies: %w", err)
+	}
+	if cookieFile != "" {
+		defer os.Remove(cookieFile)
+	}
 
-	cmd := exec.CommandContext(ctx, "yt-dlp",
+	args := []string{
 		"-f", "bv*+ba/b", // best video + best audio, or best combined
 		"--merge-output-format", "mp4",
 		"--no-playlist",
+		"--embed-chapters",
 		"--newline",                         // Output progress on new lines for parsing
 		"--remote-components", "ejs:github", // download JS challenge solver
 		"-o", outputTemplate,
-		url,
-	)
+	}
+	if config.LoadOrDefault().Download.TagMetadata {
+		args = append(args, "--embed-metadata")
+	}
+	if cookieFile != "" {
+		args = append(args, "--cookies", cookieFile)
+	}
+	cmd := exec.CommandContext(ctx, "yt-dlp", append(args, url)...)
 
 	// If no progress callback, just run normally
 	if progressFn == nil {
@@ -92,7 +144,7 @@ func DownloadWithYtdlpProgress(ctx context.Context, url, outputDir string, progr
 			return nil
 		}
 		// Fallback to youtube-dl
-		return downloadWithYoutubeDL(ctx, url, outputDir)
+		return downloadWithYoutubeDL(ctx, url, outputDir, cookieFile)
 	}
 
 	// Parse progress from stderr
@@ -102,7 +154,7 @@ func DownloadWithYtdlpProgress(ctx context.Context, url, outputDir string, progr
 	}
 
 	if err := cmd.Start(); err != nil {
-		return downloadWithYoutubeDL(ctx, url, outputDir)
+		return downloadWithYoutubeDL(ctx, url, outputDir, cookieFile)
 	}
 
 	// Parse yt-dlp progress output
@@ -142,18 +194,21 @@ func DownloadWithYtdlpProgress(ctx context.Context, url, outputDir string, progr
 	}
 
 	// Fallback to youtube-dl
-	return downloadWithYoutubeDL(ctx, url, outputDir)
+	return downloadWithYoutubeDL(ctx, url, outputDir, cookieFile)
 }
 
-func downloadWithYoutubeDL(ctx context.Context, url, outputDir string) error {
+func downloadWithYoutubeDL(ctx context.Context, url, outputDir, cookieFile string) error {
 	outputTemplate := filepath.Join(outputDir, "%(title)s.%(ext)s")
-	cmd := exec.CommandContext(ctx, "youtube-dl",
+	args := []string{
 		"-f", "bestvideo+bestaudio/best",
 		"--merge-output-format", "mp4",
 		"--no-playlist",
 		"-o", outputTemplate,
-		url,
-	)
+	}
+	if cookieFile != "" {
+		args = append(args, "--cookies", cookieFile)
+	}
+	cmd := exec.CommandContext(ctx, "youtube-dl", append(args, url)...)
 	cmd.Stdout = os.Stdout
 	cmd.Stderr = os.Stderr
 	return cmd.Run()
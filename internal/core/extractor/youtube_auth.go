@@ -0,0 +1,127 @@
+package extractor
+
+import (
+	"crypto/sha1"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+const (
+	youtubeOrigin = "https://www.youtube.com"
+
+	// youtubeLoginURL is Google's sign-in page, returning to YouTube after
+	youtubeLoginURL = "https://accounts.google.com/ServiceLogin?service=youtube&continue=https%3A%2F%2Fwww.youtube.com%2F"
+)
+
+// youtubeCookieDomains are the domains whose cookies make up a YouTube login;
+// Google sets YouTube's own copies of its session cookies after sign-in
+var youtubeCookieDomains = []string{"youtube.com"}
+
+// youtubeSAPISIDCookies hold the SAPISID the authorization header is hashed
+// from, under the names YouTube has used for it
+var youtubeSAPISIDCookies = []string{"SAPISID", "__Secure-3PAPISID"}
+
+// YouTubeLoginOptions are the BrowserLoginOptions of YouTube: sign in to
+// Google, until YouTube has set its session cookies
+func YouTubeLoginOptions() BrowserLoginOptions {
+	return BrowserLoginOptions{
+		Site:    auth.YouTube,
+		URL:     youtubeLoginURL,
+		Domains: youtubeCookieDomains,
+		loggedIn: hasCookie(youtubeOrigin+"/", youtubeSAPISIDCookies...),
+	}
+}
+
+// youtubeLogin returns the stored YouTube login to extract with, or nil when
+// there is none or network.cookies sets youtube.com's cookies instead
+func youtubeLogin(ctx *ExtractContext) *auth.Login {
+	if ctx != nil && ctx.Cookie("www.youtube.com") != "" {
+		return nil
+	}
+	login, err := auth.Get(auth.YouTube)
+	if err != nil || !login.LoggedIn() {
+		return nil
+	}
+	return login
+}
+
+// youtubeAuthHeaders are the headers that make an InnerTube request on behalf
+// of login: its cookies, and the SAPISIDHASH authorization InnerTube requires
+// along with them
+func youtubeAuthHeaders(login *auth.Login, now time.Time) map[string]string {
+	headers := map[string]string{"Cookie": login.CookieHeader()}
+	for _, name := range youtubeSAPISIDCookies {
+		if sapisid := login.Cookie(name); sapisid != "" {
+			ts := strconv.FormatInt(now.Unix(), 10)
+			sum := sha1.Sum([]byte(ts + " " + sapisid + " " + youtubeOrigin))
+			headers["Authorization"] = "SAPISIDHASH " + ts + "_" + hex.EncodeToString(sum[:])
+			headers["X-Origin"] = youtubeOrigin
+			headers["X-Goog-AuthUser"] = "0"
+			break
+		}
+	}
+	return headers
+}
+
+// netscapeCookies formats cookies as a Netscape cookies.txt, the format
+// yt-dlp's --cookies reads. Cookies without a domain, as imported from a
+// Cookie header, are given defaultDomain.
+func netscapeCookies(cookies []auth.Cookie, defaultDomain string) string {
+	var b strings.Builder
+	b.WriteString("# Netscape HTTP Cookie File\n")
+	for _, c := range cookies {
+		domain, path := c.Domain, c.Path
+		if domain == "" {
+			domain = "." + defaultDomain
+		}
+		if path == "" {
+			path = "/"
+		}
+		if c.HTTPOnly {
+			domain = "#HttpOnly_" + domain
+		}
+		var expires int64
+		if !c.Expires.IsZero() {
+			expires = c.Expires.Unix()
+		}
+		// Secure is assumed for imported cookies, whose flags are unknown
+		secure := c.Secure || c.Domain == ""
+		fmt.Fprintf(&b, "%s\t%s\t%s\t%s\t%d\t%s\t%s\n",
+			domain, netscapeBool(strings.HasPrefix(strings.TrimPrefix(domain, "#HttpOnly_"), ".")),
+			path, netscapeBool(secure), expires, c.Name, c.Value)
+	}
+	return b.String()
+}
+
+func netscapeBool(v bool) string {
+	if v {
+		return "TRUE"
+	}
+	return "FALSE"
+}
+
+// youtubeCookieFile writes the stored YouTube login to a temporary
+// cookies.txt for yt-dlp. It returns "" when there is no login; the caller
+// removes the file.
+func youtubeCookieFile() (string, error) {
+	login := youtubeLogin(NewExtractContext())
+	if login == nil {
+		return "", nil
+	}
+	f, err := os.CreateTemp("", "vget-youtube-cookies-*.txt")
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+	if _, err := f.WriteString(netscapeCookies(login.Cookies, "youtube.com")); err != nil {
+		os.Remove(f.Name())
+		return "", err
+	}
+	return f.Name(), nil
+}
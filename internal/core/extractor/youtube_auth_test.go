@@ -0,0 +1,44 @@
+package extractor
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+func TestYouTubeAuthHeaders(t *testing.T) {
+	login := &auth.Login{Cookies: auth.ParseCookieHeader("SID=s; SAPISID=abc123")}
+	headers := youtubeAuthHeaders(login, time.Unix(1700000000, 0))
+	if got, want := headers["Authorization"], "SAPISIDHASH 1700000000_9e5071f149fc514366f78b22d1a169786d40ed32"; got != want {
+		t.Errorf("Authorization = %q, want %q", got, want)
+	}
+	if headers["Cookie"] != "SID=s; SAPISID=abc123" || headers["X-Origin"] != youtubeOrigin {
+		t.Errorf("youtubeAuthHeaders() = %v", headers)
+	}
+
+	headers = youtubeAuthHeaders(&auth.Login{Cookies: auth.ParseCookieHeader("SID=s")}, time.Now())
+	if _, ok := headers["Authorization"]; ok {
+		t.Error("Authorization set without a SAPISID cookie")
+	}
+}
+
+func TestNetscapeCookies(t *testing.T) {
+	cookies := []auth.Cookie{
+		{Name: "SID", Value: "s"},
+		{Name: "LOGIN_INFO", Value: "l", Domain: ".youtube.com", Path: "/", HTTPOnly: true, Secure: true, Expires: time.Unix(1893456000, 0)},
+		{Name: "PREF", Value: "p", Domain: "www.youtube.com"},
+	}
+	got := strings.Split(netscapeCookies(cookies, "youtube.com"), "\n")
+	want := []string{
+		"# Netscape HTTP Cookie File",
+		".youtube.com\tTRUE\t/\tTRUE\t0\tSID\ts",
+		"#HttpOnly_.youtube.com\tTRUE\t/\tTRUE\t1893456000\tLOGIN_INFO\tl",
+		"www.youtube.com\tFALSE\t/\tFALSE\t0\tPREF\tp",
+		"",
+	}
+	if strings.Join(got, "\n") != strings.Join(want, "\n") {
+		t.Errorf("netscapeCookies() =\n%s\nwant\n%s", strings.Join(got, "\n"), strings.Join(want, "\n"))
+	}
+}
@@ -0,0 +1,107 @@
+package extractor
+
+import (
+	"encoding/json"
+	"net/url"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// youtubeIDRegex matches an 11-character YouTube video ID
+var youtubeIDRegex = regexp.MustCompile(`^[\w-]{11}$`)
+
+// youtubeVideoID returns the video ID of a watch, short, embed, live, or youtu.be URL
+func youtubeVideoID(u *url.URL) string {
+	var id string
+	if strings.EqualFold(u.Hostname(), "youtu.be") {
+		id = strings.Trim(u.Path, "/")
+	} else if v := u.Query().Get("v"); v != "" {
+		id = v
+	} else {
+		parts := strings.Split(strings.Trim(u.Path, "/"), "/")
+		if len(parts) == 2 && (parts[0] == "shorts" || parts[0] == "embed" || parts[0] == "live") {
+			id = parts[1]
+		}
+	}
+	if !youtubeIDRegex.MatchString(id) {
+		return ""
+	}
+	return id
+}
+
+// youtubePlayer is the subset of the InnerTube player response vget uses
+type youtubePlayer struct {
+	VideoDetails struct {
+		VideoID          string `json:"videoId"`
+		Title            string `json:"title"`
+		Author           string `json:"author"`
+		LengthSeconds    string `json:"lengthSeconds"`
+		ShortDescription string `json:"shortDescription"`
+		Thumbnail        struct {
+			Thumbnails []Thumbnail `json:"thumbnails"`
+		} `json:"thumbnail"`
+	} `json:"videoDetails"`
+	Captions struct {
+		Renderer struct {
+			CaptionTracks []struct {
+				BaseURL      string      `json:"baseUrl"`
+				Name         youtubeText `json:"name"`
+				LanguageCode string      `json:"languageCode"`
+				Kind         string      `json:"kind"` // "asr" for auto-generated
+			} `json:"captionTracks"`
+		} `json:"playerCaptionsTracklistRenderer"`
+	} `json:"captions"`
+}
+
+// fetchYouTubePlayer fetches video metadata from the InnerTube player API, as
+// l's account if it has one. Only metadata is used; streams are left to yt-dlp.
+func fetchYouTubePlayer(l *youtubeLister, videoID string) (*youtubePlayer, error) {
+	resp, err := l.call("player", map[string]any{"videoId": videoID})
+	if err != nil {
+		return nil, err
+	}
+	var player youtubePlayer
+	if err := json.Unmarshal(resp, &player); err != nil {
+		return nil, err
+	}
+	return &player, nil
+}
+
+// apply copies the player metadata onto a yt-dlp download
+func (p *youtubePlayer) apply(m *YouTubeDirectDownload) {
+	m.Title = p.VideoDetails.Title
+	m.Uploader = p.VideoDetails.Author
+	m.Duration, _ = strconv.Atoi(p.VideoDetails.LengthSeconds)
+	m.Thumbnails = p.VideoDetails.Thumbnail.Thumbnails
+	m.Subtitles = p.subtitles()
+	m.Chapters = parseDescriptionChapters(p.VideoDetails.ShortDescription, m.Duration)
+}
+
+// subtitles lists caption tracks as WebVTT, uploaded tracks first
+func (p *youtubePlayer) subtitles() []Subtitle {
+	var subs []Subtitle
+	for _, track := range p.Captions.Renderer.CaptionTracks {
+		if track.BaseURL == "" {
+			continue
+		}
+		u, err := url.Parse(track.BaseURL)
+		if err != nil {
+			continue
+		}
+		q := u.Query()
+		q.Set("fmt", "vtt")
+		u.RawQuery = q.Encode()
+
+		subs = append(subs, Subtitle{
+			Language: track.LanguageCode,
+			Name:     track.Name.String(),
+			Ext:      "vtt",
+			URL:      u.String(),
+			Auto:     track.Kind == "asr",
+		})
+	}
+	sort.SliceStable(subs, func(i, j int) bool { return !subs[i].Auto && subs[j].Auto })
+	return subs
+}
@@ -0,0 +1,350 @@
+package extractor
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+const (
+	youtubeAPIBase       = "https://www.youtube.com/youtubei/v1"
+	youtubeClientVersion = "2.20250101.00.00"
+
+	// youtubeVideosTabParams selects the Videos tab when browsing a channel
+	youtubeVideosTabParams = "EgZ2aWRlb3PyBgQKAjoA"
+
+	// youtubeMaxPages stops runaway pagination on huge channels
+	youtubeMaxPages = 200
+)
+
+// isYouTubeListURL reports whether the URL is a playlist or channel page
+// (watch URLs with &list= download just the video)
+func isYouTubeListURL(u *url.URL) bool {
+	p := strings.TrimSuffix(u.Path, "/")
+	switch {
+	case p == "/playlist":
+		return u.Query().Get("list") != ""
+	case strings.HasPrefix(p, "/@"),
+		strings.HasPrefix(p, "/channel/"),
+		strings.HasPrefix(p, "/c/"),
+		strings.HasPrefix(p, "/user/"):
+		return true
+	}
+	return false
+}
+
+// youtubeLister pages through the InnerTube browse API to list playlist or channel videos
+type youtubeLister struct {
+	client *http.Client
+	login  *auth.Login // account to make the requests as, if any
+}
+
+// List returns every video of a playlist or channel as playlist entries
+func (l *youtubeLister) List(u *url.URL) (*PlaylistMedia, error) {
+	var browseID, params string
+	if listID := u.Query().Get("list"); listID != "" {
+		browseID = "VL" + listID
+	} else {
+		channelID, err := l.resolveChannel(u)
+		if err != nil {
+			return nil, err
+		}
+		browseID, params = channelID, youtubeVideosTabParams
+	}
+
+	body := map[string]any{"browseId": browseID}
+	if params != "" {
+		body["params"] = params
+	}
+	resp, err := l.call("browse", body)
+	if err != nil {
+		return nil, err
+	}
+
+	playlist := &PlaylistMedia{ID: strings.TrimPrefix(browseID, "VL")}
+	playlist.Title, playlist.Uploader = youtubeListMetadata(resp)
+
+	seen := make(map[string]bool)
+	for page := 0; page < youtubeMaxPages; page++ {
+		entries, token := youtubeListEntries(resp)
+		for _, e := range entries {
+			if seen[e.URL] {
+				continue
+			}
+			seen[e.URL] = true
+			playlist.Entries = append(playlist.Entries, e)
+		}
+		if token == "" {
+			break
+		}
+		if resp, err = l.call("browse", map[string]any{"continuation": token}); err != nil {
+			// Return what we have rather than losing earlier pages
+			if len(playlist.Entries) > 0 {
+				break
+			}
+			return nil, err
+		}
+	}
+
+	if len(playlist.Entries) == 0 {
+		return nil, fmt.Errorf("no videos found (the playlist may be private or empty)")
+	}
+	if playlist.Title == "" {
+		playlist.Title = playlist.ID
+	}
+	return playlist, nil
+}
+
+// resolveChannel turns /@handle, /c/, /user/, and /channel/ URLs into a channel ID
+func (l *youtubeLister) resolveChannel(u *url.URL) (string, error) {
+	parts := strings.Split(strings.Trim(u.Path, "/"), "/")
+	if len(parts) >= 2 && parts[0] == "channel" {
+		return parts[1], nil
+	}
+
+	// Resolve the channel root (without /videos, /shorts, ...)
+	root := "https://www.youtube.com/" + parts[0]
+	if (parts[0] == "c" || parts[0] == "user") && len(parts) >= 2 {
+		root += "/" + parts[1]
+	}
+	resp, err := l.call("navigation/resolve_url", map[string]any{"url": root})
+	if err != nil {
+		return "", err
+	}
+	var resolved struct {
+		Endpoint struct {
+			BrowseEndpoint struct {
+				BrowseID string `json:"browseId"`
+			} `json:"browseEndpoint"`
+		} `json:"endpoint"`
+	}
+	if err := json.Unmarshal(resp, &resolved); err != nil {
+		return "", fmt.Errorf("failed to parse channel: %w", err)
+	}
+	if resolved.Endpoint.BrowseEndpoint.BrowseID == "" {
+		return "", fmt.Errorf("channel not found: %s", root)
+	}
+	return resolved.Endpoint.BrowseEndpoint.BrowseID, nil
+}
+
+// call posts an InnerTube request with the web client context
+func (l *youtubeLister) call(endpoint string, body map[string]any) (json.RawMessage, error) {
+	body["context"] = map[string]any{
+		"client": map[string]any{
+			"clientName":    "WEB",
+			"clientVersion": youtubeClientVersion,
+			"hl":            "en",
+		},
+	}
+	data, err := json.Marshal(body)
+	if err != nil {
+		return nil, err
+	}
+
+	req, err := http.NewRequest("POST", youtubeAPIBase+"/"+endpoint+"?prettyPrint=false", bytes.NewReader(data))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+	req.Header.Set("X-YouTube-Client-Name", "1")
+	req.Header.Set("X-YouTube-Client-Version", youtubeClientVersion)
+	if l.login != nil {
+		for name, value := range youtubeAuthHeaders(l.login, time.Now()) {
+			req.Header.Set(name, value)
+		}
+	}
+
+	resp, err := l.client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch %s: %w", endpoint, err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("youtube %s returned status %d", endpoint, resp.StatusCode)
+	}
+	return io.ReadAll(resp.Body)
+}
+
+// youtubeListMetadata returns the playlist or channel title and owner
+func youtubeListMetadata(resp json.RawMessage) (title, uploader string) {
+	var meta struct {
+		Metadata struct {
+			Playlist struct {
+				Title string `json:"title"`
+			} `json:"playlistMetadataRenderer"`
+			Channel struct {
+				Title string `json:"title"`
+			} `json:"channelMetadataRenderer"`
+		} `json:"metadata"`
+		Sidebar struct {
+			Renderer struct {
+				Items []struct {
+					Secondary struct {
+						VideoOwner struct {
+							Owner struct {
+								Title youtubeText `json:"title"`
+							} `json:"videoOwnerRenderer"`
+						} `json:"videoOwner"`
+					} `json:"playlistSidebarSecondaryInfoRenderer"`
+				} `json:"items"`
+			} `json:"playlistSidebarRenderer"`
+		} `json:"sidebar"`
+	}
+	if err := json.Unmarshal(resp, &meta); err != nil {
+		return "", ""
+	}
+
+	if t := meta.Metadata.Channel.Title; t != "" {
+		return t, t
+	}
+	for _, item := range meta.Sidebar.Renderer.Items {
+		if owner := item.Secondary.VideoOwner.Owner.Title.String(); owner != "" {
+			uploader = owner
+		}
+	}
+	return meta.Metadata.Playlist.Title, uploader
+}
+
+// youtubeText is InnerTube's text object ({"simpleText": ...} or {"runs": [...]})
+type youtubeText struct {
+	SimpleText string `json:"simpleText"`
+	Runs       []struct {
+		Text string `json:"text"`
+	} `json:"runs"`
+}
+
+func (t youtubeText) String() string {
+	if t.SimpleText != "" {
+		return t.SimpleText
+	}
+	var b strings.Builder
+	for _, r := range t.Runs {
+		b.WriteString(r.Text)
+	}
+	return b.String()
+}
+
+// youtubeVideoRenderer covers playlistVideoRenderer, videoRenderer, and gridVideoRenderer
+type youtubeVideoRenderer struct {
+	VideoID       string      `json:"videoId"`
+	Title         youtubeText `json:"title"`
+	LengthSeconds string      `json:"lengthSeconds"`
+	Thumbnail     struct {
+		Thumbnails []struct {
+			URL string `json:"url"`
+		} `json:"thumbnails"`
+	} `json:"thumbnail"`
+}
+
+// youtubeListEntries walks a browse response for videos and the next continuation token.
+// The page layout differs between playlists, channels, and continuations, so
+// renderers are found wherever they appear.
+func youtubeListEntries(resp json.RawMessage) ([]PlaylistEntry, string) {
+	var root any
+	if err := json.Unmarshal(resp, &root); err != nil {
+		return nil, ""
+	}
+
+	var entries []PlaylistEntry
+	var token string
+
+	var walk func(v any)
+	walk = func(v any) {
+		switch node := v.(type) {
+		case []any:
+			for _, item := range node {
+				walk(item)
+			}
+		case map[string]any:
+			for key, child := range node {
+				switch key {
+				case "playlistVideoRenderer", "videoRenderer", "gridVideoRenderer":
+					if e, ok := youtubeRendererEntry(child); ok {
+						entries = append(entries, e)
+					}
+					continue
+				case "lockupViewModel":
+					if e, ok := youtubeLockupEntry(child); ok {
+						entries = append(entries, e)
+					}
+					continue
+				case "continuationCommand":
+					if m, ok := child.(map[string]any); ok && token == "" {
+						token, _ = m["token"].(string)
+					}
+					continue
+				}
+				walk(child)
+			}
+		}
+	}
+	walk(root)
+	return entries, token
+}
+
+// youtubeLockupEntry converts a lockupViewModel (newer channel and playlist layout) into a playlist entry
+func youtubeLockupEntry(v any) (PlaylistEntry, bool) {
+	data, err := json.Marshal(v)
+	if err != nil {
+		return PlaylistEntry{}, false
+	}
+	var lockup struct {
+		ContentID   string `json:"contentId"`
+		ContentType string `json:"contentType"`
+		Metadata    struct {
+			Lockup struct {
+				Title struct {
+					Content string `json:"content"`
+				} `json:"title"`
+			} `json:"lockupMetadataViewModel"`
+		} `json:"metadata"`
+	}
+	if err := json.Unmarshal(data, &lockup); err != nil || lockup.ContentID == "" ||
+		lockup.ContentType != "LOCKUP_CONTENT_TYPE_VIDEO" {
+		return PlaylistEntry{}, false
+	}
+
+	title := lockup.Metadata.Lockup.Title.Content
+	if title == "" {
+		title = lockup.ContentID
+	}
+	return PlaylistEntry{
+		Title: title,
+		URL:   "https://www.youtube.com/watch?v=" + lockup.ContentID,
+	}, true
+}
+
+// youtubeRendererEntry converts a video renderer into a playlist entry
+func youtubeRendererEntry(v any) (PlaylistEntry, bool) {
+	data, err := json.Marshal(v)
+	if err != nil {
+		return PlaylistEntry{}, false
+	}
+	var r youtubeVideoRenderer
+	if err := json.Unmarshal(data, &r); err != nil || r.VideoID == "" {
+		return PlaylistEntry{}, false
+	}
+
+	e := PlaylistEntry{
+		Title: r.Title.String(),
+		URL:   "https://www.youtube.com/watch?v=" + r.VideoID,
+	}
+	e.Duration, _ = strconv.Atoi(r.LengthSeconds)
+	if n := len(r.Thumbnail.Thumbnails); n > 0 {
+		e.Thumbnail = r.Thumbnail.Thumbnails[n-1].URL
+	}
+	if e.Title == "" {
+		e.Title = r.VideoID
+	}
+	return e, true
+}
@@ -0,0 +1,429 @@
+package extractor
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/url"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// ytdlpTimeout bounds a single yt-dlp metadata run
+const ytdlpTimeout = 2 * time.Minute
+
+// YtdlpFallbackExtractor shells out to yt-dlp for sites without a native
+// extractor. Only metadata is read (--dump-single-json); the formats it
+// reports are downloaded by vget's own downloader.
+type YtdlpFallbackExtractor struct {
+	binary string
+}
+
+// Name returns the extractor name
+func (e *YtdlpFallbackExtractor) Name() string {
+	return "yt-dlp"
+}
+
+// Match accepts any http(s) URL; it is only consulted when nothing else matched
+func (e *YtdlpFallbackExtractor) Match(u *url.URL) bool {
+	return u.Scheme == "http" || u.Scheme == "https"
+}
+
+// Extract runs yt-dlp and converts its JSON output
+func (e *YtdlpFallbackExtractor) Extract(urlStr string) (Media, error) {
+	return e.ExtractWithContext(urlStr, nil)
+}
+
+// ExtractWithContext is Extract passing the context's proxy and user agent to yt-dlp
+func (e *YtdlpFallbackExtractor) ExtractWithContext(urlStr string, ectx *ExtractContext) (Media, error) {
+	args := []string{
+		"--dump-single-json",
+		"--flat-playlist", // list playlist entries without resolving each one
+		"--no-warnings",
+	}
+	if ectx == nil {
+		ectx = NewExtractContext()
+	}
+	switch proxy := ectx.ProxyFor(urlStr); proxy {
+	case "": // yt-dlp reads HTTP_PROXY/HTTPS_PROXY itself
+	case config.ProxyDirect:
+		args = append(args, "--proxy", "") // yt-dlp treats an empty proxy as a direct connection
+	default:
+		args = append(args, "--proxy", proxy)
+	}
+	if ectx.UserAgent != "" {
+		args = append(args, "--user-agent", ectx.UserAgent)
+	}
+	args = append(args, urlStr)
+
+	ctx, cancel := context.WithTimeout(context.Background(), ytdlpTimeout)
+	defer cancel()
+
+	cmd := exec.CommandContext(ctx, e.binary, args...)
+	var stderr bytes.Buffer
+	cmd.Stderr = &stderr
+	out, err := cmd.Output()
+	if err != nil {
+		msg := strings.TrimSpace(stderr.String())
+		if msg == "" {
+			msg = err.Error()
+		}
+		return nil, fmt.Errorf("yt-dlp: %s", msg)
+	}
+
+	var info ytdlpInfo
+	if err := json.Unmarshal(out, &info); err != nil {
+		return nil, fmt.Errorf("failed to parse yt-dlp output: %w", err)
+	}
+	return info.toMedia()
+}
+
+// ytdlpFallback returns the yt-dlp extractor when the fallback is enabled and
+// the binary can be found, or nil
+func ytdlpFallback() Extractor {
+	cfg := config.LoadOrDefault()
+	if !cfg.Ytdlp.Fallback {
+		return nil
+	}
+	binary := FindYtdlp(cfg.Ytdlp.Path)
+	if binary == "" {
+		return nil
+	}
+	return &YtdlpFallbackExtractor{binary: binary}
+}
+
+// FindYtdlp locates the yt-dlp binary: the configured path, then the managed
+// copy in <config dir>/bin, then PATH. Returns "" if not found.
+func FindYtdlp(configured string) string {
+	if configured != "" {
+		if _, err := os.Stat(configured); err == nil {
+			return configured
+		}
+		return ""
+	}
+
+	name := "yt-dlp"
+	if runtime.GOOS == "windows" {
+		name += ".exe"
+	}
+	if dir, err := config.ConfigDir(); err == nil {
+		managed := filepath.Join(dir, "bin", name)
+		if _, err := os.Stat(managed); err == nil {
+			return managed
+		}
+	}
+	if p, err := exec.LookPath(name); err == nil {
+		return p
+	}
+	return ""
+}
+
+// ytdlpInfo is the subset of yt-dlp's info JSON that vget uses
+type ytdlpInfo struct {
+	Type        string            `json:"_type"`
+	ID          string            `json:"id"`
+	Title       string            `json:"title"`
+	Uploader    string            `json:"uploader"`
+	Channel     string            `json:"channel"`
+	Duration    float64           `json:"duration"`
+	Thumbnail   string            `json:"thumbnail"`
+	URL         string            `json:"url"`
+	WebpageURL  string            `json:"webpage_url"`
+	Ext         string            `json:"ext"`
+	Timestamp   int64             `json:"timestamp"`
+	UploadDate  string            `json:"upload_date"` // YYYYMMDD
+	Description string            `json:"description"`
+	HTTPHeaders map[string]string `json:"http_headers"`
+	Formats     []ytdlpFormat     `json:"formats"`
+	Entries     []ytdlpInfo       `json:"entries"`
+
+	Subtitles         map[string][]ytdlpSubtitle `json:"subtitles"`
+	AutomaticCaptions map[string][]ytdlpSubtitle `json:"automatic_captions"`
+	Chapters          []ytdlpChapter             `json:"chapters"`
+	Thumbnails        []ytdlpThumbnail           `json:"thumbnails"`
+}
+
+type ytdlpThumbnail struct {
+	URL    string `json:"url"`
+	Width  int    `json:"width"`
+	Height int    `json:"height"`
+}
+
+type ytdlpChapter struct {
+	StartTime float64 `json:"start_time"`
+	EndTime   float64 `json:"end_time"`
+	Title     string  `json:"title"`
+}
+
+type ytdlpSubtitle struct {
+	Ext  string `json:"ext"`
+	URL  string `json:"url"`
+	Name string `json:"name"`
+}
+
+type ytdlpFormat struct {
+	FormatID    string            `json:"format_id"`
+	URL         string            `json:"url"`
+	Ext         string            `json:"ext"`
+	Protocol    string            `json:"protocol"`
+	VCodec      string            `json:"vcodec"`
+	ACodec      string            `json:"acodec"`
+	Width       int               `json:"width"`
+	Height      int               `json:"height"`
+	TBR         float64           `json:"tbr"` // kbps
+	ABR         float64           `json:"abr"` // kbps
+	FormatNote  string            `json:"format_note"`
+	HTTPHeaders map[string]string `json:"http_headers"`
+	HasDRM      any               `json:"has_drm"` // true, false, or "maybe"
+}
+
+func (f *ytdlpFormat) hasVideo() bool { return f.VCodec != "none" }
+func (f *ytdlpFormat) hasAudio() bool { return f.ACodec != "none" }
+func (f *ytdlpFormat) drm() bool      { return f.HasDRM == true }
+
+// ext maps the format to a downloader extension; "" means unsupported
+func (f *ytdlpFormat) ext() string {
+	switch {
+	case strings.HasPrefix(f.Protocol, "m3u8"):
+		return "m3u8"
+	case f.Protocol == "" || f.Protocol == "https" || f.Protocol == "http":
+		return f.Ext
+	default:
+		// DASH segments, RTMP, storyboards, ...
+		return ""
+	}
+}
+
+func (info *ytdlpInfo) uploader() string {
+	return firstNonEmpty(info.Uploader, info.Channel)
+}
+
+func (info *ytdlpInfo) toMedia() (Media, error) {
+	if info.Type == "playlist" || info.Type == "multi_video" {
+		return info.toPlaylist(), nil
+	}
+	if info.Type == "url" || info.Type == "url_transparent" {
+		return nil, fmt.Errorf("yt-dlp redirected to %s; download that URL instead", info.URL)
+	}
+
+	formats := info.Formats
+	if len(formats) == 0 && info.URL != "" {
+		formats = []ytdlpFormat{{URL: info.URL, Ext: info.Ext, HTTPHeaders: info.HTTPHeaders}}
+	}
+
+	// DRM protected formats are skipped; sawDRM reports why nothing was left
+	sawDRM := false
+	for _, f := range formats {
+		sawDRM = sawDRM || f.drm()
+	}
+
+	// Best audio-only stream, paired with video-only formats
+	var bestAudio *ytdlpFormat
+	for i := range formats {
+		f := &formats[i]
+		if f.drm() || f.hasVideo() || !f.hasAudio() || f.URL == "" || f.ext() == "" || f.ext() == "m3u8" {
+			continue
+		}
+		if bestAudio == nil || f.ABR+f.TBR > bestAudio.ABR+bestAudio.TBR {
+			bestAudio = f
+		}
+	}
+
+	var videos []VideoFormat
+	for i := range formats {
+		f := &formats[i]
+		ext := f.ext()
+		if f.URL == "" || ext == "" || !f.hasVideo() || f.drm() {
+			continue
+		}
+
+		vf := VideoFormat{
+			URL:     f.URL,
+			Quality: ytdlpQuality(f),
+			Ext:     ext,
+			Width:   f.Width,
+			Height:  f.Height,
+			Bitrate: int(f.TBR),
+			Headers: f.HTTPHeaders,
+		}
+		if !f.hasAudio() {
+			if bestAudio == nil || ext == "m3u8" {
+				continue
+			}
+			vf.AudioURL = bestAudio.URL
+		}
+		videos = append(videos, vf)
+	}
+
+	duration := int(info.Duration)
+	if len(videos) == 0 {
+		if bestAudio == nil && sawDRM {
+			return &VideoMedia{
+				ID:          info.ID,
+				Title:       info.Title,
+				Uploader:    info.uploader(),
+				Description: info.Description,
+				Published:   info.published(),
+				Duration:    duration,
+				Thumbnail:   info.Thumbnail,
+				DRM:         DRMUnknown,
+			}, nil
+		}
+		if bestAudio == nil {
+			return nil, fmt.Errorf("yt-dlp found no downloadable formats")
+		}
+		return &AudioMedia{
+			ID:          info.ID,
+			Title:       info.Title,
+			Uploader:    info.uploader(),
+			Description: info.Description,
+			Published:   info.published(),
+			Duration:    duration,
+			Thumbnail:   info.Thumbnail,
+			URL:         bestAudio.URL,
+			Ext:         bestAudio.Ext,
+		}, nil
+	}
+
+	sort.SliceStable(videos, func(i, j int) bool {
+		if videos[i].Height != videos[j].Height {
+			return videos[i].Height > videos[j].Height
+		}
+		return videos[i].Bitrate > videos[j].Bitrate
+	})
+	return &VideoMedia{
+		ID:          info.ID,
+		Title:       info.Title,
+		Uploader:    info.uploader(),
+		Description: info.Description,
+		Published:   info.published(),
+		Duration:    duration,
+		Thumbnail:   info.Thumbnail,
+		Thumbnails:  info.thumbnails(),
+		Formats:     videos,
+		Subtitles:   info.subtitles(),
+		Chapters:    info.chapters(),
+	}, nil
+}
+
+// published is when the media was published, zero if yt-dlp doesn't know
+func (info *ytdlpInfo) published() time.Time {
+	if info.Timestamp > 0 {
+		return time.Unix(info.Timestamp, 0)
+	}
+	t, _ := time.Parse("20060102", info.UploadDate)
+	return t
+}
+
+func (info *ytdlpInfo) thumbnails() []Thumbnail {
+	var thumbs []Thumbnail
+	for _, t := range info.Thumbnails {
+		if t.URL != "" {
+			thumbs = append(thumbs, Thumbnail{URL: t.URL, Width: t.Width, Height: t.Height})
+		}
+	}
+	return thumbs
+}
+
+func (info *ytdlpInfo) chapters() []Chapter {
+	var chapters []Chapter
+	for _, c := range info.Chapters {
+		chapters = append(chapters, Chapter{Title: c.Title, Start: c.StartTime, End: c.EndTime})
+	}
+	return chapters
+}
+
+// subtitles picks one track per language, preferring vtt then srt.
+// Uploaded subtitles come before automatic captions.
+func (info *ytdlpInfo) subtitles() []Subtitle {
+	var subs []Subtitle
+	for _, auto := range []bool{false, true} {
+		tracks := info.Subtitles
+		if auto {
+			tracks = info.AutomaticCaptions
+		}
+		langs := make([]string, 0, len(tracks))
+		for lang := range tracks {
+			langs = append(langs, lang)
+		}
+		sort.Strings(langs)
+
+		for _, lang := range langs {
+			var best *ytdlpSubtitle
+			for i := range tracks[lang] {
+				t := &tracks[lang][i]
+				if t.URL == "" {
+					continue
+				}
+				if best == nil || subtitleExtRank(t.Ext) < subtitleExtRank(best.Ext) {
+					best = t
+				}
+			}
+			if best == nil {
+				continue
+			}
+			subs = append(subs, Subtitle{Language: lang, Name: best.Name, Ext: best.Ext, URL: best.URL, Auto: auto})
+		}
+	}
+	return subs
+}
+
+// subtitleExtRank orders subtitle formats by preference (lower is better)
+func subtitleExtRank(ext string) int {
+	switch ext {
+	case "vtt":
+		return 0
+	case "srt":
+		return 1
+	default:
+		return 2
+	}
+}
+
+func (info *ytdlpInfo) toPlaylist() *PlaylistMedia {
+	p := &PlaylistMedia{
+		ID:        info.ID,
+		Title:     info.Title,
+		Uploader:  info.uploader(),
+		Thumbnail: info.Thumbnail,
+	}
+	for i := range info.Entries {
+		entry := &info.Entries[i]
+		link := firstNonEmpty(entry.WebpageURL, entry.URL)
+		if link == "" {
+			continue
+		}
+		pe := PlaylistEntry{
+			Title:     entry.Title,
+			URL:       link,
+			Thumbnail: entry.Thumbnail,
+			Duration:  int(entry.Duration),
+			Published: entry.published(),
+		}
+		p.Entries = append(p.Entries, pe)
+	}
+	return p
+}
+
+// ytdlpQuality labels a format like "1080p" or "1080p [mp4]" with the note
+func ytdlpQuality(f *ytdlpFormat) string {
+	label := f.FormatNote
+	if f.Height > 0 {
+		label = fmt.Sprintf("%dp", f.Height)
+	}
+	if label == "" {
+		label = f.FormatID
+	}
+	if f.Ext != "" {
+		label += " [" + f.Ext + "]"
+	}
+	return label
+}
@@ -0,0 +1,136 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Limits of AdjustOptions
+const (
+	MinSpeed = 0.25
+	MaxSpeed = 4
+	MaxGain  = 40 // dB either way
+)
+
+// AdjustOptions controls Adjust
+type AdjustOptions struct {
+	Speed float64 // playback speed, e.g. 1.5; 0 or 1 keeps it
+	Gain  float64 // volume change in dB, e.g. 6 or -3
+}
+
+// speedChanged reports whether opts changes the playback speed
+func (o AdjustOptions) speedChanged() bool {
+	return o.Speed != 0 && o.Speed != 1
+}
+
+// ValidateAdjust checks opts changes something, within the limits
+func ValidateAdjust(opts AdjustOptions) error {
+	if !opts.speedChanged() && opts.Gain == 0 {
+		return fmt.Errorf("nothing to change: set a speed or a gain")
+	}
+	if opts.speedChanged() && (opts.Speed < MinSpeed || opts.Speed > MaxSpeed) {
+		return fmt.Errorf("speed must be between %g and %g", MinSpeed, MaxSpeed)
+	}
+	if opts.Gain < -MaxGain || opts.Gain > MaxGain {
+		return fmt.Errorf("gain must be between %d and %d dB", -MaxGain, MaxGain)
+	}
+	return nil
+}
+
+// formatFactor formats a speed or gain for a filter
+func formatFactor(f float64) string {
+	return strconv.FormatFloat(f, 'f', -1, 64)
+}
+
+// atempoFilters changes the audio tempo by speed with atempo filters of 0.5
+// to 2 each, the range every ffmpeg version takes
+func atempoFilters(speed float64) []string {
+	var filters []string
+	for ; speed > 2; speed /= 2 {
+		filters = append(filters, "atempo=2")
+	}
+	for ; speed < 0.5; speed /= 0.5 {
+		filters = append(filters, "atempo=0.5")
+	}
+	if speed != 1 || len(filters) == 0 {
+		filters = append(filters, "atempo="+formatFactor(speed))
+	}
+	return filters
+}
+
+// adjustFilters are the video and audio filters applying opts; the video
+// filter is empty when the speed stays
+func adjustFilters(opts AdjustOptions) (video, audio string) {
+	var af []string
+	if opts.speedChanged() {
+		video = "setpts=PTS/" + formatFactor(opts.Speed)
+		af = atempoFilters(opts.Speed)
+	}
+	if opts.Gain != 0 {
+		af = append(af, "volume="+formatFactor(opts.Gain)+"dB")
+	}
+	return video, strings.Join(af, ",")
+}
+
+// AdjustedPath is where input with opts applied goes by default, e.g.
+// "<name> (1.5x).mp4" or "<name> (+6dB).mp3"
+func AdjustedPath(input string, opts AdjustOptions) string {
+	var changes []string
+	if opts.speedChanged() {
+		changes = append(changes, fmt.Sprintf("%gx", opts.Speed))
+	}
+	if opts.Gain != 0 {
+		changes = append(changes, fmt.Sprintf("%+gdB", opts.Gain))
+	}
+	ext := filepath.Ext(input)
+	return strings.TrimSuffix(input, ext) + " (" + strings.Join(changes, ", ") + ")" + ext
+}
+
+// Adjust changes the playback speed and volume of input, keeping the pitch
+// of the audio. Audio is re-encoded with the output format's default codec;
+// video is re-encoded only when the speed changes, and copied otherwise.
+func Adjust(ctx context.Context, input, output string, opts AdjustOptions, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if err := ValidateAdjust(opts); err != nil {
+		return err
+	}
+	video, audio := adjustFilters(opts)
+
+	// ffmpeg reports its position in the output, which runs at the new speed
+	if progressFn != nil && opts.speedChanged() {
+		report := progressFn
+		progressFn = func(done, total time.Duration) {
+			report(min(time.Duration(float64(done)*opts.Speed), total), total)
+		}
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input}
+		if IsAudioFile(output) {
+			args = append(args, "-map", "0:a")
+		} else {
+			args = append(args, "-map", "0:v?", "-map", "0:a?")
+			if video != "" {
+				args = append(args, "-filter:v", video)
+			} else {
+				args = append(args, "-c:v", "copy")
+			}
+		}
+		args = append(args, "-filter:a", audio)
+		if strings.EqualFold(filepath.Ext(output), ".mp4") {
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg speed and volume adjustment failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
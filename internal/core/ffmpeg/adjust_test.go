@@ -0,0 +1,34 @@
+package ffmpeg
+
+import "testing"
+
+func TestAdjustFilters(t *testing.T) {
+	tests := []struct {
+		opts         AdjustOptions
+		video, audio string
+		path         string
+	}{
+		{AdjustOptions{Speed: 1.5}, "setpts=PTS/1.5", "atempo=1.5", "talk (1.5x).mp4"},
+		{AdjustOptions{Speed: 3, Gain: 6}, "setpts=PTS/3", "atempo=2,atempo=1.5,volume=6dB", "talk (3x, +6dB).mp4"},
+		{AdjustOptions{Speed: 0.25}, "setpts=PTS/0.25", "atempo=0.5,atempo=0.5", "talk (0.25x).mp4"},
+		{AdjustOptions{Speed: 1, Gain: -3.5}, "", "volume=-3.5dB", "talk (-3.5dB).mp4"},
+	}
+	for _, tt := range tests {
+		video, audio := adjustFilters(tt.opts)
+		if video != tt.video || audio != tt.audio {
+			t.Errorf("adjustFilters(%+v) = %q, %q, want %q, %q", tt.opts, video, audio, tt.video, tt.audio)
+		}
+		if got := AdjustedPath("talk.mp4", tt.opts); got != tt.path {
+			t.Errorf("AdjustedPath(%+v) = %q, want %q", tt.opts, got, tt.path)
+		}
+	}
+
+	for _, opts := range []AdjustOptions{{}, {Speed: 1}, {Speed: 8}, {Gain: 50}} {
+		if err := ValidateAdjust(opts); err == nil {
+			t.Errorf("ValidateAdjust(%+v) succeeded", opts)
+		}
+	}
+	if err := ValidateAdjust(AdjustOptions{Speed: 2, Gain: -6}); err != nil {
+		t.Errorf("ValidateAdjust() = %v", err)
+	}
+}
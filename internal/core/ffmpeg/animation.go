@@ -0,0 +1,91 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// Animation formats Animate can write
+const (
+	AnimationGIF  = "gif"
+	AnimationWebP = "webp"
+)
+
+// Defaults for AnimationOptions left unset
+const (
+	DefaultAnimationFPS   = 12
+	DefaultAnimationWidth = 480
+)
+
+// AnimationOptions controls Animate
+type AnimationOptions struct {
+	Start    time.Duration
+	Duration time.Duration // 0 for the rest of the video; keep it short, animations get large fast
+	FPS      int
+	Width    int // height follows the aspect ratio; the video is never scaled up
+}
+
+// AnimationFormat validates an animation format, taking it from output's
+// extension when format is empty
+func AnimationFormat(format, output string) (string, error) {
+	if format == "" {
+		format = strings.TrimPrefix(strings.ToLower(filepath.Ext(output)), ".")
+	}
+	switch format = strings.ToLower(format); format {
+	case AnimationGIF, AnimationWebP:
+		return format, nil
+	}
+	return "", fmt.Errorf("unsupported animation format %q (use gif or webp)", format)
+}
+
+// Animate turns a stretch of a video into an animated GIF or WebP, silent and
+// looping. GIFs get a palette generated for the clip, so colors hold up far
+// better than with the fixed default palette.
+func Animate(ctx context.Context, input, output string, opts AnimationOptions, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	format, err := AnimationFormat("", output)
+	if err != nil {
+		return err
+	}
+	if opts.FPS <= 0 {
+		opts.FPS = DefaultAnimationFPS
+	}
+	if opts.Width <= 0 {
+		opts.Width = DefaultAnimationWidth
+	}
+
+	var end time.Duration
+	if opts.Duration > 0 {
+		end = opts.Start + opts.Duration
+	}
+	progressFn = clipProgress(progressFn, opts.Start, end)
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-ss", formatSeconds(opts.Start)}
+		if opts.Duration > 0 {
+			args = append(args, "-t", formatSeconds(opts.Duration))
+		}
+		args = append(args, "-i", input, "-an")
+
+		base := fmt.Sprintf("fps=%d,scale='min(iw,%d)':-2:flags=lanczos", opts.FPS, opts.Width)
+		if format == AnimationGIF {
+			// One pass: split the frames, build a palette from one copy, and
+			// map the other onto it
+			args = append(args, "-filter_complex",
+				base+",split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle")
+		} else {
+			args = append(args, "-vf", base, "-c:v", "libwebp", "-lossless", "0", "-q:v", "75")
+		}
+		args = append(args, "-loop", "0", "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg %s export failed: %w\nOutput: %s", format, err, out)
+		}
+		return nil
+	})
+}
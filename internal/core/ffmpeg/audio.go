@@ -0,0 +1,106 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// Audio formats ExtractAudio can write
+const (
+	AudioMP3  = "mp3"
+	AudioM4A  = "m4a"
+	AudioOpus = "opus"
+)
+
+// audioCodecs maps each audio format to its ffmpeg encoder
+var audioCodecs = map[string]string{
+	AudioMP3:  "libmp3lame",
+	AudioM4A:  "aac",
+	AudioOpus: "libopus",
+}
+
+// AudioOptions controls ExtractAudio. Zero values keep ffmpeg's defaults.
+type AudioOptions struct {
+	Format   string `json:"format"`             // mp3, m4a, or opus; "" picks it from the output extension
+	Bitrate  string `json:"bitrate,omitempty"`  // e.g. "192k"
+	Channels int    `json:"channels,omitempty"` // 1 for mono
+}
+
+// AudioPresets are named AudioOptions without a format
+var AudioPresets = map[string]AudioOptions{
+	"podcast": {Bitrate: "64k", Channels: 1},
+	"music":   {Bitrate: "320k"},
+}
+
+// ApplyAudioPreset fills the unset fields of opts from the named preset
+func ApplyAudioPreset(opts AudioOptions, preset string) (AudioOptions, error) {
+	if preset == "" {
+		return opts, nil
+	}
+	p, ok := AudioPresets[preset]
+	if !ok {
+		return opts, fmt.Errorf("unknown audio preset %q (use podcast or music)", preset)
+	}
+	if opts.Bitrate == "" {
+		opts.Bitrate = p.Bitrate
+	}
+	if opts.Channels == 0 {
+		opts.Channels = p.Channels
+	}
+	return opts, nil
+}
+
+// AudioFormat validates an audio format, taking it from output's extension
+// when format is empty
+func AudioFormat(format, output string) (string, error) {
+	if format == "" {
+		format = strings.TrimPrefix(strings.ToLower(filepath.Ext(output)), ".")
+	}
+	format = strings.ToLower(format)
+	if _, ok := audioCodecs[format]; !ok {
+		return "", fmt.Errorf("unsupported audio format %q (use mp3, m4a, or opus)", format)
+	}
+	return format, nil
+}
+
+// ExtractAudio re-encodes the audio of input, dropping any video, into output.
+// Cover art of the input is kept in MP3 and M4A output.
+func ExtractAudio(ctx context.Context, input, output string, opts AudioOptions, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	format, err := AudioFormat(opts.Format, output)
+	if err != nil {
+		return err
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input, "-map", "0:a:0"}
+		if cover := coverStream(ctx, input); cover >= 0 && CanHoldCover(output) {
+			args = append(args, "-map", "0:"+strconv.Itoa(cover), "-c:v", "copy")
+			args = append(args, coverArgs(filepath.Ext(output))...)
+		} else {
+			args = append(args, "-vn")
+		}
+		args = append(args, "-c:a", audioCodecs[format])
+		if opts.Bitrate != "" {
+			args = append(args, "-b:a", opts.Bitrate)
+		}
+		if opts.Channels > 0 {
+			args = append(args, "-ac", strconv.Itoa(opts.Channels))
+		}
+		if format == AudioM4A {
+			// Lets players start before the whole file is read
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg audio extraction failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
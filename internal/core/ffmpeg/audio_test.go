@@ -0,0 +1,45 @@
+package ffmpeg
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestAudioOptions(t *testing.T) {
+	opts, err := ApplyAudioPreset(AudioOptions{Bitrate: "96k"}, "podcast")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if opts.Bitrate != "96k" || opts.Channels != 1 {
+		t.Errorf("ApplyAudioPreset() = %+v, want the explicit bitrate kept and mono from the preset", opts)
+	}
+	if _, err := ApplyAudioPreset(AudioOptions{}, "audiobook"); err == nil {
+		t.Error("ApplyAudioPreset() with an unknown preset succeeded")
+	}
+
+	tests := []struct {
+		format, output, want string
+	}{
+		{"", "talk.M4A", AudioM4A},
+		{"OPUS", "talk.ogg", AudioOpus},
+		{"wav", "", ""},
+	}
+	for _, tt := range tests {
+		got, err := AudioFormat(tt.format, tt.output)
+		if got != tt.want || (err != nil) != (tt.want == "") {
+			t.Errorf("AudioFormat(%q, %q) = %q, %v, want %q", tt.format, tt.output, got, err, tt.want)
+		}
+	}
+}
+
+func TestCoverArgs(t *testing.T) {
+	if !CanHoldCover("episode.MP3") || CanHoldCover("episode.opus") {
+		t.Error("CanHoldCover() should accept mp3 and reject opus")
+	}
+	if got := strings.Join(coverArgs(".m4a"), " "); got != "-disposition:v:0 attached_pic" {
+		t.Errorf("coverArgs(.m4a) = %q", got)
+	}
+	if got := strings.Join(coverArgs(".mp3"), " "); !strings.Contains(got, "-id3v2_version 3") {
+		t.Errorf("coverArgs(.mp3) = %q, want ID3v2.3", got)
+	}
+}
@@ -0,0 +1,78 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+)
+
+// mediaExtensions are the audio and video files MediaFiles picks up
+var mediaExtensions = map[string]bool{
+	".mp3": true, ".m4a": true, ".wav": true, ".aac": true, ".ogg": true, ".flac": true, ".opus": true,
+	".mp4": true, ".webm": true, ".mkv": true, ".avi": true, ".mov": true, ".flv": true, ".ts": true,
+}
+
+// MediaFiles lists the audio and video files directly in dir, by name
+func MediaFiles(dir string) ([]string, error) {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return nil, err
+	}
+	var files []string
+	for _, entry := range entries {
+		if !entry.IsDir() && mediaExtensions[strings.ToLower(filepath.Ext(entry.Name()))] {
+			files = append(files, filepath.Join(dir, entry.Name()))
+		}
+	}
+	slices.Sort(files)
+	return files, nil
+}
+
+// BatchPreset is what a batch conversion does to each file: re-encode it with
+// a video preset, or extract its audio with an audio preset
+type BatchPreset struct {
+	Name  string
+	Video *VideoPreset // nil for audio presets
+	Audio AudioOptions
+}
+
+// FindBatchPreset looks up a video preset, else an audio preset writing
+// format (mp3 when empty)
+func FindBatchPreset(name, format string) (BatchPreset, error) {
+	if video, err := FindVideoPreset(name); err == nil {
+		return BatchPreset{Name: video.Name, Video: &video}, nil
+	}
+	if _, ok := AudioPresets[name]; !ok {
+		return BatchPreset{}, fmt.Errorf("unknown preset %q (use a video preset, podcast, or music)", name)
+	}
+	if format == "" {
+		format = AudioMP3
+	}
+	format, err := AudioFormat(format, "")
+	if err != nil {
+		return BatchPreset{}, err
+	}
+	opts, err := ApplyAudioPreset(AudioOptions{Format: format}, name)
+	return BatchPreset{Name: name, Audio: opts}, err
+}
+
+// OutputPath is where the conversion of input goes by default
+func (p BatchPreset) OutputPath(input string) string {
+	if p.Video != nil {
+		return VideoPath(input, *p.Video)
+	}
+	return SwapExt(input, p.Audio.Format)
+}
+
+// Convert converts input into output with the preset, on a hardware encoder
+// when a video preset has one
+func (p BatchPreset) Convert(ctx context.Context, input, output string, progressFn ProgressFunc) error {
+	if p.Video != nil {
+		_, err := Transcode(ctx, input, output, *p.Video, true, progressFn)
+		return err
+	}
+	return ExtractAudio(ctx, input, output, p.Audio, progressFn)
+}
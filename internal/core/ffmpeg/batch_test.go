@@ -0,0 +1,43 @@
+package ffmpeg
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+)
+
+func TestMediaFiles(t *testing.T) {
+	dir := t.TempDir()
+	for _, name := range []string{"b.MKV", "a.mp3", "notes.txt", "c.mp4.part"} {
+		if err := os.WriteFile(filepath.Join(dir, name), nil, 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := os.Mkdir(filepath.Join(dir, "d.mp4"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+
+	got, err := MediaFiles(dir)
+	want := []string{filepath.Join(dir, "a.mp3"), filepath.Join(dir, "b.MKV")}
+	if err != nil || !slices.Equal(got, want) {
+		t.Errorf("MediaFiles() = %v, %v, want %v", got, err, want)
+	}
+}
+
+func TestFindBatchPreset(t *testing.T) {
+	p, err := FindBatchPreset("web", "")
+	if err != nil || p.Video == nil || p.OutputPath("clip.webm") != "clip.mp4" {
+		t.Errorf("FindBatchPreset(web) = %+v, %v, want the web video preset", p, err)
+	}
+	p, err = FindBatchPreset("podcast", "m4a")
+	if err != nil || p.Video != nil || p.Audio.Channels != 1 || p.OutputPath("talk.mp4") != "talk.m4a" {
+		t.Errorf("FindBatchPreset(podcast, m4a) = %+v, %v, want mono m4a", p, err)
+	}
+	if _, err := FindBatchPreset("podcast", "wav"); err == nil {
+		t.Error("FindBatchPreset() with an unsupported audio format succeeded")
+	}
+	if _, err := FindBatchPreset("tiny", ""); err == nil {
+		t.Error("FindBatchPreset() with an unknown preset succeeded")
+	}
+}
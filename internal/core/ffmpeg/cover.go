@@ -0,0 +1,67 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strings"
+)
+
+// coverContainers are the audio formats that can hold cover art. Ogg can't
+// through ffmpeg, so Opus files go without.
+var coverContainers = map[string]bool{".mp3": true, ".m4a": true, ".m4b": true, ".flac": true}
+
+// CanHoldCover reports whether the audio format of path can hold cover art
+func CanHoldCover(path string) bool {
+	return coverContainers[strings.ToLower(filepath.Ext(path))]
+}
+
+// coverArgs are the output options that make the first video stream the
+// cover art of a file with extension ext
+func coverArgs(ext string) []string {
+	args := []string{"-disposition:v:0", "attached_pic"}
+	if strings.EqualFold(ext, ".mp3") {
+		// ID3v2.3 is what most players and file browsers read; the APIC frame
+		// takes its description and picture type from the stream's tags
+		args = append(args, "-id3v2_version", "3", "-metadata:s:v", "title=Album cover", "-metadata:s:v", "comment=Cover (front)")
+	}
+	return args
+}
+
+// coverStream is the index of the JPEG or PNG cover art of input, or -1 if
+// it has none
+func coverStream(ctx context.Context, input string) int {
+	streams, err := ProbeStreams(ctx, input)
+	if err != nil {
+		return -1
+	}
+	for _, s := range streams {
+		if s.Cover && (s.Codec == "mjpeg" || s.Codec == "png") {
+			return s.Index
+		}
+	}
+	return -1
+}
+
+// EmbedCover makes image the cover art of the audio file input, writing
+// output, which may be input. A cover input already has is replaced. The
+// image is converted to JPEG, which every format and player takes.
+func EmbedCover(ctx context.Context, input, output, image string) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if !CanHoldCover(output) {
+		return fmt.Errorf("%s can't hold cover art (use mp3, m4a, or flac)", filepath.Ext(output))
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input, "-i", image, "-map", "0:a", "-map", "1:v:0", "-c:a", "copy", "-c:v", "mjpeg", "-q:v", "2"}
+		args = append(args, coverArgs(filepath.Ext(output))...)
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, nil); err != nil {
+			return fmt.Errorf("ffmpeg cover art embedding failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
@@ -0,0 +1,164 @@
+// Package ffmpeg runs ffmpeg on downloaded media: merging, audio extraction,
+// and other conversions of finished files
+package ffmpeg
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Available reports whether ffmpeg is in PATH or was installed by Install
+func Available() bool {
+	return findBinary("ffmpeg") != ""
+}
+
+// ProgressFunc receives how much of the output ffmpeg has written, as media
+// time, and the length of the first input (0 if ffmpeg didn't report it)
+type ProgressFunc func(done, total time.Duration)
+
+// Run runs ffmpeg with args until it exits or ctx is done, reporting
+// progress to progressFn if it's not nil. It returns ffmpeg's log for error
+// messages.
+func Run(ctx context.Context, args []string, progressFn ProgressFunc) ([]byte, error) {
+	if progressFn == nil {
+		return exec.CommandContext(ctx, Binary(), args...).CombinedOutput()
+	}
+
+	// -progress writes key=value lines to stdout; the log stays on stderr,
+	// where the input durations are read from
+	cmd := exec.CommandContext(ctx, Binary(), append([]string{"-progress", "pipe:1", "-nostats"}, args...)...)
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return nil, err
+	}
+	stderr, err := cmd.StderrPipe()
+	if err != nil {
+		return nil, err
+	}
+	if err := cmd.Start(); err != nil {
+		return nil, err
+	}
+
+	var (
+		mu     sync.Mutex
+		total  time.Duration
+		output bytes.Buffer
+		wg     sync.WaitGroup
+	)
+	wg.Add(1)
+	go func() {
+		defer wg.Done()
+		scanner := bufio.NewScanner(stderr)
+		for scanner.Scan() {
+			line := scanner.Text()
+			mu.Lock()
+			output.WriteString(line + "\n")
+			if d, ok := parseFFmpegDuration(line); ok && total == 0 {
+				total = d
+			}
+			mu.Unlock()
+		}
+		io.Copy(io.Discard, stderr)
+	}()
+
+	scanner := bufio.NewScanner(stdout)
+	for scanner.Scan() {
+		done, ok := parseFFmpegProgress(scanner.Text())
+		if !ok {
+			continue
+		}
+		mu.Lock()
+		t := total
+		mu.Unlock()
+		if t > 0 {
+			done = min(done, t)
+		}
+		progressFn(done, t)
+	}
+	io.Copy(io.Discard, stdout)
+
+	wg.Wait()
+	err = cmd.Wait()
+	return output.Bytes(), err
+}
+
+// SwapExt returns path with its extension replaced by ext, where the output
+// of a conversion goes by default
+func SwapExt(path, ext string) string {
+	return strings.TrimSuffix(path, filepath.Ext(path)) + "." + ext
+}
+
+// PartPath is where ffmpeg writes before the result is moved to output. The
+// extension stays last so ffmpeg still picks the container from it.
+func PartPath(output string) string {
+	ext := filepath.Ext(output)
+	return strings.TrimSuffix(output, ext) + ".part" + ext
+}
+
+// WriteOutput has write create output at the path it's given, PartPath, and
+// moves the result into place once write succeeds, so an interrupted
+// conversion never leaves a truncated file under the final name
+func WriteOutput(output string, write func(part string) error) error {
+	part := PartPath(output)
+	if err := write(part); err != nil {
+		os.Remove(part)
+		return err
+	}
+	if err := os.Rename(part, output); err != nil {
+		os.Remove(part)
+		return fmt.Errorf("failed to move output into place: %w", err)
+	}
+	return nil
+}
+
+// parseFFmpegDuration reads an input's length from a log line such as
+// "  Duration: 00:03:25.47, start: 0.000000, bitrate: 1234 kb/s"
+func parseFFmpegDuration(line string) (time.Duration, bool) {
+	_, rest, ok := strings.Cut(strings.TrimSpace(line), "Duration: ")
+	if !ok {
+		return 0, false
+	}
+	value, _, _ := strings.Cut(rest, ",")
+	return parseFFmpegTime(value)
+}
+
+// parseFFmpegProgress reads the output position from a -progress line,
+// "out_time_ms=12345678". Despite its name the value is in microseconds;
+// it's used over out_time_us, which older versions lack.
+func parseFFmpegProgress(line string) (time.Duration, bool) {
+	key, value, ok := strings.Cut(strings.TrimSpace(line), "=")
+	if !ok || key != "out_time_ms" {
+		return 0, false
+	}
+	us, err := strconv.ParseInt(value, 10, 64)
+	if err != nil || us < 0 {
+		// "N/A" before the first frame is written
+		return 0, false
+	}
+	return time.Duration(us) * time.Microsecond, true
+}
+
+// parseFFmpegTime parses ffmpeg's HH:MM:SS.xx time format
+func parseFFmpegTime(value string) (time.Duration, bool) {
+	parts := strings.Split(strings.TrimSpace(value), ":")
+	if len(parts) != 3 {
+		return 0, false
+	}
+	hours, err1 := strconv.Atoi(parts[0])
+	minutes, err2 := strconv.Atoi(parts[1])
+	seconds, err3 := strconv.ParseFloat(parts[2], 64)
+	if err1 != nil || err2 != nil || err3 != nil {
+		return 0, false
+	}
+	return time.Duration(hours)*time.Hour + time.Duration(minutes)*time.Minute + time.Duration(seconds*float64(time.Second)), true
+}
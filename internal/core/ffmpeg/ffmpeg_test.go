@@ -0,0 +1,24 @@
+package ffmpeg
+
+import (
+	"testing"
+	"time"
+)
+
+func TestParseFFmpegProgress(t *testing.T) {
+	if got, ok := parseFFmpegDuration("  Duration: 00:03:25.50, start: 0.000000, bitrate: 1234 kb/s"); !ok || got != 3*time.Minute+25500*time.Millisecond {
+		t.Errorf("parseFFmpegDuration() = %v, %v, want 3m25.5s", got, ok)
+	}
+	if _, ok := parseFFmpegDuration("  Duration: N/A, bitrate: N/A"); ok {
+		t.Error("parseFFmpegDuration() of N/A succeeded")
+	}
+
+	if got, ok := parseFFmpegProgress("out_time_ms=12500000"); !ok || got != 12500*time.Millisecond {
+		t.Errorf("parseFFmpegProgress() = %v, %v, want 12.5s", got, ok)
+	}
+	for _, line := range []string{"out_time_ms=N/A", "out_time=00:00:12.500000", "progress=continue"} {
+		if _, ok := parseFFmpegProgress(line); ok {
+			t.Errorf("parseFFmpegProgress(%q) succeeded", line)
+		}
+	}
+}
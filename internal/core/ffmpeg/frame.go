@@ -0,0 +1,71 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// Image formats CaptureFrame can write
+const (
+	FramePNG  = "png"
+	FrameJPEG = "jpg"
+)
+
+// FrameFormat validates an image format, taking it from output's extension
+// when format is empty
+func FrameFormat(format, output string) (string, error) {
+	if format == "" {
+		format = strings.TrimPrefix(strings.ToLower(filepath.Ext(output)), ".")
+	}
+	switch format = strings.ToLower(format); format {
+	case FramePNG:
+		return FramePNG, nil
+	case FrameJPEG, "jpeg":
+		return FrameJPEG, nil
+	}
+	return "", fmt.Errorf("unsupported image format %q (use png or jpg)", format)
+}
+
+// FramePath is where the frame of input at at goes by default, e.g.
+// "talk (1m30s).png" next to it
+func FramePath(input string, at time.Duration, format string) string {
+	return fmt.Sprintf("%s (%s).%s", strings.TrimSuffix(input, filepath.Ext(input)), at.Round(time.Millisecond), format)
+}
+
+// CaptureFrame saves the frame of input at at as a PNG or JPEG, picked by
+// output's extension. A width above 0 scales the frame down to it, keeping
+// the aspect ratio.
+func CaptureFrame(ctx context.Context, input, output string, at time.Duration, width int) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	format, err := FrameFormat("", output)
+	if err != nil {
+		return err
+	}
+
+	return WriteOutput(output, func(part string) error {
+		// -ss before -i seeks by keyframe, then decodes up to at exactly
+		args := []string{"-ss", formatSeconds(at), "-i", input, "-map", "0:v:0", "-frames:v", "1"}
+		if width > 0 {
+			args = append(args, "-vf", fmt.Sprintf("scale='min(iw,%d)':-2", width))
+		}
+		if format == FrameJPEG {
+			args = append(args, "-q:v", "2")
+		}
+		args = append(args, "-update", "1", "-y", part)
+
+		if out, err := Run(ctx, args, nil); err != nil {
+			return fmt.Errorf("ffmpeg frame capture failed: %w\nOutput: %s", err, out)
+		}
+		// Seeking past the end writes nothing without failing
+		if info, err := os.Stat(part); err != nil || info.Size() == 0 {
+			return fmt.Errorf("no frame at %s", at)
+		}
+		return nil
+	})
+}
@@ -0,0 +1,26 @@
+package ffmpeg
+
+import (
+	"testing"
+	"time"
+)
+
+func TestFrameFormat(t *testing.T) {
+	tests := []struct {
+		format, output, want string
+	}{
+		{"", "still.PNG", FramePNG},
+		{"jpeg", "", FrameJPEG},
+		{"", "still.webp", ""},
+	}
+	for _, tt := range tests {
+		got, err := FrameFormat(tt.format, tt.output)
+		if got != tt.want || (err != nil) != (tt.want == "") {
+			t.Errorf("FrameFormat(%q, %q) = %q, %v, want %q", tt.format, tt.output, got, err, tt.want)
+		}
+	}
+
+	if got, want := FramePath("/videos/talk.mp4", 90*time.Second, FramePNG), "/videos/talk (1m30s).png"; got != want {
+		t.Errorf("FramePath() = %q, want %q", got, want)
+	}
+}
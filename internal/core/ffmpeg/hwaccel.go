@@ -0,0 +1,152 @@
+package ffmpeg
+
+import (
+	"context"
+	"os/exec"
+	"runtime"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Hardware encoding APIs
+const (
+	HardwareVideoToolbox = "videotoolbox" // macOS
+	HardwareNVENC        = "nvenc"        // NVIDIA
+	HardwareQSV          = "qsv"          // Intel Quick Sync
+	HardwareVAAPI        = "vaapi"        // Linux, Intel and AMD
+)
+
+// vaapiDevice is the render node VAAPI encoders open
+const vaapiDevice = "/dev/dri/renderD128"
+
+// encoderProbeTimeout bounds the test encode of one hardware encoder
+const encoderProbeTimeout = 15 * time.Second
+
+// Encoder is an ffmpeg video encoder
+type Encoder struct {
+	Name     string `json:"name"` // e.g. h264_nvenc
+	Codec    string `json:"codec"`
+	Hardware string `json:"hardware,omitempty"` // "" for software encoders
+}
+
+// hardwareEncoders are the candidates, in order of preference per codec
+var hardwareEncoders = []Encoder{
+	{Name: "h264_videotoolbox", Codec: CodecH264, Hardware: HardwareVideoToolbox},
+	{Name: "hevc_videotoolbox", Codec: CodecHEVC, Hardware: HardwareVideoToolbox},
+	{Name: "h264_nvenc", Codec: CodecH264, Hardware: HardwareNVENC},
+	{Name: "hevc_nvenc", Codec: CodecHEVC, Hardware: HardwareNVENC},
+	{Name: "av1_nvenc", Codec: CodecAV1, Hardware: HardwareNVENC},
+	{Name: "h264_qsv", Codec: CodecH264, Hardware: HardwareQSV},
+	{Name: "hevc_qsv", Codec: CodecHEVC, Hardware: HardwareQSV},
+	{Name: "av1_qsv", Codec: CodecAV1, Hardware: HardwareQSV},
+	{Name: "h264_vaapi", Codec: CodecH264, Hardware: HardwareVAAPI},
+	{Name: "hevc_vaapi", Codec: CodecHEVC, Hardware: HardwareVAAPI},
+	{Name: "av1_vaapi", Codec: CodecAV1, Hardware: HardwareVAAPI},
+}
+
+var (
+	detectMu     sync.Mutex
+	detected     []Encoder
+	detectedWith string // the ffmpeg binary detected was tested with
+)
+
+// HardwareEncoders returns the hardware encoders that work on this machine.
+// The first call tests each one ffmpeg was built with by encoding a frame,
+// since a listed encoder still needs the GPU and driver; later calls return
+// the same result until the ffmpeg in use changes, e.g. after Install.
+func HardwareEncoders() []Encoder {
+	detectMu.Lock()
+	defer detectMu.Unlock()
+	if bin := Binary(); bin != detectedWith {
+		detected = detectEncoders()
+		detectedWith = bin
+	}
+	return detected
+}
+
+// HardwareEncoder returns the preferred working hardware encoder for codec
+func HardwareEncoder(codec string) (Encoder, bool) {
+	for _, enc := range HardwareEncoders() {
+		if enc.Codec == codec {
+			return enc, true
+		}
+	}
+	return Encoder{}, false
+}
+
+// detectEncoders tests the hardware encoders this platform can have that
+// ffmpeg lists
+func detectEncoders() []Encoder {
+	if !Available() {
+		return nil
+	}
+	out, err := exec.Command(Binary(), "-hide_banner", "-encoders").Output()
+	if err != nil {
+		return nil
+	}
+	listed := make(map[string]bool)
+	for _, line := range strings.Split(string(out), "\n") {
+		// " V....D h264_nvenc           NVIDIA NVENC H.264 encoder"
+		if fields := strings.Fields(line); len(fields) >= 2 {
+			listed[fields[1]] = true
+		}
+	}
+
+	var working []Encoder
+	for _, enc := range hardwareEncoders {
+		if listed[enc.Name] && platformHasHardware(enc.Hardware) && enc.works() {
+			working = append(working, enc)
+		}
+	}
+	return working
+}
+
+// platformHasHardware reports whether hardware can exist on this OS
+func platformHasHardware(hardware string) bool {
+	switch hardware {
+	case HardwareVideoToolbox:
+		return runtime.GOOS == "darwin"
+	case HardwareVAAPI:
+		return runtime.GOOS == "linux"
+	default:
+		return runtime.GOOS != "darwin"
+	}
+}
+
+// works encodes one generated frame with enc
+func (enc Encoder) works() bool {
+	ctx, cancel := context.WithTimeout(context.Background(), encoderProbeTimeout)
+	defer cancel()
+
+	args := []string{"-hide_banner", "-loglevel", "error"}
+	if enc.Hardware == HardwareVAAPI {
+		args = append(args, "-vaapi_device", vaapiDevice)
+	}
+	// Small sizes are below some encoders' minimum
+	args = append(args, "-f", "lavfi", "-i", "color=black:s=640x360:d=0.1", "-frames:v", "1")
+	if enc.Hardware == HardwareVAAPI {
+		args = append(args, "-vf", "format=nv12,hwupload")
+	}
+	args = append(args, "-c:v", enc.Name, "-f", "null", "-")
+	return exec.CommandContext(ctx, Binary(), args...).Run() == nil
+}
+
+// qualityArgs are enc's options for about the quality of crf on the x264
+// scale, as each API has its own constant-quality mode
+func (enc Encoder) qualityArgs(crf int) []string {
+	q := strconv.Itoa(crf)
+	switch enc.Hardware {
+	case HardwareNVENC:
+		return []string{"-rc", "vbr", "-cq", q, "-b:v", "0"}
+	case HardwareQSV:
+		return []string{"-global_quality", q}
+	case HardwareVAAPI:
+		return []string{"-rc_mode", "CQP", "-qp", q}
+	case HardwareVideoToolbox:
+		// 1-100, higher is better
+		return []string{"-q:v", strconv.Itoa(min(100, max(1, 100-2*crf)))}
+	}
+	return nil
+}
@@ -0,0 +1,156 @@
+package ffmpeg
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Loudness defaults, following the usual levels for podcasts
+const (
+	DefaultLoudnessTarget = -16.0 // integrated loudness, LUFS
+	loudnessTruePeak      = -1.5  // dBTP
+	loudnessRange         = 11.0  // LU
+)
+
+// audioExts are the extensions of audio-only files
+var audioExts = map[string]bool{
+	".mp3": true, ".m4a": true, ".m4b": true, ".aac": true, ".opus": true,
+	".ogg": true, ".oga": true, ".flac": true, ".wav": true,
+}
+
+// IsAudioFile reports whether path is an audio-only file, by its extension
+func IsAudioFile(path string) bool {
+	return audioExts[strings.ToLower(filepath.Ext(path))]
+}
+
+// NormalizedPath is where input with normalized loudness goes by default
+func NormalizedPath(input string) string {
+	ext := filepath.Ext(input)
+	return strings.TrimSuffix(input, ext) + " (normalized)" + ext
+}
+
+// loudnessEncoders are the encoders that write audio back in its codec after
+// normalizing; PCM codecs are their own encoders
+var loudnessEncoders = map[string]string{
+	"mp3":    "libmp3lame",
+	"aac":    "aac",
+	"opus":   "libopus",
+	"vorbis": "libvorbis",
+	"flac":   "flac",
+	"alac":   "alac",
+}
+
+// losslessCodecs need no bitrate to keep their quality
+var losslessCodecs = map[string]bool{"flac": true, "alac": true}
+
+// loudnormStats are the measurements of loudnorm's first pass
+type loudnormStats struct {
+	InputI       string `json:"input_i"`
+	InputTP      string `json:"input_tp"`
+	InputLRA     string `json:"input_lra"`
+	InputThresh  string `json:"input_thresh"`
+	TargetOffset string `json:"target_offset"`
+}
+
+// parseLoudnormStats reads the JSON block loudnorm prints at the end of the
+// log of a pass with print_format=json
+func parseLoudnormStats(log []byte) (loudnormStats, error) {
+	var stats loudnormStats
+	start := bytes.LastIndexByte(log, '{')
+	end := bytes.LastIndexByte(log, '}')
+	if start < 0 || end < start {
+		return stats, fmt.Errorf("no loudness measurements in ffmpeg output")
+	}
+	if err := json.Unmarshal(log[start:end+1], &stats); err != nil {
+		return stats, fmt.Errorf("failed to parse loudness measurements: %w", err)
+	}
+	if _, err := strconv.ParseFloat(stats.InputI, 64); err != nil {
+		// "-inf" for silence
+		return stats, fmt.Errorf("can't measure the loudness of silence")
+	}
+	return stats, nil
+}
+
+// NormalizeLoudness evens out the volume of the first audio track of input
+// to target LUFS (0 for DefaultLoudnessTarget) with ffmpeg's loudnorm filter
+// in two passes: the first measures the whole file, so the second can apply
+// one linear gain rather than adjusting as it goes. The audio is re-encoded
+// in its codec at its bitrate and sample rate; any video is copied. output
+// may be input, to normalize in place.
+func NormalizeLoudness(ctx context.Context, input, output string, target float64, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if target == 0 {
+		target = DefaultLoudnessTarget
+	}
+
+	streams, err := ProbeStreams(ctx, input)
+	if err != nil {
+		return err
+	}
+	var audio *Stream
+	for i := range streams {
+		if streams[i].Type == "audio" {
+			audio = &streams[i]
+			break
+		}
+	}
+	if audio == nil {
+		return fmt.Errorf("no audio to normalize")
+	}
+	encoder := loudnessEncoders[audio.Codec]
+	if strings.HasPrefix(audio.Codec, "pcm_") {
+		encoder = audio.Codec
+	}
+	if encoder == "" {
+		return fmt.Errorf("can't re-encode %s audio after normalizing", audio.Codec)
+	}
+
+	// Each pass reads the whole file; report them as halves of the work
+	var firstHalf, secondHalf ProgressFunc
+	if progressFn != nil {
+		firstHalf = func(done, total time.Duration) { progressFn(done/2, total) }
+		secondHalf = func(done, total time.Duration) { progressFn(total/2+done/2, total) }
+	}
+
+	filter := fmt.Sprintf("loudnorm=I=%g:TP=%g:LRA=%g", target, loudnessTruePeak, loudnessRange)
+	out, err := Run(ctx, []string{"-i", input, "-map", "0:a:0", "-af", filter + ":print_format=json", "-f", "null", "-"}, firstHalf)
+	if err != nil {
+		return fmt.Errorf("ffmpeg loudness measurement failed: %w\nOutput: %s", err, out)
+	}
+	stats, err := parseLoudnormStats(out)
+	if err != nil {
+		return err
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{
+			"-i", input, "-map", "0",
+			"-af", fmt.Sprintf("%s:measured_I=%s:measured_TP=%s:measured_LRA=%s:measured_thresh=%s:offset=%s:linear=true",
+				filter, stats.InputI, stats.InputTP, stats.InputLRA, stats.InputThresh, stats.TargetOffset),
+			"-c", "copy", "-c:a", encoder,
+		}
+		// loudnorm resamples to 192 kHz internally
+		sampleRate := audio.SampleRate
+		if sampleRate <= 0 {
+			sampleRate = 48000
+		}
+		args = append(args, "-ar", strconv.Itoa(sampleRate))
+		if audio.BitRate > 0 && !losslessCodecs[audio.Codec] && !strings.HasPrefix(audio.Codec, "pcm_") {
+			args = append(args, "-b:a", strconv.FormatInt(audio.BitRate, 10))
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, secondHalf); err != nil {
+			return fmt.Errorf("ffmpeg loudness normalization failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
@@ -0,0 +1,36 @@
+package ffmpeg
+
+import "testing"
+
+func TestParseLoudnormStats(t *testing.T) {
+	log := []byte(`size=N/A time=00:42:10.05 bitrate=N/A speed= 312x
+[Parsed_loudnorm_0 @ 0x600000c3c000]
+{
+	"input_i" : "-21.37",
+	"input_tp" : "-3.02",
+	"input_lra" : "6.10",
+	"input_thresh" : "-31.65",
+	"output_i" : "-16.05",
+	"output_tp" : "-1.50",
+	"output_lra" : "5.20",
+	"output_thresh" : "-26.31",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.05"
+}
+`)
+	stats, err := parseLoudnormStats(log)
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := loudnormStats{InputI: "-21.37", InputTP: "-3.02", InputLRA: "6.10", InputThresh: "-31.65", TargetOffset: "0.05"}
+	if stats != want {
+		t.Errorf("parseLoudnormStats() = %+v, want %+v", stats, want)
+	}
+
+	if _, err := parseLoudnormStats([]byte(`{"input_i" : "-inf", "target_offset" : "inf"}`)); err == nil {
+		t.Error("parseLoudnormStats() of silence succeeded")
+	}
+	if _, err := parseLoudnormStats([]byte("Conversion failed!")); err == nil {
+		t.Error("parseLoudnormStats() without measurements succeeded")
+	}
+}
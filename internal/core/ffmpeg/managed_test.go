@@ -0,0 +1,57 @@
+package ffmpeg
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"testing"
+)
+
+func TestParseVersion(t *testing.T) {
+	tests := []struct {
+		name, out, want string
+	}{
+		{"ffmpeg", "ffmpeg version 6.1.1-static https://johnvansickle.com/ffmpeg/  Copyright (c) 2000-2023\nbuilt with gcc 8\n", "6.1.1-static"},
+		{"ffprobe", "ffprobe version n7.0 Copyright (c) 2007-2024\n", "n7.0"},
+		{"ffmpeg", "command not found\n", ""},
+	}
+	for _, tt := range tests {
+		got, err := parseVersion(tt.name, []byte(tt.out))
+		if got != tt.want || (err != nil) != (tt.want == "") {
+			t.Errorf("parseVersion(%q, %q) = %q, %v, want %q", tt.name, tt.out, got, err, tt.want)
+		}
+	}
+}
+
+func TestManagedDigests(t *testing.T) {
+	// Every platform Install supports needs both assets pinned, or none is
+	// pinned yet and Installable keeps the install command hidden
+	assets := map[string]bool{}
+	for _, goos := range managedOS {
+		for _, arch := range managedArch {
+			if goos == "win32" && arch != "x64" && arch != "ia32" {
+				continue
+			}
+			for _, asset := range managedAssets(goos + "-" + arch) {
+				assets[asset] = true
+			}
+		}
+	}
+
+	if len(managedDigests) > 0 {
+		for asset := range assets {
+			if _, ok := managedDigests[asset]; !ok {
+				t.Errorf("no pinned digest for %s", asset)
+			}
+		}
+	} else if Installable() {
+		t.Error("Installable() = true without pinned digests")
+	}
+	for asset, digest := range managedDigests {
+		if !assets[asset] {
+			t.Errorf("digest pinned for unknown asset %s", asset)
+		}
+		if b, err := hex.DecodeString(digest); err != nil || len(b) != sha256.Size {
+			t.Errorf("digest of %s = %q, want a hex SHA-256", asset, digest)
+		}
+	}
+}
@@ -0,0 +1,237 @@
+package ffmpeg
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os/exec"
+	"path/filepath"
+	"slices"
+	"strconv"
+	"strings"
+)
+
+// Containers Remux can write
+const (
+	ContainerMP4  = "mp4"
+	ContainerM4A  = "m4a"
+	ContainerMKV  = "mkv"
+	ContainerWebM = "webm"
+)
+
+// Stream is a stream of a media file, as ffprobe reports it
+type Stream struct {
+	Index      int    `json:"index"`
+	Type       string `json:"type"`  // video, audio, subtitle, data, or attachment
+	Codec      string `json:"codec"` // ffmpeg's codec name, e.g. h264
+	Cover      bool   `json:"cover,omitempty"`
+	BitRate    int64  `json:"bit_rate,omitempty"` // bits per second, if known
+	SampleRate int    `json:"sample_rate,omitempty"`
+}
+
+// ProbeStreams lists the streams of input with ffprobe
+func ProbeStreams(ctx context.Context, input string) ([]Stream, error) {
+	if findBinary("ffprobe") == "" {
+		return nil, fmt.Errorf("ffprobe not found in PATH")
+	}
+	out, err := exec.CommandContext(ctx, ProbeBinary(), "-v", "error",
+		"-show_entries", "stream=index,codec_type,codec_name,bit_rate,sample_rate:stream_disposition=attached_pic",
+		"-of", "json", input).Output()
+	if err != nil {
+		return nil, fmt.Errorf("ffprobe failed: %w", err)
+	}
+
+	var result struct {
+		Streams []struct {
+			Index       int    `json:"index"`
+			CodecType   string `json:"codec_type"`
+			CodecName   string `json:"codec_name"`
+			BitRate     string `json:"bit_rate"`
+			SampleRate  string `json:"sample_rate"`
+			Disposition struct {
+				AttachedPic int `json:"attached_pic"`
+			} `json:"disposition"`
+		} `json:"streams"`
+	}
+	if err := json.Unmarshal(out, &result); err != nil {
+		return nil, fmt.Errorf("failed to parse ffprobe output: %w", err)
+	}
+	streams := make([]Stream, 0, len(result.Streams))
+	for _, s := range result.Streams {
+		// Both are strings, or missing when unknown
+		bitRate, _ := strconv.ParseInt(s.BitRate, 10, 64)
+		sampleRate, _ := strconv.Atoi(s.SampleRate)
+		streams = append(streams, Stream{
+			Index:      s.Index,
+			Type:       s.CodecType,
+			Codec:      s.CodecName,
+			Cover:      s.Disposition.AttachedPic == 1,
+			BitRate:    bitRate,
+			SampleRate: sampleRate,
+		})
+	}
+	return streams, nil
+}
+
+// remuxCodecs are the codecs each container holds, by stream type. A nil
+// list takes any codec. Subtitles are listed separately, as text subtitles
+// are converted between formats rather than copied.
+var remuxCodecs = map[string]map[string][]string{
+	ContainerMP4: {
+		"video": {"h264", "hevc", "av1", "vp9", "mpeg4"},
+		"audio": {"aac", "mp3", "alac", "ac3", "eac3", "opus", "flac"},
+	},
+	ContainerM4A: {
+		"audio": {"aac", "alac"},
+	},
+	ContainerMKV: {
+		"video": nil,
+		"audio": nil,
+	},
+	ContainerWebM: {
+		"video": {"vp8", "vp9", "av1"},
+		"audio": {"opus", "vorbis"},
+	},
+}
+
+// remuxSubtitleCodecs is the text subtitle format of each container; "copy"
+// keeps the subtitles as they are when the container holds their format
+var remuxSubtitleCodecs = map[string]string{
+	ContainerMP4:  "mov_text",
+	ContainerMKV:  "copy",
+	ContainerWebM: "webvtt",
+}
+
+// textSubtitleCodecs are the subtitle codecs that can be converted to
+// another text format; bitmap subtitles like PGS can't
+var textSubtitleCodecs = map[string]bool{
+	"subrip": true, "ass": true, "ssa": true, "webvtt": true, "mov_text": true, "text": true,
+}
+
+// remuxStream is a stream of the input written to the output
+type remuxStream struct {
+	Input int
+	Codec string // "copy", or the subtitle format it's converted to
+}
+
+// planRemux picks the streams of input to write into container and how,
+// or explains which streams would need re-encoding. Data streams are
+// dropped, and so is video when the container is audio-only.
+func planRemux(streams []Stream, container string) ([]remuxStream, error) {
+	codecs, ok := remuxCodecs[container]
+	if !ok {
+		return nil, fmt.Errorf("unsupported container %q (use mp4, m4a, mkv, or webm)", container)
+	}
+
+	var plan []remuxStream
+	var problems []string
+	hasMedia := false
+	for _, s := range streams {
+		switch s.Type {
+		case "video", "audio":
+			allowed, holds := codecs[s.Type]
+			if s.Cover {
+				// Cover art goes where the container can hold it
+				if container != ContainerWebM && (s.Codec == "mjpeg" || s.Codec == "png") {
+					plan = append(plan, remuxStream{Input: s.Index, Codec: "copy"})
+				}
+				continue
+			}
+			if !holds {
+				continue
+			}
+			if allowed != nil && !slices.Contains(allowed, s.Codec) {
+				problems = append(problems, fmt.Sprintf("%s %s (stream %d)", s.Codec, s.Type, s.Index))
+				continue
+			}
+			plan = append(plan, remuxStream{Input: s.Index, Codec: "copy"})
+			hasMedia = true
+		case "subtitle":
+			format, holds := remuxSubtitleCodecs[container]
+			if !holds {
+				continue
+			}
+			if format == "copy" && s.Codec == "mov_text" {
+				// Matroska has no mov_text
+				format = "srt"
+			}
+			if format != "copy" && format != s.Codec && !textSubtitleCodecs[s.Codec] {
+				problems = append(problems, fmt.Sprintf("%s subtitles (stream %d)", s.Codec, s.Index))
+				continue
+			}
+			if format == s.Codec {
+				format = "copy"
+			}
+			plan = append(plan, remuxStream{Input: s.Index, Codec: format})
+		}
+	}
+
+	if len(problems) > 0 {
+		return nil, fmt.Errorf("%s can't hold %s without re-encoding (mkv holds any codec)", container, strings.Join(problems, ", "))
+	}
+	if !hasMedia {
+		return nil, fmt.Errorf("no streams %s can hold", container)
+	}
+	return plan, nil
+}
+
+// RemuxContainer validates a container, taking it from output's extension
+// when container is empty
+func RemuxContainer(container, output string) (string, error) {
+	if container == "" {
+		container = strings.TrimPrefix(filepath.Ext(output), ".")
+	}
+	container = strings.ToLower(container)
+	if _, ok := remuxCodecs[container]; !ok {
+		return "", fmt.Errorf("unsupported container %q (use mp4, m4a, mkv, or webm)", container)
+	}
+	return container, nil
+}
+
+// CheckRemux reports whether streams can be copied into container, with the
+// streams that would need re-encoding if not
+func CheckRemux(streams []Stream, container string) error {
+	_, err := planRemux(streams, container)
+	return err
+}
+
+// Remux copies the streams of input into the container of output's
+// extension without re-encoding, so nothing is lost. Before starting it
+// checks the container can hold every codec, failing with the streams that
+// would need re-encoding if not.
+func Remux(ctx context.Context, input, output string, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	container, err := RemuxContainer("", output)
+	if err != nil {
+		return err
+	}
+	streams, err := ProbeStreams(ctx, input)
+	if err != nil {
+		return err
+	}
+	plan, err := planRemux(streams, container)
+	if err != nil {
+		return err
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input}
+		for _, s := range plan {
+			args = append(args, "-map", "0:"+strconv.Itoa(s.Input))
+		}
+		for i, s := range plan {
+			args = append(args, "-c:"+strconv.Itoa(i), s.Codec)
+		}
+		if container == ContainerMP4 || container == ContainerM4A {
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg remux failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
@@ -0,0 +1,44 @@
+package ffmpeg
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestPlanRemux(t *testing.T) {
+	mkv := []Stream{
+		{Index: 0, Type: "video", Codec: "h264"},
+		{Index: 1, Type: "audio", Codec: "aac"},
+		{Index: 2, Type: "subtitle", Codec: "subrip"},
+		{Index: 3, Type: "attachment", Codec: "ttf"},
+	}
+	tests := []struct {
+		name      string
+		streams   []Stream
+		container string
+		want      []remuxStream
+		ok        bool
+	}{
+		{"mkv to mp4", mkv, ContainerMP4, []remuxStream{{0, "copy"}, {1, "copy"}, {2, "mov_text"}}, true},
+		{"mkv to m4a keeps the audio", mkv, ContainerM4A, []remuxStream{{1, "copy"}}, true},
+		{"mp4 to mkv", []Stream{
+			{Index: 0, Type: "video", Codec: "hevc"},
+			{Index: 1, Type: "subtitle", Codec: "mov_text"},
+			{Index: 2, Type: "data", Codec: "bin_data"},
+		}, ContainerMKV, []remuxStream{{0, "copy"}, {1, "srt"}}, true},
+		{"opus in m4a", []Stream{{Index: 0, Type: "audio", Codec: "opus"}}, ContainerM4A, nil, false},
+		{"bitmap subtitles in mp4", []Stream{
+			{Index: 0, Type: "video", Codec: "h264"},
+			{Index: 1, Type: "subtitle", Codec: "hdmv_pgs_subtitle"},
+		}, ContainerMP4, nil, false},
+		{"unknown container", mkv, "avi", nil, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := planRemux(tt.streams, tt.container)
+			if (err == nil) != tt.ok || !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("planRemux() = %v, %v, want %v", got, err, tt.want)
+			}
+		})
+	}
+}
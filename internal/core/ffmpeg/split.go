@@ -0,0 +1,94 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// ProbeDuration reads the length of input with ffprobe
+func ProbeDuration(ctx context.Context, input string) (time.Duration, error) {
+	if findBinary("ffprobe") == "" {
+		return 0, fmt.Errorf("ffprobe not found in PATH")
+	}
+	out, err := exec.CommandContext(ctx, ProbeBinary(), "-v", "error",
+		"-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1", input).Output()
+	if err != nil {
+		return 0, fmt.Errorf("ffprobe failed: %w", err)
+	}
+	seconds, err := strconv.ParseFloat(strings.TrimSpace(string(out)), 64)
+	if err != nil {
+		return 0, fmt.Errorf("failed to parse duration %q", strings.TrimSpace(string(out)))
+	}
+	return time.Duration(seconds * float64(time.Second)), nil
+}
+
+// SplitPath is where part n, counted from 1, of input split by Split goes
+func SplitPath(input string, n int) string {
+	ext := filepath.Ext(input)
+	return fmt.Sprintf("%s (part %d)%s", strings.TrimSuffix(input, ext), n, ext)
+}
+
+// Split cuts input into parts of about every each, named by SplitPath, and
+// returns their paths. Streams are copied, so each part starts at the first
+// keyframe after its cut and lengths vary by a few seconds. MP4 parts get
+// faststart. The input is kept.
+func Split(ctx context.Context, input string, every time.Duration, progressFn ProgressFunc) ([]string, error) {
+	if !Available() {
+		return nil, fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if every <= 0 {
+		return nil, fmt.Errorf("part length must be positive")
+	}
+
+	// The segment muxer numbers the parts through the pattern, so a % in the
+	// name itself is escaped. They are written as part files and renamed once
+	// all are done.
+	ext := filepath.Ext(input)
+	base := strings.ReplaceAll(strings.TrimSuffix(input, ext), "%", "%%")
+	args := []string{
+		"-i", input, "-map", "0:v?", "-map", "0:a?", "-c", "copy",
+		"-f", "segment", "-segment_time", formatSeconds(every), "-segment_start_number", "1", "-reset_timestamps", "1",
+	}
+	switch strings.ToLower(ext) {
+	case ".mp4", ".m4a", ".mov":
+		args = append(args, "-segment_format_options", "movflags=+faststart")
+	}
+	args = append(args, "-y", PartPath(base+" (part %d)"+ext))
+
+	out, err := Run(ctx, args, progressFn)
+	var parts []string
+	for n := 1; ; n++ {
+		part := PartPath(SplitPath(input, n))
+		if _, statErr := os.Stat(part); statErr != nil {
+			break
+		}
+		parts = append(parts, part)
+	}
+	if err == nil && len(parts) == 0 {
+		err = fmt.Errorf("no parts written")
+	}
+	if err != nil {
+		for _, part := range parts {
+			os.Remove(part)
+		}
+		return nil, fmt.Errorf("ffmpeg split failed: %w\nOutput: %s", err, out)
+	}
+
+	paths := make([]string, len(parts))
+	for i, part := range parts {
+		paths[i] = SplitPath(input, i+1)
+		if err := os.Rename(part, paths[i]); err != nil {
+			for _, part := range parts[i:] {
+				os.Remove(part)
+			}
+			return paths[:i], fmt.Errorf("failed to move part into place: %w", err)
+		}
+	}
+	return paths, nil
+}
@@ -0,0 +1,114 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// SubtitleTrack is a subtitle file to embed
+type SubtitleTrack struct {
+	Path     string `json:"path"`
+	Language string `json:"language,omitempty"`
+}
+
+// softSubtitleCodecs are the subtitle codecs of the containers that can hold
+// text subtitles; Matroska takes SRT, WebVTT, and ASS as they are
+var softSubtitleCodecs = map[string]string{
+	".mp4": "mov_text",
+	".m4v": "mov_text",
+	".mov": "mov_text",
+	".mkv": "copy",
+}
+
+// SubtitledPath is where input with embedded subtitles goes by default.
+// Burned-in subtitles are re-encoded to MP4; soft tracks keep the container
+// unless it can't hold them, then it's MKV.
+func SubtitledPath(input string, burn bool) string {
+	ext := strings.ToLower(filepath.Ext(input))
+	if burn {
+		ext = ".mp4"
+	} else if _, ok := softSubtitleCodecs[ext]; !ok {
+		ext = ".mkv"
+	}
+	return strings.TrimSuffix(input, filepath.Ext(input)) + " (subtitled)" + ext
+}
+
+// EmbedSubtitles muxes tracks into output as soft subtitles that players can
+// turn on and off. Video and audio are copied, and subtitles input already
+// has are kept after the new ones.
+func EmbedSubtitles(ctx context.Context, input, output string, tracks []SubtitleTrack, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if len(tracks) == 0 {
+		return fmt.Errorf("no subtitles to embed")
+	}
+	codec, ok := softSubtitleCodecs[strings.ToLower(filepath.Ext(output))]
+	if !ok {
+		return fmt.Errorf("%s can't hold subtitle tracks (use mp4, mov, or mkv)", filepath.Ext(output))
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input}
+		for _, track := range tracks {
+			args = append(args, "-i", track.Path)
+		}
+		args = append(args, "-map", "0:v?", "-map", "0:a?")
+		for i := range tracks {
+			args = append(args, "-map", strconv.Itoa(i+1)+":s:0")
+		}
+		args = append(args, "-map", "0:s?", "-c", "copy", "-c:s", codec)
+		for i, track := range tracks {
+			if track.Language != "" {
+				args = append(args, fmt.Sprintf("-metadata:s:s:%d", i), "language="+track.Language)
+			}
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg subtitle embedding failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
+
+// BurnSubtitles draws the subtitles of subtitlePath onto the video of input,
+// re-encoding it to H.264; audio is copied. Burned-in subtitles show in every
+// player but can't be turned off.
+func BurnSubtitles(ctx context.Context, input, output, subtitlePath string, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{
+			"-i", input, "-map", "0:v:0", "-map", "0:a?",
+			"-vf", "subtitles=filename=" + filterValue(subtitlePath),
+			"-c:v", "libx264", "-crf", "20", "-preset", "medium", "-pix_fmt", "yuv420p",
+			"-c:a", "copy",
+		}
+		if strings.EqualFold(filepath.Ext(output), ".mp4") {
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg subtitle burn-in failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
+
+// filtergraphEscaper escapes the characters special to a filtergraph
+var filtergraphEscaper = strings.NewReplacer(`\`, `\\`, `'`, `\'`, `[`, `\[`, `]`, `\]`, `,`, `\,`, `;`, `\;`)
+
+// filterValue quotes value as a filter option inside a filtergraph: quoted
+// for the option parser, then escaped for the filtergraph parser, which
+// removes its escapes first. Paths with colons, quotes, or brackets need it.
+func filterValue(value string) string {
+	quoted := "'" + strings.ReplaceAll(value, "'", `'\''`) + "'"
+	return filtergraphEscaper.Replace(quoted)
+}
@@ -0,0 +1,26 @@
+package ffmpeg
+
+import "testing"
+
+func TestSubtitledPath(t *testing.T) {
+	tests := []struct {
+		input string
+		burn  bool
+		want  string
+	}{
+		{"/videos/talk.mp4", false, "/videos/talk (subtitled).mp4"},
+		{"/videos/talk.webm", false, "/videos/talk (subtitled).mkv"},
+		{"/videos/talk.mkv", true, "/videos/talk (subtitled).mp4"},
+	}
+	for _, tt := range tests {
+		if got := SubtitledPath(tt.input, tt.burn); got != tt.want {
+			t.Errorf("SubtitledPath(%q, %v) = %q, want %q", tt.input, tt.burn, got, tt.want)
+		}
+	}
+}
+
+func TestFilterValue(t *testing.T) {
+	if got, want := filterValue(`/subs/it's [1].srt`), `\'/subs/it\'\\\'\'s \[1\].srt\'`; got != want {
+		t.Errorf("filterValue() = %s, want %s", got, want)
+	}
+}
@@ -0,0 +1,179 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Video codecs presets encode to; hardware encoders are matched on them
+const (
+	CodecH264 = "h264"
+	CodecHEVC = "hevc"
+	CodecAV1  = "av1"
+)
+
+// VideoPreset is a named set of encoding settings for Transcode
+type VideoPreset struct {
+	Name          string `json:"name"`
+	Description   string `json:"description"`
+	Container     string `json:"container"` // output extension
+	Codec         string `json:"codec"`
+	Encoder       string `json:"encoder"` // software encoder, used without a hardware one
+	CRF           int    `json:"crf"`     // quality; lower is better and larger
+	MaxHeight     int    `json:"max_height,omitempty"`
+	Profile       string `json:"profile,omitempty"`
+	AudioCodec    string `json:"audio_codec"`
+	AudioBitrate  string `json:"audio_bitrate"`
+	AudioChannels int    `json:"audio_channels,omitempty"`
+
+	// Args are further options of the software encoder
+	Args []string `json:"-"`
+}
+
+// VideoPresets are the presets Transcode accepts, most compatible first
+var VideoPresets = []VideoPreset{
+	{
+		Name:          "web",
+		Description:   "H.264 MP4 that plays in every browser and on old devices",
+		Container:     "mp4",
+		Codec:         CodecH264,
+		Encoder:       "libx264",
+		CRF:           23,
+		MaxHeight:     1080,
+		Profile:       "main",
+		AudioCodec:    "aac",
+		AudioBitrate:  "128k",
+		AudioChannels: 2,
+		Args:          []string{"-preset", "medium", "-level:v", "4.0"},
+	},
+	{
+		Name:         "h264-1080p",
+		Description:  "H.264 at up to 1080p, high quality",
+		Container:    "mp4",
+		Codec:        CodecH264,
+		Encoder:      "libx264",
+		CRF:          20,
+		MaxHeight:    1080,
+		AudioCodec:   "aac",
+		AudioBitrate: "192k",
+		Args:         []string{"-preset", "medium"},
+	},
+	{
+		Name:         "h265",
+		Description:  "H.265/HEVC, about half the size of H.264 at the same quality",
+		Container:    "mp4",
+		Codec:        CodecHEVC,
+		Encoder:      "libx265",
+		CRF:          24,
+		AudioCodec:   "aac",
+		AudioBitrate: "192k",
+		Args:         []string{"-preset", "medium"},
+	},
+	{
+		Name:         "av1",
+		Description:  "AV1, the smallest files, for recent players",
+		Container:    "mp4",
+		Codec:        CodecAV1,
+		Encoder:      "libsvtav1",
+		CRF:          32,
+		AudioCodec:   "aac",
+		AudioBitrate: "160k",
+		Args:         []string{"-preset", "8"},
+	},
+}
+
+// FindVideoPreset looks up a preset by name
+func FindVideoPreset(name string) (VideoPreset, error) {
+	var names []string
+	for _, p := range VideoPresets {
+		if strings.EqualFold(p.Name, name) {
+			return p, nil
+		}
+		names = append(names, p.Name)
+	}
+	return VideoPreset{}, fmt.Errorf("unknown video preset %q (use %s)", name, strings.Join(names, ", "))
+}
+
+// VideoPath is where input transcoded with preset goes by default: next to
+// it, with the preset's extension
+func VideoPath(input string, preset VideoPreset) string {
+	return SwapExt(input, preset.Container)
+}
+
+// Transcode re-encodes input into output with preset. With hardware set, a
+// working hardware encoder for the preset's codec is used if there is one,
+// falling back to the software encoder if it fails on this input. It returns
+// the encoder that wrote the output.
+func Transcode(ctx context.Context, input, output string, preset VideoPreset, hardware bool, progressFn ProgressFunc) (Encoder, error) {
+	if !Available() {
+		return Encoder{}, fmt.Errorf("ffmpeg not found in PATH")
+	}
+
+	software := Encoder{Name: preset.Encoder, Codec: preset.Codec}
+	if hardware {
+		if enc, ok := HardwareEncoder(preset.Codec); ok {
+			err := transcodeWith(ctx, input, output, preset, enc, progressFn)
+			if err == nil || ctx.Err() != nil {
+				return enc, err
+			}
+			// e.g. 10-bit input the hardware can't take
+		}
+	}
+	return software, transcodeWith(ctx, input, output, preset, software, progressFn)
+}
+
+// transcodeWith re-encodes input into output with preset, using enc for video
+func transcodeWith(ctx context.Context, input, output string, preset VideoPreset, enc Encoder, progressFn ProgressFunc) error {
+	return WriteOutput(output, func(part string) error {
+		var args []string
+		if enc.Hardware == HardwareVAAPI {
+			args = append(args, "-vaapi_device", vaapiDevice)
+		}
+		args = append(args, "-i", input, "-map", "0:v:0", "-map", "0:a:0?", "-c:v", enc.Name)
+
+		var filters []string
+		if preset.MaxHeight > 0 {
+			// Scale down only; -2 keeps the width even, as most encoders need
+			filters = append(filters, fmt.Sprintf("scale=-2:'min(ih,%d)'", preset.MaxHeight))
+		}
+		if enc.Hardware == HardwareVAAPI {
+			// VAAPI encodes frames uploaded to the GPU
+			filters = append(filters, "format=nv12", "hwupload")
+		} else {
+			args = append(args, "-pix_fmt", "yuv420p")
+		}
+		if len(filters) > 0 {
+			args = append(args, "-vf", strings.Join(filters, ","))
+		}
+
+		if enc.Hardware == "" {
+			args = append(args, "-crf", strconv.Itoa(preset.CRF))
+			args = append(args, preset.Args...)
+		} else {
+			args = append(args, enc.qualityArgs(preset.CRF)...)
+		}
+		if preset.Profile != "" {
+			args = append(args, "-profile:v", preset.Profile)
+		}
+		if preset.Codec == CodecHEVC && preset.Container == "mp4" {
+			// The tag Apple players require
+			args = append(args, "-tag:v", "hvc1")
+		}
+
+		args = append(args, "-c:a", preset.AudioCodec, "-b:a", preset.AudioBitrate)
+		if preset.AudioChannels > 0 {
+			args = append(args, "-ac", strconv.Itoa(preset.AudioChannels))
+		}
+		if preset.Container == "mp4" {
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg transcode with %s failed: %w\nOutput: %s", enc.Name, err, out)
+		}
+		return nil
+	})
+}
@@ -0,0 +1,101 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// ParseTimestamp parses a position in media: seconds ("90", "90.5"),
+// "MM:SS", or "HH:MM:SS", each with optional fractions of a second
+func ParseTimestamp(value string) (time.Duration, error) {
+	parts := strings.Split(strings.TrimSpace(value), ":")
+	if len(parts) > 3 || parts[0] == "" {
+		return 0, fmt.Errorf("invalid timestamp %q (use seconds, MM:SS, or HH:MM:SS)", value)
+	}
+	var seconds float64
+	for i, part := range parts {
+		n, err := strconv.ParseFloat(part, 64)
+		if err != nil || n < 0 || (i < len(parts)-1 && strings.Contains(part, ".")) {
+			return 0, fmt.Errorf("invalid timestamp %q (use seconds, MM:SS, or HH:MM:SS)", value)
+		}
+		seconds = seconds*60 + n
+	}
+	return time.Duration(seconds * float64(time.Second)), nil
+}
+
+// formatSeconds formats d for ffmpeg's time options
+func formatSeconds(d time.Duration) string {
+	return strconv.FormatFloat(d.Seconds(), 'f', 3, 64)
+}
+
+// clipProgress measures the progress of writing the part of the input from
+// start to end (0 for the end of the input) against that part, not the whole
+func clipProgress(progressFn ProgressFunc, start, end time.Duration) ProgressFunc {
+	if progressFn == nil {
+		return nil
+	}
+	return func(done, total time.Duration) {
+		if end > 0 {
+			total = end - start
+		} else if total > start {
+			total -= start
+		}
+		if total > 0 {
+			done = min(done, total)
+		}
+		progressFn(done, total)
+	}
+}
+
+// ClipPath is where a trimmed clip of input goes by default
+func ClipPath(input string) string {
+	ext := filepath.Ext(input)
+	return strings.TrimSuffix(input, ext) + " (clip)" + ext
+}
+
+// Trim cuts input from start to end (0 for the end of the file) into output.
+// Without reencode the streams are copied: fast and lossless, but the cut
+// lands on the keyframe at or before start. If copying fails, or with
+// reencode, the clip is re-encoded with the output format's default codecs
+// for a frame-accurate cut.
+func Trim(ctx context.Context, input, output string, start, end time.Duration, reencode bool, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	if end > 0 && end <= start {
+		return fmt.Errorf("end must be after start")
+	}
+
+	progressFn = clipProgress(progressFn, start, end)
+	trim := func(streamCopy bool) error {
+		return WriteOutput(output, func(part string) error {
+			args := []string{"-ss", formatSeconds(start)}
+			if end > 0 {
+				args = append(args, "-t", formatSeconds(end-start))
+			}
+			args = append(args, "-i", input, "-map", "0:v?", "-map", "0:a?")
+			if streamCopy {
+				args = append(args, "-c", "copy", "-avoid_negative_ts", "make_zero")
+			}
+			args = append(args, "-y", part)
+
+			if out, err := Run(ctx, args, progressFn); err != nil {
+				return fmt.Errorf("ffmpeg trim failed: %w\nOutput: %s", err, out)
+			}
+			return nil
+		})
+	}
+
+	if !reencode {
+		err := trim(true)
+		if err == nil || ctx.Err() != nil {
+			return err
+		}
+		// e.g. codecs the output container can't hold
+	}
+	return trim(false)
+}
@@ -0,0 +1,28 @@
+package ffmpeg
+
+import (
+	"testing"
+	"time"
+)
+
+func TestParseTimestamp(t *testing.T) {
+	tests := []struct {
+		input string
+		want  time.Duration
+		ok    bool
+	}{
+		{"90", 90 * time.Second, true},
+		{"1:30.5", 90*time.Second + 500*time.Millisecond, true},
+		{"01:02:03", time.Hour + 2*time.Minute + 3*time.Second, true},
+		{"1.5:30", 0, false},
+		{"1:2:3:4", 0, false},
+		{"-5", 0, false},
+		{"", 0, false},
+	}
+	for _, tt := range tests {
+		got, err := ParseTimestamp(tt.input)
+		if (err == nil) != tt.ok || got != tt.want {
+			t.Errorf("ParseTimestamp(%q) = %v, %v, want %v", tt.input, got, err, tt.want)
+		}
+	}
+}
@@ -0,0 +1,154 @@
+package ffmpeg
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// Watermark positions
+const (
+	WatermarkTopLeft     = "top-left"
+	WatermarkTopRight    = "top-right"
+	WatermarkBottomLeft  = "bottom-left"
+	WatermarkBottomRight = "bottom-right"
+	WatermarkCenter      = "center"
+)
+
+// Defaults for WatermarkOptions left unset
+const (
+	DefaultWatermarkOpacity = 0.8
+	DefaultWatermarkMargin  = 16
+)
+
+// WatermarkOptions is what Watermark draws onto a video: an image or a line
+// of text
+type WatermarkOptions struct {
+	Image    string  // PNG, transparency kept
+	Text     string  // drawn in white with a dark outline
+	Position string  // defaults to bottom-right
+	Opacity  float64 // 0 to 1
+	Margin   int     // pixels from the edges
+	Width    int     // scales the image; 0 keeps its size
+	FontSize int     // 0 sizes the text to the video's height
+}
+
+// WatermarkPosition validates a watermark position, defaulting to the
+// bottom-right corner
+func WatermarkPosition(position string) (string, error) {
+	switch position = strings.ToLower(strings.TrimSpace(position)); position {
+	case "":
+		return WatermarkBottomRight, nil
+	case WatermarkTopLeft, WatermarkTopRight, WatermarkBottomLeft, WatermarkBottomRight, WatermarkCenter:
+		return position, nil
+	}
+	return "", fmt.Errorf("unsupported position %q (use top-left, top-right, bottom-left, bottom-right, or center)", position)
+}
+
+// ValidateWatermark checks opts before a video is re-encoded with it
+func ValidateWatermark(opts WatermarkOptions) error {
+	_, err := watermarkFilter(opts)
+	return err
+}
+
+// watermarkXY places a watermark of size w by h in a frame of size W by H,
+// as ffmpeg expressions in the variables of the filter drawing it
+func watermarkXY(position string, margin int, W, H, w, h string) (string, string) {
+	m := strconv.Itoa(margin)
+	left, right := m, W+"-"+w+"-"+m
+	top, bottom := m, H+"-"+h+"-"+m
+	switch position {
+	case WatermarkTopLeft:
+		return left, top
+	case WatermarkTopRight:
+		return right, top
+	case WatermarkBottomLeft:
+		return left, bottom
+	case WatermarkCenter:
+		return "(" + W + "-" + w + ")/2", "(" + H + "-" + h + ")/2"
+	}
+	return right, bottom
+}
+
+// watermarkFilter is the filtergraph drawing the watermark of opts onto the
+// first input's video, labelled [v]. An image watermark is the second input.
+func watermarkFilter(opts WatermarkOptions) (string, error) {
+	if (opts.Image == "") == (opts.Text == "") {
+		return "", fmt.Errorf("give a watermark image or text, not both")
+	}
+	if opts.Opacity < 0 || opts.Opacity > 1 || opts.Margin < 0 || opts.Width < 0 || opts.FontSize < 0 {
+		return "", fmt.Errorf("opacity must be between 0 and 1; margin, width, and font size can't be negative")
+	}
+	position, err := WatermarkPosition(opts.Position)
+	if err != nil {
+		return "", err
+	}
+	if opts.Opacity == 0 {
+		opts.Opacity = DefaultWatermarkOpacity
+	}
+	if opts.Margin == 0 {
+		opts.Margin = DefaultWatermarkMargin
+	}
+	opacity := strconv.FormatFloat(opts.Opacity, 'f', -1, 64)
+
+	if opts.Image != "" {
+		x, y := watermarkXY(position, opts.Margin, "W", "H", "w", "h")
+		image := "[1:v]"
+		if opts.Width > 0 {
+			image += fmt.Sprintf("scale=%d:-1,", opts.Width)
+		}
+		image += "format=rgba,colorchannelmixer=aa=" + opacity + "[wm]"
+		return image + ";[0:v][wm]overlay=" + x + ":" + y + "[v]", nil
+	}
+
+	x, y := watermarkXY(position, opts.Margin, "w", "h", "tw", "th")
+	fontSize := "h/24"
+	if opts.FontSize > 0 {
+		fontSize = strconv.Itoa(opts.FontSize)
+	}
+	// expansion=none keeps % in the text as it is
+	return "[0:v]drawtext=text=" + filterValue(opts.Text) + ":expansion=none:fontsize=" + fontSize +
+		":fontcolor=white@" + opacity + ":borderw=2:bordercolor=black@" + opacity +
+		":x=" + x + ":y=" + y + "[v]", nil
+}
+
+// WatermarkedPath is where a watermarked video goes by default. The video is
+// re-encoded, so it's MP4 whatever input was.
+func WatermarkedPath(input string) string {
+	return strings.TrimSuffix(input, filepath.Ext(input)) + " (watermarked).mp4"
+}
+
+// Watermark overlays an image or text onto the video of input, re-encoding
+// it to H.264; audio is copied
+func Watermark(ctx context.Context, input, output string, opts WatermarkOptions, progressFn ProgressFunc) error {
+	if !Available() {
+		return fmt.Errorf("ffmpeg not found in PATH")
+	}
+	filter, err := watermarkFilter(opts)
+	if err != nil {
+		return err
+	}
+
+	return WriteOutput(output, func(part string) error {
+		args := []string{"-i", input}
+		if opts.Image != "" {
+			args = append(args, "-i", opts.Image)
+		}
+		args = append(args,
+			"-filter_complex", filter, "-map", "[v]", "-map", "0:a?",
+			"-c:v", "libx264", "-crf", "20", "-preset", "medium", "-pix_fmt", "yuv420p",
+			"-c:a", "copy",
+		)
+		if strings.EqualFold(filepath.Ext(output), ".mp4") {
+			args = append(args, "-movflags", "+faststart")
+		}
+		args = append(args, "-y", part)
+
+		if out, err := Run(ctx, args, progressFn); err != nil {
+			return fmt.Errorf("ffmpeg watermark failed: %w\nOutput: %s", err, out)
+		}
+		return nil
+	})
+}
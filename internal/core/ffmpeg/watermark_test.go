@@ -0,0 +1,40 @@
+package ffmpeg
+
+import "testing"
+
+func TestWatermarkFilter(t *testing.T) {
+	tests := []struct {
+		opts WatermarkOptions
+		want string
+	}{
+		{
+			WatermarkOptions{Image: "logo.png", Position: "Top-Left", Opacity: 0.5, Width: 200},
+			"[1:v]scale=200:-1,format=rgba,colorchannelmixer=aa=0.5[wm];[0:v][wm]overlay=16:16[v]",
+		},
+		{
+			WatermarkOptions{Image: "logo.png", Margin: 8},
+			"[1:v]format=rgba,colorchannelmixer=aa=0.8[wm];[0:v][wm]overlay=W-w-8:H-h-8[v]",
+		},
+		{
+			WatermarkOptions{Text: "50% off", Position: WatermarkCenter},
+			`[0:v]drawtext=text=\'50% off\':expansion=none:fontsize=h/24:fontcolor=white@0.8:borderw=2:bordercolor=black@0.8:x=(w-tw)/2:y=(h-th)/2[v]`,
+		},
+	}
+	for _, tt := range tests {
+		got, err := watermarkFilter(tt.opts)
+		if err != nil || got != tt.want {
+			t.Errorf("watermarkFilter(%+v) = %q, %v, want %q", tt.opts, got, err, tt.want)
+		}
+	}
+
+	for _, opts := range []WatermarkOptions{
+		{},
+		{Image: "logo.png", Text: "vget"},
+		{Text: "vget", Position: "middle"},
+		{Text: "vget", Opacity: 1.5},
+	} {
+		if err := ValidateWatermark(opts); err == nil {
+			t.Errorf("ValidateWatermark(%+v) succeeded", opts)
+		}
+	}
+}
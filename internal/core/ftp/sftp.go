@@ -0,0 +1,414 @@
+package ftp
+
+import (
+	"context"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/url"
+	"os"
+	"os/user"
+	"path/filepath"
+
+	"golang.org/x/crypto/ssh"
+	"golang.org/x/crypto/ssh/agent"
+	"golang.org/x/crypto/ssh/knownhosts"
+)
+
+// SFTP protocol version 3 packet types and status codes
+const (
+	sshFxpInit    = 1
+	sshFxpVersion = 2
+	sshFxpOpen    = 3
+	sshFxpClose   = 4
+	sshFxpRead    = 5
+	sshFxpFstat   = 8
+	sshFxpStatus  = 101
+	sshFxpHandle  = 102
+	sshFxpData    = 103
+	sshFxpAttrs   = 105
+
+	sshFxOK  = 0
+	sshFxEOF = 1
+
+	sshFxfRead          = 1
+	sshFileXferAttrSize = 1
+)
+
+const (
+	// sftpChunk is the size of each read request; servers return at most
+	// this much per request
+	sftpChunk = 32 * 1024
+
+	// sftpWindow is how many read requests are in flight at once, so
+	// throughput isn't bound by round trips
+	sftpWindow = 16
+
+	// maxPacket bounds the packets accepted from the server
+	maxPacket = 256 * 1024
+)
+
+// openSFTP connects over SSH, checking the host key against
+// ~/.ssh/known_hosts, and opens the file for reading. Without a password in
+// the URL it authenticates with the SSH agent and the default key files.
+func openSFTP(ctx context.Context, u *url.URL) (io.ReadCloser, int64, error) {
+	config, agentConn, err := sshClientConfig(u)
+	if err != nil {
+		return nil, 0, err
+	}
+	// The agent connection lives as long as the SSH client
+	closeAgent := func() {
+		if agentConn != nil {
+			agentConn.Close()
+		}
+	}
+
+	conn, err := dial(ctx, u, "22")
+	if err != nil {
+		closeAgent()
+		return nil, 0, err
+	}
+	stop := context.AfterFunc(ctx, func() { conn.Close() })
+
+	// known_hosts lists hosts by name, so verify the name from the URL
+	addr := u.Host
+	if u.Port() == "" {
+		addr = net.JoinHostPort(u.Hostname(), "22")
+	}
+	sshConn, chans, reqs, err := ssh.NewClientConn(conn, addr, config)
+	if err != nil {
+		stop()
+		conn.Close()
+		closeAgent()
+		return nil, 0, fmt.Errorf("SSH connection failed: %w", err)
+	}
+	client := ssh.NewClient(sshConn, chans, reqs)
+
+	f, err := openSFTPFile(client, u.Path)
+	if err != nil {
+		stop()
+		client.Close()
+		closeAgent()
+		return nil, 0, err
+	}
+	f.stop = stop
+	f.closeAgent = closeAgent
+	return f, f.size, nil
+}
+
+// sshClientConfig builds the SSH login for u. It also returns the connection
+// to the SSH agent its keys are signed through, nil if none, which the caller
+// closes with the SSH client.
+func sshClientConfig(u *url.URL) (*ssh.ClientConfig, net.Conn, error) {
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return nil, nil, err
+	}
+	hostKeys, err := knownhosts.New(filepath.Join(home, ".ssh", "known_hosts"))
+	if err != nil {
+		return nil, nil, fmt.Errorf("SFTP needs the server in ~/.ssh/known_hosts: %w", err)
+	}
+
+	name := ""
+	var auth []ssh.AuthMethod
+	var agentConn net.Conn
+	if u.User != nil {
+		name = u.User.Username()
+		if password, ok := u.User.Password(); ok {
+			auth = append(auth, ssh.Password(password), ssh.KeyboardInteractive(
+				func(_, _ string, questions []string, _ []bool) ([]string, error) {
+					answers := make([]string, len(questions))
+					for i := range answers {
+						answers[i] = password
+					}
+					return answers, nil
+				}))
+		}
+	}
+	if name == "" {
+		if current, err := user.Current(); err == nil {
+			name = current.Username
+		}
+	}
+	if len(auth) == 0 {
+		if sock := os.Getenv("SSH_AUTH_SOCK"); sock != "" {
+			if conn, err := net.Dial("unix", sock); err == nil {
+				agentConn = conn
+				auth = append(auth, ssh.PublicKeysCallback(agent.NewClient(conn).Signers))
+			}
+		}
+		var signers []ssh.Signer
+		for _, file := range []string{"id_ed25519", "id_ecdsa", "id_rsa"} {
+			key, err := os.ReadFile(filepath.Join(home, ".ssh", file))
+			if err != nil {
+				continue
+			}
+			// Passphrase-protected keys need the agent
+			if signer, err := ssh.ParsePrivateKey(key); err == nil {
+				signers = append(signers, signer)
+			}
+		}
+		if len(signers) > 0 {
+			auth = append(auth, ssh.PublicKeys(signers...))
+		}
+	}
+
+	return &ssh.ClientConfig{
+		User: name,
+		Auth: auth,
+		HostKeyCallback: func(hostname string, remote net.Addr, key ssh.PublicKey) error {
+			err := hostKeys(hostname, remote, key)
+			var keyErr *knownhosts.KeyError
+			if errors.As(err, &keyErr) && len(keyErr.Want) == 0 {
+				return fmt.Errorf("host %s is not in ~/.ssh/known_hosts; connect once with ssh to add it", hostname)
+			}
+			return err
+		},
+		Timeout: dialTimeout,
+	}, agentConn, nil
+}
+
+// sftpFile reads a remote file with pipelined read requests
+type sftpFile struct {
+	client *ssh.Client
+	w      io.WriteCloser
+	r      io.Reader
+	handle string
+	size   int64
+	stop   func() bool
+
+	closeAgent func() // closes the SSH agent connection used to log in
+
+	nextID   uint32
+	offset   int64             // offset of the next read request
+	pending  []sftpRead        // requests in flight, in file order
+	received map[uint32][]byte // responses that arrived ahead of their turn
+	buf      []byte
+	eof      bool
+}
+
+type sftpRead struct {
+	id     uint32
+	offset int64
+	length uint32
+}
+
+func openSFTPFile(client *ssh.Client, filePath string) (*sftpFile, error) {
+	session, err := client.NewSession()
+	if err != nil {
+		return nil, fmt.Errorf("SSH session failed: %w", err)
+	}
+	w, err := session.StdinPipe()
+	if err != nil {
+		return nil, err
+	}
+	r, err := session.StdoutPipe()
+	if err != nil {
+		return nil, err
+	}
+	if err := session.RequestSubsystem("sftp"); err != nil {
+		return nil, fmt.Errorf("server has no SFTP subsystem: %w", err)
+	}
+
+	f := &sftpFile{client: client, w: w, r: r, size: -1, received: make(map[uint32][]byte)}
+
+	if err := f.send(sshFxpInit, u32(3)); err != nil {
+		return nil, err
+	}
+	if typ, _, err := f.recv(); err != nil {
+		return nil, err
+	} else if typ != sshFxpVersion {
+		return nil, fmt.Errorf("unexpected SFTP packet %d", typ)
+	}
+
+	// OPEN: id, filename, pflags, attrs (no flags)
+	id := f.id()
+	if err := f.send(sshFxpOpen, u32(id), str(filePath), u32(sshFxfRead), u32(0)); err != nil {
+		return nil, err
+	}
+	typ, data, err := f.response(id)
+	if err != nil {
+		return nil, err
+	}
+	if typ != sshFxpHandle {
+		return nil, statusError(typ, data, filePath)
+	}
+	f.handle, _ = readString(data)
+
+	// The size is only for progress, so a failed FSTAT is ignored
+	id = f.id()
+	if err := f.send(sshFxpFstat, u32(id), str(f.handle)); err != nil {
+		return nil, err
+	}
+	if typ, data, err := f.response(id); err == nil && typ == sshFxpAttrs && len(data) >= 12 {
+		if binary.BigEndian.Uint32(data)&sshFileXferAttrSize != 0 {
+			f.size = int64(binary.BigEndian.Uint64(data[4:]))
+		}
+	}
+	return f, nil
+}
+
+func (f *sftpFile) Read(p []byte) (int, error) {
+	for len(f.buf) == 0 {
+		if f.eof {
+			return 0, io.EOF
+		}
+		if err := f.fill(); err != nil {
+			return 0, err
+		}
+	}
+	n := copy(p, f.buf)
+	f.buf = f.buf[n:]
+	return n, nil
+}
+
+// fill keeps the request window full and takes the next chunk in file order
+func (f *sftpFile) fill() error {
+	for len(f.pending) < sftpWindow {
+		req := sftpRead{id: f.id(), offset: f.offset, length: sftpChunk}
+		if err := f.send(sshFxpRead, u32(req.id), str(f.handle), u64(uint64(req.offset)), u32(req.length)); err != nil {
+			return err
+		}
+		f.pending = append(f.pending, req)
+		f.offset += sftpChunk
+	}
+
+	req := f.pending[0]
+	f.pending = f.pending[1:]
+	typ, data, err := f.response(req.id)
+	if err != nil {
+		return err
+	}
+	switch typ {
+	case sshFxpData:
+		chunk, _ := readString(data)
+		// A short read leaves a gap before the next request; ask for the rest first
+		if n := uint32(len(chunk)); n > 0 && n < req.length {
+			rest := sftpRead{id: f.id(), offset: req.offset + int64(n), length: req.length - n}
+			if err := f.send(sshFxpRead, u32(rest.id), str(f.handle), u64(uint64(rest.offset)), u32(rest.length)); err != nil {
+				return err
+			}
+			f.pending = append([]sftpRead{rest}, f.pending...)
+		}
+		f.buf = []byte(chunk)
+		return nil
+	case sshFxpStatus:
+		if len(data) >= 4 && binary.BigEndian.Uint32(data) == sshFxEOF {
+			f.eof = true
+			return nil
+		}
+	}
+	return statusError(typ, data, "read")
+}
+
+func (f *sftpFile) Close() error {
+	f.stop()
+	id := f.id()
+	f.send(sshFxpClose, u32(id), str(f.handle))
+	f.w.Close()
+	err := f.client.Close()
+	f.closeAgent()
+	return err
+}
+
+func (f *sftpFile) id() uint32 {
+	f.nextID++
+	return f.nextID
+}
+
+// send writes a packet: length, type, then the fields
+func (f *sftpFile) send(typ byte, fields ...[]byte) error {
+	length := 1
+	for _, field := range fields {
+		length += len(field)
+	}
+	packet := make([]byte, 0, 4+length)
+	packet = append(packet, u32(uint32(length))...)
+	packet = append(packet, typ)
+	for _, field := range fields {
+		packet = append(packet, field...)
+	}
+	if _, err := f.w.Write(packet); err != nil {
+		return fmt.Errorf("SFTP connection lost: %w", err)
+	}
+	return nil
+}
+
+// recv reads the next packet, returning its type and payload
+func (f *sftpFile) recv() (byte, []byte, error) {
+	var header [5]byte
+	if _, err := io.ReadFull(f.r, header[:]); err != nil {
+		return 0, nil, fmt.Errorf("SFTP connection lost: %w", err)
+	}
+	length := binary.BigEndian.Uint32(header[:4])
+	if length < 1 || length > maxPacket {
+		return 0, nil, fmt.Errorf("invalid SFTP packet length %d", length)
+	}
+	payload := make([]byte, length-1)
+	if _, err := io.ReadFull(f.r, payload); err != nil {
+		return 0, nil, fmt.Errorf("SFTP connection lost: %w", err)
+	}
+	return header[4], payload, nil
+}
+
+// response returns the reply to request id, keeping replies to other
+// requests that arrive first. The type is stored as the first byte.
+func (f *sftpFile) response(id uint32) (byte, []byte, error) {
+	for {
+		if packet, ok := f.received[id]; ok {
+			delete(f.received, id)
+			return packet[0], packet[1:], nil
+		}
+		typ, payload, err := f.recv()
+		if err != nil {
+			return 0, nil, err
+		}
+		if len(payload) < 4 {
+			return 0, nil, fmt.Errorf("short SFTP packet")
+		}
+		got := binary.BigEndian.Uint32(payload)
+		if got == id {
+			return typ, payload[4:], nil
+		}
+		f.received[got] = append([]byte{typ}, payload[4:]...)
+	}
+}
+
+// statusError describes an unexpected reply, using the message of a status packet
+func statusError(typ byte, data []byte, what string) error {
+	if typ != sshFxpStatus || len(data) < 4 {
+		return fmt.Errorf("SFTP %s: unexpected packet %d", what, typ)
+	}
+	code := binary.BigEndian.Uint32(data)
+	msg, _ := readString(data[4:])
+	if code == sshFxOK {
+		return fmt.Errorf("SFTP %s: unexpected OK status", what)
+	}
+	return fmt.Errorf("SFTP %s: %s (status %d)", what, msg, code)
+}
+
+func u32(v uint32) []byte {
+	return binary.BigEndian.AppendUint32(nil, v)
+}
+
+func u64(v uint64) []byte {
+	return binary.BigEndian.AppendUint64(nil, v)
+}
+
+func str(s string) []byte {
+	return append(u32(uint32(len(s))), s...)
+}
+
+// readString reads a length-prefixed string
+func readString(data []byte) (string, []byte) {
+	if len(data) < 4 {
+		return "", nil
+	}
+	n := binary.BigEndian.Uint32(data)
+	if uint64(n) > uint64(len(data)-4) {
+		return "", nil
+	}
+	return string(data[4 : 4+n]), data[4+n:]
+}
@@ -0,0 +1,501 @@
+// Package history keeps a persistent record of finished downloads, with
+// user tags and notes, stored as an append-only JSON Lines log in the config
+// directory.
+package history
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/csv"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+const (
+	// FileName is the history log inside the config directory. Each line is
+	// an entry; a later line for the same ID replaces the earlier ones.
+	FileName = "history.jsonl"
+
+	// legacyFileName is the JSON array older versions kept the history in.
+	// It's moved into the log the first time the store is read.
+	legacyFileName = "history.json"
+
+	// ThumbnailDir holds frames captured from downloaded videos, one per
+	// entry, inside the config directory
+	ThumbnailDir = "thumbnails"
+
+	// maxEntries caps the history size; the oldest entries are dropped first
+	maxEntries = 10000
+
+	// compactSlack is how many replaced lines the log may carry before it's
+	// rewritten with only the current entries
+	compactSlack = 1000
+)
+
+// Entry is a single finished download
+type Entry struct {
+	ID         string    `json:"id"`
+	URL        string    `json:"url"`
+	Title      string    `json:"title,omitempty"`
+	Site       string    `json:"site,omitempty"` // extractor that handled the URL
+	Filename   string    `json:"filename,omitempty"`
+	Files      []string  `json:"files,omitempty"` // every output file, Filename joins them with ", "
+	Status     string    `json:"status"` // completed, failed, cancelled
+	Error      string    `json:"error,omitempty"`
+	Size       int64     `json:"size,omitempty"`
+	Duration   int       `json:"duration,omitempty"` // seconds
+	Tags       []string  `json:"tags,omitempty"`
+	Note       string    `json:"note,omitempty"`
+	CreatedAt  time.Time `json:"created_at"`
+	FinishedAt time.Time `json:"finished_at"`
+
+	SHA256       map[string]string `json:"sha256,omitempty"` // checksum of each file when it was downloaded
+	Verification *Verification     `json:"verification,omitempty"`
+	Thumbnail    bool              `json:"thumbnail,omitempty"` // a frame of the video is saved at ThumbnailPath
+}
+
+// HasTag reports whether the entry has the tag (case-insensitive)
+func (e *Entry) HasTag(tag string) bool {
+	for _, t := range e.Tags {
+		if strings.EqualFold(t, tag) {
+			return true
+		}
+	}
+	return false
+}
+
+// Query filters history entries
+type Query struct {
+	Text   string    // every word must appear in the URL, title, site, filename, note, or tags
+	Tags   []string  // entry must have all of these tags
+	Site   string    // extractor name, e.g. "bilibili"
+	Status string    // completed, failed, cancelled
+	Since  time.Time // finished at or after, if set
+	Until  time.Time // finished before, if set
+	Limit  int       // 0 means no limit
+}
+
+// Matches reports whether the entry satisfies the query
+func (q Query) Matches(e *Entry) bool {
+	for _, tag := range q.Tags {
+		if !e.HasTag(tag) {
+			return false
+		}
+	}
+	if q.Site != "" && !strings.EqualFold(e.Site, q.Site) {
+		return false
+	}
+	if q.Status != "" && e.Status != q.Status {
+		return false
+	}
+	if (!q.Since.IsZero() && e.FinishedAt.Before(q.Since)) || (!q.Until.IsZero() && !e.FinishedAt.Before(q.Until)) {
+		return false
+	}
+
+	words := strings.Fields(strings.ToLower(q.Text))
+	if len(words) == 0 {
+		return true
+	}
+	text := strings.ToLower(strings.Join([]string{e.URL, e.Title, e.Site, e.Filename, e.Note, strings.Join(e.Tags, " ")}, "\n"))
+	for _, word := range words {
+		if !strings.Contains(text, word) {
+			return false
+		}
+	}
+	return true
+}
+
+// NormalizeTags trims, drops empty, and de-duplicates tags (case-insensitive)
+func NormalizeTags(tags []string) []string {
+	seen := make(map[string]bool)
+	var result []string
+	for _, tag := range tags {
+		tag = strings.TrimSpace(tag)
+		key := strings.ToLower(tag)
+		if tag == "" || seen[key] {
+			continue
+		}
+		seen[key] = true
+		result = append(result, tag)
+	}
+	return result
+}
+
+// Store reads and writes the history log. Entries are kept in memory and
+// only read again when another process changed the log.
+type Store struct {
+	path string
+	mu   sync.Mutex
+
+	entries []Entry        // oldest first
+	index   map[string]int // entry ID to its position in entries
+	lines   int            // lines in the log, including replaced ones
+	loaded  bool
+	size    int64 // log size and modification time when entries was read
+	modTime time.Time
+}
+
+// Open returns the store for the default history file
+func Open() (*Store, error) {
+	dir, err := config.ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+	return &Store{path: filepath.Join(dir, FileName)}, nil
+}
+
+// load returns the current entries, reading the log again if it changed
+// since the last read. The slice is shared; callers must not modify it.
+func (s *Store) load() ([]Entry, error) {
+	info, err := os.Stat(s.path)
+	if os.IsNotExist(err) {
+		if err := s.importLegacy(); err != nil {
+			return nil, err
+		}
+		info, err = os.Stat(s.path)
+	}
+	if os.IsNotExist(err) {
+		s.entries, s.index, s.lines = nil, map[string]int{}, 0
+		s.loaded, s.size, s.modTime = true, 0, time.Time{}
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	if s.loaded && info.Size() == s.size && info.ModTime().Equal(s.modTime) {
+		return s.entries, nil
+	}
+
+	f, err := os.Open(s.path)
+	if err != nil {
+		return nil, err
+	}
+	defer f.Close()
+
+	s.entries, s.index, s.lines = nil, map[string]int{}, 0
+	r := bufio.NewReader(f)
+	for {
+		line, readErr := r.ReadBytes('\n')
+		if line = bytes.TrimSpace(line); len(line) > 0 {
+			var e Entry
+			// A line torn by an interrupted write is skipped
+			if json.Unmarshal(line, &e) == nil {
+				s.lines++
+				s.set(e)
+			}
+		}
+		if readErr == io.EOF {
+			break
+		}
+		if readErr != nil {
+			s.loaded = false
+			return nil, readErr
+		}
+	}
+	s.loaded, s.size, s.modTime = true, info.Size(), info.ModTime()
+	return s.entries, nil
+}
+
+// importLegacy moves the entries of an old history.json into the log
+func (s *Store) importLegacy() error {
+	legacy := filepath.Join(filepath.Dir(s.path), legacyFileName)
+	data, err := os.ReadFile(legacy)
+	if os.IsNotExist(err) {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+	var entries []Entry
+	if err := json.Unmarshal(data, &entries); err != nil {
+		return fmt.Errorf("failed to parse %s: %w", legacy, err)
+	}
+
+	s.entries, s.index = nil, map[string]int{}
+	for _, e := range entries {
+		s.set(e)
+	}
+	if err := s.compact(); err != nil {
+		return err
+	}
+	return os.Remove(legacy)
+}
+
+// set puts e into the in-memory entries, replacing the entry with its ID
+func (s *Store) set(e Entry) {
+	if i, ok := s.index[e.ID]; ok {
+		s.entries[i] = e
+		return
+	}
+	s.index[e.ID] = len(s.entries)
+	s.entries = append(s.entries, e)
+}
+
+// put appends e to the log, then drops the oldest entries beyond maxEntries
+// and rewrites the log once it carries too many replaced lines
+func (s *Store) put(e Entry) error {
+	if _, err := s.load(); err != nil {
+		return err
+	}
+	line, err := json.Marshal(e)
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(filepath.Dir(s.path), 0755); err != nil {
+		return err
+	}
+	f, err := os.OpenFile(s.path, os.O_WRONLY|os.O_CREATE|os.O_APPEND, 0644)
+	if err != nil {
+		return err
+	}
+	_, err = f.Write(append(line, '\n'))
+	if closeErr := f.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		return err
+	}
+
+	s.set(e)
+	s.lines++
+	if info, err := os.Stat(s.path); err == nil && info.Size() == s.size+int64(len(line)+1) {
+		s.size, s.modTime = info.Size(), info.ModTime()
+	} else {
+		// Another process wrote to the log too; read it again next time
+		s.loaded = false
+	}
+
+	if len(s.entries) > maxEntries {
+		for _, dropped := range s.entries[:len(s.entries)-maxEntries] {
+			delete(s.index, dropped.ID)
+			if dropped.Thumbnail {
+				os.Remove(s.ThumbnailPath(dropped.ID))
+			}
+		}
+		s.entries = slices.Clone(s.entries[len(s.entries)-maxEntries:])
+		for i, kept := range s.entries {
+			s.index[kept.ID] = i
+		}
+		return s.compact()
+	}
+	if s.lines > len(s.entries)+compactSlack {
+		return s.compact()
+	}
+	return nil
+}
+
+// compact rewrites the log with one line per current entry
+func (s *Store) compact() error {
+	if err := os.MkdirAll(filepath.Dir(s.path), 0755); err != nil {
+		return err
+	}
+	var buf bytes.Buffer
+	for _, e := range s.entries {
+		line, err := json.Marshal(e)
+		if err != nil {
+			return err
+		}
+		buf.Write(line)
+		buf.WriteByte('\n')
+	}
+	tmp := s.path + ".tmp"
+	if err := os.WriteFile(tmp, buf.Bytes(), 0644); err != nil {
+		return err
+	}
+	if err := os.Rename(tmp, s.path); err != nil {
+		return err
+	}
+
+	s.lines = len(s.entries)
+	s.loaded = false
+	if info, err := os.Stat(s.path); err == nil {
+		s.loaded, s.size, s.modTime = true, info.Size(), info.ModTime()
+	}
+	return nil
+}
+
+// update applies fn to a copy of the entry with id and records the result
+func (s *Store) update(id string, fn func(*Entry)) (*Entry, error) {
+	entries, err := s.load()
+	if err != nil {
+		return nil, err
+	}
+	i, ok := s.index[id]
+	if !ok {
+		return nil, fmt.Errorf("history entry not found: %s", id)
+	}
+	e := entries[i]
+	fn(&e)
+	if err := s.put(e); err != nil {
+		return nil, err
+	}
+	return &e, nil
+}
+
+// Add records an entry, replacing any existing entry with the same ID
+func (s *Store) Add(e Entry) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	e.Tags = NormalizeTags(e.Tags)
+	return s.put(e)
+}
+
+// ThumbnailPath is where the thumbnail of the entry with id is saved
+func (s *Store) ThumbnailPath(id string) string {
+	return filepath.Join(filepath.Dir(s.path), ThumbnailDir, id+".jpg")
+}
+
+// Annotate updates an entry's tags and/or note. Nil arguments are left unchanged.
+func (s *Store) Annotate(id string, tags []string, note *string) (*Entry, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	return s.update(id, func(e *Entry) {
+		if tags != nil {
+			e.Tags = NormalizeTags(tags)
+		}
+		if note != nil {
+			e.Note = strings.TrimSpace(*note)
+		}
+	})
+}
+
+// Get returns an entry by ID, or nil if not found
+func (s *Store) Get(id string) (*Entry, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	entries, err := s.load()
+	if err != nil {
+		return nil, err
+	}
+	if i, ok := s.index[id]; ok {
+		e := entries[i]
+		return &e, nil
+	}
+	return nil, nil
+}
+
+// Search returns matching entries, newest first
+func (s *Store) Search(q Query) ([]Entry, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	entries, err := s.load()
+	if err != nil {
+		return nil, err
+	}
+
+	var result []Entry
+	for i := range entries {
+		if q.Matches(&entries[i]) {
+			result = append(result, entries[i])
+		}
+	}
+	sort.SliceStable(result, func(i, j int) bool {
+		return result[i].FinishedAt.After(result[j].FinishedAt)
+	})
+	if q.Limit > 0 && len(result) > q.Limit {
+		result = result[:q.Limit]
+	}
+	return result, nil
+}
+
+// Sites returns every site in the history with its entry count
+func (s *Store) Sites() (map[string]int, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	entries, err := s.load()
+	if err != nil {
+		return nil, err
+	}
+
+	counts := make(map[string]int)
+	for _, e := range entries {
+		if e.Site != "" {
+			counts[e.Site]++
+		}
+	}
+	return counts, nil
+}
+
+// Tags returns every tag in use with its entry count
+func (s *Store) Tags() (map[string]int, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	entries, err := s.load()
+	if err != nil {
+		return nil, err
+	}
+
+	counts := make(map[string]int)
+	for _, e := range entries {
+		for _, tag := range e.Tags {
+			counts[tag]++
+		}
+	}
+	return counts, nil
+}
+
+// Export writes entries as "json" or "csv". Tags are joined with ";" in CSV,
+// and text cells are escaped with csvText.
+func Export(w io.Writer, entries []Entry, format string) error {
+	switch format {
+	case "", "json":
+		enc := json.NewEncoder(w)
+		enc.SetIndent("", "  ")
+		if entries == nil {
+			entries = []Entry{}
+		}
+		return enc.Encode(entries)
+	case "csv":
+		cw := csv.NewWriter(w)
+		cw.Write([]string{"id", "url", "title", "filename", "status", "error", "size", "tags", "note", "created_at", "finished_at", "site", "duration"})
+		for _, e := range entries {
+			cw.Write([]string{
+				csvText(e.ID),
+				csvText(e.URL),
+				csvText(e.Title),
+				csvText(e.Filename),
+				csvText(e.Status),
+				csvText(e.Error),
+				strconv.FormatInt(e.Size, 10),
+				csvText(strings.Join(e.Tags, ";")),
+				csvText(e.Note),
+				e.CreatedAt.Format(time.RFC3339),
+				e.FinishedAt.Format(time.RFC3339),
+				csvText(e.Site),
+				strconv.Itoa(e.Duration),
+			})
+		}
+		cw.Flush()
+		return cw.Error()
+	default:
+		return fmt.Errorf("unsupported export format: %s (use json or csv)", format)
+	}
+}
+
+// csvText escapes a text cell so spreadsheets don't evaluate it: titles,
+// notes, and URLs come from sites and users, and a cell starting with "=",
+// "+", "-", "@", a tab, or a carriage return would be read as a formula.
+// A leading "'" makes spreadsheets show the rest as text.
+func csvText(s string) string {
+	if s != "" && strings.ContainsRune("=+-@\t\r", rune(s[0])) {
+		return "'" + s
+	}
+	return s
+}
@@ -0,0 +1,175 @@
+package history
+
+import (
+	"bytes"
+	"encoding/csv"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestQueryMatches(t *testing.T) {
+	finished := time.Date(2026, 3, 14, 12, 0, 0, 0, time.UTC)
+	e := &Entry{
+		URL:        "https://www.bilibili.com/video/BV1xx",
+		Title:      "Go Concurrency Patterns",
+		Site:       "bilibili",
+		Status:     "completed",
+		Tags:       []string{"talks"},
+		FinishedAt: finished,
+	}
+
+	tests := []struct {
+		name  string
+		query Query
+		want  bool
+	}{
+		{"empty", Query{}, true},
+		{"words in any order and field", Query{Text: "patterns BILIBILI talks"}, true},
+		{"missing word", Query{Text: "go rust"}, false},
+		{"site", Query{Site: "Bilibili"}, true},
+		{"other site", Query{Site: "youtube"}, false},
+		{"status", Query{Status: "failed"}, false},
+		{"in range", Query{Since: finished.Add(-time.Hour), Until: finished.Add(time.Hour)}, true},
+		{"until is exclusive", Query{Until: finished}, false},
+		{"before since", Query{Since: finished.Add(time.Minute)}, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.query.Matches(e); got != tt.want {
+				t.Errorf("Matches(%+v) = %v, want %v", tt.query, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSummarize(t *testing.T) {
+	day := func(d, hour int) time.Time { return time.Date(2026, 3, d, hour, 0, 0, 0, time.UTC) }
+	entries := []Entry{
+		{Site: "bilibili", Status: "completed", Size: 300, FinishedAt: day(14, 9)},
+		{Site: "youtube", Status: "completed", Size: 100, FinishedAt: day(14, 23)},
+		{Site: "bilibili", Status: "completed", Size: 200, FinishedAt: day(12, 8)},
+		{Site: "youtube", Status: "failed", Size: 999, FinishedAt: day(13, 8)},
+		{Status: "completed", Size: 50, FinishedAt: day(13, 8)},
+	}
+
+	usage := Summarize(entries, time.UTC)
+	if usage.Downloads != 4 || usage.Bytes != 650 {
+		t.Errorf("totals = %d downloads, %d bytes; want 4, 650", usage.Downloads, usage.Bytes)
+	}
+	wantDays := []DayUsage{{"2026-03-12", 1, 200}, {"2026-03-13", 1, 50}, {"2026-03-14", 2, 400}}
+	if len(usage.Days) != len(wantDays) {
+		t.Fatalf("Days = %+v, want %+v", usage.Days, wantDays)
+	}
+	for i, want := range wantDays {
+		if usage.Days[i] != want {
+			t.Errorf("Days[%d] = %+v, want %+v", i, usage.Days[i], want)
+		}
+	}
+	if usage.Sites[0] != (SiteUsage{"bilibili", 2, 500}) || usage.Sites[2] != (SiteUsage{"other", 1, 50}) {
+		t.Errorf("Sites = %+v, want bilibili first and other last", usage.Sites)
+	}
+
+	// The 23:00 UTC download falls on the next day east of UTC
+	if got := Summarize(entries, time.FixedZone("UTC+8", 8*3600)).Days; got[len(got)-1].Date != "2026-03-15" {
+		t.Errorf("Days in UTC+8 end on %s, want 2026-03-15", got[len(got)-1].Date)
+	}
+}
+
+func TestRangeStart(t *testing.T) {
+	now := time.Date(2026, 3, 14, 15, 30, 0, 0, time.UTC)
+	tests := map[string]time.Time{
+		"all":   {},
+		"7d":    time.Date(2026, 3, 8, 0, 0, 0, 0, time.UTC),
+		"30d":   time.Date(2026, 2, 13, 0, 0, 0, 0, time.UTC),
+		"month": time.Date(2026, 3, 1, 0, 0, 0, 0, time.UTC),
+		"year":  time.Date(2026, 1, 1, 0, 0, 0, 0, time.UTC),
+	}
+	for name, want := range tests {
+		if got, err := RangeStart(name, now); err != nil || !got.Equal(want) {
+			t.Errorf("RangeStart(%q) = %v, %v; want %v", name, got, err, want)
+		}
+	}
+	if _, err := RangeStart("week", now); err == nil {
+		t.Error("RangeStart(\"week\") should fail")
+	}
+}
+
+func TestStoreLog(t *testing.T) {
+	dir := t.TempDir()
+	legacy := `[{"id":"a","url":"https://example.com/a","status":"completed","created_at":"2026-03-14T12:00:00Z","finished_at":"2026-03-14T12:00:00Z"}]`
+	if err := os.WriteFile(filepath.Join(dir, legacyFileName), []byte(legacy), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	store := &Store{path: filepath.Join(dir, FileName)}
+	if err := store.Add(Entry{ID: "b", URL: "https://example.com/b", Status: "failed"}); err != nil {
+		t.Fatal(err)
+	}
+	note := "watch later"
+	if _, err := store.Annotate("a", []string{"music", " Music "}, &note); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := os.Stat(filepath.Join(dir, legacyFileName)); !os.IsNotExist(err) {
+		t.Errorf("legacy history.json still exists after import")
+	}
+
+	// The log holds the imported entry, the new one, and the annotation
+	data, err := os.ReadFile(store.path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if lines := bytes.Count(data, []byte("\n")); lines != 3 {
+		t.Errorf("log has %d lines, want 3", lines)
+	}
+
+	// A second store reads the same entries back, later lines winning
+	reopened := &Store{path: store.path}
+	a, err := reopened.Get("a")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if a == nil || a.Note != note || len(a.Tags) != 1 || a.Tags[0] != "music" {
+		t.Errorf("Get(a) = %+v, want the annotated entry", a)
+	}
+	if b, _ := reopened.Get("b"); b == nil || b.Status != "failed" {
+		t.Errorf("Get(b) = %+v", b)
+	}
+
+	// Another process appending to the log is picked up
+	if err := store.Add(Entry{ID: "c", URL: "https://example.com/c", Status: "completed"}); err != nil {
+		t.Fatal(err)
+	}
+	if c, _ := reopened.Get("c"); c == nil {
+		t.Error("Get(c) = nil after another store added it")
+	}
+}
+
+func TestExportCSVEscapesFormulas(t *testing.T) {
+	entries := []Entry{{ID: "a", URL: "https://example.com/a", Title: `=HYPERLINK("http://evil")`, Note: "-1+1", Tags: []string{"@x"}, Status: "completed"}}
+	var buf bytes.Buffer
+	if err := Export(&buf, entries, "csv"); err != nil {
+		t.Fatalf("Export() error = %v", err)
+	}
+
+	rows, err := csv.NewReader(&buf).ReadAll()
+	if err != nil {
+		t.Fatalf("reading export: %v", err)
+	}
+	row := rows[1]
+	tests := []struct {
+		column int
+		want   string
+	}{
+		{1, "https://example.com/a"},
+		{2, `'=HYPERLINK("http://evil")`},
+		{7, "'@x"},
+		{8, "'-1+1"},
+	}
+	for _, tt := range tests {
+		if row[tt.column] != tt.want {
+			t.Errorf("%s = %q, want %q", rows[0][tt.column], row[tt.column], tt.want)
+		}
+	}
+}
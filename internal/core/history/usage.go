@@ -0,0 +1,101 @@
+package history
+
+import (
+	"fmt"
+	"sort"
+	"time"
+)
+
+// DayUsage is what was downloaded on one day
+type DayUsage struct {
+	Date      string `json:"date"` // YYYY-MM-DD
+	Downloads int    `json:"downloads"`
+	Bytes     int64  `json:"bytes"`
+}
+
+// SiteUsage is what was downloaded from one site
+type SiteUsage struct {
+	Site      string `json:"site"`
+	Downloads int    `json:"downloads"`
+	Bytes     int64  `json:"bytes"`
+}
+
+// Usage sums up the completed downloads of a period
+type Usage struct {
+	Downloads int         `json:"downloads"`
+	Bytes     int64       `json:"bytes"` // size of the downloaded files
+	Days      []DayUsage  `json:"days"`  // oldest first, days without downloads omitted
+	Sites     []SiteUsage `json:"sites"` // most bytes first
+}
+
+// RangeStart returns when the named range began as of now. "all" and ""
+// return the zero time; "month" and "year" are calendar periods.
+func RangeStart(name string, now time.Time) (time.Time, error) {
+	today := time.Date(now.Year(), now.Month(), now.Day(), 0, 0, 0, 0, now.Location())
+	switch name {
+	case "", "all":
+		return time.Time{}, nil
+	case "7d":
+		return today.AddDate(0, 0, -6), nil
+	case "30d":
+		return today.AddDate(0, 0, -29), nil
+	case "month":
+		return today.AddDate(0, 0, 1-today.Day()), nil
+	case "year":
+		return time.Date(now.Year(), time.January, 1, 0, 0, 0, 0, now.Location()), nil
+	default:
+		return time.Time{}, fmt.Errorf("invalid range: %s (use 7d, 30d, month, year, or all)", name)
+	}
+}
+
+// Summarize totals the completed entries per day (in loc) and per site.
+// Entries without a site are counted under "other".
+func Summarize(entries []Entry, loc *time.Location) *Usage {
+	usage := &Usage{Days: []DayUsage{}, Sites: []SiteUsage{}}
+	days := make(map[string]*DayUsage)
+	sites := make(map[string]*SiteUsage)
+
+	for _, e := range entries {
+		if e.Status != "completed" {
+			continue
+		}
+		usage.Downloads++
+		usage.Bytes += e.Size
+
+		date := e.FinishedAt.In(loc).Format("2006-01-02")
+		day, ok := days[date]
+		if !ok {
+			day = &DayUsage{Date: date}
+			days[date] = day
+		}
+		day.Downloads++
+		day.Bytes += e.Size
+
+		name := e.Site
+		if name == "" {
+			name = "other"
+		}
+		site, ok := sites[name]
+		if !ok {
+			site = &SiteUsage{Site: name}
+			sites[name] = site
+		}
+		site.Downloads++
+		site.Bytes += e.Size
+	}
+
+	for _, day := range days {
+		usage.Days = append(usage.Days, *day)
+	}
+	for _, site := range sites {
+		usage.Sites = append(usage.Sites, *site)
+	}
+	sort.Slice(usage.Days, func(i, j int) bool { return usage.Days[i].Date < usage.Days[j].Date })
+	sort.Slice(usage.Sites, func(i, j int) bool {
+		if usage.Sites[i].Bytes != usage.Sites[j].Bytes {
+			return usage.Sites[i].Bytes > usage.Sites[j].Bytes
+		}
+		return usage.Sites[i].Site < usage.Sites[j].Site
+	})
+	return usage
+}
@@ -0,0 +1,112 @@
+package history
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"io"
+	"os"
+	"strings"
+	"time"
+)
+
+// Verification results
+const (
+	VerifyOK         = "ok"
+	VerifyCorrupted  = "corrupted"   // a file changed since it was downloaded
+	VerifyMissing    = "missing"     // a file no longer exists
+	VerifyNoChecksum = "no_checksum" // recorded before checksums were kept
+)
+
+// Verification is the result of checking an entry's files against their checksums
+type Verification struct {
+	Status    string    `json:"status"`
+	Missing   []string  `json:"missing,omitempty"`
+	Corrupted []string  `json:"corrupted,omitempty"`
+	CheckedAt time.Time `json:"checked_at"`
+}
+
+// OutputFiles returns the entry's output files. Entries recorded before Files
+// was kept only have Filename, which joined image outputs with ", "; it's
+// split only when it doesn't name an existing file itself.
+func (e *Entry) OutputFiles() []string {
+	if len(e.Files) > 0 {
+		return e.Files
+	}
+	if e.Filename == "" {
+		return nil
+	}
+	if _, err := os.Stat(e.Filename); err == nil {
+		return []string{e.Filename}
+	}
+	return strings.Split(e.Filename, ", ")
+}
+
+// HashFile returns the hex SHA-256 of the file at path
+func HashFile(path string) (string, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+
+	h := sha256.New()
+	if _, err := io.Copy(h, f); err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(h.Sum(nil)), nil
+}
+
+// Checksums hashes every existing file of the entry, keyed by path
+func Checksums(e *Entry) map[string]string {
+	sums := make(map[string]string)
+	for _, path := range e.OutputFiles() {
+		if sum, err := HashFile(path); err == nil {
+			sums[path] = sum
+		}
+	}
+	if len(sums) == 0 {
+		return nil
+	}
+	return sums
+}
+
+// Verify hashes the entry's files again and compares them with the
+// checksums recorded when it was downloaded
+func Verify(e *Entry) Verification {
+	v := Verification{CheckedAt: time.Now()}
+	for _, path := range e.OutputFiles() {
+		if _, err := os.Stat(path); err != nil {
+			v.Missing = append(v.Missing, path)
+			continue
+		}
+		want, ok := e.SHA256[path]
+		if !ok {
+			continue
+		}
+		if got, err := HashFile(path); err != nil || got != want {
+			v.Corrupted = append(v.Corrupted, path)
+		}
+	}
+
+	switch {
+	case len(v.Missing) > 0:
+		v.Status = VerifyMissing
+	case len(v.Corrupted) > 0:
+		v.Status = VerifyCorrupted
+	case len(e.SHA256) == 0:
+		v.Status = VerifyNoChecksum
+	default:
+		v.Status = VerifyOK
+	}
+	return v
+}
+
+// SetVerification records the result of verifying an entry
+func (s *Store) SetVerification(id string, v Verification) (*Entry, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	return s.update(id, func(e *Entry) {
+		e.Verification = &v
+	})
+}
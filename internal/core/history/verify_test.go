@@ -0,0 +1,58 @@
+package history
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+)
+
+func TestVerify(t *testing.T) {
+	dir := t.TempDir()
+	first := filepath.Join(dir, "1.jpg")
+	second := filepath.Join(dir, "2.jpg")
+	os.WriteFile(first, []byte("first"), 0644)
+	os.WriteFile(second, []byte("second"), 0644)
+
+	e := &Entry{Filename: first + ", " + second, Files: []string{first, second}}
+	if got := Verify(e).Status; got != VerifyNoChecksum {
+		t.Errorf("Verify() without checksums = %s, want %s", got, VerifyNoChecksum)
+	}
+
+	e.SHA256 = Checksums(e)
+	if got := Verify(e).Status; got != VerifyOK {
+		t.Errorf("Verify() = %s, want %s", got, VerifyOK)
+	}
+
+	os.WriteFile(second, []byte("changed"), 0644)
+	if v := Verify(e); v.Status != VerifyCorrupted || !slices.Equal(v.Corrupted, []string{second}) {
+		t.Errorf("Verify() after change = %+v, want %s of %s", v, VerifyCorrupted, second)
+	}
+
+	os.Remove(first)
+	if v := Verify(e); v.Status != VerifyMissing || !slices.Equal(v.Missing, []string{first}) {
+		t.Errorf("Verify() after removal = %+v, want %s of %s", v, VerifyMissing, first)
+	}
+}
+
+func TestOutputFiles(t *testing.T) {
+	dir := t.TempDir()
+	commaName := filepath.Join(dir, "Live, Remastered.mp4")
+	os.WriteFile(commaName, []byte("video"), 0644)
+
+	tests := []struct {
+		name  string
+		entry Entry
+		want  []string
+	}{
+		{"files", Entry{Filename: commaName, Files: []string{commaName}}, []string{commaName}},
+		{"legacy file with comma", Entry{Filename: commaName}, []string{commaName}},
+		{"legacy joined images", Entry{Filename: "a.jpg, b.jpg"}, []string{"a.jpg", "b.jpg"}},
+		{"no file", Entry{}, nil},
+	}
+	for _, tt := range tests {
+		if got := tt.entry.OutputFiles(); !slices.Equal(got, tt.want) {
+			t.Errorf("%s: OutputFiles() = %q, want %q", tt.name, got, tt.want)
+		}
+	}
+}
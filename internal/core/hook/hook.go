@@ -0,0 +1,93 @@
+// Package hook runs the command configured in download.hook after a
+// download completes, e.g. to move files into a media library or trigger a
+// Plex scan.
+package hook
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// Timeout bounds how long a hook may run before it is killed
+const Timeout = 10 * time.Minute
+
+// Vars are the values of a finished download available to the hook
+type Vars struct {
+	Path  string // output file
+	Title string
+	URL   string // page URL the download started from
+}
+
+// field is a value of a finished download, by placeholder and environment variable
+type field struct {
+	placeholder string
+	env         string
+	value       string
+}
+
+func (v Vars) fields() []field {
+	return []field{
+		{"{path}", "VGET_PATH", v.Path},
+		{"{dir}", "VGET_DIR", filepath.Dir(v.Path)},
+		{"{filename}", "VGET_FILENAME", filepath.Base(v.Path)},
+		{"{title}", "VGET_TITLE", v.Title},
+		{"{url}", "VGET_URL", v.URL},
+	}
+}
+
+// Expand replaces the {path}, {dir}, {filename}, {title}, and {url}
+// placeholders in command with values the shell can't interpret as commands
+func Expand(command string, v Vars) string {
+	var pairs []string
+	for _, f := range v.fields() {
+		pairs = append(pairs, f.placeholder, substitute(f))
+	}
+	return strings.NewReplacer(pairs...).Replace(command)
+}
+
+// Run runs command through the shell for a finished download and waits for
+// it. The values are also passed as VGET_PATH, VGET_DIR, VGET_FILENAME,
+// VGET_TITLE, and VGET_URL.
+// Output goes to out; when out is nil it is captured and the last line is
+// included in the error.
+func Run(command string, v Vars, out io.Writer) error {
+	if strings.TrimSpace(command) == "" || v.Path == "" {
+		return nil
+	}
+	if abs, err := filepath.Abs(v.Path); err == nil {
+		v.Path = abs
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), Timeout)
+	defer cancel()
+
+	cmd := shellCommand(ctx, Expand(command, v))
+	cmd.Env = os.Environ()
+	for _, f := range v.fields() {
+		cmd.Env = append(cmd.Env, f.env+"="+f.value)
+	}
+	var captured bytes.Buffer
+	if out == nil {
+		out = &captured
+	}
+	cmd.Stdout = out
+	cmd.Stderr = out
+
+	if err := cmd.Run(); err != nil {
+		if ctx.Err() == context.DeadlineExceeded {
+			return fmt.Errorf("hook timed out after %s", Timeout)
+		}
+		lines := strings.Split(strings.TrimSpace(captured.String()), "\n")
+		if last := lines[len(lines)-1]; last != "" {
+			return fmt.Errorf("hook failed: %w: %s", err, last)
+		}
+		return fmt.Errorf("hook failed: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,18 @@
+//go:build !windows
+
+package hook
+
+import "testing"
+
+func TestExpand(t *testing.T) {
+	v := Vars{
+		Path:  "/media/It's here.mp4",
+		Title: "It's here",
+		URL:   "https://example.com/v?id=1&t=2",
+	}
+	got := Expand("mv {path} /library/ && notify {title} {url} {dir} {filename}", v)
+	want := `mv '/media/It'\''s here.mp4' /library/ && notify 'It'\''s here' 'https://example.com/v?id=1&t=2' '/media' 'It'\''s here.mp4'`
+	if got != want {
+		t.Errorf("Expand() =\n%s\nwant\n%s", got, want)
+	}
+}
@@ -0,0 +1,23 @@
+//go:build !windows
+
+package hook
+
+import (
+	"context"
+	"os/exec"
+	"strings"
+)
+
+func shellCommand(ctx context.Context, command string) *exec.Cmd {
+	return exec.CommandContext(ctx, "/bin/sh", "-c", command)
+}
+
+// substitute puts the value itself in the command, quoted
+func substitute(f field) string {
+	return shellQuote(f.value)
+}
+
+// shellQuote wraps s in single quotes, which sh takes literally
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
@@ -0,0 +1,26 @@
+//go:build windows
+
+package hook
+
+import (
+	"context"
+	"os/exec"
+	"syscall"
+)
+
+// shellCommand runs command with cmd.exe and delayed expansion on. The command
+// line is passed as is, since Go's argument escaping doesn't follow cmd.exe
+// quoting rules.
+func shellCommand(ctx context.Context, command string) *exec.Cmd {
+	cmd := exec.CommandContext(ctx, "cmd.exe")
+	cmd.SysProcAttr = &syscall.SysProcAttr{CmdLine: `cmd.exe /V:ON /S /C "` + command + `"`}
+	return cmd
+}
+
+// substitute refers to the value's environment variable instead of putting the
+// value in the command. No quoting stops cmd.exe from interpreting %, ^, &, or
+// | in a value, but !VAR! is expanded after the command line is parsed, so the
+// value is never read as part of the command.
+func substitute(f field) string {
+	return `"!` + f.env + `!"`
+}
@@ -69,6 +69,8 @@ type DownloadTranslations struct {
 	ETA              string `yaml:"eta"`
 	Elapsed          string `yaml:"elapsed"`
 	AvgSpeed         string `yaml:"avg_speed"`
+	Connections      string `yaml:"connections"`
+	Segments         string `yaml:"segments"`
 	FileSaved        string `yaml:"file_saved"`
 	NoFormats        string `yaml:"no_formats"`
 	SelectFormat     string `yaml:"select_format"`
@@ -130,52 +132,62 @@ type SitesTranslations struct {
 
 // UITranslations holds translations for the web UI
 type UITranslations struct {
-	DownloadTo       string `yaml:"download_to" json:"download_to"`
-	Edit             string `yaml:"edit" json:"edit"`
-	Save             string `yaml:"save" json:"save"`
-	Cancel           string `yaml:"cancel" json:"cancel"`
-	PasteURL         string `yaml:"paste_url" json:"paste_url"`
-	Download         string `yaml:"download" json:"download"`
-	BulkDownload     string `yaml:"bulk_download" json:"bulk_download"`
-	ComingSoon       string `yaml:"coming_soon" json:"coming_soon"`
-	BulkPasteURLs    string `yaml:"bulk_paste_urls" json:"bulk_paste_urls"`
-	BulkSelectFile   string `yaml:"bulk_select_file" json:"bulk_select_file"`
-	BulkDragDrop     string `yaml:"bulk_drag_drop" json:"bulk_drag_drop"`
-	BulkURLCount     string `yaml:"bulk_url_count" json:"bulk_url_count"`
-	BulkSubmitAll    string `yaml:"bulk_submit_all" json:"bulk_submit_all"`
-	BulkSubmitting   string `yaml:"bulk_submitting" json:"bulk_submitting"`
-	BulkClear        string `yaml:"bulk_clear" json:"bulk_clear"`
-	BulkInvalidHint  string `yaml:"bulk_invalid_hint" json:"bulk_invalid_hint"`
-	Adding           string `yaml:"adding" json:"adding"`
-	Jobs             string `yaml:"jobs" json:"jobs"`
-	Total            string `yaml:"total" json:"total"`
-	NoDownloads      string `yaml:"no_downloads" json:"no_downloads"`
-	PasteHint        string `yaml:"paste_hint" json:"paste_hint"`
-	Queued           string `yaml:"queued" json:"queued"`
-	Downloading      string `yaml:"downloading" json:"downloading"`
-	Completed        string `yaml:"completed" json:"completed"`
-	Failed           string `yaml:"failed" json:"failed"`
-	Cancelled        string `yaml:"cancelled" json:"cancelled"`
-	Settings         string `yaml:"settings" json:"settings"`
-	Language         string `yaml:"language" json:"language"`
-	Format           string `yaml:"format" json:"format"`
-	Quality          string `yaml:"quality" json:"quality"`
-	TwitterAuth      string `yaml:"twitter_auth" json:"twitter_auth"`
-	ServerPort       string `yaml:"server_port" json:"server_port"`
-	MaxConcurrent    string `yaml:"max_concurrent" json:"max_concurrent"`
-	APIKey           string `yaml:"api_key" json:"api_key"`
-	WebDAVServers    string `yaml:"webdav_servers" json:"webdav_servers"`
-	Add              string `yaml:"add" json:"add"`
-	Delete           string `yaml:"delete" json:"delete"`
-	Name             string `yaml:"name" json:"name"`
-	URL              string `yaml:"url" json:"url"`
-	Username         string `yaml:"username" json:"username"`
-	Password         string `yaml:"password" json:"password"`
-	NoWebDAVServers  string `yaml:"no_webdav_servers" json:"no_webdav_servers"`
-	Configured       string `yaml:"configured" json:"configured"`
-	NotConfigured    string `yaml:"not_configured" json:"not_configured"`
-	ClearHistory     string `yaml:"clear_history" json:"clear_history"`
-	ClearAll         string `yaml:"clear_all" json:"clear_all"`
+	DownloadTo        string `yaml:"download_to" json:"download_to"`
+	Edit              string `yaml:"edit" json:"edit"`
+	Save              string `yaml:"save" json:"save"`
+	Cancel            string `yaml:"cancel" json:"cancel"`
+	PasteURL          string `yaml:"paste_url" json:"paste_url"`
+	Download          string `yaml:"download" json:"download"`
+	BulkDownload      string `yaml:"bulk_download" json:"bulk_download"`
+	ComingSoon        string `yaml:"coming_soon" json:"coming_soon"`
+	BulkPasteURLs     string `yaml:"bulk_paste_urls" json:"bulk_paste_urls"`
+	BulkSelectFile    string `yaml:"bulk_select_file" json:"bulk_select_file"`
+	BulkDragDrop      string `yaml:"bulk_drag_drop" json:"bulk_drag_drop"`
+	BulkURLCount      string `yaml:"bulk_url_count" json:"bulk_url_count"`
+	BulkSubmitAll     string `yaml:"bulk_submit_all" json:"bulk_submit_all"`
+	BulkSubmitting    string `yaml:"bulk_submitting" json:"bulk_submitting"`
+	BulkClear         string `yaml:"bulk_clear" json:"bulk_clear"`
+	BulkInvalidHint   string `yaml:"bulk_invalid_hint" json:"bulk_invalid_hint"`
+	Adding            string `yaml:"adding" json:"adding"`
+	Jobs              string `yaml:"jobs" json:"jobs"`
+	Total             string `yaml:"total" json:"total"`
+	NoDownloads       string `yaml:"no_downloads" json:"no_downloads"`
+	PasteHint         string `yaml:"paste_hint" json:"paste_hint"`
+	Queued            string `yaml:"queued" json:"queued"`
+	Downloading       string `yaml:"downloading" json:"downloading"`
+	Completed         string `yaml:"completed" json:"completed"`
+	Failed            string `yaml:"failed" json:"failed"`
+	Cancelled         string `yaml:"cancelled" json:"cancelled"`
+	WaitingNetwork    string `yaml:"waiting_network" json:"waiting_network"`
+	StageVideo        string `yaml:"stage_video" json:"stage_video"`
+	StageAudio        string `yaml:"stage_audio" json:"stage_audio"`
+	StageMerging      string `yaml:"stage_merging" json:"stage_merging"`
+	StagePostProcess  string `yaml:"stage_post_process" json:"stage_post_process"`
+	NoDiskSpace       string `yaml:"no_disk_space" json:"no_disk_space"`
+	AlreadyDownloaded string `yaml:"already_downloaded" json:"already_downloaded"`
+	DownloadAgain     string `yaml:"download_again" json:"download_again"`
+	DownloadedFrom    string `yaml:"downloaded_from" json:"downloaded_from"`
+	StartsAt          string `yaml:"starts_at" json:"starts_at"`
+	Settings          string `yaml:"settings" json:"settings"`
+	Language          string `yaml:"language" json:"language"`
+	Format            string `yaml:"format" json:"format"`
+	Quality           string `yaml:"quality" json:"quality"`
+	TwitterAuth       string `yaml:"twitter_auth" json:"twitter_auth"`
+	ServerPort        string `yaml:"server_port" json:"server_port"`
+	MaxConcurrent     string `yaml:"max_concurrent" json:"max_concurrent"`
+	APIKey            string `yaml:"api_key" json:"api_key"`
+	WebDAVServers     string `yaml:"webdav_servers" json:"webdav_servers"`
+	Add               string `yaml:"add" json:"add"`
+	Delete            string `yaml:"delete" json:"delete"`
+	Name              string `yaml:"name" json:"name"`
+	URL               string `yaml:"url" json:"url"`
+	Username          string `yaml:"username" json:"username"`
+	Password          string `yaml:"password" json:"password"`
+	NoWebDAVServers   string `yaml:"no_webdav_servers" json:"no_webdav_servers"`
+	Configured        string `yaml:"configured" json:"configured"`
+	NotConfigured     string `yaml:"not_configured" json:"not_configured"`
+	ClearHistory      string `yaml:"clear_history" json:"clear_history"`
+	ClearAll          string `yaml:"clear_all" json:"clear_all"`
 	// WebDAV
 	WebDAVBrowser    string `yaml:"webdav_browser" json:"webdav_browser"`
 	SelectRemote     string `yaml:"select_remote" json:"select_remote"`
@@ -201,6 +213,22 @@ type UITranslations struct {
 	// Toast
 	DownloadQueued  string `yaml:"download_queued" json:"download_queued"`
 	DownloadsQueued string `yaml:"downloads_queued" json:"downloads_queued"`
+	// History
+	History            string `yaml:"history" json:"history"`
+	HistorySearch      string `yaml:"history_search" json:"history_search"`
+	HistoryAllSites    string `yaml:"history_all_sites" json:"history_all_sites"`
+	HistoryAllStatuses string `yaml:"history_all_statuses" json:"history_all_statuses"`
+	HistoryFrom        string `yaml:"history_from" json:"history_from"`
+	HistoryTo          string `yaml:"history_to" json:"history_to"`
+	HistoryEmpty       string `yaml:"history_empty" json:"history_empty"`
+	HistoryOpen        string `yaml:"history_open" json:"history_open"`
+	HistoryReveal      string `yaml:"history_reveal" json:"history_reveal"`
+	HistorySaveFile    string `yaml:"history_save_file" json:"history_save_file"`
+	HistoryVerify      string `yaml:"history_verify" json:"history_verify"`
+	HistoryIntact      string `yaml:"history_intact" json:"history_intact"`
+	HistoryCorrupted   string `yaml:"history_corrupted" json:"history_corrupted"`
+	HistoryMissing     string `yaml:"history_missing" json:"history_missing"`
+	HistoryNoChecksum  string `yaml:"history_no_checksum" json:"history_no_checksum"`
 	// Podcast
 	Podcast                string `yaml:"podcast" json:"podcast"`
 	PodcastSearch          string `yaml:"podcast_search" json:"podcast_search"`
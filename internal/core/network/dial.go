@@ -0,0 +1,72 @@
+package network
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"net/http"
+	"net/url"
+	"sync"
+	"syscall"
+	"time"
+)
+
+// proxyPorts are the ports http.Transport dials for proxy URLs without one
+var proxyPorts = map[string]string{"http": "80", "https": "443", "socks5": "1080", "socks5h": "1080"}
+
+// LocalIP reports whether ip is loopback, private, link-local, or unspecified
+func LocalIP(ip net.IP) bool {
+	return ip.IsLoopback() || ip.IsPrivate() || ip.IsUnspecified() ||
+		ip.IsLinkLocalUnicast() || ip.IsLinkLocalMulticast() || ip.IsInterfaceLocalMulticast()
+}
+
+// PublicOnly is a net.Dialer Control that refuses connections to local
+// addresses. It runs on the address actually dialed, so a host can't pass a
+// check and then resolve elsewhere.
+func PublicOnly(network, address string, _ syscall.RawConn) error {
+	host, _, err := net.SplitHostPort(address)
+	if err != nil {
+		return err
+	}
+	if ip := net.ParseIP(host); ip == nil || LocalIP(ip) {
+		return fmt.Errorf("connecting to local address %s is not allowed", host)
+	}
+	return nil
+}
+
+// ControlDials makes t run control on every connection it dials, with the
+// address actually dialed. Connections to the proxies t.Proxy picks aren't
+// passed to control, the proxy resolves the host itself.
+func ControlDials(t *http.Transport, control func(network, address string, conn syscall.RawConn) error) {
+	// The transport asks for a request's proxy before dialing it, by the
+	// proxy URL's host and port
+	var mu sync.Mutex
+	proxies := make(map[string]bool)
+	if proxyFn := t.Proxy; proxyFn != nil {
+		t.Proxy = func(req *http.Request) (*url.URL, error) {
+			u, err := proxyFn(req)
+			if u != nil {
+				port := u.Port()
+				if port == "" {
+					port = proxyPorts[u.Scheme]
+				}
+				mu.Lock()
+				proxies[net.JoinHostPort(u.Hostname(), port)] = true
+				mu.Unlock()
+			}
+			return u, err
+		}
+	}
+
+	direct := &net.Dialer{Timeout: 15 * time.Second, KeepAlive: 30 * time.Second}
+	checked := &net.Dialer{Timeout: 15 * time.Second, KeepAlive: 30 * time.Second, Control: control}
+	t.DialContext = func(ctx context.Context, network, addr string) (net.Conn, error) {
+		mu.Lock()
+		proxy := proxies[addr]
+		mu.Unlock()
+		if proxy {
+			return direct.DialContext(ctx, network, addr)
+		}
+		return checked.DialContext(ctx, network, addr)
+	}
+}
@@ -0,0 +1,68 @@
+// Package network tells a lost connection apart from other download failures
+package network
+
+import (
+	"context"
+	"errors"
+	"io"
+	"net"
+	"net/url"
+	"time"
+)
+
+// probeAddrs are dialed to check connectivity; any one answering is enough.
+// 223.5.5.5 (AliDNS) covers networks where the others are blocked.
+var probeAddrs = []string{"1.1.1.1:443", "8.8.8.8:53", "223.5.5.5:53"}
+
+// probeTimeout bounds a connectivity check
+const probeTimeout = 3 * time.Second
+
+// Online reports whether the network is reachable. With a proxy URL only the
+// proxy is checked, since downloads go nowhere else.
+func Online(ctx context.Context, proxy string) bool {
+	addrs := probeAddrs
+	if proxy != "" {
+		u, err := url.Parse(proxy)
+		if err != nil || u.Hostname() == "" {
+			return true
+		}
+		port := u.Port()
+		if port == "" {
+			port = map[string]string{"https": "443", "socks5": "1080", "socks5h": "1080"}[u.Scheme]
+			if port == "" {
+				port = "80"
+			}
+		}
+		addrs = []string{net.JoinHostPort(u.Hostname(), port)}
+	}
+
+	ctx, cancel := context.WithTimeout(ctx, probeTimeout)
+	defer cancel()
+	results := make(chan bool, len(addrs))
+	for _, addr := range addrs {
+		go func() {
+			var d net.Dialer
+			conn, err := d.DialContext(ctx, "tcp", addr)
+			if err == nil {
+				conn.Close()
+			}
+			results <- err == nil
+		}()
+	}
+	for range addrs {
+		if <-results {
+			return true
+		}
+	}
+	return false
+}
+
+// IsNetworkError reports whether err came from the connection (DNS, dial,
+// reset, timeout, cut-off body) rather than from what a server answered
+func IsNetworkError(err error) bool {
+	if err == nil || errors.Is(err, context.Canceled) {
+		return false
+	}
+	var netErr net.Error
+	return errors.As(err, &netErr) || errors.Is(err, io.ErrUnexpectedEOF)
+}
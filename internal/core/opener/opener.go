@@ -0,0 +1,129 @@
+// Package opener runs the configured completion action (open, reveal, play)
+// for a downloaded file using the operating system's default handlers.
+package opener
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// Action is what happens to a file after its download completes
+type Action string
+
+const (
+	ActionNone   Action = "none"
+	ActionOpen   Action = "open"   // open with the default application
+	ActionReveal Action = "reveal" // show in file manager (Finder, Explorer)
+	ActionPlay   Action = "play"   // open audio/video in the default player
+)
+
+// playableExtensions are file types handed to the default media player for ActionPlay
+var playableExtensions = map[string]bool{
+	".mp4": true, ".mkv": true, ".webm": true, ".mov": true, ".avi": true,
+	".flv": true, ".ts": true, ".m4v": true,
+	".mp3": true, ".m4a": true, ".aac": true, ".ogg": true, ".opus": true,
+	".wav": true, ".flac": true,
+}
+
+// openableExtensions are file types ActionOpen hands to their default
+// application: media and documents. Anything else, such as a script or an
+// executable a site served, is revealed in the file manager instead of run.
+var openableExtensions = map[string]bool{
+	".jpg": true, ".jpeg": true, ".png": true, ".gif": true, ".webp": true,
+	".avif": true, ".heic": true, ".bmp": true,
+	".pdf": true, ".epub": true, ".txt": true, ".md": true,
+	".srt": true, ".vtt": true, ".ass": true, ".lrc": true,
+}
+
+// ParseAction validates an action name. Empty means ActionNone.
+func ParseAction(s string) (Action, error) {
+	switch a := Action(strings.ToLower(strings.TrimSpace(s))); a {
+	case "", ActionNone:
+		return ActionNone, nil
+	case ActionOpen, ActionReveal, ActionPlay:
+		return a, nil
+	default:
+		return ActionNone, fmt.Errorf("invalid completion action: %s (use none, open, reveal, or play)", s)
+	}
+}
+
+// Run performs the action for path. If the file no longer exists at path
+// (e.g., renamed after download), its directory is revealed instead.
+// Does nothing inside Docker, where there is no desktop to open files on.
+func Run(action Action, path string) error {
+	if action == ActionNone || action == "" || path == "" || config.IsRunningInDocker() {
+		return nil
+	}
+
+	absPath, err := filepath.Abs(path)
+	if err != nil {
+		return err
+	}
+
+	if _, err := os.Stat(absPath); err != nil {
+		return openPath(filepath.Dir(absPath))
+	}
+
+	switch action {
+	case ActionOpen:
+		ext := strings.ToLower(filepath.Ext(absPath))
+		if !playableExtensions[ext] && !openableExtensions[ext] {
+			return revealPath(absPath)
+		}
+		return openPath(absPath)
+	case ActionReveal:
+		return revealPath(absPath)
+	case ActionPlay:
+		if !playableExtensions[strings.ToLower(filepath.Ext(absPath))] {
+			return nil
+		}
+		return openPath(absPath)
+	}
+	return nil
+}
+
+// openPath opens a file or directory with the default handler.
+// Arguments are passed directly to the process (no shell), so paths need no escaping.
+func openPath(path string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("open", path)
+	case "windows":
+		// rundll32 avoids cmd.exe "start" quoting rules
+		cmd = exec.Command("rundll32", "url.dll,FileProtocolHandler", path)
+	default:
+		cmd = exec.Command("xdg-open", path)
+	}
+	return start(cmd)
+}
+
+// revealPath shows the file selected in the platform file manager.
+// Linux has no standard "select file" call, so the parent directory is opened.
+func revealPath(path string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("open", "-R", path)
+	case "windows":
+		cmd = exec.Command("explorer", "/select,", path)
+	default:
+		cmd = exec.Command("xdg-open", filepath.Dir(path))
+	}
+	return start(cmd)
+}
+
+// start launches cmd without waiting for the application to exit, reaping it in the background
+func start(cmd *exec.Cmd) error {
+	if err := cmd.Start(); err != nil {
+		return err
+	}
+	go cmd.Wait()
+	return nil
+}
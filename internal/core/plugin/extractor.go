@@ -0,0 +1,306 @@
+package plugin
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"crypto/rand"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"log"
+	"net"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/network"
+	"github.com/tetratelabs/wazero"
+	"github.com/tetratelabs/wazero/imports/wasi_snapshot_preview1"
+	"github.com/tetratelabs/wazero/sys"
+)
+
+const (
+	// extractTimeout bounds a whole plugin run, including its HTTP requests
+	extractTimeout = 60 * time.Second
+
+	// maxFetchBody caps the response body handed to a plugin
+	maxFetchBody = 16 * 1024 * 1024
+
+	// maxMessageSize caps a single line written by a plugin
+	maxMessageSize = 32 * 1024 * 1024
+
+	// memoryLimitPages caps plugin memory (64 KiB pages, 256 MiB total)
+	memoryLimitPages = 4096
+)
+
+// Extractor runs a WASM plugin as an extractor.Extractor
+type Extractor struct {
+	manifest *Manifest
+}
+
+var _ extractor.ContextExtractor = (*Extractor)(nil)
+
+func (e *Extractor) Name() string {
+	return "plugin:" + e.manifest.Name
+}
+
+func (e *Extractor) Match(u *url.URL) bool {
+	return e.manifest.Matches(u)
+}
+
+func (e *Extractor) Extract(rawURL string) (extractor.Media, error) {
+	return e.ExtractWithContext(rawURL, nil)
+}
+
+// ExtractWithContext is Extract with the plugin's fetches sent through the
+// context's HTTP client, so proxy, user agent, and cookie settings apply. The
+// client refuses to connect to local addresses.
+func (e *Extractor) ExtractWithContext(rawURL string, ectx *extractor.ExtractContext) (extractor.Media, error) {
+	client := ectx.HTTPClientWithControl(network.PublicOnly)
+
+	ctx, cancel := context.WithTimeout(context.Background(), extractTimeout)
+	defer cancel()
+
+	wasmBytes, err := os.ReadFile(e.manifest.WasmPath())
+	if err != nil {
+		return nil, fmt.Errorf("failed to read plugin %s: %w", e.manifest.Name, err)
+	}
+
+	runtime := wazero.NewRuntimeWithConfig(ctx, wazero.NewRuntimeConfig().
+		WithCloseOnContextDone(true).
+		WithMemoryLimitPages(memoryLimitPages))
+	defer runtime.Close(context.Background())
+
+	wasi_snapshot_preview1.MustInstantiate(ctx, runtime)
+
+	compiled, err := runtime.CompileModule(ctx, wasmBytes)
+	if err != nil {
+		return nil, fmt.Errorf("failed to compile plugin %s: %w", e.manifest.Name, err)
+	}
+
+	stdinR, stdinW := io.Pipe()
+	stdoutR, stdoutW := io.Pipe()
+	var stderr limitedBuffer
+
+	// No FS mounts, env, or sockets: the only way out is through the host on stdout
+	modConfig := wazero.NewModuleConfig().
+		WithName("").
+		WithArgs(e.manifest.Name, "extract", rawURL).
+		WithStdin(stdinR).
+		WithStdout(stdoutW).
+		WithStderr(&stderr).
+		WithSysWalltime().
+		WithSysNanotime().
+		WithRandSource(rand.Reader)
+
+	runErr := make(chan error, 1)
+	go func() {
+		_, err := runtime.InstantiateModule(ctx, compiled, modConfig)
+		stdoutW.Close()
+		runErr <- err
+	}()
+
+	media, protoErr := e.serve(ctx, client, stdoutR, stdinW)
+	stdinW.Close()
+	// Drain so the module never blocks on a write after we stop reading
+	io.Copy(io.Discard, stdoutR)
+
+	err = <-runErr
+	var exitErr *sys.ExitError
+	if errors.As(err, &exitErr) && exitErr.ExitCode() == 0 {
+		err = nil
+	}
+
+	if protoErr != nil {
+		return nil, protoErr
+	}
+	if ctx.Err() == context.DeadlineExceeded {
+		return nil, fmt.Errorf("plugin %s timed out after %s", e.manifest.Name, extractTimeout)
+	}
+	if err != nil {
+		if msg := strings.TrimSpace(stderr.String()); msg != "" {
+			return nil, fmt.Errorf("plugin %s failed: %w: %s", e.manifest.Name, err, msg)
+		}
+		return nil, fmt.Errorf("plugin %s failed: %w", e.manifest.Name, err)
+	}
+	if media == nil {
+		return nil, fmt.Errorf("plugin %s exited without returning media", e.manifest.Name)
+	}
+	return media, nil
+}
+
+// serve handles plugin requests until it returns a result, an error, or exits
+func (e *Extractor) serve(ctx context.Context, client *http.Client, out io.Reader, in io.Writer) (extractor.Media, error) {
+	scanner := bufio.NewScanner(out)
+	scanner.Buffer(make([]byte, 64*1024), maxMessageSize)
+	enc := json.NewEncoder(in)
+
+	for scanner.Scan() {
+		line := bytes.TrimSpace(scanner.Bytes())
+		if len(line) == 0 {
+			continue
+		}
+
+		var req request
+		if err := json.Unmarshal(line, &req); err != nil {
+			return nil, fmt.Errorf("plugin %s sent invalid message: %w", e.manifest.Name, err)
+		}
+
+		switch req.Op {
+		case "fetch":
+			if err := enc.Encode(e.fetch(ctx, client, &req)); err != nil {
+				return nil, fmt.Errorf("plugin %s stopped reading: %w", e.manifest.Name, err)
+			}
+		case "log":
+			log.Printf("[%s] %s", e.Name(), req.Message)
+		case "result":
+			if req.Media == nil {
+				return nil, fmt.Errorf("plugin %s returned an empty result", e.manifest.Name)
+			}
+			return req.Media.toMedia(ctx)
+		case "error":
+			return nil, fmt.Errorf("%s: %s", e.manifest.Name, req.Message)
+		default:
+			return nil, fmt.Errorf("plugin %s sent unknown op: %q", e.manifest.Name, req.Op)
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("plugin %s: %w", e.manifest.Name, err)
+	}
+	return nil, nil
+}
+
+// fetch performs an HTTP request on behalf of a plugin. Only the plugin's
+// own hosts may be requested, redirects included, and client never connects
+// to a local address.
+func (e *Extractor) fetch(ctx context.Context, client *http.Client, req *request) *fetchResponse {
+	u, err := url.Parse(req.URL)
+	if err != nil {
+		return &fetchResponse{Error: err.Error()}
+	}
+	if err := e.checkFetchURL(u); err != nil {
+		return &fetchResponse{Error: err.Error()}
+	}
+
+	method := req.Method
+	if method == "" {
+		method = http.MethodGet
+	}
+
+	var body io.Reader
+	if req.Body != "" {
+		body = strings.NewReader(req.Body)
+	}
+
+	httpReq, err := http.NewRequestWithContext(ctx, method, req.URL, body)
+	if err != nil {
+		return &fetchResponse{Error: err.Error()}
+	}
+	httpReq.Header.Set("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36")
+	for k, v := range req.Headers {
+		httpReq.Header.Set(k, v)
+	}
+
+	checked := *client
+	checked.CheckRedirect = func(r *http.Request, via []*http.Request) error {
+		if len(via) >= 10 {
+			return errors.New("stopped after 10 redirects")
+		}
+		return e.checkFetchURL(r.URL)
+	}
+
+	resp, err := checked.Do(httpReq)
+	if err != nil {
+		return &fetchResponse{Error: err.Error()}
+	}
+	defer resp.Body.Close()
+
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxFetchBody))
+	if err != nil {
+		return &fetchResponse{Status: resp.StatusCode, Error: err.Error()}
+	}
+
+	headers := make(map[string]string, len(resp.Header))
+	for k := range resp.Header {
+		headers[k] = resp.Header.Get(k)
+	}
+
+	return &fetchResponse{
+		Status:  resp.StatusCode,
+		URL:     resp.Request.URL.String(),
+		Headers: headers,
+		Body:    string(data),
+	}
+}
+
+// checkFetchURL allows http and https URLs on the plugin's hosts. Where the
+// hosts resolve to is checked by network.PublicOnly when connecting, keeping plugins
+// away from the local machine, the local network, and cloud metadata endpoints.
+func (e *Extractor) checkFetchURL(u *url.URL) error {
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return errors.New("only http and https URLs are allowed")
+	}
+	host := u.Hostname()
+	if !e.manifest.AllowsHost(host) {
+		return fmt.Errorf("host %s is not listed in the plugin's hosts", host)
+	}
+	return checkPublicHost(host)
+}
+
+// checkPublicHost rejects localhost and local IP addresses given as the host
+// of a URL, before anything is resolved or dialed
+func checkPublicHost(host string) error {
+	host = strings.TrimSuffix(strings.ToLower(host), ".")
+	if host == "localhost" || strings.HasSuffix(host, ".localhost") {
+		return fmt.Errorf("host %s is a local address", host)
+	}
+	if ip := net.ParseIP(host); ip != nil && network.LocalIP(ip) {
+		return fmt.Errorf("host %s is a local address", host)
+	}
+	return nil
+}
+
+// limitedBuffer keeps the first 4 KiB written to it (plugin stderr)
+type limitedBuffer struct {
+	bytes.Buffer
+}
+
+func (b *limitedBuffer) Write(p []byte) (int, error) {
+	if room := 4096 - b.Len(); room > 0 {
+		if len(p) > room {
+			b.Buffer.Write(p[:room])
+		} else {
+			b.Buffer.Write(p)
+		}
+	}
+	return len(p), nil
+}
+
+// provider exposes installed plugins to the extractor registry
+type provider struct{}
+
+func (provider) Match(rawURL string) extractor.Extractor {
+	// Avoid returning a typed nil inside the interface
+	if e := Match(rawURL); e != nil {
+		return e
+	}
+	return nil
+}
+
+func (provider) Sites() []extractor.SiteInfo {
+	manifests, _ := List()
+	sites := make([]extractor.SiteInfo, 0, len(manifests))
+	for _, m := range manifests {
+		sites = append(sites, extractor.SiteInfo{Name: m.Name, Hosts: m.Hosts, Source: extractor.SourcePlugin})
+	}
+	return sites
+}
+
+func init() {
+	extractor.RegisterProvider(provider{})
+}
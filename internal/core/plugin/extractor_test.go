@@ -0,0 +1,38 @@
+package plugin
+
+import (
+	"net/url"
+	"testing"
+)
+
+func TestCheckFetchURL(t *testing.T) {
+	e := &Extractor{manifest: &Manifest{
+		Name:  "example",
+		Hosts: []string{"example.com", "localhost", "127.0.0.1", "10.0.0.5", "169.254.169.254", "::1"},
+	}}
+	tests := []struct {
+		url string
+		ok  bool
+	}{
+		{"https://example.com/video", true},
+		{"http://cdn.example.com/video", true},
+		{"https://evil.net/", false},
+		{"https://example.com.evil.net/", false},
+		{"ftp://example.com/video", false},
+		{"file:///etc/passwd", false},
+		{"http://localhost:8080/", false},
+		{"http://127.0.0.1/", false},
+		{"http://[::1]/", false},
+		{"http://10.0.0.5/", false},
+		{"http://169.254.169.254/latest/meta-data/", false},
+	}
+	for _, tt := range tests {
+		u, err := url.Parse(tt.url)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if err := e.checkFetchURL(u); (err == nil) != tt.ok {
+			t.Errorf("checkFetchURL(%q) = %v, want ok %v", tt.url, err, tt.ok)
+		}
+	}
+}
@@ -0,0 +1,160 @@
+// Package plugin loads community extractor plugins compiled to WebAssembly (WASI).
+//
+// Each plugin lives in its own directory under ~/.config/vget/plugins/:
+//
+//	plugins/
+//	  example/
+//	    plugin.yml   # name, hosts, optional path pattern
+//	    plugin.wasm  # WASI command module
+//
+// Plugins run sandboxed: no filesystem, environment, or sockets. They talk to vget
+// over stdin/stdout with one JSON object per line (see protocol.go), asking the
+// host to perform HTTP requests and finally returning the extracted media.
+package plugin
+
+import (
+	"fmt"
+	"net/url"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"gopkg.in/yaml.v3"
+)
+
+const (
+	// DirName is the plugins directory inside the config directory
+	DirName = "plugins"
+
+	manifestFile = "plugin.yml"
+	defaultWasm  = "plugin.wasm"
+)
+
+// Manifest describes a plugin
+type Manifest struct {
+	Name        string   `yaml:"name"`
+	Version     string   `yaml:"version,omitempty"`
+	Description string   `yaml:"description,omitempty"`
+	Hosts       []string `yaml:"hosts"`
+	PathPattern string   `yaml:"path_pattern,omitempty"` // optional regex matched against the URL path
+	Wasm        string   `yaml:"wasm,omitempty"`         // module file, defaults to plugin.wasm
+
+	dir     string
+	pattern *regexp.Regexp
+}
+
+// WasmPath returns the absolute path of the plugin's module
+func (m *Manifest) WasmPath() string {
+	name := m.Wasm
+	if name == "" {
+		name = defaultWasm
+	}
+	return filepath.Join(m.dir, name)
+}
+
+// Matches reports whether the plugin handles the URL
+func (m *Manifest) Matches(u *url.URL) bool {
+	return m.AllowsHost(u.Hostname()) && (m.pattern == nil || m.pattern.MatchString(u.Path))
+}
+
+// AllowsHost reports whether host is one of the plugin's hosts or a subdomain of one
+func (m *Manifest) AllowsHost(host string) bool {
+	host = strings.ToLower(host)
+	for _, h := range m.Hosts {
+		h = strings.ToLower(h)
+		if host == h || strings.HasSuffix(host, "."+h) {
+			return true
+		}
+	}
+	return false
+}
+
+// Dir returns the plugins directory
+func Dir() (string, error) {
+	dir, err := config.ConfigDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, DirName), nil
+}
+
+// List reads all plugin manifests from the plugins directory.
+// Invalid plugins are reported in the returned error slice and skipped.
+func List() ([]*Manifest, []error) {
+	dir, err := Dir()
+	if err != nil {
+		return nil, []error{err}
+	}
+
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, []error{err}
+	}
+
+	var manifests []*Manifest
+	var errs []error
+	for _, entry := range entries {
+		if !entry.IsDir() {
+			continue
+		}
+		m, err := loadManifest(filepath.Join(dir, entry.Name()))
+		if err != nil {
+			errs = append(errs, fmt.Errorf("plugin %s: %w", entry.Name(), err))
+			continue
+		}
+		manifests = append(manifests, m)
+	}
+	return manifests, errs
+}
+
+func loadManifest(dir string) (*Manifest, error) {
+	data, err := os.ReadFile(filepath.Join(dir, manifestFile))
+	if err != nil {
+		return nil, err
+	}
+
+	var m Manifest
+	if err := yaml.Unmarshal(data, &m); err != nil {
+		return nil, fmt.Errorf("invalid %s: %w", manifestFile, err)
+	}
+	if m.Name == "" {
+		m.Name = filepath.Base(dir)
+	}
+	if len(m.Hosts) == 0 {
+		return nil, fmt.Errorf("no hosts in %s", manifestFile)
+	}
+	if m.PathPattern != "" {
+		m.pattern, err = regexp.Compile(m.PathPattern)
+		if err != nil {
+			return nil, fmt.Errorf("invalid path_pattern: %w", err)
+		}
+	}
+	m.dir = dir
+
+	if _, err := os.Stat(m.WasmPath()); err != nil {
+		return nil, fmt.Errorf("module not found: %s", m.WasmPath())
+	}
+	return &m, nil
+}
+
+// Match returns an extractor for the first plugin that handles rawURL, or nil.
+// Plugins are read fresh from disk on every call.
+func Match(rawURL string) *Extractor {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return nil
+	}
+
+	manifests, _ := List()
+	for _, m := range manifests {
+		if m.Matches(u) {
+			return &Extractor{manifest: m}
+		}
+	}
+	return nil
+}
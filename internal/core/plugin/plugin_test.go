@@ -0,0 +1,26 @@
+package plugin
+
+import "testing"
+
+func TestManifestAllowsHost(t *testing.T) {
+	m := &Manifest{Name: "example", Hosts: []string{"example.com", "Video.Example.org"}}
+	tests := []struct {
+		host string
+		want bool
+	}{
+		{"example.com", true},
+		{"EXAMPLE.com", true},
+		{"cdn.example.com", true},
+		{"video.example.org", true},
+		{"a.video.example.org", true},
+		{"example.org", false},
+		{"notexample.com", false},
+		{"example.com.evil.net", false},
+		{"", false},
+	}
+	for _, tt := range tests {
+		if got := m.AllowsHost(tt.host); got != tt.want {
+			t.Errorf("AllowsHost(%q) = %v, want %v", tt.host, got, tt.want)
+		}
+	}
+}
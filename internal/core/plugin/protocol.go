@@ -0,0 +1,201 @@
+package plugin
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net"
+	"net/url"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/network"
+)
+
+// The plugin is started with args ["<name>", "extract", "<url>"] and exchanges
+// newline-delimited JSON with the host.
+//
+// Plugin to host (stdout):
+//
+//	{"op":"fetch","url":"https://...","method":"GET","headers":{"Referer":"..."},"body":""}
+//	{"op":"log","message":"..."}
+//	{"op":"result","media":{...}}
+//	{"op":"error","message":"..."}
+//
+// Host to plugin (stdin), one line per fetch:
+//
+//	{"status":200,"url":"<final url>","headers":{"Content-Type":"..."},"body":"...","error":""}
+
+// request is a message written by the plugin
+type request struct {
+	Op      string            `json:"op"`
+	URL     string            `json:"url,omitempty"`
+	Method  string            `json:"method,omitempty"`
+	Headers map[string]string `json:"headers,omitempty"`
+	Body    string            `json:"body,omitempty"`
+	Message string            `json:"message,omitempty"`
+	Media   *mediaResult      `json:"media,omitempty"`
+}
+
+// fetchResponse is the host's reply to a fetch request
+type fetchResponse struct {
+	Status  int               `json:"status"`
+	URL     string            `json:"url,omitempty"`
+	Headers map[string]string `json:"headers,omitempty"`
+	Body    string            `json:"body"`
+	Error   string            `json:"error,omitempty"`
+}
+
+// mediaResult is the media returned by a plugin
+type mediaResult struct {
+	Type      string         `json:"type"` // video, audio, image
+	ID        string         `json:"id"`
+	Title     string         `json:"title"`
+	Uploader  string         `json:"uploader,omitempty"`
+	Duration  int            `json:"duration,omitempty"`
+	Thumbnail string         `json:"thumbnail,omitempty"`
+	Formats   []formatResult `json:"formats,omitempty"` // video
+	URL       string         `json:"url,omitempty"`     // audio
+	Ext       string         `json:"ext,omitempty"`     // audio
+	Images    []imageResult  `json:"images,omitempty"`  // image
+}
+
+type formatResult struct {
+	URL      string            `json:"url"`
+	Quality  string            `json:"quality,omitempty"`
+	Ext      string            `json:"ext"`
+	Width    int               `json:"width,omitempty"`
+	Height   int               `json:"height,omitempty"`
+	Bitrate  int               `json:"bitrate,omitempty"`
+	Headers  map[string]string `json:"headers,omitempty"`
+	AudioURL string            `json:"audio_url,omitempty"`
+}
+
+type imageResult struct {
+	URL    string `json:"url"`
+	Ext    string `json:"ext"`
+	Width  int    `json:"width,omitempty"`
+	Height int    `json:"height,omitempty"`
+}
+
+// lookupIPAddr resolves the hosts of media URLs, replaced in tests
+var lookupIPAddr = net.DefaultResolver.LookupIPAddr
+
+// checkMediaURL applies the fetch policy to a URL returned for downloading:
+// http or https, and not at a local address. Any host is allowed, media is
+// often served from a CDN the manifest doesn't list.
+func checkMediaURL(ctx context.Context, rawURL string) error {
+	u, err := url.Parse(rawURL)
+	if err != nil {
+		return err
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return fmt.Errorf("only http and https URLs are allowed: %s", rawURL)
+	}
+	host := u.Hostname()
+	if host == "" {
+		return errors.New("url has no host")
+	}
+	if err := checkPublicHost(host); err != nil {
+		return err
+	}
+	if net.ParseIP(host) != nil {
+		return nil
+	}
+	addrs, err := lookupIPAddr(ctx, host)
+	if err != nil {
+		return err
+	}
+	for _, addr := range addrs {
+		if network.LocalIP(addr.IP) {
+			return fmt.Errorf("host %s resolves to a local address", host)
+		}
+	}
+	return nil
+}
+
+// checkMediaURLs checks every URL in the result that gets downloaded
+func (r *mediaResult) checkMediaURLs(ctx context.Context) error {
+	urls := []string{r.URL, r.Thumbnail}
+	for _, f := range r.Formats {
+		urls = append(urls, f.URL, f.AudioURL)
+	}
+	for _, img := range r.Images {
+		urls = append(urls, img.URL)
+	}
+	for _, u := range urls {
+		if u == "" {
+			continue
+		}
+		if err := checkMediaURL(ctx, u); err != nil {
+			return fmt.Errorf("plugin returned a URL that can't be downloaded: %w", err)
+		}
+	}
+	return nil
+}
+
+// toMedia converts a plugin result into extractor media, rejecting URLs that
+// point at the local machine or network
+func (r *mediaResult) toMedia(ctx context.Context) (extractor.Media, error) {
+	if err := r.checkMediaURLs(ctx); err != nil {
+		return nil, err
+	}
+
+	switch r.Type {
+	case "video":
+		if len(r.Formats) == 0 {
+			return nil, fmt.Errorf("plugin returned a video with no formats")
+		}
+		v := &extractor.VideoMedia{
+			ID:        r.ID,
+			Title:     r.Title,
+			Uploader:  r.Uploader,
+			Duration:  r.Duration,
+			Thumbnail: r.Thumbnail,
+		}
+		for _, f := range r.Formats {
+			v.Formats = append(v.Formats, extractor.VideoFormat{
+				URL:      f.URL,
+				Quality:  f.Quality,
+				Ext:      f.Ext,
+				Width:    f.Width,
+				Height:   f.Height,
+				Bitrate:  f.Bitrate,
+				Headers:  f.Headers,
+				AudioURL: f.AudioURL,
+			})
+		}
+		return v, nil
+	case "audio":
+		if r.URL == "" {
+			return nil, fmt.Errorf("plugin returned audio with no url")
+		}
+		return &extractor.AudioMedia{
+			ID:       r.ID,
+			Title:    r.Title,
+			Uploader: r.Uploader,
+			Duration: r.Duration,
+			URL:      r.URL,
+			Ext:      r.Ext,
+		}, nil
+	case "image":
+		if len(r.Images) == 0 {
+			return nil, fmt.Errorf("plugin returned no images")
+		}
+		m := &extractor.ImageMedia{
+			ID:       r.ID,
+			Title:    r.Title,
+			Uploader: r.Uploader,
+		}
+		for _, img := range r.Images {
+			m.Images = append(m.Images, extractor.Image{
+				URL:    img.URL,
+				Ext:    img.Ext,
+				Width:  img.Width,
+				Height: img.Height,
+			})
+		}
+		return m, nil
+	default:
+		return nil, fmt.Errorf("plugin returned unknown media type: %q", r.Type)
+	}
+}
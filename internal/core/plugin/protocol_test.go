@@ -0,0 +1,49 @@
+package plugin
+
+import (
+	"context"
+	"net"
+	"testing"
+)
+
+func TestToMediaRejectsLocalURLs(t *testing.T) {
+	// Names resolve without the network: internal.test to a private address
+	lookupIPAddr = func(_ context.Context, host string) ([]net.IPAddr, error) {
+		if host == "internal.test" {
+			return []net.IPAddr{{IP: net.ParseIP("192.168.0.10")}}, nil
+		}
+		return []net.IPAddr{{IP: net.ParseIP("93.184.216.34")}}, nil
+	}
+	t.Cleanup(func() { lookupIPAddr = net.DefaultResolver.LookupIPAddr })
+
+	video := func(formatURL, audioURL, thumbnail string) *mediaResult {
+		return &mediaResult{
+			Type:      "video",
+			Thumbnail: thumbnail,
+			Formats:   []formatResult{{URL: formatURL, Ext: "mp4", AudioURL: audioURL}},
+		}
+	}
+	tests := []struct {
+		name   string
+		result *mediaResult
+		ok     bool
+	}{
+		{"public video", video("https://cdn.example.net/v.mp4", "https://cdn.example.net/a.m4a", "https://cdn.example.net/t.jpg"), true},
+		{"public audio", &mediaResult{Type: "audio", URL: "http://93.184.216.34/a.mp3"}, true},
+		{"loopback format", video("http://127.0.0.1:8080/admin", "", ""), false},
+		{"localhost format", video("http://localhost/v.mp4", "", ""), false},
+		{"private audio stream", video("https://cdn.example.net/v.mp4", "http://10.0.0.1/a.m4a", ""), false},
+		{"link-local thumbnail", video("https://cdn.example.net/v.mp4", "", "http://169.254.169.254/latest/meta-data/"), false},
+		{"resolves to private", video("https://internal.test/v.mp4", "", ""), false},
+		{"file scheme", video("file:///etc/passwd", "", ""), false},
+		{"no host", video("http:///v.mp4", "", ""), false},
+		{"private audio", &mediaResult{Type: "audio", URL: "http://192.168.1.1/a.mp3"}, false},
+		{"private image", &mediaResult{Type: "image", Images: []imageResult{{URL: "https://cdn.example.net/1.jpg"}, {URL: "http://[::1]/2.jpg"}}}, false},
+	}
+	for _, tt := range tests {
+		_, err := tt.result.toMedia(context.Background())
+		if (err == nil) != tt.ok {
+			t.Errorf("%s: toMedia() = %v, want ok %v", tt.name, err, tt.ok)
+		}
+	}
+}
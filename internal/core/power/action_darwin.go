@@ -0,0 +1,15 @@
+//go:build darwin
+
+package power
+
+import "os/exec"
+
+// Sleep puts the computer to sleep
+func Sleep() error {
+	return exec.Command("pmset", "sleepnow").Run()
+}
+
+// Shutdown turns the computer off, asking running apps to quit
+func Shutdown() error {
+	return exec.Command("osascript", "-e", `tell application "System Events" to shut down`).Run()
+}
@@ -0,0 +1,15 @@
+//go:build linux
+
+package power
+
+import "os/exec"
+
+// Sleep suspends the computer
+func Sleep() error {
+	return exec.Command("systemctl", "suspend").Run()
+}
+
+// Shutdown turns the computer off
+func Shutdown() error {
+	return exec.Command("systemctl", "poweroff").Run()
+}
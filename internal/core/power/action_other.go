@@ -0,0 +1,15 @@
+//go:build !linux && !darwin && !windows
+
+package power
+
+import "errors"
+
+// Sleep isn't implemented on this platform
+func Sleep() error {
+	return errors.New("sleep is not supported on this platform")
+}
+
+// Shutdown isn't implemented on this platform
+func Shutdown() error {
+	return errors.New("shutdown is not supported on this platform")
+}
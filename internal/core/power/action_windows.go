@@ -0,0 +1,15 @@
+//go:build windows
+
+package power
+
+import "os/exec"
+
+// Sleep suspends the computer (or hibernates, if hibernation is enabled)
+func Sleep() error {
+	return exec.Command("rundll32.exe", "powrprof.dll,SetSuspendState", "0,1,0").Run()
+}
+
+// Shutdown turns the computer off
+func Shutdown() error {
+	return exec.Command("shutdown", "/s", "/t", "0").Run()
+}
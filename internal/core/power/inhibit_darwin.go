@@ -0,0 +1,21 @@
+//go:build darwin
+
+package power
+
+import (
+	"os"
+	"os/exec"
+	"strconv"
+)
+
+// inhibit runs caffeinate until released. -w ends it with vget if vget is killed.
+func inhibit(reason string) (func(), error) {
+	cmd := exec.Command("caffeinate", "-i", "-w", strconv.Itoa(os.Getpid()))
+	if err := cmd.Start(); err != nil {
+		return nil, err
+	}
+	return func() {
+		cmd.Process.Kill()
+		cmd.Wait()
+	}, nil
+}
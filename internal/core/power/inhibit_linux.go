@@ -0,0 +1,23 @@
+//go:build linux
+
+package power
+
+import (
+	"os"
+	"os/exec"
+	"strconv"
+)
+
+// inhibit holds a systemd-inhibit lock until released. The lock's command
+// waits on vget's PID, so it ends with vget if vget is killed.
+func inhibit(reason string) (func(), error) {
+	cmd := exec.Command("systemd-inhibit", "--what=sleep:idle", "--who=vget", "--why="+reason, "--mode=block",
+		"tail", "--pid="+strconv.Itoa(os.Getpid()), "-f", "/dev/null")
+	if err := cmd.Start(); err != nil {
+		return nil, err
+	}
+	return func() {
+		cmd.Process.Kill()
+		cmd.Wait()
+	}, nil
+}
@@ -0,0 +1,10 @@
+//go:build !linux && !darwin && !windows
+
+package power
+
+import "errors"
+
+// inhibit isn't implemented on this platform
+func inhibit(reason string) (func(), error) {
+	return nil, errors.New("preventing sleep is not supported on this platform")
+}
@@ -0,0 +1,38 @@
+//go:build windows
+
+package power
+
+import (
+	"runtime"
+	"syscall"
+)
+
+var procSetThreadExecutionState = syscall.NewLazyDLL("kernel32.dll").NewProc("SetThreadExecutionState")
+
+const (
+	esContinuous     = 0x80000000
+	esSystemRequired = 0x00000001
+)
+
+// inhibit sets ES_SYSTEM_REQUIRED until released. The state belongs to the
+// calling thread, so one locked goroutine sets and clears it.
+func inhibit(reason string) (func(), error) {
+	done := make(chan struct{})
+	result := make(chan error, 1)
+	go func() {
+		runtime.LockOSThread()
+		defer runtime.UnlockOSThread()
+
+		if ok, _, err := procSetThreadExecutionState.Call(esContinuous | esSystemRequired); ok == 0 {
+			result <- err
+			return
+		}
+		result <- nil
+		<-done
+		procSetThreadExecutionState.Call(esContinuous)
+	}()
+	if err := <-result; err != nil {
+		return nil, err
+	}
+	return func() { close(done) }, nil
+}
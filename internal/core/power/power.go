@@ -0,0 +1,53 @@
+// Package power keeps the computer from going to sleep while downloads are
+// running, using caffeinate on macOS, systemd-inhibit on Linux, and
+// SetThreadExecutionState on Windows.
+package power
+
+import "sync"
+
+// Inhibitor holds a sleep assertion while at least one holder wants it
+type Inhibitor struct {
+	reason  string
+	mu      sync.Mutex
+	holders int
+	release func()
+	failed  bool
+}
+
+// NewInhibitor returns an inhibitor that gives reason to the OS
+func NewInhibitor(reason string) *Inhibitor {
+	return &Inhibitor{reason: reason}
+}
+
+// Acquire adds a holder, taking the assertion for the first one. Only the
+// first failure is returned; the assertion isn't attempted again after it.
+func (in *Inhibitor) Acquire() error {
+	in.mu.Lock()
+	defer in.mu.Unlock()
+
+	in.holders++
+	if in.release != nil || in.failed {
+		return nil
+	}
+	release, err := inhibit(in.reason)
+	if err != nil {
+		in.failed = true
+		return err
+	}
+	in.release = release
+	return nil
+}
+
+// Release removes a holder, letting the system sleep after the last one
+func (in *Inhibitor) Release() {
+	in.mu.Lock()
+	defer in.mu.Unlock()
+
+	if in.holders > 0 {
+		in.holders--
+	}
+	if in.holders == 0 && in.release != nil {
+		in.release()
+		in.release = nil
+	}
+}
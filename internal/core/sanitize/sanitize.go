@@ -0,0 +1,153 @@
+// Package sanitize makes titles and output paths safe to use as file names on
+// every platform vget runs on.
+package sanitize
+
+import (
+	"os"
+	"path/filepath"
+	"regexp"
+	"runtime"
+	"slices"
+	"strings"
+	"unicode/utf8"
+)
+
+const (
+	// maxNameBytes is the longest file name most filesystems accept
+	maxNameBytes = 255
+
+	// maxWindowsPath is MAX_PATH without the terminating NUL
+	maxWindowsPath = 259
+
+	// maxUnixPath is PATH_MAX on Linux without the terminating NUL
+	maxUnixPath = 4095
+)
+
+var (
+	urlRegex   = regexp.MustCompile(`https?://[^\s]+`)
+	spaceRegex = regexp.MustCompile(`\s+`)
+
+	// reservedNames can't be used as file names on Windows, with or without an extension
+	reservedNames = []string{
+		"CON", "PRN", "AUX", "NUL",
+		"COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+		"LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+	}
+)
+
+// Filename turns a title into a name that is valid on Windows, macOS and
+// Linux: reserved characters are removed or replaced, the result is
+// shortened, and trailing dots and spaces are trimmed
+func Filename(name string) string {
+	// Remove URLs first (before character replacement mangles them)
+	result := urlRegex.ReplaceAllString(name, "")
+
+	// Replace characters that are problematic in filenames
+	// Includes both ASCII and full-width (CJK) versions of reserved characters
+	replacer := strings.NewReplacer(
+		// ASCII versions
+		"/", "-",
+		"\\", "-",
+		":", "-",
+		"*", "",
+		"?", "",
+		"\"", "",
+		"<", "",
+		">", "",
+		"|", "",
+		"\n", " ",
+		"\r", "",
+		"\t", " ",
+		// Full-width versions (common in Chinese/Japanese text)
+		"：", "-", // U+FF1A Full-width colon
+		"／", "-", // U+FF0F Full-width solidus
+		"＼", "-", // U+FF3C Full-width reverse solidus
+		"。", "-", // U+3002 CJK full stop
+		"＊", "",  // U+FF0A Full-width asterisk
+		"？", "",  // U+FF1F Full-width question mark
+		"＂", "",  // U+FF02 Full-width quotation mark
+		"＜", "",  // U+FF1C Full-width less-than
+		"＞", "",  // U+FF1E Full-width greater-than
+		"｜", "",  // U+FF5C Full-width vertical line
+		// Additional problematic characters
+		"「", "",  // CJK left corner bracket
+		"」", "",  // CJK right corner bracket
+		"【", "",  // CJK left black lenticular bracket
+		"】", "",  // CJK right black lenticular bracket
+	)
+	result = replacer.Replace(result)
+
+	// Remove control characters (0x00-0x1F, 0x7F) which are invalid on Windows
+	result = strings.Map(func(r rune) rune {
+		if r < 32 || r == 127 {
+			return -1 // -1 means delete the rune
+		}
+		return r
+	}, result)
+
+	// Trim spaces and dots from ends
+	result = strings.TrimSpace(result)
+	result = strings.Trim(result, ".")
+
+	// Collapse multiple spaces
+	result = spaceRegex.ReplaceAllString(result, " ")
+
+	// Limit length to avoid "file name too long" errors
+	// Most filesystems limit filenames to 255 bytes. For UTF-8 with CJK characters
+	// (3-4 bytes each), 60 runes is safe (~180-240 bytes), leaving room for extension.
+	const maxRunes = 60
+	runes := []rune(result)
+	if len(runes) > maxRunes {
+		result = string(runes[:maxRunes])
+	}
+
+	// Truncation can leave a trailing space or dot, which Windows drops
+	// silently, so the name on disk would differ from the one we return
+	result = strings.TrimRight(result, " .")
+
+	// Handle Windows reserved names (CON, PRN, AUX, NUL, COM1-9, LPT1-9)
+	// These names (with or without extension) cannot be used as filenames on Windows
+	if slices.Contains(reservedNames, strings.ToUpper(result)) {
+		result = "_" + result
+	}
+
+	return result
+}
+
+// Path shortens the file name at the end of path, keeping its extension, so
+// that the name fits in 255 bytes and the full path fits the platform's path
+// limit. Directories are left as they are.
+func Path(path string) string {
+	dir, base := filepath.Split(path)
+	ext := filepath.Ext(base)
+	stem := strings.TrimSuffix(base, ext)
+
+	// Measure against the absolute path, since that is what the limit applies to
+	absDir := dir
+	if abs, err := filepath.Abs(dir); err == nil {
+		absDir = abs + string(os.PathSeparator)
+	}
+	limit := min(maxNameBytes, maxPathLength()-len(absDir)) - len(ext)
+	if len(stem) <= limit || limit <= 0 {
+		return path
+	}
+	return dir + strings.TrimRight(truncateBytes(stem, limit), " .") + ext
+}
+
+func maxPathLength() int {
+	if runtime.GOOS == "windows" {
+		return maxWindowsPath
+	}
+	return maxUnixPath
+}
+
+// truncateBytes cuts s to at most n bytes without splitting a UTF-8 sequence
+func truncateBytes(s string, n int) string {
+	if len(s) <= n {
+		return s
+	}
+	for n > 0 && !utf8.RuneStart(s[n]) {
+		n--
+	}
+	return s[:n]
+}
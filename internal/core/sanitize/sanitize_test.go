@@ -1,8 +1,13 @@
-package extractor
+package sanitize
 
-import "testing"
+import (
+	"path/filepath"
+	"strings"
+	"testing"
+	"unicode/utf8"
+)
 
-func TestSanitizeFilename(t *testing.T) {
+func TestFilename(t *testing.T) {
 	tests := []struct {
 		name     string
 		input    string
@@ -92,10 +97,26 @@ func TestSanitizeFilename(t *testing.T) {
 
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
-			result := SanitizeFilename(tt.input)
+			result := Filename(tt.input)
 			if result != tt.expected {
-				t.Errorf("SanitizeFilename(%q)\n  got:  %q\n  want: %q", tt.input, result, tt.expected)
+				t.Errorf("Filename(%q)\n  got:  %q\n  want: %q", tt.input, result, tt.expected)
 			}
 		})
 	}
 }
+
+func TestPath(t *testing.T) {
+	long := strings.Repeat("标题", 100) // 600 bytes
+	got := Path(filepath.Join("videos", long+".mp4"))
+	if filepath.Dir(got) != "videos" || filepath.Ext(got) != ".mp4" {
+		t.Fatalf("Path changed the directory or extension: %q", got)
+	}
+	if name := filepath.Base(got); len(name) > maxNameBytes || !utf8.ValidString(name) {
+		t.Errorf("Path left a %d-byte name %q", len(name), name)
+	}
+
+	short := filepath.Join("videos", "clip.mp4")
+	if got := Path(short); got != short {
+		t.Errorf("Path(%q) = %q, want it unchanged", short, got)
+	}
+}
@@ -0,0 +1,84 @@
+package scheduler
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+)
+
+// OnBattery reports whether the machine is currently running on battery power.
+// Returns false on desktops, in Docker, and when the state cannot be determined.
+func OnBattery() bool {
+	switch runtime.GOOS {
+	case "linux":
+		// Any battery reporting "Discharging" means we're off mains power
+		supplies, _ := filepath.Glob("/sys/class/power_supply/*")
+		for _, dir := range supplies {
+			kind, err := os.ReadFile(filepath.Join(dir, "type"))
+			if err != nil || strings.TrimSpace(string(kind)) != "Battery" {
+				continue
+			}
+			status, err := os.ReadFile(filepath.Join(dir, "status"))
+			if err == nil && strings.TrimSpace(string(status)) == "Discharging" {
+				return true
+			}
+		}
+		return false
+
+	case "darwin":
+		// First line looks like: Now drawing from 'Battery Power'
+		out, err := exec.Command("pmset", "-g", "batt").Output()
+		if err != nil {
+			return false
+		}
+		return strings.Contains(string(out), "'Battery Power'")
+
+	case "windows":
+		// BatteryStatus 1 = discharging
+		out, err := exec.Command("powershell", "-NoProfile", "-Command",
+			"(Get-CimInstance -ClassName Win32_Battery).BatteryStatus").Output()
+		if err != nil {
+			return false
+		}
+		return strings.TrimSpace(string(out)) == "1"
+	}
+
+	return false
+}
+
+// IsMetered reports whether the active network connection is marked as metered
+// (mobile hotspot, tethering, data-capped Wi-Fi).
+// Returns false when the state cannot be determined.
+func IsMetered() bool {
+	switch runtime.GOOS {
+	case "linux":
+		// NetworkManager reports per-device: GENERAL.METERED:yes (guessed)
+		out, err := exec.Command("nmcli", "-t", "-f", "GENERAL.METERED", "device", "show").Output()
+		if err != nil {
+			return false
+		}
+		for _, line := range strings.Split(string(out), "\n") {
+			value := strings.TrimPrefix(strings.TrimSpace(line), "GENERAL.METERED:")
+			if strings.HasPrefix(value, "yes") {
+				return true
+			}
+		}
+		return false
+
+	case "windows":
+		// NetworkCostType is Unrestricted for normal connections, Fixed/Variable for metered
+		script := "[Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime] | Out-Null; " +
+			"[Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile().GetConnectionCost().NetworkCostType"
+		out, err := exec.Command("powershell", "-NoProfile", "-Command", script).Output()
+		if err != nil {
+			return false
+		}
+		cost := strings.TrimSpace(string(out))
+		return cost == "Fixed" || cost == "Variable"
+	}
+
+	// macOS has no public CLI for Low Data Mode
+	return false
+}
@@ -0,0 +1,73 @@
+// Package scheduler evaluates the schedule policies that decide whether
+// queued downloads are allowed to start right now.
+package scheduler
+
+import (
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// Pause reasons reported by Check
+const (
+	ReasonMetered       = "metered_connection"
+	ReasonBattery       = "on_battery"
+	ReasonOutsideWindow = "outside_time_window"
+)
+
+// Check evaluates the schedule policy at the given time.
+// Returns an empty string if downloads may start, or the reason the queue should stay paused.
+// Detection failures (e.g., no battery, unsupported platform) never block downloads.
+func Check(cfg config.ScheduleConfig, now time.Time) string {
+	if cfg.WindowStart != "" || cfg.WindowEnd != "" {
+		inside, err := InWindow(now, cfg.WindowStart, cfg.WindowEnd)
+		if err == nil && !inside {
+			return ReasonOutsideWindow
+		}
+	}
+
+	if cfg.PauseOnBattery && OnBattery() {
+		return ReasonBattery
+	}
+
+	if cfg.PauseOnMetered && IsMetered() {
+		return ReasonMetered
+	}
+
+	return ""
+}
+
+// InWindow reports whether now falls inside the daily window [start, end).
+// Both bounds are "HH:MM" in local time. A window whose end is before its start
+// wraps past midnight. An empty start means midnight, an empty end means end of day.
+func InWindow(now time.Time, start, end string) (bool, error) {
+	startMin := 0
+	endMin := 24 * 60
+
+	if start != "" {
+		m, err := config.ParseClock(start)
+		if err != nil {
+			return false, err
+		}
+		startMin = m
+	}
+	if end != "" {
+		m, err := config.ParseClock(end)
+		if err != nil {
+			return false, err
+		}
+		endMin = m
+	}
+
+	current := now.Hour()*60 + now.Minute()
+
+	if startMin == endMin {
+		// Same start and end means the whole day
+		return true, nil
+	}
+	if startMin < endMin {
+		return current >= startMin && current < endMin, nil
+	}
+	// Wraps past midnight (e.g., 23:00 - 07:00)
+	return current >= startMin || current < endMin, nil
+}
@@ -0,0 +1,43 @@
+package scheduler
+
+import (
+	"testing"
+	"time"
+)
+
+func TestInWindow(t *testing.T) {
+	at := func(hour, minute int) time.Time {
+		return time.Date(2025, 1, 1, hour, minute, 0, 0, time.Local)
+	}
+
+	tests := []struct {
+		name     string
+		now      time.Time
+		start    string
+		end      string
+		expected bool
+	}{
+		{"No window", at(12, 0), "", "", true},
+		{"Inside daytime window", at(10, 30), "09:00", "18:00", true},
+		{"Before daytime window", at(8, 59), "09:00", "18:00", false},
+		{"End is exclusive", at(18, 0), "09:00", "18:00", false},
+		{"Overnight window, late evening", at(23, 30), "23:00", "07:00", true},
+		{"Overnight window, early morning", at(6, 59), "23:00", "07:00", true},
+		{"Overnight window, afternoon", at(15, 0), "23:00", "07:00", false},
+		{"Start only", at(1, 0), "02:00", "", false},
+		{"End only", at(1, 0), "", "02:00", true},
+		{"Same start and end", at(5, 0), "08:00", "08:00", true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := InWindow(tt.now, tt.start, tt.end)
+			if err != nil {
+				t.Fatalf("InWindow returned error: %v", err)
+			}
+			if got != tt.expected {
+				t.Errorf("InWindow(%s, %q, %q) = %v; want %v", tt.now.Format("15:04"), tt.start, tt.end, got, tt.expected)
+			}
+		})
+	}
+}
@@ -2,6 +2,7 @@ package bilibili
 
 import (
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
 	"net/http"
@@ -9,7 +10,7 @@ import (
 	"strings"
 	"time"
 
-	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/auth"
 )
 
 // Auth handles Bilibili authentication via QR code or cookie
@@ -33,6 +34,9 @@ const (
 	QRConfirmed QRStatus = 0     // Login successful
 )
 
+// ErrNotLoggedIn is returned by Nav when Bilibili rejects the login
+var ErrNotLoggedIn = errors.New("credentials are invalid or expired")
+
 // Credentials stores the login credentials
 type Credentials struct {
 	SESSDATA   string
@@ -167,11 +171,9 @@ func (a *Auth) parseCredentialsFromURL(urlStr string) (*Credentials, error) {
 	return creds, nil
 }
 
-// SaveCredentials saves credentials to config file
+// SaveCredentials saves credentials to the auth store
 func (a *Auth) SaveCredentials(creds *Credentials) error {
-	cfg := config.LoadOrDefault()
-	cfg.Bilibili.Cookie = creds.ToCookieString()
-	return config.Save(cfg)
+	return auth.SetCookie(auth.Bilibili, creds.ToCookieString())
 }
 
 // ToCookieString converts credentials to cookie format
@@ -180,14 +182,14 @@ func (c *Credentials) ToCookieString() string {
 		c.SESSDATA, c.BiliJCT, c.DedeUserID)
 }
 
-// LoadCredentials loads saved credentials from config
+// LoadCredentials loads saved credentials from the auth store
 func (a *Auth) LoadCredentials() *Credentials {
-	cfg := config.LoadOrDefault()
-	if cfg.Bilibili.Cookie == "" {
+	login, _ := auth.Get(auth.Bilibili)
+	if !login.LoggedIn() {
 		return nil
 	}
 
-	return ParseCookieString(cfg.Bilibili.Cookie)
+	return ParseCookieString(login.CookieHeader())
 }
 
 // ParseCookieString parses a cookie string into credentials
@@ -210,24 +212,41 @@ func ParseCookieString(cookie string) *Credentials {
 
 // ValidateCredentials checks if credentials are valid by calling user info API
 func (a *Auth) ValidateCredentials(creds *Credentials) (string, error) {
+	user, _, err := a.Nav(creds.ToCookieString())
+	if err != nil {
+		return "", err
+	}
+	return user.Name, nil
+}
+
+// UserInfo is the account a Bilibili login belongs to
+type UserInfo struct {
+	Mid    int64  `json:"mid"`
+	Name   string `json:"username"`
+	Avatar string `json:"avatar"` // URL of the avatar image
+}
+
+// Nav fetches the account a Cookie header is logged in as from the nav API,
+// along with the cookies Bilibili renewed in its response, if any
+func (a *Auth) Nav(cookie string) (*UserInfo, []auth.Cookie, error) {
 	api := "https://api.bilibili.com/x/web-interface/nav"
 
 	req, err := http.NewRequest("GET", api, nil)
 	if err != nil {
-		return "", err
+		return nil, nil, err
 	}
 	a.setHeaders(req)
-	req.Header.Set("Cookie", creds.ToCookieString())
+	req.Header.Set("Cookie", cookie)
 
 	resp, err := a.client.Do(req)
 	if err != nil {
-		return "", err
+		return nil, nil, err
 	}
 	defer resp.Body.Close()
 
 	body, err := io.ReadAll(resp.Body)
 	if err != nil {
-		return "", err
+		return nil, nil, err
 	}
 
 	var result struct {
@@ -237,22 +256,32 @@ func (a *Auth) ValidateCredentials(creds *Credentials) (string, error) {
 			IsLogin bool   `json:"isLogin"`
 			UName   string `json:"uname"`
 			Mid     int64  `json:"mid"`
+			Face    string `json:"face"`
 		} `json:"data"`
 	}
 
 	if err := json.Unmarshal(body, &result); err != nil {
-		return "", fmt.Errorf("failed to parse response: %w", err)
+		return nil, nil, fmt.Errorf("failed to parse response: %w", err)
 	}
 
-	if result.Code != 0 {
-		return "", fmt.Errorf("API error: %s (code: %d)", result.Message, result.Code)
+	// A login Bilibili rejects comes back as code -101
+	if result.Code == -101 || (result.Code == 0 && !result.Data.IsLogin) {
+		return nil, nil, ErrNotLoggedIn
 	}
 
-	if !result.Data.IsLogin {
-		return "", fmt.Errorf("credentials are invalid or expired")
+	if result.Code != 0 {
+		return nil, nil, fmt.Errorf("API error: %s (code: %d)", result.Message, result.Code)
 	}
 
-	return result.Data.UName, nil
+	var renewed []auth.Cookie
+	for _, c := range resp.Cookies() {
+		renewed = append(renewed, auth.Cookie{
+			Name: c.Name, Value: c.Value, Domain: c.Domain, Path: c.Path,
+			Expires: c.Expires, HTTPOnly: c.HttpOnly, Secure: c.Secure,
+		})
+	}
+	user := &UserInfo{Mid: result.Data.Mid, Name: result.Data.UName, Avatar: result.Data.Face}
+	return user, renewed, nil
 }
 
 // String returns a human-readable status string
@@ -0,0 +1,70 @@
+package bilibili
+
+import (
+	"bytes"
+	"encoding/base64"
+	"image"
+	"image/color"
+	"image/png"
+
+	qrcode "github.com/yeqown/go-qrcode/v2"
+)
+
+// QR code image layout: pixels per module, and the quiet zone around the
+// code in modules, which scanners need
+const (
+	qrModuleSize = 6
+	qrQuietZone  = 4
+)
+
+// pngWriter renders a QR code matrix as a black on white PNG
+type pngWriter struct {
+	buf bytes.Buffer
+}
+
+func (w *pngWriter) Write(mat qrcode.Matrix) error {
+	size := (mat.Width() + 2*qrQuietZone) * qrModuleSize
+	img := image.NewGray(image.Rect(0, 0, size, size))
+	for i := range img.Pix {
+		img.Pix[i] = 0xff
+	}
+	mat.Iterate(qrcode.IterDirection_ROW, func(x, y int, v qrcode.QRValue) {
+		if !v.IsSet() {
+			return
+		}
+		x0, y0 := (x+qrQuietZone)*qrModuleSize, (y+qrQuietZone)*qrModuleSize
+		for dy := range qrModuleSize {
+			for dx := range qrModuleSize {
+				img.SetGray(x0+dx, y0+dy, color.Gray{})
+			}
+		}
+	})
+	return png.Encode(&w.buf, img)
+}
+
+func (w *pngWriter) Close() error {
+	return nil
+}
+
+// QRCodePNG renders content as a QR code PNG
+func QRCodePNG(content string) ([]byte, error) {
+	qr, err := qrcode.NewWith(content, qrcode.WithErrorCorrectionLevel(qrcode.ErrorCorrectionLow))
+	if err != nil {
+		return nil, err
+	}
+	w := &pngWriter{}
+	if err := qr.Save(w); err != nil {
+		return nil, err
+	}
+	return w.buf.Bytes(), nil
+}
+
+// ImageDataURL renders the session's QR code as a PNG data URL, ready for
+// an <img> src
+func (s *QRSession) ImageDataURL() (string, error) {
+	data, err := QRCodePNG(s.URL)
+	if err != nil {
+		return "", err
+	}
+	return "data:image/png;base64," + base64.StdEncoding.EncodeToString(data), nil
+}
@@ -0,0 +1,36 @@
+package bilibili
+
+import (
+	"bytes"
+	"image/png"
+	"strings"
+	"testing"
+)
+
+func TestQRCodePNG(t *testing.T) {
+	data, err := QRCodePNG("https://passport.bilibili.com/h5-app/passport/login/scan?qrcode_key=abc")
+	if err != nil {
+		t.Fatalf("QRCodePNG() error = %v", err)
+	}
+	img, err := png.Decode(bytes.NewReader(data))
+	if err != nil {
+		t.Fatalf("QRCodePNG() is not a PNG: %v", err)
+	}
+	bounds := img.Bounds()
+	if bounds.Dx() != bounds.Dy() || bounds.Dx()%qrModuleSize != 0 {
+		t.Errorf("QR image is %v, want a square of whole modules", bounds)
+	}
+	// The quiet zone is white, the finder pattern in the top left corner black
+	if r, _, _, _ := img.At(0, 0).RGBA(); r != 0xffff {
+		t.Error("quiet zone is not white")
+	}
+	corner := qrQuietZone * qrModuleSize
+	if r, _, _, _ := img.At(corner, corner).RGBA(); r != 0 {
+		t.Error("finder pattern is not black")
+	}
+
+	session := &QRSession{URL: "https://example.com"}
+	if url, err := session.ImageDataURL(); err != nil || !strings.HasPrefix(url, "data:image/png;base64,") {
+		t.Errorf("ImageDataURL() = %.40q, %v", url, err)
+	}
+}
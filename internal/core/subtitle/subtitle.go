@@ -0,0 +1,213 @@
+// Package subtitle saves a video's caption tracks as sidecar files next to
+// the downloaded video, and finds them again for embedding.
+package subtitle
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/sanitize"
+)
+
+// maxSize caps subtitle downloads
+const maxSize = 10 * 1024 * 1024
+
+// All selects every available language
+const All = "all"
+
+// ParseLanguages splits a comma-separated language list such as "en,zh-Hans".
+// An empty value returns nil, meaning no subtitles are saved.
+func ParseLanguages(value string) []string {
+	var langs []string
+	for _, lang := range strings.Split(value, ",") {
+		if lang = strings.ToLower(strings.TrimSpace(lang)); lang != "" {
+			langs = append(langs, lang)
+		}
+	}
+	return langs
+}
+
+// Select picks one track per requested language, preferring uploaded tracks
+// over machine-generated ones. "en" matches "en" and regional tracks like
+// "en-US"; Bilibili's "ai-" prefix is ignored. With All, the first track of
+// every language is picked.
+func Select(subs []extractor.Subtitle, langs []string) []extractor.Subtitle {
+	var picked []extractor.Subtitle
+	seen := make(map[string]bool)
+	for _, lang := range langs {
+		if lang == All {
+			for _, sub := range subs {
+				if code := trackLanguage(sub); !seen[code] {
+					seen[code] = true
+					picked = append(picked, sub)
+				}
+			}
+			continue
+		}
+
+		var best *extractor.Subtitle
+		for i := range subs {
+			code := trackLanguage(subs[i])
+			if seen[code] || (code != lang && !strings.HasPrefix(code, lang+"-")) {
+				continue
+			}
+			if best == nil || (best.Auto && !subs[i].Auto) {
+				best = &subs[i]
+			}
+		}
+		if best != nil {
+			seen[trackLanguage(*best)] = true
+			picked = append(picked, *best)
+		}
+	}
+	return picked
+}
+
+// trackLanguage is the lowercase language code of a track without the
+// marker Bilibili puts on AI captions
+func trackLanguage(sub extractor.Subtitle) string {
+	return strings.TrimPrefix(strings.ToLower(sub.Language), "ai-")
+}
+
+// Save downloads a track next to mediaPath as "<name>.<language>.<ext>".
+// Bilibili's JSON captions are converted to SRT. Returns the saved path.
+func Save(sub extractor.Subtitle, mediaPath string) (string, error) {
+	data, err := fetch(sub.URL)
+	if err != nil {
+		return "", err
+	}
+
+	ext := strings.ToLower(sub.Ext)
+	if ext == "json" {
+		if data, err = bccToSRT(data); err != nil {
+			return "", err
+		}
+		ext = "srt"
+	}
+
+	base := strings.TrimSuffix(mediaPath, filepath.Ext(mediaPath))
+	output := fmt.Sprintf("%s.%s.%s", base, sanitize.Filename(sub.Language), ext)
+	if err := os.WriteFile(output, data, 0644); err != nil {
+		return "", fmt.Errorf("failed to save subtitle: %w", err)
+	}
+	return output, nil
+}
+
+func fetch(subURL string) ([]byte, error) {
+	req, err := http.NewRequest("GET", subURL, nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", downloader.DefaultUserAgent)
+
+	client := &http.Client{Timeout: 30 * time.Second}
+	resp, err := client.Do(req)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fetch subtitle: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("subtitle returned status %d", resp.StatusCode)
+	}
+
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxSize+1))
+	if err != nil {
+		return nil, fmt.Errorf("failed to read subtitle: %w", err)
+	}
+	if len(data) > maxSize {
+		return nil, fmt.Errorf("subtitle larger than %d MB", maxSize/1024/1024)
+	}
+	return data, nil
+}
+
+// bccToSRT converts Bilibili's BCC JSON captions to SubRip
+func bccToSRT(data []byte) ([]byte, error) {
+	var bcc struct {
+		Body []struct {
+			From    float64 `json:"from"`
+			To      float64 `json:"to"`
+			Content string  `json:"content"`
+		} `json:"body"`
+	}
+	if err := json.Unmarshal(data, &bcc); err != nil {
+		return nil, fmt.Errorf("failed to parse subtitle: %w", err)
+	}
+
+	var buf bytes.Buffer
+	for i, cue := range bcc.Body {
+		fmt.Fprintf(&buf, "%d\n%s --> %s\n%s\n\n", i+1, srtTime(cue.From), srtTime(cue.To), strings.TrimSpace(cue.Content))
+	}
+	return buf.Bytes(), nil
+}
+
+// srtTime formats seconds as HH:MM:SS,mmm
+func srtTime(seconds float64) string {
+	ms := int64(seconds*1000 + 0.5)
+	return fmt.Sprintf("%02d:%02d:%02d,%03d", ms/3600000, ms/60000%60, ms/1000%60, ms%1000)
+}
+
+// sidecarExts are the subtitle formats recognized next to a video
+var sidecarExts = map[string]bool{".srt": true, ".vtt": true, ".ass": true, ".ssa": true}
+
+// Sidecar is a subtitle file saved next to a video
+type Sidecar struct {
+	Path     string `json:"path"`
+	Language string `json:"language,omitempty"` // "" for "<name>.srt"
+}
+
+// Sidecars finds the subtitle files of mediaPath, named as Save names them
+// ("<name>.<language>.<ext>") or just "<name>.<ext>", sorted by name. With
+// langs, only those languages are returned, matched as in Select.
+func Sidecars(mediaPath string, langs []string) ([]Sidecar, error) {
+	dir := filepath.Dir(mediaPath)
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return nil, err
+	}
+	base := strings.TrimSuffix(filepath.Base(mediaPath), filepath.Ext(mediaPath))
+
+	var found []Sidecar
+	for _, entry := range entries {
+		name := entry.Name()
+		if entry.IsDir() || !sidecarExts[strings.ToLower(filepath.Ext(name))] {
+			continue
+		}
+		stem := strings.TrimSuffix(name, filepath.Ext(name))
+		var lang string
+		if stem != base {
+			var ok bool
+			lang, ok = strings.CutPrefix(stem, base+".")
+			// A dot in the language means another video, e.g.
+			// "talk.part2.en.srt" next to "talk.mp4"
+			if !ok || lang == "" || strings.Contains(lang, ".") {
+				continue
+			}
+		}
+		if len(langs) > 0 && !matchesLanguage(lang, langs) {
+			continue
+		}
+		found = append(found, Sidecar{Path: filepath.Join(dir, name), Language: lang})
+	}
+	return found, nil
+}
+
+// matchesLanguage reports whether a sidecar's language is one of langs
+func matchesLanguage(lang string, langs []string) bool {
+	code := strings.TrimPrefix(strings.ToLower(lang), "ai-")
+	for _, want := range langs {
+		if want == All || code == want || strings.HasPrefix(code, want+"-") {
+			return true
+		}
+	}
+	return false
+}
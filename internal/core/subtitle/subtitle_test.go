@@ -0,0 +1,82 @@
+package subtitle
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+func TestSelect(t *testing.T) {
+	subs := []extractor.Subtitle{
+		{Language: "en", Auto: true},
+		{Language: "en-US"},
+		{Language: "ai-zh", Auto: true},
+		{Language: "ja"},
+	}
+
+	tests := []struct {
+		name  string
+		langs []string
+		want  []string
+	}{
+		{"none", nil, nil},
+		{"uploaded over auto", []string{"en"}, []string{"en-US"}},
+		{"ai prefix ignored", []string{"zh", "fr"}, []string{"ai-zh"}},
+		{"all", []string{All}, []string{"en", "en-US", "ai-zh", "ja"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			for _, sub := range Select(subs, tt.langs) {
+				got = append(got, sub.Language)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Select(%v) = %v, want %v", tt.langs, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestBCCToSRT(t *testing.T) {
+	got, err := bccToSRT([]byte(`{"body":[{"from":0.5,"to":2,"content":"Hello"},{"from":3661.25,"to":3662,"content":" World "}]}`))
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := "1\n00:00:00,500 --> 00:00:02,000\nHello\n\n2\n01:01:01,250 --> 01:01:02,000\nWorld\n\n"
+	if string(got) != want {
+		t.Errorf("bccToSRT() =\n%q\nwant\n%q", got, want)
+	}
+}
+
+func TestSidecars(t *testing.T) {
+	dir := t.TempDir()
+	for _, name := range []string{"talk.mp4", "talk.srt", "talk.en.vtt", "talk.zh-Hans.srt", "talk.part2.en.srt", "talk.en.txt", "talks.en.srt"} {
+		if err := os.WriteFile(filepath.Join(dir, name), nil, 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	tests := []struct {
+		langs []string
+		want  []string
+	}{
+		{nil, []string{"en", "", "zh-Hans"}},
+		{[]string{"zh"}, []string{"zh-Hans"}},
+	}
+	for _, tt := range tests {
+		found, err := Sidecars(filepath.Join(dir, "talk.mp4"), tt.langs)
+		if err != nil {
+			t.Fatal(err)
+		}
+		var got []string
+		for _, sc := range found {
+			got = append(got, sc.Language)
+		}
+		if !reflect.DeepEqual(got, tt.want) {
+			t.Errorf("Sidecars(%v) = %v, want %v", tt.langs, got, tt.want)
+		}
+	}
+}
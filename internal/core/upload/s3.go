@@ -0,0 +1,155 @@
+package upload
+
+import (
+	"context"
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"io"
+	"net/http"
+	"path"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// s3MaxSinglePut is the largest object S3 accepts in a single PUT request
+const s3MaxSinglePut = 5 * 1024 * 1024 * 1024
+
+// s3Uploader uploads to S3-compatible object storage using path-style
+// requests signed with AWS Signature Version 4
+type s3Uploader struct {
+	endpoint  string
+	region    string
+	bucket    string
+	accessKey string
+	secretKey string
+	prefix    string
+}
+
+func newS3Uploader(t *config.UploadTarget) (*s3Uploader, error) {
+	if t.Endpoint == "" || t.Bucket == "" || t.AccessKey == "" || t.SecretKey == "" {
+		return nil, fmt.Errorf("s3 target requires endpoint, bucket, access_key, and secret_key")
+	}
+
+	endpoint := t.Endpoint
+	if !strings.HasPrefix(endpoint, "http://") && !strings.HasPrefix(endpoint, "https://") {
+		endpoint = "https://" + endpoint
+	}
+
+	region := t.Region
+	if region == "" {
+		region = "us-east-1"
+	}
+
+	return &s3Uploader{
+		endpoint:  strings.TrimSuffix(endpoint, "/"),
+		region:    region,
+		bucket:    t.Bucket,
+		accessKey: t.AccessKey,
+		secretKey: t.SecretKey,
+		prefix:    strings.Trim(t.Path, "/"),
+	}, nil
+}
+
+func (u *s3Uploader) Upload(ctx context.Context, localPath string, progressFn ProgressFunc) (string, error) {
+	f, reader, err := openWithProgress(localPath, progressFn)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+
+	if reader.total > s3MaxSinglePut {
+		return "", fmt.Errorf("file is larger than 5 GB, which exceeds the S3 single upload limit")
+	}
+
+	key := path.Join(u.prefix, filepath.Base(localPath))
+	objectURL := u.endpoint + "/" + s3EscapePath(u.bucket+"/"+key)
+
+	req, err := http.NewRequestWithContext(ctx, "PUT", objectURL, reader)
+	if err != nil {
+		return "", err
+	}
+	req.ContentLength = reader.total
+	u.sign(req, time.Now().UTC())
+
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return "", fmt.Errorf("s3 upload failed: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		return "", fmt.Errorf("s3 upload failed with status %d: %s", resp.StatusCode, strings.TrimSpace(string(body)))
+	}
+
+	return fmt.Sprintf("s3://%s/%s", u.bucket, key), nil
+}
+
+// sign adds AWS Signature Version 4 headers to req.
+// The payload is sent unsigned (UNSIGNED-PAYLOAD) so large files can be streamed.
+func (u *s3Uploader) sign(req *http.Request, now time.Time) {
+	amzDate := now.Format("20060102T150405Z")
+	date := now.Format("20060102")
+	const payloadHash = "UNSIGNED-PAYLOAD"
+
+	req.Header.Set("x-amz-date", amzDate)
+	req.Header.Set("x-amz-content-sha256", payloadHash)
+
+	host := req.URL.Host
+	canonicalHeaders := "host:" + host + "\n" +
+		"x-amz-content-sha256:" + payloadHash + "\n" +
+		"x-amz-date:" + amzDate + "\n"
+	signedHeaders := "host;x-amz-content-sha256;x-amz-date"
+
+	canonicalRequest := strings.Join([]string{
+		req.Method,
+		req.URL.EscapedPath(),
+		req.URL.RawQuery,
+		canonicalHeaders,
+		signedHeaders,
+		payloadHash,
+	}, "\n")
+
+	scope := date + "/" + u.region + "/s3/aws4_request"
+	stringToSign := "AWS4-HMAC-SHA256\n" + amzDate + "\n" + scope + "\n" + sha256Hex([]byte(canonicalRequest))
+
+	key := hmacSHA256([]byte("AWS4"+u.secretKey), date)
+	key = hmacSHA256(key, u.region)
+	key = hmacSHA256(key, "s3")
+	key = hmacSHA256(key, "aws4_request")
+	signature := hex.EncodeToString(hmacSHA256(key, stringToSign))
+
+	req.Header.Set("Authorization", fmt.Sprintf("AWS4-HMAC-SHA256 Credential=%s/%s, SignedHeaders=%s, Signature=%s",
+		u.accessKey, scope, signedHeaders, signature))
+}
+
+// s3EscapePath percent-encodes everything except unreserved characters and "/"
+func s3EscapePath(p string) string {
+	var b strings.Builder
+	for i := 0; i < len(p); i++ {
+		c := p[i]
+		if (c >= 'A' && c <= 'Z') || (c >= 'a' && c <= 'z') || (c >= '0' && c <= '9') ||
+			c == '-' || c == '_' || c == '.' || c == '~' || c == '/' {
+			b.WriteByte(c)
+		} else {
+			fmt.Fprintf(&b, "%%%02X", c)
+		}
+	}
+	return b.String()
+}
+
+func hmacSHA256(key []byte, data string) []byte {
+	h := hmac.New(sha256.New, key)
+	h.Write([]byte(data))
+	return h.Sum(nil)
+}
+
+func sha256Hex(data []byte) string {
+	sum := sha256.Sum256(data)
+	return hex.EncodeToString(sum[:])
+}
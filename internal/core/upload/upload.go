@@ -0,0 +1,166 @@
+// Package upload sends completed downloads to cloud storage targets
+// (S3-compatible object storage, WebDAV servers, SMB shares).
+package upload
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// ProgressFunc reports upload progress in bytes
+type ProgressFunc func(sent, total int64)
+
+// Uploader uploads a local file to a remote destination
+type Uploader interface {
+	// Upload sends localPath to the target and returns the remote location
+	Upload(ctx context.Context, localPath string, progressFn ProgressFunc) (string, error)
+}
+
+// New creates an uploader for the named target in cfg
+func New(cfg *config.Config, name string) (Uploader, error) {
+	target := cfg.GetUploadTarget(name)
+	if target == nil {
+		return nil, fmt.Errorf("upload target '%s' not found", name)
+	}
+
+	switch target.Type {
+	case "s3":
+		return newS3Uploader(target)
+	case "webdav":
+		server := cfg.GetWebDAVServer(target.Remote)
+		if server == nil {
+			return nil, fmt.Errorf("upload target '%s': WebDAV server '%s' not found", name, target.Remote)
+		}
+		return newWebDAVUploader(server, target.Path)
+	case "smb":
+		if target.Path == "" {
+			return nil, fmt.Errorf("upload target '%s': path is required for smb", name)
+		}
+		return &smbUploader{dir: target.Path}, nil
+	default:
+		return nil, fmt.Errorf("upload target '%s': unknown type '%s' (use s3, webdav, or smb)", name, target.Type)
+	}
+}
+
+// File uploads localPath to the named target, removing the local copy afterwards
+// if deleteLocal is set. Returns the remote location.
+func File(ctx context.Context, cfg *config.Config, name, localPath string, deleteLocal bool, progressFn ProgressFunc) (string, error) {
+	uploader, err := New(cfg, name)
+	if err != nil {
+		return "", err
+	}
+
+	remote, err := uploader.Upload(ctx, localPath, progressFn)
+	if err != nil {
+		return "", err
+	}
+
+	if deleteLocal {
+		if err := os.Remove(localPath); err != nil {
+			return remote, fmt.Errorf("uploaded to %s but failed to remove local file: %w", remote, err)
+		}
+	}
+	return remote, nil
+}
+
+// progressReader counts bytes read and reports them to a ProgressFunc
+type progressReader struct {
+	r          io.Reader
+	sent       int64
+	total      int64
+	progressFn ProgressFunc
+}
+
+func (p *progressReader) Read(b []byte) (int, error) {
+	n, err := p.r.Read(b)
+	if n > 0 {
+		p.sent += int64(n)
+		if p.progressFn != nil {
+			p.progressFn(p.sent, p.total)
+		}
+	}
+	return n, err
+}
+
+// openWithProgress opens a local file and wraps it in a progress-reporting reader
+func openWithProgress(localPath string, progressFn ProgressFunc) (*os.File, *progressReader, error) {
+	f, err := os.Open(localPath)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to open %s: %w", localPath, err)
+	}
+	info, err := f.Stat()
+	if err != nil {
+		f.Close()
+		return nil, nil, fmt.Errorf("failed to stat %s: %w", localPath, err)
+	}
+	return f, &progressReader{r: f, total: info.Size(), progressFn: progressFn}, nil
+}
+
+// smbUploader copies files to an SMB share reachable as a UNC path (Windows)
+// or a mounted directory (macOS/Linux)
+type smbUploader struct {
+	dir string
+}
+
+func (u *smbUploader) Upload(ctx context.Context, localPath string, progressFn ProgressFunc) (string, error) {
+	f, reader, err := openWithProgress(localPath, progressFn)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+
+	// Never create the share directory: if it isn't mounted, that would
+	// quietly write to local disk instead
+	if info, err := os.Stat(u.dir); err != nil {
+		return "", fmt.Errorf("share not reachable at %s: %w", u.dir, err)
+	} else if !info.IsDir() {
+		return "", fmt.Errorf("share not reachable at %s: not a directory", u.dir)
+	}
+
+	dest := filepath.Join(u.dir, filepath.Base(localPath))
+	tmp := dest + ".part"
+	out, err := os.Create(tmp)
+	if err != nil {
+		return "", fmt.Errorf("failed to create %s: %w", tmp, err)
+	}
+
+	buf := make([]byte, 256*1024)
+	for {
+		if ctx.Err() != nil {
+			out.Close()
+			os.Remove(tmp)
+			return "", ctx.Err()
+		}
+		n, readErr := reader.Read(buf)
+		if n > 0 {
+			if _, err := out.Write(buf[:n]); err != nil {
+				out.Close()
+				os.Remove(tmp)
+				return "", fmt.Errorf("failed to write %s: %w", tmp, err)
+			}
+		}
+		if readErr == io.EOF {
+			break
+		}
+		if readErr != nil {
+			out.Close()
+			os.Remove(tmp)
+			return "", readErr
+		}
+	}
+
+	if err := out.Close(); err != nil {
+		os.Remove(tmp)
+		return "", err
+	}
+	if err := os.Rename(tmp, dest); err != nil {
+		os.Remove(tmp)
+		return "", err
+	}
+	return dest, nil
+}
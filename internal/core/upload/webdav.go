@@ -0,0 +1,41 @@
+package upload
+
+import (
+	"context"
+	"path"
+	"path/filepath"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/webdav"
+)
+
+// webdavUploader uploads to a configured WebDAV server
+type webdavUploader struct {
+	client *webdav.Client
+	dir    string
+}
+
+func newWebDAVUploader(server *config.WebDAVServer, dir string) (*webdavUploader, error) {
+	client, err := webdav.NewClientFromConfig(server)
+	if err != nil {
+		return nil, err
+	}
+	if dir == "" {
+		dir = "/"
+	}
+	return &webdavUploader{client: client, dir: dir}, nil
+}
+
+func (u *webdavUploader) Upload(ctx context.Context, localPath string, progressFn ProgressFunc) (string, error) {
+	f, reader, err := openWithProgress(localPath, progressFn)
+	if err != nil {
+		return "", err
+	}
+	defer f.Close()
+
+	remotePath := path.Join("/", u.dir, filepath.Base(localPath))
+	if err := u.client.Upload(ctx, remotePath, reader, reader.total); err != nil {
+		return "", err
+	}
+	return u.client.GetFileURL(remotePath), nil
+}
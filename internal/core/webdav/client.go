@@ -270,3 +270,63 @@ func (c *Client) SupportsRangeRequests(ctx context.Context, filePath string) (bo
 
 	return resp.Header.Get("Accept-Ranges") == "bytes", nil
 }
+
+// Upload writes the contents of r to filePath on the server with an HTTP PUT.
+// Parent directories are created first. size is sent as Content-Length (-1 if unknown).
+func (c *Client) Upload(ctx context.Context, filePath string, r io.Reader, size int64) error {
+	if err := c.MkdirAll(ctx, path.Dir(filePath)); err != nil {
+		return err
+	}
+
+	req, err := http.NewRequestWithContext(ctx, "PUT", c.GetFileURL(filePath), r)
+	if err != nil {
+		return err
+	}
+	req.ContentLength = size
+	if auth := c.GetAuthHeader(); auth != "" {
+		req.Header.Set("Authorization", auth)
+	}
+
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to upload %s: %w", filePath, err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		return fmt.Errorf("failed to upload %s: server returned %d", filePath, resp.StatusCode)
+	}
+	return nil
+}
+
+// MkdirAll creates dirPath and any missing parents with MKCOL.
+// Directories that already exist (405 Method Not Allowed) are not an error.
+func (c *Client) MkdirAll(ctx context.Context, dirPath string) error {
+	current := ""
+	for _, segment := range strings.Split(strings.Trim(dirPath, "/"), "/") {
+		if segment == "" {
+			continue
+		}
+		current += "/" + segment
+
+		req, err := http.NewRequestWithContext(ctx, "MKCOL", c.GetFileURL(current+"/"), nil)
+		if err != nil {
+			return err
+		}
+		if auth := c.GetAuthHeader(); auth != "" {
+			req.Header.Set("Authorization", auth)
+		}
+
+		resp, err := http.DefaultClient.Do(req)
+		if err != nil {
+			return fmt.Errorf("failed to create directory %s: %w", current, err)
+		}
+		resp.Body.Close()
+
+		if resp.StatusCode != http.StatusCreated && resp.StatusCode != http.StatusMethodNotAllowed &&
+			(resp.StatusCode < 200 || resp.StatusCode >= 300) {
+			return fmt.Errorf("failed to create directory %s: server returned %d", current, resp.StatusCode)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,195 @@
+package server
+
+import (
+	"context"
+	"fmt"
+	"log"
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/power"
+)
+
+// Actions run once the download queue drains
+const (
+	AfterQueueNone     = "none"
+	AfterQueueQuit     = "quit"     // stop vget-server
+	AfterQueueSleep    = "sleep"    // put the computer to sleep
+	AfterQueueShutdown = "shutdown" // turn the computer off
+)
+
+const (
+	// defaultAfterQueueCountdown is how long the countdown before the action
+	// lasts when the request doesn't say
+	defaultAfterQueueCountdown = 60
+
+	// maxAfterQueueCountdown bounds the countdown in seconds
+	maxAfterQueueCountdown = 3600
+)
+
+// AfterQueueRequest is the request body for POST /queue/after
+type AfterQueueRequest struct {
+	Action    string `json:"action" binding:"required"`
+	Countdown int    `json:"countdown,omitempty"` // seconds to wait, cancellable, once the queue is done
+}
+
+// handleGetAfterQueue returns the pending action and, while counting down,
+// the seconds left
+func (s *Server) handleGetAfterQueue(c *gin.Context) {
+	s.afterMu.Lock()
+	after := s.afterQueue
+	remaining := 0
+	if !s.afterCountdown.IsZero() {
+		remaining = max(0, int(time.Until(s.afterCountdown).Round(time.Second).Seconds()))
+	}
+	s.afterMu.Unlock()
+
+	if after.Action == "" {
+		after.Action = AfterQueueNone
+	}
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"action":    after.Action,
+			"countdown": after.Countdown,
+			"remaining": remaining,
+		},
+		Message: "after-queue action retrieved",
+	})
+}
+
+// handleSetAfterQueue sets what happens when the last queued job finishes.
+// It isn't saved and is cleared once it runs.
+func (s *Server) handleSetAfterQueue(c *gin.Context) {
+	var req AfterQueueRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "action is required"})
+		return
+	}
+	switch req.Action {
+	case AfterQueueNone, AfterQueueQuit, AfterQueueSleep, AfterQueueShutdown:
+	default:
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: fmt.Sprintf("invalid action: %s (use none, quit, sleep, or shutdown)", req.Action)})
+		return
+	}
+	if req.Countdown < 0 || req.Countdown > maxAfterQueueCountdown {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: fmt.Sprintf("countdown must be between 0 and %d seconds", maxAfterQueueCountdown)})
+		return
+	}
+	if req.Countdown == 0 {
+		req.Countdown = defaultAfterQueueCountdown
+	}
+
+	s.setAfterQueue(req)
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"action": req.Action, "countdown": req.Countdown, "idle": s.jobQueue.IsIdle()},
+		Message: "after-queue action set",
+	})
+}
+
+// handleCancelAfterQueue clears the action, stopping a running countdown
+func (s *Server) handleCancelAfterQueue(c *gin.Context) {
+	s.setAfterQueue(AfterQueueRequest{Action: AfterQueueNone})
+	c.JSON(http.StatusOK, Response{Code: 200, Data: nil, Message: "after-queue action cleared"})
+}
+
+// setAfterQueue replaces the pending action, cancelling the previous one
+func (s *Server) setAfterQueue(req AfterQueueRequest) {
+	s.afterMu.Lock()
+	defer s.afterMu.Unlock()
+
+	if s.afterCancel != nil {
+		s.afterCancel()
+		s.afterCancel = nil
+	}
+	if !s.afterCountdown.IsZero() {
+		s.afterCountdown = time.Time{}
+		s.jobQueue.events.Publish(Event{Type: EventQueueAfterCancelled, Data: gin.H{"action": s.afterQueue.Action, "reason": "cancelled"}})
+	}
+	s.afterQueue = AfterQueueRequest{}
+	if req.Action == AfterQueueNone {
+		return
+	}
+
+	ctx, cancel := context.WithCancel(context.Background())
+	s.afterQueue = req
+	s.afterCancel = cancel
+	go s.runAfterQueue(ctx, req)
+}
+
+// runAfterQueue waits until jobs have run and the queue is idle, counts down
+// with an event every second, and then runs the action. Jobs queued during
+// the countdown stop it until they finish too.
+func (s *Server) runAfterQueue(ctx context.Context, req AfterQueueRequest) {
+	events := s.jobQueue.events
+	busy := !s.jobQueue.IsIdle()
+	remaining := -1 // not counting down
+
+	ticker := time.NewTicker(time.Second)
+	defer ticker.Stop()
+	for remaining != 0 {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+
+		if !s.jobQueue.IsIdle() {
+			busy = true
+			if remaining >= 0 {
+				remaining = -1
+				s.setAfterCountdown(ctx, time.Time{})
+				events.Publish(Event{Type: EventQueueAfterCancelled, Data: gin.H{"action": req.Action, "reason": "jobs_queued"}})
+			}
+			continue
+		}
+		if !busy {
+			continue
+		}
+		if remaining < 0 {
+			remaining = req.Countdown
+			s.setAfterCountdown(ctx, time.Now().Add(time.Duration(remaining)*time.Second))
+		} else {
+			remaining--
+		}
+		events.Publish(Event{Type: EventQueueAfterCountdown, Data: gin.H{"action": req.Action, "remaining": remaining}})
+	}
+
+	s.afterMu.Lock()
+	if ctx.Err() != nil {
+		s.afterMu.Unlock()
+		return
+	}
+	s.afterQueue = AfterQueueRequest{}
+	s.afterCancel = nil
+	s.afterCountdown = time.Time{}
+	s.afterMu.Unlock()
+
+	log.Printf("Download queue finished, running after-queue action: %s", req.Action)
+	var err error
+	switch req.Action {
+	case AfterQueueQuit:
+		stopCtx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+		defer cancel()
+		err = s.Stop(stopCtx)
+	case AfterQueueSleep:
+		err = power.Sleep()
+	case AfterQueueShutdown:
+		err = power.Shutdown()
+	}
+	if err != nil {
+		log.Printf("After-queue action %s failed: %v", req.Action, err)
+		events.Publish(Event{Type: EventQueueAfterFailed, Data: gin.H{"action": req.Action, "error": err.Error()}})
+	}
+}
+
+// setAfterCountdown records when the action of the still current ctx runs
+func (s *Server) setAfterCountdown(ctx context.Context, at time.Time) {
+	s.afterMu.Lock()
+	defer s.afterMu.Unlock()
+	if ctx.Err() == nil {
+		s.afterCountdown = at
+	}
+}
@@ -0,0 +1,207 @@
+package server
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/auth"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// SetAuthCookieRequest is the request body for PUT /auth/:site/cookie
+type SetAuthCookieRequest struct {
+	Cookie    string `json:"cookie" binding:"required"` // Cookie header of a logged-in browser session
+	UserAgent string `json:"user_agent,omitempty"`      // the browser's user agent, sent with the cookies
+}
+
+// handleGetAuthStatuses lists the login status of the known sites and every
+// other site with a stored login
+func (s *Server) handleGetAuthStatuses(c *gin.Context) {
+	statuses := auth.Statuses()
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"sites": statuses},
+		Message: fmt.Sprintf("%d sites", len(statuses)),
+	})
+}
+
+// handleGetAuthStatus returns the login status of one site
+func (s *Server) handleGetAuthStatus(c *gin.Context) {
+	status, err := auth.GetStatus(c.Param("site"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	message := "not logged in"
+	if status.LoggedIn {
+		message = "logged in"
+	} else if status.Expired {
+		message = "login expired"
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: status, Message: message})
+}
+
+// handleSetAuthCookie saves a browser cookie as the login of a site,
+// replacing its stored cookies
+func (s *Server) handleSetAuthCookie(c *gin.Context) {
+	var req SetAuthCookieRequest
+	if err := c.ShouldBindJSON(&req); err != nil || len(auth.ParseCookieHeader(req.Cookie)) == 0 {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "cookie is required, as name=value pairs separated by ';'"})
+		return
+	}
+	login, err := auth.Get(c.Param("site"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	login.Cookies = auth.ParseCookieHeader(req.Cookie)
+	login.Expires = time.Time{}
+	if req.UserAgent != "" {
+		login.UserAgent = req.UserAgent
+	}
+	if err := auth.Save(login); err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	status, _ := auth.GetStatus(login.Site)
+	c.JSON(http.StatusOK, Response{Code: 200, Data: status, Message: fmt.Sprintf("saved %d cookies for %s", status.Cookies, login.Site)})
+}
+
+// SwitchProfileRequest is the request body for PUT /auth/:site/profile
+type SwitchProfileRequest struct {
+	Profile string `json:"profile" binding:"required"` // profile to make active; a new one starts logged out
+}
+
+// handleSwitchProfile switches the active login profile of a site
+func (s *Server) handleSwitchProfile(c *gin.Context) {
+	var req SwitchProfileRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "profile is required"})
+		return
+	}
+	site, err := auth.SiteID(c.Param("site"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	if err := auth.SwitchProfile(site, req.Profile); err != nil {
+		status := http.StatusInternalServerError
+		if errors.Is(err, auth.ErrInvalidProfile) {
+			status = http.StatusBadRequest
+		}
+		c.JSON(status, Response{Code: status, Data: nil, Message: err.Error()})
+		return
+	}
+	status, _ := auth.GetStatus(site)
+	c.JSON(http.StatusOK, Response{Code: 200, Data: status, Message: fmt.Sprintf("switched %s to profile %s", site, req.Profile)})
+}
+
+// handleLogout removes the stored login of a site's active profile
+func (s *Server) handleLogout(c *gin.Context) {
+	site, err := auth.SiteID(c.Param("site"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	if err := auth.Logout(site); err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: gin.H{"site": site}, Message: "logged out of " + site})
+}
+
+// BrowserLoginRequest is the request body for POST /auth/:site/login. Sites
+// with a built-in browser login (xiaohongshu, twitter, youtube) need none;
+// for others, url and success_selector are required.
+type BrowserLoginRequest struct {
+	URL             string   `json:"url,omitempty"`              // login page
+	SuccessSelector string   `json:"success_selector,omitempty"` // CSS selector of an element shown only once logged in
+	Domains         []string `json:"domains,omitempty"`          // domains whose cookies are saved; defaults to the site's
+}
+
+// handleBrowserLogin opens a browser window on the machine running the server
+// for the user to log in to a site. It returns at once; the outcome is sent
+// as a login_success or login_failed event, and for Xiaohongshu also as
+// xhs_login_success or xhs_login_failed.
+func (s *Server) handleBrowserLogin(c *gin.Context) {
+	var req BrowserLoginRequest
+	if err := c.ShouldBindJSON(&req); err != nil && !errors.Is(err, io.EOF) {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body"})
+		return
+	}
+	site, err := auth.SiteID(c.Param("site"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	opts := extractor.BrowserLoginOptions{Site: site, URL: req.URL, SuccessSelector: req.SuccessSelector, Domains: req.Domains}
+	if builtin, ok := extractor.SiteLoginOptions(site); ok && req.URL == "" {
+		opts = builtin
+	} else if req.URL == "" || req.SuccessSelector == "" {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "url and success_selector are required to log in to " + site})
+		return
+	}
+
+	s.loginMu.Lock()
+	if s.loginCancel != nil {
+		s.loginMu.Unlock()
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "a login window is already open"})
+		return
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	s.loginCancel = cancel
+	s.loginMu.Unlock()
+
+	go func() {
+		n, err := extractor.BrowserLogin(ctx, opts)
+		s.loginMu.Lock()
+		s.loginCancel = nil
+		s.loginMu.Unlock()
+		cancel()
+
+		if err != nil {
+			log.Printf("Login to %s failed: %v", site, err)
+			s.publishLogin(EventLoginFailed, gin.H{"site": site, "error": err.Error()})
+			return
+		}
+		log.Printf("Logged in to %s, saved %d cookies", site, n)
+		s.publishLogin(EventLoginSuccess, gin.H{"site": site, "cookies": n})
+	}()
+
+	c.JSON(http.StatusAccepted, Response{Code: 202, Data: gin.H{"site": site}, Message: "login window opened"})
+}
+
+// publishLogin sends the outcome of a browser login, also under the event
+// types of the Xiaohongshu login for it
+func (s *Server) publishLogin(eventType EventType, data gin.H) {
+	s.jobQueue.events.Publish(Event{Type: eventType, Data: data})
+	if data["site"] != auth.Xiaohongshu {
+		return
+	}
+	if eventType == EventLoginSuccess {
+		eventType = EventXHSLoginSuccess
+	} else {
+		eventType = EventXHSLoginFailed
+	}
+	s.jobQueue.events.Publish(Event{Type: eventType, Data: data})
+}
+
+// handleCancelBrowserLogin closes the open browser login window
+func (s *Server) handleCancelBrowserLogin(c *gin.Context) {
+	s.loginMu.Lock()
+	cancel := s.loginCancel
+	s.loginMu.Unlock()
+	if cancel == nil {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "no login window is open"})
+		return
+	}
+	cancel()
+	c.JSON(http.StatusOK, Response{Code: 200, Data: nil, Message: "login window closed"})
+}
@@ -0,0 +1,75 @@
+package server
+
+import (
+	"context"
+	"slices"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/auth"
+)
+
+// authWatchInterval is how often stored logins are checked for changes no
+// auth.OnChange reports: logins expiring, and logins made or removed by
+// other vget processes, like the CLI
+const authWatchInterval = time.Minute
+
+// authState is what of a login's status an auth_status_changed event is sent
+// for a change of
+type authState struct {
+	loggedIn  bool
+	expired   bool
+	profile   string
+	updatedAt int64 // Unix nanoseconds; a new login of the same account
+}
+
+// publishAuthStatus sends the status of site as an auth_status_changed event
+// if it changed since the last one. A site not seen before counts as changed
+// when announce is set, and is only recorded otherwise.
+func (s *Server) publishAuthStatus(site string, announce bool) {
+	status, err := auth.GetStatus(site)
+	if err != nil {
+		return
+	}
+	state := authState{
+		loggedIn:  status.LoggedIn,
+		expired:   status.Expired,
+		profile:   status.Profile,
+		updatedAt: status.UpdatedAt.UnixNano(),
+	}
+
+	s.authMu.Lock()
+	prev, seen := s.authStates[status.Site]
+	s.authStates[status.Site] = state
+	s.authMu.Unlock()
+	if (seen && prev == state) || (!seen && !announce) {
+		return
+	}
+	s.jobQueue.events.Publish(Event{Type: EventAuthStatusChanged, Data: status})
+}
+
+// watchAuthStatus checks the stored logins every authWatchInterval until ctx
+// is done, sending an auth_status_changed event for each that changed. The
+// first check only records them.
+func (s *Server) watchAuthStatus(ctx context.Context) {
+	ticker := time.NewTicker(authWatchInterval)
+	defer ticker.Stop()
+	for announce := false; ; announce = true {
+		sites := slices.Clone(auth.KnownSites)
+		sites = append(sites, auth.Sites()...)
+		s.authMu.Lock()
+		for site := range s.authStates {
+			sites = append(sites, site) // sites logged out elsewhere drop out of auth.Sites
+		}
+		s.authMu.Unlock()
+		slices.Sort(sites)
+		for _, site := range slices.Compact(sites) {
+			s.publishAuthStatus(site, announce)
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
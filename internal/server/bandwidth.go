@@ -0,0 +1,27 @@
+package server
+
+import (
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/bandwidth"
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// handleGetBandwidth reports bytes downloaded today, per day this month, and per month
+func (s *Server) handleGetBandwidth(c *gin.Context) {
+	if s.jobQueue.bandwidth == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "bandwidth accounting is not available"})
+		return
+	}
+
+	cfg := config.LoadOrDefault()
+	report := s.jobQueue.bandwidth.Report(bandwidth.CapBytes(cfg.Bandwidth), time.Now())
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    report,
+		Message: "bandwidth usage retrieved",
+	})
+}
@@ -1,11 +1,14 @@
 package server
 
 import (
+	"context"
+	"errors"
 	"log"
 	"net/http"
+	"time"
 
 	"github.com/gin-gonic/gin"
-	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/auth"
 	"github.com/guiyumin/vget/internal/core/site/bilibili"
 )
 
@@ -23,11 +26,22 @@ func (s *Server) handleBilibiliQRGenerate(c *gin.Context) {
 		return
 	}
 
+	qrImage, err := session.ImageDataURL()
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{
+			Code:    500,
+			Data:    nil,
+			Message: "failed to render QR code: " + err.Error(),
+		})
+		return
+	}
+
 	c.JSON(http.StatusOK, Response{
 		Code: 200,
 		Data: gin.H{
 			"url":        session.URL,
 			"qrcode_key": session.QRCodeKey,
+			"qr_image":   qrImage,
 		},
 		Message: "QR code generated",
 	})
@@ -78,17 +92,16 @@ func (s *Server) handleBilibiliQRPoll(c *gin.Context) {
 		}
 
 		// Try to get username
-		username, validateErr := auth.ValidateCredentials(creds)
-		log.Printf("[Bilibili] Validate result: username=%s, err=%v", username, validateErr)
-		if username == "" {
-			username = creds.DedeUserID
+		username := creds.DedeUserID
+		user, validateErr := s.checkBilibiliLogin(0)
+		log.Printf("[Bilibili] Validate result: user=%+v, err=%v", user, validateErr)
+		if validateErr == nil && user.Name != "" {
+			username = user.Name
+			data["avatar"] = user.Avatar
 		}
 
 		data["logged_in"] = true
 		data["username"] = username
-
-		// Update server's cached config
-		s.cfg = config.LoadOrDefault()
 		log.Printf("[Bilibili] Login successful for user: %s", username)
 	}
 
@@ -99,11 +112,12 @@ func (s *Server) handleBilibiliQRPoll(c *gin.Context) {
 	})
 }
 
-// handleBilibiliStatus returns the current Bilibili login status
+// handleBilibiliStatus returns the current Bilibili login status, with the
+// account's username and avatar
 func (s *Server) handleBilibiliStatus(c *gin.Context) {
-	cfg := config.LoadOrDefault()
-
-	if cfg.Bilibili.Cookie == "" {
+	user, err := s.checkBilibiliLogin(bilibiliUserTTL)
+	switch {
+	case errors.Is(err, errBilibiliNoLogin):
 		c.JSON(http.StatusOK, Response{
 			Code: 200,
 			Data: gin.H{
@@ -111,44 +125,109 @@ func (s *Server) handleBilibiliStatus(c *gin.Context) {
 			},
 			Message: "not logged in",
 		})
-		return
-	}
-
-	// Parse and validate credentials
-	creds := bilibili.ParseCookieString(cfg.Bilibili.Cookie)
-	if creds.SESSDATA == "" {
+	case err != nil:
+		// Cookie exists but validation failed (might be expired)
 		c.JSON(http.StatusOK, Response{
 			Code: 200,
 			Data: gin.H{
 				"logged_in": false,
+				"error":     err.Error(),
 			},
-			Message: "invalid cookie",
+			Message: "cookie expired or invalid",
 		})
-		return
-	}
-
-	// Try to validate and get username
-	auth := bilibili.NewAuth()
-	username, err := auth.ValidateCredentials(creds)
-	if err != nil {
-		// Cookie exists but validation failed (might be expired)
+	default:
 		c.JSON(http.StatusOK, Response{
 			Code: 200,
 			Data: gin.H{
-				"logged_in": false,
-				"error":     err.Error(),
+				"logged_in": true,
+				"username":  user.Name,
+				"avatar":    user.Avatar,
+				"mid":       user.Mid,
 			},
-			Message: "cookie expired or invalid",
+			Message: "logged in",
 		})
-		return
 	}
+}
+
+const (
+	// bilibiliKeepAliveInterval is how often the stored Bilibili session is
+	// pinged to keep it fresh
+	bilibiliKeepAliveInterval = time.Hour
+
+	// bilibiliUserTTL is how long GET /bilibili/status reuses the account
+	// fetched for the session
+	bilibiliUserTTL = 10 * time.Minute
+)
+
+// errBilibiliNoLogin is returned by checkBilibiliLogin when no login is stored
+var errBilibiliNoLogin = errors.New("not logged in")
+
+// bilibiliUser is the account of the stored Bilibili login, as last fetched
+type bilibiliUser struct {
+	cookie    string             // Cookie header it was fetched with
+	user      *bilibili.UserInfo // nil when err is set
+	err       error
+	checkedAt time.Time
+}
+
+// checkBilibiliLogin fetches the account of the stored Bilibili login from
+// the nav API, unless it was fetched with the same cookies within maxAge. It
+// saves the cookies Bilibili renewed along the way, or marks the login
+// expired when Bilibili rejects it.
+func (s *Server) checkBilibiliLogin(maxAge time.Duration) (*bilibili.UserInfo, error) {
+	login, _ := auth.Get(auth.Bilibili)
+	if login.Empty() {
+		return nil, errBilibiliNoLogin
+	}
+	if login.Expired() {
+		return nil, bilibili.ErrNotLoggedIn
+	}
+	cookie := login.CookieHeader()
 
-	c.JSON(http.StatusOK, Response{
-		Code: 200,
-		Data: gin.H{
-			"logged_in": true,
-			"username":  username,
-		},
-		Message: "logged in",
-	})
+	s.bilibiliMu.Lock()
+	cached := s.bilibiliUser
+	s.bilibiliMu.Unlock()
+	if cached.cookie == cookie && time.Since(cached.checkedAt) < maxAge {
+		return cached.user, cached.err
+	}
+
+	user, renewed, err := bilibili.NewAuth().Nav(cookie)
+	switch {
+	case errors.Is(err, bilibili.ErrNotLoggedIn):
+		login.Expires = time.Now()
+		if saveErr := auth.Save(login); saveErr != nil {
+			log.Printf("[Bilibili] Failed to mark the login expired: %v", saveErr)
+		}
+	case err == nil && login.UpdateCookies(renewed):
+		if saveErr := auth.Save(login); saveErr != nil {
+			log.Printf("[Bilibili] Failed to save renewed cookies: %v", saveErr)
+		} else {
+			cookie = login.CookieHeader()
+		}
+	}
+
+	s.bilibiliMu.Lock()
+	s.bilibiliUser = bilibiliUser{cookie: cookie, user: user, err: err, checkedAt: time.Now()}
+	s.bilibiliMu.Unlock()
+	return user, err
+}
+
+// keepBilibiliAlive pings the stored Bilibili session every
+// bilibiliKeepAliveInterval until ctx is done, keeping it fresh and the
+// account shown in the UI current
+func (s *Server) keepBilibiliAlive(ctx context.Context) {
+	ticker := time.NewTicker(bilibiliKeepAliveInterval)
+	defer ticker.Stop()
+	for {
+		_, err := s.checkBilibiliLogin(0)
+		if err != nil && !errors.Is(err, errBilibiliNoLogin) && !errors.Is(err, bilibili.ErrNotLoggedIn) {
+			log.Printf("[Bilibili] Keep-alive: %v", err)
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
 }
@@ -0,0 +1,74 @@
+package server
+
+import (
+	"fmt"
+	"log"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+)
+
+// staleTempFiles lists leftovers of interrupted downloads older than
+// download.temp_max_age_days
+func (s *Server) staleTempFiles() ([]downloader.TempFile, int64, error) {
+	files, err := downloader.FindTempFiles(s.outputDir, config.LoadOrDefault().Download.TempMaxAge())
+	if err != nil {
+		return nil, 0, err
+	}
+	var total int64
+	for _, f := range files {
+		total += f.Size
+	}
+	return files, total, nil
+}
+
+// reportTempFiles logs stale leftovers found at startup; they're only
+// removed on request
+func (s *Server) reportTempFiles() {
+	files, total, err := s.staleTempFiles()
+	if err != nil {
+		log.Printf("Temp file scan: %v", err)
+		return
+	}
+	if len(files) > 0 {
+		log.Printf("Found %d stale temp files (%.1f MB); remove them with POST /api/cleanup or vget cleanup", len(files), float64(total)/1024/1024)
+	}
+}
+
+// handleGetCleanup lists stale temp files without removing them
+func (s *Server) handleGetCleanup(c *gin.Context) {
+	files, total, err := s.staleTempFiles()
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	if files == nil {
+		files = []downloader.TempFile{}
+	}
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"files": files, "size": total},
+		Message: fmt.Sprintf("%d stale temp files found", len(files)),
+	})
+}
+
+// handleCleanup removes stale temp files and reports the space reclaimed
+func (s *Server) handleCleanup(c *gin.Context) {
+	files, _, err := s.staleTempFiles()
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	reclaimed, failed := downloader.RemoveTempFiles(files)
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"removed":   len(files) - len(failed),
+			"reclaimed": reclaimed,
+			"failed":    failed,
+		},
+		Message: fmt.Sprintf("reclaimed %d bytes", reclaimed),
+	})
+}
@@ -0,0 +1,973 @@
+package server
+
+import (
+	"context"
+	"fmt"
+	"log"
+	"net/http"
+	"os"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+	"github.com/guiyumin/vget/internal/core/subtitle"
+)
+
+// Conversion statuses: those of download jobs, running where a job would be
+// downloading
+const (
+	ConvertQueued              = JobStatusQueued
+	ConvertRunning   JobStatus = "running"
+	ConvertCompleted           = JobStatusCompleted
+	ConvertFailed              = JobStatusFailed
+	ConvertCancelled           = JobStatusCancelled
+)
+
+// Conversion is an ffmpeg run on a downloaded file, started from one of the
+// /convert endpoints
+type Conversion struct {
+	ID        string    `json:"id"`
+	Kind      string    `json:"kind"` // endpoint that started it, e.g. "audio"
+	Input     string    `json:"input"`
+	Output    string    `json:"output"`
+	Status    JobStatus `json:"status"`
+	Progress  float64   `json:"progress"` // percent, from ffmpeg's position in the output
+	Error     string    `json:"error,omitempty"`
+	BatchID   string    `json:"batch_id,omitempty"`
+	CreatedAt time.Time `json:"created_at"`
+	UpdatedAt time.Time `json:"updated_at"`
+
+	lastEventAt time.Time
+	convert     convertFunc
+	parent      context.Context    // the job's context for a download's ffmpeg work
+	cancel      context.CancelFunc // stops ffmpeg, set once running
+	done        chan error         // receives the result of a download's ffmpeg work, nil otherwise
+	batch       *ConversionBatch
+}
+
+// keptConversions is how many finished conversions are listed before the
+// oldest are forgotten
+const keptConversions = 100
+
+// convertFunc runs a conversion's ffmpeg work until it's done or ctx is
+// cancelled
+type convertFunc func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error
+
+// ffmpegFunc runs convert as a conversion of kind on behalf of a job whose
+// context is ctx, returning once it's done
+type ffmpegFunc func(ctx context.Context, kind, input, output string, convert convertFunc) error
+
+// ConvertAudioRequest is the request body for POST /convert/audio
+type ConvertAudioRequest struct {
+	Path     string `json:"path" binding:"required"`
+	Output   string `json:"output,omitempty"` // defaults to the input with the format's extension
+	Format   string `json:"format,omitempty"` // mp3, m4a, or opus; defaults to the output's extension, else mp3
+	Preset   string `json:"preset,omitempty"` // podcast or music
+	Bitrate  string `json:"bitrate,omitempty"`
+	Channels int    `json:"channels,omitempty"`
+}
+
+// ConvertVideoRequest is the request body for POST /convert/video
+type ConvertVideoRequest struct {
+	Path   string `json:"path" binding:"required"`
+	Preset string `json:"preset" binding:"required"` // see GET /convert/presets
+	Output string `json:"output,omitempty"`          // defaults to the input with the preset's extension
+
+	// Software skips hardware encoders, e.g. for the best quality per size
+	Software bool `json:"software,omitempty"`
+}
+
+// ConvertTrimRequest is the request body for POST /convert/trim
+type ConvertTrimRequest struct {
+	Path     string `json:"path" binding:"required"`
+	Start    string `json:"start,omitempty"` // seconds, MM:SS, or HH:MM:SS; defaults to the beginning
+	End      string `json:"end,omitempty"`   // defaults to the end of the file
+	Reencode bool   `json:"reencode,omitempty"`
+	Output   string `json:"output,omitempty"` // defaults to "<name> (clip).<ext>" next to the input
+}
+
+// ConvertAnimationRequest is the request body for POST /convert/animation
+type ConvertAnimationRequest struct {
+	Path     string  `json:"path" binding:"required"`
+	Format   string  `json:"format,omitempty"` // gif or webp; defaults to the output's extension, else gif
+	Start    string  `json:"start,omitempty"`
+	Duration float64 `json:"duration,omitempty"` // seconds, 0 for the rest of the video
+	FPS      int     `json:"fps,omitempty"`
+	Width    int     `json:"width,omitempty"`
+	Output   string  `json:"output,omitempty"`
+}
+
+// ConvertFrameRequest is the request body for POST /convert/frame
+type ConvertFrameRequest struct {
+	Path   string `json:"path" binding:"required"`
+	At     string `json:"at,omitempty"`     // seconds, MM:SS, or HH:MM:SS; defaults to the first frame
+	Format string `json:"format,omitempty"` // png or jpg; defaults to the output's extension, else png
+	Width  int    `json:"width,omitempty"`  // scales the frame down; 0 keeps the video's size
+	Output string `json:"output,omitempty"` // defaults to "<name> (<at>).<format>" next to the input
+}
+
+// ConvertSubtitlesRequest is the request body for POST /convert/subtitles
+type ConvertSubtitlesRequest struct {
+	Path      string   `json:"path" binding:"required"`
+	Subtitles []string `json:"subtitles,omitempty"` // defaults to the subtitle files saved next to the video
+	Languages []string `json:"languages,omitempty"` // picks among those by language
+	Burn      bool     `json:"burn,omitempty"`      // draws the first subtitles onto the video instead of adding tracks
+	Output    string   `json:"output,omitempty"`    // defaults to "<name> (subtitled).<ext>" next to the input
+}
+
+// ConvertRemuxRequest is the request body for POST /convert/remux
+type ConvertRemuxRequest struct {
+	Path      string `json:"path" binding:"required"`
+	Container string `json:"container,omitempty"` // mp4, m4a, mkv, or webm; defaults to the output's extension
+	Output    string `json:"output,omitempty"`    // defaults to the input with the container's extension
+}
+
+// ConvertLoudnessRequest is the request body for POST /convert/loudness
+type ConvertLoudnessRequest struct {
+	Path   string  `json:"path" binding:"required"`
+	Target float64 `json:"target,omitempty"` // LUFS; defaults to download.loudness_target, else -16
+	Output string  `json:"output,omitempty"` // defaults to "<name> (normalized).<ext>" next to the input
+}
+
+// ConvertWatermarkRequest is the request body for POST /convert/watermark
+type ConvertWatermarkRequest struct {
+	Path     string  `json:"path" binding:"required"`
+	Image    string  `json:"image,omitempty"` // PNG in the output directory; either image or text
+	Text     string  `json:"text,omitempty"`
+	Position string  `json:"position,omitempty"` // top-left, top-right, bottom-left, bottom-right, or center
+	Opacity  float64 `json:"opacity,omitempty"`  // 0 to 1; defaults to 0.8
+	Margin   int     `json:"margin,omitempty"`   // pixels from the edges; defaults to 16
+	Width    int     `json:"width,omitempty"`    // scales the image; 0 keeps its size
+	FontSize int     `json:"font_size,omitempty"`
+	Output   string  `json:"output,omitempty"` // defaults to "<name> (watermarked).mp4" next to the input
+}
+
+// ConvertAdjustRequest is the request body for POST /convert/adjust
+type ConvertAdjustRequest struct {
+	Path   string  `json:"path" binding:"required"`
+	Speed  float64 `json:"speed,omitempty"`  // 0.25 to 4; 0 or 1 keeps it
+	Gain   float64 `json:"gain,omitempty"`   // dB, -40 to 40
+	Output string  `json:"output,omitempty"` // defaults to e.g. "<name> (1.5x).<ext>" next to the input
+}
+
+// mediaPath resolves a file path from a request, relative to the output
+// directory, and checks it's an existing file inside it. On failure the
+// error response is written and false returned.
+func (s *Server) mediaPath(c *gin.Context, path string) (string, bool) {
+	path, ok := s.outputPathIn(c, path)
+	if !ok {
+		return "", false
+	}
+	if info, err := os.Stat(path); err != nil || info.IsDir() {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "file not found"})
+		return "", false
+	}
+	return path, true
+}
+
+// outputPathIn resolves a path from a request relative to the output
+// directory and checks it's inside it. On failure the error response is
+// written and false returned.
+func (s *Server) outputPathIn(c *gin.Context, path string) (string, bool) {
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(s.outputDir, path)
+	}
+	absPath, err := filepath.Abs(path)
+	absOutputDir, _ := filepath.Abs(s.outputDir)
+	if err != nil || !strings.HasPrefix(absPath, absOutputDir+string(filepath.Separator)) {
+		c.JSON(http.StatusForbidden, Response{Code: 403, Data: nil, Message: "access denied: file outside output directory"})
+		return "", false
+	}
+	return absPath, true
+}
+
+// conversionOutput resolves the requested output path, or picks a free one
+// from def when none was requested
+func (s *Server) conversionOutput(c *gin.Context, requested, def string) (string, bool) {
+	if requested == "" {
+		return downloader.UniquePath(def), true
+	}
+	return s.outputPathIn(c, requested)
+}
+
+// startConversion queues convert as a tracked conversion, publishing
+// convert_updated events as it goes. Up to download.max_conversions run at
+// once, oldest first, so long transcodes don't all compete for the CPU.
+func (s *Server) startConversion(kind, input, output string, convert convertFunc) (*Conversion, error) {
+	conv, err := newConversion(kind, input, output, convert)
+	if err != nil {
+		return nil, err
+	}
+	limit := config.LoadOrDefault().Download.EffectiveMaxConversions()
+
+	s.convertMu.Lock()
+	defer s.convertMu.Unlock()
+	s.queueConversionLocked(conv)
+	s.dispatchConversionsLocked(limit)
+	snapshot := *conv
+	return &snapshot, nil
+}
+
+// runFFmpeg runs the ffmpeg work of a download as a conversion, so it waits
+// for a slot among download.max_conversions like the /convert endpoints and
+// is listed with them. Cancelling ctx, the job's context, stops it whether
+// it's queued or running.
+func (s *Server) runFFmpeg(ctx context.Context, kind, input, output string, convert convertFunc) error {
+	conv, err := newConversion(kind, input, output, convert)
+	if err != nil {
+		return err
+	}
+	conv.parent = ctx
+	conv.done = make(chan error, 1)
+	limit := config.LoadOrDefault().Download.EffectiveMaxConversions()
+
+	s.convertMu.Lock()
+	s.queueConversionLocked(conv)
+	s.dispatchConversionsLocked(limit)
+	s.convertMu.Unlock()
+
+	select {
+	case err := <-conv.done:
+		return err
+	case <-ctx.Done():
+	}
+	s.convertMu.Lock()
+	s.cancelConversionLocked(conv)
+	s.convertMu.Unlock()
+	return <-conv.done
+}
+
+// newConversion creates a queued conversion
+func newConversion(kind, input, output string, convert convertFunc) (*Conversion, error) {
+	id, err := generateJobID()
+	if err != nil {
+		return nil, err
+	}
+	now := time.Now()
+	return &Conversion{
+		ID:        id,
+		Kind:      kind,
+		Input:     input,
+		Output:    output,
+		Status:    ConvertQueued,
+		CreatedAt: now,
+		UpdatedAt: now,
+		convert:   convert,
+		parent:    context.Background(),
+	}, nil
+}
+
+// queueConversionLocked adds conv to the end of the queue. Must be called
+// with s.convertMu held.
+func (s *Server) queueConversionLocked(conv *Conversion) {
+	if s.conversions == nil {
+		s.conversions = make(map[string]*Conversion)
+	}
+	s.pruneConversionsLocked()
+	s.conversions[conv.ID] = conv
+	s.convertQueue = append(s.convertQueue, conv)
+	s.publishConversionLocked(conv)
+}
+
+// pruneConversionsLocked forgets the oldest finished conversions beyond
+// keptConversions, and the finished batches none of whose items are left. Must be
+// called with s.convertMu held.
+func (s *Server) pruneConversionsLocked() {
+	var finished []*Conversion
+	for _, conv := range s.conversions {
+		if conv.Status != ConvertQueued && conv.Status != ConvertRunning {
+			finished = append(finished, conv)
+		}
+	}
+	if len(finished) <= keptConversions {
+		return
+	}
+	sort.Slice(finished, func(i, j int) bool {
+		return finished[i].UpdatedAt.Before(finished[j].UpdatedAt)
+	})
+	for _, conv := range finished[:len(finished)-keptConversions] {
+		delete(s.conversions, conv.ID)
+	}
+	for id, batch := range s.convertBatches {
+		if batch.Status == ConvertQueued || batch.Status == ConvertRunning {
+			continue
+		}
+		kept := slices.ContainsFunc(batch.items, func(conv *Conversion) bool {
+			return s.conversions[conv.ID] != nil
+		})
+		if !kept {
+			delete(s.convertBatches, id)
+		}
+	}
+}
+
+// dispatchConversionsLocked starts queued conversions while fewer than limit
+// are running. The items of a batch run one after another, so a queued item
+// waits while an earlier one of its batch runs. Must be called with
+// s.convertMu held.
+func (s *Server) dispatchConversionsLocked(limit int) {
+	for s.convertRunning < limit {
+		i := slices.IndexFunc(s.convertQueue, func(conv *Conversion) bool {
+			return conv.batch == nil || conv.batch.Current == ""
+		})
+		if i < 0 {
+			return
+		}
+		conv := s.convertQueue[i]
+		s.convertQueue = slices.Delete(s.convertQueue, i, i+1)
+
+		ctx, cancel := context.WithCancel(conv.parent)
+		conv.cancel = cancel
+		conv.Status = ConvertRunning
+		conv.UpdatedAt = time.Now()
+		s.convertRunning++
+		s.publishConversionLocked(conv)
+		go s.runConversion(ctx, conv)
+	}
+}
+
+// runConversion runs a dispatched conversion, then starts the next queued one
+func (s *Server) runConversion(ctx context.Context, conv *Conversion) {
+	err := conv.convert(ctx, func(done, total time.Duration) {
+		if total <= 0 {
+			return
+		}
+		s.convertMu.Lock()
+		defer s.convertMu.Unlock()
+		conv.Progress = float64(done) / float64(total) * 100
+		conv.UpdatedAt = time.Now()
+		if conv.UpdatedAt.Sub(conv.lastEventAt) >= progressEventInterval {
+			s.publishConversionLocked(conv)
+		}
+	})
+	limit := config.LoadOrDefault().Download.EffectiveMaxConversions()
+
+	s.convertMu.Lock()
+	defer s.convertMu.Unlock()
+	switch {
+	case err != nil && ctx.Err() != nil:
+		log.Printf("Conversion %s of %s cancelled", conv.ID, conv.Input)
+		conv.Status = ConvertCancelled
+	case err != nil:
+		log.Printf("Conversion %s of %s failed: %v", conv.ID, conv.Input, err)
+		conv.Status = ConvertFailed
+		conv.Error = err.Error()
+	default:
+		conv.Status = ConvertCompleted
+		conv.Progress = 100
+	}
+	conv.cancel()
+	conv.UpdatedAt = time.Now()
+	s.convertRunning--
+	s.publishConversionLocked(conv)
+	if conv.done != nil {
+		conv.done <- err
+	}
+	s.dispatchConversionsLocked(limit)
+}
+
+// cancelConversionLocked stops conv if it's queued or running, reporting
+// false if it already finished. A running conversion is marked cancelled
+// once ffmpeg exits. Must be called with s.convertMu held.
+func (s *Server) cancelConversionLocked(conv *Conversion) bool {
+	switch conv.Status {
+	case ConvertQueued:
+		s.convertQueue = slices.DeleteFunc(s.convertQueue, func(queued *Conversion) bool { return queued == conv })
+		conv.Status = ConvertCancelled
+		conv.UpdatedAt = time.Now()
+		s.publishConversionLocked(conv)
+		if conv.done != nil {
+			conv.done <- context.Canceled
+		}
+	case ConvertRunning:
+		conv.cancel()
+	default:
+		return false
+	}
+	return true
+}
+
+// stopConversions cancels every queued and running conversion, so ffmpeg
+// doesn't outlive the server
+func (s *Server) stopConversions() {
+	s.convertMu.Lock()
+	defer s.convertMu.Unlock()
+	for _, conv := range s.conversions {
+		s.cancelConversionLocked(conv)
+	}
+}
+
+// publishConversionLocked sends a snapshot of conv, and of its batch, to
+// event subscribers. Must be called with s.convertMu held.
+func (s *Server) publishConversionLocked(conv *Conversion) {
+	conv.lastEventAt = time.Now()
+	snapshot := *conv
+	s.jobQueue.events.Publish(Event{Type: EventConvertUpdated, Data: &snapshot})
+	if conv.batch != nil {
+		s.publishBatchLocked(conv.batch)
+	}
+}
+
+// handleGetConversions lists the conversions of this session, newest first
+func (s *Server) handleGetConversions(c *gin.Context) {
+	s.convertMu.Lock()
+	list := make([]Conversion, 0, len(s.conversions))
+	for _, conv := range s.conversions {
+		list = append(list, *conv)
+	}
+	s.convertMu.Unlock()
+
+	sort.Slice(list, func(i, j int) bool {
+		return list[i].CreatedAt.After(list[j].CreatedAt)
+	})
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"conversions": list},
+		Message: fmt.Sprintf("%d conversions", len(list)),
+	})
+}
+
+// handleCancelConversion cancels a queued or running conversion; ffmpeg is
+// stopped and its partial output removed
+func (s *Server) handleCancelConversion(c *gin.Context) {
+	s.convertMu.Lock()
+	conv, ok := s.conversions[c.Param("id")]
+	if !ok {
+		s.convertMu.Unlock()
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "conversion not found"})
+		return
+	}
+	if !s.cancelConversionLocked(conv) {
+		s.convertMu.Unlock()
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "conversion already " + string(conv.Status)})
+		return
+	}
+	snapshot := *conv
+	s.convertMu.Unlock()
+
+	c.JSON(http.StatusOK, Response{Code: 200, Data: &snapshot, Message: "conversion cancelled"})
+}
+
+// handleConvertAudio extracts the audio of a downloaded file to MP3, M4A, or Opus
+func (s *Server) handleConvertAudio(c *gin.Context) {
+	var req ConvertAudioRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	opts, err := ffmpeg.ApplyAudioPreset(ffmpeg.AudioOptions{
+		Format:   req.Format,
+		Bitrate:  req.Bitrate,
+		Channels: req.Channels,
+	}, req.Preset)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	if opts.Format == "" && req.Output == "" {
+		opts.Format = ffmpeg.AudioMP3
+	}
+	if opts.Format, err = ffmpeg.AudioFormat(opts.Format, req.Output); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.SwapExt(input, opts.Format))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("audio", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.ExtractAudio(ctx, input, output, opts, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "audio extraction started"})
+}
+
+// handleConvertVideo re-encodes a downloaded file with a named preset
+func (s *Server) handleConvertVideo(c *gin.Context) {
+	var req ConvertVideoRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path and preset are required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	preset, err := ffmpeg.FindVideoPreset(req.Preset)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.VideoPath(input, preset))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("video", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		enc, err := ffmpeg.Transcode(ctx, input, output, preset, !req.Software, progressFn)
+		if err == nil {
+			log.Printf("Transcoded %s with %s", input, enc.Name)
+		}
+		return err
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "transcode started"})
+}
+
+// handleGetConvertPresets lists the video and audio presets
+func (s *Server) handleGetConvertPresets(c *gin.Context) {
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"video": ffmpeg.VideoPresets,
+			"audio": ffmpeg.AudioPresets,
+		},
+		Message: "presets retrieved",
+	})
+}
+
+// handleGetEncoders lists the hardware encoders that work on this machine and
+// the encoder each video preset uses
+func (s *Server) handleGetEncoders(c *gin.Context) {
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	hardware := ffmpeg.HardwareEncoders()
+	if hardware == nil {
+		hardware = []ffmpeg.Encoder{}
+	}
+	presets := make(map[string]ffmpeg.Encoder)
+	for _, p := range ffmpeg.VideoPresets {
+		enc, ok := ffmpeg.HardwareEncoder(p.Codec)
+		if !ok {
+			enc = ffmpeg.Encoder{Name: p.Encoder, Codec: p.Codec}
+		}
+		presets[p.Name] = enc
+	}
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"hardware": hardware, "presets": presets},
+		Message: fmt.Sprintf("%d hardware encoders available", len(hardware)),
+	})
+}
+
+// handleConvertTrim cuts a clip out of a downloaded file
+func (s *Server) handleConvertTrim(c *gin.Context) {
+	var req ConvertTrimRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	var start, end time.Duration
+	var err error
+	if req.Start != "" {
+		start, err = ffmpeg.ParseTimestamp(req.Start)
+	}
+	if err == nil && req.End != "" {
+		end, err = ffmpeg.ParseTimestamp(req.End)
+		if err == nil && end <= start {
+			err = fmt.Errorf("end must be after start")
+		}
+	}
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.ClipPath(input))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("trim", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.Trim(ctx, input, output, start, end, req.Reencode, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "trim started"})
+}
+
+// handleConvertAnimation turns a stretch of a downloaded video into an
+// animated GIF or WebP
+func (s *Server) handleConvertAnimation(c *gin.Context) {
+	var req ConvertAnimationRequest
+	if err := c.ShouldBindJSON(&req); err != nil || req.Duration < 0 || req.FPS < 0 || req.Width < 0 {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required; duration, fps, and width can't be negative"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	format := req.Format
+	if format == "" && req.Output == "" {
+		format = ffmpeg.AnimationGIF
+	}
+	format, err := ffmpeg.AnimationFormat(format, req.Output)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	opts := ffmpeg.AnimationOptions{
+		Duration: time.Duration(req.Duration * float64(time.Second)),
+		FPS:      req.FPS,
+		Width:    req.Width,
+	}
+	if req.Start != "" {
+		if opts.Start, err = ffmpeg.ParseTimestamp(req.Start); err != nil {
+			c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+			return
+		}
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.SwapExt(input, format))
+	if !ok {
+		return
+	}
+	if _, err := ffmpeg.AnimationFormat("", output); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	conv, err := s.startConversion("animation", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.Animate(ctx, input, output, opts, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: format + " export started"})
+}
+
+// handleConvertFrame saves a single frame of a downloaded video as an image
+func (s *Server) handleConvertFrame(c *gin.Context) {
+	var req ConvertFrameRequest
+	if err := c.ShouldBindJSON(&req); err != nil || req.Width < 0 {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required; width can't be negative"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	format := req.Format
+	if format == "" && req.Output == "" {
+		format = ffmpeg.FramePNG
+	}
+	format, err := ffmpeg.FrameFormat(format, req.Output)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+	var at time.Duration
+	if req.At != "" {
+		if at, err = ffmpeg.ParseTimestamp(req.At); err != nil {
+			c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+			return
+		}
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.FramePath(input, at, format))
+	if !ok {
+		return
+	}
+	if _, err := ffmpeg.FrameFormat("", output); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	conv, err := s.startConversion("frame", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.CaptureFrame(ctx, input, output, at, req.Width)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "frame capture started"})
+}
+
+// handleConvertSubtitles embeds subtitles in a downloaded video, as tracks or
+// burned in, pairing it with its sidecar subtitle files unless others are given
+func (s *Server) handleConvertSubtitles(c *gin.Context) {
+	var req ConvertSubtitlesRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	var tracks []ffmpeg.SubtitleTrack
+	for _, path := range req.Subtitles {
+		path, ok := s.mediaPath(c, path)
+		if !ok {
+			return
+		}
+		tracks = append(tracks, ffmpeg.SubtitleTrack{Path: path})
+	}
+	if len(tracks) == 0 {
+		sidecars, err := subtitle.Sidecars(input, subtitle.ParseLanguages(strings.Join(req.Languages, ",")))
+		if err != nil {
+			c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+			return
+		}
+		for _, sc := range sidecars {
+			tracks = append(tracks, ffmpeg.SubtitleTrack{Path: sc.Path, Language: sc.Language})
+		}
+	}
+	if len(tracks) == 0 {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "no subtitle files found next to the video"})
+		return
+	}
+
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.SubtitledPath(input, req.Burn))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("subtitles", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		if req.Burn {
+			return ffmpeg.BurnSubtitles(ctx, input, output, tracks[0].Path, progressFn)
+		}
+		return ffmpeg.EmbedSubtitles(ctx, input, output, tracks, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	message := fmt.Sprintf("embedding %d subtitle tracks", len(tracks))
+	if req.Burn {
+		message = "burning in " + filepath.Base(tracks[0].Path)
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: message})
+}
+
+// handleConvertRemux copies the streams of a downloaded file into another
+// container without re-encoding
+func (s *Server) handleConvertRemux(c *gin.Context) {
+	var req ConvertRemuxRequest
+	if err := c.ShouldBindJSON(&req); err != nil || (req.Container == "" && req.Output == "") {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path and container are required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	container, err := ffmpeg.RemuxContainer(req.Container, req.Output)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.SwapExt(input, container))
+	if !ok {
+		return
+	}
+	if _, err := ffmpeg.RemuxContainer("", output); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	// Codecs the container can't hold are reported now rather than as a
+	// failed conversion
+	streams, err := ffmpeg.ProbeStreams(c.Request.Context(), input)
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	if err := ffmpeg.CheckRemux(streams, container); err != nil {
+		c.JSON(http.StatusUnprocessableEntity, Response{Code: 422, Data: gin.H{"streams": streams}, Message: err.Error()})
+		return
+	}
+
+	conv, err := s.startConversion("remux", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.Remux(ctx, input, output, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "remux to " + container + " started"})
+}
+
+// handleConvertLoudness evens out the volume of a downloaded file
+func (s *Server) handleConvertLoudness(c *gin.Context) {
+	var req ConvertLoudnessRequest
+	if err := c.ShouldBindJSON(&req); err != nil || req.Target > 0 {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required; target is in negative LUFS, e.g. -16"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	target := req.Target
+	if target == 0 {
+		target = config.LoadOrDefault().Download.LoudnessTarget
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.NormalizedPath(input))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("loudness", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.NormalizeLoudness(ctx, input, output, target, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "loudness normalization started"})
+}
+
+// handleConvertWatermark overlays an image or text onto a downloaded video
+func (s *Server) handleConvertWatermark(c *gin.Context) {
+	var req ConvertWatermarkRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	opts := ffmpeg.WatermarkOptions{
+		Image:    req.Image,
+		Text:     req.Text,
+		Position: req.Position,
+		Opacity:  req.Opacity,
+		Margin:   req.Margin,
+		Width:    req.Width,
+		FontSize: req.FontSize,
+	}
+	if err := ffmpeg.ValidateWatermark(opts); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	if opts.Image != "" {
+		if opts.Image, ok = s.mediaPath(c, opts.Image); !ok {
+			return
+		}
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.WatermarkedPath(input))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("watermark", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.Watermark(ctx, input, output, opts, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "watermarking started"})
+}
+
+// handleConvertAdjust changes the playback speed and volume of a downloaded file
+func (s *Server) handleConvertAdjust(c *gin.Context) {
+	var req ConvertAdjustRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "path is required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	opts := ffmpeg.AdjustOptions{Speed: req.Speed, Gain: req.Gain}
+	if err := ffmpeg.ValidateAdjust(opts); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	input, ok := s.mediaPath(c, req.Path)
+	if !ok {
+		return
+	}
+	output, ok := s.conversionOutput(c, req.Output, ffmpeg.AdjustedPath(input, opts))
+	if !ok {
+		return
+	}
+
+	conv, err := s.startConversion("adjust", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		return ffmpeg.Adjust(ctx, input, output, opts, progressFn)
+	})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: conv, Message: "speed and volume adjustment started"})
+}
@@ -0,0 +1,240 @@
+package server
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// ConvertBatchRequest is the request body for POST /convert/batch
+type ConvertBatchRequest struct {
+	Paths  []string `json:"paths,omitempty"`           // files to convert, in order
+	Folder string   `json:"folder,omitempty"`          // converts the audio and video files directly in it; "." for the output directory
+	Preset string   `json:"preset" binding:"required"` // a video preset, or podcast or music to extract the audio
+	Format string   `json:"format,omitempty"`          // format of audio presets: mp3 (default), m4a, or opus
+}
+
+// ConversionBatch is a list of files converted one after another with the
+// same preset, started from POST /convert/batch. Each file is a conversion
+// with the batch's ID as its batch_id.
+type ConversionBatch struct {
+	ID        string    `json:"id"`
+	Preset    string    `json:"preset"`
+	Status    JobStatus `json:"status"`
+	Items     []string  `json:"items"`             // conversion IDs, in order
+	Current   string    `json:"current,omitempty"` // the item running now
+	Total     int       `json:"total"`
+	Completed int       `json:"completed"`
+	Failed    int       `json:"failed"`
+	Cancelled int       `json:"cancelled"`
+	Progress  float64   `json:"progress"` // percent over all items
+	CreatedAt time.Time `json:"created_at"`
+	UpdatedAt time.Time `json:"updated_at"`
+
+	items []*Conversion
+}
+
+// startBatch queues a conversion of each input with preset, as one batch
+func (s *Server) startBatch(preset ffmpeg.BatchPreset, inputs []string) (*ConversionBatch, error) {
+	id, err := generateJobID()
+	if err != nil {
+		return nil, err
+	}
+	now := time.Now()
+	batch := &ConversionBatch{
+		ID:        id,
+		Preset:    preset.Name,
+		Status:    ConvertQueued,
+		Total:     len(inputs),
+		CreatedAt: now,
+		UpdatedAt: now,
+	}
+	taken := make(map[string]bool)
+	for _, input := range inputs {
+		output := batchOutput(preset.OutputPath(input), taken)
+		conv, err := newConversion("batch", input, output, func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+			return preset.Convert(ctx, input, output, progressFn)
+		})
+		if err != nil {
+			return nil, err
+		}
+		conv.BatchID, conv.batch = batch.ID, batch
+		batch.Items = append(batch.Items, conv.ID)
+		batch.items = append(batch.items, conv)
+	}
+	limit := config.LoadOrDefault().Download.EffectiveMaxConversions()
+
+	s.convertMu.Lock()
+	defer s.convertMu.Unlock()
+	if s.convertBatches == nil {
+		s.convertBatches = make(map[string]*ConversionBatch)
+	}
+	s.convertBatches[batch.ID] = batch
+	for _, conv := range batch.items {
+		s.queueConversionLocked(conv)
+	}
+	s.dispatchConversionsLocked(limit)
+	snapshot := *batch
+	return &snapshot, nil
+}
+
+// batchOutput picks a free output path from def that no earlier item of the
+// batch took, since those outputs don't exist yet. "clip.webm" and
+// "clip.mkv" both default to "clip.mp4", for one.
+func batchOutput(def string, taken map[string]bool) string {
+	path := downloader.UniquePath(def)
+	ext := filepath.Ext(def)
+	for n := 1; taken[path]; n++ {
+		path = downloader.UniquePath(fmt.Sprintf("%s (%d)%s", strings.TrimSuffix(def, ext), n, ext))
+	}
+	taken[path] = true
+	return path
+}
+
+// publishBatchLocked tallies the items of batch and sends a snapshot of it to
+// event subscribers. Must be called with s.convertMu held.
+func (s *Server) publishBatchLocked(batch *ConversionBatch) {
+	batch.Current = ""
+	batch.Completed, batch.Failed, batch.Cancelled = 0, 0, 0
+	var progress float64
+	for _, conv := range batch.items {
+		switch conv.Status {
+		case ConvertRunning:
+			batch.Current = conv.ID
+			progress += conv.Progress
+		case ConvertCompleted:
+			batch.Completed++
+		case ConvertFailed:
+			batch.Failed++
+		case ConvertCancelled:
+			batch.Cancelled++
+		}
+	}
+	finished := batch.Completed + batch.Failed + batch.Cancelled
+	batch.Progress = (progress + float64(finished)*100) / float64(batch.Total)
+
+	switch {
+	case finished < batch.Total && (batch.Current != "" || finished > 0):
+		batch.Status = ConvertRunning
+	case finished < batch.Total:
+		batch.Status = ConvertQueued
+	case batch.Failed > 0:
+		batch.Status = ConvertFailed
+	case batch.Cancelled > 0:
+		batch.Status = ConvertCancelled
+	default:
+		batch.Status = ConvertCompleted
+	}
+	batch.UpdatedAt = time.Now()
+	snapshot := *batch
+	s.jobQueue.events.Publish(Event{Type: EventConvertBatchUpdated, Data: &snapshot})
+}
+
+// handleConvertBatch converts a list of downloaded files, or the media files
+// of a folder, with one preset
+func (s *Server) handleConvertBatch(c *gin.Context) {
+	var req ConvertBatchRequest
+	if err := c.ShouldBindJSON(&req); err != nil || (len(req.Paths) == 0 && req.Folder == "") {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "preset and paths or folder are required"})
+		return
+	}
+	if !ffmpeg.Available() {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "ffmpeg not found in PATH"})
+		return
+	}
+	preset, err := ffmpeg.FindBatchPreset(req.Preset, req.Format)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	var inputs []string
+	if req.Folder != "" {
+		folder := s.outputDir
+		if filepath.Clean(req.Folder) != "." {
+			var ok bool
+			if folder, ok = s.outputPathIn(c, req.Folder); !ok {
+				return
+			}
+		}
+		files, err := ffmpeg.MediaFiles(folder)
+		if err != nil {
+			c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "folder not found"})
+			return
+		}
+		inputs = files
+	}
+	for _, path := range req.Paths {
+		path, ok := s.mediaPath(c, path)
+		if !ok {
+			return
+		}
+		inputs = append(inputs, path)
+	}
+	if len(inputs) == 0 {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "no audio or video files to convert"})
+		return
+	}
+
+	batch, err := s.startBatch(preset, inputs)
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: batch, Message: fmt.Sprintf("converting %d files with %s", len(inputs), preset.Name)})
+}
+
+// handleGetConversionBatches lists the batches of this session, newest first
+func (s *Server) handleGetConversionBatches(c *gin.Context) {
+	s.convertMu.Lock()
+	list := make([]ConversionBatch, 0, len(s.convertBatches))
+	for _, batch := range s.convertBatches {
+		list = append(list, *batch)
+	}
+	s.convertMu.Unlock()
+
+	sort.Slice(list, func(i, j int) bool {
+		return list[i].CreatedAt.After(list[j].CreatedAt)
+	})
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"batches": list},
+		Message: fmt.Sprintf("%d batches", len(list)),
+	})
+}
+
+// handleCancelConversionBatch cancels the items of a batch that haven't
+// finished; those that have are kept
+func (s *Server) handleCancelConversionBatch(c *gin.Context) {
+	s.convertMu.Lock()
+	batch, ok := s.convertBatches[c.Param("id")]
+	if !ok {
+		s.convertMu.Unlock()
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "batch not found"})
+		return
+	}
+	cancelled := 0
+	for _, conv := range batch.items {
+		if s.cancelConversionLocked(conv) {
+			cancelled++
+		}
+	}
+	if cancelled == 0 {
+		s.convertMu.Unlock()
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "batch already " + string(batch.Status)})
+		return
+	}
+	snapshot := *batch
+	s.convertMu.Unlock()
+
+	c.JSON(http.StatusOK, Response{Code: 200, Data: &snapshot, Message: fmt.Sprintf("cancelled %d conversions", cancelled)})
+}
@@ -0,0 +1,199 @@
+package server
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+)
+
+// EventType identifies the kind of server event
+type EventType string
+
+const (
+	EventDownload       EventType = "download" // data: DownloadEvent, for every change to a job
+	EventJobRemoved     EventType = "job_removed"
+	EventQueuePaused    EventType = "queue_paused"
+	EventQueueResumed   EventType = "queue_resumed"
+	EventQueueReordered EventType = "queue_reordered" // data: {"ids": queued job IDs in their new order}
+
+	EventQueueAfterCountdown EventType = "queue_after_countdown" // data: {"action", "remaining": seconds}
+	EventQueueAfterCancelled EventType = "queue_after_cancelled" // data: {"action", "reason": "cancelled" or "jobs_queued"}
+	EventQueueAfterFailed    EventType = "queue_after_failed"    // data: {"action", "error"}
+
+	EventUpdatePending   EventType = "update_pending"
+	EventUpdateInstalled EventType = "update_installed"
+	EventUpdateFailed    EventType = "update_failed"
+
+	EventExtractResult EventType = "extract_result"
+	EventExtractDone   EventType = "extract_done"
+
+	EventDownloadsSummary EventType = "downloads_summary" // data: DownloadsSummary, every second while jobs are active
+
+	EventConvertUpdated      EventType = "convert_updated"       // data: Conversion
+	EventConvertBatchUpdated EventType = "convert_batch_updated" // data: ConversionBatch, with each change of an item
+
+	EventFFmpegProgress  EventType = "ffmpeg_progress"  // data: FFmpegInstall, sent at most every progressEventInterval
+	EventFFmpegInstalled EventType = "ffmpeg_installed" // data: FFmpegInfo
+	EventFFmpegFailed    EventType = "ffmpeg_failed"    // data: {"error"}
+
+	EventLoginSuccess    EventType = "login_success"     // data: {"site", "cookies": count saved}
+	EventLoginFailed     EventType = "login_failed"      // data: {"site", "error"}
+	EventXHSLoginSuccess EventType = "xhs_login_success" // login_success of xiaohongshu
+	EventXHSLoginFailed  EventType = "xhs_login_failed"  // login_failed of xiaohongshu
+
+	EventAuthStatusChanged EventType = "auth_status_changed" // data: auth.Status, on login, logout, profile switch, or expiry
+)
+
+// DownloadEventKind is what happened to a job in a download event
+type DownloadEventKind string
+
+const (
+	DownloadQueued       DownloadEventKind = "queued"
+	DownloadStarted      DownloadEventKind = "started"
+	DownloadProgress     DownloadEventKind = "progress"      // sent at most every progressEventInterval
+	DownloadStageChanged DownloadEventKind = "stage_changed" // the job moved on to DownloadEvent.Stage
+	DownloadCompleted    DownloadEventKind = "completed"
+	DownloadFailed       DownloadEventKind = "failed"
+	DownloadCancelled    DownloadEventKind = "cancelled"
+	DownloadUpdated      DownloadEventKind = "updated" // tags, note, priority, or schedule changed
+)
+
+// DownloadEvent is the data of a download event: what happened and the job
+// as it is afterwards. Every change to a job is sent as exactly one of them,
+// in order, so clients can follow a job without polling.
+type DownloadEvent struct {
+	Kind  DownloadEventKind `json:"kind"`
+	Stage string            `json:"stage,omitempty"` // the job's step while it's active, see jobStep
+	Job   *Job              `json:"job"`
+}
+
+// Topic returns the subscription topic an event type belongs to
+// (e.g., "job_removed" -> "job", "queue_paused" -> "queue")
+func (t EventType) Topic() string {
+	topic, _, _ := strings.Cut(string(t), "_")
+	return topic
+}
+
+// eventKeepAliveInterval is how often an idle event stream sends a comment
+// so proxies don't close the connection
+const eventKeepAliveInterval = 15 * time.Second
+
+// Event is a notification about a job or queue state change
+type Event struct {
+	Type  EventType   `json:"type"`
+	JobID string      `json:"job_id,omitempty"`
+	Data  interface{} `json:"data,omitempty"`
+	Time  time.Time   `json:"time"`
+}
+
+// EventBus fans out events to all subscribers.
+// Publishing never blocks and never skips an event: a subscriber whose buffer
+// is full is unsubscribed, closing its channel, so its client reconnects and
+// reloads the state instead of silently missing changes.
+type EventBus struct {
+	mu   sync.Mutex
+	subs map[chan Event]struct{}
+}
+
+// NewEventBus creates an empty event bus
+func NewEventBus() *EventBus {
+	return &EventBus{
+		subs: make(map[chan Event]struct{}),
+	}
+}
+
+// Subscribe returns a channel that receives all future events
+func (b *EventBus) Subscribe() chan Event {
+	ch := make(chan Event, 64)
+	b.mu.Lock()
+	b.subs[ch] = struct{}{}
+	b.mu.Unlock()
+	return ch
+}
+
+// Unsubscribe stops delivery to ch and closes it
+func (b *EventBus) Unsubscribe(ch chan Event) {
+	b.mu.Lock()
+	if _, ok := b.subs[ch]; ok {
+		delete(b.subs, ch)
+		close(ch)
+	}
+	b.mu.Unlock()
+}
+
+// Publish sends an event to every subscriber
+func (b *EventBus) Publish(evt Event) {
+	if evt.Time.IsZero() {
+		evt.Time = time.Now()
+	}
+
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	for ch := range b.subs {
+		select {
+		case ch <- evt:
+		default:
+			// Subscriber is not keeping up, disconnect it rather than let it
+			// miss this event
+			delete(b.subs, ch)
+			close(ch)
+		}
+	}
+}
+
+// handleEvents streams server events to the client using Server-Sent Events.
+// Every connected client (browser tab, script) receives the same events, so jobs
+// are never tied to the client that created them and survive it disconnecting.
+// Optional filters:
+//   - topics: comma-separated topics (e.g., "download,queue"), the part of the
+//     event type before the first "_": "download" is every change to a job,
+//     "job" only job_removed, "downloads" the downloads_summary
+//   - job_id: only download and job_removed events for a single job (events
+//     not about a job are still delivered)
+func (s *Server) handleEvents(c *gin.Context) {
+	topics := make(map[string]bool)
+	for _, topic := range strings.Split(c.Query("topics"), ",") {
+		if topic = strings.TrimSpace(topic); topic != "" {
+			topics[topic] = true
+		}
+	}
+	jobID := c.Query("job_id")
+
+	ch := s.jobQueue.events.Subscribe()
+	defer s.jobQueue.events.Unsubscribe(ch)
+
+	c.Header("Content-Type", "text/event-stream")
+	c.Header("Cache-Control", "no-cache")
+	c.Header("Connection", "keep-alive")
+	c.Header("X-Accel-Buffering", "no")
+
+	keepAlive := time.NewTicker(eventKeepAliveInterval)
+	defer keepAlive.Stop()
+
+	c.Stream(func(w io.Writer) bool {
+		select {
+		case <-c.Request.Context().Done():
+			return false
+		case evt, ok := <-ch:
+			if !ok {
+				return false
+			}
+			if len(topics) > 0 && !topics[evt.Type.Topic()] {
+				return true
+			}
+			if jobID != "" && evt.JobID != "" && evt.JobID != jobID {
+				return true
+			}
+			c.SSEvent(string(evt.Type), evt)
+			return true
+		case <-keepAlive.C:
+			fmt.Fprint(w, ": keep-alive\n\n")
+			return true
+		}
+	})
+}
@@ -0,0 +1,345 @@
+package server
+
+import (
+	"fmt"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+const (
+	// defaultExtractParallelism is how many URLs a batch extracts at once
+	defaultExtractParallelism = 4
+
+	// maxExtractParallelism caps the requested parallelism
+	maxExtractParallelism = 16
+)
+
+// ExtractRequest is the request body for POST /extract
+type ExtractRequest struct {
+	URL     string `json:"url" binding:"required"`
+	Refresh bool   `json:"refresh,omitempty"` // bypass the cache
+}
+
+// SelectFormatRequest is the request body for POST /extract/select
+type SelectFormatRequest struct {
+	URL     string `json:"url" binding:"required"`
+	Format  string `json:"format"`            // yt-dlp style selector, default "best"
+	Refresh bool   `json:"refresh,omitempty"` // bypass the cache
+}
+
+// ExtractBatchRequest is the request body for POST /extract/batch
+type ExtractBatchRequest struct {
+	URLs        []string `json:"urls" binding:"required"`
+	Parallelism int      `json:"parallelism,omitempty"` // default 4, max 16
+	Refresh     bool     `json:"refresh,omitempty"`     // bypass the cache
+}
+
+// MediaInfo is the JSON view of extracted media
+type MediaInfo struct {
+	ID            string                `json:"id"`
+	Title         string                `json:"title"`
+	Uploader      string                `json:"uploader,omitempty"`
+	Description   string                `json:"description,omitempty"`
+	Published     *time.Time            `json:"published,omitempty"`
+	Type          extractor.MediaType   `json:"type"`
+	Kind          string                `json:"kind"` // video, audio, image, multi_video, playlist
+	Duration      int                   `json:"duration,omitempty"`
+	Thumbnails    []extractor.Thumbnail `json:"thumbnails,omitempty"`
+	Formats       []FormatInfo          `json:"formats,omitempty"`
+	Subtitles     []extractor.Subtitle  `json:"subtitles,omitempty"`
+	Chapters      []extractor.Chapter   `json:"chapters,omitempty"`
+	Images        int                   `json:"images,omitempty"`
+	Videos        []MediaInfo           `json:"videos,omitempty"`
+	Entries       []EntryInfo           `json:"entries,omitempty"`
+	Cached        bool                  `json:"cached,omitempty"` // served from the extraction cache
+	DRM           bool                  `json:"drm_protected"`
+	DRMReason     string                `json:"drm_reason,omitempty"` // why the media can't be downloaded
+	Locked        []string              `json:"locked_qualities,omitempty"`
+	LockedNote    string                `json:"locked_note,omitempty"`    // how to unlock the locked qualities
+	SingleURL     string                `json:"single_url,omitempty"`     // a collection's expanded video on its own
+	RedirectChain []string              `json:"redirect_chain,omitempty"` // short link redirects, input URL first
+}
+
+// FormatInfo describes one downloadable video format
+type FormatInfo struct {
+	Quality       string `json:"quality"`
+	Ext           string `json:"ext"`
+	Width         int    `json:"width,omitempty"`
+	Height        int    `json:"height,omitempty"`
+	Bitrate       int    `json:"bitrate,omitempty"` // kbps
+	SeparateAudio bool   `json:"separate_audio,omitempty"`
+}
+
+// EntryInfo describes one playlist entry
+type EntryInfo struct {
+	Title     string     `json:"title"`
+	URL       string     `json:"url,omitempty"`
+	Duration  int        `json:"duration,omitempty"`
+	Thumbnail string     `json:"thumbnail,omitempty"`
+	Published *time.Time `json:"published,omitempty"`
+}
+
+// newMediaInfo converts extracted media to its JSON view
+func newMediaInfo(media extractor.Media) MediaInfo {
+	info := MediaInfo{
+		ID:            media.GetID(),
+		Title:         media.GetTitle(),
+		Uploader:      media.GetUploader(),
+		Type:          media.Type(),
+		Kind:          string(media.Type()),
+		Thumbnails:    extractor.Thumbnails(media),
+		DRMReason:     extractor.DRMReason(media),
+		RedirectChain: extractor.RedirectChain(media),
+	}
+	info.DRM = info.DRMReason != ""
+
+	switch m := media.(type) {
+	case *extractor.VideoMedia:
+		info.Description = m.Description
+		info.Published = optionalTime(m.Published)
+		info.Duration = m.Duration
+		info.Subtitles = m.Subtitles
+		info.Chapters = m.Chapters
+		if m.Locked != nil {
+			info.Locked = m.Locked.Qualities
+			info.LockedNote = m.Locked.Note()
+		}
+		for _, f := range m.Formats {
+			info.Formats = append(info.Formats, FormatInfo{
+				Quality:       f.QualityLabel(),
+				Ext:           f.Ext,
+				Width:         f.Width,
+				Height:        f.Height,
+				Bitrate:       f.Bitrate,
+				SeparateAudio: f.AudioURL != "",
+			})
+		}
+	case *extractor.YouTubeDirectDownload:
+		info.Duration = m.Duration
+		info.Subtitles = m.Subtitles
+		info.Chapters = m.Chapters
+	case *extractor.AudioMedia:
+		info.Description = m.Description
+		info.Published = optionalTime(m.Published)
+		info.Duration = m.Duration
+		info.Formats = []FormatInfo{{Quality: "audio", Ext: m.Ext}}
+	case *extractor.ImageMedia:
+		info.Images = len(m.Images)
+	case *extractor.MultiVideoMedia:
+		info.Kind = "multi_video"
+		for _, v := range m.Videos {
+			info.Videos = append(info.Videos, newMediaInfo(v))
+		}
+	case *extractor.PlaylistMedia:
+		info.Kind = "playlist"
+		info.SingleURL = m.SingleURL
+		for _, e := range m.Entries {
+			entry := EntryInfo{Title: e.Title, URL: e.URL, Duration: e.Duration, Thumbnail: e.Thumbnail, Published: optionalTime(e.Published)}
+			info.Entries = append(info.Entries, entry)
+		}
+	}
+	return info
+}
+
+// optionalTime is t, or nil if it's zero so it's left out of the JSON
+func optionalTime(t time.Time) *time.Time {
+	if t.IsZero() {
+		return nil
+	}
+	return &t
+}
+
+// extractInfo resolves short links and extracts a URL's media info. Results
+// are cached per canonical URL for the extractor's TTL unless refresh is set.
+func (s *Server) extractInfo(rawURL string, refresh bool) (*MediaInfo, error) {
+	media, chain, cached, err := s.extractCached(rawURL, refresh)
+	if err != nil {
+		return nil, err
+	}
+	info := newMediaInfo(media)
+	// A cached result may have been reached through a different short link
+	info.RedirectChain = chain
+	info.Cached = cached
+	return &info, nil
+}
+
+// extractCached extracts a URL through the extraction cache, returning the
+// short link redirects that led to it and whether it came from the cache
+func (s *Server) extractCached(rawURL string, refresh bool) (extractor.Media, []string, bool, error) {
+	target, err := extractor.NormalizeURL(rawURL)
+	if err != nil {
+		return nil, nil, false, err
+	}
+	target, chain := extractor.ResolveShortURL(target)
+
+	if !refresh {
+		if media, ok := s.extractCache.Get(target); ok {
+			return media, chain, true, nil
+		}
+	}
+
+	ext := s.resolveExtractor(target)
+	media, err := extractor.Extract(ext, target, nil)
+	if err != nil {
+		return nil, nil, false, err
+	}
+	extractor.SetRedirectChain(media, chain)
+	s.extractCache.Put(target, media, extractor.CacheTTL(ext))
+	return media, chain, false, nil
+}
+
+// handleExtract returns the media info of a single URL without downloading it
+func (s *Server) handleExtract(c *gin.Context) {
+	var req ExtractRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body: url is required"})
+		return
+	}
+
+	info, err := s.extractInfo(req.URL, req.Refresh)
+	if err != nil {
+		c.JSON(http.StatusUnprocessableEntity, Response{Code: 422, Data: nil, Message: fmt.Sprintf("extraction failed: %v", err)})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: info, Message: "media extracted"})
+}
+
+// handleExtractBatch starts extracting several URLs concurrently. It returns a
+// batch ID right away; each result is published as an extract_result event and
+// extract_done follows the last one.
+func (s *Server) handleExtractBatch(c *gin.Context) {
+	var req ExtractBatchRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body: urls array is required"})
+		return
+	}
+
+	var urls []string
+	for _, u := range req.URLs {
+		u = strings.TrimSpace(u)
+		// Skip empty lines and comments
+		if u != "" && !strings.HasPrefix(u, "#") {
+			urls = append(urls, u)
+		}
+	}
+	if len(urls) == 0 {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "urls array cannot be empty"})
+		return
+	}
+
+	parallelism := req.Parallelism
+	if parallelism <= 0 {
+		parallelism = defaultExtractParallelism
+	}
+	parallelism = min(parallelism, maxExtractParallelism, len(urls))
+
+	batchID, err := generateJobID()
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+
+	go s.runExtractBatch(batchID, urls, parallelism, req.Refresh)
+
+	c.JSON(http.StatusAccepted, Response{
+		Code:    202,
+		Data:    gin.H{"batch_id": batchID, "total": len(urls), "parallelism": parallelism},
+		Message: fmt.Sprintf("extracting %d URLs", len(urls)),
+	})
+}
+
+// runExtractBatch extracts urls with at most parallelism at a time and
+// publishes each result as it finishes
+func (s *Server) runExtractBatch(batchID string, urls []string, parallelism int, refresh bool) {
+	events := s.jobQueue.events
+	sem := make(chan struct{}, parallelism)
+	var wg sync.WaitGroup
+	var mu sync.Mutex
+	failed := 0
+
+	for i, u := range urls {
+		wg.Add(1)
+		sem <- struct{}{}
+		go func(index int, rawURL string) {
+			defer wg.Done()
+			defer func() { <-sem }()
+
+			result := gin.H{"batch_id": batchID, "index": index, "url": rawURL}
+			info, err := s.extractInfo(rawURL, refresh)
+			if err != nil {
+				result["error"] = err.Error()
+				mu.Lock()
+				failed++
+				mu.Unlock()
+			} else {
+				result["media"] = info
+			}
+			events.Publish(Event{Type: EventExtractResult, Data: result})
+		}(i, u)
+	}
+
+	wg.Wait()
+	events.Publish(Event{Type: EventExtractDone, Data: gin.H{
+		"batch_id":  batchID,
+		"total":     len(urls),
+		"succeeded": len(urls) - failed,
+		"failed":    failed,
+	}})
+}
+
+// handleClearExtractCache drops all cached extraction results
+func (s *Server) handleClearExtractCache(c *gin.Context) {
+	n := s.extractCache.Clear()
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"cleared": n},
+		Message: fmt.Sprintf("%d cached results cleared", n),
+	})
+}
+
+// handleSelectFormat picks one of a URL's formats with a yt-dlp style selector
+// such as "bestvideo[height<=1080]+bestaudio/best". The index refers to the
+// formats returned by POST /extract.
+func (s *Server) handleSelectFormat(c *gin.Context) {
+	var req SelectFormatRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body: url is required"})
+		return
+	}
+
+	media, _, _, err := s.extractCached(req.URL, req.Refresh)
+	if err != nil {
+		c.JSON(http.StatusUnprocessableEntity, Response{Code: 422, Data: nil, Message: fmt.Sprintf("extraction failed: %v", err)})
+		return
+	}
+	video, ok := media.(*extractor.VideoMedia)
+	if !ok {
+		c.JSON(http.StatusUnprocessableEntity, Response{Code: 422, Data: nil, Message: "format selection needs a single video"})
+		return
+	}
+
+	selected, err := extractor.SelectFormat(video.Formats, req.Format)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	// SelectFormat returns a pointer into video.Formats
+	index := 0
+	for i := range video.Formats {
+		if &video.Formats[i] == selected {
+			index = i
+		}
+	}
+	info := newMediaInfo(video)
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"index": index, "format": info.Formats[index]},
+		Message: "format selected",
+	})
+}
@@ -0,0 +1,125 @@
+package server
+
+import (
+	"context"
+	"log"
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// ffmpegInstallTimeout bounds downloading the managed ffmpeg build
+const ffmpegInstallTimeout = 15 * time.Minute
+
+// FFmpegInfo is the ffmpeg the server runs, as GET /ffmpeg reports it
+type FFmpegInfo struct {
+	Available   bool           `json:"available"`
+	Path        string         `json:"path,omitempty"`
+	Version     string         `json:"version,omitempty"`
+	Managed     bool           `json:"managed"`           // downloaded by POST /ffmpeg/install rather than in PATH
+	Installable bool           `json:"installable"`       // POST /ffmpeg/install has a build for this machine
+	Install     *FFmpegInstall `json:"install,omitempty"` // set while a download is running
+}
+
+// FFmpegInstall is the progress of downloading the managed ffmpeg build
+type FFmpegInstall struct {
+	Binary     string `json:"binary"`     // ffmpeg or ffprobe
+	Downloaded int64  `json:"downloaded"` // bytes of the compressed build
+	Total      int64  `json:"total"`      // 0 if unknown
+
+	lastEventAt time.Time
+}
+
+// ffmpegInfo looks up the ffmpeg in use and its version
+func (s *Server) ffmpegInfo(ctx context.Context) FFmpegInfo {
+	info := FFmpegInfo{Available: ffmpeg.Available(), Installable: ffmpeg.Installable()}
+	if info.Available {
+		info.Path = ffmpeg.Binary()
+		info.Managed = ffmpeg.Managed()
+		if version, err := ffmpeg.Version(ctx); err == nil {
+			info.Version = version
+		}
+	}
+
+	s.ffmpegMu.Lock()
+	if s.ffmpegInstall != nil {
+		install := *s.ffmpegInstall
+		info.Install = &install
+	}
+	s.ffmpegMu.Unlock()
+	return info
+}
+
+// handleGetFFmpeg reports whether ffmpeg is available, where, and its version
+func (s *Server) handleGetFFmpeg(c *gin.Context) {
+	ctx, cancel := context.WithTimeout(c.Request.Context(), 10*time.Second)
+	defer cancel()
+
+	c.JSON(http.StatusOK, Response{Code: 200, Data: s.ffmpegInfo(ctx), Message: "ffmpeg info retrieved"})
+}
+
+// handleInstallFFmpeg downloads a static ffmpeg build into the config
+// directory in the background, for machines without ffmpeg in PATH
+func (s *Server) handleInstallFFmpeg(c *gin.Context) {
+	if !ffmpeg.Installable() {
+		c.JSON(http.StatusNotImplemented, Response{Code: 501, Data: nil, Message: "no ffmpeg download for this machine, install ffmpeg with its package manager"})
+		return
+	}
+	if ffmpeg.Available() && !ffmpeg.Managed() {
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "ffmpeg is already installed at " + ffmpeg.Binary()})
+		return
+	}
+
+	s.ffmpegMu.Lock()
+	if s.ffmpegInstall != nil {
+		s.ffmpegMu.Unlock()
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "ffmpeg is already being downloaded"})
+		return
+	}
+	s.ffmpegInstall = &FFmpegInstall{Binary: "ffmpeg"}
+	s.ffmpegMu.Unlock()
+
+	go s.installFFmpeg()
+
+	c.JSON(http.StatusAccepted, Response{Code: 202, Data: nil, Message: "ffmpeg download started"})
+}
+
+// installFFmpeg downloads the managed ffmpeg build, publishing its progress
+func (s *Server) installFFmpeg() {
+	events := s.jobQueue.events
+
+	ctx, cancel := context.WithTimeout(context.Background(), ffmpegInstallTimeout)
+	defer cancel()
+
+	path, err := ffmpeg.Install(ctx, func(name string, downloaded, total int64) {
+		s.ffmpegMu.Lock()
+		install := s.ffmpegInstall
+		install.Binary, install.Downloaded, install.Total = name, downloaded, total
+		now := time.Now()
+		publish := now.Sub(install.lastEventAt) >= progressEventInterval || downloaded == total
+		if publish {
+			install.lastEventAt = now
+		}
+		progress := *install
+		s.ffmpegMu.Unlock()
+
+		if publish {
+			events.Publish(Event{Type: EventFFmpegProgress, Data: progress})
+		}
+	})
+
+	s.ffmpegMu.Lock()
+	s.ffmpegInstall = nil
+	s.ffmpegMu.Unlock()
+
+	if err != nil {
+		log.Printf("ffmpeg download failed: %v", err)
+		events.Publish(Event{Type: EventFFmpegFailed, Data: gin.H{"error": err.Error()}})
+		return
+	}
+	log.Printf("Installed ffmpeg at %s", path)
+	go ffmpeg.HardwareEncoders()
+	events.Publish(Event{Type: EventFFmpegInstalled, Data: s.ffmpegInfo(ctx)})
+}
@@ -0,0 +1,27 @@
+package server
+
+import (
+	"context"
+	"path/filepath"
+
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/ftp"
+	"github.com/guiyumin/vget/internal/core/sanitize"
+)
+
+// downloadFTP downloads candidate, an ftp:// or sftp:// URL, for the job of url
+func (s *Server) downloadFTP(ctx context.Context, url, candidate string, opts DownloadOptions, progressFn func(downloaded, total int64)) error {
+	reader, size, err := ftp.Open(ctx, candidate)
+	if err != nil {
+		return err
+	}
+
+	name := opts.Filename
+	if name == "" {
+		name = ftp.Filename(candidate)
+	}
+	outputPath := freeOutputPath(filepath.Join(s.outputDir, sanitize.Filename(name)), opts.OnDuplicate)
+	s.updateJobFilename(opts.JobID, outputPath)
+
+	return downloader.SaveReader(ctx, reader, size, outputPath, progressFn)
+}
@@ -0,0 +1,359 @@
+package server
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+	"github.com/guiyumin/vget/internal/core/history"
+	"github.com/guiyumin/vget/internal/core/opener"
+)
+
+// Thumbnails captured for the history view
+const (
+	historyThumbnailWidth   = 320
+	historyThumbnailTimeout = 30 * time.Second
+)
+
+// HistoryAnnotateRequest is the request body for PATCH /history/:id.
+// Omitted fields are left unchanged.
+type HistoryAnnotateRequest struct {
+	Tags []string `json:"tags"`
+	Note *string  `json:"note"`
+}
+
+// recordHistory saves a finished job (completed, failed, or cancelled) to history
+func (jq *JobQueue) recordHistory(id string) {
+	if jq.history == nil {
+		return
+	}
+
+	job := jq.GetJob(id)
+	if job == nil {
+		return
+	}
+	switch job.Status {
+	case JobStatusCompleted, JobStatusFailed, JobStatusCancelled:
+	default:
+		return
+	}
+
+	entry := history.Entry{
+		ID:         job.ID,
+		URL:        job.URL,
+		Title:      job.title,
+		Site:       job.extractor,
+		Filename:   job.Filename,
+		Files:      job.OutputFiles(),
+		Status:     string(job.Status),
+		Error:      job.Error,
+		Size:       job.Total,
+		Duration:   job.duration,
+		Tags:       job.Tags,
+		Note:       job.Note,
+		CreatedAt:  job.CreatedAt,
+		FinishedAt: time.Now(),
+	}
+	if len(entry.Files) > 0 {
+		first := entry.Files[0]
+		if entry.Title == "" {
+			entry.Title = strings.TrimSuffix(filepath.Base(first), filepath.Ext(first))
+		}
+		if info, err := os.Stat(first); err == nil && entry.Size <= 0 {
+			entry.Size = info.Size()
+		}
+	}
+
+	if job.Status == JobStatusCompleted {
+		entry.SHA256 = history.Checksums(&entry)
+		entry.Thumbnail = jq.captureThumbnail(&entry)
+	}
+
+	if err := jq.history.Add(entry); err != nil {
+		log.Printf("Job %s: failed to save history: %v", id, err)
+	}
+}
+
+// captureThumbnail saves a frame of the entry's first file for the history
+// view, reporting whether it did. Files without video, and any file when
+// ffmpeg isn't installed, get none.
+func (jq *JobQueue) captureThumbnail(entry *history.Entry) bool {
+	files := entry.OutputFiles()
+	if len(files) == 0 || !ffmpeg.Available() {
+		return false
+	}
+	output := jq.history.ThumbnailPath(entry.ID)
+	if err := os.MkdirAll(filepath.Dir(output), 0755); err != nil {
+		return false
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), historyThumbnailTimeout)
+	defer cancel()
+	// A tenth of the way in skips intros and fades from black
+	at := time.Duration(entry.Duration) * time.Second / 10
+	err := ffmpeg.CaptureFrame(ctx, files[0], output, at, historyThumbnailWidth)
+	if err != nil && at > 0 && ctx.Err() == nil {
+		err = ffmpeg.CaptureFrame(ctx, files[0], output, 0, historyThumbnailWidth)
+	}
+	return err == nil
+}
+
+// annotateJob updates the tags and/or note of a job still in the queue
+func (jq *JobQueue) annotateJob(id string, tags []string, note *string) bool {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	job, ok := jq.jobs[id]
+	if !ok {
+		return false
+	}
+	if tags != nil {
+		job.Tags = history.NormalizeTags(tags)
+	}
+	if note != nil {
+		job.Note = strings.TrimSpace(*note)
+	}
+	job.UpdatedAt = time.Now()
+	jq.publishJobLocked(job)
+	return true
+}
+
+// HistoryOpenRequest is the request body for POST /history/:id/open
+type HistoryOpenRequest struct {
+	Action string `json:"action"` // open (default), reveal, play
+}
+
+// historyQuery builds a history query from ?q=, ?tag= (repeatable or comma-separated),
+// ?site=, ?status=, ?from= and ?to= (YYYY-MM-DD, both inclusive), and ?limit=
+func historyQuery(c *gin.Context) history.Query {
+	q := history.Query{
+		Text:   strings.TrimSpace(c.Query("q")),
+		Site:   strings.TrimSpace(c.Query("site")),
+		Status: strings.TrimSpace(c.Query("status")),
+	}
+	for _, value := range c.QueryArray("tag") {
+		q.Tags = append(q.Tags, strings.Split(value, ",")...)
+	}
+	q.Tags = history.NormalizeTags(q.Tags)
+	if from, err := time.ParseInLocation(time.DateOnly, c.Query("from"), time.Local); err == nil {
+		q.Since = from
+	}
+	if to, err := time.ParseInLocation(time.DateOnly, c.Query("to"), time.Local); err == nil {
+		q.Until = to.AddDate(0, 0, 1)
+	}
+	if limit, err := strconv.Atoi(c.Query("limit")); err == nil && limit > 0 {
+		q.Limit = limit
+	}
+	return q
+}
+
+func (s *Server) handleGetHistory(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+
+	entries, err := s.jobQueue.history.Search(historyQuery(c))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	tags, _ := s.jobQueue.history.Tags()
+	sites, _ := s.jobQueue.history.Sites()
+
+	if entries == nil {
+		entries = []history.Entry{}
+	}
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"entries": entries,
+			"tags":    tags,
+			"sites":   sites,
+		},
+		Message: fmt.Sprintf("%d entries found", len(entries)),
+	})
+}
+
+// handleAnnotate sets tags and/or a note on a job and its history entry
+func (s *Server) handleAnnotate(c *gin.Context) {
+	id := c.Param("id")
+
+	var req HistoryAnnotateRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body"})
+		return
+	}
+
+	// Jobs still in the queue carry their tags into history when they finish
+	inQueue := s.jobQueue.annotateJob(id, req.Tags, req.Note)
+
+	var entry *history.Entry
+	if s.jobQueue.history != nil {
+		if existing, _ := s.jobQueue.history.Get(id); existing != nil {
+			var err error
+			entry, err = s.jobQueue.history.Annotate(id, req.Tags, req.Note)
+			if err != nil {
+				c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+				return
+			}
+		}
+	}
+
+	if !inQueue && entry == nil {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "job or history entry not found"})
+		return
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"id": id, "entry": entry},
+		Message: "annotations updated",
+	})
+}
+
+// handleOpenHistory opens the file of a history entry on the server machine,
+// or shows it in the file manager
+func (s *Server) handleOpenHistory(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+	if config.IsRunningInDocker() {
+		c.JSON(http.StatusNotImplemented, Response{Code: 501, Data: nil, Message: "files can't be opened on a server running in Docker"})
+		return
+	}
+
+	var req HistoryOpenRequest
+	if err := c.ShouldBindJSON(&req); err != nil && !errors.Is(err, io.EOF) {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "invalid request body"})
+		return
+	}
+	action := opener.ActionOpen
+	if req.Action != "" {
+		var err error
+		if action, err = opener.ParseAction(req.Action); err != nil {
+			c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+			return
+		}
+	}
+
+	entry, err := s.jobQueue.history.Get(c.Param("id"))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	if entry == nil || len(entry.OutputFiles()) == 0 {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "history entry has no file"})
+		return
+	}
+
+	// Image entries have several output files; open the first one
+	path := entry.OutputFiles()[0]
+	if _, err := os.Stat(path); err != nil {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "file no longer exists: " + path})
+		return
+	}
+	if err := opener.Run(action, path); err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"id": entry.ID, "path": path, "action": action},
+		Message: fmt.Sprintf("%s %s", action, filepath.Base(path)),
+	})
+}
+
+// handleVerifyHistory hashes the files of a history entry again and records
+// whether they are intact, changed, or gone
+func (s *Server) handleVerifyHistory(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+
+	entry, err := s.jobQueue.history.Get(c.Param("id"))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	if entry == nil || len(entry.OutputFiles()) == 0 {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "history entry has no file"})
+		return
+	}
+
+	entry, err = s.jobQueue.history.SetVerification(entry.ID, history.Verify(entry))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    entry,
+		Message: "verification " + entry.Verification.Status,
+	})
+}
+
+// handleHistoryThumbnail serves the frame captured from a history entry's
+// video when it finished downloading
+func (s *Server) handleHistoryThumbnail(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+
+	entry, err := s.jobQueue.history.Get(c.Param("id"))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+	if entry == nil || !entry.Thumbnail {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "history entry has no thumbnail"})
+		return
+	}
+	c.File(s.jobQueue.history.ThumbnailPath(entry.ID))
+}
+
+// handleExportHistory downloads matching history entries as JSON or CSV (?format=csv)
+func (s *Server) handleExportHistory(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+
+	format := c.DefaultQuery("format", "json")
+	if format != "json" && format != "csv" {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "format must be json or csv"})
+		return
+	}
+
+	entries, err := s.jobQueue.history.Search(historyQuery(c))
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+
+	contentType := "application/json"
+	if format == "csv" {
+		contentType = "text/csv; charset=utf-8"
+	}
+	c.Header("Content-Type", contentType)
+	c.Header("Content-Disposition", fmt.Sprintf("attachment; filename=\"vget-history.%s\"", format))
+	c.Status(http.StatusOK)
+	if err := history.Export(c.Writer, entries, format); err != nil {
+		log.Printf("History export failed: %v", err)
+	}
+}
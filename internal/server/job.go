@@ -4,58 +4,239 @@ import (
 	"context"
 	"crypto/rand"
 	"encoding/hex"
+	"errors"
 	"fmt"
+	"log"
+	"path/filepath"
+	"slices"
+	"strings"
 	"sync"
 	"time"
 
+	"github.com/guiyumin/vget/internal/core/bandwidth"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloader"
 	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/history"
+	"github.com/guiyumin/vget/internal/core/hook"
+	"github.com/guiyumin/vget/internal/core/opener"
+	"github.com/guiyumin/vget/internal/core/power"
+	"github.com/guiyumin/vget/internal/torrent"
 )
 
 // JobStatus represents the current state of a download job
 type JobStatus string
 
 const (
-	JobStatusQueued      JobStatus = "queued"
-	JobStatusDownloading JobStatus = "downloading"
-	JobStatusCompleted   JobStatus = "completed"
-	JobStatusFailed      JobStatus = "failed"
-	JobStatusCancelled   JobStatus = "cancelled"
+	JobStatusQueued         JobStatus = "queued"
+	JobStatusDownloading    JobStatus = "downloading"
+	JobStatusWaitingNetwork JobStatus = "waiting_network" // connection lost, resumes when it's back
+	JobStatusUploading      JobStatus = "uploading"
+	JobStatusCompleted      JobStatus = "completed"
+	JobStatusFailed         JobStatus = "failed"
+	JobStatusCancelled      JobStatus = "cancelled"
+)
+
+// JobStage is the step of a downloading job
+type JobStage string
+
+const (
+	JobStageVideo       JobStage = "video"        // downloading the video, or the only, stream
+	JobStageAudio       JobStage = "audio"        // downloading the audio stream
+	JobStageMerging     JobStage = "merging"      // muxing separate video and audio with ffmpeg
+	JobStagePostProcess JobStage = "post_process" // subtitles, archives, and other work on the finished files
 )
 
 // Job represents a download job
 type Job struct {
-	ID         string    `json:"id"`
-	URL        string    `json:"url"`
-	Filename   string    `json:"filename,omitempty"`
-	Status     JobStatus `json:"status"`
-	Progress   float64   `json:"progress"`
-	Downloaded int64     `json:"downloaded"` // bytes downloaded
-	Total      int64     `json:"total"`      // total bytes (-1 if unknown)
-	Error      string    `json:"error,omitempty"`
-	CreatedAt  time.Time `json:"created_at"`
-	UpdatedAt  time.Time `json:"updated_at"`
+	ID             string    `json:"id"`
+	URL            string    `json:"url"`
+	Filename       string    `json:"filename,omitempty"`
+	Files          []string  `json:"files,omitempty"`   // every output file, Filename joins them with ", "
+	Quality        string    `json:"quality,omitempty"` // preferred video quality, e.g. 720p
+	Status         JobStatus `json:"status"`
+	Progress       float64   `json:"progress"`
+	Downloaded     int64     `json:"downloaded"`               // bytes downloaded
+	Total          int64     `json:"total"`                    // total bytes (-1 if unknown)
+	Speed          float64   `json:"speed,omitempty"`          // smoothed download speed in bytes/s
+	ETASeconds     int64     `json:"eta_seconds,omitempty"`    // seconds left at Speed (-1 if unknown)
+	Stage          JobStage  `json:"stage,omitempty"`          // step while downloading
+	StageProgress  float64   `json:"stage_progress,omitempty"` // percent done of a step tracked on its own, e.g. merging
+	Error          string    `json:"error,omitempty"`
+	ErrorCode      string    `json:"error_code,omitempty"`   // machine-readable failure reason, see ErrorCode*
+	OnComplete     string    `json:"on_complete,omitempty"`  // none, open, reveal, play
+	OnDuplicate    string    `json:"on_duplicate,omitempty"` // prompt, skip_existing, overwrite
+	Priority       string    `json:"priority,omitempty"`     // high, normal, or low; "" is normal
+	UploadTo       string    `json:"upload_to,omitempty"`    // upload target name
+	UploadProgress float64   `json:"upload_progress,omitempty"`
+	UploadedTo     []string  `json:"uploaded_to,omitempty"` // remote locations after upload
+	Mirrors        []string  `json:"mirrors,omitempty"`     // fallback URLs tried in order when URL fails
+	Site           string    `json:"site,omitempty"`        // site whose stored login is sent with the download
+	Source         string    `json:"source,omitempty"`      // mirror the download succeeded from, if not the first URL
+	Tags           []string  `json:"tags,omitempty"`
+	Note           string    `json:"note,omitempty"`
+	CreatedAt      time.Time `json:"created_at"`
+	UpdatedAt      time.Time `json:"updated_at"`
+
+	// Headers are extra request headers for the download, layered over the site
+	// login. Never serialized, as they may carry credentials.
+	Headers map[string]string `json:"-"`
+
+	// StartAt defers a queued job until this time
+	StartAt *time.Time `json:"start_at,omitempty"`
+
+	// PlaylistReverse queues the entries of a playlist URL last first, e.g. a
+	// podcast feed oldest first
+	PlaylistReverse bool `json:"playlist_reverse,omitempty"`
+
+	// RedirectChain lists the URLs the download went through, requested URL
+	// first and FinalURL last; empty if it wasn't redirected
+	RedirectChain []string `json:"redirect_chain,omitempty"`
+	FinalURL      string   `json:"final_url,omitempty"`
+
+	// PieceCount and PiecesDone follow a torrent job piece by piece when its
+	// torrent client reports pieces. Pieces is the downloaded map as a base64
+	// bitfield, first piece in the high bit.
+	PieceCount int    `json:"piece_count,omitempty"`
+	PiecesDone int    `json:"pieces_done,omitempty"`
+	Pieces     string `json:"pieces,omitempty"`
 
 	// Internal fields (not serialized)
-	cancel context.CancelFunc `json:"-"`
-	ctx    context.Context    `json:"-"`
+	cancel      context.CancelFunc       `json:"-"`
+	ctx         context.Context          `json:"-"`
+	duration    int                      `json:"-"` // media length in seconds
+	extractor   string                   `json:"-"` // extractor that handled the URL
+	held        bool                     `json:"-"` // waiting for StartAt outside the dispatch queue
+	lastEventAt time.Time                `json:"-"`
+	lastStatus  JobStatus                `json:"-"` // status at the last event, to tell what changed
+	lastStep    string                   `json:"-"` // jobStep at the last event
+	rate        downloader.RateEstimator `json:"-"`
+	requested   string                   `json:"-"` // filename the job was submitted with
+	running     bool                     `json:"-"` // a worker is processing the job
+	siteSlot    string                   `json:"-"` // domain whose download slot the job holds
+	title       string                   `json:"-"` // media title, for history
+}
+
+// OutputFiles returns the job's output files. Jobs saved before Files was
+// kept only have Filename, taken as a single file.
+func (j *Job) OutputFiles() []string {
+	if len(j.Files) > 0 {
+		return j.Files
+	}
+	if j.Filename != "" {
+		return []string{j.Filename}
+	}
+	return nil
+}
+
+// Job priorities; the queue starts pending jobs of a higher priority first
+const (
+	PriorityHigh   = "high"
+	PriorityNormal = "normal"
+	PriorityLow    = "low"
+)
+
+// ParsePriority validates a job priority; "" is allowed and means normal
+func ParsePriority(value string) (string, error) {
+	switch value {
+	case "", PriorityHigh, PriorityNormal, PriorityLow:
+		return value, nil
+	}
+	return "", fmt.Errorf("invalid priority: %s (use %s, %s, or %s)", value, PriorityHigh, PriorityNormal, PriorityLow)
+}
+
+// priorityRank orders priorities, higher first
+func priorityRank(priority string) int {
+	switch priority {
+	case PriorityHigh:
+		return 2
+	case PriorityLow:
+		return 0
+	default:
+		return 1
+	}
+}
+
+// ErrJobNotFound is returned for an unknown job ID
+var ErrJobNotFound = errors.New("job not found")
+
+// ErrQueueStopped is returned for jobs added or resumed while the server shuts down
+var ErrQueueStopped = errors.New("job queue is stopped")
+
+// ErrorCodeInsufficientSpace is set on jobs that failed because the output
+// volume didn't have room for the download
+const ErrorCodeInsufficientSpace = "insufficient_disk_space"
+
+// ErrorCodeAlreadyDownloaded is set on jobs stopped because their media is in
+// the download archive and the duplicate policy is prompt
+const ErrorCodeAlreadyDownloaded = "already_downloaded"
+
+// ErrAlreadyDownloaded is returned by a DownloadFunc for media in the download
+// archive when the duplicate policy is prompt
+var ErrAlreadyDownloaded = errors.New("already downloaded")
+
+// ErrSkippedDuplicate is returned by a DownloadFunc that skipped archived media;
+// the job completes without downloading
+var ErrSkippedDuplicate = errors.New("already downloaded, skipped")
+
+// jobErrorCode maps a download error to the job's error_code, or ""
+func jobErrorCode(err error) string {
+	if errors.Is(err, downloader.ErrInsufficientSpace) {
+		return ErrorCodeInsufficientSpace
+	}
+	if errors.Is(err, ErrAlreadyDownloaded) {
+		return ErrorCodeAlreadyDownloaded
+	}
+	return ""
 }
 
-// JobQueue manages download jobs with a worker pool
+// progressEventInterval throttles job progress events per job
+const progressEventInterval = 250 * time.Millisecond
+
+// JobQueue manages download jobs, running at most maxConcurrent at a time
 type JobQueue struct {
 	jobs          map[string]*Job
 	mu            sync.RWMutex
 	queue         chan *Job
 	maxConcurrent int
+	pending       []*Job            // queued jobs waiting for a slot, in queue order
+	running       int               // jobs holding a download slot
+	slotsChanged  chan struct{}     // closed and replaced when a slot frees up, the limit changes, or a job is queued
+	siteRunning   map[string]int    // jobs holding a slot of a domain in download.site_limits
+	siteWaiting   map[string][]*Job // jobs parked until their domain has a free slot
 	outputDir     string
 	downloadFn    DownloadFunc
+	ffmpegFn      ffmpegFunc // queues a job's ffmpeg work with the conversions, run directly if nil
 	wg            sync.WaitGroup
 	cleanupTicker *time.Ticker
 	stopCleanup   chan struct{}
+	events        *EventBus
+	scheduleFn    func() string
+	pauseReason   string
+	recheck       chan struct{} // asks scheduleLoop to evaluate the policies again
+	history       *history.Store
+	bandwidth     *bandwidth.Tracker
+	awake         *power.Inhibitor // keeps the system awake while jobs run
+	store         *jobStore
+	dirty         bool // jobs changed since the last save
+	stopped       bool // Stop was called, nothing more may be sent to queue
+	stopOnce      sync.Once
+}
+
+// DownloadOptions are the per-job settings passed to a DownloadFunc
+type DownloadOptions struct {
+	JobID       string // the job being downloaded, which its output and progress are recorded on
+	Filename    string
+	Quality     string
+	OnDuplicate string
+	Mirrors     []string
+	Site        string
+	Headers     map[string]string
 }
 
 // DownloadFunc is the function signature for downloading a URL
-// It receives the job context, URL, output path, and a progress callback
-type DownloadFunc func(ctx context.Context, url, outputPath string, progressFn func(downloaded, total int64)) error
+// It receives the job context, URL, per-job options, and a progress callback
+type DownloadFunc func(ctx context.Context, url string, opts DownloadOptions, progressFn func(downloaded, total int64)) error
 
 // NewJobQueue creates a new job queue with the specified concurrency
 func NewJobQueue(maxConcurrent int, outputDir string, downloadFn DownloadFunc) *JobQueue {
@@ -63,70 +244,326 @@ func NewJobQueue(maxConcurrent int, outputDir string, downloadFn DownloadFunc) *
 		maxConcurrent = 10
 	}
 
+	historyStore, err := history.Open()
+	if err != nil {
+		log.Printf("Download history disabled: %v", err)
+	}
+
+	usage, err := bandwidth.Open()
+	if err != nil {
+		log.Printf("Bandwidth accounting disabled: %v", err)
+	}
+
+	store, err := openJobStore()
+	if err != nil {
+		log.Printf("Download queue will not survive restarts: %v", err)
+	}
+
 	jq := &JobQueue{
 		jobs:          make(map[string]*Job),
 		queue:         make(chan *Job, 100),
 		maxConcurrent: maxConcurrent,
+		slotsChanged:  make(chan struct{}),
+		siteRunning:   make(map[string]int),
+		siteWaiting:   make(map[string][]*Job),
 		outputDir:     outputDir,
 		downloadFn:    downloadFn,
 		stopCleanup:   make(chan struct{}),
+		recheck:       make(chan struct{}, 1),
+		events:        NewEventBus(),
+		history:       historyStore,
+		bandwidth:     usage,
+		awake:         power.NewInhibitor("Downloading"),
+		store:         store,
 	}
+	jq.scheduleFn = jq.checkQueuePolicies
+	jq.restoreJobs()
 
 	return jq
 }
 
-// Start begins the worker pool and cleanup routine
+// Start begins dispatching queued jobs and the cleanup routine
 func (jq *JobQueue) Start() {
-	// Start workers
-	for i := 0; i < jq.maxConcurrent; i++ {
-		jq.wg.Add(1)
-		go jq.worker()
-	}
+	// Know whether the queue is paused before the first job is dispatched
+	jq.setPauseReason(jq.scheduleFn())
+	go jq.scheduleLoop()
+
+	go jq.collect()
+	jq.wg.Add(1)
+	go jq.dispatch()
 
 	// Start cleanup routine (every 10 minutes, remove jobs older than 1 hour)
 	jq.cleanupTicker = time.NewTicker(10 * time.Minute)
 	go jq.cleanupLoop()
+	go jq.persistLoop()
+	go jq.summaryLoop()
+	go jq.startAtLoop()
 }
 
-// Stop gracefully shuts down the job queue
+// Stop gracefully shuts down the job queue. It may be called more than once,
+// e.g. by the after-queue quit action and a signal; later calls wait for the
+// first to finish.
 func (jq *JobQueue) Stop() {
+	jq.stopOnce.Do(jq.stop)
+}
+
+func (jq *JobQueue) stop() {
+	jq.mu.Lock()
+	jq.stopped = true
+	jq.mu.Unlock()
 	close(jq.queue)
 	close(jq.stopCleanup)
 	if jq.cleanupTicker != nil {
 		jq.cleanupTicker.Stop()
 	}
 	jq.wg.Wait()
+	if jq.bandwidth != nil {
+		jq.bandwidth.Flush()
+	}
+	jq.saveJobs()
 }
 
-func (jq *JobQueue) worker() {
+// collect moves jobs sent to the queue into pending, where dispatch picks
+// them by priority
+func (jq *JobQueue) collect() {
+	for job := range jq.queue {
+		jq.mu.Lock()
+		jq.pending = append(jq.pending, job)
+		jq.notifySlotsLocked()
+		jq.mu.Unlock()
+	}
+}
+
+// dispatch starts pending jobs as download slots become free, highest
+// priority first and in queue order within a priority. Jobs stay queued
+// until they get a slot, and while schedule policies (time window, battery,
+// metered, bandwidth cap) pause the queue.
+func (jq *JobQueue) dispatch() {
 	defer jq.wg.Done()
 
-	for job := range jq.queue {
-		jq.processJob(job)
+	for {
+		job := jq.nextJob()
+		if job == nil {
+			return
+		}
+		// A resumed job replaces its entry, so a stale copy may still be
+		// pending; cancelled jobs are dropped too. Jobs with a future start
+		// time wait for startDueJobs to queue them again, and jobs from a
+		// site at its download.site_limits wait for releaseSite.
+		if !jq.isCurrent(job) || job.ctx.Err() != nil || jq.holdUntilStart(job) || !jq.reserveSite(job) {
+			jq.releaseSlot()
+			continue
+		}
+
+		jq.wg.Add(1)
+		go func(job *Job) {
+			defer jq.wg.Done()
+			defer jq.releaseSite(job)
+			defer jq.releaseSlot()
+
+			jq.setRunning(job, true)
+			jq.processJob(job)
+			jq.recordHistory(job.ID)
+			jq.setRunning(job, false)
+			// The job's traffic may have reached the bandwidth cap
+			jq.recheckSchedule()
+		}(job)
 	}
 }
 
+// nextJob waits until the queue isn't paused, a download slot is free, and a
+// job is pending, then takes the slot for the pending job that should start
+// first. A paused queue holds no slots for the jobs it keeps waiting. It
+// returns nil once the queue stops.
+func (jq *JobQueue) nextJob() *Job {
+	for {
+		jq.mu.Lock()
+		if jq.pauseReason == "" && jq.running < jq.maxConcurrent && len(jq.pending) > 0 {
+			best := 0
+			for i, job := range jq.pending {
+				if priorityRank(job.Priority) > priorityRank(jq.pending[best].Priority) {
+					best = i
+				}
+			}
+			job := jq.pending[best]
+			jq.pending = slices.Delete(jq.pending, best, best+1)
+			jq.running++
+			jq.mu.Unlock()
+			return job
+		}
+		changed := jq.slotsChanged
+		jq.mu.Unlock()
+
+		select {
+		case <-changed:
+		case <-jq.stopCleanup:
+			return nil
+		}
+	}
+}
+
+func (jq *JobQueue) releaseSlot() {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	jq.running--
+	jq.notifySlotsLocked()
+}
+
+// notifySlotsLocked wakes the dispatcher. Must be called with jq.mu held.
+func (jq *JobQueue) notifySlotsLocked() {
+	close(jq.slotsChanged)
+	jq.slotsChanged = make(chan struct{})
+}
+
+// SetMaxConcurrent changes how many jobs may download at once. Raising it
+// starts waiting jobs right away; lowering it lets running jobs finish.
+func (jq *JobQueue) SetMaxConcurrent(n int) {
+	if n <= 0 {
+		n = 10
+	}
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	jq.maxConcurrent = n
+	jq.notifySlotsLocked()
+}
+
+// Concurrency returns the number of running jobs and the current limit
+func (jq *JobQueue) Concurrency() (running, limit int) {
+	jq.mu.RLock()
+	defer jq.mu.RUnlock()
+	return jq.running, jq.maxConcurrent
+}
+
+// isCurrent reports whether job is still the queue's entry for its ID
+func (jq *JobQueue) isCurrent(job *Job) bool {
+	jq.mu.RLock()
+	defer jq.mu.RUnlock()
+	return jq.jobs[job.ID] == job
+}
+
+func (jq *JobQueue) setRunning(job *Job, running bool) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	job.running = running
+}
+
 func (jq *JobQueue) processJob(job *Job) {
 	jq.updateJobStatus(job.ID, JobStatusDownloading, 0, "")
 
+	if !config.LoadOrDefault().Download.AllowSleep {
+		if err := jq.awake.Acquire(); err != nil {
+			log.Printf("Could not prevent sleep while downloading: %v", err)
+		}
+		defer jq.awake.Release()
+	}
+
 	// Create progress callback
 	progressFn := func(downloaded, total int64) {
 		jq.updateJobProgressBytes(job.ID, downloaded, total)
 	}
 
-	// Execute download
-	err := jq.downloadFn(job.ctx, job.URL, job.Filename, progressFn)
+	// Execute download, recording where redirects led for diagnosing failures
+	ctx, redirects := downloader.TraceRedirects(job.ctx)
+	opts := DownloadOptions{
+		JobID:       job.ID,
+		Filename:    job.Filename,
+		Quality:     job.Quality,
+		OnDuplicate: job.OnDuplicate,
+		Mirrors:     job.Mirrors,
+		Site:        job.Site,
+		Headers:     job.Headers,
+	}
+	err := jq.downloadFn(ctx, job.URL, opts, progressFn)
+	// A lost connection doesn't fail the job; it continues from the partial
+	// file once the network is back
+	for err != nil && jq.waitForNetwork(job, err) {
+		err = jq.downloadFn(ctx, job.URL, opts, progressFn)
+	}
+	jq.setJobRedirects(job.ID, redirects.Chain())
 
+	if errors.Is(err, ErrSkippedDuplicate) {
+		jq.updateJobStatus(job.ID, JobStatusCompleted, 100, "")
+		return
+	}
 	if err != nil {
+		if job.ctx.Err() == context.Canceled {
+			jq.updateJobStatus(job.ID, JobStatusCancelled, 0, "cancelled by user")
+		} else if current := jq.GetJob(job.ID); current == nil || current.Status != JobStatusWaitingNetwork {
+			// A job still waiting for the network when the server stops is
+			// saved as unfinished and queued again on restart
+			jq.failJob(job.ID, err)
+		}
+		return
+	}
+
+	jq.normalizeJobAudio(job)
+	if job.ctx.Err() == context.Canceled {
+		jq.updateJobStatus(job.ID, JobStatusCancelled, 0, "cancelled by user")
+		return
+	}
+
+	// Upload to a cloud target before reporting completion
+	if err := jq.uploadJobOutput(job); err != nil {
 		if job.ctx.Err() == context.Canceled {
 			jq.updateJobStatus(job.ID, JobStatusCancelled, 0, "cancelled by user")
 		} else {
-			jq.updateJobStatus(job.ID, JobStatusFailed, 0, err.Error())
+			jq.updateJobStatus(job.ID, JobStatusFailed, 0, "upload failed: "+err.Error())
 		}
 		return
 	}
 
 	jq.updateJobStatus(job.ID, JobStatusCompleted, 100, "")
+	jq.runCompletionAction(job.ID)
+	jq.runHook(job.ID)
+}
+
+// runCompletionAction opens, reveals, or plays the finished file according to the
+// job's on_complete option, falling back to on_complete from config
+func (jq *JobQueue) runCompletionAction(id string) {
+	job := jq.GetJob(id)
+	if job == nil || job.Filename == "" {
+		return
+	}
+
+	value := job.OnComplete
+	if value == "" {
+		value = config.LoadOrDefault().OnComplete
+	}
+	action, err := opener.ParseAction(value)
+	if err != nil {
+		log.Printf("Job %s: %v", id, err)
+		return
+	}
+
+	// Act on the first file of jobs with several, like galleries
+	path := job.OutputFiles()[0]
+	if err := opener.Run(action, path); err != nil {
+		log.Printf("Job %s: failed to %s %s: %v", id, action, path, err)
+	}
+}
+
+// runHook runs download.hook from config for each output file of the job
+func (jq *JobQueue) runHook(id string) {
+	command := config.LoadOrDefault().Download.Hook
+	job := jq.GetJob(id)
+	if command == "" || job == nil || job.Filename == "" {
+		return
+	}
+
+	for _, path := range job.OutputFiles() {
+		title := job.title
+		if title == "" {
+			title = strings.TrimSuffix(filepath.Base(path), filepath.Ext(path))
+		}
+		vars := hook.Vars{
+			Path:  path,
+			Title: title,
+			URL:   job.URL,
+		}
+		if err := hook.Run(command, vars, nil); err != nil {
+			log.Printf("Job %s: %v", id, err)
+		}
+	}
 }
 
 func (jq *JobQueue) cleanupLoop() {
@@ -150,6 +587,8 @@ func (jq *JobQueue) cleanupOldJobs() {
 		if (job.Status == JobStatusCompleted || job.Status == JobStatusFailed || job.Status == JobStatusCancelled) &&
 			job.UpdatedAt.Before(cutoff) {
 			delete(jq.jobs, id)
+			jq.markDirtyLocked()
+			jq.events.Publish(Event{Type: EventJobRemoved, JobID: id})
 		}
 	}
 }
@@ -163,6 +602,8 @@ func (jq *JobQueue) ClearHistory() int {
 	for id, job := range jq.jobs {
 		if job.Status == JobStatusCompleted || job.Status == JobStatusFailed || job.Status == JobStatusCancelled {
 			delete(jq.jobs, id)
+			jq.markDirtyLocked()
+			jq.events.Publish(Event{Type: EventJobRemoved, JobID: id})
 			count++
 		}
 	}
@@ -185,6 +626,8 @@ func (jq *JobQueue) RemoveJob(id string) bool {
 	}
 
 	delete(jq.jobs, id)
+	jq.markDirtyLocked()
+	jq.events.Publish(Event{Type: EventJobRemoved, JobID: id})
 	return true
 }
 
@@ -204,17 +647,52 @@ func (jq *JobQueue) AddFailedJob(rawURL, errorMsg string) *Job {
 
 	jq.mu.Lock()
 	jq.jobs[id] = job
+	jq.publishJobLocked(job)
 	jq.mu.Unlock()
 
 	return job
 }
 
+// JobOptions holds optional per-job settings
+type JobOptions struct {
+	Filename    string
+	Quality     string
+	OnComplete  string
+	OnDuplicate string
+	Priority    string
+	UploadTo    string
+	Tags        []string
+	Note        string
+	Mirrors     []string
+	Site        string
+	Headers     map[string]string
+	StartAt     *time.Time
+
+	PlaylistReverse bool
+}
+
 // AddJob creates and queues a new download job
 func (jq *JobQueue) AddJob(rawURL, filename string) (*Job, error) {
-	// Normalize URL: add https:// if missing
-	url, err := extractor.NormalizeURL(rawURL)
-	if err != nil {
-		return nil, err
+	return jq.AddJobWithOptions(rawURL, JobOptions{Filename: filename})
+}
+
+// AddJobWithOptions creates and queues a new download job with per-job settings
+func (jq *JobQueue) AddJobWithOptions(rawURL string, opts JobOptions) (*Job, error) {
+	// Normalize URL: add https:// if missing. Magnet links are kept as is.
+	url := strings.TrimSpace(rawURL)
+	if !torrent.IsMagnetLink(url) {
+		var err error
+		if url, err = extractor.NormalizeURL(rawURL); err != nil {
+			return nil, err
+		}
+	}
+	var mirrors []string
+	for _, mirror := range opts.Mirrors {
+		normalized, err := extractor.NormalizeURL(mirror)
+		if err != nil {
+			return nil, fmt.Errorf("invalid mirror %s: %w", mirror, err)
+		}
+		mirrors = append(mirrors, normalized)
 	}
 
 	id, err := generateJobID()
@@ -225,30 +703,45 @@ func (jq *JobQueue) AddJob(rawURL, filename string) (*Job, error) {
 	ctx, cancel := context.WithCancel(context.Background())
 
 	job := &Job{
-		ID:        id,
-		URL:       url,
-		Filename:  filename,
-		Status:    JobStatusQueued,
-		Progress:  0,
-		CreatedAt: time.Now(),
-		UpdatedAt: time.Now(),
-		ctx:       ctx,
-		cancel:    cancel,
+		ID:          id,
+		URL:         url,
+		Filename:    opts.Filename,
+		requested:   opts.Filename,
+		Quality:     opts.Quality,
+		Status:      JobStatusQueued,
+		Progress:    0,
+		OnComplete:  opts.OnComplete,
+		OnDuplicate: opts.OnDuplicate,
+		Priority:    opts.Priority,
+		UploadTo:    opts.UploadTo,
+		Tags:        history.NormalizeTags(opts.Tags),
+		Note:        opts.Note,
+		Mirrors:     mirrors,
+		Site:        opts.Site,
+		Headers:     opts.Headers,
+		StartAt:     opts.StartAt,
+		CreatedAt:   time.Now(),
+		UpdatedAt:   time.Now(),
+		ctx:         ctx,
+		cancel:      cancel,
+
+		PlaylistReverse: opts.PlaylistReverse,
 	}
 
+	// Queue the job (non-blocking with buffered channel). Sending under jq.mu
+	// keeps Stop from closing the channel in between.
 	jq.mu.Lock()
-	jq.jobs[id] = job
-	jq.mu.Unlock()
-
-	// Queue the job (non-blocking with buffered channel)
+	defer jq.mu.Unlock()
+	if jq.stopped {
+		cancel()
+		return nil, ErrQueueStopped
+	}
 	select {
 	case jq.queue <- job:
+		jq.jobs[id] = job
+		jq.publishJobLocked(job)
 		return job, nil
 	default:
-		// Queue is full
-		jq.mu.Lock()
-		delete(jq.jobs, id)
-		jq.mu.Unlock()
 		cancel()
 		return nil, fmt.Errorf("job queue is full")
 	}
@@ -290,17 +783,138 @@ func (jq *JobQueue) CancelJob(id string) bool {
 		return false
 	}
 
-	// Can only cancel queued or downloading jobs
-	if job.Status != JobStatusQueued && job.Status != JobStatusDownloading {
+	// Can only cancel queued, downloading, waiting, or uploading jobs
+	switch job.Status {
+	case JobStatusQueued, JobStatusDownloading, JobStatusWaitingNetwork, JobStatusUploading:
+	default:
 		return false
 	}
 
 	job.cancel()
 	job.Status = JobStatusCancelled
+	job.Speed, job.ETASeconds = 0, 0
+	job.Stage, job.StageProgress = "", 0
 	job.UpdatedAt = time.Now()
+	jq.publishJobLocked(job)
 	return true
 }
 
+// ResumeJob requeues a failed or cancelled job. Direct file downloads continue
+// from the partial file left behind instead of starting over.
+func (jq *JobQueue) ResumeJob(id string) (*Job, error) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	old, ok := jq.jobs[id]
+	if !ok {
+		return nil, ErrJobNotFound
+	}
+	if old.Status != JobStatusFailed && old.Status != JobStatusCancelled {
+		return nil, fmt.Errorf("job is %s, only failed or cancelled jobs can be resumed", old.Status)
+	}
+	if old.running {
+		// The cancelled download hasn't let go of its partial file yet
+		return nil, fmt.Errorf("job is still stopping, try again in a moment")
+	}
+	if jq.stopped {
+		return nil, ErrQueueStopped
+	}
+
+	ctx, cancel := context.WithCancel(context.Background())
+	job := *old
+	job.Filename, job.Files = old.requested, nil
+	job.Status = JobStatusQueued
+	job.Error = ""
+	job.ErrorCode = ""
+	job.Source = ""
+	job.rate = downloader.RateEstimator{}
+	job.UpdatedAt = time.Now()
+	job.ctx = ctx
+	job.cancel = cancel
+
+	// Sent under jq.mu so Stop can't close the channel in between
+	select {
+	case jq.queue <- &job:
+		jq.jobs[id] = &job
+		jq.publishJobLocked(&job)
+		jobCopy := job
+		return &jobCopy, nil
+	default:
+		cancel()
+		return nil, fmt.Errorf("job queue is full")
+	}
+}
+
+// RequeueFailed resumes every failed job and returns how many were queued
+func (jq *JobQueue) RequeueFailed() int {
+	jq.mu.RLock()
+	var ids []string
+	for id, job := range jq.jobs {
+		if job.Status == JobStatusFailed {
+			ids = append(ids, id)
+		}
+	}
+	jq.mu.RUnlock()
+
+	count := 0
+	for _, id := range ids {
+		if _, err := jq.ResumeJob(id); err == nil {
+			count++
+		}
+	}
+	return count
+}
+
+// SetPriority changes the priority of a queued job
+func (jq *JobQueue) SetPriority(id, priority string) (*Job, error) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	job, ok := jq.jobs[id]
+	if !ok {
+		return nil, ErrJobNotFound
+	}
+	if job.Status != JobStatusQueued || job.running {
+		return nil, fmt.Errorf("job is %s, only queued jobs can be reprioritized", job.Status)
+	}
+	job.Priority = priority
+	job.UpdatedAt = time.Now()
+	jq.publishJobLocked(job)
+	jobCopy := *job
+	return &jobCopy, nil
+}
+
+// ReorderQueue moves the pending jobs listed in ids to the front of the queue
+// in that order, ahead of the others. Priority still comes first, so the order
+// applies among jobs of the same priority. Returns how many jobs moved.
+func (jq *JobQueue) ReorderQueue(ids []string) int {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	var front, rest []*Job
+	for _, id := range ids {
+		for _, job := range jq.pending {
+			if job.ID == id && jq.jobs[id] == job && !slices.Contains(front, job) {
+				front = append(front, job)
+			}
+		}
+	}
+	for _, job := range jq.pending {
+		if !slices.Contains(front, job) {
+			rest = append(rest, job)
+		}
+	}
+	jq.pending = append(front, rest...)
+	if len(front) > 0 {
+		jq.markDirtyLocked()
+		ids := make([]string, len(jq.pending))
+		for i, job := range jq.pending {
+			ids[i] = job.ID
+		}
+		jq.events.Publish(Event{Type: EventQueueReordered, Data: map[string][]string{"ids": ids}})
+	}
+	return len(front)
+}
+
 func (jq *JobQueue) updateJobStatus(id string, status JobStatus, progress float64, errMsg string) {
 	jq.mu.Lock()
 	defer jq.mu.Unlock()
@@ -310,10 +924,45 @@ func (jq *JobQueue) updateJobStatus(id string, status JobStatus, progress float6
 		if progress > 0 {
 			job.Progress = progress
 		}
+		if status != JobStatusDownloading {
+			job.Speed, job.ETASeconds = 0, 0
+			job.Stage, job.StageProgress = "", 0
+		}
 		if errMsg != "" {
 			job.Error = errMsg
 		}
 		job.UpdatedAt = time.Now()
+		jq.publishJobLocked(job)
+	}
+}
+
+// setJobRedirects records the redirect chain of a job's download
+func (jq *JobQueue) setJobRedirects(id string, chain []string) {
+	if len(chain) == 0 {
+		return
+	}
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	if job, ok := jq.jobs[id]; ok {
+		job.RedirectChain = chain
+		job.FinalURL = chain[len(chain)-1]
+	}
+}
+
+// failJob marks a job failed with err's message and error code
+func (jq *JobQueue) failJob(id string, err error) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	if job, ok := jq.jobs[id]; ok {
+		job.Status = JobStatusFailed
+		job.Error = err.Error()
+		job.ErrorCode = jobErrorCode(err)
+		job.Speed, job.ETASeconds = 0, 0
+		job.Stage, job.StageProgress = "", 0
+		job.UpdatedAt = time.Now()
+		jq.publishJobLocked(job)
 	}
 }
 
@@ -322,13 +971,86 @@ func (jq *JobQueue) updateJobProgressBytes(id string, downloaded, total int64) {
 	defer jq.mu.Unlock()
 
 	if job, ok := jq.jobs[id]; ok {
+		if jq.bandwidth != nil {
+			delta := downloaded - job.Downloaded
+			if delta < 0 {
+				// Counter restarted (e.g., audio stream after video)
+				delta = downloaded
+			}
+			jq.bandwidth.Add(delta)
+		}
 		job.Downloaded = downloaded
 		job.Total = total
+		job.UpdatedAt = time.Now()
+		job.Speed = job.rate.Update(downloaded, job.UpdatedAt)
+		job.ETASeconds = -1
 		if total > 0 {
 			job.Progress = float64(downloaded) / float64(total) * 100
+			job.ETASeconds = job.rate.ETA(total - downloaded)
 		}
-		job.UpdatedAt = time.Now()
+		if job.UpdatedAt.Sub(job.lastEventAt) >= progressEventInterval {
+			jq.publishJobLocked(job)
+		}
+	}
+}
+
+// publishJobLocked broadcasts a snapshot of the job to all event subscribers
+// as a download event telling what changed since the last one.
+// Must be called with jq.mu held.
+func (jq *JobQueue) publishJobLocked(job *Job) {
+	jq.markDirtyLocked()
+	kind := downloadEventKind(job.lastStatus, job.lastStep, job)
+	job.lastEventAt = time.Now()
+	job.lastStatus = job.Status
+	job.lastStep = jobStep(job)
+	jobCopy := *job
+	evt := DownloadEvent{Kind: kind, Job: &jobCopy}
+	if isActiveStatus(job.Status) {
+		evt.Stage = job.lastStep
+	}
+	jq.events.Publish(Event{Type: EventDownload, JobID: job.ID, Data: evt})
+}
+
+// isActiveStatus reports whether a job with status is being worked on
+func isActiveStatus(status JobStatus) bool {
+	return status == JobStatusDownloading || status == JobStatusWaitingNetwork || status == JobStatusUploading
+}
+
+// jobStep is the step of an active job: its stage while downloading
+// ("downloading" before the first one), else waiting_network or uploading
+func jobStep(job *Job) string {
+	if job.Status == JobStatusDownloading && job.Stage != "" {
+		return string(job.Stage)
+	}
+	return string(job.Status)
+}
+
+// downloadEventKind tells what happened to job since its last event, when it
+// had status prevStatus and step prevStep
+func downloadEventKind(prevStatus JobStatus, prevStep string, job *Job) DownloadEventKind {
+	if isActiveStatus(job.Status) {
+		switch {
+		case !isActiveStatus(prevStatus):
+			return DownloadStarted
+		case jobStep(job) != prevStep:
+			return DownloadStageChanged
+		}
+		return DownloadProgress
+	}
+	if job.Status == prevStatus {
+		return DownloadUpdated
+	}
+	switch job.Status {
+	case JobStatusQueued:
+		return DownloadQueued
+	case JobStatusCompleted:
+		return DownloadCompleted
+	case JobStatusFailed:
+		return DownloadFailed
+	case JobStatusCancelled:
+		return DownloadCancelled
 	}
+	return DownloadUpdated
 }
 
 func generateJobID() (string, error) {
@@ -0,0 +1,193 @@
+package server
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"log"
+	"net/http"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+const (
+	// jobsFileName holds the download queue inside the config directory
+	jobsFileName = "jobs.json"
+
+	// jobsSaveInterval limits how often queue changes are written to disk
+	jobsSaveInterval = time.Second
+)
+
+// savedJob is a job as written to jobs.json
+type savedJob struct {
+	*Job
+	Requested string `json:"requested_filename,omitempty"`
+
+	// Headers are the job's headers without credentials. A restored job gets
+	// its cookies back from the stored login of its site.
+	Headers map[string]string `json:"headers,omitempty"`
+}
+
+// credentialHeaders are left out of jobs.json
+var credentialHeaders = []string{"Cookie", "Authorization", "Proxy-Authorization"}
+
+// persistableHeaders returns headers without the credential ones, or nil
+func persistableHeaders(headers map[string]string) map[string]string {
+	var kept map[string]string
+	for name, value := range headers {
+		if isCredentialHeader(name) {
+			continue
+		}
+		if kept == nil {
+			kept = make(map[string]string)
+		}
+		kept[name] = value
+	}
+	return kept
+}
+
+func isCredentialHeader(name string) bool {
+	name = http.CanonicalHeaderKey(name)
+	for _, h := range credentialHeaders {
+		if name == h {
+			return true
+		}
+	}
+	return false
+}
+
+// jobStore reads and writes the persisted download queue
+type jobStore struct {
+	path string
+}
+
+func openJobStore() (*jobStore, error) {
+	dir, err := config.ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+	return &jobStore{path: filepath.Join(dir, jobsFileName)}, nil
+}
+
+func (s *jobStore) load() ([]savedJob, error) {
+	data, err := os.ReadFile(s.path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+	var jobs []savedJob
+	if err := json.Unmarshal(data, &jobs); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", s.path, err)
+	}
+	return jobs, nil
+}
+
+func (s *jobStore) save(jobs []savedJob) error {
+	if err := os.MkdirAll(filepath.Dir(s.path), 0755); err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(jobs, "", "  ")
+	if err != nil {
+		return err
+	}
+	tmp := s.path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0600); err != nil {
+		return err
+	}
+	return os.Rename(tmp, s.path)
+}
+
+// restoreJobs loads the queue saved by a previous run. Jobs that were still
+// queued or running are queued again; direct downloads pick up from their
+// partial files.
+func (jq *JobQueue) restoreJobs() {
+	if jq.store == nil {
+		return
+	}
+	saved, err := jq.store.load()
+	if err != nil {
+		log.Printf("Could not restore download queue: %v", err)
+		return
+	}
+
+	requeued := 0
+	for _, sj := range saved {
+		job := sj.Job
+		if job == nil || job.ID == "" {
+			continue
+		}
+		job.requested = sj.Requested
+		job.Headers = sj.Headers
+
+		switch job.Status {
+		case JobStatusCompleted, JobStatusFailed, JobStatusCancelled:
+			// Already announced, so later changes are reported as updates
+			job.lastStatus = job.Status
+			jq.jobs[job.ID] = job
+			continue
+		}
+
+		job.ctx, job.cancel = context.WithCancel(context.Background())
+		job.Filename, job.Files = job.requested, nil
+		job.Status = JobStatusQueued
+		select {
+		case jq.queue <- job:
+			requeued++
+		default:
+			job.Status = JobStatusFailed
+			job.Error = "job queue was full when the server restarted"
+		}
+		jq.jobs[job.ID] = job
+	}
+	if requeued > 0 {
+		log.Printf("Restored %d unfinished download(s)", requeued)
+	}
+}
+
+// markDirtyLocked schedules the queue to be saved. Must be called with jq.mu held.
+func (jq *JobQueue) markDirtyLocked() {
+	jq.dirty = true
+}
+
+// persistLoop writes the queue to disk when it changed
+func (jq *JobQueue) persistLoop() {
+	ticker := time.NewTicker(jobsSaveInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			jq.saveJobs()
+		case <-jq.stopCleanup:
+			return
+		}
+	}
+}
+
+// saveJobs writes the queue to jobs.json if it changed since the last save
+func (jq *JobQueue) saveJobs() {
+	if jq.store == nil {
+		return
+	}
+
+	jq.mu.Lock()
+	if !jq.dirty {
+		jq.mu.Unlock()
+		return
+	}
+	jobs := make([]savedJob, 0, len(jq.jobs))
+	for _, job := range jq.jobs {
+		jobCopy := *job
+		jobs = append(jobs, savedJob{Job: &jobCopy, Requested: job.requested, Headers: persistableHeaders(job.Headers)})
+	}
+	jq.dirty = false
+	jq.mu.Unlock()
+
+	if err := jq.store.save(jobs); err != nil {
+		log.Printf("Failed to save download queue: %v", err)
+	}
+}
@@ -0,0 +1,66 @@
+package server
+
+import (
+	"context"
+	"log"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+)
+
+// normalizeJobAudio evens out the volume of the job's audio files when
+// download.normalize_audio is on. A file that fails is kept as downloaded.
+func (jq *JobQueue) normalizeJobAudio(job *Job) {
+	cfg := config.LoadOrDefault().Download
+	if !cfg.NormalizeAudio || !ffmpeg.Available() {
+		return
+	}
+	current := jq.GetJob(job.ID)
+	if current == nil {
+		return
+	}
+
+	for _, file := range current.OutputFiles() {
+		if !ffmpeg.IsAudioFile(file) {
+			continue
+		}
+		jq.setJobStage(job.ID, JobStagePostProcess, 0)
+		normalize := func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+			return ffmpeg.NormalizeLoudness(ctx, file, file, cfg.LoudnessTarget, func(done, total time.Duration) {
+				if progressFn != nil {
+					progressFn(done, total)
+				}
+				if total > 0 {
+					jq.setJobStage(job.ID, JobStagePostProcess, float64(done)/float64(total)*100)
+				}
+			})
+		}
+		var err error
+		if jq.ffmpegFn != nil {
+			err = jq.ffmpegFn(job.ctx, "loudness", file, file, normalize)
+		} else {
+			err = normalize(job.ctx, nil)
+		}
+		if err != nil {
+			log.Printf("Job %s: loudness normalization of %s failed: %v", job.ID, file, err)
+		}
+	}
+}
+
+// setJobStage records the step a downloading job is in and how far through
+// it the job is
+func (jq *JobQueue) setJobStage(id string, stage JobStage, progress float64) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	job, ok := jq.jobs[id]
+	if !ok || job.Status != JobStatusDownloading {
+		return
+	}
+	changed := job.Stage != stage
+	job.Stage, job.StageProgress = stage, progress
+	job.UpdatedAt = time.Now()
+	if changed || job.UpdatedAt.Sub(job.lastEventAt) >= progressEventInterval {
+		jq.publishJobLocked(job)
+	}
+}
@@ -0,0 +1,46 @@
+package server
+
+import (
+	"log"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/network"
+)
+
+// networkCheckInterval is how often jobs waiting for the network check for it
+const networkCheckInterval = 5 * time.Second
+
+// waitForNetwork holds a job whose download failed with a network error while
+// the machine is offline, and returns true once it's back online. It returns
+// false right away for other errors, or when the network is up and only the
+// server failed, and false if the job is cancelled or the queue stops.
+func (jq *JobQueue) waitForNetwork(job *Job, err error) bool {
+	if job.ctx.Err() != nil || !network.IsNetworkError(err) {
+		return false
+	}
+	proxy := config.LoadOrDefault().Network.Proxy
+	if network.Online(job.ctx, proxy) {
+		return false
+	}
+
+	log.Printf("Job %s waiting for the network: %v", job.ID, err)
+	jq.updateJobStatus(job.ID, JobStatusWaitingNetwork, 0, "")
+
+	ticker := time.NewTicker(networkCheckInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-job.ctx.Done():
+			return false
+		case <-jq.stopCleanup:
+			return false
+		case <-ticker.C:
+			if network.Online(job.ctx, proxy) {
+				log.Printf("Network is back, resuming job %s", job.ID)
+				jq.updateJobStatus(job.ID, JobStatusDownloading, 0, "")
+				return true
+			}
+		}
+	}
+}
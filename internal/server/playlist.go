@@ -0,0 +1,70 @@
+package server
+
+import (
+	"fmt"
+	"log"
+
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// queuePlaylist adds one job per playlist entry, in playlist order or last
+// first with playlist_reverse. The entries inherit the options (quality,
+// completion action, duplicate policy, upload target, tags, note, site login,
+// headers) of the job that extracted the playlist, and are named after the
+// entry titles prefixed with their position.
+func (s *Server) queuePlaylist(jobID string, m *extractor.PlaylistMedia) error {
+	if len(m.Entries) == 0 {
+		return fmt.Errorf("playlist is empty")
+	}
+
+	var opts JobOptions
+	var reverse bool
+	if job := s.jobQueue.GetJob(jobID); job != nil {
+		opts = JobOptions{
+			Quality:     job.Quality,
+			OnComplete:  job.OnComplete,
+			OnDuplicate: job.OnDuplicate,
+			Priority:    job.Priority,
+			UploadTo:    job.UploadTo,
+			Tags:        job.Tags,
+			Note:        job.Note,
+			Site:        job.Site,
+			Headers:     job.Headers,
+		}
+		reverse = job.PlaylistReverse
+	}
+
+	if reverse {
+		m = m.Reversed()
+	}
+
+	queued := 0
+	for i, entry := range m.Entries {
+		entryURL := entry.URL
+		// Resolved entries (e.g. podcast episodes) are downloaded from their media URL
+		switch media := entry.Media.(type) {
+		case *extractor.AudioMedia:
+			entryURL = media.URL
+		case *extractor.VideoMedia:
+			if len(media.Formats) > 0 {
+				entryURL = media.Formats[0].URL
+			}
+		}
+
+		entryOpts := opts
+		entryOpts.Filename = m.IndexedTitle(i)
+		if _, err := s.jobQueue.AddJobWithOptions(entryURL, entryOpts); err != nil {
+			log.Printf("Playlist %s: failed to queue %q: %v", m.Title, entry.Title, err)
+			continue
+		}
+		queued++
+	}
+
+	if queued == 0 {
+		return fmt.Errorf("failed to queue playlist items")
+	}
+	log.Printf("Playlist %s: queued %d of %d items", m.Title, queued, len(m.Entries))
+	// The playlist job itself has no output file to upload or open
+	s.updateJobFilename(jobID, "")
+	return nil
+}
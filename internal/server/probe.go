@@ -0,0 +1,39 @@
+package server
+
+import (
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// ProbeRequest is the request body for POST /probe
+type ProbeRequest struct {
+	URL     string            `json:"url" binding:"required"`
+	Site    string            `json:"site,omitempty"` // send the stored login of this site
+	Headers map[string]string `json:"headers,omitempty"`
+}
+
+// handleProbe reports the size, type, range support, and name of a direct
+// file URL before it's downloaded
+func (s *Server) handleProbe(c *gin.Context) {
+	var req ProbeRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "url is required"})
+		return
+	}
+	url, err := extractor.NormalizeURL(req.URL)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	headers := requestHeaders(DownloadOptions{Site: req.Site, Headers: req.Headers}, nil)
+	result, err := downloader.Probe(c.Request.Context(), url, headers)
+	if err != nil {
+		c.JSON(http.StatusBadGateway, Response{Code: 502, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{Code: 200, Data: result, Message: "probed"})
+}
@@ -0,0 +1,164 @@
+package server
+
+import (
+	"fmt"
+	"log"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/bandwidth"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/scheduler"
+)
+
+// scheduleCheckInterval is how often the schedule policy is re-evaluated
+const scheduleCheckInterval = 30 * time.Second
+
+// startAtCheckInterval is how often jobs waiting for their start time are checked
+const startAtCheckInterval = 10 * time.Second
+
+// checkQueuePolicies evaluates the schedule policy and the monthly bandwidth cap.
+// Config is loaded fresh so policy changes apply without restarting the server.
+func (jq *JobQueue) checkQueuePolicies() string {
+	cfg := config.LoadOrDefault()
+	now := time.Now()
+
+	if reason := scheduler.Check(cfg.Schedule, now); reason != "" {
+		return reason
+	}
+	if jq.bandwidth != nil && jq.bandwidth.CapReached(bandwidth.CapBytes(cfg.Bandwidth), now) {
+		return bandwidth.ReasonMonthlyCap
+	}
+	return ""
+}
+
+// scheduleLoop evaluates the queue policies for every worker: on a timer, and
+// whenever recheckSchedule asks, e.g. after a job finished or the config changed.
+// Checks may shell out (battery and metered detection), so workers never run them.
+func (jq *JobQueue) scheduleLoop() {
+	ticker := time.NewTicker(scheduleCheckInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+		case <-jq.recheck:
+		case <-jq.stopCleanup:
+			return
+		}
+		jq.setPauseReason(jq.scheduleFn())
+	}
+}
+
+// recheckSchedule asks scheduleLoop to evaluate the policies again soon
+func (jq *JobQueue) recheckSchedule() {
+	select {
+	case jq.recheck <- struct{}{}:
+	default:
+	}
+}
+
+// setPauseReason records the queue pause state and publishes an event when it changes
+func (jq *JobQueue) setPauseReason(reason string) {
+	jq.mu.Lock()
+	changed := jq.pauseReason != reason
+	jq.pauseReason = reason
+	if changed && reason == "" {
+		// Let the dispatcher start the jobs held during the pause
+		jq.notifySlotsLocked()
+	}
+	jq.mu.Unlock()
+
+	if !changed {
+		return
+	}
+
+	if reason != "" {
+		log.Printf("Download queue paused: %s", reason)
+		jq.events.Publish(Event{Type: EventQueuePaused, Data: map[string]string{"reason": reason}})
+	} else {
+		log.Printf("Download queue resumed")
+		jq.events.Publish(Event{Type: EventQueueResumed})
+	}
+}
+
+// PauseReason returns why the queue is currently paused, or "" if it is running
+func (jq *JobQueue) PauseReason() string {
+	jq.mu.RLock()
+	defer jq.mu.RUnlock()
+	return jq.pauseReason
+}
+
+// holdUntilStart parks a job whose start time is still ahead, so it doesn't
+// take a download slot while waiting
+func (jq *JobQueue) holdUntilStart(job *Job) bool {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	if job.StartAt == nil || !time.Now().Before(*job.StartAt) {
+		return false
+	}
+	job.held = true
+	return true
+}
+
+// startAtLoop queues held jobs once their start time has come
+func (jq *JobQueue) startAtLoop() {
+	ticker := time.NewTicker(startAtCheckInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			jq.startDueJobs()
+		case <-jq.stopCleanup:
+			return
+		}
+	}
+}
+
+// startDueJobs sends held jobs whose start time has passed to the dispatch
+// queue. A job that doesn't fit stays held and is tried again next time.
+func (jq *JobQueue) startDueJobs() {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	now := time.Now()
+	for _, job := range jq.jobs {
+		if !job.held || jq.stopped {
+			continue
+		}
+		if job.Status != JobStatusQueued {
+			job.held = false
+			continue
+		}
+		if job.StartAt != nil && now.Before(*job.StartAt) {
+			continue
+		}
+		select {
+		case jq.queue <- job:
+			job.held = false
+		default:
+		}
+	}
+}
+
+// ScheduleJob sets when a queued job may start. A nil or past startAt starts
+// it as soon as a slot is free.
+func (jq *JobQueue) ScheduleJob(id string, startAt *time.Time) (*Job, error) {
+	jq.mu.Lock()
+	job, ok := jq.jobs[id]
+	if !ok {
+		jq.mu.Unlock()
+		return nil, ErrJobNotFound
+	}
+	if job.Status != JobStatusQueued || job.running {
+		jq.mu.Unlock()
+		return nil, fmt.Errorf("job is %s, only queued jobs can be scheduled", job.Status)
+	}
+	job.StartAt = startAt
+	job.UpdatedAt = time.Now()
+	jq.publishJobLocked(job)
+	jobCopy := *job
+	jq.mu.Unlock()
+
+	jq.startDueJobs()
+	return &jobCopy, nil
+}
@@ -2,6 +2,7 @@ package server
 
 import (
 	"context"
+	"errors"
 	"fmt"
 	"io"
 	"io/fs"
@@ -14,14 +15,24 @@ import (
 	"time"
 
 	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/archive"
+	"github.com/guiyumin/vget/internal/core/auth"
 	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloaded"
 	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/downloadlist"
 	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/ffmpeg"
+	"github.com/guiyumin/vget/internal/core/ftp"
 	"github.com/guiyumin/vget/internal/core/i18n"
+	"github.com/guiyumin/vget/internal/core/opener"
+	_ "github.com/guiyumin/vget/internal/core/plugin" // registers WASM plugins as an extractor provider
+	"github.com/guiyumin/vget/internal/core/sanitize"
 	"github.com/guiyumin/vget/internal/core/tracker"
 	"github.com/guiyumin/vget/internal/core/version"
 	"github.com/guiyumin/vget/internal/core/webdav"
 	"github.com/guiyumin/vget/internal/torrent"
+	"github.com/guiyumin/vget/internal/updater"
 )
 
 // Response is the standard API response structure
@@ -33,14 +44,64 @@ type Response struct {
 
 // DownloadRequest is the request body for POST /download
 type DownloadRequest struct {
-	URL        string `json:"url" binding:"required"`
-	Filename   string `json:"filename,omitempty"`
-	ReturnFile bool   `json:"return_file,omitempty"`
+	URL         string   `json:"url" binding:"required"`
+	Filename    string   `json:"filename,omitempty"`
+	Quality     string   `json:"quality,omitempty"` // preferred video quality, e.g. 720p
+	ReturnFile  bool     `json:"return_file,omitempty"`
+	OnComplete  string   `json:"on_complete,omitempty"`  // none, open, reveal, play
+	OnDuplicate string   `json:"on_duplicate,omitempty"` // prompt, skip_existing, overwrite
+	Priority    string   `json:"priority,omitempty"`     // high, normal (default), low
+	UploadTo    string   `json:"upload_to,omitempty"`    // upload target name from config
+	Tags        []string `json:"tags,omitempty"`
+	Note        string   `json:"note,omitempty"`
+	Mirrors     []string `json:"mirrors,omitempty"` // fallback URLs tried in order if url fails
+	Site        string   `json:"site,omitempty"`    // send the stored login of this site (bilibili, xiaohongshu, twitter, youtube, or a domain)
+
+	// Headers are extra request headers for the download, overriding both the
+	// site login and the extractor's headers
+	Headers map[string]string `json:"headers,omitempty"`
+
+	// StartAt defers the download until this time (RFC 3339)
+	StartAt *time.Time `json:"start_at,omitempty"`
+
+	// PlaylistReverse queues the entries of a playlist last first
+	PlaylistReverse bool `json:"playlist_reverse,omitempty"`
 }
 
 // BulkDownloadRequest is the request body for POST /bulk-download
 type BulkDownloadRequest struct {
-	URLs []string `json:"urls" binding:"required"`
+	URLs        []string   `json:"urls" binding:"required"`
+	Quality     string     `json:"quality,omitempty"`      // applies to every URL
+	OnDuplicate string     `json:"on_duplicate,omitempty"` // applies to every URL
+	Priority    string     `json:"priority,omitempty"`     // applies to every URL
+	StartAt     *time.Time `json:"start_at,omitempty"`     // applies to every URL
+}
+
+// ImportRequest is the request body for POST /import. The list is read from
+// content, or from path on the server.
+type ImportRequest struct {
+	Content     string     `json:"content,omitempty"`
+	Path        string     `json:"path,omitempty"`
+	Quality     string     `json:"quality,omitempty"` // default for items without their own
+	OnDuplicate string     `json:"on_duplicate,omitempty"`
+	Priority    string     `json:"priority,omitempty"`
+	StartAt     *time.Time `json:"start_at,omitempty"`
+}
+
+// ScheduleRequest is the request body for POST /jobs/:id/schedule. Without
+// start_at the job starts as soon as a slot is free.
+type ScheduleRequest struct {
+	StartAt *time.Time `json:"start_at"`
+}
+
+// PriorityRequest is the request body for POST /jobs/:id/priority
+type PriorityRequest struct {
+	Priority string `json:"priority"` // high, normal, low
+}
+
+// ReorderRequest is the request body for POST /jobs/reorder
+type ReorderRequest struct {
+	IDs []string `json:"ids" binding:"required"`
 }
 
 // Server is the HTTP server for vget
@@ -53,6 +114,38 @@ type Server struct {
 	cfg        *config.Config
 	server     *http.Server
 	engine     *gin.Engine
+
+	extractCache    *extractor.Cache    // media info for the extract endpoints
+	downloadArchive *downloaded.Archive // completed media, for duplicate detection
+
+	updateMu      sync.Mutex
+	pendingUpdate string // channel of a deferred update, "" if none
+
+	afterMu        sync.Mutex
+	afterQueue     AfterQueueRequest  // action once the queue drains, for this session only
+	afterCancel    context.CancelFunc // stops the goroutine waiting to run afterQueue
+	afterCountdown time.Time          // when afterQueue runs, zero unless counting down
+
+	stopWatch context.CancelFunc // stops the watch folder goroutine
+
+	convertMu      sync.Mutex
+	conversions    map[string]*Conversion // ffmpeg conversions of this session
+	convertQueue   []*Conversion          // queued conversions, oldest first
+	convertRunning int
+	convertBatches map[string]*ConversionBatch // POST /convert/batch batches of this session
+
+	ffmpegMu      sync.Mutex
+	ffmpegInstall *FFmpegInstall // download of the managed ffmpeg, nil if none is running
+
+	loginMu     sync.Mutex
+	loginCancel context.CancelFunc // closes the browser login window, nil if none is open
+
+	bilibiliMu   sync.Mutex
+	bilibiliUser bilibiliUser // account of the stored Bilibili login, as last fetched
+
+	authMu        sync.Mutex
+	authStates    map[string]authState // login status by site, as last sent in an auth_status_changed event
+	stopAuthTasks context.CancelFunc   // stops the auth status watch and Bilibili keep-alive goroutines
 }
 
 // NewServer creates a new HTTP server
@@ -64,14 +157,27 @@ func NewServer(port int, outputDir, apiKey string, maxConcurrent int) *Server {
 		outputDir: outputDir,
 		apiKey:    apiKey,
 		cfg:       cfg,
+
+		extractCache: extractor.NewCache(),
+		authStates:   make(map[string]authState),
+	}
+
+	downloadArchive, err := downloaded.Open()
+	if err != nil {
+		log.Printf("Duplicate detection disabled: %v", err)
 	}
+	s.downloadArchive = downloadArchive
 
 	// Create job queue with download function
 	s.jobQueue = NewJobQueue(maxConcurrent, outputDir, s.downloadWithExtractor)
+	s.jobQueue.ffmpegFn = s.runFFmpeg
 
 	// Create AI job queue (limit to 2 concurrent to avoid API rate limits)
 	s.aiJobQueue = NewAIJobQueue(2, outputDir, cfg)
 
+	// Logins and logouts made here are sent as events at once
+	auth.OnChange(func(site string) { s.publishAuthStatus(site, true) })
+
 	return s
 }
 
@@ -118,10 +224,56 @@ func (s *Server) Start() error {
 	api.GET("/download", s.handleFileDownload) // Download local file by path
 	api.POST("/download", s.handleDownload)
 	api.POST("/bulk-download", s.handleBulkDownload)
+	api.POST("/import", s.handleImport)
 	api.GET("/status/:id", s.handleStatus)
 	api.GET("/jobs", s.handleGetJobs)
 	api.DELETE("/jobs", s.handleClearJobs)
 	api.DELETE("/jobs/:id", s.handleDeleteJob)
+	api.POST("/jobs/:id/resume", s.handleResumeJob)
+	api.POST("/jobs/:id/schedule", s.handleScheduleJob)
+	api.POST("/jobs/:id/priority", s.handlePriorityJob)
+	api.POST("/jobs/reorder", s.handleReorderJobs)
+	api.POST("/jobs/requeue", s.handleRequeueFailed)
+	api.GET("/queue/after", s.handleGetAfterQueue)
+	api.POST("/queue/after", s.handleSetAfterQueue)
+	api.DELETE("/queue/after", s.handleCancelAfterQueue)
+	api.GET("/events", s.handleEvents)
+	api.GET("/history", s.handleGetHistory)
+	api.GET("/history/export", s.handleExportHistory)
+	api.GET("/history/stats", s.handleGetStats)
+	api.PATCH("/history/:id", s.handleAnnotate)
+	api.POST("/history/:id/open", s.handleOpenHistory)
+	api.POST("/history/:id/verify", s.handleVerifyHistory)
+	api.GET("/history/:id/thumbnail", s.handleHistoryThumbnail)
+	api.GET("/bandwidth", s.handleGetBandwidth)
+	api.GET("/cleanup", s.handleGetCleanup)
+	api.POST("/cleanup", s.handleCleanup)
+	api.GET("/convert", s.handleGetConversions)
+	api.DELETE("/convert/:id", s.handleCancelConversion)
+	api.GET("/convert/batch", s.handleGetConversionBatches)
+	api.POST("/convert/batch", s.handleConvertBatch)
+	api.DELETE("/convert/batch/:id", s.handleCancelConversionBatch)
+	api.GET("/convert/presets", s.handleGetConvertPresets)
+	api.GET("/convert/encoders", s.handleGetEncoders)
+	api.POST("/convert/audio", s.handleConvertAudio)
+	api.POST("/convert/video", s.handleConvertVideo)
+	api.POST("/convert/trim", s.handleConvertTrim)
+	api.POST("/convert/animation", s.handleConvertAnimation)
+	api.POST("/convert/frame", s.handleConvertFrame)
+	api.POST("/convert/subtitles", s.handleConvertSubtitles)
+	api.POST("/convert/remux", s.handleConvertRemux)
+	api.POST("/convert/loudness", s.handleConvertLoudness)
+	api.POST("/convert/watermark", s.handleConvertWatermark)
+	api.POST("/convert/adjust", s.handleConvertAdjust)
+	api.GET("/ffmpeg", s.handleGetFFmpeg)
+	api.POST("/ffmpeg/install", s.handleInstallFFmpeg)
+	api.GET("/sites", s.handleGetSites)
+	api.POST("/thumbnail", s.handleThumbnail)
+	api.POST("/probe", s.handleProbe)
+	api.POST("/extract", s.handleExtract)
+	api.POST("/extract/batch", s.handleExtractBatch)
+	api.POST("/extract/select", s.handleSelectFormat)
+	api.DELETE("/extract/cache", s.handleClearExtractCache)
 	api.GET("/config", s.handleGetConfig)
 	api.POST("/config", s.handleSetConfig)
 	api.PUT("/config", s.handleUpdateConfig)
@@ -129,6 +281,8 @@ func (s *Server) Start() error {
 	api.POST("/config/webdav", s.handleAddWebDAV)
 	api.DELETE("/config/webdav/:name", s.handleDeleteWebDAV)
 	api.GET("/i18n", s.handleI18n)
+	api.GET("/update", s.handleCheckUpdate)
+	api.POST("/update", s.handleInstallUpdate)
 	api.POST("/kuaidi100", s.handleKuaidi100)
 
 	// WebDAV browsing routes
@@ -147,6 +301,15 @@ func (s *Server) Start() error {
 	api.POST("/podcast/search", s.handlePodcastSearch)
 	api.POST("/podcast/episodes", s.handlePodcastEpisodes)
 
+	// Site login routes
+	api.GET("/auth", s.handleGetAuthStatuses)
+	api.GET("/auth/:site", s.handleGetAuthStatus)
+	api.PUT("/auth/:site/cookie", s.handleSetAuthCookie)
+	api.PUT("/auth/:site/profile", s.handleSwitchProfile)
+	api.DELETE("/auth/:site", s.handleLogout)
+	api.POST("/auth/:site/login", s.handleBrowserLogin)
+	api.DELETE("/auth/:site/login", s.handleCancelBrowserLogin)
+
 	// Bilibili login routes
 	api.POST("/bilibili/qr/generate", s.handleBilibiliQRGenerate)
 	api.GET("/bilibili/qr/poll", s.handleBilibiliQRPoll)
@@ -191,14 +354,34 @@ func (s *Server) Start() error {
 	if s.apiKey != "" {
 		log.Printf("API key authentication enabled")
 	}
+	go s.reportTempFiles()
+	// Testing the hardware encoders takes a few seconds, better spent now
+	// than on the first transcode
+	go ffmpeg.HardwareEncoders()
+
+	watchCtx, stopWatch := context.WithCancel(context.Background())
+	s.stopWatch = stopWatch
+	go s.watchFolder(watchCtx)
+
+	authCtx, stopAuthTasks := context.WithCancel(context.Background())
+	s.stopAuthTasks = stopAuthTasks
+	go s.watchAuthStatus(authCtx)
+	go s.keepBilibiliAlive(authCtx)
 
 	return s.server.ListenAndServe()
 }
 
 // Stop gracefully shuts down the server
 func (s *Server) Stop(ctx context.Context) error {
+	if s.stopWatch != nil {
+		s.stopWatch()
+	}
+	if s.stopAuthTasks != nil {
+		s.stopAuthTasks()
+	}
 	s.jobQueue.Stop()
 	s.aiJobQueue.Stop()
+	s.stopConversions()
 	return s.server.Shutdown(ctx)
 }
 
@@ -218,9 +401,20 @@ func (s *Server) authMiddleware() gin.HandlerFunc {
 		// Exclude /api/download and /api/jobs which need auth
 		isProtectedAPIRoute := path == "/api/download" ||
 			path == "/api/bulk-download" ||
+			path == "/api/import" ||
 			strings.HasPrefix(path, "/api/status/") ||
 			path == "/api/jobs" ||
-			strings.HasPrefix(path, "/api/jobs/")
+			strings.HasPrefix(path, "/api/jobs/") ||
+			path == "/api/queue/after" ||
+			path == "/api/cleanup" ||
+			strings.HasPrefix(path, "/api/convert") ||
+			strings.HasPrefix(path, "/api/ffmpeg") ||
+			path == "/api/events" ||
+			path == "/api/thumbnail" ||
+			path == "/api/probe" ||
+			strings.HasPrefix(path, "/api/extract") ||
+			strings.HasPrefix(path, "/api/history") ||
+			path == "/api/update"
 
 		if !isProtectedAPIRoute {
 			c.Next()
@@ -363,8 +557,70 @@ func (s *Server) handleDownload(c *gin.Context) {
 		return
 	}
 
+	if _, err := opener.ParseAction(req.OnComplete); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	if _, err := config.ParseDuplicatePolicy(req.OnDuplicate); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	if _, err := ParsePriority(req.Priority); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	if req.Site != "" {
+		if _, err := extractor.SiteHeaders(req.Site); err != nil {
+			c.JSON(http.StatusBadRequest, Response{
+				Code:    400,
+				Data:    nil,
+				Message: err.Error(),
+			})
+			return
+		}
+	}
+
+	if req.UploadTo != "" && config.LoadOrDefault().GetUploadTarget(req.UploadTo) == nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: fmt.Sprintf("upload target '%s' not found", req.UploadTo),
+		})
+		return
+	}
+
 	// Otherwise, queue the download
-	job, err := s.jobQueue.AddJob(req.URL, req.Filename)
+	job, err := s.jobQueue.AddJobWithOptions(req.URL, JobOptions{
+		Filename:    req.Filename,
+		Quality:     req.Quality,
+		OnComplete:  req.OnComplete,
+		OnDuplicate: req.OnDuplicate,
+		Priority:    req.Priority,
+		UploadTo:    req.UploadTo,
+		Tags:        req.Tags,
+		Note:        req.Note,
+		Mirrors:     req.Mirrors,
+		Site:        req.Site,
+		Headers:     req.Headers,
+		StartAt:     req.StartAt,
+
+		PlaylistReverse: req.PlaylistReverse,
+	})
 	if err != nil {
 		c.JSON(http.StatusInternalServerError, Response{
 			Code:    500,
@@ -404,6 +660,24 @@ func (s *Server) handleBulkDownload(c *gin.Context) {
 		return
 	}
 
+	if _, err := config.ParseDuplicatePolicy(req.OnDuplicate); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	if _, err := ParsePriority(req.Priority); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
 	// Queue all downloads
 	var jobs []gin.H
 	var queued, failed int
@@ -415,7 +689,12 @@ func (s *Server) handleBulkDownload(c *gin.Context) {
 			continue
 		}
 
-		job, err := s.jobQueue.AddJob(url, "")
+		job, err := s.jobQueue.AddJobWithOptions(url, JobOptions{
+			Quality:     req.Quality,
+			OnDuplicate: req.OnDuplicate,
+			Priority:    req.Priority,
+			StartAt:     req.StartAt,
+		})
 		if err != nil {
 			// Create a failed job so it shows in the UI
 			failedJob := s.jobQueue.AddFailedJob(url, err.Error())
@@ -447,6 +726,99 @@ func (s *Server) handleBulkDownload(c *gin.Context) {
 	})
 }
 
+// handleImport queues every URL of a text or JSON download list. Items from
+// JSON lists keep their own quality, tags, and note.
+func (s *Server) handleImport(c *gin.Context) {
+	var req ImportRequest
+	if err := c.ShouldBindJSON(&req); err != nil || (req.Content == "") == (req.Path == "") {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "invalid request body: content or path is required",
+		})
+		return
+	}
+
+	if _, err := config.ParseDuplicatePolicy(req.OnDuplicate); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	if _, err := ParsePriority(req.Priority); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	var list *downloadlist.List
+	var err error
+	if req.Path != "" {
+		list, err = downloadlist.ParseFile(req.Path)
+	} else {
+		list, err = downloadlist.Parse([]byte(req.Content))
+	}
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	var jobs []gin.H
+	var queued, failed int
+	for _, item := range list.Items {
+		quality := item.Quality
+		if quality == "" {
+			quality = req.Quality
+		}
+		job, err := s.jobQueue.AddJobWithOptions(item.URL, JobOptions{
+			Quality:     quality,
+			OnDuplicate: req.OnDuplicate,
+			Priority:    req.Priority,
+			Tags:        item.Tags,
+			Note:        item.Note,
+			StartAt:     req.StartAt,
+		})
+		if err != nil {
+			failedJob := s.jobQueue.AddFailedJob(item.URL, err.Error())
+			jobs = append(jobs, gin.H{
+				"id":     failedJob.ID,
+				"url":    failedJob.URL,
+				"status": failedJob.Status,
+				"error":  failedJob.Error,
+			})
+			failed++
+			continue
+		}
+		jobs = append(jobs, gin.H{
+			"id":     job.ID,
+			"url":    job.URL,
+			"status": JobStatusQueued,
+		})
+		queued++
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"jobs":    jobs,
+			"queued":  queued,
+			"failed":  failed,
+			"invalid": list.Invalid,
+		},
+		Message: fmt.Sprintf("%d downloads queued", queued),
+	})
+}
+
 func (s *Server) handleStatus(c *gin.Context) {
 	id := c.Param("id")
 
@@ -463,11 +835,15 @@ func (s *Server) handleStatus(c *gin.Context) {
 	c.JSON(http.StatusOK, Response{
 		Code: 200,
 		Data: gin.H{
-			"id":       job.ID,
-			"status":   job.Status,
-			"progress": job.Progress,
-			"filename": job.Filename,
-			"error":    job.Error,
+			"id":          job.ID,
+			"status":      job.Status,
+			"progress":    job.Progress,
+			"downloaded":  job.Downloaded,
+			"total":       job.Total,
+			"speed":       job.Speed,
+			"eta_seconds": job.ETASeconds,
+			"filename":    job.Filename,
+			"error":       job.Error,
 		},
 		Message: string(job.Status),
 	})
@@ -479,21 +855,38 @@ func (s *Server) handleGetJobs(c *gin.Context) {
 	jobList := make([]gin.H, len(jobs))
 	for i, job := range jobs {
 		jobList[i] = gin.H{
-			"id":         job.ID,
-			"url":        job.URL,
-			"status":     job.Status,
-			"progress":   job.Progress,
-			"downloaded": job.Downloaded,
-			"total":      job.Total,
-			"filename":   job.Filename,
-			"error":      job.Error,
+			"id":             job.ID,
+			"url":            job.URL,
+			"status":         job.Status,
+			"progress":       job.Progress,
+			"downloaded":     job.Downloaded,
+			"total":          job.Total,
+			"speed":          job.Speed,
+			"eta_seconds":    job.ETASeconds,
+			"stage":          job.Stage,
+			"filename":       job.Filename,
+			"error":          job.Error,
+			"error_code":     job.ErrorCode,
+			"on_duplicate":   job.OnDuplicate,
+			"priority":       job.Priority,
+			"source":         job.Source,
+			"start_at":       job.StartAt,
+			"final_url":      job.FinalURL,
+			"redirect_chain": job.RedirectChain,
+			"piece_count":    job.PieceCount,
+			"pieces_done":    job.PiecesDone,
+			"pieces":         job.Pieces,
 		}
 	}
 
+	running, limit := s.jobQueue.Concurrency()
 	c.JSON(http.StatusOK, Response{
 		Code: 200,
 		Data: gin.H{
-			"jobs": jobList,
+			"jobs":           jobList,
+			"queue_paused":   s.jobQueue.PauseReason(),
+			"running":        running,
+			"max_concurrent": limit,
 		},
 		Message: fmt.Sprintf("%d jobs found", len(jobs)),
 	})
@@ -535,6 +928,131 @@ func (s *Server) handleDeleteJob(c *gin.Context) {
 	}
 }
 
+func (s *Server) handleRequeueFailed(c *gin.Context) {
+	count := s.jobQueue.RequeueFailed()
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"requeued": count,
+		},
+		Message: fmt.Sprintf("%d failed jobs requeued", count),
+	})
+}
+
+func (s *Server) handleResumeJob(c *gin.Context) {
+	job, err := s.jobQueue.ResumeJob(c.Param("id"))
+	if err != nil {
+		status := http.StatusConflict
+		if errors.Is(err, ErrJobNotFound) {
+			status = http.StatusNotFound
+		}
+		c.JSON(status, Response{
+			Code:    status,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    job,
+		Message: "job resumed",
+	})
+}
+
+func (s *Server) handleScheduleJob(c *gin.Context) {
+	var req ScheduleRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "invalid request body: start_at must be an RFC 3339 time",
+		})
+		return
+	}
+
+	job, err := s.jobQueue.ScheduleJob(c.Param("id"), req.StartAt)
+	if err != nil {
+		status := http.StatusConflict
+		if errors.Is(err, ErrJobNotFound) {
+			status = http.StatusNotFound
+		}
+		c.JSON(status, Response{
+			Code:    status,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    job,
+		Message: "job scheduled",
+	})
+}
+
+func (s *Server) handlePriorityJob(c *gin.Context) {
+	var req PriorityRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "invalid request body: priority is required",
+		})
+		return
+	}
+	priority, err := ParsePriority(req.Priority)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	job, err := s.jobQueue.SetPriority(c.Param("id"), priority)
+	if err != nil {
+		status := http.StatusConflict
+		if errors.Is(err, ErrJobNotFound) {
+			status = http.StatusNotFound
+		}
+		c.JSON(status, Response{
+			Code:    status,
+			Data:    nil,
+			Message: err.Error(),
+		})
+		return
+	}
+
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    job,
+		Message: "job priority set",
+	})
+}
+
+func (s *Server) handleReorderJobs(c *gin.Context) {
+	var req ReorderRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "invalid request body: ids array is required",
+		})
+		return
+	}
+
+	moved := s.jobQueue.ReorderQueue(req.IDs)
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"reordered": moved},
+		Message: fmt.Sprintf("%d jobs reordered", moved),
+	})
+}
+
 // ConfigSetRequest is the request body for POST /config
 type ConfigSetRequest struct {
 	Key   string `json:"key" binding:"required"`
@@ -548,6 +1066,8 @@ type ConfigRequest struct {
 
 func (s *Server) handleGetConfig(c *gin.Context) {
 	cfg := config.LoadOrDefault()
+	twitterLogin, _ := auth.Get(auth.Twitter)
+	bilibiliLogin, _ := auth.Get(auth.Bilibili)
 
 	// Convert WebDAV servers to a simpler format for JSON
 	webdavServers := make(map[string]map[string]string)
@@ -566,19 +1086,73 @@ func (s *Server) handleGetConfig(c *gin.Context) {
 			"language":              cfg.Language,
 			"format":                cfg.Format,
 			"quality":               cfg.Quality,
-			"twitter_auth_token":    cfg.Twitter.AuthToken,
+			"on_complete":           cfg.OnComplete,
+			"update_channel":        cfg.UpdateChannel,
+			"twitter_auth_token":    twitterLogin.Tokens["auth_token"],
 			"server_port":           cfg.Server.Port,
 			"server_max_concurrent": cfg.Server.MaxConcurrent,
 			"server_api_key":        cfg.Server.APIKey,
 			"webdav_servers":        webdavServers,
 			"express":               cfg.Express,
 			"torrent_enabled":       cfg.Torrent.Enabled,
-			"bilibili_cookie":       cfg.Bilibili.Cookie,
+			"bilibili_cookie":       bilibiliLogin.CookieHeader(),
+			"schedule": gin.H{
+				"pause_on_metered": cfg.Schedule.PauseOnMetered,
+				"pause_on_battery": cfg.Schedule.PauseOnBattery,
+				"window_start":     cfg.Schedule.WindowStart,
+				"window_end":       cfg.Schedule.WindowEnd,
+			},
+			"bandwidth": gin.H{
+				"monthly_cap_gb": cfg.Bandwidth.MonthlyCapGB,
+			},
+			"ytdlp": gin.H{
+				"fallback": cfg.Ytdlp.Fallback,
+				"path":     cfg.Ytdlp.Path,
+			},
+			"rate_limit": gin.H{
+				"default": cfg.RateLimit.Default,
+				"domains": cfg.RateLimit.Domains,
+			},
+			"network": gin.H{
+				"proxy":      cfg.Network.Proxy,
+				"proxies":    cfg.Network.Proxies,
+				"user_agent": cfg.Network.UserAgent,
+				"cookies":    cfg.Network.Cookies,
+			},
+			"download": gin.H{
+				"connections":       cfg.Download.EffectiveConnections(),
+				"on_duplicate":      cfg.Download.EffectiveOnDuplicate(""),
+				"hook":              cfg.Download.Hook,
+				"subtitles":         cfg.Download.Subtitles,
+				"site_limits":       cfg.Download.SiteLimits,
+				"allow_sleep":       cfg.Download.AllowSleep,
+				"temp_max_age_days": int(cfg.Download.TempMaxAge().Hours() / 24),
+				"watch_dir":         cfg.Download.WatchDir,
+				"normalize_audio":   cfg.Download.NormalizeAudio,
+				"loudness_target":   cfg.Download.LoudnessTarget,
+				"tag_metadata":      cfg.Download.TagMetadata,
+				"max_conversions":   cfg.Download.EffectiveMaxConversions(),
+				"split_minutes":     cfg.Download.SplitMinutes,
+			},
+			"upload": gin.H{
+				"default":      cfg.Upload.Default,
+				"delete_local": cfg.Upload.DeleteLocal,
+				"targets":      uploadTargetNames(cfg),
+			},
 		},
 		Message: "config retrieved",
 	})
 }
 
+// uploadTargetNames lists configured upload targets by name and type, without credentials
+func uploadTargetNames(cfg *config.Config) map[string]string {
+	targets := make(map[string]string)
+	for name, t := range cfg.Upload.Targets {
+		targets[name] = t.Type
+	}
+	return targets
+}
+
 func (s *Server) handleSetConfig(c *gin.Context) {
 	var req ConfigSetRequest
 	if err := c.ShouldBindJSON(&req); err != nil {
@@ -613,6 +1187,16 @@ func (s *Server) handleSetConfig(c *gin.Context) {
 	// Update server's cached config
 	s.cfg = cfg
 
+	// A new concurrency limit applies to the running queue
+	if req.Key == "server.max_concurrent" || req.Key == "server_max_concurrent" {
+		s.jobQueue.SetMaxConcurrent(cfg.Server.MaxConcurrent)
+	}
+
+	// Schedule and bandwidth cap changes may pause or resume the queue
+	if strings.HasPrefix(req.Key, "schedule.") || strings.HasPrefix(req.Key, "bandwidth.") {
+		s.jobQueue.recheckSchedule()
+	}
+
 	// Special handling for output_dir
 	if req.Key == "output_dir" {
 		if err := os.MkdirAll(req.Value, 0755); err != nil {
@@ -871,6 +1455,7 @@ type TorrentConfigRequest struct {
 	Password        string `json:"password"`
 	UseHTTPS        bool   `json:"use_https"`
 	DefaultSavePath string `json:"default_save_path"`
+	Seed            bool   `json:"seed"`
 }
 
 // TorrentAddRequest is the request body for POST /torrent
@@ -893,6 +1478,7 @@ func (s *Server) handleGetTorrentConfig(c *gin.Context) {
 			"password":          cfg.Torrent.Password,
 			"use_https":         cfg.Torrent.UseHTTPS,
 			"default_save_path": cfg.Torrent.DefaultSavePath,
+			"seed":              cfg.Torrent.Seed,
 		},
 		Message: "torrent config retrieved",
 	})
@@ -942,6 +1528,7 @@ func (s *Server) handleSetTorrentConfig(c *gin.Context) {
 		Password:        req.Password,
 		UseHTTPS:        req.UseHTTPS,
 		DefaultSavePath: req.DefaultSavePath,
+		Seed:            req.Seed,
 	}
 
 	if err := config.Save(cfg); err != nil {
@@ -1181,6 +1768,50 @@ func (s *Server) setConfigValue(cfg *config.Config, key, value string) error {
 		return nil
 	}
 
+	// Handle schedule.* keys
+	if strings.HasPrefix(key, "schedule.") {
+		return config.SetScheduleValue(&cfg.Schedule, key, value)
+	}
+
+	// Handle archive.* keys
+	if strings.HasPrefix(key, "archive.") {
+		return config.SetArchiveValue(&cfg.Archive, key, value)
+	}
+
+	// Handle upload.* keys
+	if strings.HasPrefix(key, "upload.") {
+		return config.SetUploadValue(&cfg.Upload, key, value)
+	}
+
+	// Handle bandwidth.* keys
+	if strings.HasPrefix(key, "bandwidth.") {
+		return config.SetBandwidthValue(&cfg.Bandwidth, key, value)
+	}
+
+	// Handle ytdlp.* keys
+	if strings.HasPrefix(key, "ytdlp.") {
+		return config.SetYtdlpValue(&cfg.Ytdlp, key, value)
+	}
+
+	// Handle rate_limit.* keys
+	if strings.HasPrefix(key, "rate_limit.") {
+		return config.SetRateLimitValue(&cfg.RateLimit, key, value)
+	}
+
+	// Handle network.* keys
+	if strings.HasPrefix(key, "network.") {
+		return config.SetNetworkValue(&cfg.Network, key, value)
+	}
+
+	// Handle download.* keys. The hook runs shell commands on this machine, so
+	// it can't be changed over the API.
+	if key == "download.hook" {
+		return fmt.Errorf("download.hook can only be set with 'vget config set' or in config.yml")
+	}
+	if strings.HasPrefix(key, "download.") {
+		return config.SetDownloadValue(&cfg.Download, key, value)
+	}
+
 	switch key {
 	case "language":
 		cfg.Language = value
@@ -1190,8 +1821,20 @@ func (s *Server) setConfigValue(cfg *config.Config, key, value string) error {
 		cfg.Format = value
 	case "quality":
 		cfg.Quality = value
+	case "on_complete":
+		action, err := opener.ParseAction(value)
+		if err != nil {
+			return err
+		}
+		cfg.OnComplete = string(action)
+	case "update_channel":
+		channel, err := updater.ParseChannel(value)
+		if err != nil {
+			return err
+		}
+		cfg.UpdateChannel = channel
 	case "twitter_auth_token", "twitter.auth_token":
-		cfg.Twitter.AuthToken = value
+		return auth.SetToken(auth.Twitter, "auth_token", value)
 	case "server.max_concurrent", "server_max_concurrent":
 		var val int
 		if _, err := fmt.Sscanf(value, "%d", &val); err != nil {
@@ -1201,7 +1844,7 @@ func (s *Server) setConfigValue(cfg *config.Config, key, value string) error {
 	case "server.api_key", "server_api_key":
 		cfg.Server.APIKey = value
 	case "bilibili.cookie", "bilibili_cookie":
-		cfg.Bilibili.Cookie = value
+		return auth.SetCookie(auth.Bilibili, value)
 	default:
 		return fmt.Errorf("unknown config key: %s", key)
 	}
@@ -1209,7 +1852,7 @@ func (s *Server) setConfigValue(cfg *config.Config, key, value string) error {
 }
 
 // downloadWebDAV handles WebDAV URL downloads using multi-stream for better performance
-func (s *Server) downloadWebDAV(ctx context.Context, rawURL, filename string, progressFn func(downloaded, total int64)) error {
+func (s *Server) downloadWebDAV(ctx context.Context, rawURL string, opts DownloadOptions, progressFn func(downloaded, total int64)) error {
 	var client *webdav.Client
 	var filePath string
 	var err error
@@ -1242,15 +1885,15 @@ func (s *Server) downloadWebDAV(ctx context.Context, rawURL, filename string, pr
 		}
 
 		// Determine output filename
-		outputFile := filename
+		outputFile := opts.Filename
 		if outputFile == "" {
 			outputFile = webdav.ExtractFilename(filePath)
 		}
 		// Sanitize the filename to remove invalid path characters
-		outputPath := filepath.Join(s.outputDir, extractor.SanitizeFilename(outputFile))
+		outputPath := filepath.Join(s.outputDir, sanitize.Filename(outputFile))
 
 		// Update job filename
-		s.updateJobFilename(rawURL, outputPath)
+		s.updateJobFilename(opts.JobID, outputPath)
 
 		// Download using multi-stream for better performance (same as CLI)
 		fileURL := client.GetFileURL(filePath)
@@ -1279,14 +1922,14 @@ func (s *Server) downloadWebDAV(ctx context.Context, rawURL, filename string, pr
 		return fmt.Errorf("cannot download directory: %s", filePath)
 	}
 
-	outputFile := filename
+	outputFile := opts.Filename
 	if outputFile == "" {
 		outputFile = webdav.ExtractFilename(filePath)
 	}
 	// Sanitize the filename to remove invalid path characters
-	outputPath := filepath.Join(s.outputDir, extractor.SanitizeFilename(outputFile))
+	outputPath := filepath.Join(s.outputDir, sanitize.Filename(outputFile))
 
-	s.updateJobFilename(rawURL, outputPath)
+	s.updateJobFilename(opts.JobID, outputPath)
 
 	fileURL := client.GetFileURL(filePath)
 	authHeader := client.GetAuthHeader()
@@ -1301,55 +1944,130 @@ func downloadWebDAVMultiStream(ctx context.Context, url, authHeader, outputPath
 }
 
 // downloadWithExtractor is the download function used by the job queue
-func (s *Server) downloadWithExtractor(ctx context.Context, url, filename string, progressFn func(downloaded, total int64)) error {
+func (s *Server) downloadWithExtractor(ctx context.Context, url string, opts DownloadOptions, progressFn func(downloaded, total int64)) error {
 	// Handle WebDAV URLs specially
 	if webdav.IsWebDAVURL(url) {
-		return s.downloadWebDAV(ctx, url, filename, progressFn)
+		return s.downloadWebDAV(ctx, url, opts, progressFn)
 	}
 
-	// Find matching extractor
-	ext := extractor.Match(url)
-	if ext == nil {
-		sitesConfig, _ := config.LoadSites()
-		if sitesConfig != nil {
-			if site := sitesConfig.MatchSite(url); site != nil {
-				ext = extractor.NewBrowserExtractor(site, false)
-			}
+	if torrent.IsMagnetLink(url) || torrent.IsTorrentFileURL(url) {
+		return s.downloadTorrent(ctx, url, opts.JobID, progressFn)
+	}
+
+	if len(opts.Mirrors) == 0 {
+		return s.downloadCandidate(ctx, url, url, opts, progressFn)
+	}
+
+	// The archive check and a full disk give the same result for every mirror
+	var final error
+	source, err := downloader.TryMirrors(ctx, append([]string{url}, opts.Mirrors...), func(candidate string) error {
+		err := s.downloadCandidate(ctx, url, candidate, opts, progressFn)
+		if errors.Is(err, ErrAlreadyDownloaded) || errors.Is(err, ErrSkippedDuplicate) {
+			final = err
+			return nil
 		}
-		if ext == nil {
-			ext = extractor.NewGenericBrowserExtractor(false)
+		if err != nil {
+			log.Printf("Download from %s failed: %v", candidate, err)
 		}
+		return err
+	})
+	if final != nil {
+		return final
 	}
+	if err == nil && source != url {
+		s.updateJobSource(opts.JobID, source)
+	}
+	return err
+}
 
-	// Configure Twitter extractor with auth if available
-	if twitterExt, ok := ext.(*extractor.TwitterExtractor); ok {
-		if s.cfg.Twitter.AuthToken != "" {
-			twitterExt.SetAuth(s.cfg.Twitter.AuthToken)
-		}
+// downloadCandidate extracts and downloads candidate, one of the URLs of the
+// job for url
+func (s *Server) downloadCandidate(ctx context.Context, url, candidate string, opts DownloadOptions, progressFn func(downloaded, total int64)) error {
+	if ftp.IsFTPURL(candidate) {
+		return s.downloadFTP(ctx, url, candidate, opts, progressFn)
+	}
+
+	// Follow short links (b23.tv, t.co, ...) so the right extractor matches
+	target, chain := extractor.ResolveShortURL(candidate)
+	if len(chain) > 0 {
+		log.Printf("Resolved %s -> %s", candidate, target)
 	}
 
 	// Extract media info
-	media, err := ext.Extract(url)
+	ext := s.resolveExtractor(target)
+	media, err := extractor.Extract(ext, target, nil)
 	if err != nil {
 		return fmt.Errorf("extraction failed: %w", err)
 	}
+	extractor.SetRedirectChain(media, chain)
+	if reason := extractor.DRMReason(media); reason != "" {
+		return errors.New(reason)
+	}
+	s.updateJobMedia(opts.JobID, ext, media)
+
+	// Playlists queue their entries, which are checked one by one
+	if _, ok := media.(*extractor.PlaylistMedia); ok {
+		return s.downloadMedia(ctx, url, media, opts, progressFn)
+	}
+
+	entry := downloaded.NewEntry(ext, media, target)
+	if s.downloadArchive != nil {
+		if found, err := s.downloadArchive.Find(entry); err != nil {
+			log.Printf("Download archive: %v", err)
+		} else if found != nil {
+			switch config.LoadOrDefault().Download.EffectiveOnDuplicate(opts.OnDuplicate) {
+			case config.DuplicateSkip:
+				s.updateJobFilename(opts.JobID, found.Filename)
+				return ErrSkippedDuplicate
+			case config.DuplicatePrompt:
+				return fmt.Errorf("%w on %s", ErrAlreadyDownloaded, found.DownloadedAt.Format("2006-01-02 15:04"))
+			}
+		}
+	}
+
+	if err := s.downloadMedia(ctx, url, media, opts, progressFn); err != nil {
+		return err
+	}
+
+	if s.downloadArchive != nil {
+		entry.Filename = s.jobFilename(opts.JobID)
+		if err := s.downloadArchive.Add(entry); err != nil {
+			log.Printf("Download archive: %v", err)
+		}
+	}
+	return nil
+}
+
+// downloadMedia downloads extracted media for the job of url
+func (s *Server) downloadMedia(ctx context.Context, url string, media extractor.Media, opts DownloadOptions, progressFn func(downloaded, total int64)) (err error) {
+	filename := opts.Filename
 
 	// Determine output path based on media type
 	var outputPath string
 	var downloadURL string
+	var mirrors []string
 	var headers map[string]string
+	var hls bool // split by the post-processing once the rest is done
 
 	switch m := media.(type) {
 	case *extractor.YouTubeDirectDownload:
+		s.updateJobStage(opts.JobID, JobStageVideo)
 		return extractor.DownloadWithYtdlpProgress(ctx, m.URL, s.outputDir, progressFn)
 
 	case *extractor.VideoMedia:
 		if len(m.Formats) == 0 {
 			return fmt.Errorf("no video formats available")
 		}
-		format := selectBestFormat(m.Formats)
+		if m.Locked != nil {
+			log.Printf("%s: %s", url, m.Locked.Note())
+		}
+		format := extractor.MatchQuality(m.Formats, opts.Quality)
+		if format == nil {
+			format = selectBestFormat(m.Formats)
+		}
 		downloadURL = format.URL
-		headers = format.Headers
+		mirrors = format.Mirrors
+		headers = requestHeaders(opts, format.Headers)
 
 		ext := format.Ext
 		if ext == "m3u8" {
@@ -1358,56 +2076,87 @@ func (s *Server) downloadWithExtractor(ctx context.Context, url, filename string
 
 		if filename != "" {
 			// Sanitize the provided filename to remove invalid path characters
-			sanitized := extractor.SanitizeFilename(filename)
+			sanitized := sanitize.Filename(filename)
 			// Ensure the filename has the correct extension
 			if !strings.HasSuffix(strings.ToLower(sanitized), "."+ext) {
 				sanitized = fmt.Sprintf("%s.%s", sanitized, ext)
 			}
 			outputPath = filepath.Join(s.outputDir, sanitized)
 		} else {
-			title := extractor.SanitizeFilename(m.Title)
+			title := sanitize.Filename(m.Title)
 			if title != "" {
 				outputPath = filepath.Join(s.outputDir, fmt.Sprintf("%s.%s", title, ext))
 			} else {
 				outputPath = filepath.Join(s.outputDir, fmt.Sprintf("%s.%s", m.ID, ext))
 			}
 		}
-
-		s.updateJobFilename(url, outputPath)
+		outputPath = freeOutputPath(sanitize.Path(outputPath), opts.OnDuplicate)
+
+		s.updateJobFilename(opts.JobID, outputPath)
+		s.updateJobStage(opts.JobID, JobStageVideo)
+
+		// Chapters, tags, and subtitle sidecars follow the final name, which
+		// HLS downloads may change. Merging embeds the chapters itself.
+		defer func() {
+			if err == nil {
+				s.updateJobStage(opts.JobID, JobStagePostProcess)
+				if format.AudioURL == "" {
+					s.embedChapters(ctx, m.Chapters, s.jobFilename(opts.JobID))
+				}
+				s.tagMetadata(ctx, m, s.jobFilename(opts.JobID))
+				subs := saveSubtitles(m.Subtitles, s.jobFilename(opts.JobID))
+				if hls && s.splitRecording(ctx, opts.JobID) {
+					return
+				}
+				err = s.archiveVideo(opts.JobID, m, subs)
+			}
+		}()
 
 		// Handle separate audio stream (e.g., Bilibili DASH)
 		if format.AudioURL != "" {
-			return s.downloadVideoWithAudio(ctx, format, outputPath, progressFn)
+			withHeaders := *format
+			withHeaders.Headers = headers
+			return s.downloadVideoWithAudio(ctx, opts.JobID, &withHeaders, m.Chapters, outputPath, progressFn)
 		}
 
 	case *extractor.AudioMedia:
 		downloadURL = m.URL
+		headers = requestHeaders(opts, nil)
 
 		if filename != "" {
 			// Sanitize the provided filename to remove invalid path characters
-			sanitized := extractor.SanitizeFilename(filename)
+			sanitized := sanitize.Filename(filename)
 			// Ensure the filename has the correct extension
 			if !strings.HasSuffix(strings.ToLower(sanitized), "."+m.Ext) {
 				sanitized = fmt.Sprintf("%s.%s", sanitized, m.Ext)
 			}
 			outputPath = filepath.Join(s.outputDir, sanitized)
 		} else {
-			title := extractor.SanitizeFilename(m.Title)
+			title := sanitize.Filename(m.Title)
 			if title != "" {
 				outputPath = filepath.Join(s.outputDir, fmt.Sprintf("%s.%s", title, m.Ext))
 			} else {
 				outputPath = filepath.Join(s.outputDir, fmt.Sprintf("%s.%s", m.ID, m.Ext))
 			}
 		}
+		outputPath = freeOutputPath(sanitize.Path(outputPath), opts.OnDuplicate)
+
+		s.updateJobFilename(opts.JobID, outputPath)
+		s.updateJobStage(opts.JobID, JobStageAudio)
 
-		s.updateJobFilename(url, outputPath)
+		defer func() {
+			if err == nil {
+				s.embedCoverArt(ctx, m.Thumbnail, s.jobFilename(opts.JobID))
+				s.tagMetadata(ctx, m, s.jobFilename(opts.JobID))
+			}
+		}()
 
 	case *extractor.ImageMedia:
 		if len(m.Images) == 0 {
 			return fmt.Errorf("no images available")
 		}
 
-		title := extractor.SanitizeFilename(m.Title)
+		title := sanitize.Filename(m.Title)
 		var filenames []string
 
 		for i, img := range m.Images {
@@ -1426,16 +2175,34 @@ func (s *Server) downloadWithExtractor(ctx context.Context, url, filename string
 				}
 			}
 
+			imgPath = freeOutputPath(imgPath, opts.OnDuplicate)
 			filenames = append(filenames, imgPath)
 
-			if err := downloadFile(ctx, img.URL, imgPath, nil, nil); err != nil {
+			if err := downloadFile(ctx, img.URL, imgPath, requestHeaders(opts, nil), nil); err != nil {
 				return fmt.Errorf("failed to download image %d: %w", i+1, err)
 			}
 		}
 
-		s.updateJobFilename(url, strings.Join(filenames, ", "))
+		// Package galleries into a single archive if enabled in config
+		if cfg := config.LoadOrDefault(); cfg.Archive.Enabled && len(filenames) > 1 {
+			s.updateJobStage(opts.JobID, JobStagePostProcess)
+			archivePath, err := archive.Package(filenames, s.outputDir, cfg.Archive, archiveVars(m))
+			if err != nil && !errors.Is(err, archive.ErrOriginalsKept) {
+				return fmt.Errorf("failed to create archive: %w", err)
+			}
+			if err != nil {
+				log.Printf("Archiving %s: %v", archivePath, err)
+			}
+			s.updateJobFilename(opts.JobID, archivePath)
+			return nil
+		}
+
+		s.updateJobFiles(opts.JobID, filenames)
 		return nil
 
+	case *extractor.PlaylistMedia:
+		return s.queuePlaylist(opts.JobID, m)
+
 	default:
 		return fmt.Errorf("unsupported media type")
 	}
@@ -1447,31 +2214,254 @@ func (s *Server) downloadWithExtractor(ctx context.Context, url, filename string
 		if err != nil {
 			return err
 		}
+		hls = true
+		finalPath = s.remuxTS(ctx, finalPath)
 		if finalPath != outputPath {
-			s.updateJobFilename(url, finalPath)
+			s.updateJobFilename(opts.JobID, finalPath)
 		}
 		return nil
 	}
 
-	return downloadFile(ctx, downloadURL, outputPath, headers, progressFn)
+	source, err := downloader.TryMirrors(ctx, append([]string{downloadURL}, mirrors...), func(u string) error {
+		return downloadFile(ctx, u, outputPath, headers, progressFn)
+	})
+	if err == nil && source != downloadURL {
+		s.updateJobSource(opts.JobID, source)
+	}
+	return err
 }
 
-func (s *Server) updateJobFilename(url, filename string) {
-	jobs := s.jobQueue.GetAllJobs()
-	for _, job := range jobs {
-		if job.URL == url {
-			s.jobQueue.mu.Lock()
-			if j, ok := s.jobQueue.jobs[job.ID]; ok {
-				j.Filename = filename
-			}
-			s.jobQueue.mu.Unlock()
-			break
+// requestHeaders layers the headers for a job's download request: the stored
+// login of the job's site, then the extractor's headers, then the job's own
+func requestHeaders(opts DownloadOptions, extracted map[string]string) map[string]string {
+	var site map[string]string
+	if opts.Site != "" {
+		var err error
+		if site, err = extractor.SiteHeaders(opts.Site); err != nil {
+			log.Printf("Site login headers: %v", err)
+		}
+	}
+	return extractor.LayerHeaders(site, extracted, opts.Headers)
+}
+
+// freeOutputPath moves the output to a free "name (n).ext" when a file is
+// already there, unless the job's duplicate policy is overwrite
+func freeOutputPath(path, onDuplicate string) string {
+	if config.LoadOrDefault().Download.EffectiveOnDuplicate(onDuplicate) == config.DuplicateOverwrite {
+		return path
+	}
+	return downloader.UniquePath(path)
+}
+
+// updateJobSource records the mirror job jobID was downloaded from
+func (s *Server) updateJobSource(jobID, source string) {
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	if job, ok := jq.jobs[jobID]; ok {
+		job.Source = source
+		jq.publishJobLocked(job)
+	}
+}
+
+// updateJobStage records the step job jobID is in
+func (s *Server) updateJobStage(jobID string, stage JobStage) {
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	if job, ok := jq.jobs[jobID]; ok && job.Status == JobStatusDownloading && job.Stage != stage {
+		job.Stage, job.StageProgress = stage, 0
+		jq.publishJobLocked(job)
+	}
+}
+
+// updateJobStageProgress records how far job jobID is through its current
+// step, for steps like merging that don't move its byte progress
+func (s *Server) updateJobStageProgress(jobID string, progress float64) {
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	job, ok := jq.jobs[jobID]
+	if !ok || job.Status != JobStatusDownloading {
+		return
+	}
+	job.StageProgress = progress
+	job.UpdatedAt = time.Now()
+	if job.UpdatedAt.Sub(job.lastEventAt) >= progressEventInterval {
+		jq.publishJobLocked(job)
+	}
+}
+
+// updateJobMedia records what was extracted for job jobID, for history
+func (s *Server) updateJobMedia(jobID string, ext extractor.Extractor, media extractor.Media) {
+	duration := 0
+	switch m := media.(type) {
+	case *extractor.VideoMedia:
+		duration = m.Duration
+	case *extractor.AudioMedia:
+		duration = m.Duration
+	}
+
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	if job, ok := jq.jobs[jobID]; ok {
+		job.title = media.GetTitle()
+		job.duration = duration
+		if ext != nil {
+			job.extractor = ext.Name()
 		}
 	}
 }
 
-// downloadVideoWithAudio downloads video and audio in parallel then merges them with ffmpeg
-func (s *Server) downloadVideoWithAudio(ctx context.Context, format *extractor.VideoFormat, outputPath string, progressFn func(downloaded, total int64)) error {
+// jobFilename returns the output file of job jobID
+func (s *Server) jobFilename(jobID string) string {
+	if job := s.jobQueue.GetJob(jobID); job != nil {
+		return job.Filename
+	}
+	return ""
+}
+
+// updateJobFilename records the output file of job jobID, "" for none
+func (s *Server) updateJobFilename(jobID, filename string) {
+	var files []string
+	if filename != "" {
+		files = []string{filename}
+	}
+	s.updateJobFiles(jobID, files)
+}
+
+// updateJobFiles records the output files of job jobID, such as the images
+// of a gallery
+func (s *Server) updateJobFiles(jobID string, files []string) {
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	if job, ok := jq.jobs[jobID]; ok {
+		job.Files = files
+		job.Filename = strings.Join(files, ", ")
+	}
+}
+
+// embedChapters writes chapters into a downloaded video that wasn't merged,
+// as merging embeds them already. A failure doesn't fail the job.
+func (s *Server) embedChapters(ctx context.Context, chapters []extractor.Chapter, videoPath string) {
+	if len(chapters) == 0 || videoPath == "" {
+		return
+	}
+	err := s.runFFmpeg(ctx, "chapters", videoPath, videoPath, func(ctx context.Context, _ ffmpeg.ProgressFunc) error {
+		return downloader.EmbedChapters(ctx, videoPath, chapters)
+	})
+	if err != nil {
+		log.Printf("Chapters for %s: %v", videoPath, err)
+	}
+}
+
+// embedCoverArt makes the thumbnail the cover art of a finished audio
+// download when ffmpeg is available. A failure doesn't fail the job.
+func (s *Server) embedCoverArt(ctx context.Context, thumbURL, path string) {
+	if path == "" || thumbURL == "" || !downloader.FFmpegAvailable() {
+		return
+	}
+	err := s.runFFmpeg(ctx, "cover", path, path, func(ctx context.Context, _ ffmpeg.ProgressFunc) error {
+		return downloader.EmbedCoverArt(ctx, path, thumbURL)
+	})
+	if err != nil {
+		log.Printf("Cover art for %s: %v", path, err)
+	}
+}
+
+// remuxTS moves an HLS download ffmpeg couldn't join into MP4 from MPEG-TS
+// into a faststart MP4, returning where the download ends up
+func (s *Server) remuxTS(ctx context.Context, path string) string {
+	if !strings.EqualFold(filepath.Ext(path), ".ts") || !downloader.FFmpegAvailable() {
+		return path
+	}
+	mp4Path := path
+	err := s.runFFmpeg(ctx, "remux", path, ffmpeg.SwapExt(path, ffmpeg.ContainerMP4), func(ctx context.Context, _ ffmpeg.ProgressFunc) error {
+		var err error
+		mp4Path, err = downloader.RemuxTS(ctx, path)
+		return err
+	})
+	if err != nil {
+		log.Printf("Remuxing %s to MP4: %v", path, err)
+	}
+	return mp4Path
+}
+
+// splitRecording splits the finished HLS recording of job jobID into
+// parts when download.split_minutes is set and it's longer. A failure keeps
+// the recording whole. Reports whether the recording was split.
+func (s *Server) splitRecording(ctx context.Context, jobID string) bool {
+	minutes := config.LoadOrDefault().Download.SplitMinutes
+	path := s.jobFilename(jobID)
+	if minutes <= 0 || path == "" || !downloader.FFmpegAvailable() {
+		return false
+	}
+	var parts []string
+	err := s.runFFmpeg(ctx, "split", path, path, func(ctx context.Context, _ ffmpeg.ProgressFunc) error {
+		var err error
+		parts, err = downloader.SplitRecording(ctx, path, time.Duration(minutes)*time.Minute)
+		return err
+	})
+	if err != nil {
+		log.Printf("Splitting %s: %v", path, err)
+		return false
+	}
+	if len(parts) > 1 {
+		s.updateJobFiles(jobID, parts)
+		return true
+	}
+	return false
+}
+
+// archiveVideo zips the finished video of job jobID with its subtitles
+// and thumbnail when archive.enabled is set, and makes the archive the job's file
+func (s *Server) archiveVideo(jobID string, m *extractor.VideoMedia, subtitles []string) error {
+	cfg := config.LoadOrDefault()
+	path := s.jobFilename(jobID)
+	if !cfg.Archive.Enabled || path == "" {
+		return nil
+	}
+	archivePath, err := archive.PackageVideo(path, subtitles, m.Thumbnail, s.outputDir, cfg.Archive, archiveVars(m))
+	if err != nil && !errors.Is(err, archive.ErrOriginalsKept) {
+		return fmt.Errorf("failed to create archive: %w", err)
+	}
+	if err != nil {
+		log.Printf("Archiving %s: %v", archivePath, err)
+	}
+	if archivePath != "" {
+		s.updateJobFilename(jobID, archivePath)
+	}
+	return nil
+}
+
+// archiveVars fills the archive name template values from media
+func archiveVars(media extractor.Media) archive.NameVars {
+	return archive.NameVars{
+		Title:    sanitize.Filename(media.GetTitle()),
+		ID:       media.GetID(),
+		Uploader: sanitize.Filename(media.GetUploader()),
+	}
+}
+
+// tagMetadata writes the title, uploader, date, and description of media into
+// a finished download when download.tag_metadata is on. A failure doesn't fail
+// the job.
+func (s *Server) tagMetadata(ctx context.Context, media extractor.Media, path string) {
+	if path == "" || !config.LoadOrDefault().Download.TagMetadata {
+		return
+	}
+	err := s.runFFmpeg(ctx, "metadata", path, path, func(ctx context.Context, _ ffmpeg.ProgressFunc) error {
+		return downloader.TagMetadata(ctx, path, downloader.MediaMetadata(media))
+	})
+	if err != nil {
+		log.Printf("Metadata for %s: %v", path, err)
+	}
+}
+
+// downloadVideoWithAudio downloads video and audio in parallel then merges them (with chapters) using ffmpeg
+func (s *Server) downloadVideoWithAudio(ctx context.Context, jobID string, format *extractor.VideoFormat, chapters []extractor.Chapter, outputPath string, progressFn func(downloaded, total int64)) error {
 	// Determine audio extension based on video format
 	audioExt := "m4a"
 	if format.Ext == "webm" {
@@ -1501,44 +2491,81 @@ func (s *Server) downloadVideoWithAudio(ctx context.Context, format *extractor.V
 		}
 	}
 
-	// Download video and audio in parallel
+	// Download video and audio in parallel, each falling back to its mirrors.
+	// The first failure stops the other stream instead of letting it finish.
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
 	var wg sync.WaitGroup
-	var videoErr, audioErr error
+	var firstErr error
+	var failOnce sync.Once
+	fail := func(err error) {
+		failOnce.Do(func() {
+			firstErr = err
+			cancel()
+		})
+	}
+	var videoSource, audioSource string
 
 	wg.Add(2)
 
 	// Download video stream
 	go func() {
 		defer wg.Done()
-		videoErr = downloadFile(ctx, format.URL, videoFile, format.Headers, func(downloaded, total int64) {
-			mu.Lock()
-			videoDownloaded = downloaded
-			videoTotal = total
-			mu.Unlock()
-			reportProgress()
+		var err error
+		videoSource, err = downloader.TryMirrors(ctx, format.URLs(), func(u string) error {
+			return downloadFile(ctx, u, videoFile, format.Headers, func(downloaded, total int64) {
+				mu.Lock()
+				videoDownloaded = downloaded
+				videoTotal = total
+				mu.Unlock()
+				reportProgress()
+			})
 		})
+		if err != nil {
+			fail(fmt.Errorf("failed to download video stream: %w", err))
+			return
+		}
+		// The audio stream may still be going
+		mu.Lock()
+		audioDone := audioTotal > 0 && audioDownloaded >= audioTotal
+		mu.Unlock()
+		if !audioDone {
+			s.updateJobStage(jobID, JobStageAudio)
+		}
 	}()
 
 	// Download audio stream
 	go func() {
 		defer wg.Done()
-		audioErr = downloadFile(ctx, format.AudioURL, audioFile, format.Headers, func(downloaded, total int64) {
-			mu.Lock()
-			audioDownloaded = downloaded
-			audioTotal = total
-			mu.Unlock()
-			reportProgress()
+		var err error
+		audioSource, err = downloader.TryMirrors(ctx, format.AudioURLs(), func(u string) error {
+			return downloadFile(ctx, u, audioFile, format.Headers, func(downloaded, total int64) {
+				mu.Lock()
+				audioDownloaded = downloaded
+				audioTotal = total
+				mu.Unlock()
+				reportProgress()
+			})
 		})
+		if err != nil {
+			fail(fmt.Errorf("failed to download audio stream: %w", err))
+		}
 	}()
 
 	wg.Wait()
+	if firstErr != nil {
+		return firstErr
+	}
 
-	// Check for errors
-	if videoErr != nil {
-		return fmt.Errorf("failed to download video stream: %w", videoErr)
+	var sources []string
+	if videoSource != format.URL {
+		sources = append(sources, videoSource)
+	}
+	if audioSource != format.AudioURL {
+		sources = append(sources, audioSource)
 	}
-	if audioErr != nil {
-		return fmt.Errorf("failed to download audio stream: %w", audioErr)
+	if len(sources) > 0 {
+		s.updateJobSource(jobID, strings.Join(sources, ", "))
 	}
 
 	// Merge with ffmpeg
@@ -1546,7 +2573,16 @@ func (s *Server) downloadVideoWithAudio(ctx context.Context, format *extractor.V
 		return fmt.Errorf("ffmpeg not available, video and audio downloaded separately: %s, %s", videoFile, audioFile)
 	}
 
-	_, err := downloader.MergeVideoAudioKeepOriginals(videoFile, audioFile)
+	s.updateJobStage(jobID, JobStageMerging)
+	err := s.runFFmpeg(ctx, "merge", videoFile, downloader.MergedPath(videoFile), func(ctx context.Context, progressFn ffmpeg.ProgressFunc) error {
+		_, err := downloader.MergeVideoAudioKeepOriginals(ctx, videoFile, audioFile, chapters, func(done, total time.Duration) {
+			progressFn(done, total)
+			if total > 0 {
+				s.updateJobStageProgress(jobID, float64(done)/float64(total)*100)
+			}
+		})
+		return err
+	})
 	if err != nil {
 		return fmt.Errorf("ffmpeg merge failed: %w (files: %s, %s)", err, videoFile, audioFile)
 	}
@@ -1554,28 +2590,22 @@ func (s *Server) downloadVideoWithAudio(ctx context.Context, format *extractor.V
 	return nil
 }
 
+// resolveExtractor finds the extractor for a URL (built-in, plugin, sites.yml,
+// or generic). Extractors load the stored login of their site themselves.
+func (s *Server) resolveExtractor(target string) extractor.Extractor {
+	return extractor.Resolve(target, nil)
+}
+
+// extractMedia extracts the media info of a URL
+func (s *Server) extractMedia(target string) (extractor.Media, error) {
+	return extractor.Extract(s.resolveExtractor(target), target, nil)
+}
+
 // downloadAndStream extracts and streams the file directly to the response
 func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
-	ext := extractor.Match(url)
-	if ext == nil {
-		sitesConfig, _ := config.LoadSites()
-		if sitesConfig != nil {
-			if site := sitesConfig.MatchSite(url); site != nil {
-				ext = extractor.NewBrowserExtractor(site, false)
-			}
-		}
-		if ext == nil {
-			ext = extractor.NewGenericBrowserExtractor(false)
-		}
-	}
-
-	if twitterExt, ok := ext.(*extractor.TwitterExtractor); ok {
-		if s.cfg.Twitter.AuthToken != "" {
-			twitterExt.SetAuth(s.cfg.Twitter.AuthToken)
-		}
-	}
+	url, _ = extractor.ResolveShortURL(url)
 
-	media, err := ext.Extract(url)
+	media, err := s.extractMedia(url)
 	if err != nil {
 		c.JSON(http.StatusInternalServerError, Response{
 			Code:    500,
@@ -1584,6 +2614,10 @@ func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
 		})
 		return
 	}
+	if reason := extractor.DRMReason(media); reason != "" {
+		c.JSON(http.StatusUnprocessableEntity, Response{Code: 422, Data: nil, Message: reason})
+		return
+	}
 
 	var downloadURL string
 	var headers map[string]string
@@ -1614,7 +2648,7 @@ func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
 		if filename != "" {
 			outputFilename = filename
 		} else {
-			title := extractor.SanitizeFilename(m.Title)
+			title := sanitize.Filename(m.Title)
 			ext := format.Ext
 			if ext == "m3u8" {
 				ext = "ts"
@@ -1631,7 +2665,7 @@ func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
 		if filename != "" {
 			outputFilename = filename
 		} else {
-			title := extractor.SanitizeFilename(m.Title)
+			title := sanitize.Filename(m.Title)
 			if title != "" {
 				outputFilename = fmt.Sprintf("%s.%s", title, m.Ext)
 			} else {
@@ -1653,7 +2687,7 @@ func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
 		if filename != "" {
 			outputFilename = filename
 		} else {
-			title := extractor.SanitizeFilename(m.Title)
+			title := sanitize.Filename(m.Title)
 			if title != "" {
 				outputFilename = fmt.Sprintf("%s.%s", title, img.Ext)
 			} else {
@@ -1661,6 +2695,14 @@ func (s *Server) downloadAndStream(c *gin.Context, url, filename string) {
 			}
 		}
 
+	case *extractor.PlaylistMedia:
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "playlist streaming not supported. Use queued download instead.",
+		})
+		return
+
 	default:
 		c.JSON(http.StatusInternalServerError, Response{
 			Code:    500,
@@ -1701,81 +2743,15 @@ func selectBestFormat(formats []extractor.VideoFormat) *extractor.VideoFormat {
 }
 
 func downloadFile(ctx context.Context, url, outputPath string, headers map[string]string, progressFn func(downloaded, total int64)) error {
-	client := &http.Client{
-		Timeout: 0,
-		Transport: &http.Transport{
-			Proxy: http.ProxyFromEnvironment,
-		},
-	}
-
-	req, err := http.NewRequestWithContext(ctx, "GET", url, nil)
-	if err != nil {
-		return fmt.Errorf("failed to create request: %w", err)
-	}
-
-	if len(headers) > 0 {
-		for key, value := range headers {
-			req.Header.Set(key, value)
-		}
-	} else {
-		req.Header.Set("User-Agent", downloader.DefaultUserAgent)
-	}
-
-	resp, err := client.Do(req)
-	if err != nil {
-		return fmt.Errorf("download request failed: %w", err)
-	}
-	defer resp.Body.Close()
-
-	if resp.StatusCode != http.StatusOK {
-		return fmt.Errorf("download failed with status %d", resp.StatusCode)
-	}
-
-	total := resp.ContentLength
-
-	file, err := os.Create(outputPath)
-	if err != nil {
-		return fmt.Errorf("failed to create output file: %w", err)
-	}
-	defer file.Close()
-
-	buf := make([]byte, 32*1024)
-	var downloaded int64
-
-	for {
-		select {
-		case <-ctx.Done():
-			return ctx.Err()
-		default:
-		}
-
-		n, readErr := resp.Body.Read(buf)
-		if n > 0 {
-			_, writeErr := file.Write(buf[:n])
-			if writeErr != nil {
-				return fmt.Errorf("failed to write file: %w", writeErr)
-			}
-			downloaded += int64(n)
-			if progressFn != nil {
-				progressFn(downloaded, total)
-			}
-		}
-		if readErr == io.EOF {
-			break
-		}
-		if readErr != nil {
-			return fmt.Errorf("download failed: %w", readErr)
-		}
-	}
-
-	return nil
+	connections := config.LoadOrDefault().Download.EffectiveConnections()
+	return downloader.DownloadSegmented(ctx, url, outputPath, headers, connections, progressFn, nil)
 }
 
 func streamFile(w http.ResponseWriter, url, filename string, headers map[string]string) {
 	client := &http.Client{
 		Timeout: 0,
 		Transport: &http.Transport{
-			Proxy: http.ProxyFromEnvironment,
+			Proxy: config.LoadOrDefault().Network.ProxyFunc(),
 		},
 	}
 
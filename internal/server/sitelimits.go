@@ -0,0 +1,70 @@
+package server
+
+import (
+	"net/url"
+
+	"github.com/guiyumin/vget/internal/core/config"
+)
+
+// reserveSite claims a download slot of the job's site if download.site_limits
+// caps it. A job whose site is at its limit is parked, without blocking the
+// dispatcher, until releaseSite queues it again; false is returned then.
+func (jq *JobQueue) reserveSite(job *Job) bool {
+	host := ""
+	if u, err := url.Parse(job.URL); err == nil {
+		host = u.Hostname()
+	}
+	domain, limit := config.LoadOrDefault().Download.SiteLimit(host)
+
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	job.siteSlot = ""
+	if limit <= 0 {
+		return true
+	}
+	if jq.siteRunning[domain] >= limit {
+		jq.siteWaiting[domain] = append(jq.siteWaiting[domain], job)
+		return false
+	}
+	jq.siteRunning[domain]++
+	job.siteSlot = domain
+	return true
+}
+
+// releaseSite frees the job's site slot and queues the next job parked for
+// that site. If the queue is full the job is held for startDueJobs to retry.
+func (jq *JobQueue) releaseSite(job *Job) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	domain := job.siteSlot
+	if domain == "" {
+		return
+	}
+	job.siteSlot = ""
+	if jq.siteRunning[domain]--; jq.siteRunning[domain] <= 0 {
+		delete(jq.siteRunning, domain)
+	}
+
+	waiting := jq.siteWaiting[domain]
+	for len(waiting) > 0 && !jq.stopped {
+		next := waiting[0]
+		waiting = waiting[1:]
+		// Skip jobs cancelled or resumed as a new entry while parked
+		if jq.jobs[next.ID] != next || next.Status != JobStatusQueued {
+			continue
+		}
+		select {
+		case jq.queue <- next:
+		default:
+			next.held = true
+		}
+		break
+	}
+	if len(waiting) == 0 {
+		delete(jq.siteWaiting, domain)
+	} else {
+		jq.siteWaiting[domain] = waiting
+	}
+}
@@ -0,0 +1,22 @@
+package server
+
+import (
+	"fmt"
+	"net/http"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// handleGetSites lists the sites covered by built-in extractors, plugins, and sites.yml
+func (s *Server) handleGetSites(c *gin.Context) {
+	sites := extractor.Sites()
+	if sites == nil {
+		sites = []extractor.SiteInfo{}
+	}
+	c.JSON(http.StatusOK, Response{
+		Code:    200,
+		Data:    gin.H{"sites": sites},
+		Message: fmt.Sprintf("%d extractors available", len(sites)),
+	})
+}
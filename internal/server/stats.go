@@ -0,0 +1,53 @@
+package server
+
+import (
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/history"
+)
+
+// UsageStats is the response of GET /history/stats
+type UsageStats struct {
+	Range       string     `json:"range"`
+	Since       *time.Time `json:"since,omitempty"` // nil for "all"
+	Transferred int64      `json:"transferred"`     // bytes received, including failed and cancelled jobs
+	*history.Usage
+}
+
+// handleGetStats sums up completed downloads per day and site over
+// ?range=7d|30d|month|year|all (default month)
+func (s *Server) handleGetStats(c *gin.Context) {
+	if s.jobQueue.history == nil {
+		c.JSON(http.StatusServiceUnavailable, Response{Code: 503, Data: nil, Message: "history is not available"})
+		return
+	}
+
+	name := c.DefaultQuery("range", "month")
+	if name == "" {
+		name = "all"
+	}
+	now := time.Now()
+	since, err := history.RangeStart(name, now)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	entries, err := s.jobQueue.history.Search(history.Query{Status: string(JobStatusCompleted), Since: since})
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: err.Error()})
+		return
+	}
+
+	stats := UsageStats{Range: name, Usage: history.Summarize(entries, now.Location())}
+	if !since.IsZero() {
+		stats.Since = &since
+	}
+	if s.jobQueue.bandwidth != nil {
+		stats.Transferred = s.jobQueue.bandwidth.Since(since)
+	}
+
+	c.JSON(http.StatusOK, Response{Code: 200, Data: stats, Message: "usage stats retrieved"})
+}
@@ -0,0 +1,28 @@
+package server
+
+import (
+	"log"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/extractor"
+	"github.com/guiyumin/vget/internal/core/subtitle"
+)
+
+// saveSubtitles saves the languages in download.subtitles next to a
+// downloaded video. A missing subtitle doesn't fail the job.
+func saveSubtitles(subs []extractor.Subtitle, videoPath string) []string {
+	if len(subs) == 0 || videoPath == "" {
+		return nil
+	}
+	langs := subtitle.ParseLanguages(config.LoadOrDefault().Download.Subtitles)
+	var saved []string
+	for _, sub := range subtitle.Select(subs, langs) {
+		path, err := subtitle.Save(sub, videoPath)
+		if err != nil {
+			log.Printf("Subtitles %s for %s: %v", sub.Language, videoPath, err)
+			continue
+		}
+		saved = append(saved, path)
+	}
+	return saved
+}
@@ -0,0 +1,70 @@
+package server
+
+import "time"
+
+// summaryInterval is how often the downloads summary is published
+const summaryInterval = time.Second
+
+// DownloadsSummary is the combined progress of all jobs, for a single global
+// download indicator
+type DownloadsSummary struct {
+	Active    int     `json:"active"` // jobs downloading
+	Queued    int     `json:"queued"`
+	Scheduled int     `json:"scheduled"` // queued jobs waiting for their start_at
+	Speed     float64 `json:"speed"`     // combined download speed in bytes/s
+	Progress  float64 `json:"progress"`  // overall percent of the active jobs with a known size
+}
+
+// Summary returns the combined progress of all jobs
+func (jq *JobQueue) Summary() DownloadsSummary {
+	jq.mu.RLock()
+	defer jq.mu.RUnlock()
+
+	var summary DownloadsSummary
+	var downloaded, total int64
+	for _, job := range jq.jobs {
+		switch job.Status {
+		case JobStatusQueued:
+			if job.held {
+				summary.Scheduled++
+			} else {
+				summary.Queued++
+			}
+		case JobStatusDownloading:
+			summary.Active++
+			summary.Speed += job.Speed
+			if job.Total > 0 {
+				downloaded += job.Downloaded
+				total += job.Total
+			}
+		}
+	}
+	if total > 0 {
+		summary.Progress = float64(downloaded) / float64(total) * 100
+	}
+	return summary
+}
+
+// summaryLoop publishes the downloads summary while jobs are active or queued,
+// and once more when the queue empties so indicators can clear. Jobs waiting
+// for a later start time alone don't keep it publishing.
+func (jq *JobQueue) summaryLoop() {
+	ticker := time.NewTicker(summaryInterval)
+	defer ticker.Stop()
+
+	wasIdle := true
+	for {
+		select {
+		case <-ticker.C:
+			summary := jq.Summary()
+			idle := summary.Active == 0 && summary.Queued == 0
+			if idle && wasIdle {
+				continue
+			}
+			wasIdle = idle
+			jq.events.Publish(Event{Type: EventDownloadsSummary, Data: summary})
+		case <-jq.stopCleanup:
+			return
+		}
+	}
+}
@@ -0,0 +1,84 @@
+package server
+
+import (
+	"encoding/base64"
+	"fmt"
+	"net/http"
+	"path/filepath"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/downloader"
+	"github.com/guiyumin/vget/internal/core/extractor"
+)
+
+// ThumbnailRequest is the request body for POST /thumbnail
+type ThumbnailRequest struct {
+	URL    string `json:"url" binding:"required"`
+	Index  int    `json:"index"`  // index into the thumbnails list, 0 is the largest
+	Output string `json:"output"` // media file to save the thumbnail next to; empty returns it as base64
+}
+
+// handleThumbnail lists a media item's thumbnails and either saves the chosen
+// one next to a downloaded file or returns it as a data URL for previews
+func (s *Server) handleThumbnail(c *gin.Context) {
+	var req ThumbnailRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: "url is required"})
+		return
+	}
+
+	target, _ := extractor.ResolveShortURL(req.URL)
+	media, err := s.extractMedia(target)
+	if err != nil {
+		c.JSON(http.StatusInternalServerError, Response{Code: 500, Data: nil, Message: fmt.Sprintf("extraction failed: %v", err)})
+		return
+	}
+
+	thumbs := extractor.Thumbnails(media)
+	if len(thumbs) == 0 {
+		c.JSON(http.StatusNotFound, Response{Code: 404, Data: nil, Message: "no thumbnail available"})
+		return
+	}
+	if req.Index < 0 || req.Index >= len(thumbs) {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: fmt.Sprintf("index must be between 0 and %d", len(thumbs)-1)})
+		return
+	}
+	chosen := thumbs[req.Index]
+
+	if req.Output != "" {
+		// Security: only write next to files in the output directory
+		absPath, err := filepath.Abs(req.Output)
+		absOutputDir, _ := filepath.Abs(s.outputDir)
+		if err != nil || !strings.HasPrefix(absPath, absOutputDir+string(filepath.Separator)) {
+			c.JSON(http.StatusForbidden, Response{Code: 403, Data: nil, Message: "access denied: file outside output directory"})
+			return
+		}
+
+		saved, err := downloader.SaveThumbnail(chosen.URL, absPath)
+		if err != nil {
+			c.JSON(http.StatusBadGateway, Response{Code: 502, Data: nil, Message: err.Error()})
+			return
+		}
+		c.JSON(http.StatusOK, Response{
+			Code:    200,
+			Data:    gin.H{"thumbnails": thumbs, "path": saved},
+			Message: "thumbnail saved",
+		})
+		return
+	}
+
+	data, mimeType, err := downloader.FetchThumbnail(chosen.URL)
+	if err != nil {
+		c.JSON(http.StatusBadGateway, Response{Code: 502, Data: nil, Message: err.Error()})
+		return
+	}
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"thumbnails": thumbs,
+			"data_url":   "data:" + mimeType + ";base64," + base64.StdEncoding.EncodeToString(data),
+		},
+		Message: "thumbnail fetched",
+	})
+}
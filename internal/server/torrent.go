@@ -0,0 +1,188 @@
+package server
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"os"
+	"path"
+	"path/filepath"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/torrent"
+)
+
+const (
+	// torrentPollInterval is how often a torrent job asks the client for progress
+	torrentPollInterval = 2 * time.Second
+
+	// torrentAddGrace is how long a torrent may be missing from the client
+	// after it's added, as qBittorrent and Synology add it asynchronously
+	torrentAddGrace = 30 * time.Second
+
+	// maxTorrentFileSize bounds a downloaded .torrent file
+	maxTorrentFileSize = 10 << 20
+)
+
+// downloadTorrent hands a magnet link or .torrent URL to the configured
+// torrent client and follows it until it's complete. The finished torrent is
+// paused unless torrent.seed is set; a cancelled one is removed with its data.
+func (s *Server) downloadTorrent(ctx context.Context, url, jobID string, progressFn func(downloaded, total int64)) error {
+	cfg := config.LoadOrDefault().Torrent
+	if !cfg.Enabled {
+		return errors.New("torrent is not enabled. Configure a torrent client in settings first")
+	}
+	client, err := s.createTorrentClient(&cfg)
+	if err != nil {
+		return fmt.Errorf("invalid torrent config: %w", err)
+	}
+	if err := client.Connect(); err != nil {
+		return fmt.Errorf("failed to connect to torrent client: %w", err)
+	}
+	defer client.Close()
+
+	opts := &torrent.AddOptions{SavePath: cfg.DefaultSavePath}
+	var result *torrent.AddResult
+	if torrent.IsMagnetLink(url) {
+		result, err = client.AddMagnet(url, opts)
+	} else {
+		result, err = addTorrentFile(ctx, client, url, opts)
+	}
+	if err != nil {
+		return fmt.Errorf("failed to add torrent: %w", err)
+	}
+	id := result.Hash
+	if id == "" {
+		id = result.ID
+	}
+	if id == "" {
+		return errors.New("torrent client did not report the torrent's hash")
+	}
+
+	added := time.Now()
+	ticker := time.NewTicker(torrentPollInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			// Leave a torrent that was in the client before this job alone
+			if !result.Duplicate {
+				if err := client.RemoveTorrent(id, true); err != nil {
+					log.Printf("Remove cancelled torrent %s: %v", id, err)
+				}
+			}
+			return ctx.Err()
+		case <-ticker.C:
+		}
+
+		info, err := client.GetTorrent(id)
+		if errors.Is(err, torrent.ErrTorrentNotFound) && time.Since(added) < torrentAddGrace {
+			continue
+		}
+		if err != nil {
+			return fmt.Errorf("torrent %s: %w", id, err)
+		}
+		if info.State == torrent.StateError {
+			return fmt.Errorf("torrent client error: %s", info.Error)
+		}
+		// The save path is on the torrent client's machine. Only a path that
+		// exists here becomes the job's file, so hooks, uploads, and
+		// verification skip torrents downloaded elsewhere.
+		if info.Name != "" {
+			if local := filepath.Join(info.SavePath, info.Name); fileExists(local) {
+				s.updateJobFilename(jobID, local)
+			}
+		}
+
+		// Size is 0 until a magnet link's metadata arrives
+		progressFn(int64(info.Progress*float64(info.Size)), info.Size)
+		if pr, ok := client.(torrent.PieceReporter); ok {
+			if pieces, err := pr.GetPieces(id); err == nil {
+				s.updateJobPieces(jobID, pieces)
+			}
+		}
+		if info.Progress < 1 {
+			continue
+		}
+		if !cfg.Seed && !result.Duplicate {
+			if err := client.PauseTorrent(id); err != nil {
+				log.Printf("Pause finished torrent %s: %v", id, err)
+			}
+		}
+		return nil
+	}
+}
+
+// updateJobPieces records which pieces of the torrent of job jobID are done
+func (s *Server) updateJobPieces(jobID string, pieces []bool) {
+	done := 0
+	for _, p := range pieces {
+		if p {
+			done++
+		}
+	}
+	bitfield := torrent.EncodeBitfield(pieces)
+
+	jq := s.jobQueue
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+	job, ok := jq.jobs[jobID]
+	if !ok || job.Status != JobStatusDownloading {
+		return
+	}
+	if job.Pieces != bitfield || job.PieceCount != len(pieces) {
+		job.PieceCount, job.PiecesDone, job.Pieces = len(pieces), done, bitfield
+		jq.publishJobLocked(job)
+	}
+}
+
+// addTorrentFile fetches the .torrent file at url and adds it, with its info
+// hash so the torrent can be followed in clients that don't report one
+func addTorrentFile(ctx context.Context, client torrent.Client, url string, opts *torrent.AddOptions) (*torrent.AddResult, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return nil, err
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("fetching .torrent file failed with status %d", resp.StatusCode)
+	}
+	data, err := io.ReadAll(io.LimitReader(resp.Body, maxTorrentFileSize))
+	if err != nil {
+		return nil, err
+	}
+	hash, err := torrent.InfoHash(data)
+	if err != nil {
+		return nil, err
+	}
+
+	tmp, err := os.CreateTemp("", "vget-*-"+path.Base(req.URL.Path))
+	if err != nil {
+		return nil, err
+	}
+	defer os.Remove(tmp.Name())
+	_, err = tmp.Write(data)
+	if closeErr := tmp.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	result, err := client.AddTorrentFile(tmp.Name(), opts)
+	if err != nil {
+		return nil, err
+	}
+	if result.Hash == "" {
+		result.Hash = hash
+	}
+	return result, nil
+}
@@ -0,0 +1,149 @@
+package server
+
+import (
+	"context"
+	"log"
+	"net/http"
+	"time"
+
+	"github.com/gin-gonic/gin"
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/updater"
+)
+
+// updateIdleCheckInterval is how often a deferred update checks whether the queue is idle
+const updateIdleCheckInterval = 10 * time.Second
+
+// UpdateRequest is the request body for POST /update
+type UpdateRequest struct {
+	Channel string `json:"channel,omitempty"` // stable or beta, default from config
+}
+
+// IsIdle reports whether no job is queued, downloading, waiting, or uploading
+func (jq *JobQueue) IsIdle() bool {
+	jq.mu.RLock()
+	defer jq.mu.RUnlock()
+
+	for _, job := range jq.jobs {
+		switch job.Status {
+		case JobStatusQueued, JobStatusDownloading, JobStatusWaitingNetwork, JobStatusUploading:
+			return false
+		}
+	}
+	return true
+}
+
+// resolveUpdateChannel returns the requested channel, falling back to config
+func resolveUpdateChannel(channel string) (string, error) {
+	if channel == "" {
+		channel = config.LoadOrDefault().UpdateChannel
+	}
+	return updater.ParseChannel(channel)
+}
+
+// handleCheckUpdate reports the newest release and its changelog.
+// Query: channel=stable|beta (default from config)
+func (s *Server) handleCheckUpdate(c *gin.Context) {
+	channel, err := resolveUpdateChannel(c.Query("channel"))
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	ctx, cancel := context.WithTimeout(c.Request.Context(), 30*time.Second)
+	defer cancel()
+
+	info, err := updater.Check(ctx, channel)
+	if err != nil {
+		c.JSON(http.StatusBadGateway, Response{Code: 502, Data: nil, Message: err.Error()})
+		return
+	}
+
+	s.updateMu.Lock()
+	pending := s.pendingUpdate
+	s.updateMu.Unlock()
+
+	c.JSON(http.StatusOK, Response{
+		Code: 200,
+		Data: gin.H{
+			"update":  info,
+			"pending": pending,
+		},
+		Message: "update checked",
+	})
+}
+
+// handleInstallUpdate schedules a self-update. The install is deferred until the
+// download queue is idle so active jobs are never interrupted.
+func (s *Server) handleInstallUpdate(c *gin.Context) {
+	if config.IsRunningInDocker() {
+		c.JSON(http.StatusBadRequest, Response{
+			Code:    400,
+			Data:    nil,
+			Message: "self-update is not available in Docker; pull the latest image instead",
+		})
+		return
+	}
+
+	var req UpdateRequest
+	_ = c.ShouldBindJSON(&req)
+
+	channel, err := resolveUpdateChannel(req.Channel)
+	if err != nil {
+		c.JSON(http.StatusBadRequest, Response{Code: 400, Data: nil, Message: err.Error()})
+		return
+	}
+
+	s.updateMu.Lock()
+	if s.pendingUpdate != "" {
+		s.updateMu.Unlock()
+		c.JSON(http.StatusConflict, Response{Code: 409, Data: nil, Message: "an update is already pending"})
+		return
+	}
+	s.pendingUpdate = channel
+	s.updateMu.Unlock()
+
+	go s.installWhenIdle(channel)
+
+	c.JSON(http.StatusAccepted, Response{
+		Code: 202,
+		Data: gin.H{
+			"channel": channel,
+			"idle":    s.jobQueue.IsIdle(),
+		},
+		Message: "update will be installed when the download queue is idle",
+	})
+}
+
+// installWhenIdle waits for the queue to drain, then installs the update
+func (s *Server) installWhenIdle(channel string) {
+	defer func() {
+		s.updateMu.Lock()
+		s.pendingUpdate = ""
+		s.updateMu.Unlock()
+	}()
+
+	events := s.jobQueue.events
+	events.Publish(Event{Type: EventUpdatePending, Data: gin.H{"channel": channel}})
+
+	for !s.jobQueue.IsIdle() {
+		time.Sleep(updateIdleCheckInterval)
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), 10*time.Minute)
+	defer cancel()
+
+	installed, err := updater.Install(ctx, channel)
+	if err != nil {
+		log.Printf("Update failed: %v", err)
+		events.Publish(Event{Type: EventUpdateFailed, Data: gin.H{"channel": channel, "error": err.Error()}})
+		return
+	}
+
+	if installed == "" {
+		log.Printf("Update skipped: already up to date")
+	} else {
+		log.Printf("Updated to %s, restart the server to apply", installed)
+	}
+	events.Publish(Event{Type: EventUpdateInstalled, Data: gin.H{"channel": channel, "version": installed}})
+}
@@ -0,0 +1,75 @@
+package server
+
+import (
+	"log"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/upload"
+)
+
+// uploadJobOutput uploads the job's output files to its upload target, falling back
+// to upload.default from config. Does nothing when no target is set.
+func (jq *JobQueue) uploadJobOutput(job *Job) error {
+	current := jq.GetJob(job.ID)
+	if current == nil {
+		return nil
+	}
+	files := current.OutputFiles()
+	if len(files) == 0 {
+		return nil
+	}
+
+	cfg := config.LoadOrDefault()
+	target := current.UploadTo
+	if target == "" {
+		target = cfg.Upload.Default
+	}
+	if target == "" {
+		return nil
+	}
+
+	jq.updateJobStatus(job.ID, JobStatusUploading, 0, "")
+
+	var remotes []string
+	for i, file := range files {
+		progressFn := func(sent, total int64) {
+			if total <= 0 {
+				return
+			}
+			done := (float64(i) + float64(sent)/float64(total)) / float64(len(files)) * 100
+			jq.updateJobUploadProgress(job.ID, done)
+		}
+
+		remote, err := upload.File(job.ctx, cfg, target, file, cfg.Upload.DeleteLocal, progressFn)
+		if err != nil {
+			return err
+		}
+		log.Printf("Job %s: uploaded %s to %s", job.ID, file, remote)
+		remotes = append(remotes, remote)
+	}
+
+	jq.mu.Lock()
+	if j, ok := jq.jobs[job.ID]; ok {
+		j.UploadProgress = 100
+		j.UploadedTo = remotes
+		j.UpdatedAt = time.Now()
+		jq.publishJobLocked(j)
+	}
+	jq.mu.Unlock()
+
+	return nil
+}
+
+func (jq *JobQueue) updateJobUploadProgress(id string, progress float64) {
+	jq.mu.Lock()
+	defer jq.mu.Unlock()
+
+	if job, ok := jq.jobs[id]; ok {
+		job.UploadProgress = progress
+		job.UpdatedAt = time.Now()
+		if job.UpdatedAt.Sub(job.lastEventAt) >= progressEventInterval {
+			jq.publishJobLocked(job)
+		}
+	}
+}
@@ -0,0 +1,146 @@
+package server
+
+import (
+	"context"
+	"fmt"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/guiyumin/vget/internal/core/config"
+	"github.com/guiyumin/vget/internal/core/downloadlist"
+)
+
+const (
+	// watchInterval is how often the watch folder is checked
+	watchInterval = 5 * time.Second
+
+	// watchSettle is how long a dropped file must go unmodified before it's
+	// read, so one still being written isn't picked up half done
+	watchSettle = 2 * time.Second
+)
+
+// Subfolders of the watch folder that picked-up files are moved into
+const (
+	watchDoneDir   = "done"
+	watchFailedDir = "failed"
+)
+
+// watchFolder queues the links in .url and .txt files dropped into
+// download.watch_dir until ctx is done. The setting is reread on every check,
+// so the folder can be set or changed while the server runs.
+func (s *Server) watchFolder(ctx context.Context) {
+	ticker := time.NewTicker(watchInterval)
+	defer ticker.Stop()
+
+	lastDir := ""
+	stuck := make(map[string]bool) // handled files that couldn't be moved away
+	for {
+		dir := config.LoadOrDefault().Download.WatchDir
+		if dir != lastDir {
+			if dir != "" {
+				log.Printf("Watching %s for .url and .txt files", dir)
+			}
+			lastDir = dir
+		}
+		if dir != "" {
+			s.scanWatchFolder(dir, stuck)
+		}
+
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+	}
+}
+
+// scanWatchFolder queues every settled drop file in dir and moves it out of
+// the way, into done/ or, if nothing could be read from it, failed/. Files
+// that can't be moved are recorded in stuck so they're only queued once.
+func (s *Server) scanWatchFolder(dir string, stuck map[string]bool) {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		if !os.IsNotExist(err) {
+			log.Printf("Watch folder: %v", err)
+		}
+		return
+	}
+
+	for _, entry := range entries {
+		name := entry.Name()
+		ext := strings.ToLower(filepath.Ext(name))
+		if entry.IsDir() || strings.HasPrefix(name, ".") || (ext != ".url" && ext != ".txt") {
+			continue
+		}
+		info, err := entry.Info()
+		if err != nil || time.Since(info.ModTime()) < watchSettle {
+			continue
+		}
+
+		path := filepath.Join(dir, name)
+		if stuck[path] {
+			continue
+		}
+		queued, err := s.queueWatchFile(path)
+		dest := watchDoneDir
+		if err != nil {
+			log.Printf("Watch folder: %s: %v", name, err)
+			dest = watchFailedDir
+		} else {
+			log.Printf("Watch folder: queued %d downloads from %s", queued, name)
+		}
+		if err := moveWatchFile(path, filepath.Join(dir, dest)); err != nil {
+			log.Printf("Watch folder: %v", err)
+			stuck[path] = true
+		}
+	}
+}
+
+// queueWatchFile queues the links in a drop file and returns how many were
+// queued. Links the queue rejects show up as failed jobs.
+func (s *Server) queueWatchFile(path string) (int, error) {
+	list, err := downloadlist.ParseFile(path)
+	if err != nil {
+		return 0, err
+	}
+	for _, invalid := range list.Invalid {
+		log.Printf("Watch folder: %s: skipping invalid %s", filepath.Base(path), invalid)
+	}
+	if len(list.Items) == 0 {
+		return 0, fmt.Errorf("no links found")
+	}
+
+	queued := 0
+	for _, item := range list.Items {
+		_, err := s.jobQueue.AddJobWithOptions(item.URL, JobOptions{
+			Quality: item.Quality,
+			Tags:    item.Tags,
+			Note:    item.Note,
+		})
+		if err != nil {
+			s.jobQueue.AddFailedJob(item.URL, err.Error())
+			continue
+		}
+		queued++
+	}
+	return queued, nil
+}
+
+// moveWatchFile moves a handled drop file into destDir, prefixing the time if
+// a file of that name was handled before
+func moveWatchFile(path, destDir string) error {
+	if err := os.MkdirAll(destDir, 0755); err != nil {
+		return fmt.Errorf("failed to create %s: %w", destDir, err)
+	}
+	dest := filepath.Join(destDir, filepath.Base(path))
+	if _, err := os.Stat(dest); err == nil {
+		dest = filepath.Join(destDir, time.Now().Format("20060102-150405-")+filepath.Base(path))
+	}
+	if err := os.Rename(path, dest); err != nil {
+		return fmt.Errorf("failed to move %s: %w", filepath.Base(path), err)
+	}
+	return nil
+}
@@ -6,6 +6,8 @@ package torrent
 import (
 	"errors"
 	"fmt"
+	"net/url"
+	"strings"
 )
 
 // TorrentState represents the current state of a torrent
@@ -176,6 +178,15 @@ func IsMagnetLink(url string) bool {
 	return len(url) > 8 && url[:8] == "magnet:?"
 }
 
+// IsTorrentFileURL checks if an HTTP(S) URL's path ends in .torrent
+func IsTorrentFileURL(rawURL string) bool {
+	u, err := url.Parse(rawURL)
+	if err != nil || (u.Scheme != "http" && u.Scheme != "https") {
+		return false
+	}
+	return strings.HasSuffix(strings.ToLower(u.Path), ".torrent")
+}
+
 // IsTorrentURL checks if a URL points to a .torrent file
 func IsTorrentURL(url string) bool {
 	// Simple check - could be more sophisticated
@@ -0,0 +1,89 @@
+package torrent
+
+import (
+	"crypto/sha1"
+	"encoding/hex"
+	"fmt"
+	"strconv"
+)
+
+// InfoHash returns the hex info hash of a .torrent file: the SHA-1 of its
+// bencoded info dictionary. Clients identify torrents by it, so a job can
+// follow a torrent it added from a file.
+func InfoHash(data []byte) (string, error) {
+	if len(data) == 0 || data[0] != 'd' {
+		return "", ErrInvalidTorrent
+	}
+	pos := 1
+	for pos < len(data) && data[pos] != 'e' {
+		keyEnd, err := skipBencode(data, pos)
+		if err != nil {
+			return "", err
+		}
+		key, err := bencodeString(data[pos:keyEnd])
+		if err != nil {
+			return "", err
+		}
+		valueEnd, err := skipBencode(data, keyEnd)
+		if err != nil {
+			return "", err
+		}
+		if key == "info" {
+			sum := sha1.Sum(data[keyEnd:valueEnd])
+			return hex.EncodeToString(sum[:]), nil
+		}
+		pos = valueEnd
+	}
+	return "", fmt.Errorf("%w: no info dictionary", ErrInvalidTorrent)
+}
+
+// skipBencode returns the offset just past the bencoded value at pos
+func skipBencode(data []byte, pos int) (int, error) {
+	if pos >= len(data) {
+		return 0, fmt.Errorf("%w: unexpected end", ErrInvalidTorrent)
+	}
+	switch c := data[pos]; {
+	case c == 'i':
+		for i := pos + 1; i < len(data); i++ {
+			if data[i] == 'e' {
+				return i + 1, nil
+			}
+		}
+		return 0, fmt.Errorf("%w: unterminated integer", ErrInvalidTorrent)
+	case c == 'l' || c == 'd':
+		pos++
+		for pos < len(data) && data[pos] != 'e' {
+			end, err := skipBencode(data, pos)
+			if err != nil {
+				return 0, err
+			}
+			pos = end
+		}
+		if pos >= len(data) {
+			return 0, fmt.Errorf("%w: unterminated list", ErrInvalidTorrent)
+		}
+		return pos + 1, nil
+	case c >= '0' && c <= '9':
+		colon := pos
+		for colon < len(data) && data[colon] != ':' {
+			colon++
+		}
+		length, err := strconv.Atoi(string(data[pos:colon]))
+		if err != nil || colon+1+length > len(data) {
+			return 0, fmt.Errorf("%w: bad string length", ErrInvalidTorrent)
+		}
+		return colon + 1 + length, nil
+	default:
+		return 0, fmt.Errorf("%w: unexpected %q", ErrInvalidTorrent, c)
+	}
+}
+
+// bencodeString decodes a bencoded string such as "4:info"
+func bencodeString(value []byte) (string, error) {
+	for i, c := range value {
+		if c == ':' {
+			return string(value[i+1:]), nil
+		}
+	}
+	return "", fmt.Errorf("%w: dictionary key is not a string", ErrInvalidTorrent)
+}
@@ -0,0 +1,29 @@
+package torrent
+
+import (
+	"crypto/sha1"
+	"encoding/hex"
+	"errors"
+	"testing"
+)
+
+func TestInfoHash(t *testing.T) {
+	info := "d6:lengthi1024e4:name9:video.mp412:piece lengthi16384e6:pieces20:aaaaaaaaaaaaaaaaaaaae"
+	data := []byte("d8:announce21:http://tracker/announce13:announce-listll21:http://tracker/announceee4:info" + info + "e")
+
+	sum := sha1.Sum([]byte(info))
+	want := hex.EncodeToString(sum[:])
+	got, err := InfoHash(data)
+	if err != nil {
+		t.Fatalf("InfoHash() error: %v", err)
+	}
+	if got != want {
+		t.Errorf("InfoHash() = %s, want %s", got, want)
+	}
+
+	for _, bad := range []string{"", "<html>", "d8:announce3:urle", "d4:infod4:name"} {
+		if _, err := InfoHash([]byte(bad)); !errors.Is(err, ErrInvalidTorrent) {
+			t.Errorf("InfoHash(%q) error = %v, want ErrInvalidTorrent", bad, err)
+		}
+	}
+}
@@ -0,0 +1,105 @@
+package torrent
+
+import (
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/url"
+)
+
+// PieceReporter is implemented by clients that can tell which pieces of a
+// torrent are downloaded. Synology Download Station has no such API.
+type PieceReporter interface {
+	// GetPieces returns one entry per piece, true once it's downloaded.
+	// It is empty until a magnet link's metadata arrives.
+	GetPieces(id string) ([]bool, error)
+}
+
+var (
+	_ PieceReporter = (*TransmissionClient)(nil)
+	_ PieceReporter = (*QBittorrentClient)(nil)
+)
+
+// EncodeBitfield packs pieces into a base64 bitfield, first piece in the high
+// bit of the first byte, as in the BitTorrent protocol
+func EncodeBitfield(pieces []bool) string {
+	bits := make([]byte, (len(pieces)+7)/8)
+	for i, done := range pieces {
+		if done {
+			bits[i/8] |= 0x80 >> (i % 8)
+		}
+	}
+	return base64.StdEncoding.EncodeToString(bits)
+}
+
+// decodeBitfield unpacks count pieces from a base64 bitfield
+func decodeBitfield(encoded string, count int) ([]bool, error) {
+	bits, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil {
+		return nil, fmt.Errorf("invalid piece bitfield: %w", err)
+	}
+	if len(bits)*8 < count {
+		return nil, fmt.Errorf("piece bitfield has %d bytes for %d pieces", len(bits), count)
+	}
+	pieces := make([]bool, count)
+	for i := range pieces {
+		pieces[i] = bits[i/8]&(0x80>>(i%8)) != 0
+	}
+	return pieces, nil
+}
+
+// GetPieces reads the torrent's piece bitfield
+func (c *TransmissionClient) GetPieces(id string) ([]bool, error) {
+	args := map[string]interface{}{
+		"ids":    []string{id},
+		"fields": []string{"pieces", "pieceCount"},
+	}
+
+	resp, err := c.doRequest("torrent-get", args)
+	if err != nil {
+		return nil, err
+	}
+
+	var result struct {
+		Torrents []struct {
+			Pieces     string `json:"pieces"`
+			PieceCount int    `json:"pieceCount"`
+		} `json:"torrents"`
+	}
+	if err := json.Unmarshal(resp.Arguments, &result); err != nil {
+		return nil, err
+	}
+	if len(result.Torrents) == 0 {
+		return nil, ErrTorrentNotFound
+	}
+
+	t := result.Torrents[0]
+	return decodeBitfield(t.Pieces, t.PieceCount)
+}
+
+// GetPieces reads the torrent's piece states: 0 missing, 1 downloading, 2 done
+func (c *QBittorrentClient) GetPieces(id string) ([]bool, error) {
+	resp, err := c.client.Get(c.baseURL + "/api/v2/torrents/pieceStates?hash=" + url.QueryEscape(id))
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode == http.StatusNotFound {
+		return nil, ErrTorrentNotFound
+	}
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("failed to get piece states: status %d", resp.StatusCode)
+	}
+
+	var states []int
+	if err := json.NewDecoder(resp.Body).Decode(&states); err != nil {
+		return nil, err
+	}
+	pieces := make([]bool, len(states))
+	for i, state := range states {
+		pieces[i] = state == 2
+	}
+	return pieces, nil
+}
@@ -0,0 +1,26 @@
+package torrent
+
+import (
+	"slices"
+	"testing"
+)
+
+func TestBitfield(t *testing.T) {
+	pieces := []bool{true, false, false, true, false, false, false, false, true, true}
+	encoded := EncodeBitfield(pieces)
+	if encoded != "kMA=" {
+		t.Errorf("EncodeBitfield() = %q, want %q", encoded, "kMA=")
+	}
+
+	got, err := decodeBitfield(encoded, len(pieces))
+	if err != nil {
+		t.Fatalf("decodeBitfield() error: %v", err)
+	}
+	if !slices.Equal(got, pieces) {
+		t.Errorf("decodeBitfield() = %v, want %v", got, pieces)
+	}
+
+	if _, err := decodeBitfield(encoded, 17); err == nil {
+		t.Error("decodeBitfield() with too few bytes expected error")
+	}
+}
@@ -4,6 +4,7 @@ import (
 	"context"
 	"fmt"
 	"runtime"
+	"time"
 
 	"github.com/creativeprojects/go-selfupdate"
 	"github.com/guiyumin/vget/internal/core/version"
@@ -14,88 +15,183 @@ const (
 	repoName  = "vget"
 )
 
-// CheckUpdate checks if a new version is available
-func CheckUpdate() (*selfupdate.Release, bool, error) {
+// Update channels
+const (
+	ChannelStable = "stable"
+	ChannelBeta   = "beta"
+)
+
+// ParseChannel validates an update channel. Empty means stable.
+func ParseChannel(channel string) (string, error) {
+	switch channel {
+	case "", ChannelStable:
+		return ChannelStable, nil
+	case ChannelBeta:
+		return ChannelBeta, nil
+	default:
+		return "", fmt.Errorf("invalid update channel: %s (use stable or beta)", channel)
+	}
+}
+
+// Info describes the newest release on a channel
+type Info struct {
+	Channel        string    `json:"channel"`
+	CurrentVersion string    `json:"current_version"`
+	LatestVersion  string    `json:"latest_version,omitempty"`
+	Available      bool      `json:"available"`
+	Prerelease     bool      `json:"prerelease,omitempty"`
+	Name           string    `json:"name,omitempty"`
+	Notes          string    `json:"notes,omitempty"` // changelog (release notes markdown)
+	URL            string    `json:"url,omitempty"`
+	PublishedAt    time.Time `json:"published_at,omitempty"`
+	Size           int       `json:"size,omitempty"` // asset size in bytes
+}
+
+// detectLatest finds the newest release for the channel.
+// The beta channel includes prereleases; stable only sees full releases.
+func detectLatest(ctx context.Context, channel string) (*selfupdate.Updater, *selfupdate.Release, bool, error) {
 	source, err := selfupdate.NewGitHubSource(selfupdate.GitHubConfig{})
 	if err != nil {
-		return nil, false, err
+		return nil, nil, false, err
 	}
 
 	updater, err := selfupdate.NewUpdater(selfupdate.Config{
-		Source: source,
+		Source:     source,
+		Prerelease: channel == ChannelBeta,
 	})
 	if err != nil {
-		return nil, false, err
+		return nil, nil, false, err
 	}
 
-	latest, found, err := updater.DetectLatest(context.Background(), selfupdate.NewRepositorySlug(repoOwner, repoName))
+	latest, found, err := updater.DetectLatest(ctx, selfupdate.NewRepositorySlug(repoOwner, repoName))
 	if err != nil {
-		return nil, false, fmt.Errorf("failed to check for updates: %w", err)
+		return nil, nil, false, fmt.Errorf("failed to check for updates: %w", err)
 	}
+	return updater, latest, found, nil
+}
 
-	if !found {
-		return nil, false, nil
+// currentVersion returns the running version without a 'v' prefix
+func currentVersion() string {
+	v := version.Version
+	if len(v) > 0 && v[0] == 'v' {
+		v = v[1:]
 	}
+	return v
+}
 
-	currentVersion := version.Version
-	// Remove 'v' prefix if present for comparison
-	if len(currentVersion) > 0 && currentVersion[0] == 'v' {
-		currentVersion = currentVersion[1:]
+// CheckUpdate checks if a new version is available on the stable channel
+func CheckUpdate() (*selfupdate.Release, bool, error) {
+	_, latest, found, err := detectLatest(context.Background(), ChannelStable)
+	if err != nil || !found {
+		return nil, false, err
 	}
 
-	if latest.LessOrEqual(currentVersion) {
+	if latest.LessOrEqual(currentVersion()) {
 		return latest, false, nil
 	}
 
 	return latest, true, nil
 }
 
-// Update performs the self-update
-func Update() error {
-	source, err := selfupdate.NewGitHubSource(selfupdate.GitHubConfig{})
+// Check reports the newest release on a channel along with its changelog
+func Check(ctx context.Context, channel string) (*Info, error) {
+	channel, err := ParseChannel(channel)
 	if err != nil {
-		return err
+		return nil, err
 	}
 
-	updater, err := selfupdate.NewUpdater(selfupdate.Config{
-		Source: source,
-	})
+	info := &Info{
+		Channel:        channel,
+		CurrentVersion: currentVersion(),
+	}
+
+	_, latest, found, err := detectLatest(ctx, channel)
+	if err != nil {
+		return nil, err
+	}
+	if !found {
+		return info, nil
+	}
+
+	info.LatestVersion = latest.Version()
+	info.Available = latest.GreaterThan(info.CurrentVersion)
+	info.Prerelease = latest.Prerelease
+	info.Name = latest.Name
+	info.Notes = latest.ReleaseNotes
+	info.URL = latest.URL
+	info.PublishedAt = latest.PublishedAt
+	info.Size = latest.AssetByteSize
+	return info, nil
+}
+
+// Update performs the self-update from the stable channel
+func Update() error {
+	return UpdateChannel(ChannelStable)
+}
+
+// UpdateChannel performs the self-update from the given channel
+func UpdateChannel(channel string) error {
+	channel, err := ParseChannel(channel)
 	if err != nil {
 		return err
 	}
 
-	latest, found, err := updater.DetectLatest(context.Background(), selfupdate.NewRepositorySlug(repoOwner, repoName))
+	updater, latest, found, err := detectLatest(context.Background(), channel)
 	if err != nil {
-		return fmt.Errorf("failed to check for updates: %w", err)
+		return err
 	}
 
 	if !found {
 		return fmt.Errorf("no releases found for %s/%s", repoOwner, repoName)
 	}
 
-	currentVersion := version.Version
-	if len(currentVersion) > 0 && currentVersion[0] == 'v' {
-		currentVersion = currentVersion[1:]
+	current := currentVersion()
+	if latest.LessOrEqual(current) {
+		fmt.Printf("Already up to date (v%s)\n", current)
+		return nil
 	}
 
-	if latest.LessOrEqual(currentVersion) {
-		fmt.Printf("Already up to date (v%s)\n", currentVersion)
-		return nil
+	fmt.Printf("Updating from v%s to %s...\n", current, latest.Version())
+
+	if err := install(context.Background(), updater, latest); err != nil {
+		return err
 	}
 
-	fmt.Printf("Updating from v%s to %s...\n", currentVersion, latest.Version())
+	fmt.Printf("Successfully updated to %s\n", latest.Version())
+	return nil
+}
 
+// Install downloads and installs the newest release on a channel without printing.
+// Returns the installed version, or "" if already up to date.
+func Install(ctx context.Context, channel string) (string, error) {
+	channel, err := ParseChannel(channel)
+	if err != nil {
+		return "", err
+	}
+
+	updater, latest, found, err := detectLatest(ctx, channel)
+	if err != nil {
+		return "", err
+	}
+	if !found || latest.LessOrEqual(currentVersion()) {
+		return "", nil
+	}
+
+	if err := install(ctx, updater, latest); err != nil {
+		return "", err
+	}
+	return latest.Version(), nil
+}
+
+func install(ctx context.Context, updater *selfupdate.Updater, latest *selfupdate.Release) error {
 	exe, err := selfupdate.ExecutablePath()
 	if err != nil {
 		return fmt.Errorf("failed to get executable path: %w", err)
 	}
 
-	err = updater.UpdateTo(context.Background(), latest, exe)
-	if err != nil {
+	if err := updater.UpdateTo(ctx, latest, exe); err != nil {
 		return fmt.Errorf("failed to update: %w", err)
 	}
-
-	fmt.Printf("Successfully updated to %s\n", latest.Version())
 	return nil
 }
 
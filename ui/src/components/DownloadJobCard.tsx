@@ -1,12 +1,12 @@
 import clsx from "clsx";
-import { useRef, useEffect, useState } from "react";
-import type { Job, JobStatus } from "../utils/apis";
+import type { Job, JobStage, JobStatus } from "../utils/apis";
 import type { UITranslations } from "../utils/translations";
 
 interface DownloadJobCardProps {
   job: Job;
   onCancel: () => void;
   onClear: () => void;
+  onRedownload: () => void;
   t: UITranslations;
 }
 
@@ -28,69 +28,52 @@ function formatSpeed(bytesPerSecond: number): string {
   );
 }
 
+function formatETA(seconds: number): string {
+  const h = Math.floor(seconds / 3600);
+  const m = Math.floor((seconds % 3600) / 60);
+  const s = seconds % 60;
+  const pad = (n: number) => n.toString().padStart(2, "0");
+  return h > 0 ? `${h}:${pad(m)}:${pad(s)}` : `${pad(m)}:${pad(s)}`;
+}
+
 export function DownloadJobCard({
   job,
   onCancel,
   onClear,
+  onRedownload,
   t,
 }: DownloadJobCardProps) {
-  const canCancel = job.status === "queued" || job.status === "downloading";
+  const canCancel =
+    job.status === "queued" ||
+    job.status === "downloading" ||
+    job.status === "waiting_network";
   const canClear =
     job.status === "completed" ||
     job.status === "failed" ||
     job.status === "cancelled";
 
-  // Track download speed with exponential moving average for smoothing
-  const prevDownloaded = useRef<number>(0);
-  const prevTime = useRef<number>(0);
-  const smoothedSpeed = useRef<number>(0);
-  const [speed, setSpeed] = useState<number>(0);
-
-  useEffect(() => {
-    if (job.status === "downloading") {
-      const now = Date.now();
-      // Initialize on first update
-      if (prevTime.current === 0) {
-        prevTime.current = now;
-        prevDownloaded.current = job.downloaded;
-        return;
-      }
-
-      const timeDelta = (now - prevTime.current) / 1000; // seconds
-      const bytesDelta = job.downloaded - prevDownloaded.current;
-
-      if (timeDelta > 0 && bytesDelta >= 0) {
-        const instantSpeed = bytesDelta / timeDelta;
-        // Exponential moving average: higher alpha = more weight on recent data
-        const alpha = 0.7;
-        smoothedSpeed.current =
-          alpha * instantSpeed + (1 - alpha) * smoothedSpeed.current;
-        setSpeed(smoothedSpeed.current);
-      }
-
-      prevDownloaded.current = job.downloaded;
-      prevTime.current = now;
-    } else {
-      // Reset when not downloading
-      prevDownloaded.current = 0;
-      prevTime.current = 0;
-      smoothedSpeed.current = 0;
-      setSpeed(0);
-    }
-  }, [job.downloaded, job.status]);
-
   const statusText: Record<JobStatus, string> = {
     queued: t.queued,
     downloading: t.downloading,
+    waiting_network: t.waiting_network,
     completed: t.completed,
     failed: t.failed,
     cancelled: t.cancelled,
   };
 
+  const stageText: Record<JobStage, string> = {
+    video: t.stage_video,
+    audio: t.stage_audio,
+    merging: t.stage_merging,
+    post_process: t.stage_post_process,
+  };
+
   const statusStyles: Record<JobStatus, string> = {
     queued: "bg-zinc-300 dark:bg-zinc-700 text-zinc-500",
     downloading:
       "bg-blue-100 dark:bg-blue-900/50 text-blue-600 dark:text-blue-400",
+    waiting_network:
+      "bg-amber-100 dark:bg-amber-900/50 text-amber-600 dark:text-amber-400",
     completed:
       "bg-green-100 dark:bg-green-900/50 text-green-600 dark:text-green-500",
     failed: "bg-red-100 dark:bg-red-900/50 text-red-600 dark:text-red-500",
@@ -138,8 +121,26 @@ export function DownloadJobCard({
           {job.filename}
         </p>
       )}
+      {job.status === "queued" &&
+        job.start_at &&
+        new Date(job.start_at) > new Date() && (
+          <p className="text-xs text-zinc-400 dark:text-zinc-600 mb-2">
+            {t.starts_at}: {new Date(job.start_at).toLocaleString()}
+          </p>
+        )}
+      {job.source && (
+        <p className="text-xs text-zinc-400 dark:text-zinc-600 mb-2 break-all">
+          {t.downloaded_from}: {job.source}
+        </p>
+      )}
       {job.status === "downloading" && (
         <div className="flex items-center gap-3 mt-3">
+          {job.stage && (
+            <span className="text-xs text-zinc-500 dark:text-zinc-500">
+              {stageText[job.stage]}
+              {job.stage_progress ? ` ${job.stage_progress.toFixed(0)}%` : ""}
+            </span>
+          )}
           <div className="flex-1 h-1 bg-zinc-300 dark:bg-zinc-700 rounded overflow-hidden">
             <div
               className={clsx(
@@ -155,13 +156,32 @@ export function DownloadJobCard({
               : formatBytes(job.downloaded)}
           </span>
           <span className="text-xs text-zinc-500 dark:text-zinc-500 min-w-20 text-right">
-            {formatSpeed(speed)}
+            {formatSpeed(job.speed ?? 0)}
           </span>
+          {job.eta_seconds !== undefined && job.eta_seconds >= 0 && (
+            <span className="text-xs text-zinc-500 dark:text-zinc-500 min-w-12 text-right">
+              {formatETA(job.eta_seconds)}
+            </span>
+          )}
         </div>
       )}
       {job.status === "failed" && job.error && (
         <div className="mt-2 p-2 bg-red-100 dark:bg-red-900/30 rounded text-xs text-red-700 dark:text-red-300">
+          {job.error_code === "insufficient_disk_space" && (
+            <div className="font-medium">{t.no_disk_space}</div>
+          )}
+          {job.error_code === "already_downloaded" && (
+            <div className="font-medium">{t.already_downloaded}</div>
+          )}
           {job.error}
+          {job.error_code === "already_downloaded" && (
+            <button
+              className="mt-2 block px-2 py-1 border border-red-300 dark:border-red-800 rounded bg-transparent text-[0.7rem] cursor-pointer hover:border-red-500 transition-colors"
+              onClick={onRedownload}
+            >
+              {t.download_again}
+            </button>
+          )}
         </div>
       )}
     </div>
@@ -0,0 +1,108 @@
+import { useState, useEffect, useCallback } from "react";
+import { fetchFFmpeg, installFFmpeg, type FFmpegInfo } from "../utils/apis";
+
+interface FFmpegSettingsProps {
+  isConnected: boolean;
+}
+
+// How often the download progress is polled
+const POLL_INTERVAL = 1000;
+
+export function FFmpegSettings({ isConnected }: FFmpegSettingsProps) {
+  const [info, setInfo] = useState<FFmpegInfo | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      const res = await fetchFFmpeg();
+      if (res.code === 200) {
+        setInfo(res.data);
+      }
+    } catch {
+      // Ignore errors
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  const installing = !!info?.install;
+
+  useEffect(() => {
+    if (!installing) return;
+    const timer = setInterval(load, POLL_INTERVAL);
+    return () => clearInterval(timer);
+  }, [installing, load]);
+
+  const handleInstall = async () => {
+    setError(null);
+    try {
+      const res = await installFFmpeg();
+      if (res.code !== 202) {
+        setError(res.message);
+      }
+    } catch {
+      setError("Failed to start the download");
+    }
+    load();
+  };
+
+  const install = info?.install;
+  const percent =
+    install && install.total > 0
+      ? Math.round((install.downloaded / install.total) * 100)
+      : null;
+
+  return (
+    <div className="bg-white dark:bg-zinc-900 border border-zinc-300 dark:border-zinc-700 rounded-lg p-4">
+      <div className="flex justify-between items-center mb-4">
+        <h2 className="text-sm font-semibold text-zinc-900 dark:text-white">
+          FFmpeg
+        </h2>
+        {info?.installable && (!info.available || info.managed) && (
+          <button
+            className="px-3 py-1.5 rounded text-xs cursor-pointer transition-colors bg-blue-500 border border-blue-500 text-white hover:bg-blue-600 hover:border-blue-600 disabled:opacity-50 disabled:cursor-not-allowed"
+            onClick={handleInstall}
+            disabled={!isConnected || installing}
+          >
+            {installing ? "..." : info.available ? "Reinstall" : "Download"}
+          </button>
+        )}
+      </div>
+
+      {error && (
+        <div className="mb-4 px-3 py-2 rounded-md text-sm bg-red-100 dark:bg-red-900/30 text-red-700 dark:text-red-300">
+          {error}
+        </div>
+      )}
+
+      <div className="flex flex-col gap-1 text-sm text-zinc-700 dark:text-zinc-200">
+        {!info && <span className="text-zinc-500">Loading...</span>}
+        {info && !info.available && !installing && (
+          <span>
+            Not found. Merging separate video and audio, conversions, and
+            embedding chapters need ffmpeg.
+          </span>
+        )}
+        {info?.available && (
+          <>
+            <span>
+              {info.version ?? "unknown version"}
+              {info.managed ? " (downloaded by vget)" : ""}
+            </span>
+            <span className="text-xs font-mono text-zinc-500 break-all">
+              {info.path}
+            </span>
+          </>
+        )}
+        {install && (
+          <span className="text-xs text-zinc-500">
+            Downloading {install.binary}...
+            {percent !== null && ` ${percent}%`}
+          </span>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,3 +1,4 @@
+import { useEffect } from "react";
 import { Outlet } from "@tanstack/react-router";
 import clsx from "clsx";
 import { CiLight, CiDark } from "react-icons/ci";
@@ -6,7 +7,21 @@ import { useApp } from "../context/AppContext";
 import logo from "../assets/logo.png";
 
 export function Layout() {
-  const { health, isConnected, darkMode, setDarkMode, configLang } = useApp();
+  const {
+    health,
+    isConnected,
+    darkMode,
+    setDarkMode,
+    configLang,
+    downloadsSummary,
+  } = useApp();
+
+  useEffect(() => {
+    document.title =
+      downloadsSummary && downloadsSummary.active > 0
+        ? `(${Math.round(downloadsSummary.progress)}%) VGet Server UI`
+        : "VGet Server UI";
+  }, [downloadsSummary]);
 
   return (
     <div className="flex w-full h-screen max-w-4xl bg-zinc-100 dark:bg-zinc-950 text-zinc-900 dark:text-white transition-colors">
@@ -28,6 +43,14 @@ export function Layout() {
             </h1>
           </div>
           <div className="flex items-center gap-3">
+            {downloadsSummary && (
+              <span className="text-blue-600 dark:text-blue-400 text-sm px-2 py-1 bg-blue-100 dark:bg-blue-900/50 rounded">
+                ↓ {downloadsSummary.active}
+                {downloadsSummary.queued > 0 && ` +${downloadsSummary.queued}`}
+                {downloadsSummary.active > 0 &&
+                  ` · ${Math.round(downloadsSummary.progress)}%`}
+              </span>
+            )}
             <button
               className="bg-transparent border border-zinc-300 dark:border-zinc-700 rounded-md px-2 py-1.5 cursor-pointer text-base leading-none transition-colors hover:border-zinc-500 hover:bg-zinc-100 dark:hover:bg-zinc-800"
               onClick={() => setDarkMode(!darkMode)}
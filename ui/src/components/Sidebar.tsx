@@ -12,6 +12,7 @@ import {
   FaB,
   FaWandMagicSparkles,
   FaMicrophone,
+  FaClockRotateLeft,
 } from "react-icons/fa6";
 import { useApp } from "../context/AppContext";
 
@@ -44,6 +45,12 @@ export function Sidebar({ lang }: SidebarProps) {
       label: t.bulk_download,
       show: true,
     },
+    {
+      to: "/history",
+      icon: <FaClockRotateLeft />,
+      label: t.history,
+      show: true,
+    },
     {
       icon: <FaWandMagicSparkles />,
       label: t.ai,
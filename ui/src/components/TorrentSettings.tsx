@@ -30,6 +30,7 @@ export function TorrentSettings({ isConnected }: TorrentSettingsProps) {
   const [password, setPassword] = useState("");
   const [useHttps, setUseHttps] = useState(false);
   const [defaultSavePath, setDefaultSavePath] = useState("");
+  const [seed, setSeed] = useState(false);
 
   // Load initial config
   useEffect(() => {
@@ -44,6 +45,7 @@ export function TorrentSettings({ isConnected }: TorrentSettingsProps) {
           setPassword(res.data.password || "");
           setUseHttps(res.data.use_https || false);
           setDefaultSavePath(res.data.default_save_path || "");
+          setSeed(res.data.seed || false);
         }
       } catch {
         // Ignore errors
@@ -66,6 +68,7 @@ export function TorrentSettings({ isConnected }: TorrentSettingsProps) {
         password,
         use_https: useHttps,
         default_save_path: defaultSavePath,
+        seed,
       };
       const res = await saveTorrentConfig(config);
       if (res.code === 200) {
@@ -263,6 +266,23 @@ export function TorrentSettings({ isConnected }: TorrentSettingsProps) {
                 disabled={!isConnected || saving}
               />
             </div>
+
+            {/* Keep seeding torrents downloaded as jobs */}
+            <div className="flex items-center gap-3">
+              <span className="min-w-[120px] text-sm text-zinc-700 dark:text-zinc-200">
+                Keep Seeding
+              </span>
+              <label className="relative inline-flex items-center cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={seed}
+                  onChange={(e) => setSeed(e.target.checked)}
+                  disabled={!isConnected || saving}
+                  className="sr-only peer"
+                />
+                <div className="w-9 h-5 bg-zinc-300 dark:bg-zinc-700 peer-focus:outline-none rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-zinc-300 after:border after:rounded-full after:h-4 after:w-4 after:transition-all peer-checked:bg-blue-500"></div>
+              </label>
+            </div>
           </>
         )}
 
@@ -21,6 +21,8 @@ import {
   type Job,
   type HealthData,
   type WebDAVServer,
+  type DuplicatePolicy,
+  type DownloadsSummary,
   fetchHealth,
   fetchJobs,
   fetchConfig,
@@ -43,6 +45,7 @@ interface AppContextType {
 
   // Jobs
   jobs: Job[];
+  downloadsSummary: DownloadsSummary | null;
 
   // Config
   outputDir: string;
@@ -68,7 +71,10 @@ interface AppContextType {
 
   // Actions
   refresh: () => Promise<void>;
-  submitDownload: (url: string) => Promise<boolean>;
+  submitDownload: (
+    url: string,
+    onDuplicate?: DuplicatePolicy
+  ) => Promise<boolean>;
   cancelJob: (id: string) => Promise<void>;
   clearAllHistory: () => Promise<void>;
   updateOutputDir: (dir: string) => Promise<boolean>;
@@ -88,6 +94,8 @@ const AppContext = createContext<AppContextType | null>(null);
 export function AppProvider({ children }: { children: ReactNode }) {
   const [health, setHealth] = useState<HealthData | null>(null);
   const [jobs, setJobs] = useState<Job[]>([]);
+  const [downloadsSummary, setDownloadsSummary] =
+    useState<DownloadsSummary | null>(null);
   const [loading, setLoading] = useState(true);
   const [outputDir, setOutputDir] = useState("");
   const [darkMode, setDarkModeState] = useState(() => {
@@ -178,9 +186,23 @@ export function AppProvider({ children }: { children: ReactNode }) {
     return () => clearInterval(interval);
   }, [refresh]);
 
+  // Global download indicator, fed by the server's downloads_summary events
+  useEffect(() => {
+    const source = new EventSource("/api/events?topics=downloads");
+    source.addEventListener("downloads_summary", (e) => {
+      const summary: DownloadsSummary = JSON.parse(
+        (e as MessageEvent).data
+      ).data;
+      setDownloadsSummary(
+        summary.active > 0 || summary.queued > 0 ? summary : null
+      );
+    });
+    return () => source.close();
+  }, []);
+
   const submitDownload = useCallback(
-    async (url: string) => {
-      const res = await postDownload(url.trim());
+    async (url: string, onDuplicate?: DuplicatePolicy) => {
+      const res = await postDownload(url.trim(), undefined, onDuplicate);
       if (res.code === 200) {
         refresh();
         return true;
@@ -268,6 +290,7 @@ export function AppProvider({ children }: { children: ReactNode }) {
         isConnected,
         loading,
         jobs,
+        downloadsSummary,
         outputDir,
         configLang,
         configFormat,
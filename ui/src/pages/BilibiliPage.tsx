@@ -1,7 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from "react";
 import { useApp } from "../context/AppContext";
 import { setConfigValue } from "../utils/apis";
-import { QRCodeSVG } from "qrcode.react";
 
 type LoginMethod = "qr" | "cookie";
 
@@ -20,11 +19,13 @@ interface CookieFields {
 interface QRSession {
   url: string;
   qrcode_key: string;
+  qr_image: string;
 }
 
 interface BilibiliStatus {
   logged_in: boolean;
   username?: string;
+  avatar?: string;
   error?: string;
 }
 
@@ -76,6 +77,18 @@ export function BilibiliPage() {
     fetchStatus();
   }, [fetchStatus]);
 
+  // Refetch when the login changes elsewhere: another tab, the CLI, or expiry
+  useEffect(() => {
+    const source = new EventSource("/api/events?topics=auth");
+    source.addEventListener("auth_status_changed", (e) => {
+      const { site } = JSON.parse((e as MessageEvent).data).data;
+      if (site === "bilibili") {
+        fetchStatus();
+      }
+    });
+    return () => source.close();
+  }, [fetchStatus]);
+
   const handleLogout = async () => {
     try {
       await setConfigValue("bilibili.cookie", "");
@@ -108,9 +121,19 @@ export function BilibiliPage() {
             <span className="text-lg font-medium">已登录</span>
           </div>
 
-          <div className="mb-6">
-            <span className="text-zinc-600 dark:text-zinc-400">用户: </span>
-            <span className="font-medium">{status.username || "Unknown"}</span>
+          <div className="mb-6 flex items-center gap-3">
+            {status.avatar && (
+              <img
+                src={status.avatar}
+                alt=""
+                referrerPolicy="no-referrer"
+                className="w-10 h-10 rounded-full"
+              />
+            )}
+            <div>
+              <span className="text-zinc-600 dark:text-zinc-400">用户: </span>
+              <span className="font-medium">{status.username || "Unknown"}</span>
+            </div>
           </div>
 
           <button
@@ -314,11 +337,10 @@ function QRLogin({ onSuccess }: { onSuccess: () => void }) {
               {error}
             </div>
           ) : qrSession ? (
-            <QRCodeSVG
-              value={qrSession.url}
-              size={192}
-              level="L"
-              className={qrStatus === QR_EXPIRED ? "opacity-30" : ""}
+            <img
+              src={qrSession.qr_image}
+              alt="Bilibili login QR code"
+              className={`w-48 h-48 ${qrStatus === QR_EXPIRED ? "opacity-30" : ""}`}
             />
           ) : (
             <div className="w-48 h-48 flex items-center justify-center text-zinc-500">
@@ -2,11 +2,14 @@ import { useState, useRef, useCallback } from "react";
 import clsx from "clsx";
 import { useApp } from "../context/AppContext";
 import { FaUpload, FaFileAlt } from "react-icons/fa";
-import { postBulkDownload } from "../utils/apis";
+import { postBulkDownload, importDownloadList } from "../utils/apis";
+
+const qualityPresets = ["best", "2160p", "1440p", "1080p", "720p", "480p"];
 
 export function BulkDownloadPage() {
   const { t, isConnected, showToast, refresh } = useApp();
   const [urlText, setUrlText] = useState("");
+  const [quality, setQuality] = useState("best");
   const [submitting, setSubmitting] = useState(false);
   const [dragOver, setDragOver] = useState(false);
   const fileInputRef = useRef<HTMLInputElement>(null);
@@ -21,22 +24,64 @@ export function BulkDownloadPage() {
 
   const urls = parseUrls(urlText);
 
+  // Report how many URLs were queued
+  const showQueuedToast = useCallback(
+    (queued: number, failed: number) => {
+      if (queued > 0 && failed === 0) {
+        showToast("success", `${queued} ${t.downloads_queued}`);
+      } else if (queued > 0 && failed > 0) {
+        showToast("warning", `${queued} queued, ${failed} invalid`);
+      } else if (failed > 0) {
+        showToast("error", `${failed} invalid URL(s)`);
+      }
+    },
+    [showToast, t]
+  );
+
+  // JSON lists (e.g. a history export) are imported by the server, which
+  // keeps per-item quality, tags, and notes
+  const importJSONList = useCallback(
+    async (text: string) => {
+      setSubmitting(true);
+      try {
+        const res = await importDownloadList(text, quality);
+        if (res.code === 200) {
+          const { queued, failed, invalid } = res.data;
+          refresh();
+          showQueuedToast(queued, failed + (invalid?.length ?? 0));
+        } else {
+          showToast("error", res.message || "Failed to import list");
+        }
+      } catch {
+        showToast("error", "Failed to import list");
+      } finally {
+        setSubmitting(false);
+      }
+    },
+    [quality, refresh, showQueuedToast, showToast]
+  );
+
   // Handle file selection
   const handleFileSelect = useCallback(
     async (file: File) => {
-      if (!file.name.endsWith(".txt")) {
-        showToast("error", "Please select a .txt file");
+      const isJSON = file.name.endsWith(".json");
+      if (!isJSON && !file.name.endsWith(".txt")) {
+        showToast("error", "Please select a .txt or .json file");
         return;
       }
 
       try {
         const text = await file.text();
-        setUrlText(text);
+        if (isJSON) {
+          await importJSONList(text);
+        } else {
+          setUrlText(text);
+        }
       } catch {
         showToast("error", "Failed to read file");
       }
     },
-    [showToast]
+    [showToast, importJSONList]
   );
 
   // Handle file input change
@@ -86,18 +131,12 @@ export function BulkDownloadPage() {
     setSubmitting(true);
 
     try {
-      const res = await postBulkDownload(urls);
+      const res = await postBulkDownload(urls, quality);
       if (res.code === 200) {
         const { queued, failed } = res.data;
         setUrlText("");
         refresh();
-        if (queued > 0 && failed === 0) {
-          showToast("success", `${queued} ${t.downloads_queued}`);
-        } else if (queued > 0 && failed > 0) {
-          showToast("warning", `${queued} queued, ${failed} invalid`);
-        } else if (failed > 0) {
-          showToast("error", `${failed} invalid URL(s)`);
-        }
+        showQueuedToast(queued, failed);
       } else {
         showToast("error", res.message || "Failed to queue downloads");
       }
@@ -106,7 +145,7 @@ export function BulkDownloadPage() {
     } finally {
       setSubmitting(false);
     }
-  }, [urls, submitting, showToast, t, refresh]);
+  }, [urls, quality, submitting, showToast, showQueuedToast, refresh]);
 
   // Handle clear
   const handleClear = useCallback(() => {
@@ -157,7 +196,7 @@ export function BulkDownloadPage() {
         <input
           ref={fileInputRef}
           type="file"
-          accept=".txt"
+          accept=".txt,.json"
           onChange={handleFileInputChange}
           className="hidden"
         />
@@ -194,6 +233,19 @@ export function BulkDownloadPage() {
           )}
         </div>
         <div className="flex gap-3">
+          <select
+            className="px-3 py-2 border border-zinc-300 dark:border-zinc-700 rounded-lg text-sm bg-white dark:bg-zinc-900 text-zinc-700 dark:text-zinc-300 disabled:opacity-50"
+            value={quality}
+            onChange={(e) => setQuality(e.target.value)}
+            disabled={submitting}
+            title={t.quality}
+          >
+            {qualityPresets.map((q) => (
+              <option key={q} value={q}>
+                {q}
+              </option>
+            ))}
+          </select>
           <button
             type="button"
             onClick={handleClear}
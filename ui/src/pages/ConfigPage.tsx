@@ -1,6 +1,7 @@
 import { useApp } from "../context/AppContext";
 import { ConfigEditor, type ConfigValues } from "../components/ConfigEditor";
 import { TorrentSettings } from "../components/TorrentSettings";
+import { FFmpegSettings } from "../components/FFmpegSettings";
 
 export function ConfigPage() {
   const {
@@ -60,6 +61,8 @@ export function ConfigPage() {
         onDeleteWebDAV={deleteWebDAV}
       />
 
+      <FFmpegSettings isConnected={isConnected} />
+
       <TorrentSettings isConnected={isConnected} />
     </div>
   );
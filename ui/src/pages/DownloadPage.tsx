@@ -173,6 +173,11 @@ export function DownloadPage() {
                 job={job}
                 onCancel={() => cancelJob(job.id)}
                 onClear={() => cancelJob(job.id)}
+                onRedownload={async () => {
+                  if (await submitDownload(job.url, "overwrite")) {
+                    cancelJob(job.id);
+                  }
+                }}
                 t={t}
               />
             ))}
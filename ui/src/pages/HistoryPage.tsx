@@ -0,0 +1,302 @@
+import { useState, useEffect, useCallback } from "react";
+import clsx from "clsx";
+import { useApp } from "../context/AppContext";
+import {
+  FaMagnifyingGlass,
+  FaFolderOpen,
+  FaPlay,
+  FaDownload,
+  FaShieldHalved,
+} from "react-icons/fa6";
+import {
+  searchHistory,
+  openHistoryEntry,
+  verifyHistoryEntry,
+  historyThumbnailURL,
+  type HistoryEntry,
+  type HistoryFilters,
+} from "../utils/apis";
+
+const statuses = ["completed", "failed", "cancelled"] as const;
+
+const verificationStyles = {
+  ok: "bg-green-100 dark:bg-green-900/40 text-green-700 dark:text-green-400",
+  corrupted: "bg-red-100 dark:bg-red-900/40 text-red-600 dark:text-red-400",
+  missing: "bg-red-100 dark:bg-red-900/40 text-red-600 dark:text-red-400",
+  no_checksum: "bg-zinc-100 dark:bg-zinc-800 text-zinc-600 dark:text-zinc-400",
+} as const;
+
+function formatSize(bytes?: number): string {
+  if (!bytes || bytes <= 0) return "";
+  const units = ["B", "KB", "MB", "GB", "TB"];
+  const i = Math.floor(Math.log(bytes) / Math.log(1024));
+  return (bytes / Math.pow(1024, i)).toFixed(i > 0 ? 1 : 0) + " " + units[i];
+}
+
+function formatDuration(seconds?: number): string {
+  if (!seconds || seconds <= 0) return "";
+  const h = Math.floor(seconds / 3600);
+  const m = Math.floor((seconds % 3600) / 60);
+  const s = seconds % 60;
+  if (h > 0) {
+    return `${h}:${m.toString().padStart(2, "0")}:${s.toString().padStart(2, "0")}`;
+  }
+  return `${m}:${s.toString().padStart(2, "0")}`;
+}
+
+// First output file of an entry; entries recorded before files was kept
+// joined image outputs with ", " in filename
+function firstFile(entry: HistoryEntry): string {
+  return entry.files?.[0] ?? entry.filename?.split(", ")[0] ?? "";
+}
+
+export function HistoryPage() {
+  const { t, isConnected, showToast } = useApp();
+  const [filters, setFilters] = useState<HistoryFilters>({ limit: 200 });
+  const [entries, setEntries] = useState<HistoryEntry[]>([]);
+  const [sites, setSites] = useState<string[]>([]);
+  const [loading, setLoading] = useState(false);
+
+  const inputClass =
+    "px-3 py-2 border border-zinc-300 dark:border-zinc-700 rounded-lg text-sm bg-white dark:bg-zinc-900 text-zinc-700 dark:text-zinc-300 focus:outline-none focus:border-blue-500";
+
+  // Search again shortly after the filters stop changing
+  useEffect(() => {
+    if (!isConnected) return;
+    const timer = setTimeout(async () => {
+      setLoading(true);
+      try {
+        const res = await searchHistory(filters);
+        if (res.code === 200) {
+          setEntries(res.data.entries);
+          setSites(Object.keys(res.data.sites ?? {}).sort());
+        } else {
+          showToast("error", res.message);
+        }
+      } catch {
+        showToast("error", "Failed to load history");
+      } finally {
+        setLoading(false);
+      }
+    }, 300);
+    return () => clearTimeout(timer);
+  }, [filters, isConnected, showToast]);
+
+  const setFilter = (key: keyof HistoryFilters, value: string) => {
+    setFilters((prev) => ({ ...prev, [key]: value }));
+  };
+
+  const handleOpen = useCallback(
+    async (entry: HistoryEntry, action: "open" | "reveal") => {
+      try {
+        const res = await openHistoryEntry(entry.id, action);
+        if (res.code !== 200) {
+          showToast("error", res.message);
+        }
+      } catch {
+        showToast("error", "Failed to open file");
+      }
+    },
+    [showToast]
+  );
+
+  const handleVerify = useCallback(
+    async (entry: HistoryEntry) => {
+      try {
+        const res = await verifyHistoryEntry(entry.id);
+        if (res.code === 200) {
+          setEntries((prev) =>
+            prev.map((e) => (e.id === res.data.id ? res.data : e))
+          );
+        } else {
+          showToast("error", res.message);
+        }
+      } catch {
+        showToast("error", "Failed to verify file");
+      }
+    },
+    [showToast]
+  );
+
+  const verificationText = {
+    ok: t.history_intact,
+    corrupted: t.history_corrupted,
+    missing: t.history_missing,
+    no_checksum: t.history_no_checksum,
+  };
+
+  return (
+    <div className="max-w-3xl mx-auto flex flex-col gap-4">
+      <h1 className="text-xl font-semibold text-zinc-800 dark:text-zinc-100">
+        {t.history}
+      </h1>
+
+      {/* Filters */}
+      <div className="flex flex-col gap-2">
+        <div className="relative">
+          <FaMagnifyingGlass className="absolute left-3 top-1/2 -translate-y-1/2 text-zinc-400" />
+          <input
+            type="search"
+            className={clsx(inputClass, "w-full pl-9")}
+            placeholder={t.history_search}
+            value={filters.q ?? ""}
+            onChange={(e) => setFilter("q", e.target.value)}
+          />
+        </div>
+        <div className="flex flex-wrap items-center gap-2">
+          <select
+            className={inputClass}
+            value={filters.site ?? ""}
+            onChange={(e) => setFilter("site", e.target.value)}
+          >
+            <option value="">{t.history_all_sites}</option>
+            {sites.map((site) => (
+              <option key={site} value={site}>
+                {site}
+              </option>
+            ))}
+          </select>
+          <select
+            className={inputClass}
+            value={filters.status ?? ""}
+            onChange={(e) => setFilter("status", e.target.value)}
+          >
+            <option value="">{t.history_all_statuses}</option>
+            {statuses.map((status) => (
+              <option key={status} value={status}>
+                {t[status]}
+              </option>
+            ))}
+          </select>
+          <label className="flex items-center gap-2 text-sm text-zinc-600 dark:text-zinc-400">
+            {t.history_from}
+            <input
+              type="date"
+              className={inputClass}
+              value={filters.from ?? ""}
+              onChange={(e) => setFilter("from", e.target.value)}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-zinc-600 dark:text-zinc-400">
+            {t.history_to}
+            <input
+              type="date"
+              className={inputClass}
+              value={filters.to ?? ""}
+              onChange={(e) => setFilter("to", e.target.value)}
+            />
+          </label>
+        </div>
+      </div>
+
+      {/* Results */}
+      {entries.length === 0 ? (
+        <p className="text-center text-sm text-zinc-500 dark:text-zinc-400 py-8">
+          {loading ? t.loading : t.history_empty}
+        </p>
+      ) : (
+        <ul className="flex flex-col gap-2">
+          {entries.map((entry) => {
+            const file = firstFile(entry);
+            const details = [
+              entry.site,
+              formatSize(entry.size),
+              formatDuration(entry.duration),
+              new Date(entry.finished_at).toLocaleString(),
+            ].filter(Boolean);
+
+            return (
+              <li
+                key={entry.id}
+                className="p-3 border border-zinc-300 dark:border-zinc-700 rounded-lg bg-white dark:bg-zinc-900 flex items-center gap-3"
+              >
+                {entry.thumbnail && (
+                  <img
+                    className="w-24 aspect-video object-cover rounded bg-zinc-100 dark:bg-zinc-800 shrink-0"
+                    src={historyThumbnailURL(entry.id)}
+                    alt=""
+                    loading="lazy"
+                  />
+                )}
+                <div className="flex-1 min-w-0">
+                  <div className="flex items-center gap-2">
+                    <span
+                      className="font-medium text-sm text-zinc-800 dark:text-zinc-100 truncate"
+                      title={entry.title || entry.url}
+                    >
+                      {entry.title || entry.url}
+                    </span>
+                    {entry.status !== "completed" && (
+                      <span className="text-xs px-1.5 py-0.5 rounded bg-red-100 dark:bg-red-900/40 text-red-600 dark:text-red-400">
+                        {t[entry.status]}
+                      </span>
+                    )}
+                    {entry.verification && (
+                      <span
+                        className={clsx(
+                          "text-xs px-1.5 py-0.5 rounded whitespace-nowrap",
+                          verificationStyles[entry.verification.status]
+                        )}
+                        title={new Date(
+                          entry.verification.checked_at
+                        ).toLocaleString()}
+                      >
+                        {verificationText[entry.verification.status]}
+                      </span>
+                    )}
+                  </div>
+                  <div className="text-xs text-zinc-500 dark:text-zinc-400 truncate">
+                    {details.join(" · ")}
+                  </div>
+                  {file && (
+                    <div
+                      className="text-xs text-zinc-400 dark:text-zinc-500 font-mono truncate"
+                      title={file}
+                    >
+                      {file}
+                    </div>
+                  )}
+                </div>
+                {file && entry.status === "completed" && (
+                  <div className="flex gap-1 text-zinc-500 dark:text-zinc-400">
+                    <button
+                      type="button"
+                      className="p-2 rounded hover:bg-zinc-100 dark:hover:bg-zinc-800"
+                      title={t.history_open}
+                      onClick={() => handleOpen(entry, "open")}
+                    >
+                      <FaPlay />
+                    </button>
+                    <button
+                      type="button"
+                      className="p-2 rounded hover:bg-zinc-100 dark:hover:bg-zinc-800"
+                      title={t.history_reveal}
+                      onClick={() => handleOpen(entry, "reveal")}
+                    >
+                      <FaFolderOpen />
+                    </button>
+                    <button
+                      type="button"
+                      className="p-2 rounded hover:bg-zinc-100 dark:hover:bg-zinc-800"
+                      title={t.history_verify}
+                      onClick={() => handleVerify(entry)}
+                    >
+                      <FaShieldHalved />
+                    </button>
+                    <a
+                      className="p-2 rounded hover:bg-zinc-100 dark:hover:bg-zinc-800"
+                      title={t.history_save_file}
+                      href={`/api/download?path=${encodeURIComponent(file)}`}
+                    >
+                      <FaDownload />
+                    </a>
+                  </div>
+                )}
+              </li>
+            );
+          })}
+        </ul>
+      )}
+    </div>
+  );
+}
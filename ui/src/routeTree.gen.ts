@@ -13,6 +13,7 @@ import { Route as WebdavRouteImport } from './routes/webdav'
 import { Route as TorrentRouteImport } from './routes/torrent'
 import { Route as PodcastRouteImport } from './routes/podcast'
 import { Route as Kuaidi100RouteImport } from './routes/kuaidi100'
+import { Route as HistoryRouteImport } from './routes/history'
 import { Route as ConfigRouteImport } from './routes/config'
 import { Route as BulkRouteImport } from './routes/bulk'
 import { Route as BilibiliRouteImport } from './routes/bilibili'
@@ -40,6 +41,11 @@ const Kuaidi100Route = Kuaidi100RouteImport.update({
   path: '/kuaidi100',
   getParentRoute: () => rootRouteImport,
 } as any)
+const HistoryRoute = HistoryRouteImport.update({
+  id: '/history',
+  path: '/history',
+  getParentRoute: () => rootRouteImport,
+} as any)
 const ConfigRoute = ConfigRouteImport.update({
   id: '/config',
   path: '/config',
@@ -76,6 +82,7 @@ export interface FileRoutesByFullPath {
   '/bilibili': typeof BilibiliRoute
   '/bulk': typeof BulkRoute
   '/config': typeof ConfigRoute
+  '/history': typeof HistoryRoute
   '/kuaidi100': typeof Kuaidi100Route
   '/podcast': typeof PodcastRoute
   '/torrent': typeof TorrentRoute
@@ -88,6 +95,7 @@ export interface FileRoutesByTo {
   '/bilibili': typeof BilibiliRoute
   '/bulk': typeof BulkRoute
   '/config': typeof ConfigRoute
+  '/history': typeof HistoryRoute
   '/kuaidi100': typeof Kuaidi100Route
   '/podcast': typeof PodcastRoute
   '/torrent': typeof TorrentRoute
@@ -101,6 +109,7 @@ export interface FileRoutesById {
   '/bilibili': typeof BilibiliRoute
   '/bulk': typeof BulkRoute
   '/config': typeof ConfigRoute
+  '/history': typeof HistoryRoute
   '/kuaidi100': typeof Kuaidi100Route
   '/podcast': typeof PodcastRoute
   '/torrent': typeof TorrentRoute
@@ -115,6 +124,7 @@ export interface FileRouteTypes {
     | '/bilibili'
     | '/bulk'
     | '/config'
+    | '/history'
     | '/kuaidi100'
     | '/podcast'
     | '/torrent'
@@ -127,6 +137,7 @@ export interface FileRouteTypes {
     | '/bilibili'
     | '/bulk'
     | '/config'
+    | '/history'
     | '/kuaidi100'
     | '/podcast'
     | '/torrent'
@@ -139,6 +150,7 @@ export interface FileRouteTypes {
     | '/bilibili'
     | '/bulk'
     | '/config'
+    | '/history'
     | '/kuaidi100'
     | '/podcast'
     | '/torrent'
@@ -152,6 +164,7 @@ export interface RootRouteChildren {
   BilibiliRoute: typeof BilibiliRoute
   BulkRoute: typeof BulkRoute
   ConfigRoute: typeof ConfigRoute
+  HistoryRoute: typeof HistoryRoute
   Kuaidi100Route: typeof Kuaidi100Route
   PodcastRoute: typeof PodcastRoute
   TorrentRoute: typeof TorrentRoute
@@ -190,6 +203,13 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof Kuaidi100RouteImport
       parentRoute: typeof rootRouteImport
     }
+    '/history': {
+      id: '/history'
+      path: '/history'
+      fullPath: '/history'
+      preLoaderRoute: typeof HistoryRouteImport
+      parentRoute: typeof rootRouteImport
+    }
     '/config': {
       id: '/config'
       path: '/config'
@@ -240,6 +260,7 @@ const rootRouteChildren: RootRouteChildren = {
   BilibiliRoute: BilibiliRoute,
   BulkRoute: BulkRoute,
   ConfigRoute: ConfigRoute,
+  HistoryRoute: HistoryRoute,
   Kuaidi100Route: Kuaidi100Route,
   PodcastRoute: PodcastRoute,
   TorrentRoute: TorrentRoute,
@@ -3,10 +3,13 @@ import type { UITranslations, ServerTranslations } from "./translations";
 export type JobStatus =
   | "queued"
   | "downloading"
+  | "waiting_network"
   | "completed"
   | "failed"
   | "cancelled";
 
+export type JobStage = "video" | "audio" | "merging" | "post_process";
+
 export interface Job {
   id: string;
   url: string;
@@ -14,8 +17,23 @@ export interface Job {
   progress: number;
   downloaded: number;
   total: number;
+  speed?: number; // smoothed bytes/s while downloading
+  eta_seconds?: number; // -1 when the total size is unknown
+  stage?: JobStage; // step while downloading
+  stage_progress?: number; // percent done of the stage, e.g. merging
   filename?: string;
   error?: string;
+  error_code?: string;
+  on_duplicate?: DuplicatePolicy;
+  source?: string;
+  start_at?: string | null;
+}
+
+export interface DownloadsSummary {
+  active: number;
+  queued: number;
+  speed: number;
+  progress: number;
 }
 
 export interface ApiResponse<T> {
@@ -58,6 +76,7 @@ export interface TorrentConfig {
   password: string;
   use_https: boolean;
   default_save_path: string;
+  seed: boolean;
 }
 
 export interface TorrentAddResult {
@@ -121,14 +140,17 @@ export async function setConfigValue(
   return res.json();
 }
 
+export type DuplicatePolicy = "prompt" | "skip_existing" | "overwrite";
+
 export async function postDownload(
   url: string,
-  filename?: string
+  filename?: string,
+  onDuplicate?: DuplicatePolicy
 ): Promise<ApiResponse<{ id: string; status: string }>> {
   const res = await fetch("/api/download", {
     method: "POST",
     headers: { "Content-Type": "application/json" },
-    body: JSON.stringify({ url, filename }),
+    body: JSON.stringify({ url, filename, on_duplicate: onDuplicate }),
   });
   return res.json();
 }
@@ -144,15 +166,29 @@ export interface BulkDownloadResult {
   jobs: BulkDownloadJob[];
   queued: number;
   failed: number;
+  invalid?: string[];
 }
 
 export async function postBulkDownload(
-  urls: string[]
+  urls: string[],
+  quality?: string
 ): Promise<ApiResponse<BulkDownloadResult>> {
   const res = await fetch("/api/bulk-download", {
     method: "POST",
     headers: { "Content-Type": "application/json" },
-    body: JSON.stringify({ urls }),
+    body: JSON.stringify({ urls, quality }),
+  });
+  return res.json();
+}
+
+export async function importDownloadList(
+  content: string,
+  quality?: string
+): Promise<ApiResponse<BulkDownloadResult>> {
+  const res = await fetch("/api/import", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ content, quality }),
   });
   return res.json();
 }
@@ -194,6 +230,111 @@ export async function clearHistory(): Promise<
   return res.json();
 }
 
+// Download history APIs
+
+export interface HistoryEntry {
+  id: string;
+  url: string;
+  title?: string;
+  site?: string;
+  filename?: string;
+  files?: string[]; // every output file; older entries only have filename
+  status: "completed" | "failed" | "cancelled";
+  error?: string;
+  size?: number;
+  duration?: number; // seconds
+  tags?: string[];
+  note?: string;
+  created_at: string;
+  finished_at: string;
+  sha256?: Record<string, string>;
+  verification?: HistoryVerification;
+  thumbnail?: boolean; // served by historyThumbnailURL
+}
+
+export interface HistoryVerification {
+  status: "ok" | "corrupted" | "missing" | "no_checksum";
+  missing?: string[];
+  corrupted?: string[];
+  checked_at: string;
+}
+
+export interface HistoryFilters {
+  q?: string;
+  site?: string;
+  status?: string;
+  from?: string; // YYYY-MM-DD
+  to?: string; // YYYY-MM-DD, inclusive
+  limit?: number;
+}
+
+export interface HistoryData {
+  entries: HistoryEntry[];
+  tags: Record<string, number>;
+  sites: Record<string, number>;
+}
+
+export async function searchHistory(
+  filters: HistoryFilters
+): Promise<ApiResponse<HistoryData>> {
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(filters)) {
+    if (value) params.set(key, String(value));
+  }
+  const res = await fetch(`/api/history?${params}`);
+  return res.json();
+}
+
+export async function openHistoryEntry(
+  id: string,
+  action: "open" | "reveal"
+): Promise<ApiResponse<{ id: string; path: string; action: string }>> {
+  const res = await fetch(`/api/history/${id}/open`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ action }),
+  });
+  return res.json();
+}
+
+export function historyThumbnailURL(id: string): string {
+  return `/api/history/${id}/thumbnail`;
+}
+
+export async function verifyHistoryEntry(
+  id: string
+): Promise<ApiResponse<HistoryEntry>> {
+  const res = await fetch(`/api/history/${id}/verify`, { method: "POST" });
+  return res.json();
+}
+
+// FFmpeg APIs
+
+export interface FFmpegInstall {
+  binary: string;
+  downloaded: number;
+  total: number;
+}
+
+export interface FFmpegInfo {
+  available: boolean;
+  path?: string;
+  version?: string;
+  managed: boolean;
+  installable: boolean;
+  install?: FFmpegInstall;
+}
+
+export async function fetchFFmpeg(): Promise<ApiResponse<FFmpegInfo>> {
+  const res = await fetch("/api/ffmpeg");
+  return res.json();
+}
+
+export async function installFFmpeg(): Promise<ApiResponse<null>> {
+  const res = await fetch("/api/ffmpeg/install", { method: "POST" });
+  return res.json();
+}
+
 // Torrent APIs
 
 export async function fetchTorrentConfig(): Promise<
@@ -25,6 +25,16 @@ export interface UITranslations {
   completed: string;
   failed: string;
   cancelled: string;
+  waiting_network: string;
+  stage_video: string;
+  stage_audio: string;
+  stage_merging: string;
+  stage_post_process: string;
+  no_disk_space: string;
+  already_downloaded: string;
+  download_again: string;
+  downloaded_from: string;
+  starts_at: string;
   settings: string;
   language: string;
   format: string;
@@ -68,6 +78,22 @@ export interface UITranslations {
   // Toast
   download_queued: string;
   downloads_queued: string;
+  // History
+  history: string;
+  history_search: string;
+  history_all_sites: string;
+  history_all_statuses: string;
+  history_from: string;
+  history_to: string;
+  history_empty: string;
+  history_open: string;
+  history_reveal: string;
+  history_save_file: string;
+  history_verify: string;
+  history_intact: string;
+  history_corrupted: string;
+  history_missing: string;
+  history_no_checksum: string;
   // Podcast
   podcast: string;
   podcast_search: string;
@@ -144,7 +170,7 @@ export const defaultTranslations: UITranslations = {
   coming_soon: "Coming Soon",
   bulk_paste_urls: "Paste URLs here (one per line)...",
   bulk_select_file: "Select File",
-  bulk_drag_drop: "or drag and drop a .txt file here",
+  bulk_drag_drop: "or drag and drop a .txt or .json file here",
   bulk_url_count: "URLs",
   bulk_submit_all: "Download All",
   bulk_submitting: "Submitting...",
@@ -160,6 +186,16 @@ export const defaultTranslations: UITranslations = {
   completed: "completed",
   failed: "failed",
   cancelled: "cancelled",
+  waiting_network: "waiting for network",
+  stage_video: "Video",
+  stage_audio: "Audio",
+  stage_merging: "Merging",
+  stage_post_process: "Processing",
+  no_disk_space: "Not enough disk space",
+  already_downloaded: "Already downloaded",
+  download_again: "Download again",
+  downloaded_from: "Downloaded from",
+  starts_at: "Starts at",
   settings: "Settings",
   language: "Language",
   format: "Format",
@@ -203,6 +239,22 @@ export const defaultTranslations: UITranslations = {
   // Toast
   download_queued: "Download started. Check progress on Download page.",
   downloads_queued: "downloads started. Check progress on Download page.",
+  // History
+  history: "History",
+  history_search: "Search titles, URLs, notes...",
+  history_all_sites: "All sites",
+  history_all_statuses: "All statuses",
+  history_from: "From",
+  history_to: "To",
+  history_empty: "No downloads found",
+  history_open: "Open",
+  history_reveal: "Show in folder",
+  history_save_file: "Save file",
+  history_verify: "Verify file",
+  history_intact: "Intact",
+  history_corrupted: "Changed since download",
+  history_missing: "File missing",
+  history_no_checksum: "No checksum",
   // Podcast
   podcast: "Podcast",
   podcast_search: "Search",